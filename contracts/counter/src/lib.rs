@@ -7,6 +7,9 @@ pub mod state;
 
 pub use crate::error::ContractError;
 // ANCHOR: fn_re_export
+pub use crate::msg::{
+    AsyncExecuteMsgFns as AsyncCounterExecuteMsgFns, AsyncQueryMsgFns as AsyncCounterQueryMsgFns,
+};
 pub use crate::msg::{ExecuteMsgFns as CounterExecuteMsgFns, QueryMsgFns as CounterQueryMsgFns};
 // ANCHOR_END: fn_re_export
 
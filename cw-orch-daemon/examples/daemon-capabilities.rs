@@ -3,7 +3,7 @@ use std::str::FromStr;
 use cosmrs::{tx::Msg, AccountId, Coin, Denom};
 use cosmwasm_std::coins;
 // ANCHOR: full_counter_example
-use cw_orch_daemon::DaemonBuilder;
+use cw_orch_daemon::{proto_registry, DaemonBuilder};
 use cw_orch_networks::networks;
 
 // From https://github.com/CosmosContracts/juno/blob/32568dba828ff7783aea8cb5bb4b8b5832888255/docker/test-user.env#L2
@@ -51,6 +51,15 @@ pub fn main() -> anyhow::Result<()> {
         }],
         None,
     ))?;
+
+    // Common messages can be built with their type url from the proto registry
+    let redelegate = proto_registry::staking::redelegate(
+        "<my-address>",
+        "<my-least-favorite-validator>",
+        "<my-favorite-validator>",
+        &cosmwasm_std::coin(100_000_000_000_000, "ujuno"),
+    );
+    rt.block_on(wallet.commit_tx_any(vec![redelegate], None))?;
     // ANCHOR_END: any_tx
 
     // ANCHOR: simulate_tx
@@ -0,0 +1,197 @@
+//! Named addresses and denoms of a chain, stored next to the deployments.
+//!
+//! Scripts often need addresses that are not deployed by them: multisigs, oracles, token denoms, ...
+//! The address book stores them under a name, per chain, in the `address_book` section of the state file.
+//! Entries are shared by all the deployments of the chain and by every daemon using the same state file, which includes the daemons of an interchain environment.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{networks, Daemon};
+//!
+//! let daemon = Daemon::builder().chain(networks::JUNO_1).build().unwrap();
+//! let mut book = daemon.address_book();
+//! book.register_address("treasury", "juno1...").unwrap();
+//! book.register_denom("usdc", "ibc/EAC38D55372F38F1AFD68DF7FE9EF762DCF69F26520643CF3F9D292A738D8034").unwrap();
+//!
+//! let treasury = book.address("treasury").unwrap();
+//! let usdc = book.denom("usdc").unwrap();
+//! ```
+
+use cosmwasm_std::Addr;
+use serde::{Deserialize, Serialize};
+
+use crate::{sender::QuerySender, DaemonAsyncBase, DaemonBase, DaemonError, DaemonState};
+
+/// State key the address book is stored under
+pub const ADDRESS_BOOK_KEY: &str = "address_book";
+
+/// Entry of the address book
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "value")]
+pub enum AddressBookEntry {
+    /// Account or contract address
+    Address(Addr),
+    /// Native or ibc denom
+    Denom(String),
+}
+
+/// Named addresses and denoms of the chain of a [`DaemonState`], see the [module documentation](self)
+#[derive(Debug, Clone)]
+pub struct AddressBook {
+    state: DaemonState,
+}
+
+impl AddressBook {
+    /// Address book of the chain of `state`
+    pub fn new(state: DaemonState) -> Self {
+        Self { state }
+    }
+
+    /// Registers an address under `name`, replacing the previous entry.
+    /// Errors if the address doesn't have the prefix of the chain
+    pub fn register_address(
+        &mut self,
+        name: &str,
+        address: impl Into<String>,
+    ) -> Result<(), DaemonError> {
+        let address = address.into();
+        let prefix = &self.state.chain_data.network_info.pub_address_prefix;
+        if !address.starts_with(&format!("{prefix}1")) {
+            return Err(DaemonError::WrongAddressPrefix {
+                address,
+                expected: prefix.clone(),
+            });
+        }
+        self.register(name, AddressBookEntry::Address(Addr::unchecked(address)))
+    }
+
+    /// Registers a denom under `name`, replacing the previous entry
+    pub fn register_denom(
+        &mut self,
+        name: &str,
+        denom: impl Into<String>,
+    ) -> Result<(), DaemonError> {
+        self.register(name, AddressBookEntry::Denom(denom.into()))
+    }
+
+    /// Registers `entry` under `name`, replacing the previous entry
+    pub fn register(&mut self, name: &str, entry: AddressBookEntry) -> Result<(), DaemonError> {
+        self.state.set(ADDRESS_BOOK_KEY, name, &entry)
+    }
+
+    /// Entry registered under `name`, if any
+    pub fn get(&self, name: &str) -> Result<Option<AddressBookEntry>, DaemonError> {
+        let value = self.state.get(ADDRESS_BOOK_KEY)?[name].clone();
+        if value.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_value(value)?))
+    }
+
+    /// Address registered under `name`. Errors if there is no entry or if it's not an address
+    pub fn address(&self, name: &str) -> Result<Addr, DaemonError> {
+        match self.get(name)? {
+            Some(AddressBookEntry::Address(address)) => Ok(address),
+            entry => Err(self.unexpected_entry(name, "address", entry)),
+        }
+    }
+
+    /// Denom registered under `name`. Errors if there is no entry or if it's not a denom
+    pub fn denom(&self, name: &str) -> Result<String, DaemonError> {
+        match self.get(name)? {
+            Some(AddressBookEntry::Denom(denom)) => Ok(denom),
+            entry => Err(self.unexpected_entry(name, "denom", entry)),
+        }
+    }
+
+    /// Removes the entry registered under `name`
+    pub fn remove(&mut self, name: &str) -> Result<(), DaemonError> {
+        self.state.remove(ADDRESS_BOOK_KEY, name)
+    }
+
+    /// All the entries of the address book, by name
+    pub fn entries(&self) -> Result<Vec<(String, AddressBookEntry)>, DaemonError> {
+        self.state
+            .get(ADDRESS_BOOK_KEY)?
+            .as_object()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|(_, value)| !value.is_null())
+                    .map(|(name, value)| -> Result<_, DaemonError> {
+                        Ok((name.clone(), serde_json::from_value(value.clone())?))
+                    })
+                    .collect()
+            })
+            .unwrap_or(Ok(vec![]))
+    }
+
+    fn unexpected_entry(
+        &self,
+        name: &str,
+        expected: &str,
+        entry: Option<AddressBookEntry>,
+    ) -> DaemonError {
+        let found = match entry {
+            None => "no entry".to_string(),
+            Some(entry) => format!("{entry:?}"),
+        };
+        DaemonError::AddressBookEntry {
+            name: name.to_string(),
+            chain_id: self.state.chain_data.chain_id.clone(),
+            expected: expected.to_string(),
+            found,
+        }
+    }
+}
+
+impl<S: QuerySender> DaemonAsyncBase<S> {
+    /// Named addresses and denoms of the chain, see [`AddressBook`]
+    pub fn address_book(&self) -> AddressBook {
+        AddressBook::new(self.state.clone())
+    }
+}
+
+impl<S: QuerySender> DaemonBase<S> {
+    /// Named addresses and denoms of the chain, see [`AddressBook`]
+    pub fn address_book(&self) -> AddressBook {
+        self.daemon.address_book()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::networks;
+
+    #[test]
+    fn address_book_is_shared_by_deployments() -> anyhow::Result<()> {
+        let path = DaemonState::temporary_state_file_path();
+        let mut state = DaemonState::new(
+            path,
+            networks::JUNO_1.into(),
+            "default".to_string(),
+            false,
+            true,
+        )?;
+        state.remove_file_on_drop();
+
+        let mut book = AddressBook::new(state.clone());
+        book.register_address("multisig", "juno1multisig")?;
+        book.register_denom("usdc", "ibc/usdc")?;
+        assert!(book.register_address("oracle", "osmo1oracle").is_err());
+
+        state.deployment_id = "other".to_string();
+        let book = AddressBook::new(state.clone());
+        assert_eq!(book.address("multisig")?, Addr::unchecked("juno1multisig"));
+        assert_eq!(book.denom("usdc")?, "ibc/usdc");
+        assert!(book.denom("multisig").is_err());
+        assert!(book.address("oracle").is_err());
+        assert_eq!(book.entries()?.len(), 2);
+        // Not a deployment
+        assert!(!state
+            .deployment_ids()?
+            .contains(&ADDRESS_BOOK_KEY.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,508 @@
+//! Distribution of tokens to a list of recipients ("airdrop").
+//!
+//! An [`Airdrop`] reads the recipients and their amounts from a CSV or JSON file, validates the addresses against the prefix of the chain
+//! and sends the tokens in batches of `MsgMultiSend` (or `MsgSend`) messages that fit in the size and gas limits of a transaction.
+//! The progress of the airdrop is saved in the daemon state after each transaction, so an interrupted airdrop resumes where it stopped.
+//!
+//! ## File formats
+//! CSV, with an optional header. Recipients appearing on multiple rows receive the sum of their amounts.
+//! ```csv
+//! address,amount,denom
+//! juno1...,1000,ujuno
+//! juno1...,25,ibc/...
+//! ```
+//! JSON
+//! ```json
+//! [{ "address": "juno1...", "amount": [{ "amount": "1000", "denom": "ujuno" }] }]
+//! ```
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{airdrop::Airdrop, networks, Daemon};
+//!
+//! let daemon = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//! let report = Airdrop::from_csv("launch.csv")
+//!     .unwrap()
+//!     .max_recipients_per_tx(100)
+//!     .run(&daemon, "launch")
+//!     .unwrap();
+//! println!("{report}");
+//! ```
+
+use std::{fmt::Display, fs, path::Path, str::FromStr};
+
+use cosmrs::{
+    bank::{MsgMultiSend, MsgSend, MultiSendIo},
+    tx::Msg,
+    AccountId, Any,
+};
+use cosmwasm_std::{Coin, Coins, Uint128};
+use cw_orch_core::environment::ChainState;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{core::parse_cw_coins, Daemon, DaemonError, Wallet};
+
+/// Key of the airdrops progress in the state file
+pub(crate) const AIRDROP_KEY: &str = "airdrops";
+const DEFAULT_MAX_RECIPIENTS_PER_TX: usize = 100;
+/// Keeps transactions well under the default mempool limit of 1MB
+const DEFAULT_MAX_TX_BYTES: usize = 200_000;
+
+/// Recipient of an airdrop
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AirdropRecipient {
+    pub address: String,
+    pub amount: Vec<Coin>,
+}
+
+/// How the tokens are sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AirdropMode {
+    /// A single `MsgMultiSend` per transaction
+    #[default]
+    MultiSend,
+    /// One `MsgSend` per recipient, for chains that disabled `MsgMultiSend`
+    Send,
+}
+
+/// Progress of an airdrop, saved in the state after each transaction
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AirdropProgress {
+    /// Total number of recipients of the airdrop
+    pub recipients: usize,
+    /// Hash of the recipients and their amounts, an airdrop can only be resumed with the same recipients
+    pub recipients_hash: String,
+    /// Number of recipients that received their tokens, in the order of the recipients list
+    pub distributed: usize,
+    /// Hashes of the airdrop transactions
+    pub txs: Vec<String>,
+}
+
+/// Token distribution, see the [module documentation](self)
+#[derive(Debug, Clone)]
+pub struct Airdrop {
+    recipients: Vec<AirdropRecipient>,
+    mode: AirdropMode,
+    max_recipients_per_tx: usize,
+    max_tx_bytes: usize,
+    max_gas_per_tx: Option<u64>,
+    memo: Option<String>,
+}
+
+impl Airdrop {
+    /// Airdrop to these recipients. Recipients appearing multiple times receive the sum of their amounts
+    pub fn new(recipients: Vec<AirdropRecipient>) -> Self {
+        let mut merged: Vec<AirdropRecipient> = vec![];
+        for recipient in recipients {
+            match merged.iter_mut().find(|r| r.address == recipient.address) {
+                Some(existing) => existing.amount = add_coins(&existing.amount, &recipient.amount),
+                None => merged.push(AirdropRecipient {
+                    amount: add_coins(&[], &recipient.amount),
+                    address: recipient.address,
+                }),
+            }
+        }
+        Self {
+            recipients: merged,
+            mode: AirdropMode::default(),
+            max_recipients_per_tx: DEFAULT_MAX_RECIPIENTS_PER_TX,
+            max_tx_bytes: DEFAULT_MAX_TX_BYTES,
+            max_gas_per_tx: None,
+            memo: None,
+        }
+    }
+
+    /// Reads the recipients from a CSV file with `address,amount,denom` rows
+    pub fn from_csv(path: impl AsRef<Path>) -> Result<Self, DaemonError> {
+        Self::parse_csv(&fs::read_to_string(path)?)
+    }
+
+    /// Parses recipients from CSV content with `address,amount,denom` rows
+    pub fn parse_csv(content: &str) -> Result<Self, DaemonError> {
+        let mut recipients = vec![];
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (line_number == 0 && line.starts_with("address")) {
+                continue;
+            }
+            let invalid_row = || {
+                DaemonError::InvalidAirdrop(format!(
+                    "line {}: expected `address,amount,denom`, got `{line}`",
+                    line_number + 1
+                ))
+            };
+            let [address, amount, denom] = line.split(',').map(str::trim).collect::<Vec<_>>()[..]
+            else {
+                return Err(invalid_row());
+            };
+            let amount = Uint128::from_str(amount).map_err(|_| invalid_row())?;
+            recipients.push(AirdropRecipient {
+                address: address.to_string(),
+                amount: vec![Coin::new(amount.u128(), denom)],
+            });
+        }
+        Ok(Self::new(recipients))
+    }
+
+    /// Reads the recipients from a JSON file containing a list of [`AirdropRecipient`]
+    pub fn from_json(path: impl AsRef<Path>) -> Result<Self, DaemonError> {
+        let recipients = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Ok(Self::new(recipients))
+    }
+
+    /// Sets how the tokens are sent, defaults to [`AirdropMode::MultiSend`]
+    pub fn mode(&mut self, mode: AirdropMode) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Maximum number of recipients per transaction, defaults to 100
+    pub fn max_recipients_per_tx(&mut self, max_recipients: usize) -> &mut Self {
+        self.max_recipients_per_tx = max_recipients.max(1);
+        self
+    }
+
+    /// Maximum size of the messages of a transaction, in bytes. Defaults to 200kB
+    pub fn max_tx_bytes(&mut self, max_bytes: usize) -> &mut Self {
+        self.max_tx_bytes = max_bytes;
+        self
+    }
+
+    /// Maximum gas of a transaction. When set, every batch is simulated and split in two until it fits
+    pub fn max_gas_per_tx(&mut self, max_gas: u64) -> &mut Self {
+        self.max_gas_per_tx = Some(max_gas);
+        self
+    }
+
+    /// Memo of the airdrop transactions
+    pub fn memo(&mut self, memo: impl Into<String>) -> &mut Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Recipients of the airdrop
+    pub fn recipients(&self) -> &[AirdropRecipient] {
+        &self.recipients
+    }
+
+    /// Hex encoded sha256 of the recipients and their amounts, in order
+    pub fn recipients_hash(&self) -> Result<String, DaemonError> {
+        let recipients = serde_json::to_vec(&self.recipients)?;
+        Ok(hex::encode(Sha256::digest(recipients)))
+    }
+
+    /// Sum of all the amounts of the airdrop
+    pub fn total(&self) -> Vec<Coin> {
+        self.recipients.iter().fold(vec![], |total, recipient| {
+            add_coins(&total, &recipient.amount)
+        })
+    }
+
+    /// Checks that all the recipients have a valid address with the `prefix` of the chain and a non-zero amount
+    pub fn validate(&self, prefix: &str) -> Result<(), DaemonError> {
+        let invalid: Vec<String> = self
+            .recipients
+            .iter()
+            .filter_map(|recipient| {
+                let reason = match AccountId::from_str(&recipient.address) {
+                    Err(e) => e.to_string(),
+                    Ok(account) if account.prefix() != prefix => {
+                        format!("expected the {prefix} prefix")
+                    }
+                    Ok(_) if recipient.amount.is_empty() => "nothing to send".to_string(),
+                    Ok(_) => return None,
+                };
+                Some(format!("{}: {reason}", recipient.address))
+            })
+            .collect();
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(DaemonError::InvalidAirdrop(invalid.join(", ")))
+        }
+    }
+
+    /// Distributes the tokens from the sender of the daemon.
+    /// The progress is saved in the state under `name`, running an airdrop with the same name again resumes it.
+    pub fn run(&self, daemon: &Daemon, name: &str) -> Result<AirdropReport, DaemonError> {
+        daemon.rt_handle.block_on(self._run(daemon, name))
+    }
+
+    async fn _run(&self, daemon: &Daemon, name: &str) -> Result<AirdropReport, DaemonError> {
+        let wallet = daemon.wallet();
+        self.validate(&wallet.chain_info.network_info.pub_address_prefix)?;
+
+        let mut state = daemon.state();
+        let mut progress: AirdropProgress = match state.get(AIRDROP_KEY)?[name].clone() {
+            serde_json::Value::Null => AirdropProgress {
+                recipients: self.recipients.len(),
+                recipients_hash: self.recipients_hash()?,
+                ..Default::default()
+            },
+            value => serde_json::from_value(value)?,
+        };
+        if progress.recipients != self.recipients.len() {
+            return Err(DaemonError::InvalidAirdrop(format!(
+                "airdrop {name} was started with {} recipients, got {}",
+                progress.recipients,
+                self.recipients.len()
+            )));
+        }
+        if progress.recipients_hash != self.recipients_hash()? {
+            return Err(DaemonError::InvalidAirdrop(format!(
+                "airdrop {name} was started with different recipients or amounts"
+            )));
+        }
+        if progress.distributed > 0 {
+            log::info!(
+                "Resuming airdrop {name} after {} of {} recipients",
+                progress.distributed,
+                progress.recipients
+            );
+        }
+
+        let mut error = None;
+        let remaining = &self.recipients[progress.distributed..];
+        let batches = self.batches(&wallet, remaining)?;
+        // Saves the progress after every transaction, before anything else can fail
+        let mut record = |recipients: usize, txhash: String| -> Result<(), DaemonError> {
+            progress.distributed += recipients;
+            progress.txs.push(txhash);
+            state.set(AIRDROP_KEY, name, &progress)?;
+            log::info!(
+                "Airdrop {name}: {}/{} recipients",
+                progress.distributed,
+                progress.recipients
+            );
+            Ok(())
+        };
+        for batch in batches {
+            if let Err(e) = self.send(&wallet, batch, &mut record).await {
+                error = Some(e.to_string());
+                break;
+            }
+        }
+
+        Ok(AirdropReport {
+            name: name.to_string(),
+            expected: self.total(),
+            distributed: self.recipients[..progress.distributed]
+                .iter()
+                .fold(vec![], |total, recipient| {
+                    add_coins(&total, &recipient.amount)
+                }),
+            pending: self.recipients[progress.distributed..].to_vec(),
+            progress,
+            error,
+        })
+    }
+
+    /// Splits the recipients into batches respecting the recipients and size limits
+    fn batches<'a>(
+        &self,
+        wallet: &Wallet,
+        recipients: &'a [AirdropRecipient],
+    ) -> Result<Vec<&'a [AirdropRecipient]>, DaemonError> {
+        let mut batches = vec![];
+        let mut start = 0;
+        while start < recipients.len() {
+            let mut end = (start + self.max_recipients_per_tx).min(recipients.len());
+            while end - start > 1
+                && encoded_len(&self.msgs(wallet, &recipients[start..end])?) > self.max_tx_bytes
+            {
+                end = start + (end - start) / 2;
+            }
+            batches.push(&recipients[start..end]);
+            start = end;
+        }
+        Ok(batches)
+    }
+
+    /// Sends a batch, splitting it while it exceeds the gas limit.
+    /// `record` is called with the number of recipients and the hash of every transaction as soon as it is committed
+    async fn send(
+        &self,
+        wallet: &Wallet,
+        batch: &[AirdropRecipient],
+        record: &mut impl FnMut(usize, String) -> Result<(), DaemonError>,
+    ) -> Result<(), DaemonError> {
+        let mut pending = vec![batch];
+        while let Some(batch) = pending.pop() {
+            let msgs = self.msgs(wallet, batch)?;
+            if let Some(max_gas) = self.max_gas_per_tx {
+                let (gas, _) = wallet.simulate(msgs.clone(), self.memo.as_deref()).await?;
+                if gas > max_gas && batch.len() > 1 {
+                    let (first, second) = batch.split_at(batch.len() / 2);
+                    pending.push(second);
+                    pending.push(first);
+                    continue;
+                }
+            }
+            let tx = wallet.commit_tx_any(msgs, self.memo.as_deref()).await?;
+            record(batch.len(), tx.txhash)?;
+        }
+        Ok(())
+    }
+
+    fn msgs(&self, wallet: &Wallet, batch: &[AirdropRecipient]) -> Result<Vec<Any>, DaemonError> {
+        let sender = wallet.msg_sender()?;
+        match self.mode {
+            AirdropMode::MultiSend => {
+                let total = batch.iter().fold(vec![], |total, recipient| {
+                    add_coins(&total, &recipient.amount)
+                });
+                let outputs = batch
+                    .iter()
+                    .map(|recipient| {
+                        Ok(MultiSendIo {
+                            address: AccountId::from_str(&recipient.address)?,
+                            coins: parse_cw_coins(&recipient.amount)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, DaemonError>>()?;
+                let msg = MsgMultiSend {
+                    inputs: vec![MultiSendIo {
+                        address: sender,
+                        coins: parse_cw_coins(&total)?,
+                    }],
+                    outputs,
+                };
+                Ok(vec![msg.into_any()?])
+            }
+            AirdropMode::Send => batch
+                .iter()
+                .map(|recipient| {
+                    let msg = MsgSend {
+                        from_address: sender.clone(),
+                        to_address: AccountId::from_str(&recipient.address)?,
+                        amount: parse_cw_coins(&recipient.amount)?,
+                    };
+                    Ok(msg.into_any()?)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Reconciliation of an airdrop run
+#[derive(Debug, Clone, PartialEq)]
+pub struct AirdropReport {
+    /// Name of the airdrop in the state
+    pub name: String,
+    /// Progress saved in the state
+    pub progress: AirdropProgress,
+    /// Total amount of the airdrop
+    pub expected: Vec<Coin>,
+    /// Amount distributed, across all the runs of the airdrop
+    pub distributed: Vec<Coin>,
+    /// Recipients that didn't receive their tokens yet
+    pub pending: Vec<AirdropRecipient>,
+    /// Error that interrupted the airdrop, run it again to resume
+    pub error: Option<String>,
+}
+
+impl AirdropReport {
+    /// Returns true if all the recipients received their tokens
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl Display for AirdropReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coins = |coins: &[Coin]| {
+            coins
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        writeln!(f, "Airdrop {}", self.name)?;
+        writeln!(
+            f,
+            "  recipients: {}/{}",
+            self.progress.distributed, self.progress.recipients
+        )?;
+        writeln!(f, "  expected: {}", coins(&self.expected))?;
+        writeln!(f, "  distributed: {}", coins(&self.distributed))?;
+        writeln!(f, "  transactions: {}", self.progress.txs.len())?;
+        if let Some(error) = &self.error {
+            writeln!(f, "  interrupted: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+fn add_coins(total: &[Coin], amount: &[Coin]) -> Vec<Coin> {
+    let mut coins = Coins::try_from(total.to_vec()).unwrap_or_default();
+    for coin in amount {
+        // Adding zero amounts is a no-op
+        let _ = coins.add(coin.clone());
+    }
+    coins.into_vec()
+}
+
+fn encoded_len(msgs: &[Any]) -> usize {
+    msgs.iter().map(Message::encoded_len).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CSV: &str = "address,amount,denom
+juno1qqqsyqcyq5rqwzqfpg9scrgwpugpzysn4yjpk9,100,ujuno
+juno1qqqsyqcyq5rqwzqfpg9scrgwpugpzysn4yjpk9,5,uatom
+
+juno1qurswpc8qurswpc8qurswpc8qurswpc89pyp8a,50,ujuno
+";
+
+    #[test]
+    fn parse_csv() -> anyhow::Result<()> {
+        let airdrop = Airdrop::parse_csv(CSV)?;
+        assert_eq!(airdrop.recipients().len(), 2);
+        assert_eq!(
+            airdrop.recipients()[0].amount,
+            vec![Coin::new(5, "uatom"), Coin::new(100, "ujuno")]
+        );
+        assert_eq!(
+            airdrop.total(),
+            vec![Coin::new(5, "uatom"), Coin::new(150, "ujuno")]
+        );
+
+        assert!(matches!(
+            Airdrop::parse_csv("juno1...,ten,ujuno"),
+            Err(DaemonError::InvalidAirdrop(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn recipients_hash() -> anyhow::Result<()> {
+        let airdrop = Airdrop::parse_csv(CSV)?;
+        assert_eq!(
+            airdrop.recipients_hash()?,
+            Airdrop::parse_csv(CSV)?.recipients_hash()?
+        );
+
+        // Same number of recipients, different amount
+        let edited = Airdrop::parse_csv(&CSV.replace(",50,", ",51,"))?;
+        assert_eq!(edited.recipients().len(), airdrop.recipients().len());
+        assert_ne!(edited.recipients_hash()?, airdrop.recipients_hash()?);
+        Ok(())
+    }
+
+    #[test]
+    fn validate_prefix() -> anyhow::Result<()> {
+        let airdrop = Airdrop::parse_csv(CSV)?;
+        airdrop.validate("juno")?;
+
+        let Err(DaemonError::InvalidAirdrop(invalid)) = airdrop.validate("osmo") else {
+            panic!("expected the juno addresses to be invalid on osmosis")
+        };
+        assert!(invalid.contains("expected the osmo prefix"));
+        Ok(())
+    }
+}
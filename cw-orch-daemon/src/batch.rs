@@ -0,0 +1,567 @@
+//! Batching of several logical operations into a single transaction.
+//!
+//! A [`TxBatch`] queues operations (e.g. contract executions), each made of one or more messages and identified by a label.
+//! All the messages are broadcasted in a single transaction. When the transaction fails, the index of the failing message
+//! reported by the chain is mapped back to the operation that queued it, so the [`BatchReport`] tells which operation failed.
+//!
+//! With [`TxBatch::broadcast_splitting`], the failing operations are removed and the remaining operations are retried,
+//! until the transaction goes through.
+//!
+//! Batches that don't fit in a single transaction can be split with [`TxBatch::limits`]: operations are grouped, in order,
+//! into transactions that stay within the configured gas and byte limits, see [`BatchLimits`].
+//!
+//! The broadcast can be scheduled at a block height or a block time with [`TxBatch::broadcast_at_height`] and [`TxBatch::broadcast_at_time`],
+//! e.g. to coordinate a migration at a specific block.
+//!
+//! Note: with an authz granter, all the messages are wrapped in a single `MsgExec`, so failures can't be attributed to an operation.
+//!
+//! ## Usage
+//! ```no_run
+//! use cosmwasm_std::Addr;
+//! use cw_orch_daemon::{batch::TxBatch, networks, Daemon};
+//!
+//! let daemon = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//! let mut batch = TxBatch::new(&daemon);
+//! batch
+//!     .execute("increment", &Addr::unchecked("juno1counter"), &serde_json::json!({"increment": {}}), &[])
+//!     .unwrap()
+//!     .with_callback(|outcome| println!("increment: {:?}", outcome));
+//! batch
+//!     .execute("reset", &Addr::unchecked("juno1counter"), &serde_json::json!({"reset": {"count": 0}}), &[])
+//!     .unwrap();
+//!
+//! let report = batch.broadcast_splitting().unwrap();
+//! for (label, outcome) in report.outcomes() {
+//!     println!("{label}: {outcome:?}");
+//! }
+//! // Retry the failed operations later
+//! let retry = report.retry_batch();
+//! ```
+
+use std::{fmt::Debug, str::FromStr, sync::Arc};
+
+use cosmrs::{cosmwasm::MsgExecuteContract, tx::Msg, AccountId, Any};
+use cosmwasm_std::{Addr, Coin, Timestamp};
+use serde::Serialize;
+use tokio::runtime::Handle;
+
+use crate::{
+    core::parse_cw_coins, queriers::Node, CosmTxResponse, Daemon, DaemonError, TxBuilder, Wallet,
+};
+
+/// Default maximum size of a transaction accepted in the mempool of CometBFT nodes (`max_tx_bytes`)
+pub const DEFAULT_MAX_TX_BYTES: usize = 1024 * 1024;
+
+/// Bytes added to the encoded body to account for the auth info and signature of the transaction
+const TX_OVERHEAD_BYTES: usize = 256;
+
+/// Limits of the transactions broadcasted by a [`TxBatch`].
+/// When the operations don't fit in a single transaction, they are split, in order, into several transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchLimits {
+    /// Maximum gas of a transaction, usually the block gas limit of the chain.
+    /// The gas is simulated each time an operation is added to a transaction, `None` disables the simulations
+    pub max_gas: Option<u64>,
+    /// Maximum size of a transaction in bytes, usually the `max_tx_bytes` of the mempool of the nodes
+    pub max_tx_bytes: Option<usize>,
+}
+
+impl Default for BatchLimits {
+    fn default() -> Self {
+        Self {
+            max_gas: None,
+            max_tx_bytes: Some(DEFAULT_MAX_TX_BYTES),
+        }
+    }
+}
+
+impl BatchLimits {
+    /// No limit, all the operations are broadcasted in a single transaction
+    pub fn unlimited() -> Self {
+        Self {
+            max_gas: None,
+            max_tx_bytes: None,
+        }
+    }
+
+    /// Sets the maximum gas of a transaction
+    pub fn max_gas(mut self, max_gas: u64) -> Self {
+        self.max_gas = Some(max_gas);
+        self
+    }
+
+    /// Sets the maximum size of a transaction in bytes
+    pub fn max_tx_bytes(mut self, max_tx_bytes: usize) -> Self {
+        self.max_tx_bytes = Some(max_tx_bytes);
+        self
+    }
+}
+
+/// When a [`TxBatch`] is broadcasted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastSchedule {
+    /// Broadcast once block `height - 1` is committed, so the transactions are included in block `height` if the mempool isn't congested
+    Height(u64),
+    /// Broadcast once the time of the latest block reaches this timestamp
+    Time(Timestamp),
+}
+
+/// Callback called with the outcome of an operation once the batch is broadcasted
+pub type OutcomeCallback = Arc<dyn Fn(&OperationOutcome) + Send + Sync>;
+
+/// Outcome of a queued operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum OperationOutcome {
+    /// The operation was included in the transaction with this hash
+    Succeeded {
+        /// Hash of the transaction
+        txhash: String,
+    },
+    /// The operation failed
+    Failed {
+        /// Error returned by the chain
+        error: String,
+    },
+    /// The operation was not executed because another operation of the batch failed
+    Skipped,
+}
+
+/// Logical operation queued in a batch
+#[derive(Clone)]
+pub struct BatchOperation {
+    /// Label identifying the operation
+    pub label: String,
+    /// Messages of the operation
+    pub msgs: Vec<Any>,
+    callback: Option<OutcomeCallback>,
+}
+
+impl Debug for BatchOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchOperation")
+            .field("label", &self.label)
+            .field("msgs", &self.msgs)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+/// Operations to broadcast in a single transaction, see the [module documentation](self)
+#[derive(Clone)]
+pub struct TxBatch {
+    wallet: Wallet,
+    rt_handle: Option<Handle>,
+    memo: Option<String>,
+    limits: BatchLimits,
+    schedule: Option<BroadcastSchedule>,
+    operations: Vec<BatchOperation>,
+}
+
+impl TxBatch {
+    /// Empty batch, broadcasted with the sender of the daemon
+    pub fn new(daemon: &Daemon) -> Self {
+        Self {
+            wallet: daemon.wallet(),
+            rt_handle: Some(daemon.rt_handle.clone()),
+            memo: None,
+            limits: BatchLimits::default(),
+            schedule: None,
+            operations: vec![],
+        }
+    }
+
+    /// Empty batch, broadcasted with this sender.
+    /// Only the async broadcast functions are available
+    pub fn new_async(wallet: Wallet) -> Self {
+        Self {
+            wallet,
+            rt_handle: None,
+            memo: None,
+            limits: BatchLimits::default(),
+            schedule: None,
+            operations: vec![],
+        }
+    }
+
+    /// Sets the memo of the batch transactions
+    pub fn memo(&mut self, memo: impl Into<String>) -> &mut Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Sets the limits used to split the operations into several transactions, see [`BatchLimits`]
+    pub fn limits(&mut self, limits: BatchLimits) -> &mut Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Waits for the chain to reach `height` before broadcasting, see [`BroadcastSchedule::Height`]
+    pub fn broadcast_at_height(&mut self, height: u64) -> &mut Self {
+        self.schedule = Some(BroadcastSchedule::Height(height));
+        self
+    }
+
+    /// Waits for the block time to reach `time` before broadcasting, see [`BroadcastSchedule::Time`]
+    pub fn broadcast_at_time(&mut self, time: Timestamp) -> &mut Self {
+        self.schedule = Some(BroadcastSchedule::Time(time));
+        self
+    }
+
+    /// Queues an operation made of raw messages
+    pub fn push(&mut self, label: impl Into<String>, msgs: Vec<Any>) -> &mut Self {
+        self.operations.push(BatchOperation {
+            label: label.into(),
+            msgs,
+            callback: None,
+        });
+        self
+    }
+
+    /// Queues the execution of a contract
+    pub fn execute<E: Serialize>(
+        &mut self,
+        label: impl Into<String>,
+        contract_address: &Addr,
+        exec_msg: &E,
+        coins: &[Coin],
+    ) -> Result<&mut Self, DaemonError> {
+        let exec_msg = MsgExecuteContract {
+            sender: self.wallet.msg_sender()?,
+            contract: AccountId::from_str(contract_address.as_str())?,
+            msg: serde_json::to_vec(&exec_msg)?,
+            funds: parse_cw_coins(coins)?,
+        };
+        Ok(self.push(label, vec![exec_msg.into_any()?]))
+    }
+
+    /// Sets a callback on the last queued operation, called with its outcome once the batch is broadcasted
+    pub fn with_callback(
+        &mut self,
+        callback: impl Fn(&OperationOutcome) + Send + Sync + 'static,
+    ) -> &mut Self {
+        if let Some(operation) = self.operations.last_mut() {
+            operation.callback = Some(Arc::new(callback));
+        }
+        self
+    }
+
+    /// Queued operations
+    pub fn operations(&self) -> &[BatchOperation] {
+        &self.operations
+    }
+
+    /// Returns true if no operation is queued
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Broadcasts all the operations, in as many transactions as needed to stay within the [`BatchLimits`].
+    /// If an operation fails, the other ones of its transaction and the following transactions are [`OperationOutcome::Skipped`]
+    pub fn broadcast(&self) -> Result<BatchReport, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._broadcast(false))
+    }
+
+    /// Broadcasts all the operations, removing the failing operations and retrying the other ones until the transactions go through
+    pub fn broadcast_splitting(&self) -> Result<BatchReport, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._broadcast(true))
+    }
+
+    /// Broadcasts the operations, retrying without the failing operations if `split` is true.
+    /// Errors that can't be attributed to an operation (e.g. connection errors) fail all the pending operations of the transaction
+    pub async fn _broadcast(&self, split: bool) -> Result<BatchReport, DaemonError> {
+        self.wait_for_schedule().await?;
+
+        let mut outcomes = vec![OperationOutcome::Skipped; self.operations.len()];
+        let mut txs = vec![];
+
+        let mut chunks = self.plan(&mut outcomes).await?;
+        let oversized = outcomes
+            .iter()
+            .any(|outcome| matches!(outcome, OperationOutcome::Failed { .. }));
+        if oversized && !split {
+            chunks.clear();
+        }
+        if chunks.len() > 1 {
+            log::info!(
+                "Splitting the batch of {} operations into {} transactions",
+                self.operations.len(),
+                chunks.len()
+            );
+        }
+        for chunk in chunks {
+            let succeeded = self
+                .broadcast_chunk(chunk, split, &mut outcomes, &mut txs)
+                .await;
+            if !succeeded && !split {
+                break;
+            }
+        }
+
+        for (operation, outcome) in self.operations.iter().zip(&outcomes) {
+            if let Some(callback) = &operation.callback {
+                callback(outcome);
+            }
+        }
+
+        Ok(BatchReport {
+            batch: self.clone(),
+            outcomes,
+            txs,
+        })
+    }
+
+    /// Waits until the scheduled height or time, if any, using the average block speed of the chain
+    async fn wait_for_schedule(&self) -> Result<(), DaemonError> {
+        let node = Node::new_async(self.wallet.channel());
+        match self.schedule {
+            Some(BroadcastSchedule::Height(height)) => {
+                log::info!("Waiting for block {height} to broadcast the batch");
+                node._wait_for_height(height.saturating_sub(1)).await
+            }
+            Some(BroadcastSchedule::Time(time)) => {
+                log::info!("Waiting for block time {time} to broadcast the batch");
+                node._wait_for_time(time).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Groups the operations, in order, into transactions within the [`BatchLimits`].
+    /// Operations that exceed the limits on their own are marked as failed in `outcomes`
+    async fn plan(
+        &self,
+        outcomes: &mut [OperationOutcome],
+    ) -> Result<Vec<Vec<usize>>, DaemonError> {
+        let mut chunks: Vec<Vec<usize>> = vec![];
+        let mut current: Vec<usize> = vec![];
+
+        for op in 0..self.operations.len() {
+            current.push(op);
+            if self.fits(&current).await? {
+                continue;
+            }
+            current.pop();
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+                current.push(op);
+                if self.fits(&current).await? {
+                    continue;
+                }
+                current.pop();
+            }
+            let label = &self.operations[op].label;
+            log::warn!("Batch operation {label} exceeds the transaction limits on its own");
+            outcomes[op] = OperationOutcome::Failed {
+                error: format!(
+                    "Operation {label} exceeds the transaction limits {:?}",
+                    self.limits
+                ),
+            };
+        }
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+        Ok(chunks)
+    }
+
+    /// Returns true if the operations fit in a single transaction.
+    /// Operations whose simulation fails are considered to fit, the error is reported when broadcasting
+    async fn fits(&self, ops: &[usize]) -> Result<bool, DaemonError> {
+        let msgs = self.msgs(ops);
+        if let Some(max_tx_bytes) = self.limits.max_tx_bytes {
+            let body = TxBuilder::build_body(msgs.clone(), self.memo.as_deref(), 0);
+            if tx_size(body.into_bytes()?.len()) > max_tx_bytes {
+                return Ok(false);
+            }
+        }
+        if let Some(max_gas) = self.limits.max_gas {
+            match self.wallet.simulate(msgs, self.memo.as_deref()).await {
+                Ok((gas, _)) => return Ok(gas <= max_gas),
+                Err(e) => log::debug!("Simulation of the batch failed: {e}"),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Messages of the operations, in order
+    fn msgs(&self, ops: &[usize]) -> Vec<Any> {
+        ops.iter()
+            .flat_map(|&op| self.operations[op].msgs.iter().cloned())
+            .collect()
+    }
+
+    /// Broadcasts the operations in a single transaction, retrying without the failing operations if `split` is true.
+    /// Returns true if all the operations succeeded
+    async fn broadcast_chunk(
+        &self,
+        mut pending: Vec<usize>,
+        split: bool,
+        outcomes: &mut [OperationOutcome],
+        txs: &mut Vec<CosmTxResponse>,
+    ) -> bool {
+        let mut succeeded = true;
+        while !pending.is_empty() {
+            // Messages of the pending operations, with the index of the operation of each message
+            let (msg_owners, msgs): (Vec<usize>, Vec<Any>) = pending
+                .iter()
+                .flat_map(|&op| {
+                    self.operations[op]
+                        .msgs
+                        .iter()
+                        .map(move |msg| (op, msg.clone()))
+                })
+                .unzip();
+
+            match self.wallet.commit_tx_any(msgs, self.memo.as_deref()).await {
+                Ok(tx) => {
+                    for &op in &pending {
+                        outcomes[op] = OperationOutcome::Succeeded {
+                            txhash: tx.txhash.clone(),
+                        };
+                    }
+                    txs.push(tx);
+                    break;
+                }
+                Err(e) => {
+                    succeeded = false;
+                    let error = e.to_string();
+                    let failed_op = failed_msg_index(&error)
+                        .and_then(|index| msg_owners.get(index))
+                        .copied();
+                    let Some(failed_op) = failed_op else {
+                        log::warn!("Batch failed and the error can't be attributed to an operation: {error}");
+                        for &op in &pending {
+                            outcomes[op] = OperationOutcome::Failed {
+                                error: error.clone(),
+                            };
+                        }
+                        break;
+                    };
+                    log::warn!(
+                        "Batch operation {} failed: {error}",
+                        self.operations[failed_op].label
+                    );
+                    outcomes[failed_op] = OperationOutcome::Failed { error };
+                    if !split {
+                        break;
+                    }
+                    pending.retain(|&op| op != failed_op);
+                }
+            }
+        }
+        succeeded
+    }
+}
+
+/// Estimated size of a signed transaction with a body of `body_len` bytes
+fn tx_size(body_len: usize) -> usize {
+    body_len + TX_OVERHEAD_BYTES
+}
+
+/// Outcomes of the operations of a broadcasted [`TxBatch`]
+#[derive(Clone)]
+pub struct BatchReport {
+    batch: TxBatch,
+    outcomes: Vec<OperationOutcome>,
+    /// Transactions that went through, in the order they were broadcasted
+    pub txs: Vec<CosmTxResponse>,
+}
+
+impl BatchReport {
+    /// Outcome of every operation, with its label, in the order they were queued
+    pub fn outcomes(&self) -> impl Iterator<Item = (&str, &OperationOutcome)> {
+        self.batch
+            .operations
+            .iter()
+            .map(|operation| operation.label.as_str())
+            .zip(&self.outcomes)
+    }
+
+    /// Outcome of the operation with this label
+    pub fn outcome(&self, label: &str) -> Option<&OperationOutcome> {
+        self.outcomes()
+            .find(|(operation, _)| *operation == label)
+            .map(|(_, outcome)| outcome)
+    }
+
+    /// Transaction that included the operation with this label, if it succeeded
+    pub fn tx(&self, label: &str) -> Option<&CosmTxResponse> {
+        match self.outcome(label)? {
+            OperationOutcome::Succeeded { txhash } => {
+                self.txs.iter().find(|tx| &tx.txhash == txhash)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if all the operations succeeded
+    pub fn is_success(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|outcome| matches!(outcome, OperationOutcome::Succeeded { .. }))
+    }
+
+    /// Labels of the failed operations
+    pub fn failed(&self) -> Vec<&str> {
+        self.outcomes()
+            .filter(|(_, outcome)| matches!(outcome, OperationOutcome::Failed { .. }))
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    /// Batch containing the operations that didn't succeed (failed or skipped), to retry them
+    pub fn retry_batch(&self) -> TxBatch {
+        let mut batch = self.batch.clone();
+        batch.operations = self
+            .batch
+            .operations
+            .iter()
+            .zip(&self.outcomes)
+            .filter(|(_, outcome)| !matches!(outcome, OperationOutcome::Succeeded { .. }))
+            .map(|(operation, _)| operation.clone())
+            .collect();
+        batch
+    }
+}
+
+/// Index of the failing message, as reported by the cosmos-sdk (`message index: 1`)
+fn failed_msg_index(error: &str) -> Option<usize> {
+    const MESSAGE_INDEX: &str = "message index: ";
+    let start = error.find(MESSAGE_INDEX)? + MESSAGE_INDEX.len();
+    let digits: String = error[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{failed_msg_index, BatchLimits, DEFAULT_MAX_TX_BYTES};
+
+    #[test]
+    fn batch_limits() {
+        assert_eq!(
+            BatchLimits::default().max_tx_bytes,
+            Some(DEFAULT_MAX_TX_BYTES)
+        );
+        let limits = BatchLimits::unlimited().max_gas(10_000_000);
+        assert_eq!(limits.max_gas, Some(10_000_000));
+        assert_eq!(limits.max_tx_bytes, None);
+    }
+
+    #[test]
+    fn parse_failed_msg_index() {
+        assert_eq!(
+            failed_msg_index("failed to execute message; message index: 2: Unauthorized: execute wasm contract failed"),
+            Some(2)
+        );
+        assert_eq!(failed_msg_index("account sequence mismatch"), None);
+    }
+}
@@ -0,0 +1,168 @@
+//! Backends used to submit signed transactions.
+//!
+//! By default, transactions are broadcasted with the gRPC endpoint of the chain, like queries.
+//! Some infrastructure setups don't allow outbound gRPC for write operations (e.g. daemons behind a NAT or a restrictive proxy).
+//! The [`HttpBroadcaster`] backend submits the signed transactions to an HTTP broadcaster/relay service instead, while queries still use gRPC.
+//!
+//! The service must accept the body of the cosmos-sdk REST broadcast endpoint (`POST /cosmos/tx/v1beta1/txs`)
+//! and answer with its response format. A plain LCD endpoint can therefore be used as well.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{broadcast_backend::HttpBroadcaster, networks, DaemonBuilder};
+//!
+//! let daemon = DaemonBuilder::default()
+//!     .chain(networks::JUNO_1)
+//!     .broadcaster(
+//!         HttpBroadcaster::new("https://broadcaster.example.com/cosmos/tx/v1beta1/txs")
+//!             .api_key("my-api-key"),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use base64::{engine::general_purpose, Engine};
+use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
+use serde::{Deserialize, Serialize};
+
+use crate::DaemonError;
+
+/// Header used to send the API key by default
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// Backend used by the sender to submit signed transactions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum BroadcastBackend {
+    /// Broadcast with the gRPC channel of the sender
+    #[default]
+    Grpc,
+    /// Broadcast through an HTTP broadcaster service
+    Http(HttpBroadcaster),
+}
+
+impl From<HttpBroadcaster> for BroadcastBackend {
+    fn from(broadcaster: HttpBroadcaster) -> Self {
+        BroadcastBackend::Http(broadcaster)
+    }
+}
+
+/// HTTP broadcaster/relay service, see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpBroadcaster {
+    /// Url transactions are posted to
+    pub url: String,
+    /// API key sent with every request
+    pub api_key: Option<String>,
+    /// Header the API key is sent in, defaults to [`DEFAULT_API_KEY_HEADER`]
+    pub api_key_header: String,
+}
+
+impl HttpBroadcaster {
+    /// Broadcaster posting transactions to `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            api_key: None,
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+        }
+    }
+
+    /// Sets the API key sent with every request
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sets the header the API key is sent in
+    pub fn api_key_header(mut self, header: impl Into<String>) -> Self {
+        self.api_key_header = header.into();
+        self
+    }
+
+    /// Submits the signed transaction bytes in sync mode
+    pub async fn broadcast(&self, tx_bytes: Vec<u8>) -> Result<TxResponse, DaemonError> {
+        let body = serde_json::to_string(&BroadcastRequest {
+            tx_bytes: general_purpose::STANDARD.encode(tx_bytes),
+            mode: "BROADCAST_MODE_SYNC",
+        })?;
+
+        let mut request = reqwest::Client::new()
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(api_key) = &self.api_key {
+            request = request.header(self.api_key_header.as_str(), api_key);
+        }
+        let response = request.send().await?.error_for_status()?.text().await?;
+
+        parse_broadcast_response(&response)
+    }
+}
+
+#[derive(Serialize)]
+struct BroadcastRequest {
+    tx_bytes: String,
+    mode: &'static str,
+}
+
+#[derive(Deserialize)]
+struct BroadcastResponse {
+    tx_response: Option<RestTxResponse>,
+}
+
+/// Fields of the REST tx response that are used after broadcasting
+#[derive(Deserialize)]
+struct RestTxResponse {
+    #[serde(default)]
+    height: String,
+    txhash: String,
+    #[serde(default)]
+    codespace: String,
+    #[serde(default)]
+    code: u32,
+    #[serde(default)]
+    raw_log: String,
+}
+
+fn parse_broadcast_response(response: &str) -> Result<TxResponse, DaemonError> {
+    let response: BroadcastResponse = serde_json::from_str(response)?;
+    let tx_response = response.tx_response.ok_or_else(|| {
+        DaemonError::StdErr("Broadcaster response doesn't contain a tx response".into())
+    })?;
+    Ok(TxResponse {
+        height: if tx_response.height.is_empty() {
+            0
+        } else {
+            tx_response.height.parse()?
+        },
+        txhash: tx_response.txhash,
+        codespace: tx_response.codespace,
+        code: tx_response.code,
+        raw_log: tx_response.raw_log,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_response() {
+        let response = parse_broadcast_response(
+            r#"{"tx_response":{"height":"0","txhash":"ABCD","codespace":"","code":0,"data":"","raw_log":"[]","logs":[],"info":"","gas_wanted":"0","gas_used":"0","tx":null,"timestamp":"","events":[]}}"#,
+        )
+        .unwrap();
+        assert_eq!(response.txhash, "ABCD");
+        assert_eq!(response.code, 0);
+
+        let response = parse_broadcast_response(
+            r#"{"tx_response":{"txhash":"ABCD","codespace":"sdk","code":32,"raw_log":"account sequence mismatch"}}"#,
+        )
+        .unwrap();
+        assert_eq!(response.code, 32);
+        assert_eq!(response.raw_log, "account sequence mismatch");
+
+        assert!(parse_broadcast_response(r#"{"code":2,"message":"invalid"}"#).is_err());
+    }
+}
@@ -1,14 +1,31 @@
 use crate::{
+    broadcast_backend::BroadcastBackend,
+    cache::QueryCache,
+    fees::{FeeToken, GasPriceOracle},
+    frontend_export::FrontendExport,
+    gas_table::GasEstimation,
+    json_lock::{StateFileFormat, StateLockOptions},
+    keys::hd_path::HdPath,
     log::print_if_log_disabled,
+    query_only_sender::QueryOnlySender,
     sender::{SenderBuilder, SenderOptions},
-    DaemonAsync, DaemonBuilder, DaemonStateFile, GrpcChannel,
+    source_verification::SourceVerification,
+    tx_broadcaster::BroadcastPolicy,
+    DaemonAsync, DaemonAsyncBase, DaemonBuilder, DaemonStateFile, GrpcChannel,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use bitcoin::secp256k1::All;
 
-use super::{error::DaemonError, sender::Sender, state::DaemonState};
-use cw_orch_core::environment::ChainInfoOwned;
+use super::{
+    error::DaemonError,
+    sender::Sender,
+    state::{check_deployment_id, DaemonState},
+};
+use cw_orch_core::{
+    environment::{AddressAlgorithm, ChainId, ChainInfoOwned, ChainKind},
+    CwEnvError,
+};
 
 /// The default deployment id if none is provided
 pub const DEFAULT_DEPLOYMENT: &str = "default";
@@ -35,6 +52,16 @@ pub struct DaemonAsyncBuilder {
     /// State from rebuild or existing daemon
     pub(crate) state: Option<DaemonState>,
     pub(crate) write_on_change: Option<bool>,
+    pub(crate) read_only_state: bool,
+    pub(crate) test_state: bool,
+    pub(crate) state_lock_options: StateLockOptions,
+    pub(crate) state_file_format: Option<StateFileFormat>,
+    pub(crate) frontend_export: Option<FrontendExport>,
+    pub(crate) source_verification: Option<SourceVerification>,
+    pub(crate) query_cache: Option<QueryCache>,
+    pub(crate) ignore_pending_txs: bool,
+    pub(crate) expected_chain_kind: Option<ChainKind>,
+    pub(crate) expected_chain_id: Option<ChainId>,
 
     /* Sender related options */
     /// Wallet sender
@@ -67,6 +94,34 @@ impl DaemonAsyncBuilder {
         self
     }
 
+    /// Use the mnemonic stored under `name` in the OS keychain, see [`MnemonicKeyring`](crate::keyring::MnemonicKeyring)
+    #[cfg(feature = "keyring")]
+    pub fn keyring_entry(&mut self, name: impl ToString) -> &mut Self {
+        self.sender = Some(SenderBuilder::Keyring(name.to_string()));
+        self
+    }
+
+    /// Use an armored private key, as exported by `<chaind> keys export <name>`, decrypted with `passphrase`
+    pub fn armored_key(&mut self, armor: impl ToString, passphrase: impl ToString) -> &mut Self {
+        self.sender = Some(SenderBuilder::Armored {
+            armor: armor.to_string(),
+            passphrase: passphrase.to_string(),
+        });
+        self
+    }
+
+    /// Fail to build if the chain is not of this kind (e.g. only allow [`ChainKind::Mainnet`] in release scripts)
+    pub fn expect_chain_kind(&mut self, kind: ChainKind) -> &mut Self {
+        self.expected_chain_kind = Some(kind);
+        self
+    }
+
+    /// Fail to build if the chain doesn't have this chain id
+    pub fn expect_chain_id(&mut self, chain_id: impl Into<ChainId>) -> &mut Self {
+        self.expected_chain_id = Some(chain_id.into());
+        self
+    }
+
     /// Specifies a sender to use with this chain
     /// This will be used in priority when set on the builder
     pub fn sender(&mut self, wallet: Sender<All>) -> &mut Self {
@@ -92,6 +147,67 @@ impl DaemonAsyncBuilder {
         self
     }
 
+    /// Specifies the full derivation path of the daemon sender, see [`HdPath`]
+    /// Used to access funds held on nonstandard derivations of the chain
+    pub fn hd_path(&mut self, path: HdPath) -> &mut Self {
+        self.sender_options.set_hd_path(path);
+        self
+    }
+
+    /// Specifies the backend used to broadcast transactions, see [`BroadcastBackend`]
+    /// Queries still use the gRPC endpoints of the chain.
+    /// Defaults to broadcasting with gRPC
+    pub fn broadcaster(&mut self, broadcaster: impl Into<BroadcastBackend>) -> &mut Self {
+        self.sender_options.set_broadcaster(broadcaster);
+        self
+    }
+
+    /// Tokens fees can be paid in, by order of preference, see [`FeeToken`]
+    /// Defaults to the gas denom and gas price of the chain
+    pub fn fee_tokens(&mut self, fee_tokens: Vec<FeeToken>) -> &mut Self {
+        self.sender_options.set_fee_tokens(fee_tokens);
+        self
+    }
+
+    /// Fetch the gas price before every transaction, to avoid failing transactions during fee spikes, see [`GasPriceOracle`]
+    pub fn gas_price_oracle(&mut self, oracle: GasPriceOracle) -> &mut Self {
+        self.sender_options.set_gas_price_oracle(oracle);
+        self
+    }
+
+    /// Specifies how the gas of the transactions is estimated, e.g. without simulation on chains that disable it, see [`GasEstimation`]
+    pub fn gas_estimation(&mut self, gas_estimation: GasEstimation) -> &mut Self {
+        self.sender_options.set_gas_estimation(gas_estimation);
+        self
+    }
+
+    /// Specifies how failed transaction broadcasts are retried, see [`BroadcastPolicy`]
+    pub fn broadcast_policy(&mut self, policy: BroadcastPolicy) -> &mut Self {
+        self.sender_options.set_broadcast_policy(policy);
+        self
+    }
+
+    /// Keeps the account sequence locally, so transactions can be broadcasted without waiting for the previous ones, see [`crate::sequence`]
+    pub fn track_sequence(&mut self, track_sequence: bool) -> &mut Self {
+        self.sender_options.set_track_sequence(track_sequence);
+        self
+    }
+
+    /// Type url of the sender public key on chains with the ethereum coin type,
+    /// e.g. [`ETHERMINT_PUB_KEY_TYPE_URL`](crate::proto::injective::ETHERMINT_PUB_KEY_TYPE_URL).
+    /// Defaults to the Injective public key on Injective and to the ethermint one on other chains
+    pub fn eth_pub_key_type_url(&mut self, type_url: impl Into<String>) -> &mut Self {
+        self.sender_options.set_eth_pub_key_type_url(type_url);
+        self
+    }
+
+    /// Algorithm used to derive the sender address, for chains that don't use the usual algorithm of their coin type.
+    /// Defaults to the algorithm of the coin type of the chain, see [`AddressAlgorithm::default_for_coin_type`]
+    pub fn address_algorithm(&mut self, algorithm: AddressAlgorithm) -> &mut Self {
+        self.sender_options.set_address_algorithm(algorithm);
+        self
+    }
+
     /// Reuse already existent [`DaemonState`]
     /// Useful for multi-chain scenarios
     pub fn state(&mut self, state: DaemonState) -> &mut Self {
@@ -108,6 +224,67 @@ impl DaemonAsyncBuilder {
         self
     }
 
+    /// Use a read-only state: the state file is not locked, so other processes can keep writing to it.
+    /// Useful for scripts that only query contracts while a deployment is running.
+    /// Writing to the state (e.g. uploading or instantiating contracts) returns [`DaemonError::StateReadOnly`].
+    /// Defaults to `false`
+    pub fn read_only_state(&mut self, read_only: bool) -> &mut Self {
+        self.read_only_state = read_only;
+        self
+    }
+
+    /// Use an isolated state file in the temp directory, deleted when the daemon is dropped.
+    /// Tests don't share state between them, whatever the `STATE_FILE` env variable.
+    /// Ignored when an existing state is passed with [`Self::state`]
+    pub fn test_state(&mut self) -> &mut Self {
+        self.test_state = true;
+        self
+    }
+
+    /// Wait up to `timeout` for other processes to release the state file instead of failing with [`DaemonError::StateLocked`]
+    pub fn state_lock_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.state_lock_options.timeout = Some(timeout);
+        self
+    }
+
+    /// Submit source verification metadata to an explorer or source registry after every mainnet upload, see [`SourceVerification`]
+    pub fn source_verification(&mut self, verification: SourceVerification) -> &mut Self {
+        self.source_verification = Some(verification);
+        self
+    }
+
+    /// Cache the responses to immutable queries (code checksums, code info, historical blocks), see [`QueryCache`]
+    pub fn query_cache(&mut self, cache: QueryCache) -> &mut Self {
+        self.query_cache = Some(cache);
+        self
+    }
+
+    /// Don't resume the confirmation of the transactions left pending by a previous run when building the daemon, see [`crate::receipts`].
+    /// Defaults to `false`
+    pub fn ignore_pending_txs(&mut self, ignore: bool) -> &mut Self {
+        self.ignore_pending_txs = ignore;
+        self
+    }
+
+    /// Specifies how to wait for other processes to release the state file, see [`StateLockOptions`]
+    pub fn state_lock_options(&mut self, options: StateLockOptions) -> &mut Self {
+        self.state_lock_options = options;
+        self
+    }
+
+    /// Specifies how the state file is written (pretty or compact json, atomic writes), see [`StateFileFormat`]
+    /// Defaults to pretty json written atomically
+    pub fn state_file_format(&mut self, format: StateFileFormat) -> &mut Self {
+        self.state_file_format = Some(format);
+        self
+    }
+
+    /// Export the deployments of the state to a JSON or TypeScript file every time a code id or address is stored, see [`FrontendExport`]
+    pub fn frontend_export(&mut self, export: FrontendExport) -> &mut Self {
+        self.frontend_export = Some(export);
+        self
+    }
+
     /// Specifies path to the daemon state file
     /// Defaults to env variable.
     ///
@@ -120,16 +297,111 @@ impl DaemonAsyncBuilder {
 
     /// Build a daemon
     pub async fn build(&self) -> Result<DaemonAsync, DaemonError> {
+        let (chain_info, state) = self.build_state()?;
+        // if mnemonic provided, use it. Else use env variables to retrieve mnemonic
+        let sender_options = self.sender_options.clone();
+
+        let sender = match self.sender.clone() {
+            Some(sender) => match sender {
+                SenderBuilder::Mnemonic(mnemonic) => Sender::from_mnemonic_with_options(
+                    chain_info.clone(),
+                    GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await?,
+                    &mnemonic,
+                    sender_options,
+                )?,
+                #[cfg(feature = "keyring")]
+                SenderBuilder::Keyring(name) => Sender::from_mnemonic_with_options(
+                    chain_info.clone(),
+                    GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await?,
+                    &crate::keyring::MnemonicKeyring::default().export(&name)?,
+                    sender_options,
+                )?,
+                SenderBuilder::Armored { armor, passphrase } => Sender::from_armored_with_options(
+                    chain_info.clone(),
+                    GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await?,
+                    &armor,
+                    &passphrase,
+                    sender_options,
+                )?,
+                SenderBuilder::Sender(mut sender) => {
+                    sender.set_options(self.sender_options.clone());
+                    sender
+                }
+            },
+            None => Sender::new_with_options(
+                chain_info.clone(),
+                GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await?,
+                sender_options,
+            )?,
+        };
+
+        let daemon = DaemonAsync {
+            state,
+            sender: Arc::new(sender),
+            source_verification: self.source_verification.clone(),
+            query_cache: self.query_cache.clone(),
+        };
+        if !self.ignore_pending_txs && !daemon.state.is_read_only() {
+            daemon.resume_pending_txs().await?;
+        }
+        print_if_log_disabled()?;
+        Ok(daemon)
+    }
+
+    /// Build a daemon that can only query the chain, impersonating `address`.
+    /// No mnemonic or key is needed, which is useful for read-only scripts and state inspection
+    pub async fn build_query_only(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<DaemonAsyncBase<QueryOnlySender>, DaemonError> {
+        let (chain_info, state) = self.build_state()?;
+        let sender = QueryOnlySender::new(
+            chain_info.clone(),
+            GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await?,
+            address,
+        )?;
+
+        let daemon = DaemonAsyncBase {
+            state,
+            sender,
+            source_verification: self.source_verification.clone(),
+            query_cache: self.query_cache.clone(),
+        };
+        print_if_log_disabled()?;
+        Ok(daemon)
+    }
+
+    /// Checks the chain expectations and builds the state of the daemon
+    fn build_state(&self) -> Result<(ChainInfoOwned, DaemonState), DaemonError> {
         let chain_info = self
             .chain
             .clone()
             .ok_or(DaemonError::BuilderMissing("chain information".into()))?;
+        if let Some(kind) = &self.expected_chain_kind {
+            if chain_info.kind != *kind {
+                return Err(CwEnvError::UnexpectedChainKind {
+                    chain_id: chain_info.chain_id,
+                    expected: kind.clone(),
+                    found: chain_info.kind,
+                }
+                .into());
+            }
+        }
+        if let Some(chain_id) = &self.expected_chain_id {
+            if *chain_id != chain_info.chain_id.as_str() {
+                return Err(DaemonError::UnexpectedChain {
+                    expected: chain_id.to_string(),
+                    found: chain_info.chain_id,
+                });
+            }
+        }
         let deployment_id = self
             .deployment_id
             .clone()
             .unwrap_or(DEFAULT_DEPLOYMENT.to_string());
+        check_deployment_id(&deployment_id)?;
 
-        let state = match &self.state {
+        let mut state = match &self.state {
             Some(state) => {
                 let mut state = state.clone();
                 state.chain_data = chain_info.clone();
@@ -152,49 +424,35 @@ impl DaemonAsyncBuilder {
                 state
             }
             None => {
-                let json_file_path = self
-                    .state_path
-                    .clone()
-                    .unwrap_or(DaemonState::state_file_path()?);
+                let json_file_path = if self.test_state {
+                    DaemonState::temporary_state_file_path()
+                } else {
+                    self.state_path
+                        .clone()
+                        .unwrap_or(DaemonState::state_file_path()?)
+                };
 
-                DaemonState::new(
+                let state = DaemonState::new_with_lock_options(
                     json_file_path,
                     chain_info.clone(),
                     deployment_id,
-                    false,
+                    self.read_only_state && !self.test_state,
                     self.write_on_change.unwrap_or(true),
-                )?
-            }
-        };
-        // if mnemonic provided, use it. Else use env variables to retrieve mnemonic
-        let sender_options = self.sender_options.clone();
-
-        let sender = match self.sender.clone() {
-            Some(sender) => match sender {
-                SenderBuilder::Mnemonic(mnemonic) => Sender::from_mnemonic_with_options(
-                    chain_info.clone(),
-                    GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await?,
-                    &mnemonic,
-                    sender_options,
-                )?,
-                SenderBuilder::Sender(mut sender) => {
-                    sender.set_options(self.sender_options.clone());
-                    sender
+                    self.state_lock_options,
+                )?;
+                if self.test_state {
+                    state.remove_file_on_drop();
                 }
-            },
-            None => Sender::new_with_options(
-                chain_info.clone(),
-                GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await?,
-                sender_options,
-            )?,
-        };
-
-        let daemon = DaemonAsync {
-            state,
-            sender: Arc::new(sender),
+                state
+            }
         };
-        print_if_log_disabled()?;
-        Ok(daemon)
+        if let Some(format) = self.state_file_format {
+            state.set_file_format(format);
+        }
+        if let Some(export) = &self.frontend_export {
+            state.frontend_export = Some(export.clone());
+        }
+        Ok((chain_info, state))
     }
 }
 
@@ -208,6 +466,16 @@ impl From<DaemonBuilder> for DaemonAsyncBuilder {
             state: value.state,
             state_path: value.state_path,
             write_on_change: value.write_on_change,
+            read_only_state: value.read_only_state,
+            test_state: value.test_state,
+            state_lock_options: value.state_lock_options,
+            state_file_format: value.state_file_format,
+            frontend_export: value.frontend_export,
+            source_verification: value.source_verification,
+            query_cache: value.query_cache,
+            ignore_pending_txs: value.ignore_pending_txs,
+            expected_chain_kind: value.expected_chain_kind,
+            expected_chain_id: value.expected_chain_id,
         }
     }
 }
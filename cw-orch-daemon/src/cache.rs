@@ -0,0 +1,263 @@
+//! Opt-in cache of the responses to immutable queries.
+//!
+//! Some queries always return the same response on a chain: the checksum, info and bytes of a code, or a block at a given height.
+//! A [`QueryCache`] set on the daemon with [`DaemonBuilder::query_cache`](crate::DaemonBuilder::query_cache) keeps these responses
+//! in memory, keyed by chain id, query and height. With [`QueryCache::persist`], the responses are also written to disk
+//! so that repeated script runs don't query them again.
+//!
+//! Contract info changes with migrations and admin updates, it's only cached when enabled with [`QueryCache::cache_contract_info`],
+//! e.g. for environments forked at a fixed height.
+//!
+//! ## Example
+//! ```no_run
+//! use cw_orch_daemon::{cache::QueryCache, networks, Daemon};
+//!
+//! let daemon = Daemon::builder()
+//!     .chain(networks::JUNO_1)
+//!     .query_cache(QueryCache::new().persist("./.cw-orch-cache"))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::DaemonError;
+
+/// Key of a cached response
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Chain the query was sent to
+    pub chain_id: String,
+    /// Query and its arguments, e.g. `code_checksum/12`
+    pub query: String,
+    /// Height the response is valid at, `None` if it's valid at any height
+    pub height: Option<u64>,
+}
+
+impl CacheKey {
+    /// Name of the file the response is persisted to, inside the directory of the chain
+    fn file_name(&self) -> String {
+        let query = self.query.replace(['/', '\\', ':'], "_");
+        match self.height {
+            Some(height) => format!("{query}@{height}.json"),
+            None => format!("{query}.json"),
+        }
+    }
+}
+
+/// In-memory cache of the responses to immutable queries, optionally persisted to disk.
+/// Clones share the same entries
+#[derive(Debug, Clone, Default)]
+pub struct QueryCache {
+    memory: Arc<Mutex<HashMap<CacheKey, Vec<u8>>>>,
+    dir: Option<PathBuf>,
+    contract_info: bool,
+}
+
+impl QueryCache {
+    /// Empty in-memory cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also persists the responses to `<dir>/<chain_id>/`, and reads them back from there
+    pub fn persist(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Caches contract info responses. Defaults to `false`, as contract info changes with migrations and admin updates
+    pub fn cache_contract_info(mut self, enabled: bool) -> Self {
+        self.contract_info = enabled;
+        self
+    }
+
+    /// Whether contract info responses are cached
+    pub fn caches_contract_info(&self) -> bool {
+        self.contract_info
+    }
+
+    /// View of the cache for the queries sent to `chain_id`
+    pub fn for_chain(&self, chain_id: impl Into<String>) -> ChainQueryCache {
+        ChainQueryCache {
+            cache: self.clone(),
+            chain_id: chain_id.into(),
+        }
+    }
+
+    /// Cached response of `key`, read from disk if it's not in memory
+    pub fn get<T: DeserializeOwned>(&self, key: &CacheKey) -> Option<T> {
+        let cached = self.memory.lock().unwrap().get(key).cloned();
+        let bytes = match cached {
+            Some(bytes) => bytes,
+            None => {
+                let path = self.dir.as_ref()?.join(&key.chain_id).join(key.file_name());
+                let bytes = std::fs::read(path).ok()?;
+                self.memory
+                    .lock()
+                    .unwrap()
+                    .insert(key.clone(), bytes.clone());
+                bytes
+            }
+        };
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Caches the response `value` of `key`. Failing to persist it is only logged
+    pub fn insert<T: Serialize>(&self, key: CacheKey, value: &T) {
+        let Ok(bytes) = serde_json::to_vec(value) else {
+            return;
+        };
+        if let Some(dir) = &self.dir {
+            let dir = dir.join(&key.chain_id);
+            if let Err(e) = std::fs::create_dir_all(&dir)
+                .and_then(|_| std::fs::write(dir.join(key.file_name()), &bytes))
+            {
+                log::warn!("Couldn't persist the cached query {}: {}", key.query, e);
+            }
+        }
+        self.memory.lock().unwrap().insert(key, bytes);
+    }
+
+    /// Number of responses in memory
+    pub fn len(&self) -> usize {
+        self.memory.lock().unwrap().len()
+    }
+
+    /// Returns true if no response is in memory
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the responses from memory and from disk
+    pub fn clear(&self) -> Result<(), DaemonError> {
+        self.memory.lock().unwrap().clear();
+        if let Some(dir) = &self.dir {
+            if dir.exists() {
+                std::fs::remove_dir_all(dir)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// [`QueryCache`] of a single chain, used by the queriers
+#[derive(Debug, Clone)]
+pub struct ChainQueryCache {
+    cache: QueryCache,
+    chain_id: String,
+}
+
+impl ChainQueryCache {
+    /// Underlying cache
+    pub fn cache(&self) -> &QueryCache {
+        &self.cache
+    }
+
+    /// Returns the cached response of `query` at `height`, or sends the query with `send` and caches its response
+    pub async fn get_or_query<T, F, Fut>(
+        &self,
+        query: impl Into<String>,
+        height: Option<u64>,
+        send: F,
+    ) -> Result<T, DaemonError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, DaemonError>>,
+    {
+        let key = CacheKey {
+            chain_id: self.chain_id.clone(),
+            query: query.into(),
+            height,
+        };
+        if let Some(cached) = self.cache.get(&key) {
+            log::debug!("Cache hit for {} on {}", key.query, key.chain_id);
+            return Ok(cached);
+        }
+        let response = send().await?;
+        self.cache.insert(key, &response);
+        Ok(response)
+    }
+}
+
+/// Sends the query with `send`, through `cache` if it's set
+pub(crate) async fn cached<T, F, Fut>(
+    cache: Option<&ChainQueryCache>,
+    query: impl Into<String>,
+    height: Option<u64>,
+    send: F,
+) -> Result<T, DaemonError>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, DaemonError>>,
+{
+    match cache {
+        Some(cache) => cache.get_or_query(query, height, send).await,
+        None => send().await,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn responses_are_cached_per_chain_and_height() -> anyhow::Result<()> {
+        let cache = QueryCache::new();
+        let juno = cache.for_chain("juno-1");
+
+        let first: u64 = juno
+            .get_or_query("block/1", Some(1), || async { Ok(1) })
+            .await?;
+        let second: u64 = juno
+            .get_or_query("block/1", Some(1), || async {
+                Err(DaemonError::StdErr("not cached".to_string()))
+            })
+            .await?;
+        assert_eq!(first, second);
+
+        let other_height: u64 = juno
+            .get_or_query("block/1", Some(2), || async { Ok(2) })
+            .await?;
+        let other_chain: u64 = cache
+            .for_chain("osmosis-1")
+            .get_or_query("block/1", Some(1), || async { Ok(3) })
+            .await?;
+        assert_eq!((other_height, other_chain), (2, 3));
+        assert_eq!(cache.len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn responses_are_persisted() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("cw-orch-cache-{}", std::process::id()));
+        let checksum = "abcd".to_string();
+        QueryCache::new()
+            .persist(&dir)
+            .for_chain("juno-1")
+            .get_or_query("code_checksum/1", None, || async { Ok(checksum.clone()) })
+            .await?;
+
+        // A new cache reads the response back from disk
+        let cache = QueryCache::new().persist(&dir);
+        let cached: Option<String> = cache.get(&CacheKey {
+            chain_id: "juno-1".to_string(),
+            query: "code_checksum/1".to_string(),
+            height: None,
+        });
+        assert_eq!(cached, Some(checksum));
+
+        cache.clear()?;
+        assert!(!dir.exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,289 @@
+//! Reconstruction of the contract call tree of a transaction from its events.
+//!
+//! Wasmd emits an `execute` (or `instantiate`, `migrate`, `sudo`) event when a contract is called, followed by the events of its response,
+//! then the events of the submessages it dispatched. `reply` events mark the return of a submessage to its caller.
+//! The tree is rebuilt from this ordering:
+//! ```no_run
+//! # use cw_orch_daemon::CosmTxResponse;
+//! # let response = CosmTxResponse::default();
+//! // Prints the nested calls, with the contract events of each call
+//! println!("{}", response.call_trace());
+//! ```
+//!
+//! Calls dispatched without reply can't be told apart from the calls dispatched by the previous callee, they are nested under the last open call.
+
+use std::fmt::Display;
+
+use cosmwasm_std::Event;
+
+use crate::tx_resp::MSG_INDEX_ATTR;
+
+/// Contract address attribute added by wasmd to all the contract events
+pub const CONTRACT_ADDRESS_ATTR: &str = "_contract_address";
+
+/// Entry point of a contract call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CallKind {
+    Instantiate,
+    Execute,
+    Migrate,
+    Sudo,
+    Reply,
+}
+
+impl CallKind {
+    fn from_event_type(event_type: &str) -> Option<Self> {
+        match event_type {
+            "instantiate" => Some(CallKind::Instantiate),
+            "execute" => Some(CallKind::Execute),
+            "migrate" => Some(CallKind::Migrate),
+            "sudo" => Some(CallKind::Sudo),
+            "reply" => Some(CallKind::Reply),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CallKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            CallKind::Instantiate => "instantiate",
+            CallKind::Execute => "execute",
+            CallKind::Migrate => "migrate",
+            CallKind::Sudo => "sudo",
+            CallKind::Reply => "reply",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// A contract call and the calls it dispatched
+#[derive(Clone, Debug, PartialEq)]
+pub struct TracedCall {
+    pub kind: CallKind,
+    pub contract: String,
+    /// Events emitted during this call, excluding the events of the nested calls
+    pub events: Vec<Event>,
+    /// Calls dispatched by this call, in execution order
+    pub calls: Vec<TracedCall>,
+}
+
+impl TracedCall {
+    fn new(kind: CallKind, contract: String) -> Self {
+        Self {
+            kind,
+            contract,
+            events: vec![],
+            calls: vec![],
+        }
+    }
+
+    /// All the calls made to `contract` in this call tree, depth first
+    pub fn calls_to(&self, contract: &str) -> Vec<&TracedCall> {
+        let mut calls = vec![];
+        if self.contract == contract {
+            calls.push(self);
+        }
+        for call in &self.calls {
+            calls.extend(call.calls_to(contract));
+        }
+        calls
+    }
+
+    /// Number of nested levels under this call
+    pub fn depth(&self) -> usize {
+        self.calls
+            .iter()
+            .map(|call| call.depth() + 1)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn fmt_indented(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        writeln!(f, "{:indent$}{} {}", "", self.kind, self.contract)?;
+        for event in &self.events {
+            let attributes = event
+                .attributes
+                .iter()
+                .filter(|attr| attr.key != CONTRACT_ADDRESS_ATTR && attr.key != MSG_INDEX_ATTR)
+                .map(|attr| format!("{}={}", attr.key, attr.value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                f,
+                "{:width$}- {} [{attributes}]",
+                "",
+                event.ty,
+                width = indent + 2
+            )?;
+        }
+        for call in &self.calls {
+            call.fmt_indented(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+/// Contract call tree of a transaction, one root per contract called by the top-level messages
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CallTrace {
+    pub calls: Vec<TracedCall>,
+}
+
+impl CallTrace {
+    /// Rebuilds the call tree from the ordered events of a transaction
+    pub fn from_events(events: &[Event]) -> Self {
+        let mut roots: Vec<TracedCall> = vec![];
+        // Path of indexes from the last root to the currently open call
+        let mut stack: Vec<usize> = vec![];
+        let mut msg_index = None;
+
+        for event in events {
+            let event_msg_index = attribute(event, MSG_INDEX_ATTR);
+            if event_msg_index.is_some() && event_msg_index != msg_index {
+                // New top-level message
+                msg_index = event_msg_index;
+                stack.clear();
+            }
+            let contract = attribute(event, CONTRACT_ADDRESS_ATTR);
+
+            match (CallKind::from_event_type(&event.ty), contract) {
+                (Some(CallKind::Reply), Some(contract)) => {
+                    // The submessage returned to its caller: close the calls opened after it
+                    while let Some(open) = open_call(&mut roots, &stack) {
+                        if open.contract == contract {
+                            break;
+                        }
+                        stack.pop();
+                    }
+                    push_call(
+                        &mut roots,
+                        &mut stack,
+                        TracedCall::new(CallKind::Reply, contract),
+                    );
+                }
+                (Some(kind), Some(contract)) => {
+                    push_call(&mut roots, &mut stack, TracedCall::new(kind, contract));
+                }
+                _ => {
+                    if let Some(open) = open_call(&mut roots, &stack) {
+                        open.events.push(event.clone());
+                    }
+                }
+            }
+        }
+
+        Self { calls: roots }
+    }
+
+    /// All the calls made to `contract` in the transaction
+    pub fn calls_to(&self, contract: &str) -> Vec<&TracedCall> {
+        self.calls
+            .iter()
+            .flat_map(|call| call.calls_to(contract))
+            .collect()
+    }
+}
+
+impl Display for CallTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for call in &self.calls {
+            call.fmt_indented(f, 0)?;
+        }
+        Ok(())
+    }
+}
+
+fn attribute(event: &Event, key: &str) -> Option<String> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.clone())
+}
+
+fn open_call<'a>(roots: &'a mut [TracedCall], stack: &[usize]) -> Option<&'a mut TracedCall> {
+    let (first, path) = stack.split_first()?;
+    let mut call = roots.get_mut(*first)?;
+    for index in path {
+        call = call.calls.get_mut(*index)?;
+    }
+    Some(call)
+}
+
+fn push_call(roots: &mut Vec<TracedCall>, stack: &mut Vec<usize>, call: TracedCall) {
+    match open_call(roots, stack) {
+        Some(parent) => {
+            parent.calls.push(call);
+            stack.push(parent.calls.len() - 1);
+        }
+        None => {
+            roots.push(call);
+            *stack = vec![roots.len() - 1];
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn contract_event(ty: &str, contract: &str) -> Event {
+        Event::new(ty).add_attribute(CONTRACT_ADDRESS_ATTR, contract)
+    }
+
+    #[test]
+    fn nested_calls_with_replies() {
+        let events = vec![
+            Event::new("message").add_attribute("action", "/cosmwasm.wasm.v1.MsgExecuteContract"),
+            contract_event("execute", "router"),
+            contract_event("wasm", "router").add_attribute("action", "route"),
+            contract_event("execute", "pool"),
+            contract_event("wasm", "pool").add_attribute("action", "swap"),
+            contract_event("execute", "token"),
+            contract_event("wasm", "token").add_attribute("action", "transfer"),
+            contract_event("reply", "router"),
+            contract_event("wasm", "router").add_attribute("action", "routed"),
+        ];
+
+        let trace = CallTrace::from_events(&events);
+        assert_eq!(trace.calls.len(), 1);
+
+        let router = &trace.calls[0];
+        assert_eq!(router.kind, CallKind::Execute);
+        assert_eq!(router.contract, "router");
+        assert_eq!(router.events.len(), 1);
+        assert_eq!(router.depth(), 2);
+
+        let [pool, reply] = &router.calls[..] else {
+            panic!(
+                "expected the pool call and the reply, got {:?}",
+                router.calls
+            );
+        };
+        assert_eq!(pool.contract, "pool");
+        assert_eq!(pool.calls[0].contract, "token");
+        assert_eq!(reply.kind, CallKind::Reply);
+        assert_eq!(reply.events[0].attributes[1].value, "routed");
+
+        assert_eq!(trace.calls_to("token").len(), 1);
+        assert!(trace
+            .to_string()
+            .starts_with("execute router\n  - wasm [action=route]\n  execute pool\n"));
+    }
+
+    #[test]
+    fn one_root_per_message() {
+        let events = vec![
+            contract_event("execute", "first").add_attribute(MSG_INDEX_ATTR, "0"),
+            contract_event("wasm", "first").add_attribute(MSG_INDEX_ATTR, "0"),
+            contract_event("execute", "second").add_attribute(MSG_INDEX_ATTR, "1"),
+        ];
+
+        let trace = CallTrace::from_events(&events);
+        assert_eq!(trace.calls.len(), 2);
+        assert_eq!(trace.calls[0].contract, "first");
+        assert_eq!(trace.calls[0].events.len(), 1);
+        assert_eq!(trace.calls[1].contract, "second");
+    }
+}
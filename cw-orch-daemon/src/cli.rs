@@ -0,0 +1,161 @@
+//! Standard command line arguments for deployment scripts.
+//!
+//! [`DaemonArgs`] provides the flags most deployment binaries need and maps them onto a [`DaemonBuilder`]:
+//! - `--network <chain-id>`: network to deploy on, looked up in the builtin and user defined networks, see [`crate::network_config`]
+//! - `--deployment-id <id>`: deployment id used to store addresses and code ids
+//! - `--state-file <path>`: state file to use instead of the `STATE_FILE` env variable
+//! - `--dry-run`: the script should only report what it would do
+//! - `--yes`: skip the confirmation prompts
+//!
+//! This module requires the `cli` feature.
+//!
+//! ## Usage
+//! ```no_run
+//! use clap::Parser;
+//! use cw_orch_daemon::cli::DaemonArgs;
+//!
+//! #[derive(Parser)]
+//! struct Cli {
+//!     #[command(flatten)]
+//!     daemon: DaemonArgs,
+//!     /// Script specific argument
+//!     #[arg(long)]
+//!     admin: Option<String>,
+//! }
+//!
+//! let cli = Cli::parse();
+//! let daemon = cli.daemon.build().unwrap();
+//!
+//! if cli.daemon.confirm("Deploy the contracts?").unwrap() {
+//!     // deploy
+//! }
+//! ```
+
+use std::io::{BufRead, Write};
+
+use clap::Parser;
+use cw_orch_core::environment::ChainInfoOwned;
+
+use crate::{network_config::parse_network, Daemon, DaemonBuilder, DaemonError};
+
+/// Standard arguments of a deployment script, see the [module documentation](self)
+#[derive(Parser, Debug, Clone, PartialEq)]
+pub struct DaemonArgs {
+    /// Chain id of the network to use
+    #[arg(long, short = 'n')]
+    pub network: String,
+    /// Deployment id used to store the addresses and code ids
+    #[arg(long)]
+    pub deployment_id: Option<String>,
+    /// State file to use, defaults to the `STATE_FILE` env variable
+    #[arg(long)]
+    pub state_file: Option<String>,
+    /// Only report what the script would do, without broadcasting transactions
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Don't ask for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+impl DaemonArgs {
+    /// Network selected with `--network`
+    pub fn chain(&self) -> Result<ChainInfoOwned, DaemonError> {
+        parse_network(&self.network)
+    }
+
+    /// Applies the arguments to an existing builder
+    pub fn apply<'a>(
+        &self,
+        builder: &'a mut DaemonBuilder,
+    ) -> Result<&'a mut DaemonBuilder, DaemonError> {
+        builder.chain(self.chain()?);
+        if let Some(deployment_id) = &self.deployment_id {
+            builder.deployment_id(deployment_id);
+        }
+        if let Some(state_file) = &self.state_file {
+            builder.state_path(state_file);
+        }
+        Ok(builder)
+    }
+
+    /// Creates a builder configured with the arguments
+    pub fn builder(&self) -> Result<DaemonBuilder, DaemonError> {
+        let mut builder = DaemonBuilder::default();
+        self.apply(&mut builder)?;
+        Ok(builder)
+    }
+
+    /// Builds a [`Daemon`] configured with the arguments
+    pub fn build(&self) -> Result<Daemon, DaemonError> {
+        self.builder()?.build()
+    }
+
+    /// Asks the user to confirm an action on stdin.
+    /// Returns true without asking with `--yes` and false without asking with `--dry-run`
+    pub fn confirm(&self, prompt: &str) -> Result<bool, DaemonError> {
+        if self.dry_run {
+            log::info!("Dry-run, skipping: {}", prompt);
+            return Ok(false);
+        }
+        if self.yes {
+            return Ok(true);
+        }
+        confirm(prompt, std::io::stdin().lock(), std::io::stdout())
+    }
+}
+
+fn confirm(
+    prompt: &str,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<bool, DaemonError> {
+    write!(output, "{} [y/N] ", prompt)?;
+    output.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use cw_orch_networks::networks::LOCAL_JUNO;
+
+    use super::*;
+
+    #[test]
+    fn parse_args() {
+        let args = DaemonArgs::try_parse_from([
+            "deploy",
+            "--network",
+            LOCAL_JUNO.chain_id,
+            "--deployment-id",
+            "v1",
+            "--state-file",
+            "/tmp/state.json",
+            "--dry-run",
+        ])
+        .unwrap();
+        assert_eq!(args.chain().unwrap().chain_id, LOCAL_JUNO.chain_id);
+        assert!(args.dry_run);
+        assert!(!args.confirm("Deploy?").unwrap());
+
+        let builder = args.builder().unwrap();
+        assert_eq!(builder.deployment_id.as_deref(), Some("v1"));
+        assert_eq!(builder.state_path.as_deref(), Some("/tmp/state.json"));
+
+        assert!(DaemonArgs::try_parse_from(["deploy"]).is_err());
+        let args = DaemonArgs::try_parse_from(["deploy", "-n", "unknown-1", "-y"]).unwrap();
+        assert!(args.chain().is_err());
+        assert!(args.confirm("Deploy?").unwrap());
+    }
+
+    #[test]
+    fn confirm_prompt() {
+        let mut output = vec![];
+        assert!(confirm("Deploy?", "yes\n".as_bytes(), &mut output).unwrap());
+        assert_eq!(output, b"Deploy? [y/N] ");
+        assert!(!confirm("Deploy?", "\n".as_bytes(), vec![]).unwrap());
+    }
+}
@@ -0,0 +1,306 @@
+//! Pinning of codes in the wasm VM cache.
+//!
+//! Pinned codes stay in the memory cache of the nodes, which makes instantiating and executing their contracts cheaper.
+//! Pinning is restricted to the authority of the wasm module, usually the gov module. The helpers below submit a
+//! gov proposal containing `MsgPinCodes`/`MsgUnpinCodes`, or send the messages directly when the sender is the authority
+//! (e.g. on local chains or chains with a permissioned wasm module).
+//!
+//! [`Daemon::pin_code`] and [`Daemon::unpin_code`] submit the proposal for a single code with the minimum deposit of the chain.
+//!
+//! The pinned codes are queried with [`CosmWasm::_pinned_code_ids`](crate::queriers::CosmWasm::_pinned_code_ids)
+//! and [`Daemon::is_code_pinned`].
+//!
+//! ## Usage
+//! ```no_run
+//! use cosmwasm_std::coins;
+//! use cw_orch_daemon::{code_pinning::PinCodesProposal, networks, Daemon};
+//!
+//! let daemon = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//! let proposal_id = daemon
+//!     .propose_pin_codes(
+//!         PinCodesProposal::new(vec![12, 13], coins(10_000_000, "ujuno"))
+//!             .title("Pin the vault codes"),
+//!     )
+//!     .unwrap();
+//! ```
+
+use cosmrs::Any;
+use cosmwasm_std::{Addr, Coin};
+use cw_orch_core::log::transaction_target;
+use prost::Message;
+
+use crate::{
+    core::parse_cw_coins,
+    proto::{
+        gov::MsgSubmitProposal,
+        wasm::{MsgPinCodes, MsgUnpinCodes},
+    },
+    queriers::{cosmrs_to_cosmwasm_coins, module_address, CosmWasm, Gov, GOV_MODULE},
+    CosmTxResponse, Daemon, DaemonAsync, DaemonError,
+};
+
+/// Gov proposal to pin or unpin codes
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinCodesProposal {
+    /// Codes to pin or unpin
+    pub code_ids: Vec<u64>,
+    /// Deposit of the proposal
+    pub deposit: Vec<Coin>,
+    /// Title of the proposal
+    pub title: Option<String>,
+    /// Summary of the proposal
+    pub summary: Option<String>,
+}
+
+impl PinCodesProposal {
+    /// Proposal for `code_ids`, with the given deposit
+    pub fn new(code_ids: Vec<u64>, deposit: Vec<Coin>) -> Self {
+        Self {
+            code_ids,
+            deposit,
+            title: None,
+            summary: None,
+        }
+    }
+
+    /// Sets the title of the proposal
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the summary of the proposal
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+}
+
+/// Address of the gov module, the default authority of the wasm module
+pub fn gov_module_address(prefix: &str) -> Result<Addr, DaemonError> {
+    module_address(prefix, GOV_MODULE)
+}
+
+fn pin_codes_msg(authority: String, code_ids: Vec<u64>, pin: bool) -> Any {
+    if pin {
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgPinCodes".to_string(),
+            value: MsgPinCodes {
+                authority,
+                code_ids,
+            }
+            .encode_to_vec(),
+        }
+    } else {
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgUnpinCodes".to_string(),
+            value: MsgUnpinCodes {
+                authority,
+                code_ids,
+            }
+            .encode_to_vec(),
+        }
+    }
+}
+
+impl DaemonAsync {
+    /// Submits a gov proposal executing `msgs` with the gov module as signer.
+    /// Returns the id of the proposal
+    pub async fn submit_gov_proposal(
+        &self,
+        msgs: Vec<Any>,
+        deposit: &[Coin],
+        title: &str,
+        summary: &str,
+    ) -> Result<u64, DaemonError> {
+        let proposal = MsgSubmitProposal {
+            messages: msgs,
+            initial_deposit: parse_cw_coins(deposit)?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            proposer: self.sender.msg_sender()?.to_string(),
+            metadata: String::new(),
+            title: title.to_string(),
+            summary: summary.to_string(),
+        };
+        let result = self
+            .sender
+            .commit_tx_any(
+                vec![Any {
+                    type_url: "/cosmos.gov.v1.MsgSubmitProposal".to_string(),
+                    value: proposal.encode_to_vec(),
+                }],
+                None,
+            )
+            .await?;
+        let proposal_id = proposal_id(&result)?;
+        log::info!(target: &transaction_target(), "Submitted proposal {proposal_id}: {:?}", result.txhash);
+        Ok(proposal_id)
+    }
+
+    /// Submits a gov proposal to pin codes. Returns the id of the proposal
+    pub async fn propose_pin_codes(&self, proposal: PinCodesProposal) -> Result<u64, DaemonError> {
+        self.propose_code_pinning(proposal, true).await
+    }
+
+    /// Submits a gov proposal to unpin codes. Returns the id of the proposal
+    pub async fn propose_unpin_codes(
+        &self,
+        proposal: PinCodesProposal,
+    ) -> Result<u64, DaemonError> {
+        self.propose_code_pinning(proposal, false).await
+    }
+
+    async fn propose_code_pinning(
+        &self,
+        proposal: PinCodesProposal,
+        pin: bool,
+    ) -> Result<u64, DaemonError> {
+        let authority =
+            gov_module_address(&self.sender.chain_info.network_info.pub_address_prefix)?;
+        let action = if pin { "Pin" } else { "Unpin" };
+        let title = proposal
+            .title
+            .unwrap_or_else(|| format!("{action} codes {:?}", proposal.code_ids));
+        let summary = proposal.summary.unwrap_or_else(|| {
+            format!(
+                "{action} codes {:?} in the wasm VM cache",
+                proposal.code_ids
+            )
+        });
+        let msg = pin_codes_msg(authority.to_string(), proposal.code_ids, pin);
+        self.submit_gov_proposal(vec![msg], &proposal.deposit, &title, &summary)
+            .await
+    }
+
+    /// Minimum deposit of a gov proposal on the chain
+    pub async fn min_gov_deposit(&self) -> Result<Vec<Coin>, DaemonError> {
+        let params = Gov::new_async(self.channel())._params("deposit").await?;
+        Ok(params
+            .deposit_params
+            .map(|params| cosmrs_to_cosmwasm_coins(params.min_deposit))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Submits a gov proposal to pin `code_id`, with the minimum deposit of the chain.
+    /// Returns the id of the proposal
+    pub async fn pin_code(&self, code_id: u64) -> Result<u64, DaemonError> {
+        let deposit = self.min_gov_deposit().await?;
+        self.propose_pin_codes(PinCodesProposal::new(vec![code_id], deposit))
+            .await
+    }
+
+    /// Submits a gov proposal to unpin `code_id`, with the minimum deposit of the chain.
+    /// Returns the id of the proposal
+    pub async fn unpin_code(&self, code_id: u64) -> Result<u64, DaemonError> {
+        let deposit = self.min_gov_deposit().await?;
+        self.propose_unpin_codes(PinCodesProposal::new(vec![code_id], deposit))
+            .await
+    }
+
+    /// Returns whether `code_id` is pinned in the wasm VM cache
+    pub async fn is_code_pinned(&self, code_id: u64) -> Result<bool, DaemonError> {
+        CosmWasm::new_async(self.channel())
+            ._is_code_pinned(code_id)
+            .await
+    }
+
+    /// Pins codes directly. Only works when the sender is the authority of the wasm module
+    pub async fn pin_codes(&self, code_ids: Vec<u64>) -> Result<CosmTxResponse, DaemonError> {
+        let msg = pin_codes_msg(self.sender.msg_sender()?.to_string(), code_ids, true);
+        self.sender.commit_tx_any(vec![msg], None).await
+    }
+
+    /// Unpins codes directly. Only works when the sender is the authority of the wasm module
+    pub async fn unpin_codes(&self, code_ids: Vec<u64>) -> Result<CosmTxResponse, DaemonError> {
+        let msg = pin_codes_msg(self.sender.msg_sender()?.to_string(), code_ids, false);
+        self.sender.commit_tx_any(vec![msg], None).await
+    }
+}
+
+impl Daemon {
+    /// Submits a gov proposal executing `msgs` with the gov module as signer.
+    /// Returns the id of the proposal
+    pub fn submit_gov_proposal(
+        &self,
+        msgs: Vec<Any>,
+        deposit: &[Coin],
+        title: &str,
+        summary: &str,
+    ) -> Result<u64, DaemonError> {
+        self.rt_handle.block_on(
+            self.daemon
+                .submit_gov_proposal(msgs, deposit, title, summary),
+        )
+    }
+
+    /// Submits a gov proposal to pin codes. Returns the id of the proposal
+    pub fn propose_pin_codes(&self, proposal: PinCodesProposal) -> Result<u64, DaemonError> {
+        self.rt_handle
+            .block_on(self.daemon.propose_pin_codes(proposal))
+    }
+
+    /// Submits a gov proposal to unpin codes. Returns the id of the proposal
+    pub fn propose_unpin_codes(&self, proposal: PinCodesProposal) -> Result<u64, DaemonError> {
+        self.rt_handle
+            .block_on(self.daemon.propose_unpin_codes(proposal))
+    }
+
+    /// Minimum deposit of a gov proposal on the chain
+    pub fn min_gov_deposit(&self) -> Result<Vec<Coin>, DaemonError> {
+        self.rt_handle.block_on(self.daemon.min_gov_deposit())
+    }
+
+    /// Submits a gov proposal to pin `code_id`, with the minimum deposit of the chain.
+    /// Returns the id of the proposal
+    pub fn pin_code(&self, code_id: u64) -> Result<u64, DaemonError> {
+        self.rt_handle.block_on(self.daemon.pin_code(code_id))
+    }
+
+    /// Submits a gov proposal to unpin `code_id`, with the minimum deposit of the chain.
+    /// Returns the id of the proposal
+    pub fn unpin_code(&self, code_id: u64) -> Result<u64, DaemonError> {
+        self.rt_handle.block_on(self.daemon.unpin_code(code_id))
+    }
+
+    /// Returns whether `code_id` is pinned in the wasm VM cache
+    pub fn is_code_pinned(&self, code_id: u64) -> Result<bool, DaemonError> {
+        self.rt_handle.block_on(self.daemon.is_code_pinned(code_id))
+    }
+
+    /// Pins codes directly. Only works when the sender is the authority of the wasm module
+    pub fn pin_codes(&self, code_ids: Vec<u64>) -> Result<CosmTxResponse, DaemonError> {
+        self.rt_handle.block_on(self.daemon.pin_codes(code_ids))
+    }
+
+    /// Unpins codes directly. Only works when the sender is the authority of the wasm module
+    pub fn unpin_codes(&self, code_ids: Vec<u64>) -> Result<CosmTxResponse, DaemonError> {
+        self.rt_handle.block_on(self.daemon.unpin_codes(code_ids))
+    }
+}
+
+/// Id of the proposal submitted in the transaction
+fn proposal_id(response: &CosmTxResponse) -> Result<u64, DaemonError> {
+    let id = response
+        .get_events("submit_proposal")
+        .iter()
+        .flat_map(|event| event.get_first_attribute_value("proposal_id"))
+        .next()
+        .ok_or_else(|| DaemonError::StdErr("No proposal id in the transaction events".into()))?;
+    Ok(id.parse()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gov_address() {
+        assert_eq!(
+            gov_module_address("cosmos").unwrap().to_string(),
+            "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Layered configuration of the daemon settings.
+//!
+//! Every setting of [`DaemonEnvVars`] is resolved from the following layers, the last one wins:
+//! 1. The defaults documented on [`DaemonEnvVars`].
+//! 2. The `cw-orch.toml` config file of the current directory, or the file at the `CW_ORCH_CONFIG` env variable.
+//!    The values at the top of the file apply to every profile, the `[profiles.<name>]` tables override them for the active profile.
+//! 3. The env variables (e.g. `CW_ORCH_GAS_BUFFER`), which keeps the existing env configurations working.
+//! 4. The overrides of the [`DaemonBuilder`](crate::DaemonBuilder) (e.g. [`state_path`](crate::DaemonBuilder::state_path) or [`gas`](crate::DaemonBuilder::gas)).
+//!
+//! The active profile is set with the `CW_ORCH_PROFILE` env variable or at runtime with [`DaemonConfig::use_profile`].
+//! Mnemonics are never read from the config file, they stay in env variables or in the OS keyring.
+//!
+//! ## Example
+//! ```toml
+//! # cw-orch.toml
+//! gas_buffer = 1.3
+//! log_format = "json"
+//!
+//! [profiles.dev]
+//! state_file = "./dev-state.json"
+//! min_block_speed = "500ms"
+//!
+//! [profiles.prod]
+//! state_file = "prod-state.json"
+//! max_tx_query_retries = 100
+//! ```
+//!
+//! ```no_run
+//! use cw_orch_daemon::{config::DaemonConfig, env::DaemonEnvVars};
+//!
+//! DaemonConfig::use_profile(Some("prod"));
+//! // `prod-state.json` unless the `STATE_FILE` env variable is set
+//! let state_file = DaemonEnvVars::state_file();
+//! ```
+
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::{env::DaemonEnvVars, DaemonError};
+
+/// Name of the config file looked up in the current directory
+pub const DEFAULT_CONFIG_FILE: &str = "cw-orch.toml";
+pub const CONFIG_FILE_ENV_NAME: &str = "CW_ORCH_CONFIG";
+pub const PROFILE_ENV_NAME: &str = "CW_ORCH_PROFILE";
+
+/// Profile selected with [`DaemonConfig::use_profile`], takes precedence over the `CW_ORCH_PROFILE` env variable
+static ACTIVE_PROFILE: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+/// Config file layer, cached with the path and profile it was loaded for
+type CachedConfig = (PathBuf, Option<String>, DaemonConfig);
+static FILE_CONFIG: Lazy<RwLock<Option<CachedConfig>>> = Lazy::new(|| RwLock::new(None));
+
+/// Settings of the daemon that can be set in the config file.
+/// `None` values fall back to the previous layer, see the [module documentation](self)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaemonConfig {
+    /// See [`DaemonEnvVars::state_file`]
+    pub state_file: Option<String>,
+    /// See [`DaemonEnvVars::gas_buffer`]
+    pub gas_buffer: Option<f64>,
+    /// See [`DaemonEnvVars::min_gas`]
+    pub min_gas: Option<u64>,
+    /// See [`DaemonEnvVars::max_tx_query_retries`]
+    pub max_tx_query_retries: Option<usize>,
+    /// See [`DaemonEnvVars::min_block_speed`], e.g. `"500ms"` or `"2s"`
+    pub min_block_speed: Option<String>,
+    /// See [`DaemonEnvVars::wallet_balance_assertion`]
+    pub wallet_balance_assertion: Option<bool>,
+    /// See [`DaemonEnvVars::logs_message`]
+    pub logs_message: Option<bool>,
+    /// See [`DaemonEnvVars::runtime_worker_threads`]
+    pub runtime_worker_threads: Option<usize>,
+    /// See [`DaemonEnvVars::log_format`], `"text"` or `"json"`
+    pub log_format: Option<String>,
+    /// See [`DaemonEnvVars::chain_log_dir`]
+    pub chain_log_dir: Option<PathBuf>,
+    /// See [`DaemonEnvVars::networks_file`]
+    pub networks_file: Option<PathBuf>,
+}
+
+impl DaemonConfig {
+    /// Returns `self` with the values set in `other` replacing its values
+    pub fn merge(self, other: DaemonConfig) -> DaemonConfig {
+        DaemonConfig {
+            state_file: other.state_file.or(self.state_file),
+            gas_buffer: other.gas_buffer.or(self.gas_buffer),
+            min_gas: other.min_gas.or(self.min_gas),
+            max_tx_query_retries: other.max_tx_query_retries.or(self.max_tx_query_retries),
+            min_block_speed: other.min_block_speed.or(self.min_block_speed),
+            wallet_balance_assertion: other
+                .wallet_balance_assertion
+                .or(self.wallet_balance_assertion),
+            logs_message: other.logs_message.or(self.logs_message),
+            runtime_worker_threads: other.runtime_worker_threads.or(self.runtime_worker_threads),
+            log_format: other.log_format.or(self.log_format),
+            chain_log_dir: other.chain_log_dir.or(self.chain_log_dir),
+            networks_file: other.networks_file.or(self.networks_file),
+        }
+    }
+
+    /// Reads the config of `profile` from a config file: the values at the top of the file, overridden by the `[profiles.<profile>]` table.
+    /// Errors if the profile isn't defined in the file
+    pub fn from_file(path: impl AsRef<Path>, profile: Option<&str>) -> Result<Self, DaemonError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| DaemonError::OpenFile(path.display().to_string(), e.to_string()))?;
+        Self::from_toml(&content, profile)
+            .map_err(|e| DaemonError::Config(format!("{}: {e}", path.display())))
+    }
+
+    /// Reads the config of `profile` from the content of a config file, see [`DaemonConfig::from_file`]
+    pub fn from_toml(content: &str, profile: Option<&str>) -> Result<Self, DaemonError> {
+        let config_error = |e: toml::de::Error| DaemonError::Config(e.to_string());
+        let mut table: toml::Table = toml::from_str(content).map_err(config_error)?;
+        let mut profiles: HashMap<String, DaemonConfig> = match table.remove("profiles") {
+            Some(profiles) => profiles.try_into().map_err(config_error)?,
+            None => HashMap::new(),
+        };
+        let base: DaemonConfig = toml::Value::Table(table).try_into().map_err(config_error)?;
+
+        let Some(profile) = profile else {
+            return Ok(base);
+        };
+        let profile_config = profiles
+            .remove(profile)
+            .ok_or_else(|| DaemonError::Config(format!("profile `{profile}` is not defined")))?;
+        Ok(base.merge(profile_config))
+    }
+
+    /// Selects the active profile at runtime, `None` falls back to the `CW_ORCH_PROFILE` env variable.
+    /// Settings are read when they are used, so the profile applies to the daemons built afterwards
+    pub fn use_profile(profile: Option<&str>) {
+        *ACTIVE_PROFILE.write().unwrap() = profile.map(ToString::to_string);
+    }
+
+    /// Active profile, see [`DaemonConfig::use_profile`]
+    pub fn active_profile() -> Option<String> {
+        ACTIVE_PROFILE
+            .read()
+            .unwrap()
+            .clone()
+            .or_else(|| env::var(PROFILE_ENV_NAME).ok())
+    }
+
+    /// Path of the config file: the `CW_ORCH_CONFIG` env variable or `cw-orch.toml` in the current directory
+    pub fn file_path() -> PathBuf {
+        env::var(CONFIG_FILE_ENV_NAME)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_CONFIG_FILE))
+    }
+
+    /// Config file layer of the active profile, empty if there is no config file.
+    /// Panics if the config file is invalid or doesn't define the active profile, like invalid env variables
+    pub fn file_layer() -> DaemonConfig {
+        let path = Self::file_path();
+        let profile = Self::active_profile();
+        if let Some((cached_path, cached_profile, config)) = &*FILE_CONFIG.read().unwrap() {
+            if cached_path == &path && cached_profile == &profile {
+                return config.clone();
+            }
+        }
+
+        let config = if path.exists() {
+            Self::from_file(&path, profile.as_deref())
+                .unwrap_or_else(|e| panic!("Couldn't load the cw-orch config: {e}"))
+        } else if let Some(profile) = &profile {
+            panic!(
+                "Profile `{profile}` is selected but there is no config file at {}",
+                path.display()
+            )
+        } else {
+            DaemonConfig::default()
+        };
+        *FILE_CONFIG.write().unwrap() = Some((path, profile, config.clone()));
+        config
+    }
+
+    /// Settings resolved from all the layers but the builder overrides, for display or debugging
+    pub fn resolved() -> DaemonConfig {
+        DaemonConfig {
+            state_file: Some(DaemonEnvVars::state_file().display().to_string()),
+            gas_buffer: DaemonEnvVars::gas_buffer(),
+            min_gas: DaemonEnvVars::min_gas(),
+            max_tx_query_retries: Some(DaemonEnvVars::max_tx_query_retries()),
+            min_block_speed: Some(format!(
+                "{}ms",
+                DaemonEnvVars::min_block_speed().as_millis()
+            )),
+            wallet_balance_assertion: Some(DaemonEnvVars::wallet_balance_assertion()),
+            logs_message: Some(DaemonEnvVars::logs_message()),
+            runtime_worker_threads: DaemonEnvVars::runtime_worker_threads(),
+            log_format: Some(format!("{:?}", DaemonEnvVars::log_format()).to_lowercase()),
+            chain_log_dir: DaemonEnvVars::chain_log_dir(),
+            networks_file: DaemonEnvVars::networks_file(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        gas_buffer = 1.3
+        state_file = "state.json"
+
+        [profiles.prod]
+        state_file = "prod-state.json"
+        max_tx_query_retries = 100
+    "#;
+
+    #[test]
+    fn profiles_override_the_base_config() -> anyhow::Result<()> {
+        let base = DaemonConfig::from_toml(CONFIG, None)?;
+        assert_eq!(base.gas_buffer, Some(1.3));
+        assert_eq!(base.state_file.as_deref(), Some("state.json"));
+        assert_eq!(base.max_tx_query_retries, None);
+
+        let prod = DaemonConfig::from_toml(CONFIG, Some("prod"))?;
+        assert_eq!(prod.gas_buffer, Some(1.3));
+        assert_eq!(prod.state_file.as_deref(), Some("prod-state.json"));
+        assert_eq!(prod.max_tx_query_retries, Some(100));
+
+        assert!(DaemonConfig::from_toml(CONFIG, Some("staging")).is_err());
+        assert!(DaemonConfig::from_toml("gas_bufer = 1.3", None).is_err());
+        Ok(())
+    }
+}
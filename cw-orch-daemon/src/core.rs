@@ -1,26 +1,42 @@
-use crate::{queriers::CosmWasm, DaemonState};
+use crate::{
+    cache::{ChainQueryCache, QueryCache},
+    frontend_export::{CHECKSUMS_KEY, INIT_MSG_HASHES_KEY},
+    queriers::{raw_grpc_query, smart_contract_state, CosmWasm, SmartQueryOptions},
+    receipts::{
+        clear_pending_tx, pending_txs, record_pending_tx, PendingTx, PendingTxKind, ResumeOutcome,
+        ResumedTx,
+    },
+    source_verification::SourceVerification,
+    tx_builder::TxOptions,
+    DaemonState,
+};
 
 use super::{
-    builder::DaemonAsyncBuilder, cosmos_modules, error::DaemonError, queriers::Node,
-    sender::Wallet, tx_resp::CosmTxResponse,
+    builder::DaemonAsyncBuilder,
+    error::DaemonError,
+    queriers::Node,
+    sender::{QuerySender, Wallet},
+    tx_resp::CosmTxResponse,
 };
 
 use cosmrs::{
     cosmwasm::{MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract},
     proto::cosmwasm::wasm::v1::MsgInstantiateContract2,
     tendermint::Time,
+    tx::Msg,
     AccountId, Any, Denom,
 };
-use cosmwasm_std::{Addr, Binary, Coin};
+use cosmwasm_std::{Addr, Binary, Coin, HexBinary};
 use cw_orch_core::{
-    contract::interface_traits::Uploadable,
-    environment::{ChainState, IndexResponse},
+    contract::{interface_traits::Uploadable, WasmPath},
+    environment::{AsyncTxHandler, AsyncWasmQuerier, ChainState, IndexResponse},
     log::transaction_target,
 };
 use flate2::{write, Compression};
 use prost::Message;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::from_str;
+use sha2::{Digest, Sha256};
 use std::{
     fmt::Debug,
     io::Write,
@@ -62,22 +78,38 @@ use tonic::transport::Channel;
     If you do so, you WILL get account sequence errors and your transactions won't get broadcasted.
     Use a Mutex on top of this DaemonAsync to avoid such errors.
 */
-pub struct DaemonAsync {
+pub struct DaemonAsyncBase<S: QuerySender = Wallet> {
     /// Sender to send transactions to the chain
-    pub sender: Wallet,
+    pub sender: S,
     /// State of the daemon
     pub state: DaemonState,
+    /// Hook submitting source verification metadata after uploads
+    pub source_verification: Option<SourceVerification>,
+    /// Cache of the responses to immutable queries
+    pub query_cache: Option<QueryCache>,
 }
 
+/// [`DaemonAsyncBase`] that signs and broadcasts transactions with a [`Wallet`]
+pub type DaemonAsync = DaemonAsyncBase<Wallet>;
+
 impl DaemonAsync {
     /// Get the daemon builder
     pub fn builder() -> DaemonAsyncBuilder {
         DaemonAsyncBuilder::default()
     }
+}
 
+impl<S: QuerySender> DaemonAsyncBase<S> {
     /// Get the channel configured for this DaemonAsync.
     pub fn channel(&self) -> Channel {
-        self.sender.grpc_channel.clone()
+        self.sender.grpc_channel()
+    }
+
+    /// Query cache of the daemon for its chain, see [`QueryCache`]
+    pub(crate) fn chain_query_cache(&self) -> Option<ChainQueryCache> {
+        self.query_cache
+            .as_ref()
+            .map(|cache| cache.for_chain(self.state.chain_data.chain_id.to_string()))
     }
 
     /// Flushes all the state related to the current chain
@@ -85,9 +117,162 @@ impl DaemonAsync {
     pub fn flush_state(&mut self) -> Result<(), DaemonError> {
         self.state.flush()
     }
+
+    /// Get the sender address
+    pub fn sender(&self) -> Addr {
+        self.sender.address().unwrap()
+    }
+
+    /// Query a contract.
+    pub async fn query<Q: Serialize + Debug, T: Serialize + DeserializeOwned>(
+        &self,
+        query_msg: &Q,
+        contract_address: &Addr,
+    ) -> Result<T, DaemonError> {
+        self.query_with_options(query_msg, contract_address, &SmartQueryOptions::default())
+            .await
+    }
+
+    /// Query a contract with a timeout, see [`SmartQueryOptions`]
+    pub async fn query_with_options<Q: Serialize + Debug, T: Serialize + DeserializeOwned>(
+        &self,
+        query_msg: &Q,
+        contract_address: &Addr,
+        options: &SmartQueryOptions,
+    ) -> Result<T, DaemonError> {
+        let data = smart_contract_state(
+            self.channel(),
+            contract_address.to_string(),
+            serde_json::to_vec(&query_msg)?,
+            options,
+        )
+        .await?;
+
+        Ok(from_str(from_utf8(&data).unwrap())?)
+    }
+
+    /// Execute a protobuf query on a chain module.
+    /// `path` is the full gRPC method path, for instance `/cosmos.bank.v1beta1.Query/Balance`
+    pub async fn raw_module_query<Req, Res>(
+        &self,
+        path: &str,
+        request: &Req,
+    ) -> Result<Res, DaemonError>
+    where
+        Req: Message + Clone + Send + Sync + 'static,
+        Res: Message + Default + Send + Sync + 'static,
+    {
+        raw_grpc_query(self.channel(), path, request).await
+    }
+
+    /// Wait for a given amount of blocks.
+    pub async fn wait_blocks(&self, amount: u64) -> Result<(), DaemonError> {
+        let mut last_height = Node::new_async(self.channel())._block_height().await?;
+        let end_height = last_height + amount;
+
+        let average_block_speed = Node::new_async(self.channel())
+            ._average_block_speed(Some(0.9))
+            .await?;
+
+        let wait_time = average_block_speed.mul_f64(amount as f64);
+
+        // now wait for that amount of time
+        tokio::time::sleep(wait_time).await;
+        // now check every block until we hit the target
+        while last_height < end_height {
+            // wait
+
+            tokio::time::sleep(average_block_speed).await;
+
+            // ping latest block
+            last_height = Node::new_async(self.channel())._block_height().await?;
+        }
+        Ok(())
+    }
+
+    /// Wait for a given amount of seconds.
+    pub async fn wait_seconds(&self, secs: u64) -> Result<(), DaemonError> {
+        tokio::time::sleep(Duration::from_secs(secs)).await;
+
+        Ok(())
+    }
+
+    /// Wait for the next block.
+    pub async fn next_block(&self) -> Result<(), DaemonError> {
+        self.wait_blocks(1).await
+    }
+
+    /// Get the current block info.
+    pub async fn block_info(&self) -> Result<cosmwasm_std::BlockInfo, DaemonError> {
+        let block = Node::new_async(self.channel())._latest_block().await?;
+        let since_epoch = block.header.time.duration_since(Time::unix_epoch())?;
+        let time = cosmwasm_std::Timestamp::from_nanos(since_epoch.as_nanos() as u64);
+        Ok(cosmwasm_std::BlockInfo {
+            height: block.header.height.value(),
+            time,
+            chain_id: block.header.chain_id.to_string(),
+        })
+    }
+
+    /// Confirms the transactions that were broadcasted but not confirmed by a previous run, see [`crate::receipts`].
+    /// The contract address of instantiations that landed is registered in the state under their label
+    pub async fn resume_pending_txs(&self) -> Result<Vec<ResumedTx>, DaemonError> {
+        let mut state = self.state.clone();
+        let mut resumed = vec![];
+        for pending in pending_txs(&state)? {
+            log::info!(
+                target: &transaction_target(),
+                "Resuming the confirmation of transaction {}",
+                pending.txhash
+            );
+            let outcome = match Node::new_async(self.channel())
+                ._find_tx(pending.txhash.clone())
+                .await
+            {
+                Ok(tx) if tx.code == 0 => {
+                    if let PendingTxKind::Instantiate { label } = &pending.kind {
+                        let address = tx.instantiated_contract_address()?;
+                        log::info!(
+                            target: &transaction_target(),
+                            "Instantiation of {label} landed at {address}"
+                        );
+                        state.set(&pending.deployment_id, label, address.as_str())?;
+                    }
+                    if let PendingTxKind::Upload { checksum } = &pending.kind {
+                        log::info!(
+                            target: &transaction_target(),
+                            "Upload of code {checksum} landed with code id {}",
+                            tx.uploaded_code_id()?
+                        );
+                    }
+                    ResumeOutcome::Landed(tx)
+                }
+                Ok(tx) => {
+                    log::warn!(
+                        target: &transaction_target(),
+                        "Transaction {} failed: {}",
+                        tx.txhash,
+                        tx.raw_log
+                    );
+                    ResumeOutcome::Failed(tx)
+                }
+                Err(DaemonError::TXNotFound(txhash, _)) => {
+                    log::warn!(
+                        target: &transaction_target(),
+                        "Transaction {txhash} not found, it was dropped"
+                    );
+                    ResumeOutcome::Dropped
+                }
+                Err(e) => return Err(e),
+            };
+            clear_pending_tx(&mut state, &pending.txhash)?;
+            resumed.push(ResumedTx { pending, outcome });
+        }
+        Ok(resumed)
+    }
 }
 
-impl ChainState for DaemonAsync {
+impl<S: QuerySender> ChainState for DaemonAsyncBase<S> {
     type Out = DaemonState;
 
     fn state(&self) -> Self::Out {
@@ -95,18 +280,40 @@ impl ChainState for DaemonAsync {
     }
 }
 
-// Execute on the real chain, returns tx response.
-impl DaemonAsync {
-    /// Get the sender address
-    pub fn sender(&self) -> Addr {
-        self.sender.address().unwrap()
+impl<S: QuerySender> AsyncWasmQuerier for DaemonAsyncBase<S> {
+    type Error = DaemonError;
+
+    async fn async_query<Q: Serialize + Debug, T: Serialize + DeserializeOwned>(
+        &self,
+        query_msg: &Q,
+        contract_address: &Addr,
+    ) -> Result<T, DaemonError> {
+        DaemonAsyncBase::query(self, query_msg, contract_address).await
+    }
+}
+
+impl AsyncTxHandler for DaemonAsync {
+    type Response = CosmTxResponse;
+
+    async fn async_execute<E: Serialize + Debug>(
+        &self,
+        exec_msg: &E,
+        coins: &[Coin],
+        contract_address: &Addr,
+    ) -> Result<CosmTxResponse, DaemonError> {
+        DaemonAsync::execute(self, exec_msg, coins, contract_address).await
     }
+}
 
+// Execute on the real chain, returns tx response.
+impl DaemonAsync {
     /// Returns a new [`DaemonAsyncBuilder`] with the current configuration.
     /// Does not consume the original [`DaemonAsync`].
     pub fn rebuild(&self) -> DaemonAsyncBuilder {
         let mut builder = DaemonAsyncBuilder {
             state: Some(self.state()),
+            source_verification: self.source_verification.clone(),
+            query_cache: self.query_cache.clone(),
             ..Default::default()
         };
         builder
@@ -121,6 +328,18 @@ impl DaemonAsync {
         exec_msg: &E,
         coins: &[cosmwasm_std::Coin],
         contract_address: &Addr,
+    ) -> Result<CosmTxResponse, DaemonError> {
+        self.execute_with_options(exec_msg, coins, contract_address, &TxOptions::default())
+            .await
+    }
+
+    /// Execute a message on a contract with a custom memo and timeout height.
+    pub async fn execute_with_options<E: Serialize>(
+        &self,
+        exec_msg: &E,
+        coins: &[cosmwasm_std::Coin],
+        contract_address: &Addr,
+        options: &TxOptions,
     ) -> Result<CosmTxResponse, DaemonError> {
         let exec_msg: MsgExecuteContract = MsgExecuteContract {
             sender: self.sender.msg_sender()?,
@@ -128,7 +347,10 @@ impl DaemonAsync {
             msg: serde_json::to_vec(&exec_msg)?,
             funds: parse_cw_coins(coins)?,
         };
-        let result = self.sender.commit_tx(vec![exec_msg], None).await?;
+        let result = self
+            .sender
+            .commit_tx_any_with_options(vec![exec_msg.into_any()?], options)
+            .await?;
         log::info!(target: &transaction_target(), "Execution done: {:?}", result.txhash);
 
         Ok(result)
@@ -143,8 +365,6 @@ impl DaemonAsync {
         admin: Option<&Addr>,
         coins: &[Coin],
     ) -> Result<CosmTxResponse, DaemonError> {
-        let sender = &self.sender;
-
         let init_msg = MsgInstantiateContract {
             code_id,
             label: Some(label.unwrap_or("instantiate_contract").to_string()),
@@ -154,7 +374,15 @@ impl DaemonAsync {
             funds: parse_cw_coins(coins)?,
         };
 
-        let result = sender.commit_tx(vec![init_msg], None).await?;
+        let label = init_msg.label.clone().unwrap_or_default();
+        let init_msg_hash = HexBinary::from(Sha256::digest(&init_msg.msg).to_vec()).to_hex();
+        let result = self
+            .commit_tracked(
+                vec![init_msg.into_any()?],
+                PendingTxKind::Instantiate { label },
+            )
+            .await?;
+        self.record_init_msg_hash(&result, init_msg_hash)?;
 
         log::info!(target: &transaction_target(), "Instantiation done: {:?}", result.txhash);
 
@@ -184,38 +412,24 @@ impl DaemonAsync {
             fix_msg: false,
         };
 
-        let result = sender
-            .commit_tx_any(
+        let label = init_msg.label.clone();
+        let init_msg_hash = HexBinary::from(Sha256::digest(&init_msg.msg).to_vec()).to_hex();
+        let result = self
+            .commit_tracked(
                 vec![Any {
                     type_url: "/cosmwasm.wasm.v1.MsgInstantiateContract2".to_string(),
                     value: init_msg.encode_to_vec(),
                 }],
-                None,
+                PendingTxKind::Instantiate { label },
             )
             .await?;
+        self.record_init_msg_hash(&result, init_msg_hash)?;
 
         log::info!(target: &transaction_target(), "Instantiation done: {:?}", result.txhash);
 
         Ok(result)
     }
 
-    /// Query a contract.
-    pub async fn query<Q: Serialize + Debug, T: Serialize + DeserializeOwned>(
-        &self,
-        query_msg: &Q,
-        contract_address: &Addr,
-    ) -> Result<T, DaemonError> {
-        let mut client = cosmos_modules::cosmwasm::query_client::QueryClient::new(self.channel());
-        let resp = client
-            .smart_contract_state(cosmos_modules::cosmwasm::QuerySmartContractStateRequest {
-                address: contract_address.to_string(),
-                query_data: serde_json::to_vec(&query_msg)?,
-            })
-            .await?;
-
-        Ok(from_str(from_utf8(&resp.into_inner().data).unwrap())?)
-    }
-
     /// Migration a contract.
     pub async fn migrate<M: Serialize + Debug>(
         &self,
@@ -229,72 +443,43 @@ impl DaemonAsync {
             msg: serde_json::to_vec(&migrate_msg)?,
             code_id: new_code_id,
         };
-        let result = self.sender.commit_tx(vec![exec_msg], None).await?;
-        Ok(result)
-    }
-
-    /// Wait for a given amount of blocks.
-    pub async fn wait_blocks(&self, amount: u64) -> Result<(), DaemonError> {
-        let mut last_height = Node::new_async(self.channel())._block_height().await?;
-        let end_height = last_height + amount;
-
-        let average_block_speed = Node::new_async(self.channel())
-            ._average_block_speed(Some(0.9))
+        let result = self
+            .commit_tracked(
+                vec![exec_msg.into_any()?],
+                PendingTxKind::Migrate {
+                    contract: contract_address.to_string(),
+                    code_id: new_code_id,
+                },
+            )
             .await?;
-
-        let wait_time = average_block_speed.mul_f64(amount as f64);
-
-        // now wait for that amount of time
-        tokio::time::sleep(wait_time).await;
-        // now check every block until we hit the target
-        while last_height < end_height {
-            // wait
-
-            tokio::time::sleep(average_block_speed).await;
-
-            // ping latest block
-            last_height = Node::new_async(self.channel())._block_height().await?;
-        }
-        Ok(())
-    }
-
-    /// Wait for a given amount of seconds.
-    pub async fn wait_seconds(&self, secs: u64) -> Result<(), DaemonError> {
-        tokio::time::sleep(Duration::from_secs(secs)).await;
-
-        Ok(())
-    }
-
-    /// Wait for the next block.
-    pub async fn next_block(&self) -> Result<(), DaemonError> {
-        self.wait_blocks(1).await
-    }
-
-    /// Get the current block info.
-    pub async fn block_info(&self) -> Result<cosmwasm_std::BlockInfo, DaemonError> {
-        let block = Node::new_async(self.channel())._latest_block().await?;
-        let since_epoch = block.header.time.duration_since(Time::unix_epoch())?;
-        let time = cosmwasm_std::Timestamp::from_nanos(since_epoch.as_nanos() as u64);
-        Ok(cosmwasm_std::BlockInfo {
-            height: block.header.height.value(),
-            time,
-            chain_id: block.header.chain_id.to_string(),
-        })
+        Ok(result)
     }
 
     /// Upload a contract to the chain.
+    /// The in-memory code of the contract is used if provided, otherwise its wasm file is read
     pub async fn upload<T: Uploadable>(
         &self,
         _uploadable: &T,
     ) -> Result<CosmTxResponse, DaemonError> {
-        let sender = &self.sender;
+        if let Some(code) = <T as Uploadable>::wasm_bytes(&self.sender.chain_info) {
+            return self.upload_from_bytes(code).await;
+        }
         let wasm_path = <T as Uploadable>::wasm(&self.sender.chain_info);
+        self.upload_wasm(&wasm_path).await
+    }
 
+    /// Upload a wasm file to the chain.
+    pub async fn upload_wasm(&self, wasm_path: &WasmPath) -> Result<CosmTxResponse, DaemonError> {
         log::debug!(target: &transaction_target(), "Uploading file at {:?}", wasm_path);
 
         let file_contents = std::fs::read(wasm_path.path())?;
+        self.upload_from_bytes(file_contents).await
+    }
+
+    /// Upload raw wasm code to the chain.
+    pub async fn upload_from_bytes(&self, code: Vec<u8>) -> Result<CosmTxResponse, DaemonError> {
         let mut e = write::GzEncoder::new(Vec::new(), Compression::default());
-        e.write_all(&file_contents)?;
+        e.write_all(&code)?;
         let wasm_byte_code = e.finish()?;
         let store_msg = cosmrs::cosmwasm::MsgStoreCode {
             sender: self.sender.msg_sender()?,
@@ -302,20 +487,91 @@ impl DaemonAsync {
             instantiate_permission: None,
         };
 
-        let result = sender.commit_tx(vec![store_msg], None).await?;
+        let checksum = HexBinary::from(Sha256::digest(&code).to_vec()).to_hex();
+        let result = self
+            .commit_tracked(
+                vec![store_msg.into_any()?],
+                PendingTxKind::Upload {
+                    checksum: checksum.clone(),
+                },
+            )
+            .await?;
 
         log::info!(target: &transaction_target(), "Uploading done: {:?}", result.txhash);
 
         let code_id = result.uploaded_code_id().unwrap();
+        self.record_metadata(CHECKSUMS_KEY, &code_id.to_string(), checksum)?;
 
         // wait for the node to return the contract information for this upload
         let wasm = CosmWasm::new_async(self.channel());
         while wasm._code(code_id).await.is_err() {
             self.next_block().await?;
         }
+
+        if let Some(verification) = &self.source_verification {
+            if verification.applies_to(&self.sender.chain_info.kind) {
+                let request = verification.request(
+                    self.sender.chain_info.chain_id.clone(),
+                    code_id,
+                    &code,
+                    result.txhash.clone(),
+                );
+                verification
+                    .submit_and_record(&mut self.state.clone(), &request)
+                    .await?;
+            }
+        }
         Ok(result)
     }
 
+    /// Records the instantiate message hash of the contract instantiated by `result`, see [`crate::frontend_export`]
+    fn record_init_msg_hash(
+        &self,
+        result: &CosmTxResponse,
+        init_msg_hash: String,
+    ) -> Result<(), DaemonError> {
+        match result.event_attr_value("instantiate", "_contract_address") {
+            Ok(address) => self.record_metadata(INIT_MSG_HASHES_KEY, &address, init_msg_hash),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Records deployment metadata in the state, read-only states are left untouched
+    fn record_metadata(&self, key: &str, id: &str, value: String) -> Result<(), DaemonError> {
+        let mut state = self.state.clone();
+        if state.is_read_only() {
+            return Ok(());
+        }
+        state.set(key, id, value)
+    }
+
+    /// Commits the messages, recording the transaction in the state until it's confirmed, see [`crate::receipts`]
+    async fn commit_tracked(
+        &self,
+        msgs: Vec<Any>,
+        kind: PendingTxKind,
+    ) -> Result<CosmTxResponse, DaemonError> {
+        let mut state = self.state.clone();
+        let mut broadcasted = None;
+        let result = self
+            .sender
+            .commit_tx_any_with_receipt(msgs, &TxOptions::default(), |txhash| {
+                broadcasted = Some(txhash.to_string());
+                record_pending_tx(
+                    &mut state,
+                    &PendingTx::new(txhash, &self.state.deployment_id, kind),
+                )
+            })
+            .await;
+        // Transactions that are not found stay pending, to be resumed by the next run
+        if let Some(txhash) = broadcasted {
+            if matches!(result, Ok(_) | Err(DaemonError::TxFailed { .. })) {
+                clear_pending_tx(&mut state, &txhash)?;
+            }
+        }
+        result
+    }
+
     /// Set the sender to use with this DaemonAsync to be the given wallet
     pub fn set_sender(&mut self, sender: &Wallet) {
         self.sender = sender.clone();
@@ -0,0 +1,371 @@
+//! Single-node local networks running in Docker.
+//!
+//! A [`Devnet`] starts a fresh chain for a [`ChainInfo`](cw_orch_core::environment::ChainInfo) in a Docker container,
+//! funds the deployer and the configured accounts at genesis, waits for the first block and returns a ready [`Daemon`].
+//! This replaces the manual "start a local node first" step of examples and tests, only a running Docker daemon is needed.
+//!
+//! The [`DevnetImage`] presets use the `wasmd`, `junod` and `osmosisd` images. Any Cosmos SDK image with `sh` can be used with [`DevnetImage::custom`].
+//!
+//! ## Example
+//! ```no_run
+//! use cosmwasm_std::coins;
+//! use cw_orch_daemon::{devnet::{Devnet, DevnetImage}, networks};
+//!
+//! let devnet = Devnet::new(networks::LOCAL_JUNO, DevnetImage::Juno)
+//!     .fund("juno1xxx", coins(1_000_000, "ujunox"))
+//!     .start()
+//!     .unwrap();
+//! let daemon = devnet.daemon().unwrap();
+//! // The container is removed when `devnet` is dropped
+//! ```
+
+use std::{
+    process::Command,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use cosmwasm_std::Coin;
+use cw_orch_core::environment::{ChainInfoOwned, ChainKind, QueryHandler};
+
+use crate::{env::DaemonEnvVars, Daemon, DaemonError};
+
+/// Mnemonic of the deployer when none is provided, from the Juno test user
+pub const DEVNET_MNEMONIC: &str = "clip hire initial neck maid actor venue client foam budget lock catalog sweet steak waste crater broccoli pipe steak sister coyote moment obvious choose";
+
+/// Amount of gas tokens given to the validator and the deployer at genesis
+const GENESIS_AMOUNT: u128 = 1_000_000_000_000;
+/// gRPC port inside the container
+const CONTAINER_GRPC_PORT: u16 = 9090;
+/// RPC port inside the container
+const CONTAINER_RPC_PORT: u16 = 26657;
+
+/// Docker image of a devnet node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevnetImage {
+    /// `cosmwasm/wasmd`
+    Wasmd,
+    /// `ghcr.io/cosmoscontracts/juno`
+    Juno,
+    /// `osmolabs/osmosis`
+    Osmosis,
+    /// Any Cosmos SDK image
+    Custom {
+        /// Image, with its tag
+        image: String,
+        /// Name of the node binary inside the image
+        binary: String,
+    },
+}
+
+impl DevnetImage {
+    /// Cosmos SDK image `image` with the node binary `binary`
+    pub fn custom(image: impl Into<String>, binary: impl Into<String>) -> Self {
+        DevnetImage::Custom {
+            image: image.into(),
+            binary: binary.into(),
+        }
+    }
+
+    /// Image, with its tag
+    pub fn image(&self) -> &str {
+        match self {
+            DevnetImage::Wasmd => "cosmwasm/wasmd:v0.50.0",
+            DevnetImage::Juno => "ghcr.io/cosmoscontracts/juno:v12.0.0",
+            DevnetImage::Osmosis => "osmolabs/osmosis:25.0.0-alpine",
+            DevnetImage::Custom { image, .. } => image,
+        }
+    }
+
+    /// Name of the node binary inside the image
+    pub fn binary(&self) -> &str {
+        match self {
+            DevnetImage::Wasmd => "wasmd",
+            DevnetImage::Juno => "junod",
+            DevnetImage::Osmosis => "osmosisd",
+            DevnetImage::Custom { binary, .. } => binary,
+        }
+    }
+}
+
+/// Configuration of a single-node local network, see the [module documentation](self)
+#[derive(Debug, Clone)]
+pub struct Devnet {
+    chain: ChainInfoOwned,
+    image: DevnetImage,
+    container_name: String,
+    mnemonic: Option<String>,
+    accounts: Vec<(String, Vec<Coin>)>,
+    grpc_port: u16,
+    rpc_port: u16,
+    startup_timeout: Duration,
+    keep_running: bool,
+}
+
+impl Devnet {
+    /// Devnet running `chain` in `image`.
+    /// The chain id and the gas denom of `chain` are used for the genesis, its endpoints are replaced by the ones of the container
+    pub fn new(chain: impl Into<ChainInfoOwned>, image: DevnetImage) -> Self {
+        let chain = chain.into();
+        Self {
+            container_name: format!("cw-orch-devnet-{}", chain.chain_id),
+            chain,
+            image,
+            mnemonic: None,
+            accounts: vec![],
+            grpc_port: CONTAINER_GRPC_PORT,
+            rpc_port: CONTAINER_RPC_PORT,
+            startup_timeout: Duration::from_secs(60),
+            keep_running: false,
+        }
+    }
+
+    /// Name of the container. Defaults to `cw-orch-devnet-<chain_id>`
+    pub fn container_name(mut self, name: impl Into<String>) -> Self {
+        self.container_name = name.into();
+        self
+    }
+
+    /// Mnemonic of the deployer, funded at genesis.
+    /// Defaults to the `LOCAL_MNEMONIC` env variable, then to [`DEVNET_MNEMONIC`]
+    pub fn mnemonic(mut self, mnemonic: impl Into<String>) -> Self {
+        self.mnemonic = Some(mnemonic.into());
+        self
+    }
+
+    /// Funds `address` with `amount` at genesis
+    pub fn fund(mut self, address: impl Into<String>, amount: Vec<Coin>) -> Self {
+        self.accounts.push((address.into(), amount));
+        self
+    }
+
+    /// Host port the gRPC endpoint is published on. Defaults to 9090
+    pub fn grpc_port(mut self, port: u16) -> Self {
+        self.grpc_port = port;
+        self
+    }
+
+    /// Host port the RPC endpoint is published on. Defaults to 26657
+    pub fn rpc_port(mut self, port: u16) -> Self {
+        self.rpc_port = port;
+        self
+    }
+
+    /// Maximum time to wait for the first block. Defaults to 60 seconds
+    pub fn startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Keeps the container running when the [`RunningDevnet`] is dropped
+    pub fn keep_running(mut self, keep: bool) -> Self {
+        self.keep_running = keep;
+        self
+    }
+
+    /// Chain info of the devnet, pointing to the published endpoints
+    pub fn chain_info(&self) -> ChainInfoOwned {
+        let mut chain = self.chain.clone();
+        chain.kind = ChainKind::Local;
+        chain.grpc_urls = vec![format!("http://localhost:{}", self.grpc_port)];
+        chain.lcd_url = None;
+        chain.fcd_url = None;
+        chain.rpc_url = Some(format!("http://localhost:{}", self.rpc_port));
+        chain
+    }
+
+    fn deployer_mnemonic(&self) -> String {
+        self.mnemonic
+            .clone()
+            .or_else(DaemonEnvVars::local_mnemonic)
+            .unwrap_or_else(|| DEVNET_MNEMONIC.to_string())
+    }
+
+    /// Script initializing the genesis and starting the node inside the container
+    fn setup_script(&self) -> String {
+        let denom = &self.chain.gas_denom;
+        let chain_id = &self.chain.chain_id;
+        let mut script = format!(
+            r#"set -e
+BIN={binary}
+HOME_DIR=/tmp/devnet
+KEYS="--keyring-backend test --home $HOME_DIR"
+add_account() {{ $BIN genesis add-genesis-account "$1" "$2" $KEYS || $BIN add-genesis-account "$1" "$2" $KEYS; }}
+$BIN init devnet --chain-id {chain_id} --home $HOME_DIR > /dev/null 2>&1
+sed -i 's/"stake"/"{denom}"/g' $HOME_DIR/config/genesis.json
+sed -i 's/timeout_commit = "5s"/timeout_commit = "1s"/' $HOME_DIR/config/config.toml
+$BIN keys add validator $KEYS > /dev/null 2>&1
+echo "$DEVNET_MNEMONIC" | $BIN keys add deployer --recover $KEYS > /dev/null 2>&1
+add_account validator {GENESIS_AMOUNT}{denom}
+add_account deployer {GENESIS_AMOUNT}{denom}
+"#,
+            binary = self.image.binary(),
+        );
+        for (address, amount) in &self.accounts {
+            let mut amount = amount.clone();
+            amount.sort_by(|a, b| a.denom.cmp(&b.denom));
+            let coins: Vec<String> = amount.iter().map(ToString::to_string).collect();
+            script.push_str(&format!("add_account {} {}\n", address, coins.join(",")));
+        }
+        script.push_str(&format!(
+            r#"$BIN genesis gentx validator 1000000000{denom} --chain-id {chain_id} $KEYS || $BIN gentx validator 1000000000{denom} --chain-id {chain_id} $KEYS
+$BIN genesis collect-gentxs --home $HOME_DIR || $BIN collect-gentxs --home $HOME_DIR
+exec $BIN start --home $HOME_DIR --rpc.laddr tcp://0.0.0.0:{CONTAINER_RPC_PORT} --grpc.address 0.0.0.0:{CONTAINER_GRPC_PORT} --minimum-gas-prices 0{denom}
+"#
+        ));
+        script
+    }
+
+    /// Starts the container and waits for the first block
+    pub fn start(self) -> Result<RunningDevnet, DaemonError> {
+        let mnemonic = self.deployer_mnemonic();
+        log::info!(
+            "Starting devnet {} ({}) in container {}",
+            self.chain.chain_id,
+            self.image.image(),
+            self.container_name
+        );
+        docker(&[
+            "run",
+            "-d",
+            "--rm",
+            "--name",
+            &self.container_name,
+            "-p",
+            &format!("{}:{CONTAINER_GRPC_PORT}", self.grpc_port),
+            "-p",
+            &format!("{}:{CONTAINER_RPC_PORT}", self.rpc_port),
+            "-e",
+            &format!("DEVNET_MNEMONIC={mnemonic}"),
+            "--entrypoint",
+            "sh",
+            self.image.image(),
+            "-c",
+            &self.setup_script(),
+        ])?;
+
+        let devnet = RunningDevnet {
+            container_name: self.container_name.clone(),
+            chain: self.chain_info(),
+            mnemonic,
+            keep_running: self.keep_running,
+        };
+        devnet.wait_for_first_block(self.startup_timeout)?;
+        Ok(devnet)
+    }
+}
+
+/// Devnet running in a Docker container, removed when dropped unless [`Devnet::keep_running`] is set
+#[derive(Debug)]
+pub struct RunningDevnet {
+    container_name: String,
+    chain: ChainInfoOwned,
+    mnemonic: String,
+    keep_running: bool,
+}
+
+impl RunningDevnet {
+    /// Chain info of the devnet
+    pub fn chain_info(&self) -> &ChainInfoOwned {
+        &self.chain
+    }
+
+    /// Name of the container running the devnet
+    pub fn container_name(&self) -> &str {
+        &self.container_name
+    }
+
+    /// Daemon sending transactions from the deployer funded at genesis
+    pub fn daemon(&self) -> Result<Daemon, DaemonError> {
+        Daemon::builder()
+            .chain(self.chain.clone())
+            .mnemonic(&self.mnemonic)
+            .build()
+    }
+
+    /// Stops and removes the container
+    pub fn stop(&self) -> Result<(), DaemonError> {
+        log::info!("Stopping devnet container {}", self.container_name);
+        docker(&["rm", "-f", &self.container_name])?;
+        Ok(())
+    }
+
+    fn wait_for_first_block(&self, timeout: Duration) -> Result<(), DaemonError> {
+        let start = Instant::now();
+        loop {
+            let height = self
+                .daemon()
+                .and_then(|daemon| Ok(daemon.block_info()?.height));
+            match height {
+                Ok(height) if height > 0 => {
+                    log::info!("Devnet {} is live", self.chain.chain_id);
+                    return Ok(());
+                }
+                Ok(_) => {}
+                Err(e) => log::debug!("Devnet {} not live yet: {}", self.chain.chain_id, e),
+            }
+            if start.elapsed() > timeout {
+                let logs =
+                    docker(&["logs", "--tail", "20", &self.container_name]).unwrap_or_default();
+                return Err(DaemonError::Devnet(format!(
+                    "{} didn't produce a block after {:?}, last logs:\n{}",
+                    self.chain.chain_id, timeout, logs
+                )));
+            }
+            sleep(Duration::from_secs(1));
+        }
+    }
+}
+
+impl Drop for RunningDevnet {
+    fn drop(&mut self) {
+        if !self.keep_running {
+            if let Err(e) = self.stop() {
+                log::warn!(
+                    "Couldn't stop devnet container {}: {}",
+                    self.container_name,
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Runs a docker command, returning its stdout
+fn docker(args: &[&str]) -> Result<String, DaemonError> {
+    let output = Command::new("docker")
+        .args(args)
+        .output()
+        .map_err(|e| DaemonError::Devnet(format!("couldn't run docker: {e}")))?;
+    if !output.status.success() {
+        return Err(DaemonError::Devnet(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::coin;
+
+    use super::*;
+    use crate::networks::LOCAL_JUNO;
+
+    #[test]
+    fn setup_script_funds_accounts() {
+        let devnet = Devnet::new(LOCAL_JUNO, DevnetImage::Juno)
+            .fund("juno1alice", vec![coin(10, "ujunox"), coin(5, "uatom")])
+            .grpc_port(9091);
+        let script = devnet.setup_script();
+
+        assert!(script.starts_with("set -e\nBIN=junod\n"));
+        assert!(script.contains("--chain-id testing"));
+        assert!(script.contains("add_account juno1alice 5uatom,10ujunox\n"));
+        assert!(script.contains("--minimum-gas-prices 0ujunox"));
+        assert_eq!(
+            devnet.chain_info().grpc_urls,
+            vec!["http://localhost:9091".to_string()]
+        );
+    }
+}
@@ -0,0 +1,483 @@
+//! Emergency pause toolkit.
+//!
+//! Pauses or unpauses a set of contracts, on one or many chains, and verifies their paused state afterwards.
+//! Contracts need to implement the standard pause interface:
+//! - `{"pause":{}}` and `{"unpause":{}}` execute messages
+//! - a `{"paused":{}}` query that returns `{"paused": <bool>}`
+//!
+//! Chains are handled concurrently. Contracts on the same chain are handled sequentially to avoid account sequence mismatches.
+//! A failure on one contract doesn't stop the others, all outcomes are collected inside an [`EmergencyReport`].
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_core::contract::Contract;
+//! use cw_orch_daemon::{emergency::EmergencyToolkit, Daemon, networks};
+//!
+//! let daemon = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//! let toolkit = EmergencyToolkit::new(vec![Contract::new("vault", daemon.clone())]);
+//!
+//! // Check what would happen without broadcasting anything
+//! println!("{}", toolkit.clone().dry_run().pause());
+//!
+//! let report = toolkit.pause();
+//! assert!(report.is_success(), "{}", report);
+//! ```
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use cosmwasm_std::Addr;
+use cw_orch_core::{
+    contract::Contract,
+    environment::{AsyncTxHandler, AsyncWasmQuerier, IndexResponse},
+    CwEnvError,
+};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+
+use crate::Daemon;
+
+/// Execute messages of the standard pause interface
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseExecuteMsg {
+    /// Pauses the contract
+    Pause {},
+    /// Unpauses the contract
+    Unpause {},
+}
+
+/// Query messages of the standard pause interface
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseQueryMsg {
+    /// Returns a [`PausedResponse`]
+    Paused {},
+}
+
+/// Response to [`PauseQueryMsg::Paused`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PausedResponse {
+    /// Whether the contract is paused
+    pub paused: bool,
+}
+
+/// Action applied by the [`EmergencyToolkit`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmergencyAction {
+    /// Pause the contracts
+    Pause,
+    /// Unpause the contracts
+    Unpause,
+}
+
+impl EmergencyAction {
+    /// Paused state the contracts need to be in after the action
+    pub fn target_paused(&self) -> bool {
+        matches!(self, EmergencyAction::Pause)
+    }
+
+    fn msg(&self) -> PauseExecuteMsg {
+        match self {
+            EmergencyAction::Pause => PauseExecuteMsg::Pause {},
+            EmergencyAction::Unpause => PauseExecuteMsg::Unpause {},
+        }
+    }
+}
+
+impl Display for EmergencyAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmergencyAction::Pause => write!(f, "pause"),
+            EmergencyAction::Unpause => write!(f, "unpause"),
+        }
+    }
+}
+
+/// Pauses and unpauses contracts across chains
+#[derive(Clone)]
+pub struct EmergencyToolkit {
+    contracts: Vec<Contract<Daemon>>,
+    dry_run: bool,
+}
+
+impl EmergencyToolkit {
+    /// Creates a toolkit acting on the given contracts.
+    /// The contracts can be deployed on different chains
+    pub fn new(contracts: Vec<Contract<Daemon>>) -> Self {
+        Self {
+            contracts,
+            dry_run: false,
+        }
+    }
+
+    /// Only query the current paused state of the contracts, without broadcasting any transaction
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Pauses all the contracts and verifies they are paused
+    pub fn pause(&self) -> EmergencyReport {
+        self.run(EmergencyAction::Pause)
+    }
+
+    /// Unpauses all the contracts and verifies they are not paused anymore
+    pub fn unpause(&self) -> EmergencyReport {
+        self.run(EmergencyAction::Unpause)
+    }
+
+    /// Queries the paused state of all the contracts
+    pub fn status(&self) -> EmergencyReport {
+        self.clone().dry_run().run(EmergencyAction::Pause)
+    }
+
+    fn run(&self, action: EmergencyAction) -> EmergencyReport {
+        let Some(first) = self.contracts.first() else {
+            return EmergencyReport {
+                action,
+                dry_run: self.dry_run,
+                entries: vec![],
+            };
+        };
+
+        // Contracts are grouped by chain, chains are handled concurrently
+        let mut per_chain: BTreeMap<String, Vec<&Contract<Daemon>>> = BTreeMap::new();
+        for contract in &self.contracts {
+            let chain_id = &contract.get_chain().daemon.state.chain_data.chain_id;
+            per_chain
+                .entry(chain_id.to_string())
+                .or_default()
+                .push(contract);
+        }
+
+        let entries = first
+            .get_chain()
+            .rt_handle
+            .block_on(join_all(per_chain.into_iter().map(
+                |(chain_id, contracts)| async move {
+                    let mut entries = vec![];
+                    for contract in contracts {
+                        entries.push(self.contract_action(&chain_id, contract, action).await);
+                    }
+                    entries
+                },
+            )));
+
+        EmergencyReport {
+            action,
+            dry_run: self.dry_run,
+            entries: entries.into_iter().flatten().collect(),
+        }
+    }
+
+    async fn contract_action(
+        &self,
+        chain_id: &str,
+        contract: &Contract<Daemon>,
+        action: EmergencyAction,
+    ) -> EmergencyReportEntry {
+        action_entry(
+            &contract.get_chain().daemon,
+            chain_id,
+            &contract.id,
+            contract.address(),
+            action,
+            self.dry_run,
+        )
+        .await
+    }
+}
+
+/// Applies `action` on the contract at `address` and reports the outcome, errors are recorded in the entry
+async fn action_entry<Chain: AsyncTxHandler>(
+    chain: &Chain,
+    chain_id: &str,
+    contract_id: &str,
+    address: Result<Addr, CwEnvError>,
+    action: EmergencyAction,
+    dry_run: bool,
+) -> EmergencyReportEntry {
+    let mut entry = EmergencyReportEntry {
+        chain_id: chain_id.to_string(),
+        contract_id: contract_id.to_string(),
+        paused_before: None,
+        tx_hash: None,
+        paused_after: None,
+        error: None,
+    };
+
+    let result = match address {
+        Ok(address) => apply(chain, &address, action, dry_run, &mut entry).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::error!("[{}][{}] {} failed: {}", chain_id, contract_id, action, e);
+        entry.error = Some(e.to_string());
+    }
+    entry
+}
+
+async fn apply<Chain: AsyncTxHandler>(
+    chain: &Chain,
+    address: &Addr,
+    action: EmergencyAction,
+    dry_run: bool,
+    entry: &mut EmergencyReportEntry,
+) -> Result<(), CwEnvError> {
+    let paused_before = paused(chain, address).await?;
+    entry.paused_before = Some(paused_before);
+    if dry_run || paused_before == action.target_paused() {
+        return Ok(());
+    }
+
+    let response = chain
+        .async_execute(&action.msg(), &[], address)
+        .await
+        .map_err(Into::into)?;
+    entry.tx_hash = response.tx_hash();
+    entry.paused_after = Some(paused(chain, address).await?);
+    Ok(())
+}
+
+async fn paused<Chain: AsyncWasmQuerier>(
+    chain: &Chain,
+    address: &Addr,
+) -> Result<bool, CwEnvError> {
+    let response: PausedResponse = chain
+        .async_query(&PauseQueryMsg::Paused {}, address)
+        .await
+        .map_err(Into::into)?;
+    Ok(response.paused)
+}
+
+/// Outcome of an [`EmergencyAction`] on a single contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmergencyReportEntry {
+    /// Chain of the contract
+    pub chain_id: String,
+    /// Id of the contract
+    pub contract_id: String,
+    /// Paused state before the action, if it could be queried
+    pub paused_before: Option<bool>,
+    /// Hash of the pause/unpause transaction, if one was broadcasted and the environment reports it
+    pub tx_hash: Option<String>,
+    /// Paused state after the transaction, if one was broadcasted
+    pub paused_after: Option<bool>,
+    /// Error encountered while handling the contract
+    pub error: Option<String>,
+}
+
+impl EmergencyReportEntry {
+    /// Paused state the contract is known to be in
+    pub fn paused(&self) -> Option<bool> {
+        self.paused_after.or(self.paused_before)
+    }
+}
+
+/// Outcome of an [`EmergencyAction`] on all contracts of an [`EmergencyToolkit`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmergencyReport {
+    /// Action that was applied
+    pub action: EmergencyAction,
+    /// Whether transactions were broadcasted
+    pub dry_run: bool,
+    /// Outcome per contract
+    pub entries: Vec<EmergencyReportEntry>,
+}
+
+impl EmergencyReport {
+    /// Contracts that errored or that are not in the expected paused state
+    /// In dry-run mode, only errors are reported
+    pub fn failures(&self) -> Vec<&EmergencyReportEntry> {
+        let target = self.action.target_paused();
+        self.entries
+            .iter()
+            .filter(|e| e.error.is_some() || (!self.dry_run && e.paused() != Some(target)))
+            .collect()
+    }
+
+    /// Returns true if all contracts are in the expected paused state
+    pub fn is_success(&self) -> bool {
+        self.failures().is_empty()
+    }
+}
+
+impl Display for EmergencyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Emergency {}{}:",
+            self.action,
+            if self.dry_run { " (dry-run)" } else { "" }
+        )?;
+        for entry in &self.entries {
+            write!(f, "  [{}][{}] ", entry.chain_id, entry.contract_id)?;
+            if let Some(error) = &entry.error {
+                writeln!(f, "error: {}", error)?;
+                continue;
+            }
+            match (entry.paused_before, entry.paused_after) {
+                (Some(before), Some(after)) => {
+                    write!(f, "paused: {} -> {}", before, after)?;
+                    match &entry.tx_hash {
+                        Some(tx_hash) => writeln!(f, " (tx {})", tx_hash)?,
+                        None => writeln!(f)?,
+                    }
+                }
+                (Some(paused), None) => writeln!(f, "paused: {}", paused)?,
+                _ => writeln!(f, "unknown state")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        from_json, to_json_binary, to_json_vec, Binary, Deps, DepsMut, Empty, Env, MessageInfo,
+        Response, StdResult,
+    };
+    use cw_orch_core::environment::TxHandler;
+    use cw_orch_mock::{cw_multi_test::ContractWrapper, Mock};
+    use futures::executor::block_on;
+
+    use super::*;
+
+    const PAUSED_KEY: &[u8] = b"paused";
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: PauseExecuteMsg,
+    ) -> StdResult<Response> {
+        let paused = matches!(msg, PauseExecuteMsg::Pause {});
+        deps.storage.set(PAUSED_KEY, &to_json_vec(&paused)?);
+        Ok(Response::new())
+    }
+
+    /// Accepts the pause messages but never changes its paused state
+    fn execute_ignored(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: PauseExecuteMsg,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: PauseQueryMsg) -> StdResult<Binary> {
+        let paused = match deps.storage.get(PAUSED_KEY) {
+            Some(paused) => from_json(paused)?,
+            None => false,
+        };
+        to_json_binary(&PausedResponse { paused })
+    }
+
+    fn pausable(chain: &Mock, ignore_pause: bool) -> anyhow::Result<Addr> {
+        let execute_fn = if ignore_pause {
+            execute_ignored
+        } else {
+            execute
+        };
+        let code = chain.upload_custom(
+            "pausable",
+            Box::new(ContractWrapper::new(execute_fn, instantiate, query)),
+        )?;
+        let response = chain.instantiate(code.uploaded_code_id()?, &Empty {}, None, None, &[])?;
+        Ok(response.instantiated_contract_address()?)
+    }
+
+    fn entry(
+        chain: &Mock,
+        address: &Addr,
+        action: EmergencyAction,
+        dry_run: bool,
+    ) -> EmergencyReportEntry {
+        block_on(action_entry(
+            chain,
+            "mock-1",
+            "vault",
+            Ok(address.clone()),
+            action,
+            dry_run,
+        ))
+    }
+
+    #[test]
+    fn pause_interface_serialization() {
+        assert_eq!(
+            serde_json::to_string(&PauseExecuteMsg::Pause {}).unwrap(),
+            r#"{"pause":{}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&PauseQueryMsg::Paused {}).unwrap(),
+            r#"{"paused":{}}"#
+        );
+    }
+
+    #[test]
+    fn pause_contracts() -> anyhow::Result<()> {
+        let chain = Mock::new("sender");
+        let vault = pausable(&chain, false)?;
+        let stubborn = pausable(&chain, true)?;
+
+        // Dry runs only report the current state
+        let dry_run = entry(&chain, &vault, EmergencyAction::Pause, true);
+        assert_eq!(dry_run.paused(), Some(false));
+        assert_eq!(dry_run.paused_after, None);
+        let report = EmergencyReport {
+            action: EmergencyAction::Pause,
+            dry_run: true,
+            entries: vec![dry_run],
+        };
+        assert!(report.is_success());
+
+        let paused = entry(&chain, &vault, EmergencyAction::Pause, false);
+        assert_eq!(
+            (paused.paused_before, paused.paused_after),
+            (Some(false), Some(true))
+        );
+        // Already paused contracts are left untouched
+        let already_paused = entry(&chain, &vault, EmergencyAction::Pause, false);
+        assert_eq!(already_paused.paused_after, None);
+        let report = EmergencyReport {
+            action: EmergencyAction::Pause,
+            dry_run: false,
+            entries: vec![paused, already_paused],
+        };
+        assert!(report.is_success());
+        assert!(report.to_string().contains("paused: false -> true"));
+
+        // A contract that stays unpaused and a contract that can't be queried are failures
+        let stubborn = entry(&chain, &stubborn, EmergencyAction::Pause, false);
+        assert_eq!(stubborn.paused_after, Some(false));
+        let missing = entry(
+            &chain,
+            &Addr::unchecked("missing"),
+            EmergencyAction::Pause,
+            false,
+        );
+        assert!(missing.error.is_some());
+        let report = EmergencyReport {
+            action: EmergencyAction::Pause,
+            dry_run: false,
+            entries: vec![stubborn, missing],
+        };
+        assert_eq!(report.failures().len(), 2);
+
+        let unpaused = entry(&chain, &vault, EmergencyAction::Unpause, false);
+        assert_eq!(unpaused.paused(), Some(false));
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 //! This regroups all env variables used by cw-orch-daemon. It allows for easier documentation and env variable management
 //! This is used to import environment variables with safe names (and at a centralized location)
+//! Settings not set in the env are read from the config file, see [`crate::config`]
 //! To get the env variable parsed value, you can use
 //! ```rust,no_run
 //! use cw_orch_daemon::env::DaemonEnvVars;
@@ -8,6 +9,7 @@
 
 use std::{env, path::PathBuf, str::FromStr};
 
+use crate::{config::DaemonConfig, log::LogFormat};
 use cosmwasm_std::StdError;
 use regex::Regex;
 use std::time::Duration;
@@ -21,6 +23,10 @@ pub const MAX_TX_QUERIES_RETRY_ENV_NAME: &str = "CW_ORCH_MAX_TX_QUERY_RETRIES";
 pub const MIN_BLOCK_SPEED_ENV_NAME: &str = "CW_ORCH_MIN_BLOCK_SPEED";
 pub const WALLET_BALANCE_ASSERTION_ENV_NAME: &str = "CW_ORCH_WALLET_BALANCE_ASSERTION";
 pub const LOGS_ACTIVATION_MESSAGE_ENV_NAME: &str = "CW_ORCH_LOGS_ACTIVATION_MESSAGE";
+pub const RUNTIME_WORKER_THREADS_ENV_NAME: &str = "CW_ORCH_RUNTIME_WORKER_THREADS";
+pub const LOG_FORMAT_ENV_NAME: &str = "CW_ORCH_LOG_FORMAT";
+pub const CHAIN_LOG_DIR_ENV_NAME: &str = "CW_ORCH_CHAIN_LOG_DIR";
+pub const NETWORKS_FILE_ENV_NAME: &str = "CW_ORCH_NETWORKS_FILE";
 
 pub const MAIN_MNEMONIC_ENV_NAME: &str = "MAIN_MNEMONIC";
 pub const TEST_MNEMONIC_ENV_NAME: &str = "TEST_MNEMONIC";
@@ -35,7 +41,10 @@ impl DaemonEnvVars {
     /// `/usr/var/file.json` will resolve to `/usr/var/file.json`
     /// Defaults to "~./cw-orchestrator/state.json"
     pub fn state_file() -> PathBuf {
-        let state_file_string = env::var(STATE_FILE_ENV_NAME).unwrap_or("state.json".to_string());
+        let state_file_string = env::var(STATE_FILE_ENV_NAME)
+            .ok()
+            .or_else(|| DaemonConfig::file_layer().state_file)
+            .unwrap_or("state.json".to_string());
         parse_with_log(state_file_string, STATE_FILE_ENV_NAME)
     }
 
@@ -43,40 +52,34 @@ impl DaemonEnvVars {
     /// This allows changing the gas buffer applied after tx simulation
     /// If not specified, a more complex algorithm is applied for dealing with small gas fee cases
     pub fn gas_buffer() -> Option<f64> {
-        if let Ok(str_value) = env::var(GAS_BUFFER_ENV_NAME) {
-            Some(parse_with_log(str_value, GAS_BUFFER_ENV_NAME))
-        } else {
-            None
-        }
+        env_or_file(GAS_BUFFER_ENV_NAME, |config| config.gas_buffer)
     }
 
     /// Optional - Integer
     /// Defaults to None
     /// Minimum gas amount. Useful when transaction still won't pass even when setting a high gas_buffer or for mixed transaction scripts
     pub fn min_gas() -> Option<u64> {
-        if let Ok(str_value) = env::var(MIN_GAS_ENV_NAME) {
-            Some(parse_with_log(str_value, MIN_GAS_ENV_NAME))
-        } else {
-            None
-        }
+        env_or_file(MIN_GAS_ENV_NAME, |config| config.min_gas)
     }
 
     /// Optional - Integer
     /// Defaults to [`DEFAULT_TX_QUERY_RETRIES`]
     /// This changes the number of tx queries before it fails if it doesn't find any result
     pub fn max_tx_query_retries() -> usize {
-        if let Ok(str_value) = env::var(MAX_TX_QUERIES_RETRY_ENV_NAME) {
-            parse_with_log(str_value, MAX_TX_QUERIES_RETRY_ENV_NAME)
-        } else {
-            DEFAULT_TX_QUERY_RETRIES
-        }
+        env_or_file(MAX_TX_QUERIES_RETRY_ENV_NAME, |config| {
+            config.max_tx_query_retries
+        })
+        .unwrap_or(DEFAULT_TX_QUERY_RETRIES)
     }
 
     /// Optional - Integer
     /// Defaults to 1
     /// Minimum block speed in milliseconds. Useful when the block speeds are varying a lot
     pub fn min_block_speed() -> Duration {
-        if let Ok(str_value) = env::var(MIN_BLOCK_SPEED_ENV_NAME) {
+        if let Some(str_value) = env::var(MIN_BLOCK_SPEED_ENV_NAME)
+            .ok()
+            .or_else(|| DaemonConfig::file_layer().min_block_speed)
+        {
             let ms_re = Regex::new(r"(\d+)ms").unwrap();
             let s_re = Regex::new(r"(\d+)s").unwrap();
 
@@ -111,11 +114,10 @@ impl DaemonEnvVars {
     /// Disable wallet balance assertion.
     /// When balance assertion is enabled, it asserts that the balance of the sender is sufficient before submitting any transactions (during the simulation step)
     pub fn wallet_balance_assertion() -> bool {
-        if let Ok(str_value) = env::var(WALLET_BALANCE_ASSERTION_ENV_NAME) {
-            parse_with_log(str_value, WALLET_BALANCE_ASSERTION_ENV_NAME)
-        } else {
-            true
-        }
+        env_or_file(WALLET_BALANCE_ASSERTION_ENV_NAME, |config| {
+            config.wallet_balance_assertion
+        })
+        .unwrap_or(true)
     }
 
     /// Optional - boolean
@@ -123,11 +125,55 @@ impl DaemonEnvVars {
     /// Disable the "Enable Logs" message
     /// It allows forcing cw-orch to not output anything
     pub fn logs_message() -> bool {
-        if let Ok(str_value) = env::var(LOGS_ACTIVATION_MESSAGE_ENV_NAME) {
-            parse_with_log(str_value, LOGS_ACTIVATION_MESSAGE_ENV_NAME)
-        } else {
-            true
-        }
+        env_or_file(LOGS_ACTIVATION_MESSAGE_ENV_NAME, |config| {
+            config.logs_message
+        })
+        .unwrap_or(true)
+    }
+
+    /// Optional - Integer
+    /// Defaults to the number of cores
+    /// Number of worker threads of the global runtime used by the synchronous daemon
+    pub fn runtime_worker_threads() -> Option<usize> {
+        env_or_file(RUNTIME_WORKER_THREADS_ENV_NAME, |config| {
+            config.runtime_worker_threads
+        })
+    }
+
+    /// Optional - `text` or `json`
+    /// Defaults to "text"
+    /// Output format of the [`DaemonLogger`](crate::log::DaemonLogger) built from the env
+    pub fn log_format() -> LogFormat {
+        env::var(LOG_FORMAT_ENV_NAME)
+            .ok()
+            .or_else(|| DaemonConfig::file_layer().log_format)
+            .map(|str_value| parse_with_log(str_value, LOG_FORMAT_ENV_NAME))
+            .unwrap_or(LogFormat::Text)
+    }
+
+    /// Optional - Path
+    /// Defaults to None
+    /// Directory the [`DaemonLogger`](crate::log::DaemonLogger) built from the env writes the per-chain transaction logs to
+    pub fn chain_log_dir() -> Option<PathBuf> {
+        env::var(CHAIN_LOG_DIR_ENV_NAME)
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| DaemonConfig::file_layer().chain_log_dir)
+    }
+
+    /// Optional - Path
+    /// Defaults to "~/.cw-orchestrator/networks.toml"
+    /// File of the user defined networks, see [`crate::network_config`]
+    pub fn networks_file() -> Option<PathBuf> {
+        env::var(NETWORKS_FILE_ENV_NAME)
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| DaemonConfig::file_layer().networks_file)
+            .or_else(|| {
+                default_state_folder()
+                    .ok()
+                    .map(|folder| folder.join("networks.toml"))
+            })
     }
 
     /// Optional - String
@@ -165,6 +211,17 @@ pub fn default_state_folder() -> Result<PathBuf, StdError> {
             )))
 }
 
+/// Value of the env variable, or of the config file if the env variable isn't set
+fn env_or_file<F: FromStr<Err = E>, E: std::fmt::Display>(
+    env_var_name: &str,
+    file_value: impl FnOnce(DaemonConfig) -> Option<F>,
+) -> Option<F> {
+    match env::var(env_var_name) {
+        Ok(str_value) => Some(parse_with_log(str_value, env_var_name)),
+        Err(_) => file_value(DaemonConfig::file_layer()),
+    }
+}
+
 fn parse_with_log<F: FromStr<Err = E>, E: std::fmt::Display>(
     value: String,
     env_var_name: &str,
@@ -2,6 +2,7 @@
 
 use cosmwasm_std::{Coin, Instantiate2AddressError};
 use cw_orch_core::CwEnvError;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -44,6 +45,14 @@ pub enum DaemonError {
     Phrasing,
     #[error("Mnemonic - Missing Phrase")]
     MissingPhrase,
+    #[error(
+        "Invalid HD derivation path `{0}`, expected m/purpose'/coin_type'/account/change/index"
+    )]
+    InvalidHdPath(String),
+    #[error("Invalid armored private key: {0}")]
+    InvalidArmor(String),
+    #[error("Unable to decrypt the armored private key, wrong passphrase")]
+    ArmorDecryption,
     #[error("Bad Implementation. Missing Component")]
     Implementation,
     #[error("Unable to convert into public key `{key}`")]
@@ -118,14 +127,55 @@ pub enum DaemonError {
     NotEnoughBalance { expected: Coin, current: Coin },
     #[error("Can't set the daemon state, it's read-only {0}")]
     StateReadOnly(String),
+    #[error("No checkpoint {label} in {path}")]
+    CheckpointNotFound { label: String, path: String },
+    #[error("Deployment id {0} is reserved by the state file, use another deployment id")]
+    ReservedDeploymentId(String),
     #[error("You need to pass a runtime to the querier object to do synchronous queries. Use daemon.querier instead")]
     QuerierNeedRuntime,
+    #[error("The global runtime was already initialized, configure it before using any daemon")]
+    RuntimeAlreadyInitialized,
+    #[error("The sudo entry point of {0} can't be called directly on a live chain, submit a governance proposal with a MsgSudoContract instead")]
+    SudoNotSupported(String),
+    #[error("Invalid airdrop: {0}")]
+    InvalidAirdrop(String),
     #[error(transparent)]
     Instantiate2Error(#[from] Instantiate2AddressError),
     #[error("Error opening file {0},err: ({1})")]
     OpenFile(String, String),
     #[error("State file {0} already locked, use another state file, clone daemon which holds the lock, or use `state` method of Builder")]
     StateAlreadyLocked(String),
+    #[error("State file {0} is locked by another process, wait for it to finish, set a lock timeout on the Builder or use a read-only state")]
+    StateLocked(String),
+    #[cfg(feature = "keyring")]
+    #[error(transparent)]
+    Keyring(#[from] ::keyring::Error),
+    #[error("No mnemonic named {name} in the {service} keyring")]
+    KeyringEntryNotFound { service: String, name: String },
+    #[error("Address {address} doesn't have the {expected} prefix of the chain")]
+    WrongAddressPrefix { address: String, expected: String },
+    #[error("Daemon built for chain {found}, expected {expected}")]
+    UnexpectedChain { expected: String, found: String },
+    #[error("Smart query of {address} ran out of gas on the node ({message}). The limit is set by the node operator with `query_gas_limit` in the `[wasm]` section of app.toml, paginate the query or use a node with a higher limit")]
+    QueryOutOfGas { address: String, message: String },
+    #[error("Smart query of {address} timed out after {timeout:?}. Increase the timeout with `SmartQueryOptions::timeout` or paginate the query")]
+    QueryTimeout { address: String, timeout: Duration },
+    #[error("Devnet error: {0}")]
+    Devnet(String),
+    #[error("Chain {0} has no tendermint RPC url, set `rpc_url` in its `ChainInfo`")]
+    MissingRpcUrl(String),
+    #[error("Invalid cw-orch config: {0}")]
+    Config(String),
+    #[error("Address book entry {name} of {chain_id} isn't a {expected}: {found}")]
+    AddressBookEntry {
+        name: String,
+        chain_id: String,
+        expected: String,
+        found: String,
+    },
+    #[cfg(feature = "rpc")]
+    #[error(transparent)]
+    TendermintRpc(#[from] ::cosmrs::rpc::Error),
 }
 
 impl DaemonError {
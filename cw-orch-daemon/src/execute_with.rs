@@ -0,0 +1,61 @@
+//! Contract executions with per-call transaction options.
+//!
+//! ```no_run
+//! use cw_orch_core::CwEnvError;
+//! use cw_orch_daemon::{execute_with::ExecuteWithOptions, TxOptions};
+//!
+//! fn execute_proposal<C: ExecuteWithOptions>(
+//!     contract: &C,
+//!     msg: &C::ExecuteMsg,
+//! ) -> Result<(), CwEnvError> {
+//!     contract.execute_with(
+//!         msg,
+//!         &[],
+//!         TxOptions::default()
+//!             .memo("Proposal #12 execution")
+//!             .timeout_height(12_345_678),
+//!     )?;
+//!     Ok(())
+//! }
+//! ```
+
+use cosmwasm_std::Coin;
+use cw_orch_core::{
+    contract::interface_traits::{ContractInstance, ExecutableContract},
+    log::contract_target,
+    CwEnvError,
+};
+
+use crate::{tx_builder::TxOptions, CosmTxResponse, Daemon};
+
+/// Executes contracts with a custom memo and timeout height, see [`TxOptions`]
+pub trait ExecuteWithOptions: ExecutableContract + ContractInstance<Daemon> {
+    /// Send a ExecuteMsg to the contract with the given transaction options.
+    fn execute_with(
+        &self,
+        execute_msg: &Self::ExecuteMsg,
+        coins: &[Coin],
+        options: TxOptions,
+    ) -> Result<CosmTxResponse, CwEnvError> {
+        log::info!(
+            target: &contract_target(),
+            "[{}][Execute][{}] {}",
+            self.id(),
+            self.address()?,
+            log_serialize_message(execute_msg)?
+        );
+        let response = self.get_chain().execute_with_options(
+            execute_msg,
+            coins,
+            &self.address()?,
+            &options,
+        )?;
+        Ok(response)
+    }
+}
+
+impl<T: ExecutableContract + ContractInstance<Daemon>> ExecuteWithOptions for T {}
+
+fn log_serialize_message<E: serde::Serialize>(msg: &E) -> Result<String, CwEnvError> {
+    Ok(serde_json::to_string(msg)?)
+}
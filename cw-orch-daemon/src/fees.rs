@@ -0,0 +1,200 @@
+//! Fee tokens and dynamic gas prices.
+//!
+//! By default, fees are paid in the gas denom of the chain at its fixed gas price.
+//! - [`FeeToken`]s let the sender pay fees in other denoms: the first token the sender holds enough of is used.
+//! - A [`GasPriceOracle`] fetches the current gas price before every transaction, so transactions don't fail during fee spikes.
+//!   The fetched price is only used when it's higher than the configured one.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{fees::{FeeToken, GasPriceOracle}, networks, DaemonBuilder};
+//!
+//! let daemon = DaemonBuilder::default()
+//!     .chain(networks::OSMOSIS_1)
+//!     .fee_tokens(vec![
+//!         FeeToken::new("uosmo", 0.0025),
+//!         FeeToken::new("ibc/D189335C6E4A68B513C10AB227BF1C1D38C746766278BA3EEB4FB14124F1D858", 0.003),
+//!     ])
+//!     .gas_price_oracle(GasPriceOracle::osmosis_base_fee().multiplier(1.2))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use serde_json::Value;
+use tonic::transport::Channel;
+
+use crate::{
+    proto::txfees::{QueryEipBaseFeeRequest, QueryEipBaseFeeResponse},
+    queriers::raw_grpc_query,
+    DaemonError,
+};
+
+/// One, as a cosmos-sdk decimal in its proto representation (18 decimals)
+const SDK_DEC_ONE: f64 = 1e18;
+
+/// Denom fees can be paid in, with its gas price
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeToken {
+    /// Denom of the fees
+    pub denom: String,
+    /// Gas price in this denom
+    pub gas_price: f64,
+}
+
+impl FeeToken {
+    /// Fee token paying `gas_price` of `denom` per unit of gas
+    pub fn new(denom: impl Into<String>, gas_price: f64) -> Self {
+        Self {
+            denom: denom.into(),
+            gas_price,
+        }
+    }
+}
+
+/// Source of dynamic gas prices
+#[derive(Debug, Clone, PartialEq)]
+pub enum GasPriceSource {
+    /// Base fee of the Osmosis EIP-1559 fee market (`/osmosis.txfees.v1beta1.Query/GetEipBaseFee`).
+    /// Only applies to the gas denom of the chain
+    OsmosisBaseFee,
+    /// Json HTTP API (e.g. Skip's gas price API).
+    /// `{denom}` in the url is replaced by the fee denom and the price is read at `pointer` (json pointer, e.g. `/price`)
+    Http {
+        /// Url of the API
+        url: String,
+        /// Json pointer to the gas price in the response, as a number or a string
+        pointer: String,
+    },
+}
+
+/// Fetches gas prices before every transaction, see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasPriceOracle {
+    /// Where the gas price is fetched from
+    pub source: GasPriceSource,
+    /// Multiplier applied to the fetched gas price, to absorb price movements. Defaults to 1.0
+    pub multiplier: f64,
+    /// Maximum gas price used, to protect against abnormal prices
+    pub max_gas_price: Option<f64>,
+}
+
+impl GasPriceOracle {
+    /// Oracle fetching gas prices from `source`
+    pub fn new(source: GasPriceSource) -> Self {
+        Self {
+            source,
+            multiplier: 1.0,
+            max_gas_price: None,
+        }
+    }
+
+    /// Oracle using the Osmosis EIP-1559 base fee
+    pub fn osmosis_base_fee() -> Self {
+        Self::new(GasPriceSource::OsmosisBaseFee)
+    }
+
+    /// Oracle using a json HTTP API, see [`GasPriceSource::Http`]
+    pub fn http(url: impl Into<String>, pointer: impl Into<String>) -> Self {
+        Self::new(GasPriceSource::Http {
+            url: url.into(),
+            pointer: pointer.into(),
+        })
+    }
+
+    /// Sets the multiplier applied to the fetched gas price
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the maximum gas price used
+    pub fn max_gas_price(mut self, max_gas_price: f64) -> Self {
+        self.max_gas_price = Some(max_gas_price);
+        self
+    }
+
+    /// Fetches the gas price of `denom`, multiplied and capped.
+    /// Returns `None` if the source doesn't price this denom
+    pub async fn gas_price(
+        &self,
+        channel: Channel,
+        denom: &str,
+        chain_gas_denom: &str,
+    ) -> Result<Option<f64>, DaemonError> {
+        let price = match &self.source {
+            GasPriceSource::OsmosisBaseFee => {
+                if denom != chain_gas_denom {
+                    return Ok(None);
+                }
+                let response: QueryEipBaseFeeResponse = raw_grpc_query(
+                    channel,
+                    "/osmosis.txfees.v1beta1.Query/GetEipBaseFee",
+                    &QueryEipBaseFeeRequest {},
+                )
+                .await?;
+                parse_sdk_dec(&response.base_fee)?
+            }
+            GasPriceSource::Http { url, pointer } => {
+                let response: Value = reqwest::get(url.replace("{denom}", denom))
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                parse_json_price(&response, pointer)?
+            }
+        };
+        let price = price * self.multiplier;
+        Ok(Some(match self.max_gas_price {
+            Some(max) => price.min(max),
+            None => price,
+        }))
+    }
+}
+
+/// Parses a cosmos-sdk decimal, either in its human readable (`0.0025`) or its proto (`2500000000000000`) representation
+fn parse_sdk_dec(dec: &str) -> Result<f64, DaemonError> {
+    let value: f64 = dec
+        .parse()
+        .map_err(|_| DaemonError::StdErr(format!("Invalid gas price {dec}")))?;
+    if dec.contains('.') {
+        Ok(value)
+    } else {
+        Ok(value / SDK_DEC_ONE)
+    }
+}
+
+fn parse_json_price(response: &Value, pointer: &str) -> Result<f64, DaemonError> {
+    match response.pointer(pointer) {
+        Some(Value::Number(price)) => price
+            .as_f64()
+            .ok_or_else(|| DaemonError::StdErr(format!("Invalid gas price {price}"))),
+        Some(Value::String(price)) => price
+            .parse()
+            .map_err(|_| DaemonError::StdErr(format!("Invalid gas price {price}"))),
+        _ => Err(DaemonError::StdErr(format!(
+            "Gas price not found at {pointer} in {response}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_prices() {
+        assert_eq!(parse_sdk_dec("0.0025").unwrap(), 0.0025);
+        assert_eq!(parse_sdk_dec("2500000000000000").unwrap(), 0.0025);
+        assert!(parse_sdk_dec("abc").is_err());
+
+        let response = json!({ "prices": [{ "denom": "uosmo", "price": "0.004" }], "fast": 0.01 });
+        assert_eq!(
+            parse_json_price(&response, "/prices/0/price").unwrap(),
+            0.004
+        );
+        assert_eq!(parse_json_price(&response, "/fast").unwrap(), 0.01);
+        assert!(parse_json_price(&response, "/slow").is_err());
+    }
+}
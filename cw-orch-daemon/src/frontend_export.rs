@@ -0,0 +1,199 @@
+//! Deployment files for frontends and indexers.
+//!
+//! A [`FrontendExport`] writes the contracts of the state file to a JSON or TypeScript file every time a code id or an address is stored,
+//! so that other repositories can consume the canonical addresses generated by the deployment scripts.
+//! The file contains, per chain id, the address, code id, code checksum and instantiate message hash of every contract
+//! (see [`DeploymentExport`] for the format).
+//!
+//! The checksums and instantiate message hashes are recorded in the state by the daemon when it uploads and instantiates contracts.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{frontend_export::FrontendExport, networks, Daemon};
+//!
+//! let daemon = Daemon::builder()
+//!     .chain(networks::JUNO_1)
+//!     // `.ts` files are written as TypeScript modules, other files as JSON
+//!     .frontend_export(FrontendExport::new("../frontend/src/deployments.ts"))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use cw_orch_core::contract::{ChainDeployment, DeploymentExport, ExportedContract};
+use serde_json::Value;
+
+use crate::{state::CODE_IDS_KEY, DaemonError};
+
+/// State key of the code checksums, by code id
+pub const CHECKSUMS_KEY: &str = "checksums";
+/// State key of the instantiate message hashes, by contract address
+pub const INIT_MSG_HASHES_KEY: &str = "init_msg_hashes";
+
+/// Format of a [`FrontendExport`] file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The JSON of the [`DeploymentExport`]
+    Json,
+    /// TypeScript module exporting the deployments, see [`DeploymentExport::to_typescript`]
+    TypeScript,
+}
+
+/// File the deployments of the state are exported to, see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontendExport {
+    path: PathBuf,
+    format: ExportFormat,
+    deployment_id: Option<String>,
+}
+
+impl FrontendExport {
+    /// Exports to `path`, as TypeScript if the file has a `.ts` extension and as JSON otherwise
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ts") => ExportFormat::TypeScript,
+            _ => ExportFormat::Json,
+        };
+        Self {
+            path,
+            format,
+            deployment_id: None,
+        }
+    }
+
+    /// Overrides the format deduced from the file extension
+    pub fn format(mut self, format: ExportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Exports the addresses of this deployment id instead of the deployment id of the daemon
+    pub fn deployment_id(mut self, deployment_id: impl Into<String>) -> Self {
+        self.deployment_id = Some(deployment_id.into());
+        self
+    }
+
+    /// Path of the export file
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Whether a change of the `key` section of the state changes the export
+    pub(crate) fn tracks(&self, key: &str, deployment_id: &str) -> bool {
+        [CODE_IDS_KEY, CHECKSUMS_KEY, INIT_MSG_HASHES_KEY].contains(&key)
+            || key == self.deployment_id.as_deref().unwrap_or(deployment_id)
+    }
+
+    /// Writes the export of the whole state file, with the addresses of `deployment_id` unless another deployment id was set
+    pub fn write(&self, state: &Value, deployment_id: &str) -> Result<(), DaemonError> {
+        let export = export_state(
+            state,
+            self.deployment_id.as_deref().unwrap_or(deployment_id),
+        );
+        let content = match self.format {
+            ExportFormat::Json => serde_json::to_string_pretty(&export)?,
+            ExportFormat::TypeScript => export.to_typescript()?,
+        };
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+/// Contracts of all the chains of a state file, with the addresses of `deployment_id`
+pub fn export_state(state: &Value, deployment_id: &str) -> DeploymentExport {
+    let mut export = DeploymentExport::default();
+    let Some(chain_names) = state.as_object() else {
+        return export;
+    };
+    for (chain_name, chains) in chain_names {
+        let Some(chains) = chains.as_object() else {
+            continue;
+        };
+        for (chain_id, chain_state) in chains {
+            let mut contracts = BTreeMap::<String, ExportedContract>::new();
+            if let Some(code_ids) = chain_state[CODE_IDS_KEY].as_object() {
+                for (id, code_id) in code_ids {
+                    let Some(code_id) = code_id.as_u64() else {
+                        continue;
+                    };
+                    let contract = contracts.entry(id.clone()).or_default();
+                    contract.code_id = Some(code_id);
+                    contract.checksum = chain_state[CHECKSUMS_KEY][code_id.to_string()]
+                        .as_str()
+                        .map(ToString::to_string);
+                }
+            }
+            if let Some(addresses) = chain_state[deployment_id].as_object() {
+                for (id, address) in addresses {
+                    let Some(address) = address.as_str() else {
+                        continue;
+                    };
+                    let contract = contracts.entry(id.clone()).or_default();
+                    contract.address = Some(address.to_string());
+                    contract.init_msg_hash = chain_state[INIT_MSG_HASHES_KEY][address]
+                        .as_str()
+                        .map(ToString::to_string);
+                }
+            }
+            if contracts.is_empty() {
+                continue;
+            }
+            export.chains.insert(
+                chain_id.clone(),
+                ChainDeployment {
+                    chain_name: chain_name.clone(),
+                    contracts,
+                },
+            );
+        }
+    }
+    export
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn export_state_with_metadata() {
+        let state = json!({
+            "juno": {
+                "juno-1": {
+                    "code_ids": { "counter": 12, "vault": 13 },
+                    "checksums": { "12": "aabb" },
+                    "init_msg_hashes": { "juno1counter": "ccdd" },
+                    "default": { "counter": "juno1counter", "removed": null },
+                    "v2": { "counter": "juno1other" }
+                },
+                "uni-6": { "code_ids": {}, "default": {} }
+            }
+        });
+        let export = export_state(&state, "default");
+        assert_eq!(export.chain_ids(), vec!["juno-1"]);
+        let contracts = &export.chains["juno-1"].contracts;
+        assert_eq!(
+            contracts["counter"],
+            ExportedContract {
+                address: Some("juno1counter".to_string()),
+                code_id: Some(12),
+                checksum: Some("aabb".to_string()),
+                init_msg_hash: Some("ccdd".to_string()),
+            }
+        );
+        assert_eq!(contracts["vault"].address, None);
+        assert!(!contracts.contains_key("removed"));
+
+        let export = FrontendExport::new("deployments.ts").deployment_id("v2");
+        assert_eq!(export.format, ExportFormat::TypeScript);
+        assert!(export.tracks("v2", "default"));
+        assert!(export.tracks(CHECKSUMS_KEY, "default"));
+        assert!(!export.tracks("default", "default"));
+    }
+}
@@ -0,0 +1,185 @@
+//! Gas estimation without simulation.
+//!
+//! By default, the gas of every transaction is estimated by simulating it on a node.
+//! Some chains disable the simulate endpoint, or return unreliable results from it.
+//! A [`GasTable`] estimates the gas of a transaction from the gas of each of its messages instead:
+//! - a static gas amount configured per message type url,
+//! - or the gas learned from previous successful transactions containing this message type,
+//! - or a default gas amount for unknown message types.
+//!
+//! The learned gas can be persisted to a json file, so it survives across runs.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{gas_table::{GasEstimation, GasTable}, networks, DaemonBuilder};
+//!
+//! let gas_table = GasTable::new(300_000)
+//!     .with_gas("/cosmwasm.wasm.v1.MsgStoreCode", 5_000_000)
+//!     .persist("gas/my-chain.json")
+//!     .unwrap();
+//!
+//! let daemon = DaemonBuilder::default()
+//!     .chain(networks::LOCAL_JUNO)
+//!     .gas_estimation(GasEstimation::Table(gas_table))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use cosmrs::Any;
+
+use crate::DaemonError;
+
+/// How the gas of a transaction is estimated
+#[derive(Debug, Clone, Default)]
+pub enum GasEstimation {
+    /// Simulate the transaction on a node
+    #[default]
+    Simulate,
+    /// Estimate the gas with a [`GasTable`], without simulating
+    Table(GasTable),
+    /// Simulate the transaction, and estimate it with the [`GasTable`] when the simulation fails
+    SimulateOrTable(GasTable),
+}
+
+impl GasEstimation {
+    /// Gas table used by this estimation, if any
+    pub fn gas_table(&self) -> Option<&GasTable> {
+        match self {
+            GasEstimation::Simulate => None,
+            GasEstimation::Table(table) | GasEstimation::SimulateOrTable(table) => Some(table),
+        }
+    }
+}
+
+/// Gas used per message type, see the [module documentation](self)
+///
+/// Clones share the learned gas.
+#[derive(Debug, Clone)]
+pub struct GasTable {
+    /// Gas configured per message type url, takes precedence over the learned gas
+    static_gas: HashMap<String, u64>,
+    /// Gas used for message types that are neither configured nor learned
+    default_gas: u64,
+    /// File the learned gas is persisted to
+    path: Option<PathBuf>,
+    learned: Arc<Mutex<BTreeMap<String, u64>>>,
+}
+
+impl GasTable {
+    /// Gas table estimating unknown message types with `default_gas`
+    pub fn new(default_gas: u64) -> Self {
+        Self {
+            static_gas: HashMap::new(),
+            default_gas,
+            path: None,
+            learned: Default::default(),
+        }
+    }
+
+    /// Uses `gas` for every message of type `type_url`
+    pub fn with_gas(mut self, type_url: impl Into<String>, gas: u64) -> Self {
+        self.static_gas.insert(type_url.into(), gas);
+        self
+    }
+
+    /// Persists the learned gas to the json file at `path`, and loads the gas learned in previous runs from it
+    pub fn persist(mut self, path: impl Into<PathBuf>) -> Result<Self, DaemonError> {
+        let path = path.into();
+        if path.exists() {
+            let learned: BTreeMap<String, u64> =
+                serde_json::from_reader(std::fs::File::open(&path)?)?;
+            self.learned.lock().unwrap().extend(learned);
+        }
+        self.path = Some(path);
+        Ok(self)
+    }
+
+    /// Gas of a message of type `type_url`
+    pub fn gas(&self, type_url: &str) -> u64 {
+        if let Some(gas) = self.static_gas.get(type_url) {
+            return *gas;
+        }
+        self.learned
+            .lock()
+            .unwrap()
+            .get(type_url)
+            .copied()
+            .unwrap_or(self.default_gas)
+    }
+
+    /// Estimated gas of a transaction containing `msgs`
+    pub fn estimate(&self, msgs: &[Any]) -> u64 {
+        msgs.iter().map(|msg| self.gas(&msg.type_url)).sum()
+    }
+
+    /// Learns the gas used by a successful transaction containing `msgs`.
+    /// The gas is split evenly between the messages and the highest gas seen for a message type is kept
+    pub fn learn(&self, msgs: &[Any], gas_used: u64) -> Result<(), DaemonError> {
+        if msgs.is_empty() {
+            return Ok(());
+        }
+        let gas_per_msg = gas_used / msgs.len() as u64;
+        let mut learned = self.learned.lock().unwrap();
+        let mut changed = false;
+        for msg in msgs {
+            let gas = learned.entry(msg.type_url.clone()).or_default();
+            if gas_per_msg > *gas {
+                *gas = gas_per_msg;
+                changed = true;
+            }
+        }
+
+        if let (Some(path), true) = (&self.path, changed) {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, serde_json::to_vec_pretty(&*learned)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn msg(type_url: &str) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        }
+    }
+
+    #[test]
+    fn learned_gas() -> anyhow::Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("cw-orch-gas-table-{}.json", std::process::id()));
+        let table = GasTable::new(100_000)
+            .with_gas("/cosmwasm.wasm.v1.MsgStoreCode", 5_000_000)
+            .persist(&path)?;
+
+        let execute = msg("/cosmwasm.wasm.v1.MsgExecuteContract");
+        let store = msg("/cosmwasm.wasm.v1.MsgStoreCode");
+        assert_eq!(table.estimate(&[execute.clone(), store.clone()]), 5_100_000);
+
+        table.learn(&[execute.clone(), execute.clone()], 400_000)?;
+        // Static gas isn't overwritten by the learned one
+        table.learn(&[store.clone()], 1_000)?;
+        assert_eq!(table.estimate(&[execute.clone(), store]), 5_200_000);
+        // Lower gas usages don't decrease the estimation
+        table.learn(&[execute.clone()], 150_000)?;
+        assert_eq!(table.gas(&execute.type_url), 200_000);
+
+        let reloaded = GasTable::new(100_000).persist(&path)?;
+        assert_eq!(reloaded.gas(&execute.type_url), 200_000);
+
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+}
@@ -1,7 +1,117 @@
 use crate::DaemonError;
 use file_lock::{FileLock, FileOptions};
+use serde::{Serialize, Serializer};
 use serde_json::{from_reader, json, Value};
-use std::{fs::File, io::Seek};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{ErrorKind, Seek, Write},
+    time::{Duration, Instant},
+};
+
+/// Default interval between two attempts to lock a state file
+pub const DEFAULT_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Options used when locking a state file that is already locked by another process.
+///
+/// By default, locking fails immediately with [`DaemonError::StateLocked`].
+/// When a timeout is set, locking is retried until the other process releases the file or the timeout expires.
+/// Processes that only need to read the state can use a read-only state instead, which doesn't lock the file
+/// (see `DaemonBuilder::read_only_state`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateLockOptions {
+    /// Maximum time to wait for the lock, `None` to fail immediately
+    pub timeout: Option<Duration>,
+    /// Interval between two attempts to lock the file
+    pub retry_interval: Duration,
+}
+
+impl Default for StateLockOptions {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            retry_interval: DEFAULT_LOCK_RETRY_INTERVAL,
+        }
+    }
+}
+
+impl StateLockOptions {
+    /// Retry locking the file until `timeout` expires
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the interval between two attempts to lock the file
+    pub fn retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = retry_interval;
+        self
+    }
+}
+
+/// How the state file is written.
+///
+/// Object keys are always sorted, so the state file is stable across writes and produces small diffs when committed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateFileFormat {
+    /// Indent the json, `false` writes it on a single line. Defaults to `true`
+    pub pretty: bool,
+    /// Write the state to a temporary file and rename it over the state file,
+    /// so a crash during the write never leaves a truncated state. Defaults to `true` on unix,
+    /// other platforms can't replace a file that is open
+    pub atomic_write: bool,
+}
+
+impl Default for StateFileFormat {
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            atomic_write: cfg!(unix),
+        }
+    }
+}
+
+impl StateFileFormat {
+    /// Writes the json on a single line
+    pub fn compact(mut self) -> Self {
+        self.pretty = false;
+        self
+    }
+
+    /// Sets whether the state is written to a temporary file before being renamed over the state file
+    pub fn atomic_write(mut self, atomic_write: bool) -> Self {
+        self.atomic_write = atomic_write;
+        self
+    }
+
+    /// Serializes the state with sorted keys
+    pub fn to_vec(&self, json: &Value) -> Result<Vec<u8>, DaemonError> {
+        let json = SortedJson(json);
+        let bytes = if self.pretty {
+            serde_json::to_vec_pretty(&json)?
+        } else {
+            serde_json::to_vec(&json)?
+        };
+        Ok(bytes)
+    }
+}
+
+/// Serializes a json value with its object keys sorted, whatever the `serde_json` map implementation
+struct SortedJson<'a>(&'a Value);
+
+impl Serialize for SortedJson<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Object(object) => object
+                .iter()
+                .map(|(key, value)| (key, SortedJson(value)))
+                .collect::<BTreeMap<_, _>>()
+                .serialize(serializer),
+            Value::Array(array) => serializer.collect_seq(array.iter().map(SortedJson)),
+            value => value.serialize(serializer),
+        }
+    }
+}
 
 /// State file reader and writer
 /// Mainly used by [`crate::Daemon`] and [`crate::DaemonAsync`], but could also be used for tests or custom edits of the state
@@ -10,41 +120,70 @@ pub struct JsonLockedState {
     lock: FileLock,
     json: Value,
     path: String,
+    format: StateFileFormat,
+    /// Delete the file instead of writing it when dropped, used by temporary test states
+    remove_on_drop: bool,
 }
 
 impl JsonLockedState {
     /// Lock a state files
     /// Other process won't be able to lock it
     pub fn new(path: &str) -> Self {
-        // open file pointer set read/write permissions to true
-        // create it if it does not exists
-        // don't truncate it
+        Self::try_new(path, StateLockOptions::default())
+            .unwrap_or_else(|_| panic!("Was not able to receive {path} state lock"))
+    }
 
-        let options = FileOptions::new()
-            .create(true)
-            .read(true)
-            .write(true)
-            .truncate(false);
+    /// Lock a state file, retrying according to the lock options if another process holds the lock.
+    /// Other process won't be able to lock it
+    pub fn try_new(path: &str, options: StateLockOptions) -> Result<Self, DaemonError> {
+        let start = Instant::now();
+        let lock = loop {
+            // open file pointer set read/write permissions to true
+            // create it if it does not exists
+            // don't truncate it
+            let file_options = FileOptions::new()
+                .create(true)
+                .read(true)
+                .write(true)
+                .truncate(false);
 
-        // Lock file, non blocking so it errors in case someone else already holding lock of it
-        let lock: FileLock = FileLock::lock(path, false, options)
-            .unwrap_or_else(|_| panic!("Was not able to receive {path} state lock"));
+            // Lock file, non blocking so it errors in case someone else already holding lock of it
+            match FileLock::lock(path, false, file_options) {
+                // The file was replaced by an atomic write while we were waiting for the lock
+                Ok(lock) if is_replaced(&lock, path)? => continue,
+                Ok(lock) => break lock,
+                // The lock is held by another process
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    let Some(timeout) = options.timeout else {
+                        return Err(DaemonError::StateLocked(path.to_owned()));
+                    };
+                    if start.elapsed() >= timeout {
+                        return Err(DaemonError::StateLocked(path.to_owned()));
+                    }
+                    log::debug!("State file {path} is locked by another process, retrying");
+                    std::thread::sleep(options.retry_interval);
+                }
+                Err(err) => return Err(err.into()),
+            }
+        };
 
         // return empty json object if file is empty
         // return file content if not
-        let json: Value = if lock.file.metadata().unwrap().len().eq(&0) {
+        let json: Value = if lock.file.metadata()?.len().eq(&0) {
             json!({})
         } else {
-            from_reader(&lock.file).unwrap()
+            from_reader(&lock.file)?
         };
 
         let filename = path.to_owned();
 
-        JsonLockedState {
+        Ok(JsonLockedState {
             lock,
             json,
             path: filename,
-        }
+            format: StateFileFormat::default(),
+            remove_on_drop: false,
+        })
     }
 
     /// Prepare json for further writes
@@ -68,6 +207,11 @@ impl JsonLockedState {
         self.json.clone()
     }
 
+    /// Replaces the whole state, it's written on the next write
+    pub fn set_state(&mut self, json: Value) {
+        self.json = json;
+    }
+
     /// Get a value for read
     pub fn get(&self, network_id: &str, chain_id: &str) -> &Value {
         &self.json[network_id][chain_id]
@@ -78,11 +222,57 @@ impl JsonLockedState {
         self.json[network_id].get_mut(chain_id).unwrap()
     }
 
+    /// Sets how the state is written to the file, see [`StateFileFormat`]
+    pub fn set_format(&mut self, format: StateFileFormat) {
+        self.format = format;
+    }
+
+    pub fn format(&self) -> StateFileFormat {
+        self.format
+    }
+
+    /// Deletes the state file when this state is dropped, instead of writing it
+    pub fn set_remove_on_drop(&mut self, remove_on_drop: bool) {
+        self.remove_on_drop = remove_on_drop;
+    }
+
     /// Force write to a file
     pub fn force_write(&mut self) {
-        self.lock.file.set_len(0).unwrap();
-        self.lock.file.rewind().unwrap();
-        serde_json::to_writer_pretty(&self.lock.file, &self.json).unwrap();
+        self.try_write()
+            .unwrap_or_else(|e| panic!("Was not able to write state file {}: {e}", self.path))
+    }
+
+    /// Writes the state to the file, see [`StateFileFormat`]
+    pub fn try_write(&mut self) -> Result<(), DaemonError> {
+        let bytes = self.format.to_vec(&self.json)?;
+        if !self.format.atomic_write {
+            self.lock.file.set_len(0)?;
+            self.lock.file.rewind()?;
+            return Ok(self.lock.file.write_all(&bytes)?);
+        }
+
+        // The temporary file is locked before it replaces the state file,
+        // so other processes can't lock the new state file before we release it
+        let tmp_path = format!("{}.tmp", self.path);
+        let file_options = FileOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(true);
+        let mut tmp_lock = FileLock::lock(&tmp_path, false, file_options)?;
+        tmp_lock.file.write_all(&bytes)?;
+        tmp_lock.file.sync_all()?;
+        std::fs::rename(&tmp_path, &self.path)?;
+
+        // Releases the lock on the replaced file
+        self.lock = tmp_lock;
+        Ok(())
+    }
+
+    /// Removes all the `null` entries from the state, returns the number of removed entries
+    /// Removed contracts and code ids are set to `null`, so they accumulate over time
+    pub fn compact(&mut self) -> usize {
+        compact_value(&mut self.json)
     }
 
     pub fn path(&self) -> &str {
@@ -93,8 +283,43 @@ impl JsonLockedState {
 // Write json when dropping
 impl Drop for JsonLockedState {
     fn drop(&mut self) {
-        self.force_write()
+        if self.remove_on_drop {
+            let _ = std::fs::remove_file(&self.path);
+        } else {
+            self.force_write()
+        }
+    }
+}
+
+fn compact_value(value: &mut Value) -> usize {
+    let Some(object) = value.as_object_mut() else {
+        return 0;
+    };
+    let before = object.len();
+    object.retain(|_, v| !v.is_null());
+    let mut removed = before - object.len();
+    for v in object.values_mut() {
+        removed += compact_value(v);
     }
+    removed
+}
+
+/// Whether the locked file is not the state file anymore, because another process replaced it
+#[cfg(unix)]
+fn is_replaced(lock: &FileLock, path: &str) -> Result<bool, DaemonError> {
+    use std::os::unix::fs::MetadataExt;
+
+    let locked = lock.file.metadata()?;
+    match std::fs::metadata(path) {
+        Ok(current) => Ok(locked.ino() != current.ino() || locked.dev() != current.dev()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(true),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(not(unix))]
+fn is_replaced(_lock: &FileLock, _path: &str) -> Result<bool, DaemonError> {
+    Ok(false)
 }
 
 pub fn read(filename: &String) -> Result<Value, DaemonError> {
@@ -103,3 +328,127 @@ pub fn read(filename: &String) -> Result<Value, DaemonError> {
     let json: serde_json::Value = from_reader(file)?;
     Ok(json)
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{compact_value, JsonLockedState, StateFileFormat, StateLockOptions};
+
+    #[test]
+    fn try_new_writes_on_drop() {
+        let path = std::env::temp_dir().join(format!(
+            "cw-orch-json-lock-test-{}.json",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let options = StateLockOptions::default()
+            .timeout(Duration::from_secs(1))
+            .retry_interval(Duration::from_millis(10));
+        let mut state = JsonLockedState::try_new(path, options).unwrap();
+        state.prepare("juno-1", "juno", "default");
+        drop(state);
+
+        let state = JsonLockedState::try_new(path, StateLockOptions::default()).unwrap();
+        assert_eq!(
+            state.get("juno", "juno-1"),
+            &json!({ "default": {}, "code_ids": {} })
+        );
+    }
+
+    #[test]
+    fn format_sorts_keys() {
+        let mut state = serde_json::Map::new();
+        state.insert(
+            "b".to_string(),
+            json!({ "d": 1, "c": [{ "f": 2, "e": 3 }] }),
+        );
+        state.insert("a".to_string(), json!(null));
+        let state = serde_json::Value::Object(state);
+
+        let compact = StateFileFormat::default().compact().to_vec(&state).unwrap();
+        assert_eq!(
+            String::from_utf8(compact).unwrap(),
+            r#"{"a":null,"b":{"c":[{"e":3,"f":2}],"d":1}}"#
+        );
+
+        let pretty = StateFileFormat::default().to_vec(&state).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&pretty).unwrap(),
+            state
+        );
+    }
+
+    #[test]
+    fn atomic_write_replaces_file() {
+        let path = std::env::temp_dir().join(format!(
+            "cw-orch-json-lock-atomic-test-{}.json",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let mut state = JsonLockedState::try_new(path, StateLockOptions::default()).unwrap();
+        state.set_format(StateFileFormat::default().compact());
+        state.prepare("juno-1", "juno", "default");
+        state.try_write().unwrap();
+
+        assert!(!std::path::Path::new(&format!("{path}.tmp")).exists());
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            r#"{"juno":{"juno-1":{"code_ids":{},"default":{}}}}"#
+        );
+
+        drop(state);
+        let state = JsonLockedState::try_new(path, StateLockOptions::default()).unwrap();
+        assert_eq!(
+            state.get("juno", "juno-1"),
+            &json!({ "default": {}, "code_ids": {} })
+        );
+    }
+
+    #[test]
+    fn remove_on_drop_deletes_file() {
+        let path = std::env::temp_dir().join(format!(
+            "cw-orch-json-lock-remove-test-{}.json",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+
+        let mut state = JsonLockedState::try_new(path, StateLockOptions::default()).unwrap();
+        state.prepare("juno-1", "juno", "default");
+        state.force_write();
+        state.set_remove_on_drop(true);
+        assert!(std::path::Path::new(path).exists());
+        drop(state);
+        assert!(!std::path::Path::new(path).exists());
+    }
+
+    #[test]
+    fn compact_removes_null_entries() {
+        let mut state = json!({
+            "juno": {
+                "juno-1": {
+                    "code_ids": { "a": 1, "b": null },
+                    "default": { "a": "juno1a", "b": null, "c": null }
+                }
+            }
+        });
+        assert_eq!(compact_value(&mut state), 3);
+        assert_eq!(
+            state,
+            json!({
+                "juno": {
+                    "juno-1": {
+                        "code_ids": { "a": 1 },
+                        "default": { "a": "juno1a" }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! Rotation of a deployer key.
+//!
+//! Moves everything a deployment gave to a deployer key over to a new key:
+//! - the admin of every contract of the deployment administrated by the old key is set to the new key,
+//! - authz grants given by the old key are granted again by the new key, then revoked by the old key,
+//! - fee allowances given by the old key are granted again by the new key, then revoked by the old key.
+//!
+//! Grants and allowances the old key received can't be moved by the old key itself, they are listed as manual actions for their granters.
+//! Every action is attempted even if a previous one failed, all outcomes are collected inside a [`RotationReport`].
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{key_rotation::KeyRotation, networks, Daemon};
+//!
+//! let old = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//! let new = old.rebuild().mnemonic("new deployer mnemonic ...").build().unwrap();
+//!
+//! // Check what would be moved without broadcasting anything
+//! println!("{}", KeyRotation::new(old.clone(), new.clone()).dry_run().rotate().unwrap());
+//!
+//! let report = KeyRotation::new(old, new).rotate().unwrap();
+//! assert!(report.is_success(), "{}", report);
+//! ```
+
+use std::fmt::Display;
+
+use cosmrs::{proto::cosmos::staking::v1beta1::StakeAuthorization, Any};
+use cosmwasm_std::Addr;
+use cw_orch_core::environment::{ChainState, TxHandler};
+use prost::Message;
+use prost_types::Timestamp;
+
+use crate::{
+    cosmos_modules,
+    proto_registry::{authz, feegrant, wasm},
+    queriers::{Authz, CosmWasm, FeeGrant},
+    Daemon, DaemonError,
+};
+
+/// Action moving a permission from the old key to the new key
+#[derive(Debug, Clone, PartialEq)]
+pub enum RotationAction {
+    /// Set the admin of the contract to the new key
+    UpdateAdmin {
+        /// Id of the contract in the deployment
+        contract_id: String,
+        /// Address of the contract
+        contract: Addr,
+    },
+    /// Grant the authorization from the new key, then revoke the grant of the old key
+    MoveAuthzGrant {
+        /// Grantee of the authorization
+        grantee: String,
+        /// Type of the messages the grantee is authorized to send
+        msg_type_url: String,
+        /// Encoded authorization
+        authorization: Any,
+        /// Expiration of the grant
+        expiration: Option<Timestamp>,
+    },
+    /// Grant the fee allowance from the new key, then revoke the allowance of the old key
+    MoveFeeGrant {
+        /// Grantee of the allowance
+        grantee: String,
+        /// Encoded allowance
+        allowance: Any,
+    },
+    /// Action that can't be done by the old or the new key
+    Manual {
+        /// What needs to be done
+        description: String,
+    },
+}
+
+impl Display for RotationAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RotationAction::UpdateAdmin {
+                contract_id,
+                contract,
+            } => write!(f, "update admin of {contract_id} ({contract})"),
+            RotationAction::MoveAuthzGrant {
+                grantee,
+                msg_type_url,
+                ..
+            } => write!(f, "move authz grant of {msg_type_url} to {grantee}"),
+            RotationAction::MoveFeeGrant { grantee, .. } => {
+                write!(f, "move fee allowance of {grantee}")
+            }
+            RotationAction::Manual { description } => write!(f, "manual: {description}"),
+        }
+    }
+}
+
+/// Outcome of a [`RotationAction`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotationEntry {
+    /// Planned action
+    pub action: RotationAction,
+    /// Hashes of the broadcasted transactions
+    pub tx_hashes: Vec<String>,
+    /// Error encountered while applying the action
+    pub error: Option<String>,
+}
+
+/// Outcome of [`KeyRotation::rotate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotationReport {
+    /// Chain the keys were rotated on
+    pub chain_id: String,
+    /// Address of the old key
+    pub old: Addr,
+    /// Address of the new key
+    pub new: Addr,
+    /// Whether transactions were broadcasted
+    pub dry_run: bool,
+    /// Outcome per action
+    pub entries: Vec<RotationEntry>,
+}
+
+impl RotationReport {
+    /// Actions that errored
+    pub fn failures(&self) -> Vec<&RotationEntry> {
+        self.entries.iter().filter(|e| e.error.is_some()).collect()
+    }
+
+    /// Actions left to the granters of the old key
+    pub fn manual_actions(&self) -> Vec<&RotationEntry> {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.action, RotationAction::Manual { .. }))
+            .collect()
+    }
+
+    /// Returns true if no action errored
+    pub fn is_success(&self) -> bool {
+        self.failures().is_empty()
+    }
+}
+
+impl Display for RotationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Key rotation on {} from {} to {}{}:",
+            self.chain_id,
+            self.old,
+            self.new,
+            if self.dry_run { " (dry-run)" } else { "" }
+        )?;
+        for entry in &self.entries {
+            write!(f, "  {}", entry.action)?;
+            if let Some(error) = &entry.error {
+                writeln!(f, ", error: {}", error)?;
+            } else if !entry.tx_hashes.is_empty() {
+                writeln!(f, " (tx {})", entry.tx_hashes.join(", "))?;
+            } else {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Moves the contract admins, authz grants and fee allowances of a deployment from an old key to a new key
+pub struct KeyRotation {
+    old: Daemon,
+    new: Daemon,
+    dry_run: bool,
+}
+
+impl KeyRotation {
+    /// Rotation from the sender of `old` to the sender of `new`.
+    /// The contracts are the ones of the deployment of `old`
+    pub fn new(old: Daemon, new: Daemon) -> Self {
+        Self {
+            old,
+            new,
+            dry_run: false,
+        }
+    }
+
+    /// Only plan the actions, without broadcasting any transaction
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Lists the actions needed to move the permissions of the old key to the new key
+    pub fn plan(&self) -> Result<Vec<RotationAction>, DaemonError> {
+        let old = self.old.sender();
+        let new = self.new.sender();
+        let chain_id = self.old.state().chain_data.chain_id.to_string();
+        let new_chain_id = self.new.state().chain_data.chain_id.to_string();
+        if chain_id != new_chain_id {
+            return Err(DaemonError::UnexpectedChain {
+                expected: chain_id,
+                found: new_chain_id,
+            });
+        }
+        let rt = &self.old.rt_handle;
+        let channel = self.old.channel();
+        let mut actions = vec![];
+
+        // Contract admins
+        let state = self.old.state();
+        let wasm = CosmWasm::new_async(channel.clone());
+        let addresses = state.get(&state.deployment_id)?;
+        for (contract_id, address) in addresses.as_object().cloned().unwrap_or_default() {
+            let Some(address) = address.as_str() else {
+                continue;
+            };
+            let info = rt.block_on(wasm._contract_info(address))?;
+            if info.admin.map(|admin| admin.to_string()) == Some(old.to_string()) {
+                actions.push(RotationAction::UpdateAdmin {
+                    contract_id,
+                    contract: Addr::unchecked(address),
+                });
+            }
+        }
+
+        // Authz grants
+        let authz = Authz::new_async(channel.clone());
+        let given = rt.block_on(authz._granter_grants(old.to_string(), None))?;
+        for grant in given.grants {
+            // The new key can't grant an authorization to itself
+            if grant.grantee == new.as_str() {
+                continue;
+            }
+            let Some(authorization) = grant.authorization else {
+                continue;
+            };
+            match authorization_msg_type_url(&authorization) {
+                Some(msg_type_url) => actions.push(RotationAction::MoveAuthzGrant {
+                    grantee: grant.grantee,
+                    msg_type_url,
+                    authorization,
+                    expiration: grant.expiration,
+                }),
+                None => actions.push(RotationAction::Manual {
+                    description: format!(
+                        "move the {} grant to {}, its message type is unknown",
+                        authorization.type_url, grant.grantee
+                    ),
+                }),
+            }
+        }
+        let received = rt.block_on(authz._grantee_grants(old.to_string(), None))?;
+        for grant in received.grants {
+            actions.push(RotationAction::Manual {
+                description: format!(
+                    "{} needs to grant the {} authorization to {new}",
+                    grant.granter,
+                    grant.authorization.map(|a| a.type_url).unwrap_or_default()
+                ),
+            });
+        }
+
+        // Fee allowances
+        let feegrant = FeeGrant::new_async(channel);
+        for grant in rt.block_on(feegrant._allowances_by_granter(old.to_string(), None))? {
+            let Some(allowance) = grant.allowance else {
+                continue;
+            };
+            actions.push(RotationAction::MoveFeeGrant {
+                grantee: grant.grantee,
+                allowance,
+            });
+        }
+        for grant in rt.block_on(feegrant._allowances(old.to_string(), None))? {
+            actions.push(RotationAction::Manual {
+                description: format!("{} needs to grant a fee allowance to {new}", grant.granter),
+            });
+        }
+
+        Ok(actions)
+    }
+
+    /// Plans and applies the rotation
+    pub fn rotate(&self) -> Result<RotationReport, DaemonError> {
+        let actions = self.plan()?;
+        let entries = actions
+            .into_iter()
+            .map(|action| {
+                let mut entry = RotationEntry {
+                    action,
+                    tx_hashes: vec![],
+                    error: None,
+                };
+                if !self.dry_run {
+                    if let Err(e) = self.apply(&entry.action, &mut entry.tx_hashes) {
+                        log::error!("Key rotation: {} failed: {}", entry.action, e);
+                        entry.error = Some(e.to_string());
+                    }
+                }
+                entry
+            })
+            .collect();
+
+        Ok(RotationReport {
+            chain_id: self.old.state().chain_data.chain_id.to_string(),
+            old: self.old.sender(),
+            new: self.new.sender(),
+            dry_run: self.dry_run,
+            entries,
+        })
+    }
+
+    fn apply(
+        &self,
+        action: &RotationAction,
+        tx_hashes: &mut Vec<String>,
+    ) -> Result<(), DaemonError> {
+        let old = self.old.sender();
+        let new = self.new.sender();
+        match action {
+            RotationAction::UpdateAdmin { contract, .. } => {
+                tx_hashes.push(commit(&self.old, wasm::update_admin(&old, contract, &new))?);
+            }
+            RotationAction::MoveAuthzGrant {
+                grantee,
+                msg_type_url,
+                authorization,
+                expiration,
+            } => {
+                let grant = authz::grant(&new, grantee, authorization.clone(), expiration.clone());
+                tx_hashes.push(commit(&self.new, grant)?);
+                tx_hashes.push(commit(
+                    &self.old,
+                    authz::revoke(&old, grantee, msg_type_url),
+                )?);
+            }
+            RotationAction::MoveFeeGrant { grantee, allowance } => {
+                let grant = feegrant::grant_allowance(&new, grantee, allowance.clone());
+                tx_hashes.push(commit(&self.new, grant)?);
+                tx_hashes.push(commit(
+                    &self.old,
+                    feegrant::revoke_allowance(&old, grantee),
+                )?);
+            }
+            RotationAction::Manual { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+fn commit(daemon: &Daemon, msg: Any) -> Result<String, DaemonError> {
+    let wallet = daemon.wallet();
+    let response = daemon
+        .rt_handle
+        .block_on(wallet.commit_tx_any(vec![msg], None))?;
+    Ok(response.txhash)
+}
+
+/// Type of the messages an encoded authorization allows to send, if it's known
+fn authorization_msg_type_url(authorization: &Any) -> Option<String> {
+    let type_url = match authorization.type_url.as_str() {
+        "/cosmos.authz.v1beta1.GenericAuthorization" => {
+            let generic =
+                cosmos_modules::authz::GenericAuthorization::decode(authorization.value.as_slice())
+                    .ok()?;
+            return Some(generic.msg);
+        }
+        "/cosmos.staking.v1beta1.StakeAuthorization" => {
+            let stake = StakeAuthorization::decode(authorization.value.as_slice()).ok()?;
+            match stake.authorization_type {
+                1 => "/cosmos.staking.v1beta1.MsgDelegate",
+                2 => "/cosmos.staking.v1beta1.MsgUndelegate",
+                3 => "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+                _ => return None,
+            }
+        }
+        "/cosmos.bank.v1beta1.SendAuthorization" => "/cosmos.bank.v1beta1.MsgSend",
+        "/ibc.applications.transfer.v1.TransferAuthorization" => {
+            "/ibc.applications.transfer.v1.MsgTransfer"
+        }
+        "/cosmwasm.wasm.v1.ContractExecutionAuthorization" => {
+            "/cosmwasm.wasm.v1.MsgExecuteContract"
+        }
+        "/cosmwasm.wasm.v1.ContractMigrationAuthorization" => {
+            "/cosmwasm.wasm.v1.MsgMigrateContract"
+        }
+        _ => return None,
+    };
+    Some(type_url.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn authorization_type_urls() {
+        let generic = Any {
+            type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
+            value: cosmos_modules::authz::GenericAuthorization {
+                msg: "/cosmwasm.wasm.v1.MsgStoreCode".to_string(),
+            }
+            .encode_to_vec(),
+        };
+        assert_eq!(
+            authorization_msg_type_url(&generic).as_deref(),
+            Some("/cosmwasm.wasm.v1.MsgStoreCode")
+        );
+
+        let send = Any {
+            type_url: "/cosmos.bank.v1beta1.SendAuthorization".to_string(),
+            value: vec![],
+        };
+        assert_eq!(
+            authorization_msg_type_url(&send).as_deref(),
+            Some("/cosmos.bank.v1beta1.MsgSend")
+        );
+
+        let unknown = Any {
+            type_url: "/custom.v1.Authorization".to_string(),
+            value: vec![],
+        };
+        assert_eq!(authorization_msg_type_url(&unknown), None);
+    }
+
+    #[test]
+    fn report_failures() {
+        let report = RotationReport {
+            chain_id: "juno-1".to_string(),
+            old: Addr::unchecked("old"),
+            new: Addr::unchecked("new"),
+            dry_run: false,
+            entries: vec![
+                RotationEntry {
+                    action: RotationAction::UpdateAdmin {
+                        contract_id: "vault".to_string(),
+                        contract: Addr::unchecked("vault_addr"),
+                    },
+                    tx_hashes: vec!["hash".to_string()],
+                    error: None,
+                },
+                RotationEntry {
+                    action: RotationAction::Manual {
+                        description: "granter needs to grant".to_string(),
+                    },
+                    tx_hashes: vec![],
+                    error: None,
+                },
+            ],
+        };
+        assert!(report.is_success());
+        assert_eq!(report.manual_actions().len(), 1);
+        assert!(report.to_string().contains("update admin of vault"));
+    }
+}
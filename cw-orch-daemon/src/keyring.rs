@@ -0,0 +1,113 @@
+//! Mnemonic storage in the OS keychain (macOS Keychain, Secret Service on Linux, Windows Credential Manager).
+//!
+//! Mnemonics are stored under a named entry, so scripts don't need to keep them in plaintext env variables:
+//! ```no_run
+//! use cw_orch_daemon::{keyring::MnemonicKeyring, networks, DaemonBuilder};
+//!
+//! // Once, to store the mnemonic
+//! MnemonicKeyring::default().import("deployer", "<24 words mnemonic>").unwrap();
+//!
+//! // In the deployment scripts
+//! let daemon = DaemonBuilder::default()
+//!     .chain(networks::JUNO_1)
+//!     .keyring_entry("deployer")
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::DaemonError;
+
+/// Keychain service the mnemonics are stored under by default
+pub const DEFAULT_KEYRING_SERVICE: &str = "cw-orch";
+
+/// Named mnemonics stored in the OS keychain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MnemonicKeyring {
+    service: String,
+}
+
+impl Default for MnemonicKeyring {
+    fn default() -> Self {
+        Self::new(DEFAULT_KEYRING_SERVICE)
+    }
+}
+
+impl MnemonicKeyring {
+    /// Keyring storing mnemonics under a custom keychain service
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Stores a mnemonic under `name`, replacing any existing entry
+    pub fn import(&self, name: &str, mnemonic: &str) -> Result<(), DaemonError> {
+        validate_mnemonic(mnemonic)?;
+        self.entry(name)?.set_password(mnemonic.trim())?;
+        log::info!("Stored mnemonic {name} in the {} keyring", self.service);
+        Ok(())
+    }
+
+    /// Loads the mnemonic stored under `name`
+    pub fn export(&self, name: &str) -> Result<String, DaemonError> {
+        match self.entry(name)?.get_password() {
+            Ok(mnemonic) => Ok(mnemonic),
+            Err(::keyring::Error::NoEntry) => Err(DaemonError::KeyringEntryNotFound {
+                service: self.service.clone(),
+                name: name.to_string(),
+            }),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Whether a mnemonic is stored under `name`
+    pub fn contains(&self, name: &str) -> Result<bool, DaemonError> {
+        match self.export(name) {
+            Ok(_) => Ok(true),
+            Err(DaemonError::KeyringEntryNotFound { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Removes the mnemonic stored under `name`
+    pub fn delete(&self, name: &str) -> Result<(), DaemonError> {
+        self.entry(name)?.delete_password()?;
+        Ok(())
+    }
+
+    fn entry(&self, name: &str) -> Result<::keyring::Entry, DaemonError> {
+        Ok(::keyring::Entry::new(&self.service, name)?)
+    }
+}
+
+fn validate_mnemonic(mnemonic: &str) -> Result<(), DaemonError> {
+    let mnemonic = mnemonic.trim();
+    if mnemonic.split(' ').count() != 24 {
+        return Err(DaemonError::WrongLength);
+    }
+    hkd32::mnemonic::Phrase::new(mnemonic, hkd32::mnemonic::Language::English)
+        .map_err(|_| DaemonError::Phrasing)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn import_rejects_invalid_mnemonics() {
+        let keyring = MnemonicKeyring::new("cw-orch-test");
+        assert!(matches!(
+            keyring.import("test", "notice oak worry"),
+            Err(DaemonError::WrongLength)
+        ));
+        assert!(matches!(
+            keyring.import("test", &["notice"; 24].join(" ")),
+            Err(DaemonError::Phrasing)
+        ));
+    }
+}
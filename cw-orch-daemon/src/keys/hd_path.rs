@@ -0,0 +1,181 @@
+//! HD derivation paths of the private keys.
+//! Keys are derived following BIP-44 by default: `m/44'/{coin_type}'/{account}'/{change}/{index}`
+
+use std::{fmt::Write, str::FromStr};
+
+use crate::DaemonError;
+
+/// Purpose of the BIP-44 derivation paths
+pub const DEFAULT_PURPOSE: u32 = 44;
+
+/// Derivation path used to generate a private key from a mnemonic.
+///
+/// Allows accessing funds on nonstandard derivations, for instance a chain key derived with the 118 coin type
+/// or with a custom account level:
+/// ```
+/// use cw_orch_daemon::keys::hd_path::HdPath;
+///
+/// let path: HdPath = "m/44'/118'/1'/0/3".parse().unwrap();
+/// assert_eq!(path, HdPath::default().coin_type(118).account(1).index(3));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HdPath {
+    /// Purpose level, always hardened
+    pub purpose: u32,
+    /// Coin type (slip44) level, always hardened. Defaults to the coin type of the network
+    pub coin_type: Option<u32>,
+    /// Account level
+    pub account: u32,
+    /// Change level
+    pub change: u32,
+    /// Address index level
+    pub index: u32,
+    /// Whether the account level is hardened, true by default
+    pub hardened_account: bool,
+    /// Whether the change level is hardened, false by default
+    pub hardened_change: bool,
+    /// Whether the address index level is hardened, false by default
+    pub hardened_index: bool,
+}
+
+impl Default for HdPath {
+    fn default() -> Self {
+        Self {
+            purpose: DEFAULT_PURPOSE,
+            coin_type: None,
+            account: 0,
+            change: 0,
+            index: 0,
+            hardened_account: true,
+            hardened_change: false,
+            hardened_index: false,
+        }
+    }
+}
+
+impl HdPath {
+    /// Overrides the coin type of the network
+    pub fn coin_type(mut self, coin_type: u32) -> Self {
+        self.coin_type = Some(coin_type);
+        self
+    }
+    pub fn account(mut self, account: u32) -> Self {
+        self.account = account;
+        self
+    }
+    pub fn change(mut self, change: u32) -> Self {
+        self.change = change;
+        self
+    }
+    pub fn index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+    pub fn hardened_account(mut self, hardened: bool) -> Self {
+        self.hardened_account = hardened;
+        self
+    }
+    pub fn hardened_change(mut self, hardened: bool) -> Self {
+        self.hardened_change = hardened;
+        self
+    }
+    pub fn hardened_index(mut self, hardened: bool) -> Self {
+        self.hardened_index = hardened;
+        self
+    }
+
+    /// Full derivation path, using `network_coin_type` when no coin type is set on the path
+    pub fn derivation_path(&self, network_coin_type: u32) -> String {
+        let mut path = format!(
+            "m/{}'/{}'",
+            self.purpose,
+            self.coin_type.unwrap_or(network_coin_type)
+        );
+        for (value, hardened) in [
+            (self.account, self.hardened_account),
+            (self.change, self.hardened_change),
+            (self.index, self.hardened_index),
+        ] {
+            write!(path, "/{value}{}", if hardened { "'" } else { "" }).unwrap();
+        }
+        path
+    }
+}
+
+impl FromStr for HdPath {
+    type Err = DaemonError;
+
+    /// Parses a full derivation path, like `m/44'/118'/0'/0/0`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DaemonError::InvalidHdPath(s.to_string());
+
+        let levels = s
+            .trim()
+            .strip_prefix("m/")
+            .ok_or_else(invalid)?
+            .split('/')
+            .map(|level| match level.strip_suffix('\'') {
+                Some(value) => value.parse().map(|value: u32| (value, true)),
+                None => level.parse().map(|value: u32| (value, false)),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+
+        let [(purpose, true), (coin_type, true), (account, hardened_account), (change, hardened_change), (index, hardened_index)] =
+            levels[..]
+        else {
+            return Err(invalid());
+        };
+
+        Ok(Self {
+            purpose,
+            coin_type: Some(coin_type),
+            account,
+            change,
+            index,
+            hardened_account,
+            hardened_change,
+            hardened_index,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_path() {
+        assert_eq!(HdPath::default().derivation_path(118), "m/44'/118'/0'/0/0");
+        assert_eq!(
+            HdPath::default().index(2).derivation_path(60),
+            "m/44'/60'/0'/0/2"
+        );
+    }
+
+    #[test]
+    fn custom_path() {
+        let path = HdPath::default()
+            .coin_type(118)
+            .account(1)
+            .change(1)
+            .hardened_change(true)
+            .index(4);
+        assert_eq!(path.derivation_path(330), "m/44'/118'/1'/1'/4");
+        assert_eq!(path.derivation_path(330).parse::<HdPath>().unwrap(), path);
+    }
+
+    #[test]
+    fn invalid_paths() {
+        for path in [
+            "44'/118'/0'/0/0",
+            "m/44'/118'/0'/0",
+            "m/44/118'/0'/0/0",
+            "m/44'/118/0'/0/0",
+            "m/44'/118'/0'/0/a",
+            "m/44'/118'/0'/0/0/0",
+        ] {
+            assert!(path.parse::<HdPath>().is_err(), "{path}");
+        }
+    }
+}
@@ -1,4 +1,6 @@
 #![allow(unused)]
+pub mod armor;
+pub mod hd_path;
 pub mod private;
 pub mod public;
 pub mod signature;
@@ -1,15 +1,15 @@
+use super::hd_path::HdPath;
 use super::public::PublicKey;
-use crate::proto::injective::{InjectivePubKey, ETHEREUM_COIN_TYPE};
+use crate::proto::injective::{InjectivePubKey, ETHEREUM_COIN_TYPE, INJECTIVE_PUB_KEY_TYPE_URL};
 use crate::DaemonError;
-#[cfg(feature = "eth")]
-use ::ethers_core::k256::ecdsa::SigningKey;
 use base64::Engine;
 use bitcoin::secp256k1::{self, Secp256k1};
 use bitcoin::{
-    bip32::{ExtendedPrivKey, IntoDerivationPath},
+    bip32::{ChainCode, ChildNumber, ExtendedPrivKey, Fingerprint, IntoDerivationPath},
     Network,
 };
 use cosmrs::tx::SignerPublicKey;
+use cw_orch_core::environment::AddressAlgorithm;
 use cw_orch_core::log::local_target;
 use hkd32::mnemonic::{Phrase, Seed};
 use prost_types::Any;
@@ -41,7 +41,7 @@ impl PrivateKey {
     ) -> Result<PrivateKey, DaemonError> {
         let phrase = hkd32::mnemonic::Phrase::random(OsRng, hkd32::mnemonic::Language::English);
 
-        PrivateKey::gen_private_key_phrase(secp, phrase, 0, 0, coin_type, "")
+        PrivateKey::gen_private_key_phrase(secp, phrase, &HdPath::default(), coin_type, "")
     }
     /// generate a new private key with a seed phrase
     pub fn new_seed<C: secp256k1::Signing + secp256k1::Context>(
@@ -51,7 +51,7 @@ impl PrivateKey {
     ) -> Result<PrivateKey, DaemonError> {
         let phrase = hkd32::mnemonic::Phrase::random(OsRng, hkd32::mnemonic::Language::English);
 
-        PrivateKey::gen_private_key_phrase(secp, phrase, 0, 0, coin_type, seed_phrase)
+        PrivateKey::gen_private_key_phrase(secp, phrase, &HdPath::default(), coin_type, seed_phrase)
     }
     /// for private key recovery. This is also used by wallet routines to re-hydrate the structure
     pub fn from_words<C: secp256k1::Signing + secp256k1::Context>(
@@ -60,15 +60,25 @@ impl PrivateKey {
         account: u32,
         index: u32,
         coin_type: u32,
+    ) -> Result<PrivateKey, DaemonError> {
+        let path = HdPath::default().account(account).index(index);
+        Self::from_words_with_path(secp, words, &path, coin_type)
+    }
+
+    /// for private key recovery with a custom derivation path.
+    /// `coin_type` is the coin type of the network, used when the path doesn't override it
+    pub fn from_words_with_path<C: secp256k1::Signing + secp256k1::Context>(
+        secp: &Secp256k1<C>,
+        words: &str,
+        path: &HdPath,
+        coin_type: u32,
     ) -> Result<PrivateKey, DaemonError> {
         if words.split(' ').count() != 24 {
             return Err(DaemonError::WrongLength);
         }
 
         match hkd32::mnemonic::Phrase::new(words, hkd32::mnemonic::Language::English) {
-            Ok(phrase) => {
-                PrivateKey::gen_private_key_phrase(secp, phrase, account, index, coin_type, "")
-            }
+            Ok(phrase) => PrivateKey::gen_private_key_phrase(secp, phrase, path, coin_type, ""),
             Err(_) => Err(DaemonError::Phrasing),
         }
     }
@@ -81,9 +91,13 @@ impl PrivateKey {
         coin_type: u32,
     ) -> Result<PrivateKey, DaemonError> {
         match hkd32::mnemonic::Phrase::new(words, hkd32::mnemonic::Language::English) {
-            Ok(phrase) => {
-                PrivateKey::gen_private_key_phrase(secp, phrase, 0, 0, coin_type, seed_pass)
-            }
+            Ok(phrase) => PrivateKey::gen_private_key_phrase(
+                secp,
+                phrase,
+                &HdPath::default(),
+                coin_type,
+                seed_pass,
+            ),
             Err(_) => Err(DaemonError::Phrasing),
         }
     }
@@ -95,37 +109,66 @@ impl PrivateKey {
         index: u32,
         coin_type: u32,
     ) -> Result<PrivateKey, DaemonError> {
-        Self::gen_private_key_raw(secp, raw_key, account, index, coin_type)
+        let path = HdPath::default().account(account).index(index);
+        Self::gen_private_key_raw(secp, raw_key, &path, coin_type)
+    }
+
+    /// Private key derived from raw key bytes with a custom derivation path
+    pub fn from_raw_key_with_path<C: secp256k1::Signing + secp256k1::Context>(
+        secp: &Secp256k1<C>,
+        raw_key: &[u8],
+        path: &HdPath,
+        coin_type: u32,
+    ) -> Result<PrivateKey, DaemonError> {
+        Self::gen_private_key_raw(secp, raw_key, path, coin_type)
     }
 
-    /// generate the public key for this private key
+    /// generate the public key for this private key.
+    /// The address is derived with the algorithm usually associated with the coin type of the key,
+    /// use [`PrivateKey::public_key_with_algorithm`] to derive it with the algorithm declared by the network
     pub fn public_key<C: secp256k1::Signing + secp256k1::Context>(
         &self,
         secp: &Secp256k1<C>,
     ) -> PublicKey {
-        if self.coin_type == ETHEREUM_COIN_TYPE {
-            #[cfg(feature = "eth")]
-            return PublicKey::from_ethers_address_bytes(
-                ethers_core::utils::secret_key_to_address(
-                    &SigningKey::from_slice(self.raw_key().as_slice()).unwrap(),
-                ),
-            );
-            panic!(
-                "Coin Type {} not supported without eth feature",
-                ETHEREUM_COIN_TYPE
-            );
-        }
+        self.public_key_with_algorithm(
+            secp,
+            AddressAlgorithm::default_for_coin_type(self.coin_type),
+        )
+    }
 
+    /// generate the public key for this private key, with the address derived using `algorithm`
+    pub fn public_key_with_algorithm<C: secp256k1::Signing + secp256k1::Context>(
+        &self,
+        secp: &Secp256k1<C>,
+        algorithm: AddressAlgorithm,
+    ) -> PublicKey {
         let x = self.private_key.private_key.public_key(secp);
-        PublicKey::from_bitcoin_public_key(&bitcoin::PublicKey::new(x))
+        match algorithm {
+            AddressAlgorithm::Cosmos => {
+                PublicKey::from_bitcoin_public_key(&bitcoin::PublicKey::new(x))
+            }
+            AddressAlgorithm::Ethereum => {
+                PublicKey::from_eth_public_key(&x.serialize_uncompressed())
+            }
+        }
     }
 
     pub fn get_injective_public_key<C: secp256k1::Signing + secp256k1::Context>(
         &self,
         secp: &Secp256k1<C>,
+    ) -> SignerPublicKey {
+        self.get_eth_public_key(secp, INJECTIVE_PUB_KEY_TYPE_URL)
+    }
+
+    /// ethsecp256k1 public key of this private key, with the public key type url of the chain
+    /// (see [`default_eth_pub_key_type_url`](crate::proto::injective::default_eth_pub_key_type_url))
+    pub fn get_eth_public_key<C: secp256k1::Signing + secp256k1::Context>(
+        &self,
+        secp: &Secp256k1<C>,
+        type_url: &str,
     ) -> SignerPublicKey {
         use base64::engine::general_purpose;
-        use cosmrs::tx::MessageExt;
+        use prost::Message;
         use secp256k1::SecretKey;
 
         let secret_key = SecretKey::from_slice(self.raw_key().as_slice()).unwrap();
@@ -135,18 +178,34 @@ impl PrivateKey {
 
         log::debug!(target: &local_target(), "{:?}, public key", general_purpose::STANDARD.encode(vec_pk));
 
-        let inj_key = InjectivePubKey { key: vec_pk.into() };
+        // Injective and ethermint public keys share the same proto definition
+        let eth_key = InjectivePubKey { key: vec_pk.into() };
 
-        cosmrs::Any::from_msg(&inj_key).unwrap().try_into().unwrap()
+        cosmrs::Any {
+            type_url: type_url.to_string(),
+            value: eth_key.encode_to_vec(),
+        }
+        .try_into()
+        .unwrap()
     }
 
     pub fn get_signer_public_key<C: secp256k1::Signing + secp256k1::Context>(
         &self,
         secp: &Secp256k1<C>,
+    ) -> Option<SignerPublicKey> {
+        self.get_signer_public_key_with_type(secp, INJECTIVE_PUB_KEY_TYPE_URL)
+    }
+
+    /// Public key used in the signer infos of the transactions.
+    /// `eth_pub_key_type_url` is the public key type url used by chains with the [`ETHEREUM_COIN_TYPE`]
+    pub fn get_signer_public_key_with_type<C: secp256k1::Signing + secp256k1::Context>(
+        &self,
+        secp: &Secp256k1<C>,
+        eth_pub_key_type_url: &str,
     ) -> Option<SignerPublicKey> {
         if self.coin_type == ETHEREUM_COIN_TYPE {
             #[cfg(feature = "eth")]
-            return Some(self.get_injective_public_key(secp));
+            return Some(self.get_eth_public_key(secp, eth_pub_key_type_url));
             panic!(
                 "Coin Type {} not supported without eth feature",
                 ETHEREUM_COIN_TYPE
@@ -161,6 +220,27 @@ impl PrivateKey {
         )
     }
 
+    /// Private key from the bytes of a secp256k1 secret key, without any derivation.
+    /// Used for keys exported by the chain CLIs, that don't carry their mnemonic.
+    pub fn from_secret_key(secret_key: &[u8], coin_type: u32) -> Result<PrivateKey, DaemonError> {
+        let private_key = ExtendedPrivKey {
+            network: Network::Bitcoin,
+            depth: 0,
+            parent_fingerprint: Fingerprint::default(),
+            child_number: ChildNumber::from_normal_idx(0)?,
+            private_key: secp256k1::SecretKey::from_slice(secret_key)?,
+            chain_code: ChainCode::from([0u8; 32]),
+        };
+        Ok(PrivateKey {
+            account: 0,
+            index: 0,
+            coin_type,
+            mnemonic: None,
+            root_private_key: private_key,
+            private_key,
+        })
+    }
+
     pub fn raw_key(&self) -> [u8; secp256k1::constants::SECRET_KEY_SIZE] {
         self.private_key.private_key.secret_bytes()
     }
@@ -169,14 +249,12 @@ impl PrivateKey {
     fn gen_private_key_phrase<C: secp256k1::Signing + secp256k1::Context>(
         secp: &Secp256k1<C>,
         phrase: Phrase,
-        account: u32,
-        index: u32,
+        path: &HdPath,
         coin_type: u32,
         seed_phrase: &str,
     ) -> Result<PrivateKey, DaemonError> {
         let seed = phrase.to_seed(seed_phrase);
-        let mut private_key =
-            Self::gen_private_key_raw(secp, seed.as_bytes(), account, index, coin_type)?;
+        let mut private_key = Self::gen_private_key_raw(secp, seed.as_bytes(), path, coin_type)?;
         private_key.mnemonic = Some(phrase);
         Ok(private_key)
     }
@@ -185,19 +263,19 @@ impl PrivateKey {
     fn gen_private_key_raw<C: secp256k1::Signing + secp256k1::Context>(
         secp: &Secp256k1<C>,
         raw_key: &[u8],
-        account: u32,
-        index: u32,
+        path: &HdPath,
         coin_type: u32,
     ) -> Result<PrivateKey, DaemonError> {
         let root_private_key = ExtendedPrivKey::new_master(Network::Bitcoin, raw_key).unwrap();
         // For injective: https://docs.injective.network/learn/basic-concepts/accounts#injective-accounts
-        let path = format!("m/44'/{coin_type}'/{account}'/0/{index}");
-        let derivation_path = path.into_derivation_path()?;
+        let derivation_path = path.derivation_path(coin_type).into_derivation_path()?;
 
         let private_key = root_private_key.derive_priv(secp, &derivation_path)?;
         Ok(PrivateKey {
-            account,
-            index,
+            account: path.account,
+            index: path.index,
+            // The coin type of the network defines how addresses are derived and txs are signed,
+            // even when the key is derived with another coin type
             coin_type,
             mnemonic: None,
             root_private_key,
@@ -302,6 +380,43 @@ mod tst {
         Ok(())
     }
 
+    #[test]
+    pub fn tst_words_with_path() -> anyhow::Result<()> {
+        let str_1 = "wonder caution square unveil april art add hover spend smile proud admit modify old copper throw crew happy nature luggage reopen exhibit ordinary napkin";
+        let secp = Secp256k1::new();
+
+        // Default path is the same as the network derivation
+        let pk = PrivateKey::from_words_with_path(&secp, str_1, &HdPath::default(), 330)?;
+        assert_eq!(
+            pk.raw_key(),
+            PrivateKey::from_words(&secp, str_1, 0, 0, 330)?.raw_key()
+        );
+
+        // A terra key derived with the 118 coin type
+        let path: HdPath = "m/44'/118'/0'/0/1".parse()?;
+        let pk = PrivateKey::from_words_with_path(&secp, str_1, &path, 330)?;
+        assert_eq!(pk.coin_type, 330);
+        assert_eq!(pk.index, 1);
+        assert_eq!(
+            pk.raw_key(),
+            PrivateKey::from_words(&secp, str_1, 0, 1, 118)?.raw_key()
+        );
+
+        // Custom account levels
+        let pk = PrivateKey::from_words_with_path(
+            &secp,
+            str_1,
+            &HdPath::default().account(2).hardened_account(false),
+            330,
+        )?;
+        assert_ne!(
+            pk.raw_key(),
+            PrivateKey::from_words(&secp, str_1, 2, 0, 330)?.raw_key()
+        );
+
+        Ok(())
+    }
+
     #[cfg(feature = "eth")]
     #[test]
     pub fn inj() -> anyhow::Result<()> {
@@ -329,6 +444,71 @@ mod tst {
         Ok(())
     }
 
+    #[test]
+    pub fn address_algorithms() -> anyhow::Result<()> {
+        let str_1: &str = "across left ignore gold echo argue track joy hire release captain enforce hotel wide flash hotel brisk joke midnight duck spare drop chronic stool";
+        let secp = Secp256k1::new();
+        let pk = PrivateKey::from_words(&secp, str_1, 0, 0, 118)?;
+
+        let eth_address =
+            ethers_core::utils::secret_key_to_address(&SigningKey::from_slice(&pk.raw_key())?);
+        let eth_pub_key = pk.public_key_with_algorithm(&secp, AddressAlgorithm::Ethereum);
+        assert_eq!(
+            eth_pub_key.raw_address.unwrap(),
+            eth_address.as_bytes().to_vec()
+        );
+
+        // Coin type 118 keys use the cosmos derivation by default
+        let cosmos_pub_key = pk.public_key_with_algorithm(&secp, AddressAlgorithm::Cosmos);
+        assert_eq!(
+            pk.public_key(&secp).account("juno")?,
+            cosmos_pub_key.account("juno")?
+        );
+        assert_ne!(
+            eth_pub_key.account("juno")?,
+            cosmos_pub_key.account("juno")?
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "eth")]
+    #[test]
+    pub fn ethermint() -> anyhow::Result<()> {
+        use crate::proto::injective::{EthSigner, ETHERMINT_PUB_KEY_TYPE_URL};
+
+        let str_1: &str = "across left ignore gold echo argue track joy hire release captain enforce hotel wide flash hotel brisk joke midnight duck spare drop chronic stool";
+        let secp = Secp256k1::new();
+        let pk = PrivateKey::from_words(&secp, str_1, 0, 0, ETHEREUM_COIN_TYPE)?;
+
+        // Same key as injective, with the evmos prefix
+        let evmos_address = pk.public_key(&secp).account("evmos")?;
+        let inj_address = pk.public_key(&secp).account("inj")?;
+        assert_eq!(
+            bitcoin::bech32::decode(&evmos_address)?.1,
+            bitcoin::bech32::decode(&inj_address)?.1
+        );
+
+        let Some(SignerPublicKey::Any(any)) =
+            pk.get_signer_public_key_with_type(&secp, ETHERMINT_PUB_KEY_TYPE_URL)
+        else {
+            panic!("expected an ethsecp256k1 public key");
+        };
+        assert_eq!(any.type_url, ETHERMINT_PUB_KEY_TYPE_URL);
+
+        let Some(SignerPublicKey::Any(any)) = pk.get_signer_public_key(&secp) else {
+            panic!("expected an ethsecp256k1 public key");
+        };
+        assert_eq!(any.type_url, INJECTIVE_PUB_KEY_TYPE_URL);
+
+        // EIP-191 signatures can be recovered by ethereum tooling
+        let signature = pk.sign_eip191(b"cw-orch")?;
+        let signature = ethers_core::types::Signature::try_from(signature.as_slice())?;
+        let wallet = ethers_signers::Wallet::from_bytes(&pk.raw_key())?;
+        signature.verify("cw-orch", wallet.address())?;
+
+        Ok(())
+    }
+
     // #[test]
     // pub fn test_sign() -> anyhow::Result<()> {
     //     // This test is using message from python SDK.. so these keys generate same sigs as they do.
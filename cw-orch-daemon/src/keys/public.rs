@@ -5,6 +5,7 @@ pub use ed25519_dalek::VerifyingKey as Ed25519;
 use ring::digest::{Context, SHA256};
 use ripemd::{Digest as _, Ripemd160};
 use serde::{Deserialize, Serialize};
+use sha3::Keccak256;
 static BECH32_PUBKEY_DATA_PREFIX_SECP256K1: [u8; 5] = [0xeb, 0x5a, 0xe9, 0x87, 0x21]; // "eb5ae98721";
 static BECH32_PUBKEY_DATA_PREFIX_ED25519: [u8; 5] = [0x16, 0x24, 0xde, 0x64, 0x20]; // "eb5ae98721";
 
@@ -169,6 +170,16 @@ impl PublicKey {
         }
     }
 
+    /// Generate Public key from an uncompressed secp256k1 public key, with an ethereum address (last 20 bytes of its keccak256 hash)
+    pub fn from_eth_public_key(uncompressed_public_key: &[u8]) -> PublicKey {
+        PublicKey {
+            raw_pub_key: None,
+            raw_address: Some(PublicKey::address_from_eth_public_key(
+                uncompressed_public_key,
+            )),
+        }
+    }
+
     fn check_prefix_and_length(
         prefix: &str,
         data: &str,
@@ -245,6 +256,20 @@ impl PublicKey {
         address
     }
 
+    /**
+    Gets a raw ethereum address from an uncompressed (65 bytes) or raw (64 bytes) secp256k1 public key.
+
+    @param publicKey uncompressed public key
+    */
+    pub fn address_from_eth_public_key(public_key: &[u8]) -> Vec<u8> {
+        let public_key = match public_key {
+            [0x04, raw @ ..] if raw.len() == 64 => raw,
+            raw => raw,
+        };
+        let hash = Keccak256::digest(public_key);
+        hash[12..].to_vec()
+    }
+
     /**
     Gets a raw address from a  ed25519 public key.
 
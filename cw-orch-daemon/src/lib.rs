@@ -2,29 +2,61 @@
 //!
 //! The `Daemon` type is a synchronous wrapper around the `DaemonAsync` type and can be used as a contract execution environment.
 
+pub mod address_book;
+pub mod airdrop;
+pub mod batch;
+pub mod broadcast_backend;
 pub mod builder;
+pub mod cache;
+pub mod call_trace;
 pub mod channel;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod code_pinning;
+pub mod config;
 pub mod core;
+pub mod devnet;
 pub mod error;
+pub mod execute_with;
+pub mod fees;
+pub mod frontend_export;
+pub mod gas_table;
 pub mod json_lock;
+pub mod key_rotation;
 /// Proto types for different blockchains
 pub mod proto;
+pub mod proto_registry;
+pub mod prune;
 pub mod sender;
+pub mod sequence;
+pub mod source_verification;
 pub mod state;
 pub mod sync;
 pub mod tx_resp;
 // expose these as mods as they can grow
+pub mod emergency;
 pub mod env;
+#[cfg(feature = "keyring")]
+pub mod keyring;
 pub mod keys;
 pub mod live_mock;
-mod log;
+pub mod log;
+pub mod manifest;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod network_config;
 pub mod queriers;
+pub mod query_only_sender;
+pub mod receipts;
+pub mod runtime;
 pub mod tx_broadcaster;
 pub mod tx_builder;
+pub mod tx_decode;
+pub mod verify;
 pub use self::{builder::*, channel::*, core::*, error::*, state::*, sync::*, tx_resp::*};
 pub use cw_orch_networks::networks;
-pub use sender::Wallet;
-pub use tx_builder::TxBuilder;
+pub use sender::{QuerySender, Wallet};
+pub use tx_builder::{TxBuilder, TxOptions};
 mod cosmos_proto_patches;
 
 pub(crate) mod cosmos_modules {
@@ -53,5 +85,6 @@ pub(crate) mod cosmos_modules {
 }
 
 lazy_static::lazy_static! {
-    pub static ref RUNTIME: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    /// Global runtime, see [`runtime`] to configure it
+    pub static ref RUNTIME: &'static tokio::runtime::Runtime = runtime::global_runtime();
 }
@@ -1,9 +1,32 @@
 //! Live mock is a mock that uses a live chain to query for data.
 //! It can be used to do chain-backed unit-testing. It can't be used for state-changing operations.
+//!
+//! Supported queries:
+//! - Bank: `Balance`, `AllBalances` and `Supply`
+//! - Staking: `BondedDenom`, `AllDelegations`, `AllValidators` and `Validator`
+//! - Wasm: `Smart`, `Raw` and `ContractInfo`
+//!
+//! The querier can be configured with a [`LiveMockConfig`]:
+//! - Responses can be cached, so that repeated queries don't hit the node again and return consistent results within a test.
+//! - Responses can be recorded to a file and replayed later, so that tests can run offline and deterministically.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{live_mock::{mock_dependencies_with_config, LiveMockConfig}, networks::JUNO_1};
+//!
+//! // Record the responses of the node the first time, then replay them with `LiveMockConfig::replay`
+//! let deps = mock_dependencies_with_config(
+//!     JUNO_1.into(),
+//!     LiveMockConfig::new().cache(true).record("tests/live_mock/juno.json"),
+//! )
+//! .unwrap();
+//! ```
 
 use crate::queriers::Bank;
 use crate::queriers::CosmWasm;
 use crate::queriers::Staking;
+use crate::queriers::StakingBondStatus;
+use crate::DaemonError;
 use crate::RUNTIME;
 use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::Addr;
@@ -14,29 +37,33 @@ use cosmwasm_std::Binary;
 use cosmwasm_std::Delegation;
 use cosmwasm_std::Empty;
 use cosmwasm_std::StakingQuery;
+use cosmwasm_std::SupplyResponse;
 use cosmwasm_std::{
     from_json, to_json_binary, Coin, ContractResult, OwnedDeps, Querier, QuerierResult,
     QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use cosmwasm_std::{AllDelegationsResponse, BondedDenomResponse};
-use cw_orch_core::environment::BankQuerier;
+use cosmwasm_std::{AllValidatorsResponse, ValidatorResponse};
 use cw_orch_core::environment::ChainInfoOwned;
-use cw_orch_core::environment::WasmQuerier;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::marker::PhantomData;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Mutex;
 use tonic::transport::Channel;
 
 use crate::channel::GrpcChannel;
 
-fn to_cosmwasm_coin(c: cosmrs::proto::cosmos::base::v1beta1::Coin) -> Coin {
-    Coin {
-        amount: Uint128::from_str(&c.amount).unwrap(),
+fn to_cosmwasm_coin(c: cosmrs::proto::cosmos::base::v1beta1::Coin) -> Result<Coin, DaemonError> {
+    Ok(Coin {
+        amount: Uint128::from_str(&c.amount)?,
         denom: c.denom,
-    }
+    })
 }
 
 const QUERIER_ERROR: &str =
-    "Only Bank balances and Wasm (raw + smart) and Some staking queries are covered for now";
+    "Only Bank (balances + supply), Wasm (raw + smart + contract info) and some staking queries are covered for now";
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
@@ -53,13 +80,95 @@ pub fn mock_dependencies(
     }
 }
 
+/// Same as [`mock_dependencies`], with a custom [`LiveMockConfig`]
+pub fn mock_dependencies_with_config(
+    chain_info: ChainInfoOwned,
+    config: LiveMockConfig,
+) -> Result<OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, DaemonError> {
+    Ok(OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: WasmMockQuerier::with_config(chain_info, config)?,
+        custom_query_type: PhantomData,
+    })
+}
+
+/// Where the responses of the live mock come from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum LiveMockMode {
+    /// Query the node for every request
+    #[default]
+    Live,
+    /// Query the node and record all the responses to a file.
+    /// The file is written when the querier is dropped or with [`WasmMockQuerier::save_recording`]
+    Record(PathBuf),
+    /// Answer with the responses recorded in a file, without connecting to the node.
+    /// Requests that were not recorded return an error
+    Replay(PathBuf),
+}
+
+/// Configuration of the [`WasmMockQuerier`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LiveMockConfig {
+    /// Cache responses, so that identical requests are only sent once to the node
+    pub cache: bool,
+    /// Source of the responses
+    pub mode: LiveMockMode,
+    /// gRPC endpoint to use instead of the endpoints of the chain.
+    /// Useful to always query the same node in tests, as nodes can be at different heights
+    pub grpc_url: Option<String>,
+}
+
+impl LiveMockConfig {
+    /// Default configuration: no cache, live queries on the chain endpoints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables the cache
+    pub fn cache(mut self, cache: bool) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Records the responses to `path`
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.mode = LiveMockMode::Record(path.into());
+        self
+    }
+
+    /// Replays the responses recorded in `path`
+    pub fn replay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.mode = LiveMockMode::Replay(path.into());
+        self
+    }
+
+    /// Queries this gRPC endpoint only
+    pub fn grpc_url(mut self, grpc_url: impl Into<String>) -> Self {
+        self.grpc_url = Some(grpc_url.into());
+        self
+    }
+}
+
 /// Querier struct that fetches queries on-chain directly
 pub struct WasmMockQuerier {
-    channel: Channel,
+    /// Not connected when replaying recorded responses
+    channel: Option<Channel>,
+    config: LiveMockConfig,
+    /// Responses indexed by raw request
+    responses: Mutex<HashMap<Vec<u8>, QuerierResult>>,
 }
 
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        // Recorded responses are also used as a cache
+        let store_responses = self.config.cache || self.config.mode != LiveMockMode::Live;
+        if store_responses {
+            if let Some(response) = self.responses.lock().unwrap().get(bin_request) {
+                return response.clone();
+            }
+        }
+
         // MockQuerier doesn't support Custom, so we ignore it completely here
         let request: QueryRequest<Empty> = match from_json(bin_request) {
             Ok(v) => v,
@@ -70,7 +179,14 @@ impl Querier for WasmMockQuerier {
                 })
             }
         };
-        self.handle_query(&request)
+        let response = self.handle_query(&request);
+        if store_responses && self.channel.is_some() {
+            self.responses
+                .lock()
+                .unwrap()
+                .insert(bin_request.to_vec(), response.clone());
+        }
+        response
     }
 }
 
@@ -78,128 +194,255 @@ impl WasmMockQuerier {
     /// Function used to handle a query and customize the query behavior
     /// This implements some queries by querying an actual node for the responses
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        let Some(channel) = &self.channel else {
+            return SystemResult::Err(SystemError::InvalidRequest {
+                error: "Query was not recorded, record it again with `LiveMockConfig::record`"
+                    .to_string(),
+                request: to_json_binary(&request).unwrap_or_default(),
+            });
+        };
         let handle = RUNTIME.handle();
         match &request {
             QueryRequest::Wasm(x) => {
-                let querier = CosmWasm {
-                    channel: self.channel.clone(),
-                    rt_handle: Some(handle.clone()),
-                };
+                let querier = CosmWasm::new_async(channel.clone());
                 match x {
                     WasmQuery::Smart { contract_addr, msg } => {
                         // We forward the request to the cosmwasm querier
-
                         let query_result: Result<Binary, _> = handle
                             .block_on(
                                 querier._contract_state(contract_addr.to_string(), msg.to_vec()),
                             )
                             .map(|query_result| query_result.into());
-                        SystemResult::Ok(ContractResult::from(query_result))
+                        to_querier_result(query_result)
                     }
                     WasmQuery::Raw { contract_addr, key } => {
-                        // We forward the request to the cosmwasm querie
-                        let query_result = querier
-                            .raw_query(contract_addr.to_string(), key.to_vec())
-                            .map(|query_result| query_result.into());
-
-                        SystemResult::Ok(ContractResult::from(query_result))
+                        // We forward the request to the cosmwasm querier
+                        let query_result: Result<Binary, _> = handle
+                            .block_on(
+                                querier
+                                    ._contract_raw_state(contract_addr.to_string(), key.to_vec()),
+                            )
+                            .map(|query_result| query_result.data.into());
+                        to_querier_result(query_result)
+                    }
+                    WasmQuery::ContractInfo { contract_addr } => {
+                        let query_result =
+                            handle.block_on(querier._contract_info(contract_addr.to_string()));
+                        to_querier_result(serialize(query_result))
                     }
-                    _ => SystemResult::Err(SystemError::InvalidRequest {
-                        error: QUERIER_ERROR.to_string(),
-                        request: to_json_binary(&request).unwrap(),
-                    }),
+                    _ => unsupported(request),
                 }
             }
             QueryRequest::Bank(x) => {
-                let querier = Bank {
-                    channel: self.channel.clone(),
-                    rt_handle: Some(handle.clone()),
-                };
+                let querier = Bank::new_async(channel.clone());
                 match x {
                     BankQuery::Balance { address, denom } => {
-                        let query_result =
-                            querier.balance(address, Some(denom.clone())).map(|result| {
-                                to_json_binary(&BalanceResponse {
-                                    amount: result[0].clone(),
-                                })
-                                .unwrap()
+                        let query_result = handle
+                            .block_on(querier._balance(address, Some(denom.clone())))
+                            .map(|result| BalanceResponse {
+                                amount: result[0].clone(),
                             });
-                        SystemResult::Ok(ContractResult::from(query_result))
+                        to_querier_result(serialize(query_result))
                     }
                     BankQuery::AllBalances { address } => {
-                        let query_result = querier
-                            .balance(address, None)
-                            .map(|result| AllBalanceResponse { amount: result })
-                            .map(|query_result| to_json_binary(&query_result))
-                            .unwrap();
-                        SystemResult::Ok(ContractResult::from(query_result))
+                        let query_result = handle
+                            .block_on(querier._balance(address, None))
+                            .map(|result| AllBalanceResponse { amount: result });
+                        to_querier_result(serialize(query_result))
+                    }
+                    BankQuery::Supply { denom } => {
+                        let query_result =
+                            handle.block_on(querier._supply_of(denom)).map(|amount| {
+                                let mut response = SupplyResponse::default();
+                                response.amount = amount;
+                                response
+                            });
+                        to_querier_result(serialize(query_result))
                     }
-                    _ => SystemResult::Err(SystemError::InvalidRequest {
-                        error: QUERIER_ERROR.to_string(),
-                        request: to_json_binary(&request).unwrap(),
-                    }),
+                    _ => unsupported(request),
                 }
             }
             QueryRequest::Staking(x) => {
-                let querier = Staking::new_async(self.channel.clone());
+                let querier = Staking::new_async(channel.clone());
                 match x {
                     StakingQuery::BondedDenom {} => {
-                        let query_result = handle
-                            .block_on(querier._params())
-                            .map(|result| BondedDenomResponse {
-                                denom: result.params.unwrap().bond_denom,
+                        let query_result = handle.block_on(querier._params()).and_then(|result| {
+                            Ok(BondedDenomResponse {
+                                denom: result
+                                    .params
+                                    .ok_or(DaemonError::StdErr("Missing staking params".into()))?
+                                    .bond_denom,
                             })
-                            .map(|query_result| to_json_binary(&query_result))
-                            .unwrap();
-                        SystemResult::Ok(ContractResult::from(query_result))
+                        });
+                        to_querier_result(serialize(query_result))
                     }
-                    // This query is not perfect. I guess that on_chain you should be able to get ALL delegations and not a paginated result
-                    // TODO, do better here
                     StakingQuery::AllDelegations { delegator } => {
+                        let query_result = self.all_delegations(&querier, delegator);
+                        to_querier_result(serialize(query_result))
+                    }
+                    StakingQuery::AllValidators {} => {
                         let query_result = handle
-                            .block_on(querier._delegator_delegations(delegator, None))
-                            .map(|result| AllDelegationsResponse {
-                                delegations: result
-                                    .delegation_responses
-                                    .into_iter()
-                                    .filter_map(|delegation| {
-                                        delegation.delegation.map(|d| Delegation {
-                                            delegator: Addr::unchecked(d.delegator_address),
-                                            validator: d.validator_address,
-                                            amount: to_cosmwasm_coin(delegation.balance.unwrap()),
-                                        })
-                                    })
-                                    .collect(),
-                            })
-                            .map(|query_result| to_json_binary(&query_result))
-                            .unwrap();
-                        SystemResult::Ok(ContractResult::from(query_result))
+                            .block_on(querier._validators(StakingBondStatus::Bonded))
+                            .map(|validators| {
+                                let mut response = AllValidatorsResponse::default();
+                                response.validators = validators;
+                                response
+                            });
+                        to_querier_result(serialize(query_result))
+                    }
+                    StakingQuery::Validator { address } => {
+                        let query_result =
+                            handle
+                                .block_on(querier._validator(address))
+                                .map(|validator| {
+                                    let mut response = ValidatorResponse::default();
+                                    response.validator = Some(validator);
+                                    response
+                                });
+                        to_querier_result(serialize(query_result))
                     }
-                    _ => todo!(),
+                    _ => unsupported(request),
                 }
             }
-            _ => SystemResult::Err(SystemError::InvalidRequest {
-                error: QUERIER_ERROR.to_string(),
-                request: to_json_binary(&request).unwrap(),
-            }),
+            _ => unsupported(request),
+        }
+    }
+
+    /// Queries all the pages of delegations of the delegator
+    fn all_delegations(
+        &self,
+        querier: &Staking,
+        delegator: &str,
+    ) -> Result<AllDelegationsResponse, DaemonError> {
+        let mut delegations = vec![];
+        let mut next_key = None;
+        loop {
+            let pagination =
+                next_key.map(
+                    |key| cosmrs::proto::cosmos::base::query::v1beta1::PageRequest {
+                        key,
+                        ..Default::default()
+                    },
+                );
+            let result = RUNTIME.block_on(querier._delegator_delegations(delegator, pagination))?;
+            for delegation in result.delegation_responses {
+                let (Some(d), Some(balance)) = (delegation.delegation, delegation.balance) else {
+                    continue;
+                };
+                delegations.push(Delegation {
+                    delegator: Addr::unchecked(d.delegator_address),
+                    validator: d.validator_address,
+                    amount: to_cosmwasm_coin(balance)?,
+                });
+            }
+            match result.pagination {
+                Some(page) if !page.next_key.is_empty() => next_key = Some(page.next_key),
+                _ => break,
+            }
         }
+        Ok(AllDelegationsResponse { delegations })
     }
 }
 
 impl WasmMockQuerier {
     /// Creates a querier from chain information
     pub fn new(chain: ChainInfoOwned) -> Self {
-        let channel = RUNTIME
-            .block_on(GrpcChannel::connect(
-                &chain.grpc_urls,
-                chain.chain_id.as_str(),
-            ))
-            .unwrap();
+        Self::with_config(chain, LiveMockConfig::default()).unwrap()
+    }
 
-        WasmMockQuerier { channel }
+    /// Creates a querier from chain information with a custom configuration
+    pub fn with_config(
+        mut chain: ChainInfoOwned,
+        config: LiveMockConfig,
+    ) -> Result<Self, DaemonError> {
+        let mut responses = HashMap::new();
+        let channel = match &config.mode {
+            LiveMockMode::Replay(path) => {
+                let recorded: BTreeMap<String, QuerierResult> =
+                    serde_json::from_str(&std::fs::read_to_string(path)?)?;
+                responses.extend(
+                    recorded
+                        .into_iter()
+                        .map(|(request, response)| (request.into_bytes(), response)),
+                );
+                None
+            }
+            LiveMockMode::Live | LiveMockMode::Record(_) => {
+                if let Some(grpc_url) = &config.grpc_url {
+                    chain.grpc_urls = vec![grpc_url.clone()];
+                }
+                Some(RUNTIME.block_on(GrpcChannel::connect(
+                    &chain.grpc_urls,
+                    chain.chain_id.as_str(),
+                ))?)
+            }
+        };
+
+        Ok(WasmMockQuerier {
+            channel,
+            config,
+            responses: Mutex::new(responses),
+        })
+    }
+
+    /// Removes all the cached responses.
+    /// In [`LiveMockMode::Record`] mode, this also removes the responses recorded so far.
+    /// Does nothing in [`LiveMockMode::Replay`] mode
+    pub fn clear_cache(&self) {
+        if self.channel.is_some() {
+            self.responses.lock().unwrap().clear()
+        }
+    }
+
+    /// Writes the recorded responses to the recording file.
+    /// Does nothing if the querier is not in [`LiveMockMode::Record`] mode
+    pub fn save_recording(&self) -> Result<(), DaemonError> {
+        let LiveMockMode::Record(path) = &self.config.mode else {
+            return Ok(());
+        };
+        let recorded: BTreeMap<String, QuerierResult> = self
+            .responses
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(request, response)| {
+                (
+                    String::from_utf8_lossy(request).to_string(),
+                    response.clone(),
+                )
+            })
+            .collect();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&recorded)?)?;
+        Ok(())
     }
 }
 
+impl Drop for WasmMockQuerier {
+    fn drop(&mut self) {
+        if let Err(e) = self.save_recording() {
+            log::error!("Failed to save the live mock recording: {}", e);
+        }
+    }
+}
+
+fn serialize<T: Serialize>(result: Result<T, DaemonError>) -> Result<Binary, DaemonError> {
+    Ok(to_json_binary(&result?)?)
+}
+
+fn to_querier_result(result: Result<Binary, DaemonError>) -> QuerierResult {
+    SystemResult::Ok(ContractResult::from(result))
+}
+
+fn unsupported(request: &QueryRequest<Empty>) -> QuerierResult {
+    SystemResult::Err(SystemError::InvalidRequest {
+        error: QUERIER_ERROR.to_string(),
+        request: to_json_binary(request).unwrap_or_default(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -241,4 +484,32 @@ mod tests {
         // We can't really test that response, but it has to unwrap at least !
         Ok(())
     }
+
+    #[test]
+    fn record_and_replay() -> Result<(), anyhow::Error> {
+        let recording = std::env::temp_dir().join(format!(
+            "cw-orch-live-mock-recording-{}.json",
+            std::process::id()
+        ));
+        let request = QueryRequest::<Empty>::Staking(StakingQuery::BondedDenom {});
+
+        let deps =
+            mock_dependencies_with_config(JUNO_1.into(), LiveMockConfig::new().record(&recording))?;
+        let recorded: BondedDenomResponse = deps.as_ref().querier.query(&request)?;
+        drop(deps);
+
+        let deps =
+            mock_dependencies_with_config(JUNO_1.into(), LiveMockConfig::new().replay(&recording))?;
+        let replayed: BondedDenomResponse = deps.as_ref().querier.query(&request)?;
+        assert_eq!(replayed, recorded);
+
+        // Requests that were not recorded fail
+        deps.as_ref()
+            .querier
+            .query::<AllBalanceResponse>(&QueryRequest::Bank(BankQuery::AllBalances {
+                address: "juno1rkhrfuq7k2k68k0hctrmv8efyxul6tgn8hny6y".to_string(),
+            }))
+            .unwrap_err();
+        Ok(())
+    }
 }
@@ -1,11 +1,43 @@
+//! Logging facilities of the daemon.
+//!
+//! The daemon logs through the `log` facade, any logger can be used to display them (e.g. `env_logger`).
+//! For CI deployments, [`DaemonLogger`] outputs machine-readable logs:
+//! - Records can be formatted as one JSON object per line with [`LogFormat::Json`].
+//! - The lifecycle of every transaction (simulation, broadcast, confirmation) is logged as a [`TxEvent`]
+//!   under the [`TX_LIFECYCLE_TARGET`] target, with the chain id and the hash of the transaction.
+//! - Transaction events can additionally be appended to one file per chain id, see [`DaemonLogger::chain_log_dir`].
+//!
+//! ## Example
+//! ```no_run
+//! use cw_orch_daemon::log::{DaemonLogger, LogFormat};
+//!
+//! DaemonLogger::new()
+//!     .format(LogFormat::Json)
+//!     .chain_log_dir("./logs")
+//!     .init()
+//!     .unwrap();
+//! ```
+//! The format and the chain log directory can also be set with the `CW_ORCH_LOG_FORMAT` and `CW_ORCH_CHAIN_LOG_DIR` env variables,
+//! see [`DaemonLogger::from_env`].
+
 use crate::env::DaemonEnvVars;
 
 use crate::DaemonError;
 
-use std::sync::Once;
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Mutex, Once};
 
 static LOGS_DISABLED: Once = Once::new();
 
+/// Target of the transaction lifecycle events
+pub const TX_LIFECYCLE_TARGET: &str = "cw_orch::tx";
+
 // Prints a warning if log is disabled for the application
 pub fn print_if_log_disabled() -> Result<(), DaemonError> {
     LOGS_DISABLED.call_once(|| {
@@ -20,3 +52,307 @@ pub fn print_if_log_disabled() -> Result<(), DaemonError> {
     });
     Ok(())
 }
+
+/// Stage of the lifecycle of a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "stage")]
+pub enum TxStage {
+    /// The transaction was simulated
+    Simulated {
+        /// Gas used by the simulation
+        gas_used: u64,
+        /// Gas limit set on the transaction
+        gas_limit: u64,
+        /// Fee paid for the transaction, e.g. `1500ujuno`
+        fee: String,
+    },
+    /// The transaction was accepted in the mempool
+    Broadcasted {
+        /// Hash of the transaction
+        txhash: String,
+    },
+    /// The transaction was included in a block
+    Confirmed {
+        /// Hash of the transaction
+        txhash: String,
+        /// Height of the block including the transaction
+        height: u64,
+        /// Gas used by the transaction
+        gas_used: u64,
+        /// Result code of the transaction, 0 on success
+        code: usize,
+    },
+    /// The transaction couldn't be broadcasted or confirmed
+    Failed {
+        /// Hash of the transaction, if it was broadcasted
+        txhash: Option<String>,
+        /// Reason of the failure
+        error: String,
+    },
+}
+
+/// Lifecycle event of a transaction, logged as JSON under the [`TX_LIFECYCLE_TARGET`] target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxEvent {
+    /// Chain the transaction is sent to
+    pub chain_id: String,
+    /// Sender of the transaction
+    pub sender: String,
+    /// Stage reached by the transaction
+    #[serde(flatten)]
+    pub stage: TxStage,
+}
+
+impl TxEvent {
+    /// Event of `sender` on `chain_id`
+    pub fn new(chain_id: impl Into<String>, sender: impl Into<String>, stage: TxStage) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            sender: sender.into(),
+            stage,
+        }
+    }
+
+    /// Logs the event, failures at the warn level and other stages at the info level
+    pub fn log(&self) {
+        let level = match self.stage {
+            TxStage::Failed { .. } => log::Level::Warn,
+            _ => log::Level::Info,
+        };
+        if log::log_enabled!(target: TX_LIFECYCLE_TARGET, level) {
+            if let Ok(event) = serde_json::to_string(self) {
+                log::log!(target: TX_LIFECYCLE_TARGET, level, "{event}");
+            }
+        }
+    }
+}
+
+/// Output format of the [`DaemonLogger`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// `<timestamp> <level> <target>: <message>`
+    #[default]
+    Text,
+    /// One JSON object per line, with the `timestamp`, `level`, `target` and `message` fields.
+    /// Transaction lifecycle records also contain the [`TxEvent`] under the `event` field
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "Unknown log format {other}, expected `text` or `json`"
+            )),
+        }
+    }
+}
+
+/// Logger writing to stderr, with optional JSON output and per-chain transaction log files
+pub struct DaemonLogger {
+    format: LogFormat,
+    level: LevelFilter,
+    chain_log_dir: Option<PathBuf>,
+    chain_files: Mutex<HashMap<String, File>>,
+}
+
+impl Default for DaemonLogger {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            level: LevelFilter::Info,
+            chain_log_dir: None,
+            chain_files: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl DaemonLogger {
+    /// Text logger at the info level
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Logger configured by the `CW_ORCH_LOG_FORMAT` and `CW_ORCH_CHAIN_LOG_DIR` env variables
+    pub fn from_env() -> Self {
+        let logger = Self::new().format(DaemonEnvVars::log_format());
+        match DaemonEnvVars::chain_log_dir() {
+            Some(dir) => logger.chain_log_dir(dir),
+            None => logger,
+        }
+    }
+
+    /// Sets the output format
+    pub fn format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the maximum level of the logged records
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Appends the transaction lifecycle events of each chain to `<dir>/<chain_id>.log`, in the output format
+    pub fn chain_log_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.chain_log_dir = Some(dir.into());
+        self
+    }
+
+    /// Installs the logger as the global logger
+    pub fn init(self) -> Result<(), DaemonError> {
+        if let Some(dir) = &self.chain_log_dir {
+            std::fs::create_dir_all(dir)?;
+        }
+        let level = self.level;
+        log::set_boxed_logger(Box::new(self)).map_err(|e| DaemonError::StdErr(e.to_string()))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    /// Formats a record in the output format
+    fn format_record(&self, record: &Record, event: Option<&TxEvent>) -> String {
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let target = record.target().trim();
+        match self.format {
+            LogFormat::Text => format!(
+                "{timestamp} {:<5} {target}: {}",
+                record.level(),
+                record.args()
+            ),
+            LogFormat::Json => {
+                let mut line = serde_json::json!({
+                    "timestamp": timestamp,
+                    "level": record.level().as_str(),
+                    "target": target,
+                    "message": record.args().to_string(),
+                });
+                if let Some(event) = event {
+                    line["event"] = serde_json::to_value(event).unwrap_or_default();
+                }
+                line.to_string()
+            }
+        }
+    }
+
+    fn write_chain_file(&self, chain_id: &str, line: &str) -> std::io::Result<()> {
+        let Some(dir) = &self.chain_log_dir else {
+            return Ok(());
+        };
+        let mut files = self.chain_files.lock().unwrap();
+        let file = match files.get_mut(chain_id) {
+            Some(file) => file,
+            None => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(dir.join(format!("{chain_id}.log")))?;
+                files.entry(chain_id.to_string()).or_insert(file)
+            }
+        };
+        writeln!(file, "{line}")
+    }
+}
+
+impl Log for DaemonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let event = (record.target() == TX_LIFECYCLE_TARGET)
+            .then(|| serde_json::from_str::<TxEvent>(&record.args().to_string()).ok())
+            .flatten();
+        let line = self.format_record(record, event.as_ref());
+
+        eprintln!("{line}");
+        if let Some(event) = &event {
+            if let Err(e) = self.write_chain_file(&event.chain_id, &line) {
+                eprintln!("Couldn't write the logs of {}: {e}", event.chain_id);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        for file in self.chain_files.lock().unwrap().values_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tx_event_serialization() -> anyhow::Result<()> {
+        let event = TxEvent::new(
+            "juno-1",
+            "juno1sender",
+            TxStage::Confirmed {
+                txhash: "ABCD".to_string(),
+                height: 42,
+                gas_used: 100_000,
+                code: 0,
+            },
+        );
+        let value = serde_json::to_value(&event)?;
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "chain_id": "juno-1",
+                "sender": "juno1sender",
+                "stage": "confirmed",
+                "txhash": "ABCD",
+                "height": 42,
+                "gas_used": 100_000,
+                "code": 0,
+            })
+        );
+        assert_eq!(serde_json::from_value::<TxEvent>(value)?, event);
+        Ok(())
+    }
+
+    #[test]
+    fn json_lines_and_chain_files() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("cw-orch-logs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let logger = DaemonLogger::new()
+            .format(LogFormat::Json)
+            .chain_log_dir(&dir);
+
+        let event = TxEvent::new(
+            "juno-1",
+            "juno1sender",
+            TxStage::Broadcasted {
+                txhash: "ABCD".to_string(),
+            },
+        );
+        let message = serde_json::to_string(&event)?;
+        logger.log(
+            &Record::builder()
+                .args(format_args!("{message}"))
+                .level(log::Level::Info)
+                .target(TX_LIFECYCLE_TARGET)
+                .build(),
+        );
+        logger.flush();
+
+        let content = std::fs::read_to_string(dir.join("juno-1.log"))?;
+        let line: serde_json::Value = serde_json::from_str(content.trim())?;
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["target"], TX_LIFECYCLE_TARGET);
+        assert_eq!(line["event"]["txhash"], "ABCD");
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,566 @@
+//! Declarative deployments ("infrastructure as code").
+//!
+//! A [`DeploymentManifest`] describes which contracts should be deployed on which chains, with which code and which instantiate/migrate messages.
+//! The [`ManifestReconciler`] compares this description with the on-chain reality (using the deployment state) and
+//! uploads, instantiates or migrates contracts until both match.
+//! Code ids and addresses of the state that don't exist on chain (e.g. after a chain reset) are planned as missing.
+//! A contract running another code is only re-uploaded when it can be migrated, otherwise the difference is reported as drift.
+//!
+//! ## Manifest format
+//! ```json
+//! {
+//!   "contracts": [
+//!     {
+//!       "id": "counter",
+//!       "wasm": "artifacts/counter_contract.wasm",
+//!       "chains": ["juno-1", "osmosis-1"],
+//!       "instantiate": {
+//!         "msg": { "count": 0 },
+//!         "admin": "sender"
+//!       },
+//!       "migrate_msg": {},
+//!       "version": "0.2.0"
+//!     }
+//!   ]
+//! }
+//! ```
+//! The `admin` field accepts either an address or `"sender"` to use the address of the deployer.
+//! The optional `version` is compared with the cw2 version of the instantiated contract.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{manifest::{DeploymentManifest, ManifestReconciler}, Daemon, networks};
+//!
+//! let daemon = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//! let manifest = DeploymentManifest::load("deployment.json").unwrap();
+//! let reconciler = ManifestReconciler::new(manifest, vec![daemon]);
+//! // Inspect what would change
+//! for action in reconciler.plan().unwrap() {
+//!     println!("{}", action);
+//! }
+//! // Apply the changes
+//! reconciler.apply().unwrap();
+//! ```
+
+use std::{collections::HashMap, fmt::Display, fs::File, path::PathBuf};
+
+use cosmwasm_std::{Addr, Coin};
+use cw_orch_core::{
+    contract::{Contract, ContractVersion, WasmPath},
+    environment::{
+        CwEnv, DefaultQueriers, EnvironmentQuerier, IndexResponse, StorageKey, TxHandler,
+        WasmQuerier,
+    },
+    CwEnvError,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Daemon, DaemonError};
+
+const SENDER_ADMIN: &str = "sender";
+
+/// Storage key of the cw2 contract version
+const CW2_CONTRACT_INFO: &str = "contract_info";
+
+/// Declarative description of a deployment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeploymentManifest {
+    /// Contracts that are part of the deployment
+    pub contracts: Vec<ManifestContract>,
+}
+
+impl DeploymentManifest {
+    /// Loads a manifest from a json file
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, DaemonError> {
+        let file = File::open(path.into())?;
+        Ok(serde_json::from_reader(file)?)
+    }
+}
+
+/// Description of a single contract inside a [`DeploymentManifest`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestContract {
+    /// Id of the contract, used as key in the daemon state
+    pub id: String,
+    /// Path to the wasm file of the contract
+    pub wasm: PathBuf,
+    /// Chain ids on which the contract needs to be deployed
+    pub chains: Vec<String>,
+    /// Instantiation parameters. If not provided, the contract is only uploaded
+    #[serde(default)]
+    pub instantiate: Option<ManifestInstantiate>,
+    /// Migrate message used when the contract is not running the code described by the manifest.
+    /// If not provided, the code difference is only reported as drift and the code isn't uploaded
+    #[serde(default)]
+    pub migrate_msg: Option<Value>,
+    /// Expected cw2 version of the instantiated contract, a different version is reported as drift
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Instantiation parameters of a [`ManifestContract`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestInstantiate {
+    /// Instantiate message
+    pub msg: Value,
+    /// Admin of the contract. Use `"sender"` to set the deployer as admin
+    #[serde(default)]
+    pub admin: Option<String>,
+    /// Label of the contract, defaults to the contract id
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Funds sent along the instantiation
+    #[serde(default)]
+    pub funds: Vec<Coin>,
+}
+
+/// Action needed to reconcile a contract with its manifest description
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifestAction {
+    /// The contract matches the manifest
+    UpToDate {
+        /// Chain of the contract
+        chain_id: String,
+        /// Id of the contract
+        contract_id: String,
+    },
+    /// The wasm code is not uploaded yet
+    Upload {
+        /// Chain of the contract
+        chain_id: String,
+        /// Id of the contract
+        contract_id: String,
+    },
+    /// The contract is not instantiated yet
+    Instantiate {
+        /// Chain of the contract
+        chain_id: String,
+        /// Id of the contract
+        contract_id: String,
+    },
+    /// The contract is running another code than the one described in the manifest
+    Migrate {
+        /// Chain of the contract
+        chain_id: String,
+        /// Id of the contract
+        contract_id: String,
+        /// Code id currently running on chain
+        from_code_id: u64,
+    },
+    /// The on-chain state differs from the manifest and can't be reconciled automatically
+    Drift {
+        /// Chain of the contract
+        chain_id: String,
+        /// Id of the contract
+        contract_id: String,
+        /// Description of the difference
+        reason: String,
+    },
+}
+
+impl Display for ManifestAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestAction::UpToDate {
+                chain_id,
+                contract_id,
+            } => write!(f, "[{chain_id}][{contract_id}] up to date"),
+            ManifestAction::Upload {
+                chain_id,
+                contract_id,
+            } => write!(f, "[{chain_id}][{contract_id}] upload"),
+            ManifestAction::Instantiate {
+                chain_id,
+                contract_id,
+            } => write!(f, "[{chain_id}][{contract_id}] instantiate"),
+            ManifestAction::Migrate {
+                chain_id,
+                contract_id,
+                from_code_id,
+            } => write!(
+                f,
+                "[{chain_id}][{contract_id}] migrate from code id {from_code_id}"
+            ),
+            ManifestAction::Drift {
+                chain_id,
+                contract_id,
+                reason,
+            } => write!(f, "[{chain_id}][{contract_id}] drift: {reason}"),
+        }
+    }
+}
+
+/// Reconciles on-chain deployments with a [`DeploymentManifest`]
+pub struct ManifestReconciler<Chain: CwEnv = Daemon> {
+    manifest: DeploymentManifest,
+    chains: HashMap<String, Chain>,
+}
+
+impl<Chain: CwEnv> ManifestReconciler<Chain> {
+    /// Creates a reconciler for the given manifest.
+    /// `chains` need to contain an environment for every chain referenced in the manifest
+    pub fn new(manifest: DeploymentManifest, chains: Vec<Chain>) -> Self {
+        Self {
+            manifest,
+            chains: chains
+                .into_iter()
+                .map(|chain| (chain.env_info().chain_id, chain))
+                .collect(),
+        }
+    }
+
+    /// Computes the actions needed to reconcile the chains with the manifest, without broadcasting anything.
+    /// A contract that is neither uploaded nor instantiated results in both an upload and an instantiate action
+    pub fn plan(&self) -> Result<Vec<ManifestAction>, DaemonError> {
+        let mut actions = vec![];
+        for contract in &self.manifest.contracts {
+            for chain_id in &contract.chains {
+                let chain = self.chain(chain_id)?;
+                actions.extend(self.contract_plan(chain, contract)?);
+            }
+        }
+        Ok(actions)
+    }
+
+    /// Applies the actions needed to reconcile the chains with the manifest.
+    /// Returns all the actions that were considered, drifts are returned but not acted upon.
+    /// The version of the instantiated or migrated contracts is checked, a different version is returned as drift
+    pub fn apply(&self) -> Result<Vec<ManifestAction>, DaemonError> {
+        let mut actions = vec![];
+        for contract in &self.manifest.contracts {
+            for chain_id in &contract.chains {
+                let chain = self.chain(chain_id)?;
+                for action in self.contract_plan(chain, contract)? {
+                    self.execute(chain, contract, &action)?;
+                    let deployed = matches!(
+                        action,
+                        ManifestAction::Instantiate { .. } | ManifestAction::Migrate { .. }
+                    );
+                    actions.push(action);
+                    if deployed {
+                        let address = Contract::new(&contract.id, chain.clone()).address()?;
+                        if let Some(drift) = self.version_drift(chain, contract, &address) {
+                            log::warn!("{}", drift);
+                            actions.push(drift);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(actions)
+    }
+
+    fn chain(&self, chain_id: &str) -> Result<&Chain, DaemonError> {
+        self.chains.get(chain_id).ok_or(DaemonError::StdErr(format!(
+            "No environment provided for chain {chain_id} referenced in the manifest"
+        )))
+    }
+
+    fn contract_plan(
+        &self,
+        chain: &Chain,
+        contract: &ManifestContract,
+    ) -> Result<Vec<ManifestAction>, DaemonError> {
+        let chain_id = chain.env_info().chain_id;
+        let contract_id = contract.id.clone();
+        let instance = Contract::new(&contract.id, chain.clone());
+        let wasm = chain.wasm_querier();
+        let local_hash = WasmPath::new(&contract.wasm)?.checksum()?;
+        let matches_manifest = |code_id: u64| -> Result<bool, CwEnvError> {
+            Ok(wasm.code_id_hash(code_id).map_err(Into::into)? == local_hash)
+        };
+
+        // Upload status, a code id that doesn't exist on chain is planned as not uploaded
+        let uploaded_code_id = match instance.code_id() {
+            Ok(code_id) => match matches_manifest(code_id) {
+                Ok(true) => Some(code_id),
+                Ok(false) => None,
+                Err(error) => {
+                    log::warn!("[{chain_id}][{contract_id}] code id {code_id} of the state can't be queried: {error}");
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+
+        // Instantiation status, an address that doesn't exist on chain is planned as not instantiated
+        let running = match instance.address() {
+            Ok(address) => match wasm.contract_info(&address).map_err(Into::into) {
+                Ok(info) => Some((address, info.code_id)),
+                Err(error) => {
+                    log::warn!("[{chain_id}][{contract_id}] address {address} of the state can't be queried: {error}");
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+
+        let upload = ManifestAction::Upload {
+            chain_id: chain_id.clone(),
+            contract_id: contract_id.clone(),
+        };
+        let mut actions = vec![];
+        match running {
+            None => {
+                if uploaded_code_id.is_none() {
+                    actions.push(upload);
+                }
+                if contract.instantiate.is_some() {
+                    actions.push(ManifestAction::Instantiate {
+                        chain_id: chain_id.clone(),
+                        contract_id: contract_id.clone(),
+                    });
+                }
+            }
+            Some((address, running_code_id)) => {
+                if !matches_manifest(running_code_id)? {
+                    if contract.migrate_msg.is_some() {
+                        if uploaded_code_id.is_none() {
+                            actions.push(upload);
+                        }
+                        actions.push(ManifestAction::Migrate {
+                            chain_id: chain_id.clone(),
+                            contract_id: contract_id.clone(),
+                            from_code_id: running_code_id,
+                        });
+                    } else {
+                        // Without a migrate path, uploading would only replace the code id of the state
+                        actions.push(ManifestAction::Drift {
+                            chain_id: chain_id.clone(),
+                            contract_id: contract_id.clone(),
+                            reason: format!(
+                                "running code id {running_code_id} doesn't match the manifest code and no migrate message is provided"
+                            ),
+                        });
+                    }
+                }
+                // The version is expected to change with the migration
+                if actions.is_empty() || contract.migrate_msg.is_none() {
+                    actions.extend(self.version_drift(chain, contract, &address));
+                }
+            }
+        }
+
+        if actions.is_empty() {
+            actions.push(ManifestAction::UpToDate {
+                chain_id,
+                contract_id,
+            });
+        }
+        Ok(actions)
+    }
+
+    /// Drift between the cw2 version of the contract and the version of the manifest, if any
+    fn version_drift(
+        &self,
+        chain: &Chain,
+        contract: &ManifestContract,
+        address: &Addr,
+    ) -> Option<ManifestAction> {
+        let expected = contract.version.as_ref()?;
+        let on_chain = chain
+            .wasm_querier()
+            .raw_query_typed::<ContractVersion>(address, StorageKey::item(CW2_CONTRACT_INFO));
+        let reason = match on_chain {
+            Ok(version) if &version.version == expected => return None,
+            Ok(version) => format!(
+                "on-chain version {} doesn't match the manifest version {expected}",
+                version.version
+            ),
+            Err(_) => format!("no cw2 version found, expected {expected}"),
+        };
+        Some(ManifestAction::Drift {
+            chain_id: chain.env_info().chain_id,
+            contract_id: contract.id.clone(),
+            reason,
+        })
+    }
+
+    fn execute(
+        &self,
+        chain: &Chain,
+        contract: &ManifestContract,
+        action: &ManifestAction,
+    ) -> Result<(), DaemonError> {
+        let instance = Contract::new(&contract.id, chain.clone());
+        match action {
+            ManifestAction::Upload { .. } => {
+                let wasm = std::fs::read(WasmPath::new(&contract.wasm)?.path())?;
+                let response = chain
+                    .upload_from_bytes(wasm)
+                    .map_err(Into::<CwEnvError>::into)?;
+                instance.set_code_id(response.uploaded_code_id()?);
+            }
+            ManifestAction::Instantiate { .. } => {
+                let instantiate = contract.instantiate.as_ref().unwrap();
+                let admin = instantiate.admin.as_ref().map(|admin| {
+                    if admin == SENDER_ADMIN {
+                        chain.sender()
+                    } else {
+                        Addr::unchecked(admin)
+                    }
+                });
+                let response = chain
+                    .instantiate(
+                        instance.code_id()?,
+                        &instantiate.msg,
+                        Some(instantiate.label.as_deref().unwrap_or(&contract.id)),
+                        admin.as_ref(),
+                        &instantiate.funds,
+                    )
+                    .map_err(Into::<CwEnvError>::into)?;
+                instance.set_address(&response.instantiated_contract_address()?);
+            }
+            ManifestAction::Migrate { .. } => {
+                let migrate_msg = contract.migrate_msg.as_ref().unwrap();
+                chain
+                    .migrate(migrate_msg, instance.code_id()?, &instance.address()?)
+                    .map_err(Into::<CwEnvError>::into)?;
+            }
+            ManifestAction::UpToDate { .. } => {}
+            ManifestAction::Drift { .. } => {
+                log::warn!("{}", action);
+                return Ok(());
+            }
+        }
+        log::info!("{}", action);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cw_orch_core::contract::interface_traits::{
+        ContractInstance, CwOrchInstantiate, CwOrchUpload,
+    };
+    use cw_orch_mock::Mock;
+    use mock_contract::{InstantiateMsg, MockContract};
+    use serde_json::json;
+
+    use super::*;
+
+    const MOCK_WASM: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../artifacts/mock_contract.wasm"
+    );
+
+    fn mock_manifest(migrate_msg: Option<Value>, version: Option<&str>) -> DeploymentManifest {
+        DeploymentManifest {
+            contracts: vec![ManifestContract {
+                id: "mock-contract".to_string(),
+                wasm: MOCK_WASM.into(),
+                chains: vec![],
+                instantiate: Some(ManifestInstantiate {
+                    msg: json!({}),
+                    admin: Some(SENDER_ADMIN.to_string()),
+                    label: None,
+                    funds: vec![],
+                }),
+                migrate_msg,
+                version: version.map(ToString::to_string),
+            }],
+        }
+    }
+
+    fn reconciler(mut manifest: DeploymentManifest, chain: &Mock) -> ManifestReconciler<Mock> {
+        manifest.contracts[0].chains = vec![chain.env_info().chain_id];
+        ManifestReconciler::new(manifest, vec![chain.clone()])
+    }
+
+    fn kinds(actions: &[ManifestAction]) -> Vec<&'static str> {
+        actions
+            .iter()
+            .map(|action| match action {
+                ManifestAction::Upload { .. } => "upload",
+                ManifestAction::Instantiate { .. } => "instantiate",
+                ManifestAction::Migrate { .. } => "migrate",
+                ManifestAction::UpToDate { .. } => "up_to_date",
+                ManifestAction::Drift { .. } => "drift",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plan_missing_and_stale_contract() -> anyhow::Result<()> {
+        let chain = Mock::new("sender");
+        let reconciler = reconciler(mock_manifest(None, None), &chain);
+        assert_eq!(kinds(&reconciler.plan()?), ["upload", "instantiate"]);
+
+        // State left from a previous chain, neither the code nor the contract exist
+        let contract = Contract::new("mock-contract", chain.clone());
+        contract.set_code_id(42);
+        contract.set_address(&Addr::unchecked("stale"));
+        assert_eq!(kinds(&reconciler.plan()?), ["upload", "instantiate"]);
+        Ok(())
+    }
+
+    #[test]
+    fn plan_drift_without_migrate_path() -> anyhow::Result<()> {
+        let chain = Mock::new("sender");
+        let contract = MockContract::new("mock-contract", chain.clone());
+        contract.upload()?;
+        contract.instantiate(&InstantiateMsg {}, None, None)?;
+        let code_id = contract.code_id()?;
+
+        // The mock code doesn't match the wasm file, it can't be migrated so it's not re-uploaded
+        let actions = reconciler(mock_manifest(None, Some("0")), &chain).plan()?;
+        assert_eq!(kinds(&actions), ["drift"]);
+
+        let actions = reconciler(mock_manifest(None, Some("1.0.0")), &chain).plan()?;
+        assert_eq!(kinds(&actions), ["drift", "drift"]);
+        let ManifestAction::Drift { reason, .. } = &actions[1] else {
+            unreachable!()
+        };
+        assert!(reason.contains("on-chain version 0"));
+        assert_eq!(contract.code_id()?, code_id);
+        Ok(())
+    }
+
+    #[test]
+    fn plan_migration() -> anyhow::Result<()> {
+        let chain = Mock::new("sender");
+        let contract = MockContract::new("mock-contract", chain.clone());
+        contract.upload()?;
+        contract.instantiate(&InstantiateMsg {}, None, None)?;
+        let code_id = contract.code_id()?;
+
+        let manifest = mock_manifest(Some(json!({ "t": "success" })), Some("1.0.0"));
+        let actions = reconciler(manifest, &chain).plan()?;
+        assert_eq!(kinds(&actions), ["upload", "migrate"]);
+        assert_eq!(
+            actions[1],
+            ManifestAction::Migrate {
+                chain_id: chain.env_info().chain_id,
+                contract_id: "mock-contract".to_string(),
+                from_code_id: code_id,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_manifest() {
+        let manifest: DeploymentManifest = serde_json::from_str(
+            r#"{
+                "contracts": [
+                    {
+                        "id": "counter",
+                        "wasm": "artifacts/counter_contract.wasm",
+                        "chains": ["juno-1"],
+                        "instantiate": { "msg": { "count": 0 }, "admin": "sender" }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let contract = &manifest.contracts[0];
+        assert_eq!(contract.id, "counter");
+        assert_eq!(contract.migrate_msg, None);
+        assert_eq!(contract.version, None);
+        let instantiate = contract.instantiate.as_ref().unwrap();
+        assert_eq!(instantiate.admin.as_deref(), Some(SENDER_ADMIN));
+        assert!(instantiate.funds.is_empty());
+    }
+}
@@ -0,0 +1,364 @@
+//! Telemetry hooks of the daemon, enabled with the `metrics` feature.
+//!
+//! The daemon reports broadcasted, confirmed, failed and retried transactions, the gas they used and the latency of the queries
+//! to the global [`MetricsRecorder`], installed once with [`set_recorder`].
+//! Implement the trait to forward the measures to your own monitoring stack,
+//! or use [`DaemonMetrics`] which aggregates them in memory and renders them in the Prometheus text format.
+//!
+//! ## Example
+//! ```no_run
+//! use std::sync::Arc;
+//! use cw_orch_daemon::metrics::{set_recorder, DaemonMetrics};
+//!
+//! let metrics = Arc::new(DaemonMetrics::default());
+//! set_recorder(metrics.clone()).unwrap();
+//!
+//! // ... run the bot, then serve the metrics on the `/metrics` endpoint
+//! let body = metrics.render_prometheus();
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::{log::TxStage, DaemonError};
+
+static RECORDER: OnceCell<Box<dyn MetricsRecorder>> = OnceCell::new();
+
+/// Receiver of the measures of the daemon. All methods do nothing by default
+pub trait MetricsRecorder: Send + Sync {
+    /// A transaction was accepted in the mempool of `chain_id`
+    fn tx_broadcasted(&self, _chain_id: &str) {}
+
+    /// A transaction was included in a block of `chain_id`
+    fn tx_confirmed(&self, _chain_id: &str, _gas_used: u64) {}
+
+    /// A transaction couldn't be broadcasted or confirmed on `chain_id`
+    fn tx_failed(&self, _chain_id: &str) {}
+
+    /// The broadcast of a transaction on `chain_id` is retried because of `reason`
+    fn tx_retried(&self, _chain_id: &str, _reason: &str) {}
+
+    /// A query to the gRPC method `method` completed after `latency`
+    fn query_completed(&self, _method: &str, _latency: Duration, _success: bool) {}
+}
+
+impl<T: MetricsRecorder + ?Sized> MetricsRecorder for Arc<T> {
+    fn tx_broadcasted(&self, chain_id: &str) {
+        (**self).tx_broadcasted(chain_id)
+    }
+
+    fn tx_confirmed(&self, chain_id: &str, gas_used: u64) {
+        (**self).tx_confirmed(chain_id, gas_used)
+    }
+
+    fn tx_failed(&self, chain_id: &str) {
+        (**self).tx_failed(chain_id)
+    }
+
+    fn tx_retried(&self, chain_id: &str, reason: &str) {
+        (**self).tx_retried(chain_id, reason)
+    }
+
+    fn query_completed(&self, method: &str, latency: Duration, success: bool) {
+        (**self).query_completed(method, latency, success)
+    }
+}
+
+/// Installs the global recorder. It can only be set once
+pub fn set_recorder(recorder: impl MetricsRecorder + 'static) -> Result<(), DaemonError> {
+    RECORDER
+        .set(Box::new(recorder))
+        .map_err(|_| DaemonError::StdErr("The metrics recorder is already set".to_string()))
+}
+
+/// Global recorder, if set
+pub fn recorder() -> Option<&'static dyn MetricsRecorder> {
+    RECORDER.get().map(|recorder| recorder.as_ref())
+}
+
+/// Reports a lifecycle stage of a transaction to the global recorder
+pub(crate) fn record_tx_stage(chain_id: &str, stage: &TxStage) {
+    let Some(recorder) = recorder() else {
+        return;
+    };
+    match stage {
+        TxStage::Simulated { .. } => {}
+        TxStage::Broadcasted { .. } => recorder.tx_broadcasted(chain_id),
+        TxStage::Confirmed { gas_used, .. } => recorder.tx_confirmed(chain_id, *gas_used),
+        TxStage::Failed { .. } => recorder.tx_failed(chain_id),
+    }
+}
+
+/// Reports a query started at `start` to the global recorder
+pub(crate) fn record_query<T, E>(method: &str, start: Instant, result: &Result<T, E>) {
+    if let Some(recorder) = recorder() {
+        recorder.query_completed(method, start.elapsed(), result.is_ok());
+    }
+}
+
+/// Upper bounds of the query latency buckets, in seconds
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+/// Upper bounds of the gas used buckets
+const GAS_BUCKETS: &[f64] = &[
+    100_000.0,
+    200_000.0,
+    500_000.0,
+    1_000_000.0,
+    2_000_000.0,
+    5_000_000.0,
+    10_000_000.0,
+];
+
+/// Cumulative histogram
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    buckets: &'static [f64],
+    counts: Vec<u64>,
+    /// Sum of the observed values
+    pub sum: f64,
+    /// Number of observed values
+    pub count: u64,
+}
+
+impl Histogram {
+    fn new(buckets: &'static [f64]) -> Self {
+        Self {
+            buckets,
+            counts: vec![0; buckets.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.buckets.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Upper bounds of the buckets and the number of values lower or equal to them
+    pub fn buckets(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        self.buckets
+            .iter()
+            .copied()
+            .zip(self.counts.iter().copied())
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (bound, count) in self.buckets() {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+/// Measures of the transactions sent to a chain
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainMetrics {
+    /// Transactions accepted in the mempool
+    pub txs_broadcasted: u64,
+    /// Transactions included in a block
+    pub txs_confirmed: u64,
+    /// Transactions that couldn't be broadcasted or confirmed
+    pub txs_failed: u64,
+    /// Broadcast retries
+    pub tx_retries: u64,
+    /// Gas used by the confirmed transactions
+    pub gas_used: Histogram,
+}
+
+impl Default for ChainMetrics {
+    fn default() -> Self {
+        Self {
+            txs_broadcasted: 0,
+            txs_confirmed: 0,
+            txs_failed: 0,
+            tx_retries: 0,
+            gas_used: Histogram::new(GAS_BUCKETS),
+        }
+    }
+}
+
+/// Measures of the queries to a gRPC method
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryMetrics {
+    /// Failed queries
+    pub errors: u64,
+    /// Latency of the queries, in seconds
+    pub latency: Histogram,
+}
+
+impl Default for QueryMetrics {
+    fn default() -> Self {
+        Self {
+            errors: 0,
+            latency: Histogram::new(LATENCY_BUCKETS),
+        }
+    }
+}
+
+/// In-memory [`MetricsRecorder`], rendered in the Prometheus text format with [`DaemonMetrics::render_prometheus`]
+#[derive(Debug, Default)]
+pub struct DaemonMetrics {
+    chains: Mutex<BTreeMap<String, ChainMetrics>>,
+    queries: Mutex<BTreeMap<String, QueryMetrics>>,
+}
+
+impl DaemonMetrics {
+    /// Measures of the transactions sent to `chain_id`
+    pub fn chain(&self, chain_id: &str) -> ChainMetrics {
+        self.chains
+            .lock()
+            .unwrap()
+            .get(chain_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Measures of the queries to the gRPC method `method`
+    pub fn query(&self, method: &str) -> QueryMetrics {
+        self.queries
+            .lock()
+            .unwrap()
+            .get(method)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// All the measures in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        let chains = self.chains.lock().unwrap();
+        let counters: [(&str, fn(&ChainMetrics) -> u64); 4] = [
+            ("cw_orch_txs_broadcasted_total", |m| m.txs_broadcasted),
+            ("cw_orch_txs_confirmed_total", |m| m.txs_confirmed),
+            ("cw_orch_txs_failed_total", |m| m.txs_failed),
+            ("cw_orch_tx_retries_total", |m| m.tx_retries),
+        ];
+        for (name, value) in counters {
+            let _ = writeln!(out, "# TYPE {name} counter");
+            for (chain_id, metrics) in chains.iter() {
+                let _ = writeln!(out, "{name}{{chain_id=\"{chain_id}\"}} {}", value(metrics));
+            }
+        }
+        let _ = writeln!(out, "# TYPE cw_orch_tx_gas_used histogram");
+        for (chain_id, metrics) in chains.iter() {
+            metrics.gas_used.render(
+                &mut out,
+                "cw_orch_tx_gas_used",
+                &format!("chain_id=\"{chain_id}\""),
+            );
+        }
+
+        let queries = self.queries.lock().unwrap();
+        let _ = writeln!(out, "# TYPE cw_orch_query_errors_total counter");
+        for (method, metrics) in queries.iter() {
+            let _ = writeln!(
+                out,
+                "cw_orch_query_errors_total{{method=\"{method}\"}} {}",
+                metrics.errors
+            );
+        }
+        let _ = writeln!(out, "# TYPE cw_orch_query_duration_seconds histogram");
+        for (method, metrics) in queries.iter() {
+            metrics.latency.render(
+                &mut out,
+                "cw_orch_query_duration_seconds",
+                &format!("method=\"{method}\""),
+            );
+        }
+        out
+    }
+
+    fn update_chain(&self, chain_id: &str, update: impl FnOnce(&mut ChainMetrics)) {
+        update(
+            self.chains
+                .lock()
+                .unwrap()
+                .entry(chain_id.to_string())
+                .or_default(),
+        )
+    }
+}
+
+impl MetricsRecorder for DaemonMetrics {
+    fn tx_broadcasted(&self, chain_id: &str) {
+        self.update_chain(chain_id, |m| m.txs_broadcasted += 1)
+    }
+
+    fn tx_confirmed(&self, chain_id: &str, gas_used: u64) {
+        self.update_chain(chain_id, |m| {
+            m.txs_confirmed += 1;
+            m.gas_used.observe(gas_used as f64);
+        })
+    }
+
+    fn tx_failed(&self, chain_id: &str) {
+        self.update_chain(chain_id, |m| m.txs_failed += 1)
+    }
+
+    fn tx_retried(&self, chain_id: &str, _reason: &str) {
+        self.update_chain(chain_id, |m| m.tx_retries += 1)
+    }
+
+    fn query_completed(&self, method: &str, latency: Duration, success: bool) {
+        let mut queries = self.queries.lock().unwrap();
+        let metrics = queries.entry(method.to_string()).or_default();
+        if !success {
+            metrics.errors += 1;
+        }
+        metrics.latency.observe(latency.as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn daemon_metrics() {
+        let metrics = DaemonMetrics::default();
+        metrics.tx_broadcasted("juno-1");
+        metrics.tx_confirmed("juno-1", 150_000);
+        metrics.tx_retried("juno-1", "account sequence mismatch");
+        metrics.query_completed(
+            "/cosmwasm.wasm.v1.Query/SmartContractState",
+            Duration::from_millis(20),
+            true,
+        );
+
+        let chain = metrics.chain("juno-1");
+        assert_eq!(chain.txs_broadcasted, 1);
+        assert_eq!(chain.txs_confirmed, 1);
+        assert_eq!(chain.tx_retries, 1);
+        assert_eq!(chain.gas_used.count, 1);
+        assert_eq!(
+            chain.gas_used.buckets().next(),
+            Some((100_000.0, 0)),
+            "150k gas is above the first bucket"
+        );
+
+        let rendered = metrics.render_prometheus();
+        assert!(rendered.contains("cw_orch_txs_broadcasted_total{chain_id=\"juno-1\"} 1"));
+        assert!(
+            rendered.contains("cw_orch_tx_gas_used_bucket{chain_id=\"juno-1\",le=\"200000\"} 1")
+        );
+        assert!(rendered.contains(
+            "cw_orch_query_duration_seconds_bucket{method=\"/cosmwasm.wasm.v1.Query/SmartContractState\",le=\"0.025\"} 1"
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+//! User defined networks, merged with the builtin [`networks`](crate::networks).
+//!
+//! Private chains, forks or other endpoints can be targeted without code changes by defining them in `~/.cw-orchestrator/networks.toml`
+//! (or the file at the `CW_ORCH_NETWORKS_FILE` env variable). Each table is keyed by chain id:
+//! - For a builtin chain, only the fields set in the file replace the builtin values.
+//! - For a new chain, `gas_denom`, `gas_price`, `grpc_urls` and `pub_address_prefix` are required.
+//!
+//! ## Example
+//! ```toml
+//! # ~/.cw-orchestrator/networks.toml
+//! [juno-1]
+//! grpc_urls = ["https://juno-grpc.my-node.com:443"]
+//!
+//! [my-fork-1]
+//! kind = "local"
+//! chain_name = "juno"
+//! gas_denom = "ujuno"
+//! gas_price = 0.025
+//! grpc_urls = ["http://localhost:9090"]
+//! pub_address_prefix = "juno"
+//! coin_type = 118
+//! ```
+//!
+//! ```no_run
+//! use cw_orch_daemon::{network_config::parse_network, Daemon};
+//!
+//! let chain = parse_network("my-fork-1").unwrap();
+//! let daemon = Daemon::builder().chain(chain).build().unwrap();
+//! ```
+
+use std::{collections::BTreeMap, path::Path};
+
+use cw_orch_core::environment::{ChainInfoOwned, NetworkInfoOwned};
+use serde::{Deserialize, Serialize};
+
+use crate::{env::DaemonEnvVars, networks, DaemonError};
+
+/// Coin type of the chains that don't set one
+const DEFAULT_COIN_TYPE: u32 = 118;
+
+/// Definition of a network in the networks file, see the [module documentation](self)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkDefinition {
+    /// `local`, `testnet` or `mainnet`
+    pub kind: Option<String>,
+    pub gas_denom: Option<String>,
+    pub gas_price: Option<f64>,
+    pub grpc_urls: Option<Vec<String>>,
+    pub lcd_url: Option<String>,
+    pub fcd_url: Option<String>,
+    pub rpc_url: Option<String>,
+    /// Defaults to the chain id for new chains
+    pub chain_name: Option<String>,
+    pub pub_address_prefix: Option<String>,
+    /// Defaults to 118 for new chains
+    pub coin_type: Option<u32>,
+}
+
+impl NetworkDefinition {
+    /// Applies the definition of `chain_id` on top of the builtin `base` chain, or builds a new chain if there is none
+    pub fn apply(
+        self,
+        chain_id: &str,
+        base: Option<ChainInfoOwned>,
+    ) -> Result<ChainInfoOwned, DaemonError> {
+        let Some(base) = base else {
+            let missing = |field: &str| {
+                DaemonError::Config(format!(
+                    "network {chain_id} is not builtin, `{field}` must be set"
+                ))
+            };
+            return Ok(ChainInfoOwned {
+                chain_id: chain_id.to_string(),
+                gas_denom: self.gas_denom.ok_or_else(|| missing("gas_denom"))?,
+                gas_price: self.gas_price.ok_or_else(|| missing("gas_price"))?,
+                grpc_urls: self.grpc_urls.ok_or_else(|| missing("grpc_urls"))?,
+                lcd_url: self.lcd_url,
+                fcd_url: self.fcd_url,
+                rpc_url: self.rpc_url,
+                network_info: NetworkInfoOwned {
+                    chain_name: self.chain_name.unwrap_or_else(|| chain_id.to_string()),
+                    pub_address_prefix: self
+                        .pub_address_prefix
+                        .ok_or_else(|| missing("pub_address_prefix"))?,
+                    coin_type: self.coin_type.unwrap_or(DEFAULT_COIN_TYPE),
+                },
+                kind: self.kind.unwrap_or_default().into(),
+            });
+        };
+
+        Ok(ChainInfoOwned {
+            chain_id: base.chain_id,
+            gas_denom: self.gas_denom.unwrap_or(base.gas_denom),
+            gas_price: self.gas_price.unwrap_or(base.gas_price),
+            grpc_urls: self.grpc_urls.unwrap_or(base.grpc_urls),
+            lcd_url: self.lcd_url.or(base.lcd_url),
+            fcd_url: self.fcd_url.or(base.fcd_url),
+            rpc_url: self.rpc_url.or(base.rpc_url),
+            network_info: NetworkInfoOwned {
+                chain_name: self.chain_name.unwrap_or(base.network_info.chain_name),
+                pub_address_prefix: self
+                    .pub_address_prefix
+                    .unwrap_or(base.network_info.pub_address_prefix),
+                coin_type: self.coin_type.unwrap_or(base.network_info.coin_type),
+            },
+            kind: self.kind.map(Into::into).unwrap_or(base.kind),
+        })
+    }
+}
+
+/// Reads the network definitions of a networks file, by chain id
+pub fn read_network_config(
+    path: impl AsRef<Path>,
+) -> Result<BTreeMap<String, NetworkDefinition>, DaemonError> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| DaemonError::OpenFile(path.display().to_string(), e.to_string()))?;
+    toml::from_str(&content).map_err(|e| DaemonError::Config(format!("{}: {e}", path.display())))
+}
+
+/// Network definitions of the user's networks file, empty if there is no such file
+pub fn user_networks() -> Result<BTreeMap<String, NetworkDefinition>, DaemonError> {
+    match DaemonEnvVars::networks_file() {
+        Some(path) if path.exists() => read_network_config(path),
+        _ => Ok(BTreeMap::new()),
+    }
+}
+
+/// Builtin networks merged with the networks of the user's networks file
+pub fn supported_networks() -> Result<Vec<ChainInfoOwned>, DaemonError> {
+    merge_networks(user_networks()?)
+}
+
+/// Network of `chain_id`, from the user's networks file and the builtin networks.
+/// Like [`networks::parse_network`] with the user defined networks
+pub fn parse_network(chain_id: &str) -> Result<ChainInfoOwned, DaemonError> {
+    let builtin = networks::parse_network(chain_id).ok().map(Into::into);
+    match user_networks()?.remove(chain_id) {
+        Some(definition) => definition.apply(chain_id, builtin),
+        None => {
+            builtin.ok_or_else(|| DaemonError::StdErr(format!("Network not found: {chain_id}")))
+        }
+    }
+}
+
+fn merge_networks(
+    mut definitions: BTreeMap<String, NetworkDefinition>,
+) -> Result<Vec<ChainInfoOwned>, DaemonError> {
+    let mut chains = networks::SUPPORTED_NETWORKS
+        .iter()
+        .map(|chain| {
+            let chain: ChainInfoOwned = chain.clone().into();
+            match definitions.remove(&chain.chain_id) {
+                Some(definition) => definition.apply(&chain.chain_id.clone(), Some(chain)),
+                None => Ok(chain),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for (chain_id, definition) in definitions {
+        chains.push(definition.apply(&chain_id, None)?);
+    }
+    Ok(chains)
+}
+
+#[cfg(test)]
+mod test {
+    use cw_orch_core::environment::ChainKind;
+
+    use super::*;
+    use crate::networks::JUNO_1;
+
+    const NETWORKS: &str = r#"
+        [juno-1]
+        grpc_urls = ["https://juno-grpc.my-node.com:443"]
+
+        [my-fork-1]
+        kind = "local"
+        gas_denom = "ujuno"
+        gas_price = 0.025
+        grpc_urls = ["http://localhost:9090"]
+        pub_address_prefix = "juno"
+    "#;
+
+    #[test]
+    fn user_networks_merge_with_builtin_networks() -> anyhow::Result<()> {
+        let definitions: BTreeMap<String, NetworkDefinition> = toml::from_str(NETWORKS)?;
+        let chains = merge_networks(definitions)?;
+        assert_eq!(chains.len(), networks::SUPPORTED_NETWORKS.len() + 1);
+
+        let juno = chains
+            .iter()
+            .find(|c| c.chain_id == JUNO_1.chain_id)
+            .unwrap();
+        assert_eq!(juno.grpc_urls, vec!["https://juno-grpc.my-node.com:443"]);
+        assert_eq!(juno.gas_denom, JUNO_1.gas_denom);
+
+        let fork = chains.last().unwrap();
+        assert_eq!(fork.chain_id, "my-fork-1");
+        assert_eq!(fork.network_info.chain_name, "my-fork-1");
+        assert_eq!(fork.network_info.coin_type, 118);
+        assert_eq!(fork.kind, ChainKind::Local);
+
+        let incomplete = NetworkDefinition {
+            gas_denom: Some("ufoo".to_string()),
+            ..Default::default()
+        };
+        assert!(incomplete.apply("foo-1", None).is_err());
+        Ok(())
+    }
+}
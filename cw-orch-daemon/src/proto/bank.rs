@@ -0,0 +1,22 @@
+//! Types of the bank module that aren't available in the cosmos-sdk 0.46 protos.
+
+use crate::cosmos_modules::bank::SendEnabled;
+use cosmrs::proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+
+/// Query of the send enabled status of denoms, available since cosmos-sdk 0.47
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuerySendEnabledRequest {
+    /// Denoms to query, all the denoms with a send enabled status when empty
+    #[prost(string, repeated, tag = "1")]
+    pub denoms: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "99")]
+    pub pagination: ::core::option::Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuerySendEnabledResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub send_enabled: ::prost::alloc::vec::Vec<SendEnabled>,
+    #[prost(message, optional, tag = "99")]
+    pub pagination: ::core::option::Option<PageResponse>,
+}
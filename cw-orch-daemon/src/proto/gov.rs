@@ -0,0 +1,43 @@
+//! Messages of the `gov` module v1 (`cosmos.gov.v1`), that are missing from the generated protos.
+
+/// Submits a proposal executing `messages` with the gov module as signer
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSubmitProposal {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: ::prost::alloc::vec::Vec<::cosmrs::Any>,
+    #[prost(message, repeated, tag = "2")]
+    pub initial_deposit: ::prost::alloc::vec::Vec<::cosmrs::proto::cosmos::base::v1beta1::Coin>,
+    #[prost(string, tag = "3")]
+    pub proposer: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub metadata: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub summary: ::prost::alloc::string::String,
+}
+
+/// Votes on a proposal
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgVote {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub voter: ::prost::alloc::string::String,
+    /// `VoteOption` of the vote: 1 yes, 2 abstain, 3 no, 4 no with veto
+    #[prost(int32, tag = "3")]
+    pub option: i32,
+    #[prost(string, tag = "4")]
+    pub metadata: ::prost::alloc::string::String,
+}
+
+/// Adds a deposit to a proposal
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgDeposit {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub depositor: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: ::prost::alloc::vec::Vec<::cosmrs::proto::cosmos::base::v1beta1::Coin>,
+}
@@ -9,10 +9,27 @@ use prost::Name;
 use crate::keys::private::PrivateKey;
 
 #[cfg(feature = "eth")]
-use ::{cosmrs::proto, ethers_core::utils::keccak256};
+use ::{
+    cosmrs::proto,
+    ethers_core::utils::{hash_message, keccak256},
+};
 
 pub const ETHEREUM_COIN_TYPE: u32 = 60;
 
+/// Type url of the ethsecp256k1 public keys on Injective
+pub const INJECTIVE_PUB_KEY_TYPE_URL: &str = "/injective.crypto.v1beta1.ethsecp256k1.PubKey";
+/// Type url of the ethsecp256k1 public keys on ethermint based chains (Evmos, Canto, XPLA...)
+pub const ETHERMINT_PUB_KEY_TYPE_URL: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+/// Type url of the ethsecp256k1 public keys of a chain with the [`ETHEREUM_COIN_TYPE`], from its address prefix.
+/// Injective uses its own protos, other chains use the ethermint ones.
+pub fn default_eth_pub_key_type_url(pub_address_prefix: &str) -> &'static str {
+    match pub_address_prefix {
+        "inj" => INJECTIVE_PUB_KEY_TYPE_URL,
+        _ => ETHERMINT_PUB_KEY_TYPE_URL,
+    }
+}
+
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct InjectiveEthAccount {
     #[prost(message, optional, tag = "1")]
@@ -41,9 +58,33 @@ pub trait InjectiveSigner {
     fn sign_injective(&self, sign_doc: SignDoc) -> Result<Raw, DaemonError>;
 }
 
+/// Signer for the chains using ethsecp256k1 keys (Injective, Evmos, Canto, XPLA...)
+pub trait EthSigner {
+    /// Signs the keccak256 hash of the sign doc, as expected by the ethsecp256k1 keys of the chain
+    fn sign_eth(&self, sign_doc: SignDoc) -> Result<Raw, DaemonError>;
+    /// Signs an arbitrary message following EIP-191 (`personal_sign`), returns the 65 bytes `r || s || v` signature
+    fn sign_eip191(&self, message: &[u8]) -> Result<Vec<u8>, DaemonError>;
+}
+
 #[cfg(feature = "eth")]
 impl InjectiveSigner for PrivateKey {
     fn sign_injective(&self, sign_doc: SignDoc) -> Result<Raw, DaemonError> {
+        self.sign_eth(sign_doc)
+    }
+}
+
+#[cfg(feature = "eth")]
+impl EthSigner for PrivateKey {
+    fn sign_eip191(&self, message: &[u8]) -> Result<Vec<u8>, DaemonError> {
+        let msg_hash = hash_message(message);
+        let eth_signature = ethers_signers::Wallet::from_bytes(&self.raw_key())
+            .map_err(|e| DaemonError::StdErr(e.to_string()))?
+            .sign_hash(msg_hash)
+            .map_err(|e| DaemonError::StdErr(e.to_string()))?;
+        Ok(eth_signature.to_vec())
+    }
+
+    fn sign_eth(&self, sign_doc: SignDoc) -> Result<Raw, DaemonError> {
         let sign_doc_bytes = sign_doc.clone().into_bytes()?;
 
         // sign with ethers
@@ -1 +1,7 @@
+pub mod bank;
+pub mod gov;
 pub mod injective;
+pub mod tokenfactory;
+pub mod tx;
+pub mod txfees;
+pub mod wasm;
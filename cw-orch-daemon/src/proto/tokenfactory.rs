@@ -0,0 +1,78 @@
+//! Types of the tokenfactory module.
+//! Most chains (osmosis, juno, neutron, ...) use the `osmosis.tokenfactory.v1beta1` package, some forks only change the package name.
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryDenomsFromCreatorRequest {
+    #[prost(string, tag = "1")]
+    pub creator: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryDenomsFromCreatorResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub denoms: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryDenomAuthorityMetadataRequest {
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryDenomAuthorityMetadataResponse {
+    #[prost(message, optional, tag = "1")]
+    pub authority_metadata: ::core::option::Option<DenomAuthorityMetadata>,
+}
+
+/// Authority of a tokenfactory denom
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DenomAuthorityMetadata {
+    /// Address allowed to mint, burn and change the admin of the denom. Empty if the denom has no admin
+    #[prost(string, tag = "1")]
+    pub admin: ::prost::alloc::string::String,
+}
+
+/// Creates the denom `factory/{sender}/{subdenom}`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgCreateDenom {
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub subdenom: ::prost::alloc::string::String,
+}
+
+/// Mints tokens of a denom administered by the sender
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgMint {
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub amount: ::core::option::Option<::cosmrs::proto::cosmos::base::v1beta1::Coin>,
+    /// Recipient of the minted tokens, the sender if empty
+    #[prost(string, tag = "3")]
+    pub mint_to_address: ::prost::alloc::string::String,
+}
+
+/// Burns tokens of a denom administered by the sender
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgBurn {
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub amount: ::core::option::Option<::cosmrs::proto::cosmos::base::v1beta1::Coin>,
+    /// Address the tokens are burned from, the sender if empty
+    #[prost(string, tag = "3")]
+    pub burn_from_address: ::prost::alloc::string::String,
+}
+
+/// Transfers the administration of a denom
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChangeAdmin {
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub denom: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub new_admin: ::prost::alloc::string::String,
+}
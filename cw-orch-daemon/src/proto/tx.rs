@@ -0,0 +1,22 @@
+//! Transaction body of cosmos-sdk 0.53 (`cosmos.tx.v1beta1.TxBody`), with the unordered transaction fields
+//! that are missing from the generated protos.
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TxBody {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: ::prost::alloc::vec::Vec<::cosmrs::Any>,
+    #[prost(string, tag = "2")]
+    pub memo: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub timeout_height: u64,
+    /// The transaction is not ordered by the account sequence, it's deduplicated by hash until `timeout_timestamp`
+    #[prost(bool, tag = "4")]
+    pub unordered: bool,
+    /// Block time after which the transaction is not included anymore. Required for unordered transactions
+    #[prost(message, optional, tag = "5")]
+    pub timeout_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, repeated, tag = "1023")]
+    pub extension_options: ::prost::alloc::vec::Vec<::cosmrs::Any>,
+    #[prost(message, repeated, tag = "2047")]
+    pub non_critical_extension_options: ::prost::alloc::vec::Vec<::cosmrs::Any>,
+}
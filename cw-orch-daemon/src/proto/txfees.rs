@@ -0,0 +1,11 @@
+//! Query types of the osmosis `txfees` module (`osmosis.txfees.v1beta1`).
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryEipBaseFeeRequest {}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryEipBaseFeeResponse {
+    /// Current base fee of the EIP-1559 fee market, as a cosmos-sdk decimal
+    #[prost(string, tag = "1")]
+    pub base_fee: ::prost::alloc::string::String,
+}
@@ -0,0 +1,71 @@
+//! Governance messages and queries of the cosmwasm `wasm` module (`cosmwasm.wasm.v1`) that are missing from the generated protos.
+
+/// Pins codes in the wasm VM cache. Must be sent by the authority of the module (usually the gov module)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPinCodes {
+    #[prost(string, tag = "1")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(uint64, repeated, tag = "2")]
+    pub code_ids: ::prost::alloc::vec::Vec<u64>,
+}
+
+/// Unpins codes from the wasm VM cache. Must be sent by the authority of the module (usually the gov module)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgUnpinCodes {
+    #[prost(string, tag = "1")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(uint64, repeated, tag = "2")]
+    pub code_ids: ::prost::alloc::vec::Vec<u64>,
+}
+
+/// Response of the `Code` query, without the wasm bytecode
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryCodeInfoResponse {
+    #[prost(message, optional, tag = "1")]
+    pub code_info: ::core::option::Option<CodeInfoResponse>,
+}
+
+/// Code metadata, including the instantiate permission missing from the generated protos
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CodeInfoResponse {
+    #[prost(uint64, tag = "1")]
+    pub code_id: u64,
+    #[prost(string, tag = "2")]
+    pub creator: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub data_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "6")]
+    pub instantiate_permission: ::core::option::Option<AccessConfig>,
+}
+
+/// Access control of the instantiation of a code
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccessConfig {
+    /// `AccessType`: 0 unspecified, 1 nobody, 2 only address (wasmd < 0.42), 3 everybody, 4 any of addresses
+    #[prost(int32, tag = "1")]
+    pub permission: i32,
+    /// Address allowed with the `OnlyAddress` permission, before wasmd 0.42
+    #[prost(string, tag = "2")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "3")]
+    pub addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+
+/// Query of the contracts instantiated by an address, since wasmd 0.40
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryContractsByCreatorRequest {
+    #[prost(string, tag = "1")]
+    pub creator_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub pagination:
+        ::core::option::Option<::cosmrs::proto::cosmos::base::query::v1beta1::PageRequest>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryContractsByCreatorResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub contract_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "2")]
+    pub pagination:
+        ::core::option::Option<::cosmrs::proto::cosmos::base::query::v1beta1::PageResponse>,
+}
@@ -0,0 +1,498 @@
+//! Typed builders of common module messages, encoded as [`Any`] with their type url.
+//!
+//! The messages can be committed with `commit_tx_any` or the `Stargate::commit_any` function without hand-typing the type urls.
+//! Modules whose package differs between chains (like the tokenfactory) pick the package of the chain.
+//!
+//! ## Usage
+//! ```no_run
+//! use cosmwasm_std::coin;
+//! use cw_orch_daemon::{networks, proto_registry::{staking, TokenFactoryMsgs}, Daemon};
+//!
+//! let daemon = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//! let wallet = daemon.wallet();
+//! let sender = wallet.address().unwrap();
+//!
+//! let delegate = staking::delegate(&sender, "junovaloper1...", &coin(100, "ujunox"));
+//! let tokenfactory = TokenFactoryMsgs::for_chain(&wallet.chain_info.network_info.chain_name);
+//! let create_denom = tokenfactory.create_denom(&sender, "token");
+//!
+//! daemon
+//!     .rt_handle
+//!     .block_on(wallet.commit_tx_any(vec![delegate, create_denom], None))
+//!     .unwrap();
+//! ```
+
+use cosmrs::{proto::cosmos::base::v1beta1::Coin as ProtoCoin, Any};
+use prost::Message;
+
+/// Encodes `msg` as an [`Any`] of type `type_url`
+fn any(type_url: &str, msg: impl Message) -> Any {
+    Any {
+        type_url: type_url.to_string(),
+        value: msg.encode_to_vec(),
+    }
+}
+
+fn proto_coin(coin: &cosmwasm_std::Coin) -> ProtoCoin {
+    ProtoCoin {
+        denom: coin.denom.clone(),
+        amount: coin.amount.to_string(),
+    }
+}
+
+fn proto_coins(coins: &[cosmwasm_std::Coin]) -> Vec<ProtoCoin> {
+    coins.iter().map(proto_coin).collect()
+}
+
+/// Messages of the `cosmos.gov.v1` module
+pub mod gov {
+    use super::*;
+    use crate::proto::gov::{MsgDeposit, MsgSubmitProposal, MsgVote};
+
+    /// Option of a vote on a proposal
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum VoteOption {
+        Yes = 1,
+        Abstain = 2,
+        No = 3,
+        NoWithVeto = 4,
+    }
+
+    /// Submits a proposal executing `messages` with the gov module as signer
+    pub fn submit_proposal(
+        proposer: impl ToString,
+        messages: Vec<Any>,
+        initial_deposit: &[cosmwasm_std::Coin],
+        title: impl ToString,
+        summary: impl ToString,
+    ) -> Any {
+        any(
+            "/cosmos.gov.v1.MsgSubmitProposal",
+            MsgSubmitProposal {
+                messages,
+                initial_deposit: proto_coins(initial_deposit),
+                proposer: proposer.to_string(),
+                metadata: String::new(),
+                title: title.to_string(),
+                summary: summary.to_string(),
+            },
+        )
+    }
+
+    /// Votes `option` on a proposal
+    pub fn vote(voter: impl ToString, proposal_id: u64, option: VoteOption) -> Any {
+        any(
+            "/cosmos.gov.v1.MsgVote",
+            MsgVote {
+                proposal_id,
+                voter: voter.to_string(),
+                option: option as i32,
+                metadata: String::new(),
+            },
+        )
+    }
+
+    /// Adds a deposit to a proposal
+    pub fn deposit(
+        depositor: impl ToString,
+        proposal_id: u64,
+        amount: &[cosmwasm_std::Coin],
+    ) -> Any {
+        any(
+            "/cosmos.gov.v1.MsgDeposit",
+            MsgDeposit {
+                proposal_id,
+                depositor: depositor.to_string(),
+                amount: proto_coins(amount),
+            },
+        )
+    }
+}
+
+/// Messages of the `cosmos.staking.v1beta1` module
+pub mod staking {
+    use super::*;
+    use cosmrs::proto::cosmos::staking::v1beta1::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate};
+
+    /// Delegates `amount` to a validator
+    pub fn delegate(
+        delegator: impl ToString,
+        validator: impl ToString,
+        amount: &cosmwasm_std::Coin,
+    ) -> Any {
+        any(
+            "/cosmos.staking.v1beta1.MsgDelegate",
+            MsgDelegate {
+                delegator_address: delegator.to_string(),
+                validator_address: validator.to_string(),
+                amount: Some(proto_coin(amount)),
+            },
+        )
+    }
+
+    /// Undelegates `amount` from a validator
+    pub fn undelegate(
+        delegator: impl ToString,
+        validator: impl ToString,
+        amount: &cosmwasm_std::Coin,
+    ) -> Any {
+        any(
+            "/cosmos.staking.v1beta1.MsgUndelegate",
+            MsgUndelegate {
+                delegator_address: delegator.to_string(),
+                validator_address: validator.to_string(),
+                amount: Some(proto_coin(amount)),
+            },
+        )
+    }
+
+    /// Moves a delegation of `amount` from `src_validator` to `dst_validator`
+    pub fn redelegate(
+        delegator: impl ToString,
+        src_validator: impl ToString,
+        dst_validator: impl ToString,
+        amount: &cosmwasm_std::Coin,
+    ) -> Any {
+        any(
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+            MsgBeginRedelegate {
+                delegator_address: delegator.to_string(),
+                validator_src_address: src_validator.to_string(),
+                validator_dst_address: dst_validator.to_string(),
+                amount: Some(proto_coin(amount)),
+            },
+        )
+    }
+}
+
+/// Messages of the `cosmos.distribution.v1beta1` module
+pub mod distribution {
+    use super::*;
+    use cosmrs::proto::cosmos::distribution::v1beta1::{
+        MsgSetWithdrawAddress, MsgWithdrawDelegatorReward,
+    };
+
+    /// Withdraws the staking rewards of a delegation
+    pub fn withdraw_delegator_reward(delegator: impl ToString, validator: impl ToString) -> Any {
+        any(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            MsgWithdrawDelegatorReward {
+                delegator_address: delegator.to_string(),
+                validator_address: validator.to_string(),
+            },
+        )
+    }
+
+    /// Sets the address the staking rewards of the delegator are withdrawn to
+    pub fn set_withdraw_address(delegator: impl ToString, withdraw_address: impl ToString) -> Any {
+        any(
+            "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+            MsgSetWithdrawAddress {
+                delegator_address: delegator.to_string(),
+                withdraw_address: withdraw_address.to_string(),
+            },
+        )
+    }
+}
+
+/// Messages of the `cosmos.authz.v1beta1` module
+pub mod authz {
+    use super::*;
+    use cosmrs::proto::cosmos::authz::v1beta1::{Grant, MsgGrant, MsgRevoke};
+    use prost_types::Timestamp;
+
+    /// Grants the encoded `authorization` to `grantee`, until `expiration` if set
+    pub fn grant(
+        granter: impl ToString,
+        grantee: impl ToString,
+        authorization: Any,
+        expiration: Option<Timestamp>,
+    ) -> Any {
+        any(
+            "/cosmos.authz.v1beta1.MsgGrant",
+            MsgGrant {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+                grant: Some(Grant {
+                    authorization: Some(authorization),
+                    expiration,
+                }),
+            },
+        )
+    }
+
+    /// Revokes the authorization of `grantee` to send messages of type `msg_type_url`
+    pub fn revoke(
+        granter: impl ToString,
+        grantee: impl ToString,
+        msg_type_url: impl ToString,
+    ) -> Any {
+        any(
+            "/cosmos.authz.v1beta1.MsgRevoke",
+            MsgRevoke {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+                msg_type_url: msg_type_url.to_string(),
+            },
+        )
+    }
+}
+
+/// Messages of the `cosmos.feegrant.v1beta1` module
+pub mod feegrant {
+    use super::*;
+    use cosmrs::proto::cosmos::feegrant::v1beta1::{MsgGrantAllowance, MsgRevokeAllowance};
+
+    /// Grants the encoded fee `allowance` to `grantee`
+    pub fn grant_allowance(granter: impl ToString, grantee: impl ToString, allowance: Any) -> Any {
+        any(
+            "/cosmos.feegrant.v1beta1.MsgGrantAllowance",
+            MsgGrantAllowance {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+                allowance: Some(allowance),
+            },
+        )
+    }
+
+    /// Revokes the fee allowance of `grantee`
+    pub fn revoke_allowance(granter: impl ToString, grantee: impl ToString) -> Any {
+        any(
+            "/cosmos.feegrant.v1beta1.MsgRevokeAllowance",
+            MsgRevokeAllowance {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+            },
+        )
+    }
+}
+
+/// Messages of the `cosmwasm.wasm.v1` module
+pub mod wasm {
+    use super::*;
+    use crate::DaemonError;
+    use cosmrs::proto::cosmwasm::wasm::v1::{
+        MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract,
+        MsgUpdateAdmin,
+    };
+    use serde::Serialize;
+
+    /// Executes a contract with the json encoded `msg`
+    pub fn execute(
+        sender: impl ToString,
+        contract: impl ToString,
+        msg: &impl Serialize,
+        funds: &[cosmwasm_std::Coin],
+    ) -> Result<Any, DaemonError> {
+        Ok(any(
+            "/cosmwasm.wasm.v1.MsgExecuteContract",
+            MsgExecuteContract {
+                sender: sender.to_string(),
+                contract: contract.to_string(),
+                msg: serde_json::to_vec(msg)?,
+                funds: proto_coins(funds),
+            },
+        ))
+    }
+
+    /// Instantiates a contract from `code_id` with the json encoded `msg`
+    pub fn instantiate(
+        sender: impl ToString,
+        code_id: u64,
+        msg: &impl Serialize,
+        label: impl ToString,
+        admin: Option<String>,
+        funds: &[cosmwasm_std::Coin],
+    ) -> Result<Any, DaemonError> {
+        Ok(any(
+            "/cosmwasm.wasm.v1.MsgInstantiateContract",
+            MsgInstantiateContract {
+                sender: sender.to_string(),
+                admin: admin.unwrap_or_default(),
+                code_id,
+                label: label.to_string(),
+                msg: serde_json::to_vec(msg)?,
+                funds: proto_coins(funds),
+            },
+        ))
+    }
+
+    /// Migrates a contract to `code_id` with the json encoded `msg`
+    pub fn migrate(
+        sender: impl ToString,
+        contract: impl ToString,
+        code_id: u64,
+        msg: &impl Serialize,
+    ) -> Result<Any, DaemonError> {
+        Ok(any(
+            "/cosmwasm.wasm.v1.MsgMigrateContract",
+            MsgMigrateContract {
+                sender: sender.to_string(),
+                contract: contract.to_string(),
+                code_id,
+                msg: serde_json::to_vec(msg)?,
+            },
+        ))
+    }
+
+    /// Sets the admin of a contract
+    pub fn update_admin(
+        sender: impl ToString,
+        contract: impl ToString,
+        new_admin: impl ToString,
+    ) -> Any {
+        any(
+            "/cosmwasm.wasm.v1.MsgUpdateAdmin",
+            MsgUpdateAdmin {
+                sender: sender.to_string(),
+                new_admin: new_admin.to_string(),
+                contract: contract.to_string(),
+            },
+        )
+    }
+
+    /// Removes the admin of a contract, which makes it non-migratable
+    pub fn clear_admin(sender: impl ToString, contract: impl ToString) -> Any {
+        any(
+            "/cosmwasm.wasm.v1.MsgClearAdmin",
+            MsgClearAdmin {
+                sender: sender.to_string(),
+                contract: contract.to_string(),
+            },
+        )
+    }
+}
+
+/// Package of the tokenfactory module used by most chains
+pub const OSMOSIS_TOKENFACTORY_PACKAGE: &str = "osmosis.tokenfactory.v1beta1";
+
+/// Messages of the tokenfactory module, in the package used by the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenFactoryMsgs {
+    /// Proto package of the module, defaults to [`OSMOSIS_TOKENFACTORY_PACKAGE`]
+    pub package: String,
+}
+
+impl Default for TokenFactoryMsgs {
+    fn default() -> Self {
+        Self::new(OSMOSIS_TOKENFACTORY_PACKAGE)
+    }
+}
+
+impl TokenFactoryMsgs {
+    /// Messages in the proto package `package`
+    pub fn new(package: impl Into<String>) -> Self {
+        Self {
+            package: package.into(),
+        }
+    }
+
+    /// Messages in the package used by the chain named `chain_name` (see `NetworkInfo::chain_name`)
+    pub fn for_chain(chain_name: &str) -> Self {
+        match chain_name {
+            "injective" => Self::new("injective.tokenfactory.v1beta1"),
+            "sei" => Self::new("seiprotocol.seichain.tokenfactory"),
+            "kujira" => Self::new("kujira.denom"),
+            _ => Self::default(),
+        }
+    }
+
+    fn type_url(&self, msg: &str) -> String {
+        format!("/{}.{}", self.package, msg)
+    }
+
+    /// Creates the denom `factory/{sender}/{subdenom}`
+    pub fn create_denom(&self, sender: impl ToString, subdenom: impl ToString) -> Any {
+        any(
+            &self.type_url("MsgCreateDenom"),
+            crate::proto::tokenfactory::MsgCreateDenom {
+                sender: sender.to_string(),
+                subdenom: subdenom.to_string(),
+            },
+        )
+    }
+
+    /// Mints `amount` of a denom administered by the sender to `recipient`
+    pub fn mint(
+        &self,
+        sender: impl ToString,
+        amount: &cosmwasm_std::Coin,
+        recipient: impl ToString,
+    ) -> Any {
+        any(
+            &self.type_url("MsgMint"),
+            crate::proto::tokenfactory::MsgMint {
+                sender: sender.to_string(),
+                amount: Some(proto_coin(amount)),
+                mint_to_address: recipient.to_string(),
+            },
+        )
+    }
+
+    /// Burns `amount` of a denom administered by the sender from `burn_from`
+    pub fn burn(
+        &self,
+        sender: impl ToString,
+        amount: &cosmwasm_std::Coin,
+        burn_from: impl ToString,
+    ) -> Any {
+        any(
+            &self.type_url("MsgBurn"),
+            crate::proto::tokenfactory::MsgBurn {
+                sender: sender.to_string(),
+                amount: Some(proto_coin(amount)),
+                burn_from_address: burn_from.to_string(),
+            },
+        )
+    }
+
+    /// Transfers the administration of `denom` to `new_admin`
+    pub fn change_admin(
+        &self,
+        sender: impl ToString,
+        denom: impl ToString,
+        new_admin: impl ToString,
+    ) -> Any {
+        any(
+            &self.type_url("MsgChangeAdmin"),
+            crate::proto::tokenfactory::MsgChangeAdmin {
+                sender: sender.to_string(),
+                denom: denom.to_string(),
+                new_admin: new_admin.to_string(),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::coin;
+
+    #[test]
+    fn type_urls() {
+        let delegate = staking::delegate("delegator", "validator", &coin(100, "ujuno"));
+        assert_eq!(delegate.type_url, "/cosmos.staking.v1beta1.MsgDelegate");
+        let decoded =
+            cosmrs::proto::cosmos::staking::v1beta1::MsgDelegate::decode(delegate.value.as_slice())
+                .unwrap();
+        assert_eq!(decoded.validator_address, "validator");
+        assert_eq!(decoded.amount.unwrap().amount, "100");
+
+        let vote = gov::vote("voter", 4, gov::VoteOption::NoWithVeto);
+        assert_eq!(vote.type_url, "/cosmos.gov.v1.MsgVote");
+
+        assert_eq!(
+            TokenFactoryMsgs::for_chain("osmosis")
+                .create_denom("sender", "token")
+                .type_url,
+            "/osmosis.tokenfactory.v1beta1.MsgCreateDenom"
+        );
+        assert_eq!(
+            TokenFactoryMsgs::for_chain("injective")
+                .mint("sender", &coin(1, "factory/sender/token"), "sender")
+                .type_url,
+            "/injective.tokenfactory.v1beta1.MsgMint"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Garbage collection of the daemon state file.
+//!
+//! State files accumulate entries over time: contracts of chains that were reset, removed entries and old deployments.
+//! The [`StatePruner`] removes contracts and code ids that don't exist on chain anymore, archives old deployments and compacts the state file.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{prune::StatePruner, Daemon, networks};
+//!
+//! let daemon = Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap();
+//!
+//! // List what would be removed
+//! let report = StatePruner::new(daemon.clone()).dry_run().prune().unwrap();
+//! println!("{}", report);
+//!
+//! // Remove dead entries and move the `v1` deployment to the archive file
+//! StatePruner::new(daemon).archive(["v1"]).prune().unwrap();
+//! ```
+
+use std::fmt::Display;
+
+use cosmwasm_std::Addr;
+use cw_orch_core::environment::{ChainState, StateInterface};
+
+use crate::{queriers::CosmWasm, Daemon, DaemonError};
+
+/// Entry removed or archived by the [`StatePruner`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PruneAction {
+    /// The contract doesn't exist on chain anymore
+    RemoveAddress {
+        /// Deployment containing the contract
+        deployment_id: String,
+        /// Id of the contract
+        contract_id: String,
+        /// Stored address
+        address: Addr,
+    },
+    /// The code doesn't exist on chain anymore
+    RemoveCodeId {
+        /// Id of the contract
+        contract_id: String,
+        /// Stored code id
+        code_id: u64,
+    },
+    /// The deployment is moved to the archive file
+    ArchiveDeployment {
+        /// Archived deployment
+        deployment_id: String,
+    },
+}
+
+impl Display for PruneAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PruneAction::RemoveAddress {
+                deployment_id,
+                contract_id,
+                address,
+            } => write!(
+                f,
+                "remove address of {contract_id} in deployment {deployment_id} ({address})"
+            ),
+            PruneAction::RemoveCodeId {
+                contract_id,
+                code_id,
+            } => write!(f, "remove code id of {contract_id} ({code_id})"),
+            PruneAction::ArchiveDeployment { deployment_id } => {
+                write!(f, "archive deployment {deployment_id}")
+            }
+        }
+    }
+}
+
+/// Outcome of [`StatePruner::prune`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PruneReport {
+    /// Chain whose state was pruned
+    pub chain_id: String,
+    /// Whether the state was left untouched
+    pub dry_run: bool,
+    /// Removed or archived entries
+    pub actions: Vec<PruneAction>,
+    /// Number of removed entries deleted while compacting the state file
+    pub compacted_entries: usize,
+}
+
+impl Display for PruneReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "State pruning of {}{}:",
+            self.chain_id,
+            if self.dry_run { " (dry-run)" } else { "" }
+        )?;
+        for action in &self.actions {
+            writeln!(f, "  {}", action)?;
+        }
+        if !self.dry_run {
+            writeln!(f, "  compacted {} removed entries", self.compacted_entries)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prunes the state of a [`Daemon`] for its chain
+pub struct StatePruner {
+    daemon: Daemon,
+    dry_run: bool,
+    archive: Vec<String>,
+}
+
+impl StatePruner {
+    /// Creates a pruner for the state of the chain the daemon is connected to
+    pub fn new(daemon: Daemon) -> Self {
+        Self {
+            daemon,
+            dry_run: false,
+            archive: vec![],
+        }
+    }
+
+    /// Only list the entries that would be removed, without modifying the state
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Deployments to move to the archive file
+    pub fn archive(mut self, deployment_ids: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.archive = deployment_ids.into_iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    /// Verifies all stored contracts and code ids against the chain and removes the dead entries,
+    /// archives the requested deployments and compacts the state file
+    pub fn prune(&self) -> Result<PruneReport, DaemonError> {
+        let mut state = self.daemon.state();
+        let wasm = CosmWasm::new_async(self.daemon.channel());
+        if self.archive.contains(&state.deployment_id) {
+            return Err(DaemonError::StdErr(format!(
+                "Can't archive deployment {}, it's in use",
+                state.deployment_id
+            )));
+        }
+
+        let mut actions = vec![];
+        for deployment_id in state.deployment_ids()? {
+            if self.archive.contains(&deployment_id) {
+                actions.push(PruneAction::ArchiveDeployment { deployment_id });
+                continue;
+            }
+            let addresses = state.get(&deployment_id)?;
+            for (contract_id, address) in addresses.as_object().cloned().unwrap_or_default() {
+                let Some(address) = address.as_str() else {
+                    continue;
+                };
+                let exists = self.daemon.rt_handle.block_on(wasm._contract_info(address));
+                if is_not_found(exists)? {
+                    actions.push(PruneAction::RemoveAddress {
+                        deployment_id: deployment_id.clone(),
+                        contract_id,
+                        address: Addr::unchecked(address),
+                    });
+                }
+            }
+        }
+
+        for (contract_id, code_id) in state.get_all_code_ids()? {
+            let exists = self.daemon.rt_handle.block_on(wasm._code(code_id));
+            if is_not_found(exists)? {
+                actions.push(PruneAction::RemoveCodeId {
+                    contract_id,
+                    code_id,
+                });
+            }
+        }
+
+        let mut compacted_entries = 0;
+        if !self.dry_run {
+            for action in &actions {
+                log::info!("Pruning state: {}", action);
+                match action {
+                    PruneAction::RemoveAddress {
+                        deployment_id,
+                        contract_id,
+                        ..
+                    } => state.remove(deployment_id, contract_id)?,
+                    PruneAction::RemoveCodeId { contract_id, .. } => {
+                        state.remove_code_id(contract_id)
+                    }
+                    PruneAction::ArchiveDeployment { deployment_id } => {
+                        state.archive_deployment(deployment_id)?;
+                    }
+                }
+            }
+            compacted_entries = state.compact()?;
+        }
+
+        Ok(PruneReport {
+            chain_id: state.chain_data.chain_id.to_string(),
+            dry_run: self.dry_run,
+            actions,
+            compacted_entries,
+        })
+    }
+}
+
+/// Returns true if the query failed because the queried object doesn't exist.
+/// Other errors (e.g. connection errors) are returned so that no entry is removed by mistake
+fn is_not_found<T>(query_result: Result<T, DaemonError>) -> Result<bool, DaemonError> {
+    match query_result {
+        Ok(_) => Ok(false),
+        Err(DaemonError::Status(status))
+            if status.code() == tonic::Code::NotFound
+                || status.message().contains("not found")
+                || status.message().contains("no such") =>
+        {
+            Ok(true)
+        }
+        Err(e) => Err(e),
+    }
+}
@@ -44,6 +44,37 @@ macro_rules! cosmos_query {
 };
 }
 
+/// Performs a unary gRPC query on `path`, e.g. `/cosmos.bank.v1beta1.Query/Balance`.
+/// This is used for modules that don't have a generated query client.
+pub(crate) async fn raw_grpc_query<Req, Res>(
+    channel: tonic::transport::Channel,
+    path: &str,
+    request: &Req,
+) -> Result<Res, crate::DaemonError>
+where
+    Req: prost::Message + Clone + Send + Sync + 'static,
+    Res: prost::Message + Default + Send + Sync + 'static,
+{
+    #[cfg(feature = "metrics")]
+    let (method, start) = (path.to_string(), std::time::Instant::now());
+    let path = tonic::codegen::http::uri::PathAndQuery::try_from(path)
+        .map_err(|e| crate::DaemonError::StdErr(format!("Invalid query path {path}: {e}")))?;
+    let mut client = tonic::client::Grpc::new(channel);
+    client.ready().await?;
+    let resp = client
+        .unary(
+            tonic::Request::new(request.clone()),
+            path,
+            tonic::codec::ProstCodec::default(),
+        )
+        .await;
+    #[cfg(feature = "metrics")]
+    crate::metrics::record_query(&method, start, &resp);
+
+    Ok(resp?.into_inner())
+}
+
+mod auth;
 mod authz;
 mod bank;
 mod cosmwasm;
@@ -53,13 +84,27 @@ mod gov;
 mod ibc;
 mod node;
 mod staking;
+#[cfg(feature = "rpc")]
+mod tendermint_rpc;
+mod tokenfactory;
 
+pub use auth::{
+    ibc_escrow_address, module_address, Auth, DISTRIBUTION_MODULE, FEE_COLLECTOR_MODULE,
+    GOV_MODULE, TRANSFER_MODULE,
+};
 pub use authz::Authz;
 pub use bank::{cosmrs_to_cosmwasm_coins, Bank};
-pub use cosmwasm::CosmWasm;
+pub(crate) use cosmwasm::smart_contract_state;
+pub use cosmwasm::{
+    CodeAccess, ContractHistoryEntry, ContractOperation, CosmWasm, CosmWasmBase,
+    InstantiatePermission, SmartQueryOptions,
+};
 pub use feegrant::FeeGrant;
-pub use ibc::Ibc;
+pub use ibc::{Ibc, IbcChannelHealth};
 pub use node::Node;
+#[cfg(feature = "rpc")]
+pub use tendermint_rpc::TendermintRpc;
+pub use tokenfactory::TokenFactory;
 
 // this two containt structs that are helpers for the queries
 pub use gov::*;
@@ -0,0 +1,148 @@
+use crate::{cosmos_modules, error::DaemonError, sender::QuerySender, DaemonBase};
+use cosmrs::AccountId;
+use cosmwasm_std::Addr;
+use cw_orch_core::environment::{Querier, QuerierGetter};
+use prost::Message;
+use sha2::{Digest, Sha256};
+use tokio::runtime::Handle;
+use tonic::transport::Channel;
+
+/// Name of the module account of the gov module, owner of the deposits
+pub const GOV_MODULE: &str = "gov";
+/// Name of the module account of the distribution module, owner of the rewards and the community pool
+pub const DISTRIBUTION_MODULE: &str = "distribution";
+/// Name of the module account of the ibc transfer module, minter of the received vouchers
+pub const TRANSFER_MODULE: &str = "transfer";
+/// Name of the module account collecting the transaction fees
+pub const FEE_COLLECTOR_MODULE: &str = "fee_collector";
+
+/// Version of the ICS20 protocol, used to derive the escrow addresses
+const ICS20_VERSION: &str = "ics20-1";
+
+/// Address of the module account `module_name` on a chain with the bech32 prefix `prefix`
+pub fn module_address(prefix: &str, module_name: &str) -> Result<Addr, DaemonError> {
+    let hash = Sha256::digest(module_name.as_bytes());
+    Ok(Addr::unchecked(
+        AccountId::new(prefix, &hash[..20])?.to_string(),
+    ))
+}
+
+/// Address of the account escrowing the tokens sent over the channel `channel` of the port `port` (usually `transfer`),
+/// on a chain with the bech32 prefix `prefix`
+pub fn ibc_escrow_address(prefix: &str, port: &str, channel: &str) -> Result<Addr, DaemonError> {
+    let mut preimage = ICS20_VERSION.as_bytes().to_vec();
+    preimage.push(0);
+    preimage.extend(format!("{port}/{channel}").as_bytes());
+    let hash = Sha256::digest(preimage);
+    Ok(Addr::unchecked(
+        AccountId::new(prefix, &hash[..20])?.to_string(),
+    ))
+}
+
+/// Querier for the Cosmos Auth module
+/// All the async function are prefixed with `_`
+///
+/// Module accounts can also be derived offline with [`module_address`]
+pub struct Auth {
+    pub channel: Channel,
+    pub rt_handle: Option<Handle>,
+}
+
+impl Auth {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
+        Self {
+            channel: daemon.channel(),
+            rt_handle: Some(daemon.rt_handle.clone()),
+        }
+    }
+
+    pub fn new_async(channel: Channel) -> Self {
+        Self {
+            channel,
+            rt_handle: None,
+        }
+    }
+}
+
+impl Querier for Auth {
+    type Error = DaemonError;
+}
+
+impl<S: QuerySender> QuerierGetter<Auth> for DaemonBase<S> {
+    fn querier(&self) -> Auth {
+        Auth::new(self)
+    }
+}
+
+impl Auth {
+    /// Query all the module accounts of the chain
+    pub async fn _module_accounts(
+        &self,
+    ) -> Result<Vec<cosmos_modules::auth::ModuleAccount>, DaemonError> {
+        let module_accounts: cosmos_modules::auth::QueryModuleAccountsResponse =
+            cosmos_query!(self, auth, module_accounts, QueryModuleAccountsRequest {});
+        module_accounts
+            .accounts
+            .into_iter()
+            .map(|account| {
+                Ok(cosmos_modules::auth::ModuleAccount::decode(
+                    account.value.as_slice(),
+                )?)
+            })
+            .collect()
+    }
+
+    /// Query the module account `name` (e.g. [`GOV_MODULE`])
+    pub async fn _module_account(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<cosmos_modules::auth::ModuleAccount, DaemonError> {
+        let name = name.into();
+        let module_account: cosmos_modules::auth::QueryModuleAccountByNameResponse = cosmos_query!(
+            self,
+            auth,
+            module_account_by_name,
+            QueryModuleAccountByNameRequest { name: name.clone() }
+        );
+        let account = module_account.account.ok_or(DaemonError::StdErr(format!(
+            "Module account {name} not found"
+        )))?;
+        Ok(cosmos_modules::auth::ModuleAccount::decode(
+            account.value.as_slice(),
+        )?)
+    }
+
+    /// Query the address of the module account `name` (e.g. [`GOV_MODULE`])
+    pub async fn _module_address(&self, name: impl Into<String>) -> Result<Addr, DaemonError> {
+        let module_account = self._module_account(name).await?;
+        let base_account = module_account
+            .base_account
+            .ok_or(DaemonError::StdErr(format!(
+                "Module account {} has no base account",
+                module_account.name
+            )))?;
+        Ok(Addr::unchecked(base_account.address))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn derived_addresses() -> anyhow::Result<()> {
+        assert_eq!(
+            module_address("cosmos", GOV_MODULE)?,
+            Addr::unchecked("cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn")
+        );
+        assert_eq!(
+            module_address("cosmos", DISTRIBUTION_MODULE)?,
+            Addr::unchecked("cosmos1jv65s3grqf6v6jl3dp4t6c9t9rk99cd88lyufl")
+        );
+        assert_eq!(
+            ibc_escrow_address("cosmos", "transfer", "channel-141")?,
+            Addr::unchecked("cosmos1x54ltnyg88k0ejmk8ytwrhd3ltm84xehrnlslf")
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{cosmos_modules, error::DaemonError, sender::QuerySender, DaemonBase};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 use cw_orch_core::environment::{Querier, QuerierGetter};
 use tokio::runtime::Handle;
@@ -12,7 +12,7 @@ pub struct Authz {
 }
 
 impl Authz {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
@@ -31,7 +31,7 @@ impl Querier for Authz {
     type Error = DaemonError;
 }
 
-impl QuerierGetter<Authz> for Daemon {
+impl<S: QuerySender> QuerierGetter<Authz> for DaemonBase<S> {
     fn querier(&self) -> Authz {
         Authz::new(self)
     }
@@ -1,9 +1,17 @@
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{
+    cosmos_modules,
+    error::DaemonError,
+    proto::bank::{QuerySendEnabledRequest, QuerySendEnabledResponse},
+    sender::QuerySender,
+    DaemonBase,
+};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 use cosmwasm_std::{Coin, StdError};
 use cw_orch_core::environment::{BankQuerier, Querier, QuerierGetter};
 use tokio::runtime::Handle;
-use tonic::transport::Channel;
+use tonic::{transport::Channel, Code};
+
+use super::raw_grpc_query;
 
 /// Queries for Cosmos Bank Module
 /// All the async function are prefixed with `_`
@@ -13,7 +21,7 @@ pub struct Bank {
 }
 
 impl Bank {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
@@ -31,7 +39,7 @@ impl Querier for Bank {
     type Error = DaemonError;
 }
 
-impl QuerierGetter<Bank> for Daemon {
+impl<S: QuerySender> QuerierGetter<Bank> for DaemonBase<S> {
     fn querier(&self) -> Bank {
         Bank::new(self)
     }
@@ -150,6 +158,53 @@ impl Bank {
         );
         Ok(denoms_metadata.metadatas)
     }
+
+    /// Query the send enabled status of `denoms`.
+    /// Denoms without a specific status use the `default_send_enabled` param.
+    ///
+    /// Before cosmos-sdk 0.47, the status is read from the `send_enabled` param
+    pub async fn _send_enabled(
+        &self,
+        denoms: Vec<String>,
+    ) -> Result<Vec<cosmos_modules::bank::SendEnabled>, DaemonError> {
+        let configured = match raw_grpc_query::<_, QuerySendEnabledResponse>(
+            self.channel.clone(),
+            "/cosmos.bank.v1beta1.Query/SendEnabled",
+            &QuerySendEnabledRequest {
+                denoms: denoms.clone(),
+                pagination: None,
+            },
+        )
+        .await
+        {
+            Ok(response) => response.send_enabled,
+            Err(DaemonError::Status(status)) if status.code() == Code::Unimplemented => {
+                self._params().await?.send_enabled
+            }
+            Err(e) => return Err(e),
+        };
+
+        let default_send_enabled = self._params().await?.default_send_enabled;
+        Ok(denoms
+            .into_iter()
+            .map(|denom| {
+                configured
+                    .iter()
+                    .find(|send_enabled| send_enabled.denom == denom)
+                    .cloned()
+                    .unwrap_or(cosmos_modules::bank::SendEnabled {
+                        denom,
+                        enabled: default_send_enabled,
+                    })
+            })
+            .collect())
+    }
+
+    /// Query if `denom` can be transferred
+    pub async fn _is_send_enabled(&self, denom: impl Into<String>) -> Result<bool, DaemonError> {
+        let send_enabled = self._send_enabled(vec![denom.into()]).await?;
+        Ok(send_enabled[0].enabled)
+    }
 }
 
 pub fn cosmrs_to_cosmwasm_coin(
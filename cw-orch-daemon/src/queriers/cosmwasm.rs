@@ -1,60 +1,180 @@
-use std::str::FromStr;
+use std::{marker::PhantomData, str::FromStr, time::Duration};
 
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{
+    cache::{cached, ChainQueryCache},
+    cosmos_modules,
+    error::DaemonError,
+    proto::wasm::{
+        AccessConfig, QueryCodeInfoResponse, QueryContractsByCreatorRequest,
+        QueryContractsByCreatorResponse,
+    },
+    sender::QuerySender,
+    DaemonBase, Wallet,
+};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 use cosmrs::AccountId;
 use cosmwasm_std::{
-    from_json, instantiate2_address, to_json_binary, CanonicalAddr, CodeInfoResponse,
+    from_json, instantiate2_address, to_json_binary, Addr, Binary, CanonicalAddr, CodeInfoResponse,
     ContractInfoResponse, HexBinary,
 };
 use cw_orch_core::{
     contract::interface_traits::Uploadable,
-    environment::{Querier, QuerierGetter, WasmQuerier},
+    environment::{Querier, QuerierGetter, StorageDump, StorageInspector, WasmQuerier},
 };
+use sha2::{Digest, Sha256};
 use tokio::runtime::Handle;
 use tonic::transport::Channel;
 
+use super::raw_grpc_query;
+
 /// Querier for the CosmWasm SDK module
 /// All the async function are prefixed with `_`
-pub struct CosmWasm {
+pub struct CosmWasmBase<S = Wallet> {
     pub channel: Channel,
     pub rt_handle: Option<Handle>,
+    cache: Option<ChainQueryCache>,
+    _sender: PhantomData<S>,
+}
+
+/// Querier for the CosmWasm SDK module of a [`Daemon`](crate::Daemon)
+pub type CosmWasm = CosmWasmBase<Wallet>;
+
+/// Number of latest codes looked at when looking a code up by checksum
+pub const CHECKSUM_LOOKUP_LIMIT: u64 = 100;
+
+/// Addresses allowed to instantiate a code
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InstantiatePermission {
+    Nobody,
+    Everybody,
+    AnyOfAddresses(Vec<Addr>),
 }
 
-impl CosmWasm {
-    pub fn new(daemon: &Daemon) -> Self {
+impl InstantiatePermission {
+    /// Whether `address` can instantiate the code
+    pub fn allows(&self, address: &Addr) -> bool {
+        match self {
+            InstantiatePermission::Nobody => false,
+            InstantiatePermission::Everybody => true,
+            InstantiatePermission::AnyOfAddresses(addresses) => addresses.contains(address),
+        }
+    }
+}
+
+impl From<AccessConfig> for InstantiatePermission {
+    fn from(access: AccessConfig) -> Self {
+        match access.permission {
+            1 => InstantiatePermission::Nobody,
+            // Deprecated `OnlyAddress`
+            2 => InstantiatePermission::AnyOfAddresses(vec![Addr::unchecked(access.address)]),
+            4 => InstantiatePermission::AnyOfAddresses(
+                access.addresses.into_iter().map(Addr::unchecked).collect(),
+            ),
+            _ => InstantiatePermission::Everybody,
+        }
+    }
+}
+
+/// Metadata of an uploaded code, including who can instantiate it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeAccess {
+    pub code_id: u64,
+    pub creator: Addr,
+    pub checksum: HexBinary,
+    pub instantiate_permission: InstantiatePermission,
+}
+
+/// Operation that changed the code of a contract
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContractOperation {
+    Instantiate,
+    Migrate,
+    /// Contract imported at genesis
+    Genesis,
+}
+
+/// Entry of the code history of a contract
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractHistoryEntry {
+    pub operation: ContractOperation,
+    pub code_id: u64,
+    /// Height of the operation, not set for genesis contracts
+    pub block_height: Option<u64>,
+    /// Instantiate or migrate message
+    pub msg: Binary,
+}
+
+impl From<cosmos_modules::cosmwasm::ContractCodeHistoryEntry> for ContractHistoryEntry {
+    fn from(entry: cosmos_modules::cosmwasm::ContractCodeHistoryEntry) -> Self {
+        let operation = match entry.operation {
+            2 => ContractOperation::Migrate,
+            3 => ContractOperation::Genesis,
+            _ => ContractOperation::Instantiate,
+        };
+        Self {
+            operation,
+            code_id: entry.code_id,
+            block_height: entry
+                .updated
+                .map(|position| position.block_height)
+                .filter(|height| *height != 0),
+            msg: entry.msg.into(),
+        }
+    }
+}
+
+impl<S: QuerySender> CosmWasmBase<S> {
+    pub fn new(daemon: &DaemonBase<S>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
+            cache: daemon.daemon.chain_query_cache(),
+            _sender: PhantomData,
         }
     }
     pub fn new_async(channel: Channel) -> Self {
         Self {
             channel,
             rt_handle: None,
+            cache: None,
+            _sender: PhantomData,
         }
     }
+
+    /// Caches the responses to immutable queries in `cache`, see [`crate::cache`]
+    pub fn with_cache(mut self, cache: ChainQueryCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 }
 
-impl QuerierGetter<CosmWasm> for Daemon {
-    fn querier(&self) -> CosmWasm {
-        CosmWasm::new(self)
+impl<S: QuerySender> QuerierGetter<CosmWasmBase<S>> for DaemonBase<S> {
+    fn querier(&self) -> CosmWasmBase<S> {
+        CosmWasmBase::new(self)
     }
 }
 
-impl Querier for CosmWasm {
+impl<S> Querier for CosmWasmBase<S> {
     type Error = DaemonError;
 }
 
-impl CosmWasm {
+impl<S> CosmWasmBase<S> {
     /// Query code_id by hash
     pub async fn _code_id_hash(&self, code_id: u64) -> Result<HexBinary, DaemonError> {
         use cosmos_modules::cosmwasm::{query_client::*, QueryCodeRequest};
-        let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
-        let request = QueryCodeRequest { code_id };
-        let resp = client.code(request).await?.into_inner();
-        let contract_hash = resp.code_info.unwrap().data_hash;
-        Ok(contract_hash.into())
+        cached(
+            self.cache.as_ref(),
+            format!("code_checksum/{code_id}"),
+            None,
+            || async {
+                let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
+                let request = QueryCodeRequest { code_id };
+                let resp = client.code(request).await?.into_inner();
+                let contract_hash = resp.code_info.unwrap().data_hash;
+                Ok(contract_hash.into())
+            },
+        )
+        .await
     }
 
     /// Query contract info
@@ -63,27 +183,36 @@ impl CosmWasm {
         address: impl Into<String>,
     ) -> Result<ContractInfoResponse, DaemonError> {
         use cosmos_modules::cosmwasm::{query_client::*, QueryContractInfoRequest};
-        let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
-        let request = QueryContractInfoRequest {
-            address: address.into(),
-        };
-        let resp = client.contract_info(request).await?.into_inner();
-        let contract_info = resp.contract_info.unwrap();
-
-        let mut c = ContractInfoResponse::default();
-        c.code_id = contract_info.code_id;
-        c.creator = contract_info.creator;
-        c.admin = if contract_info.admin.is_empty() {
-            None
-        } else {
-            Some(contract_info.admin)
-        };
-        c.ibc_port = if contract_info.ibc_port_id.is_empty() {
-            None
-        } else {
-            Some(contract_info.ibc_port_id)
-        };
-        Ok(c)
+        let address = address.into();
+        // Contract info changes with migrations and admin updates, it's only cached on demand
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|cache| cache.cache().caches_contract_info());
+        cached(cache, format!("contract_info/{address}"), None, || async {
+            let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
+            let request = QueryContractInfoRequest {
+                address: address.clone(),
+            };
+            let resp = client.contract_info(request).await?.into_inner();
+            let contract_info = resp.contract_info.unwrap();
+
+            let mut c = ContractInfoResponse::default();
+            c.code_id = contract_info.code_id;
+            c.creator = contract_info.creator;
+            c.admin = if contract_info.admin.is_empty() {
+                None
+            } else {
+                Some(contract_info.admin)
+            };
+            c.ibc_port = if contract_info.ibc_port_id.is_empty() {
+                None
+            } else {
+                Some(contract_info.ibc_port_id)
+            };
+            Ok(c)
+        })
+        .await
     }
 
     /// Query contract history
@@ -101,23 +230,98 @@ impl CosmWasm {
         Ok(client.contract_history(request).await?.into_inner())
     }
 
+    /// Query the whole code history of a contract, oldest first, following pagination
+    pub async fn _contract_history_entries(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<Vec<ContractHistoryEntry>, DaemonError> {
+        let address = address.into();
+        let mut entries = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response = self
+                ._contract_history(
+                    address.clone(),
+                    Some(PageRequest {
+                        key: next_key,
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+            entries.extend(response.entries.into_iter().map(ContractHistoryEntry::from));
+            match response.pagination {
+                Some(page) if !page.next_key.is_empty() => next_key = page.next_key,
+                _ => break,
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Query the admin of a contract, `None` if the contract has no admin
+    pub async fn _contract_admin(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<Option<Addr>, DaemonError> {
+        let contract_info = self._contract_info(address).await?;
+        Ok(contract_info.admin.map(Addr::unchecked))
+    }
+
+    /// Query the address that instantiated a contract
+    pub async fn _contract_creator(&self, address: impl Into<String>) -> Result<Addr, DaemonError> {
+        let contract_info = self._contract_info(address).await?;
+        Ok(Addr::unchecked(contract_info.creator))
+    }
+
+    /// Query the addresses of all the contracts instantiated by `creator`, following pagination.
+    /// Only available since wasmd 0.40
+    pub async fn _contracts_by_creator(
+        &self,
+        creator: impl Into<String>,
+    ) -> Result<Vec<Addr>, DaemonError> {
+        let creator = creator.into();
+        let mut contracts = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: QueryContractsByCreatorResponse = raw_grpc_query(
+                self.channel.clone(),
+                "/cosmwasm.wasm.v1.Query/ContractsByCreator",
+                &QueryContractsByCreatorRequest {
+                    creator_address: creator.clone(),
+                    pagination: Some(PageRequest {
+                        key: next_key,
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await?;
+            contracts.extend(response.contract_addresses.into_iter().map(Addr::unchecked));
+            match response.pagination {
+                Some(page) if !page.next_key.is_empty() => next_key = page.next_key,
+                _ => break,
+            }
+        }
+        Ok(contracts)
+    }
+
     /// Query contract state
     pub async fn _contract_state(
         &self,
         address: impl Into<String>,
         query_data: Vec<u8>,
     ) -> Result<Vec<u8>, DaemonError> {
-        use cosmos_modules::cosmwasm::{query_client::*, QuerySmartContractStateRequest};
-        let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
-        let request = QuerySmartContractStateRequest {
-            address: address.into(),
-            query_data,
-        };
-        Ok(client
-            .smart_contract_state(request)
-            .await?
-            .into_inner()
-            .data)
+        self._contract_state_with_options(address, query_data, &SmartQueryOptions::default())
+            .await
+    }
+
+    /// Query contract state with a timeout, see [`SmartQueryOptions`]
+    pub async fn _contract_state_with_options(
+        &self,
+        address: impl Into<String>,
+        query_data: Vec<u8>,
+        options: &SmartQueryOptions,
+    ) -> Result<Vec<u8>, DaemonError> {
+        let address = address.into();
+        smart_contract_state(self.channel.clone(), address, query_data, options).await
     }
 
     /// Query all contract state
@@ -135,22 +339,151 @@ impl CosmWasm {
         Ok(client.all_contract_state(request).await?.into_inner())
     }
 
+    /// Query all the raw storage entries of a contract, following pagination
+    pub async fn _contract_storage(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<StorageDump, DaemonError> {
+        let address = address.into();
+        let mut storage = StorageDump::new();
+        let mut next_key = vec![];
+        loop {
+            let response = self
+                ._all_contract_state(
+                    address.clone(),
+                    Some(PageRequest {
+                        key: next_key,
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+            storage.extend(response.models.into_iter().map(|m| (m.key, m.value)));
+
+            match response.pagination {
+                Some(page) if !page.next_key.is_empty() => next_key = page.next_key,
+                _ => break,
+            }
+        }
+        Ok(storage)
+    }
+
     /// Query code
     pub async fn _code(&self, code_id: u64) -> Result<CodeInfoResponse, DaemonError> {
         use cosmos_modules::cosmwasm::{query_client::*, QueryCodeRequest};
-        let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
-        let request = QueryCodeRequest { code_id };
-        let response = client.code(request).await?.into_inner().code_info.unwrap();
+        cached(
+            self.cache.as_ref(),
+            format!("code_info/{code_id}"),
+            None,
+            || async {
+                let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
+                let request = QueryCodeRequest { code_id };
+                let response = client.code(request).await?.into_inner().code_info.unwrap();
+
+                Ok(cosmrs_to_cosmwasm_code_info(response))
+            },
+        )
+        .await
+    }
+
+    /// Query the metadata of a code, including its instantiate permission
+    pub async fn _code_access(&self, code_id: u64) -> Result<CodeAccess, DaemonError> {
+        use cosmos_modules::cosmwasm::QueryCodeRequest;
+        let response: QueryCodeInfoResponse = raw_grpc_query(
+            self.channel.clone(),
+            "/cosmwasm.wasm.v1.Query/Code",
+            &QueryCodeRequest { code_id },
+        )
+        .await?;
+        let code_info = response
+            .code_info
+            .ok_or(DaemonError::StdErr(format!("Code {code_id} not found")))?;
+        Ok(CodeAccess {
+            code_id: code_info.code_id,
+            creator: Addr::unchecked(code_info.creator),
+            checksum: code_info.data_hash.into(),
+            instantiate_permission: code_info
+                .instantiate_permission
+                .map(InstantiatePermission::from)
+                .unwrap_or(InstantiatePermission::Everybody),
+        })
+    }
+
+    /// Query the metadata of a code, including its instantiate permission
+    pub fn code_access(&self, code_id: u64) -> Result<CodeAccess, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._code_access(code_id))
+    }
+
+    /// Query the whole code history of a contract, oldest first
+    pub fn contract_history_entries(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<Vec<ContractHistoryEntry>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._contract_history_entries(address))
+    }
 
-        Ok(cosmrs_to_cosmwasm_code_info(response))
+    /// Query the addresses of all the contracts instantiated by `creator`
+    pub fn contracts_by_creator(
+        &self,
+        creator: impl Into<String>,
+    ) -> Result<Vec<Addr>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._contracts_by_creator(creator))
+    }
+
+    /// Query the id of the latest code uploaded with the given checksum that `instantiator` can instantiate.
+    /// Codes can't be queried by checksum, so only the latest [`CHECKSUM_LOOKUP_LIMIT`] codes of the chain are looked at
+    pub async fn _code_id_by_checksum(
+        &self,
+        checksum: &HexBinary,
+        instantiator: &Addr,
+    ) -> Result<Option<u64>, DaemonError> {
+        use cosmos_modules::cosmwasm::{query_client::*, QueryCodesRequest};
+        let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
+        // Codes are listed from the latest one
+        let request = QueryCodesRequest {
+            pagination: Some(PageRequest {
+                limit: CHECKSUM_LOOKUP_LIMIT,
+                reverse: true,
+                ..Default::default()
+            }),
+        };
+        let response = client.codes(request).await?.into_inner();
+        for code in response
+            .code_infos
+            .iter()
+            .filter(|code| code.data_hash == checksum.as_slice())
+        {
+            let access = self._code_access(code.code_id).await?;
+            if access.instantiate_permission.allows(instantiator) {
+                return Ok(Some(code.code_id));
+            }
+        }
+        Ok(None)
     }
 
     /// Query code bytes
     pub async fn _code_data(&self, code_id: u64) -> Result<Vec<u8>, DaemonError> {
         use cosmos_modules::cosmwasm::{query_client::*, QueryCodeRequest};
-        let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
-        let request = QueryCodeRequest { code_id };
-        Ok(client.code(request).await?.into_inner().data)
+        let data: Binary = cached(
+            self.cache.as_ref(),
+            format!("code_data/{code_id}"),
+            None,
+            || async {
+                let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
+                let request = QueryCodeRequest { code_id };
+                Ok(client.code(request).await?.into_inner().data.into())
+            },
+        )
+        .await?;
+        Ok(data.into())
     }
 
     /// Query codes
@@ -179,6 +512,52 @@ impl CosmWasm {
         Ok(client.pinned_codes(request).await?.into_inner())
     }
 
+    /// Query the ids of all the pinned codes
+    pub async fn _pinned_code_ids(&self) -> Result<Vec<u64>, DaemonError> {
+        use cosmos_modules::cosmwasm::{query_client::*, QueryPinnedCodesRequest};
+        let mut client: QueryClient<Channel> = QueryClient::new(self.channel.clone());
+        let mut code_ids = vec![];
+        let mut next_key = vec![];
+        loop {
+            let request = QueryPinnedCodesRequest {
+                pagination: Some(PageRequest {
+                    key: next_key,
+                    ..Default::default()
+                }),
+            };
+            let response = client.pinned_codes(request).await?.into_inner();
+            code_ids.extend(response.code_ids);
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        Ok(code_ids)
+    }
+
+    /// Returns whether the code is pinned
+    pub async fn _is_code_pinned(&self, code_id: u64) -> Result<bool, DaemonError> {
+        Ok(self._pinned_code_ids().await?.contains(&code_id))
+    }
+
+    /// Query the ids of all the pinned codes
+    pub fn pinned_code_ids(&self) -> Result<Vec<u64>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._pinned_code_ids())
+    }
+
+    /// Returns whether the code is pinned
+    pub fn is_code_pinned(&self, code_id: u64) -> Result<bool, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._is_code_pinned(code_id))
+    }
+
     /// Query contracts by code
     pub async fn _contract_by_codes(
         &self,
@@ -218,8 +597,30 @@ impl CosmWasm {
     }
 }
 
-impl WasmQuerier for CosmWasm {
-    type Chain = Daemon;
+impl<S: QuerySender> CosmWasmBase<S> {
+    /// Smart query of a contract with a timeout, see [`SmartQueryOptions`]
+    pub fn smart_query_with_options<Q: serde::Serialize, T: serde::de::DeserializeOwned>(
+        &self,
+        address: impl Into<String>,
+        query_data: &Q,
+        options: &SmartQueryOptions,
+    ) -> Result<T, DaemonError> {
+        let response = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._contract_state_with_options(
+                address,
+                to_json_binary(&query_data)?.to_vec(),
+                options,
+            ))?;
+
+        Ok(from_json(response)?)
+    }
+}
+
+impl<S: QuerySender> WasmQuerier for CosmWasmBase<S> {
+    type Chain = DaemonBase<S>;
     fn code_id_hash(&self, code_id: u64) -> Result<HexBinary, Self::Error> {
         self.rt_handle
             .as_ref()
@@ -272,6 +673,17 @@ impl WasmQuerier for CosmWasm {
             .block_on(self._code(code_id))
     }
 
+    fn code_id_by_checksum(
+        &self,
+        checksum: &HexBinary,
+        instantiator: &Addr,
+    ) -> Result<Option<u64>, Self::Error> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._code_id_by_checksum(checksum, instantiator))
+    }
+
     fn instantiate2_addr(
         &self,
         code_id: u64,
@@ -290,12 +702,92 @@ impl WasmQuerier for CosmWasm {
 
     fn local_hash<
         T: cw_orch_core::contract::interface_traits::Uploadable
-            + cw_orch_core::contract::interface_traits::ContractInstance<Daemon>,
+            + cw_orch_core::contract::interface_traits::ContractInstance<DaemonBase<S>>,
     >(
         &self,
         contract: &T,
     ) -> Result<HexBinary, cw_orch_core::CwEnvError> {
-        <T as Uploadable>::wasm(&contract.get_chain().daemon.sender.chain_info).checksum()
+        let chain_info = contract.get_chain().daemon.sender.chain_info();
+        if let Some(code) = <T as Uploadable>::wasm_bytes(chain_info) {
+            let checksum: [u8; 32] = Sha256::digest(code).into();
+            return Ok(checksum.into());
+        }
+        <T as Uploadable>::wasm(chain_info).checksum()
+    }
+}
+
+/// Options of a smart query.
+///
+/// The gas available to smart queries is not set by the client but by the node operator,
+/// with `query_gas_limit` in the `[wasm]` section of app.toml.
+/// Queries exceeding it fail with [`DaemonError::QueryOutOfGas`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SmartQueryOptions {
+    /// Maximum duration of the query, sent to the node as the gRPC deadline and enforced locally.
+    /// Queries exceeding it fail with [`DaemonError::QueryTimeout`]
+    pub timeout: Option<Duration>,
+}
+
+impl SmartQueryOptions {
+    /// Sets the maximum duration of the query
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// Smart query of `address`, with the node-side failures mapped to dedicated errors
+pub(crate) async fn smart_contract_state(
+    channel: Channel,
+    address: String,
+    query_data: Vec<u8>,
+    options: &SmartQueryOptions,
+) -> Result<Vec<u8>, DaemonError> {
+    use cosmos_modules::cosmwasm::{query_client::*, QuerySmartContractStateRequest};
+    let mut client: QueryClient<Channel> = QueryClient::new(channel);
+    let mut request = tonic::Request::new(QuerySmartContractStateRequest {
+        address: address.clone(),
+        query_data,
+    });
+    #[cfg(feature = "metrics")]
+    let start = std::time::Instant::now();
+    let response = match options.timeout {
+        Some(timeout) => {
+            request.set_timeout(timeout);
+            tokio::time::timeout(timeout, client.smart_contract_state(request))
+                .await
+                .unwrap_or_else(|_| Err(tonic::Status::deadline_exceeded("query timed out")))
+        }
+        None => client.smart_contract_state(request).await,
+    };
+    #[cfg(feature = "metrics")]
+    crate::metrics::record_query(
+        "/cosmwasm.wasm.v1.Query/SmartContractState",
+        start,
+        &response,
+    );
+    response
+        .map(|resp| resp.into_inner().data)
+        .map_err(|status| smart_query_error(status, address, options.timeout))
+}
+
+/// Maps the out of gas and deadline errors of a smart query to [`DaemonError::QueryOutOfGas`] and [`DaemonError::QueryTimeout`]
+fn smart_query_error(
+    status: tonic::Status,
+    address: String,
+    timeout: Option<Duration>,
+) -> DaemonError {
+    if status.message().contains("out of gas") {
+        return DaemonError::QueryOutOfGas {
+            address,
+            message: status.message().to_string(),
+        };
+    }
+    match (status.code(), timeout) {
+        (tonic::Code::DeadlineExceeded, Some(timeout)) => {
+            DaemonError::QueryTimeout { address, timeout }
+        }
+        _ => status.into(),
     }
 }
 
@@ -308,3 +800,82 @@ pub fn cosmrs_to_cosmwasm_code_info(
     c.checksum = code_info.data_hash.into();
     c
 }
+
+impl<S: QuerySender> StorageInspector for DaemonBase<S> {
+    type Error = DaemonError;
+
+    fn dump_storage(&self, contract: &Addr) -> Result<StorageDump, Self::Error> {
+        self.rt_handle
+            .block_on(CosmWasm::new_async(self.channel())._contract_storage(contract))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn access_and_history_conversions() {
+        let only_address = AccessConfig {
+            permission: 2,
+            address: "juno1admin".to_string(),
+            addresses: vec![],
+        };
+        assert_eq!(
+            InstantiatePermission::from(only_address),
+            InstantiatePermission::AnyOfAddresses(vec![Addr::unchecked("juno1admin")])
+        );
+        let nobody = AccessConfig {
+            permission: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            InstantiatePermission::from(nobody),
+            InstantiatePermission::Nobody
+        );
+        assert!(
+            InstantiatePermission::AnyOfAddresses(vec![Addr::unchecked("juno1admin")])
+                .allows(&Addr::unchecked("juno1admin"))
+        );
+        assert!(!InstantiatePermission::Nobody.allows(&Addr::unchecked("juno1admin")));
+
+        let entry =
+            ContractHistoryEntry::from(cosmos_modules::cosmwasm::ContractCodeHistoryEntry {
+                operation: 2,
+                code_id: 7,
+                updated: Some(cosmos_modules::cosmwasm::AbsoluteTxPosition {
+                    block_height: 100,
+                    tx_index: 1,
+                }),
+                msg: b"{}".to_vec(),
+            });
+        assert_eq!(entry.operation, ContractOperation::Migrate);
+        assert_eq!(entry.block_height, Some(100));
+    }
+
+    #[test]
+    fn smart_query_errors() {
+        let out_of_gas = tonic::Status::unknown(
+            "out of gas in location: wasm contract; gasWanted: 3000000, gasUsed: 3000123: out of gas",
+        );
+        assert!(matches!(
+            smart_query_error(out_of_gas, "juno1contract".to_string(), None),
+            DaemonError::QueryOutOfGas { .. }
+        ));
+
+        let deadline = tonic::Status::deadline_exceeded("context deadline exceeded");
+        assert!(matches!(
+            smart_query_error(
+                deadline.clone(),
+                "juno1contract".to_string(),
+                Some(Duration::from_secs(5))
+            ),
+            DaemonError::QueryTimeout { timeout, .. } if timeout == Duration::from_secs(5)
+        ));
+        // Without a timeout set by the caller, the deadline comes from elsewhere
+        assert!(matches!(
+            smart_query_error(deadline, "juno1contract".to_string(), None),
+            DaemonError::Status(_)
+        ));
+    }
+}
@@ -1,18 +1,13 @@
 use cw_orch_core::environment::{EnvironmentInfo, EnvironmentQuerier};
 
-use crate::Daemon;
+use crate::{sender::QuerySender, DaemonBase};
 
-impl EnvironmentQuerier for Daemon {
+impl<S: QuerySender> EnvironmentQuerier for DaemonBase<S> {
     fn env_info(&self) -> EnvironmentInfo {
+        let chain_info = self.daemon.sender.chain_info();
         EnvironmentInfo {
-            chain_id: self.daemon.sender.chain_info.chain_id.clone(),
-            chain_name: self
-                .daemon
-                .sender
-                .chain_info
-                .network_info
-                .chain_name
-                .clone(),
+            chain_id: chain_info.chain_id.clone(),
+            chain_name: chain_info.network_info.chain_name.clone(),
             deployment_id: self.daemon.state.deployment_id.clone(),
         }
     }
@@ -1,4 +1,4 @@
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{cosmos_modules, error::DaemonError, sender::QuerySender, DaemonBase};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 use cw_orch_core::environment::{Querier, QuerierGetter};
 use tokio::runtime::Handle;
@@ -12,7 +12,7 @@ pub struct FeeGrant {
 }
 
 impl FeeGrant {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
@@ -31,7 +31,7 @@ impl Querier for FeeGrant {
     type Error = DaemonError;
 }
 
-impl QuerierGetter<FeeGrant> for Daemon {
+impl<S: QuerySender> QuerierGetter<FeeGrant> for DaemonBase<S> {
     fn querier(&self) -> FeeGrant {
         FeeGrant::new(self)
     }
@@ -75,4 +75,24 @@ impl FeeGrant {
         );
         Ok(allowances.allowances)
     }
+
+    /// Query allowances granted by the granter address with a given pagination
+    ///
+    /// see [PageRequest] for pagination
+    pub async fn _allowances_by_granter(
+        &self,
+        granter: impl Into<String>,
+        pagination: Option<PageRequest>,
+    ) -> Result<Vec<cosmos_modules::feegrant::Grant>, DaemonError> {
+        let allowances: cosmos_modules::feegrant::QueryAllowancesByGranterResponse = cosmos_query!(
+            self,
+            feegrant,
+            allowances_by_granter,
+            QueryAllowancesByGranterRequest {
+                granter: granter.into(),
+                pagination: pagination
+            }
+        );
+        Ok(allowances.allowances)
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{cosmos_modules, error::DaemonError, sender::QuerySender, DaemonBase};
 use cosmos_modules::ibc_channel;
 use cosmrs::proto::ibc::{
     applications::transfer::v1::{DenomTrace, QueryDenomHashResponse, QueryDenomTraceResponse},
@@ -7,7 +7,7 @@ use cosmrs::proto::ibc::{
         client::v1::{IdentifiedClientState, QueryClientStatesResponse},
         connection::v1::{ConnectionEnd, IdentifiedConnection, State},
     },
-    lightclients::tendermint::v1::ClientState,
+    lightclients::tendermint::v1::{ClientState, ConsensusState},
 };
 use cw_orch_core::environment::{Querier, QuerierGetter};
 use prost::Message;
@@ -22,7 +22,7 @@ pub struct Ibc {
 }
 
 impl Ibc {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
@@ -41,7 +41,7 @@ impl Querier for Ibc {
     type Error = DaemonError;
 }
 
-impl QuerierGetter<Ibc> for Daemon {
+impl<S: QuerySender> QuerierGetter<Ibc> for DaemonBase<S> {
     fn querier(&self) -> Ibc {
         Ibc::new(self)
     }
@@ -120,6 +120,65 @@ impl Ibc {
         Ok(response)
     }
 
+    /// Get the consensus state of a specific IBC client at a given height, with its proof
+    /// If `height` is `None`, the latest consensus state is returned
+    /// `height` is provided as `(revision_number, revision_height)`
+    pub async fn _consensus_state(
+        &self,
+        client_id: impl ToString,
+        height: Option<(u64, u64)>,
+    ) -> Result<cosmos_modules::ibc_client::QueryConsensusStateResponse, DaemonError> {
+        let (revision_number, revision_height) = height.unwrap_or_default();
+        let response: cosmos_modules::ibc_client::QueryConsensusStateResponse = cosmos_query!(
+            self,
+            ibc_client,
+            consensus_state,
+            QueryConsensusStateRequest {
+                client_id: client_id.to_string(),
+                revision_number: revision_number,
+                revision_height: revision_height,
+                latest_height: height.is_none(),
+            }
+        );
+        Ok(response)
+    }
+
+    /// Get the decoded (tendermint) client state of a specific IBC client
+    pub async fn _tendermint_client_state(
+        &self,
+        client_id: impl ToString,
+    ) -> Result<ClientState, DaemonError> {
+        let client_id = client_id.to_string();
+        let response = self._client_state(&client_id).await?;
+        let client_state = response.client_state.ok_or(DaemonError::ibc_err(format!(
+            "client state not found for client {}",
+            client_id
+        )))?;
+
+        ClientState::decode(client_state.value.as_slice())
+            .map_err(|e| DaemonError::ibc_err(format!("error decoding client state: {}", e)))
+    }
+
+    /// Get the decoded (tendermint) consensus state of a specific IBC client at a given height
+    /// If `height` is `None`, the latest consensus state is returned
+    pub async fn _tendermint_consensus_state(
+        &self,
+        client_id: impl ToString,
+        height: Option<(u64, u64)>,
+    ) -> Result<ConsensusState, DaemonError> {
+        let client_id = client_id.to_string();
+        let response = self._consensus_state(&client_id, height).await?;
+        let consensus_state = response
+            .consensus_state
+            .ok_or(DaemonError::ibc_err(format!(
+                "consensus state not found for client {}",
+                client_id
+            )))?;
+
+        ConsensusState::decode(consensus_state.value.as_slice())
+            .map_err(|e| DaemonError::ibc_err(format!("error decoding consensus state: {}", e)))
+    }
+
     /// Get the consensus status of a specific IBC client
     pub async fn _client_status(
         &self,
@@ -206,6 +265,26 @@ impl Ibc {
         Ok(ibc_client_connections.connection)
     }
 
+    /// Get the information about a specific connection, along with its proof and proof height
+    pub async fn _connection_with_proof(
+        &self,
+        connection_id: impl Into<String>,
+    ) -> Result<cosmos_modules::ibc_connection::QueryConnectionResponse, DaemonError> {
+        use cosmos_modules::ibc_connection::QueryConnectionResponse;
+
+        let connection_id = connection_id.into();
+        let response: QueryConnectionResponse = cosmos_query!(
+            self,
+            ibc_connection,
+            connection,
+            QueryConnectionRequest {
+                connection_id: connection_id
+            }
+        );
+
+        Ok(response)
+    }
+
     /// Get all the connections for this client
     pub async fn _client_connections(
         &self,
@@ -285,6 +364,63 @@ impl Ibc {
         )))
     }
 
+    /// Get the channel for a specific port and channel id, along with its proof and proof height
+    pub async fn _channel_with_proof(
+        &self,
+        port_id: impl Into<String>,
+        channel_id: impl Into<String>,
+    ) -> Result<ibc_channel::QueryChannelResponse, DaemonError> {
+        let response: ibc_channel::QueryChannelResponse = cosmos_query!(
+            self,
+            ibc_channel,
+            channel,
+            QueryChannelRequest {
+                port_id: port_id.into(),
+                channel_id: channel_id.into(),
+            }
+        );
+
+        Ok(response)
+    }
+
+    /// Audits the health of a channel by following it down to its light client
+    /// Returns the state of the channel, its connection and the status of the underlying client
+    pub async fn _channel_health(
+        &self,
+        port_id: impl Into<String>,
+        channel_id: impl Into<String>,
+    ) -> Result<IbcChannelHealth, DaemonError> {
+        let port_id = port_id.into();
+        let channel_id = channel_id.into();
+
+        let channel = self._channel(&port_id, &channel_id).await?;
+        let connection_id =
+            channel
+                .connection_hops
+                .first()
+                .cloned()
+                .ok_or(DaemonError::ibc_err(format!(
+                    "no connection found for channel {} on port {}",
+                    channel_id, port_id
+                )))?;
+        let connection =
+            self._connection_end(&connection_id)
+                .await?
+                .ok_or(DaemonError::ibc_err(format!(
+                    "connection {} not found",
+                    connection_id
+                )))?;
+        let client_status = self._client_status(&connection.client_id).await?.status;
+
+        Ok(IbcChannelHealth {
+            channel_state: channel.state(),
+            connection_id,
+            connection_state: connection.state(),
+            client_id: connection.client_id,
+            client_status,
+        })
+    }
+
     /// Get all the channels for a specific connection
     pub async fn _connection_channels(
         &self,
@@ -535,3 +671,27 @@ impl Ibc {
         Ok(next_receive.next_sequence_receive)
     }
 }
+
+/// Health report of an IBC channel, returned by [`Ibc::_channel_health`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct IbcChannelHealth {
+    /// State of the channel
+    pub channel_state: ibc_channel::State,
+    /// Connection the channel is built upon
+    pub connection_id: String,
+    /// State of the connection
+    pub connection_state: State,
+    /// Light client the connection is built upon
+    pub client_id: String,
+    /// Status of the light client ("Active", "Frozen", "Expired", ...)
+    pub client_status: String,
+}
+
+impl IbcChannelHealth {
+    /// Returns true if the channel, its connection and its client are all usable
+    pub fn is_healthy(&self) -> bool {
+        self.channel_state == ibc_channel::State::Open
+            && self.connection_state == State::Open
+            && self.client_status == "Active"
+    }
+}
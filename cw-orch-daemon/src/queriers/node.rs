@@ -1,48 +1,74 @@
-use std::{cmp::min, time::Duration};
+use std::{cmp::min, str::FromStr, time::Duration};
 
 use crate::{
-    cosmos_modules, env::DaemonEnvVars, error::DaemonError, tx_resp::CosmTxResponse, Daemon,
+    cache::{cached, ChainQueryCache},
+    cosmos_modules,
+    env::DaemonEnvVars,
+    error::DaemonError,
+    sender::QuerySender,
+    tx_resp::CosmTxResponse,
+    DaemonBase,
 };
 
+use super::cosmrs_to_cosmwasm_coins;
+
+use base64::{engine::general_purpose, Engine};
 use cosmrs::{
     proto::cosmos::{
         base::query::v1beta1::PageRequest,
         tx::v1beta1::{OrderBy, SimulateResponse},
     },
+    proto::{cosmwasm::wasm::v1::MsgExecuteContract, traits::Message},
     tendermint::{Block, Time},
+    tx::{SignerPublicKey, Tx},
+    AccountId,
 };
-use cosmwasm_std::BlockInfo;
+use cosmwasm_std::{Addr, BlockInfo, Timestamp};
 use cw_orch_core::{
-    environment::{NodeQuerier, Querier, QuerierGetter},
+    environment::{ContractExecution, NodeQuerier, Querier, QuerierGetter},
     log::query_target,
 };
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use tokio::runtime::Handle;
 use tonic::transport::Channel;
 
+/// Number of transactions fetched per page when searching the executions of a contract
+const EXECUTIONS_PAGE_LIMIT: u64 = 100;
+
 /// Querier for the Tendermint node.
 /// Supports queries for block and tx information
 /// All the async function are prefixed with `_`
 pub struct Node {
     pub channel: Channel,
     pub rt_handle: Option<Handle>,
+    cache: Option<ChainQueryCache>,
 }
 
 impl Node {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
+            cache: daemon.daemon.chain_query_cache(),
         }
     }
     pub fn new_async(channel: Channel) -> Self {
         Self {
             channel,
             rt_handle: None,
+            cache: None,
         }
     }
+
+    /// Caches the blocks fetched by height in `cache`, see [`crate::cache`]
+    pub fn with_cache(mut self, cache: ChainQueryCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 }
 
-impl QuerierGetter<Node> for Daemon {
+impl<S: QuerySender> QuerierGetter<Node> for DaemonBase<S> {
     fn querier(&self) -> Node {
         Node::new(self)
     }
@@ -96,17 +122,21 @@ impl Node {
 
     /// Returns block information fetched by height
     pub async fn _block_by_height(&self, height: u64) -> Result<Block, DaemonError> {
-        let mut client =
-            cosmos_modules::tendermint::service_client::ServiceClient::new(self.channel.clone());
-
-        let resp = client
-            .get_block_by_height(cosmos_modules::tendermint::GetBlockByHeightRequest {
-                height: height as i64,
-            })
-            .await?
-            .into_inner();
-
-        Ok(Block::try_from(resp.block.unwrap())?)
+        cached(self.cache.as_ref(), "block", Some(height), || async {
+            let mut client = cosmos_modules::tendermint::service_client::ServiceClient::new(
+                self.channel.clone(),
+            );
+
+            let resp = client
+                .get_block_by_height(cosmos_modules::tendermint::GetBlockByHeightRequest {
+                    height: height as i64,
+                })
+                .await?
+                .into_inner();
+
+            Ok(Block::try_from(resp.block.unwrap())?)
+        })
+        .await
     }
 
     /// Return the average block time for the last 50 blocks or since inception
@@ -149,6 +179,42 @@ impl Node {
         Ok(average_block_time)
     }
 
+    /// Waits until the latest block of the chain reaches `height`.
+    /// Sleeps for the time estimated from the average block speed between checks
+    pub async fn _wait_for_height(&self, height: u64) -> Result<(), DaemonError> {
+        let block_speed = self
+            ._average_block_speed(Some(0.9))
+            .await?
+            .max(DaemonEnvVars::min_block_speed());
+        loop {
+            let current_height = self._block_height().await?;
+            if current_height >= height {
+                return Ok(());
+            }
+            let wait = block_speed.mul_f64((height - current_height) as f64);
+            log::debug!(target: &query_target(), "Waiting {:?} for block {height}, current block is {current_height}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Waits until the time of the latest block of the chain reaches `time`.
+    /// The block time is used instead of the local clock, so the next transaction is executed after `time`
+    pub async fn _wait_for_time(&self, time: Timestamp) -> Result<(), DaemonError> {
+        let block_speed = self
+            ._average_block_speed(Some(0.9))
+            .await?
+            .max(DaemonEnvVars::min_block_speed());
+        loop {
+            let block_time = self._block_info().await?.time;
+            if block_time >= time {
+                return Ok(());
+            }
+            let wait = Duration::from_nanos(time.nanos() - block_time.nanos()).max(block_speed);
+            log::debug!(target: &query_target(), "Waiting {:?} for block time {time}, current block time is {block_time}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
     /// Returns latests validator set
     pub async fn _latest_validator_set(
         &self,
@@ -222,6 +288,16 @@ impl Node {
         block_to_block_info(block)
     }
 
+    /// Returns the block info at the given height
+    pub async fn _block_info_by_height(
+        &self,
+        height: u64,
+    ) -> Result<cosmwasm_std::BlockInfo, DaemonError> {
+        let block = self._block_by_height(height).await?;
+
+        block_to_block_info(block)
+    }
+
     /// Find TX by hash
     pub async fn _find_tx(&self, hash: String) -> Result<CosmTxResponse, DaemonError> {
         self._find_tx_with_retries(hash, DaemonEnvVars::max_tx_query_retries())
@@ -353,6 +429,229 @@ impl Node {
             DaemonEnvVars::max_tx_query_retries(),
         ))
     }
+
+    /// Returns the executions of `contract` sent in successful transactions between `from_height` and `to_height` (included), oldest first.
+    /// Executions wrapped in other messages (e.g. authz) and executions triggered by other contracts are not returned.
+    /// The executions can be replayed on a mock environment to approximate the state of the contract
+    pub async fn _contract_executions(
+        &self,
+        contract: &Addr,
+        from_height: u64,
+        to_height: Option<u64>,
+    ) -> Result<Vec<ContractExecution>, DaemonError> {
+        let mut client = crate::cosmos_proto_patches::v0_50::tx::service_client::ServiceClient::new(
+            self.channel.clone(),
+        );
+        let mut events = vec![
+            format!("execute._contract_address='{contract}'"),
+            format!("tx.height>={from_height}"),
+        ];
+        if let Some(to_height) = to_height {
+            events.push(format!("tx.height<={to_height}"));
+        }
+
+        let mut executions = vec![];
+        // Pages start at 1
+        let mut page = 1;
+        loop {
+            #[allow(deprecated)]
+            let request = crate::cosmos_proto_patches::v0_50::tx::GetTxsEventRequest {
+                events: events.clone(),
+                pagination: None,
+                order_by: OrderBy::Asc.into(),
+                page,
+                limit: EXECUTIONS_PAGE_LIMIT,
+                query: events.join(" AND "),
+            };
+            let response = client.get_txs_event(request).await?.into_inner();
+            let found = response.txs.len() as u64;
+
+            for (tx, tx_response) in response.txs.into_iter().zip(response.tx_responses) {
+                if tx_response.code != 0 {
+                    continue;
+                }
+                for msg in tx.body.map(|body| body.messages).unwrap_or_default() {
+                    if msg.type_url != "/cosmwasm.wasm.v1.MsgExecuteContract" {
+                        continue;
+                    }
+                    let execute = MsgExecuteContract::decode(msg.value.as_slice())?;
+                    if execute.contract != contract.as_str() {
+                        continue;
+                    }
+                    executions.push(ContractExecution {
+                        height: tx_response.height as u64,
+                        txhash: tx_response.txhash.clone(),
+                        sender: Addr::unchecked(execute.sender),
+                        contract: contract.clone(),
+                        msg: execute.msg.into(),
+                        funds: cosmrs_to_cosmwasm_coins(execute.funds)?,
+                    });
+                }
+            }
+
+            if found < EXECUTIONS_PAGE_LIMIT {
+                break;
+            }
+            page += 1;
+        }
+        Ok(executions)
+    }
+
+    // ### Mempool queries (RPC) ### //
+
+    /// Returns the transactions waiting in the mempool of the node exposing the tendermint RPC at `rpc_url`
+    /// The mempool isn't exposed through gRPC, so this query needs the RPC endpoint of the node.
+    /// Nodes return at most 100 transactions per query, whatever the `limit`
+    pub async fn _unconfirmed_txs(
+        &self,
+        rpc_url: &str,
+        limit: Option<u64>,
+    ) -> Result<UnconfirmedTxs, DaemonError> {
+        mempool_query(format!(
+            "{}/unconfirmed_txs?limit={}",
+            rpc_url.trim_end_matches('/'),
+            limit.unwrap_or(100)
+        ))
+        .await
+    }
+
+    /// Returns the number and size of the transactions waiting in the mempool, without the transactions
+    pub async fn _num_unconfirmed_txs(&self, rpc_url: &str) -> Result<UnconfirmedTxs, DaemonError> {
+        mempool_query(format!(
+            "{}/num_unconfirmed_txs",
+            rpc_url.trim_end_matches('/')
+        ))
+        .await
+    }
+
+    /// Returns the transactions signed by `address` that are waiting in the mempool
+    pub async fn _pending_txs(
+        &self,
+        rpc_url: &str,
+        address: &str,
+    ) -> Result<Vec<PendingTx>, DaemonError> {
+        let account = AccountId::from_str(address)?;
+        let mempool_size = self._num_unconfirmed_txs(rpc_url).await?.total;
+        let unconfirmed = self
+            ._unconfirmed_txs(rpc_url, Some(mempool_size.max(1)))
+            .await?;
+        if unconfirmed.n_txs < unconfirmed.total {
+            log::warn!(
+                target: &query_target(),
+                "The node only returned {} of the {} transactions of its mempool, pending transactions of {} may be missed",
+                unconfirmed.n_txs,
+                unconfirmed.total,
+                address
+            );
+        }
+
+        let mut pending = vec![];
+        for raw in unconfirmed.txs {
+            // Transactions that can't be decoded can't be ours
+            let Ok(tx) = Tx::from_bytes(&raw) else {
+                continue;
+            };
+            for signer in tx.auth_info.signer_infos {
+                let Some(SignerPublicKey::Single(public_key)) = signer.public_key else {
+                    continue;
+                };
+                if public_key.account_id(account.prefix()).ok().as_ref() == Some(&account) {
+                    let hash: [u8; 32] = Sha256::digest(&raw).into();
+                    pending.push(PendingTx {
+                        hash: hex::encode_upper(hash),
+                        sequence: signer.sequence,
+                    });
+                }
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Waits until `address` doesn't have pending transactions in the mempool anymore.
+    /// Broadcasting while previous transactions are still pending can lead to account sequence mismatch errors.
+    /// Errors if transactions are still pending after `timeout`
+    pub async fn _wait_for_mempool_drain(
+        &self,
+        rpc_url: &str,
+        address: &str,
+        timeout: Duration,
+    ) -> Result<(), DaemonError> {
+        let start = std::time::Instant::now();
+        loop {
+            let pending = self._pending_txs(rpc_url, address).await?;
+            if pending.is_empty() {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                return Err(DaemonError::StdErr(format!(
+                    "{} transactions of {} are still pending after {:?}",
+                    pending.len(),
+                    address,
+                    timeout
+                )));
+            }
+            log::debug!(target: &query_target(), "Waiting for {} pending transactions of {}", pending.len(), address);
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
+
+/// Transactions waiting in the mempool of a node
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnconfirmedTxs {
+    /// Number of returned transactions
+    pub n_txs: u64,
+    /// Total number of transactions in the mempool
+    pub total: u64,
+    /// Total size of the transactions in the mempool
+    pub total_bytes: u64,
+    /// Raw returned transactions
+    pub txs: Vec<Vec<u8>>,
+}
+
+/// Transaction of an account waiting in the mempool
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTx {
+    /// Hash of the transaction
+    pub hash: String,
+    /// Account sequence used to sign the transaction
+    pub sequence: u64,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: T,
+}
+
+#[derive(Deserialize)]
+struct RpcUnconfirmedTxs {
+    n_txs: String,
+    total: String,
+    total_bytes: String,
+    txs: Option<Vec<String>>,
+}
+
+async fn mempool_query(url: String) -> Result<UnconfirmedTxs, DaemonError> {
+    let body = reqwest::get(url).await?.error_for_status()?.text().await?;
+    parse_unconfirmed_txs(&body)
+}
+
+fn parse_unconfirmed_txs(body: &str) -> Result<UnconfirmedTxs, DaemonError> {
+    let response: RpcResponse<RpcUnconfirmedTxs> = serde_json::from_str(body)?;
+    let result = response.result;
+    let txs = result
+        .txs
+        .unwrap_or_default()
+        .iter()
+        .map(|tx| general_purpose::STANDARD.decode(tx))
+        .collect::<Result<_, _>>()?;
+
+    Ok(UnconfirmedTxs {
+        n_txs: result.n_txs.parse()?,
+        total: result.total.parse()?,
+        total_bytes: result.total_bytes.parse()?,
+        txs,
+    })
 }
 
 // Now we define traits
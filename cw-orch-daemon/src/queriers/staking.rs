@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{cosmos_modules, error::DaemonError, sender::QuerySender, DaemonBase};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 use cosmwasm_std::{Addr, StdError};
 use cw_orch_core::environment::{Querier, QuerierGetter};
@@ -17,7 +17,7 @@ pub struct Staking {
 }
 
 impl Staking {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
@@ -36,7 +36,7 @@ impl Querier for Staking {
     type Error = DaemonError;
 }
 
-impl QuerierGetter<Staking> for Daemon {
+impl<S: QuerySender> QuerierGetter<Staking> for DaemonBase<S> {
     fn querier(&self) -> Staking {
         Staking::new(self)
     }
@@ -0,0 +1,141 @@
+use crate::{error::DaemonError, sender::QuerySender, DaemonBase};
+use cosmrs::{
+    rpc::{
+        endpoint::{abci_query::AbciQuery, consensus_params, net_info},
+        Client, HttpClient,
+    },
+    tendermint::block::Height,
+};
+use cw_orch_core::environment::{ChainInfoOwned, Querier};
+use tokio::runtime::Handle;
+
+/// Querier for the tendermint RPC of a node.
+/// Covers the queries that the gRPC endpoints don't expose: consensus parameters, peers and abci queries with proofs.
+/// It's available when the `rpc_url` of the [`ChainInfoOwned`] is set.
+/// All the async function are prefixed with `_`
+pub struct TendermintRpc {
+    pub client: HttpClient,
+    pub rt_handle: Option<Handle>,
+}
+
+impl TendermintRpc {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Result<Self, DaemonError> {
+        let mut querier = Self::new_async(daemon.daemon.sender.chain_info())?;
+        querier.rt_handle = Some(daemon.rt_handle.clone());
+        Ok(querier)
+    }
+
+    pub fn new_async(chain_info: &ChainInfoOwned) -> Result<Self, DaemonError> {
+        let rpc_url = chain_info
+            .rpc_url
+            .as_ref()
+            .ok_or_else(|| DaemonError::MissingRpcUrl(chain_info.chain_id.clone()))?;
+        Ok(Self {
+            client: HttpClient::new(rpc_url.as_str())?,
+            rt_handle: None,
+        })
+    }
+}
+
+impl Querier for TendermintRpc {
+    type Error = DaemonError;
+}
+
+impl TendermintRpc {
+    /// Query the consensus parameters at `height`, or at the latest height if `None`
+    pub async fn _consensus_params(
+        &self,
+        height: Option<u64>,
+    ) -> Result<consensus_params::Response, DaemonError> {
+        let response = match height {
+            Some(height) => {
+                self.client
+                    .consensus_params(Height::try_from(height)?)
+                    .await?
+            }
+            None => self.client.latest_consensus_params().await?,
+        };
+        Ok(response)
+    }
+
+    /// Query the network information of the node (listeners and peers)
+    pub async fn _net_info(&self) -> Result<net_info::Response, DaemonError> {
+        Ok(self.client.net_info().await?)
+    }
+
+    /// Query the store of the application at `path` (e.g. `/store/wasm/key`).
+    /// Set `prove` to get the merkle proof of the value, at the latest height if `height` is `None`
+    pub async fn _abci_query(
+        &self,
+        path: impl Into<String>,
+        data: Vec<u8>,
+        height: Option<u64>,
+        prove: bool,
+    ) -> Result<AbciQuery, DaemonError> {
+        let height = height.map(Height::try_from).transpose()?;
+        Ok(self
+            .client
+            .abci_query(Some(path.into()), data, height, prove)
+            .await?)
+    }
+
+    /// Query the consensus parameters at `height`, or at the latest height if `None`
+    pub fn consensus_params(
+        &self,
+        height: Option<u64>,
+    ) -> Result<consensus_params::Response, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._consensus_params(height))
+    }
+
+    /// Query the network information of the node (listeners and peers)
+    pub fn net_info(&self) -> Result<net_info::Response, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._net_info())
+    }
+
+    /// Query the store of the application at `path` (e.g. `/store/wasm/key`).
+    /// Set `prove` to get the merkle proof of the value, at the latest height if `height` is `None`
+    pub fn abci_query(
+        &self,
+        path: impl Into<String>,
+        data: Vec<u8>,
+        height: Option<u64>,
+        prove: bool,
+    ) -> Result<AbciQuery, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._abci_query(path, data, height, prove))
+    }
+}
+
+impl<S: QuerySender> DaemonBase<S> {
+    /// Querier for the tendermint RPC of the node, errors if the chain has no `rpc_url`
+    pub fn rpc_querier(&self) -> Result<TendermintRpc, DaemonError> {
+        TendermintRpc::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cw_orch_networks::networks::LOCAL_JUNO;
+
+    use super::*;
+
+    #[test]
+    fn requires_rpc_url() {
+        let mut chain: ChainInfoOwned = LOCAL_JUNO.into();
+        assert!(matches!(
+            TendermintRpc::new_async(&chain),
+            Err(DaemonError::MissingRpcUrl(_))
+        ));
+
+        chain.rpc_url = Some("http://localhost:26657".to_string());
+        assert!(TendermintRpc::new_async(&chain).is_ok());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{
+    error::DaemonError,
+    proto::tokenfactory::{
+        QueryDenomAuthorityMetadataRequest, QueryDenomAuthorityMetadataResponse,
+        QueryDenomsFromCreatorRequest, QueryDenomsFromCreatorResponse,
+    },
+    sender::QuerySender,
+    DaemonBase,
+};
+use cw_orch_core::environment::{Querier, QuerierGetter};
+use tokio::runtime::Handle;
+use tonic::transport::Channel;
+
+use super::raw_grpc_query;
+
+/// gRPC service of the tokenfactory module used by most chains
+pub const OSMOSIS_TOKENFACTORY_SERVICE: &str = "osmosis.tokenfactory.v1beta1.Query";
+
+/// Querier for the TokenFactory module
+/// All the async function are prefixed with `_`
+///
+/// Use this querier to discover the denoms created by an address (e.g. a contract) instead of formatting `factory/{creator}/{subdenom}` manually.
+pub struct TokenFactory {
+    pub channel: Channel,
+    pub rt_handle: Option<Handle>,
+    /// gRPC service of the module, defaults to [`OSMOSIS_TOKENFACTORY_SERVICE`]
+    pub service: String,
+}
+
+impl TokenFactory {
+    pub fn new<S: QuerySender>(daemon: &DaemonBase<S>) -> Self {
+        Self {
+            channel: daemon.channel(),
+            rt_handle: Some(daemon.rt_handle.clone()),
+            service: OSMOSIS_TOKENFACTORY_SERVICE.to_string(),
+        }
+    }
+
+    pub fn new_async(channel: Channel) -> Self {
+        Self {
+            channel,
+            rt_handle: None,
+            service: OSMOSIS_TOKENFACTORY_SERVICE.to_string(),
+        }
+    }
+
+    /// Use a different gRPC service, for chains that renamed the tokenfactory module package
+    /// (e.g. `injective.tokenfactory.v1beta1.Query`)
+    pub fn with_service(mut self, service: impl Into<String>) -> Self {
+        self.service = service.into();
+        self
+    }
+
+    fn path(&self, method: &str) -> String {
+        format!("/{}/{}", self.service, method)
+    }
+}
+
+impl Querier for TokenFactory {
+    type Error = DaemonError;
+}
+
+impl<S: QuerySender> QuerierGetter<TokenFactory> for DaemonBase<S> {
+    fn querier(&self) -> TokenFactory {
+        TokenFactory::new(self)
+    }
+}
+
+impl TokenFactory {
+    /// Query all the denoms created by an address
+    pub async fn _denoms_from_creator(
+        &self,
+        creator: impl Into<String>,
+    ) -> Result<Vec<String>, DaemonError> {
+        let response: QueryDenomsFromCreatorResponse = raw_grpc_query(
+            self.channel.clone(),
+            &self.path("DenomsFromCreator"),
+            &QueryDenomsFromCreatorRequest {
+                creator: creator.into(),
+            },
+        )
+        .await?;
+        Ok(response.denoms)
+    }
+
+    /// Query the admin of a denom
+    /// Returns `None` if the denom has no admin
+    pub async fn _denom_admin(
+        &self,
+        denom: impl Into<String>,
+    ) -> Result<Option<String>, DaemonError> {
+        let response: QueryDenomAuthorityMetadataResponse = raw_grpc_query(
+            self.channel.clone(),
+            &self.path("DenomAuthorityMetadata"),
+            &QueryDenomAuthorityMetadataRequest {
+                denom: denom.into(),
+            },
+        )
+        .await?;
+        Ok(response
+            .authority_metadata
+            .map(|metadata| metadata.admin)
+            .filter(|admin| !admin.is_empty()))
+    }
+
+    /// Query the full denom of a subdenom created by `creator`
+    /// Errors if the creator didn't create this subdenom
+    pub async fn _denom(
+        &self,
+        creator: impl Into<String>,
+        subdenom: &str,
+    ) -> Result<String, DaemonError> {
+        let creator = creator.into();
+        let denoms = self._denoms_from_creator(creator.clone()).await?;
+        denoms
+            .into_iter()
+            .find(|denom| denom.rsplit_once('/').map(|(_, sub)| sub) == Some(subdenom))
+            .ok_or(DaemonError::StdErr(format!(
+                "Denom {subdenom} was not created by {creator}"
+            )))
+    }
+}
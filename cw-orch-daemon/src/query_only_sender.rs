@@ -0,0 +1,72 @@
+//! Key-less sender, used to query a chain on behalf of an arbitrary address.
+//!
+//! ```no_run
+//! use cw_orch_daemon::{networks, DaemonBuilder};
+//!
+//! // No mnemonic needed
+//! let daemon = DaemonBuilder::default()
+//!     .chain(networks::JUNO_1)
+//!     .build_query_only("juno1...")
+//!     .unwrap();
+//! ```
+
+use cosmrs::AccountId;
+use cosmwasm_std::Addr;
+use cw_orch_core::environment::ChainInfoOwned;
+use tonic::transport::Channel;
+
+use crate::{sender::QuerySender, DaemonError};
+
+/// Watch-only sender, holding an address but no key.
+/// [`DaemonBase<QueryOnlySender>`](crate::DaemonBase) can query the chain and manage the state, but can't broadcast transactions
+#[derive(Clone)]
+pub struct QueryOnlySender {
+    /// gRPC channel
+    pub grpc_channel: Channel,
+    /// Information about the chain
+    pub chain_info: ChainInfoOwned,
+    /// Impersonated address
+    address: Addr,
+}
+
+impl QueryOnlySender {
+    /// Sender impersonating `address`, which must be an address of the chain
+    pub fn new(
+        chain_info: ChainInfoOwned,
+        channel: Channel,
+        address: impl Into<String>,
+    ) -> Result<Self, DaemonError> {
+        let address: AccountId = address.into().parse()?;
+        if address.prefix() != chain_info.network_info.pub_address_prefix {
+            return Err(DaemonError::WrongAddressPrefix {
+                address: address.to_string(),
+                expected: chain_info.network_info.pub_address_prefix,
+            });
+        }
+        Ok(Self {
+            grpc_channel: channel,
+            chain_info,
+            address: Addr::unchecked(address.to_string()),
+        })
+    }
+
+    /// Impersonates another address
+    pub fn set_address(&mut self, address: impl Into<String>) -> Result<(), DaemonError> {
+        *self = Self::new(self.chain_info.clone(), self.grpc_channel.clone(), address)?;
+        Ok(())
+    }
+}
+
+impl QuerySender for QueryOnlySender {
+    fn chain_info(&self) -> &ChainInfoOwned {
+        &self.chain_info
+    }
+
+    fn grpc_channel(&self) -> Channel {
+        self.grpc_channel.clone()
+    }
+
+    fn address(&self) -> Result<Addr, DaemonError> {
+        Ok(self.address.clone())
+    }
+}
@@ -0,0 +1,217 @@
+//! Crash-safe tracking of broadcasted transactions.
+//!
+//! Uploads, instantiations and migrations are recorded in the state under the `pending_txs` key as soon as they are broadcasted,
+//! and removed once they are found on chain. If the process stops in between (crash, ctrl-c, lost connection),
+//! the next daemon built on the same state resumes the confirmation of those transactions before doing anything else
+//! (see [`DaemonAsyncBuilder::ignore_pending_txs`](crate::DaemonAsyncBuilder::ignore_pending_txs)):
+//! - Instantiations that landed register the contract address under their label, which is the contract id for contract interfaces.
+//! - Uploads that landed are reported with their code id. The code is then found by checksum by `upload_if_needed` instead of being uploaded again.
+//! - Transactions that can't be found are considered dropped by the chain and forgotten.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{networks, receipts::ResumeOutcome, DaemonBuilder};
+//!
+//! // Pending transactions are resumed when building the daemon
+//! let daemon = DaemonBuilder::default()
+//!     .chain(networks::LOCAL_JUNO)
+//!     .build()
+//!     .unwrap();
+//! // They can also be resumed explicitly, to inspect the outcomes
+//! for resumed in daemon.resume_pending_txs().unwrap() {
+//!     if let ResumeOutcome::Landed(tx) = &resumed.outcome {
+//!         println!("{} landed at height {}", tx.txhash, tx.height);
+//!     }
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{CosmTxResponse, DaemonError, DaemonState};
+
+/// State key the pending transactions are stored under
+pub const PENDING_TXS_KEY: &str = "pending_txs";
+
+/// Operation of a pending transaction, used to reconcile the state once it's confirmed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum PendingTxKind {
+    /// Code upload
+    Upload {
+        /// Hex encoded sha256 checksum of the uploaded code
+        checksum: String,
+    },
+    /// Contract instantiation
+    Instantiate {
+        /// Label of the contract, the contract id for contract interfaces
+        label: String,
+    },
+    /// Contract migration
+    Migrate {
+        /// Address of the migrated contract
+        contract: String,
+        /// Code id the contract is migrated to
+        code_id: u64,
+    },
+}
+
+/// Transaction broadcasted but not confirmed yet, as recorded in the state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingTx {
+    /// Hash of the transaction
+    pub txhash: String,
+    /// Deployment the transaction was broadcasted for
+    pub deployment_id: String,
+    /// Operation of the transaction
+    #[serde(flatten)]
+    pub kind: PendingTxKind,
+    /// Unix timestamp of the broadcast, in seconds
+    pub broadcasted_at: u64,
+}
+
+impl PendingTx {
+    /// Pending transaction broadcasted now
+    pub fn new(
+        txhash: impl Into<String>,
+        deployment_id: impl Into<String>,
+        kind: PendingTxKind,
+    ) -> Self {
+        Self {
+            txhash: txhash.into(),
+            deployment_id: deployment_id.into(),
+            kind,
+            broadcasted_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Outcome of the confirmation of a pending transaction
+#[derive(Debug, Clone)]
+pub enum ResumeOutcome {
+    /// The transaction was included in a block and succeeded
+    Landed(CosmTxResponse),
+    /// The transaction was included in a block but failed
+    Failed(CosmTxResponse),
+    /// The transaction wasn't found, it was dropped by the chain
+    Dropped,
+}
+
+/// Pending transaction and the outcome of its confirmation
+#[derive(Debug, Clone)]
+pub struct ResumedTx {
+    /// Transaction as recorded in the state
+    pub pending: PendingTx,
+    /// Outcome of the confirmation
+    pub outcome: ResumeOutcome,
+}
+
+/// Records a broadcasted transaction in the state and writes the state file right away.
+/// Nothing is recorded on a read-only state
+pub fn record_pending_tx(state: &mut DaemonState, pending: &PendingTx) -> Result<(), DaemonError> {
+    if state.is_read_only() {
+        return Ok(());
+    }
+    state.set(PENDING_TXS_KEY, &pending.txhash, pending)?;
+    if !state.write_on_change {
+        state.force_write()?;
+    }
+    Ok(())
+}
+
+/// Removes a confirmed transaction from the state
+pub fn clear_pending_tx(state: &mut DaemonState, txhash: &str) -> Result<(), DaemonError> {
+    if state.is_read_only() {
+        return Ok(());
+    }
+    state.remove(PENDING_TXS_KEY, txhash)?;
+    if !state.write_on_change {
+        state.force_write()?;
+    }
+    Ok(())
+}
+
+/// Transactions recorded in the state that are not confirmed yet, oldest first
+pub fn pending_txs(state: &DaemonState) -> Result<Vec<PendingTx>, DaemonError> {
+    let mut pending = state
+        .get(PENDING_TXS_KEY)?
+        .as_object()
+        .map(|txs| {
+            txs.values()
+                .filter(|value| !value.is_null())
+                .map(|value| serde_json::from_value(value.clone()))
+                .collect::<Result<Vec<PendingTx>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    pending.sort_by_key(|tx| tx.broadcasted_at);
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{clear_pending_tx, pending_txs, record_pending_tx, PendingTx, PendingTxKind};
+    use crate::{networks, DaemonState};
+
+    #[test]
+    fn pending_txs_are_persisted() -> anyhow::Result<()> {
+        let path = DaemonState::temporary_state_file_path();
+        let mut state = DaemonState::new(
+            path.clone(),
+            networks::JUNO_1.into(),
+            "default".to_string(),
+            false,
+            false,
+        )?;
+        state.remove_file_on_drop();
+
+        let upload = PendingTx::new(
+            "UPLOAD",
+            "default",
+            PendingTxKind::Upload {
+                checksum: "aa".to_string(),
+            },
+        );
+        record_pending_tx(&mut state, &upload)?;
+        // The receipt is written to the file right away, even without `write_on_change`
+        let file = crate::json_lock::read(&path)?;
+        assert_eq!(
+            file["juno"]["juno-1"]["pending_txs"]["UPLOAD"]["kind"],
+            "upload"
+        );
+        assert_eq!(pending_txs(&state)?, vec![upload]);
+        assert!(!state.deployment_ids()?.contains(&"pending_txs".to_string()));
+
+        clear_pending_tx(&mut state, "UPLOAD")?;
+        assert!(pending_txs(&state)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn pending_tx_serialization() -> anyhow::Result<()> {
+        let pending = PendingTx {
+            txhash: "ABCD".to_string(),
+            deployment_id: "default".to_string(),
+            kind: PendingTxKind::Instantiate {
+                label: "counter".to_string(),
+            },
+            broadcasted_at: 1_700_000_000,
+        };
+        let value = serde_json::to_value(&pending)?;
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "txhash": "ABCD",
+                "deployment_id": "default",
+                "kind": "instantiate",
+                "label": "counter",
+                "broadcasted_at": 1_700_000_000u64,
+            })
+        );
+        assert_eq!(serde_json::from_value::<PendingTx>(value)?, pending);
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+//! Global tokio runtime used by the synchronous environments when no runtime handle is provided.
+//!
+//! The runtime is created on first use with the default [`RuntimeConfig`].
+//! It can be configured, or replaced by a runtime of the application, once before it is first used:
+//! ```no_run
+//! use cw_orch_daemon::runtime::{init_runtime, RuntimeConfig};
+//! init_runtime(RuntimeConfig::default().worker_threads(2).thread_name("cw-orch")).unwrap();
+//! ```
+
+use once_cell::sync::OnceCell;
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use crate::{
+    env::DaemonEnvVars, DaemonAsyncBase, DaemonAsyncBuilder, DaemonBase, DaemonBuilder,
+    DaemonError, QuerySender,
+};
+
+static GLOBAL_RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+/// Configuration of the global multi-threaded runtime
+#[derive(Clone, Debug)]
+pub struct RuntimeConfig {
+    worker_threads: Option<usize>,
+    thread_name: Option<String>,
+    enable_time: bool,
+    enable_io: bool,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            worker_threads: DaemonEnvVars::runtime_worker_threads(),
+            thread_name: None,
+            enable_time: true,
+            enable_io: true,
+        }
+    }
+}
+
+impl RuntimeConfig {
+    /// Number of worker threads, defaults to the number of cores
+    pub fn worker_threads(&mut self, worker_threads: usize) -> &mut Self {
+        self.worker_threads = Some(worker_threads);
+        self
+    }
+
+    /// Name of the threads spawned by the runtime
+    pub fn thread_name(&mut self, thread_name: impl Into<String>) -> &mut Self {
+        self.thread_name = Some(thread_name.into());
+        self
+    }
+
+    /// Enable the time driver, needed to wait for blocks. Defaults to `true`
+    pub fn enable_time(&mut self, enable_time: bool) -> &mut Self {
+        self.enable_time = enable_time;
+        self
+    }
+
+    /// Enable the IO driver, needed to connect to the nodes. Defaults to `true`
+    pub fn enable_io(&mut self, enable_io: bool) -> &mut Self {
+        self.enable_io = enable_io;
+        self
+    }
+
+    /// Builds a runtime with this configuration
+    pub fn build(&self) -> Result<Runtime, DaemonError> {
+        let mut builder = Builder::new_multi_thread();
+        if let Some(worker_threads) = self.worker_threads {
+            builder.worker_threads(worker_threads);
+        }
+        if let Some(thread_name) = &self.thread_name {
+            builder.thread_name(thread_name);
+        }
+        if self.enable_time {
+            builder.enable_time();
+        }
+        if self.enable_io {
+            builder.enable_io();
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Creates the global runtime with `config`.
+/// Errors if the global runtime was already initialized or used.
+pub fn init_runtime(config: &RuntimeConfig) -> Result<(), DaemonError> {
+    if GLOBAL_RUNTIME.get().is_some() {
+        return Err(DaemonError::RuntimeAlreadyInitialized);
+    }
+    set_runtime(config.build()?)
+}
+
+/// Uses `runtime` as the global runtime.
+/// Errors if the global runtime was already initialized or used.
+pub fn set_runtime(runtime: Runtime) -> Result<(), DaemonError> {
+    GLOBAL_RUNTIME
+        .set(runtime)
+        .map_err(|_| DaemonError::RuntimeAlreadyInitialized)
+}
+
+/// Global runtime, created with the default [`RuntimeConfig`] if it wasn't initialized
+pub fn global_runtime() -> &'static Runtime {
+    GLOBAL_RUNTIME.get_or_init(|| {
+        RuntimeConfig::default()
+            .build()
+            .expect("Failed to build the global tokio runtime")
+    })
+}
+
+/// Access to the runtime handle an environment uses to await async functions
+pub trait RuntimeHandle {
+    /// Handle of the runtime used by this environment
+    fn runtime_handle(&self) -> Handle;
+}
+
+impl<S: QuerySender> RuntimeHandle for DaemonBase<S> {
+    fn runtime_handle(&self) -> Handle {
+        self.rt_handle.clone()
+    }
+}
+
+impl<S: QuerySender> RuntimeHandle for DaemonAsyncBase<S> {
+    /// The runtime the async daemon is awaited in, or the global runtime outside of a runtime context
+    fn runtime_handle(&self) -> Handle {
+        current_or_global()
+    }
+}
+
+impl RuntimeHandle for DaemonBuilder {
+    fn runtime_handle(&self) -> Handle {
+        self.handle
+            .clone()
+            .unwrap_or_else(|| global_runtime().handle().clone())
+    }
+}
+
+impl RuntimeHandle for DaemonAsyncBuilder {
+    fn runtime_handle(&self) -> Handle {
+        current_or_global()
+    }
+}
+
+fn current_or_global() -> Handle {
+    Handle::try_current().unwrap_or_else(|_| global_runtime().handle().clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn runtime_config() -> anyhow::Result<()> {
+        let runtime = RuntimeConfig::default()
+            .worker_threads(1)
+            .thread_name("cw-orch-test")
+            .build()?;
+
+        let thread_name = runtime.block_on(async {
+            tokio::spawn(async { std::thread::current().name().map(ToString::to_string) }).await
+        })?;
+        assert_eq!(thread_name.as_deref(), Some("cw-orch-test"));
+
+        // The global runtime can't be replaced once it's been used
+        global_runtime();
+        assert!(matches!(
+            init_runtime(&RuntimeConfig::default()),
+            Err(DaemonError::RuntimeAlreadyInitialized)
+        ));
+        Ok(())
+    }
+}
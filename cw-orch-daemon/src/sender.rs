@@ -1,42 +1,50 @@
 use crate::{
+    broadcast_backend::BroadcastBackend,
     env::DaemonEnvVars,
-    proto::injective::ETHEREUM_COIN_TYPE,
+    fees::{FeeToken, GasPriceOracle},
+    gas_table::GasEstimation,
+    proto::injective::{default_eth_pub_key_type_url, ETHEREUM_COIN_TYPE},
     queriers::Bank,
-    tx_broadcaster::{
-        account_sequence_strategy, assert_broadcast_code_cosm_response, insufficient_fee_strategy,
-        TxBroadcaster,
-    },
+    sequence::SequenceTracker,
+    tx_broadcaster::{assert_broadcast_code_cosm_response, BroadcastPolicy, TxBroadcaster},
 };
 
 use super::{
     cosmos_modules::{self, auth::BaseAccount},
     error::DaemonError,
     queriers::Node,
-    tx_builder::TxBuilder,
+    tx_builder::{TxBuilder, TxOptions},
     tx_resp::CosmTxResponse,
 };
 use crate::proto::injective::InjectiveEthAccount;
 
 #[cfg(feature = "eth")]
-use crate::proto::injective::InjectiveSigner;
+use crate::proto::injective::EthSigner;
 
-use crate::{core::parse_cw_coins, keys::private::PrivateKey};
+use crate::{
+    core::parse_cw_coins,
+    keys::{hd_path::HdPath, private::PrivateKey},
+};
 use cosmrs::{
     bank::MsgSend,
     crypto::secp256k1::SigningKey,
-    proto::{cosmos::authz::v1beta1::MsgExec, traits::Message},
+    proto::{
+        cosmos::{authz::v1beta1::MsgExec, base::abci::v1beta1::TxResponse},
+        traits::Message,
+    },
     tendermint::chain::Id,
-    tx::{self, ModeInfo, Msg, Raw, SignDoc, SignMode, SignerInfo},
+    tx::{self, ModeInfo, Msg, Raw, SignDoc, SignMode, SignerInfo, SignerPublicKey},
     AccountId, Any,
 };
 use cosmwasm_std::{coin, Addr, Coin};
 use cw_orch_core::{
-    environment::{ChainInfoOwned, ChainKind},
-    log::local_target,
+    environment::{AddressAlgorithm, ChainInfoOwned, ChainKind},
+    log::{local_target, transaction_target},
     CoreEnvVars, CwEnvError,
 };
 
 use crate::env::{LOCAL_MNEMONIC_ENV_NAME, MAIN_MNEMONIC_ENV_NAME, TEST_MNEMONIC_ENV_NAME};
+use crate::log::{TxEvent, TxStage};
 use bitcoin::secp256k1::{All, Context, Secp256k1, Signing};
 use std::{str::FromStr, sync::Arc};
 
@@ -47,11 +55,44 @@ const GAS_BUFFER: f64 = 1.3;
 const BUFFER_THRESHOLD: u64 = 200_000;
 const SMALL_GAS_BUFFER: f64 = 1.4;
 
+/// Sender interface needed to query a chain, without signing capabilities.
+/// Implemented by [`Wallet`] and by the key-less [`QueryOnlySender`](crate::query_only_sender::QueryOnlySender)
+pub trait QuerySender: Clone {
+    /// Information about the chain
+    fn chain_info(&self) -> &ChainInfoOwned;
+    /// gRPC channel used for the queries
+    fn grpc_channel(&self) -> Channel;
+    /// Address the queries are made for
+    fn address(&self) -> Result<Addr, DaemonError>;
+}
+
+impl QuerySender for Wallet {
+    fn chain_info(&self) -> &ChainInfoOwned {
+        &self.chain_info
+    }
+
+    fn grpc_channel(&self) -> Channel {
+        self.channel()
+    }
+
+    fn address(&self) -> Result<Addr, DaemonError> {
+        Sender::<All>::address(self)
+    }
+}
+
 /// This enum allows for choosing which sender type will be constructed in a DaemonBuilder
 #[derive(Clone)]
 pub enum SenderBuilder<C: Signing + Context> {
     Sender(Sender<C>),
     Mnemonic(String),
+    /// Name of a mnemonic stored in the OS keychain, see [`crate::keyring`]
+    #[cfg(feature = "keyring")]
+    Keyring(String),
+    /// Armored private key exported with `<chaind> keys export`, with its passphrase
+    Armored {
+        armor: String,
+        passphrase: String,
+    },
 }
 
 /// A wallet is a sender of transactions, can be safely cloned and shared within the same thread.
@@ -68,6 +109,8 @@ pub struct Sender<C: Signing + Context> {
     /// Information about the chain
     pub chain_info: ChainInfoOwned,
     pub(crate) options: SenderOptions,
+    /// Sequence kept locally when [`SenderOptions::track_sequence`] is enabled
+    pub(crate) sequence: SequenceTracker,
 }
 
 /// Options for how txs should be constructed for this sender.
@@ -77,6 +120,27 @@ pub struct SenderOptions {
     pub authz_granter: Option<String>,
     pub fee_granter: Option<String>,
     pub hd_index: Option<u32>,
+    /// Derivation path of the sender key, for funds held on nonstandard derivations.
+    /// Defaults to `m/44'/{coin_type}'/0'/0/{hd_index}`, `hd_index` overrides the index of the path when set
+    pub hd_path: Option<HdPath>,
+    /// Backend used to submit the signed transactions
+    pub broadcaster: BroadcastBackend,
+    /// Tokens fees can be paid in, by order of preference. Defaults to the gas denom of the chain
+    pub fee_tokens: Vec<FeeToken>,
+    /// Oracle fetching the gas price before every transaction
+    pub gas_price_oracle: Option<GasPriceOracle>,
+    /// Retry policy of the transaction broadcasting
+    pub broadcast_policy: BroadcastPolicy,
+    /// Keep the account sequence locally instead of querying it before each transaction, see [`crate::sequence`]
+    pub track_sequence: bool,
+    /// Type url of the ethsecp256k1 public key, for chains with the ethereum coin type.
+    /// Defaults to the Injective or ethermint public key depending on the chain, see [`default_eth_pub_key_type_url`]
+    pub eth_pub_key_type_url: Option<String>,
+    /// How the gas of the transactions is estimated, see [`crate::gas_table`]
+    pub gas_estimation: GasEstimation,
+    /// Algorithm used to derive the sender address, for chains that don't use the usual algorithm of their coin type.
+    /// Defaults to the algorithm of the coin type of the chain, see [`AddressAlgorithm::default_for_coin_type`]
+    pub address_algorithm: Option<AddressAlgorithm>,
 }
 
 impl SenderOptions {
@@ -101,6 +165,77 @@ impl SenderOptions {
     pub fn set_hd_index(&mut self, index: u32) {
         self.hd_index = Some(index);
     }
+    pub fn hd_path(mut self, path: HdPath) -> Self {
+        self.hd_path = Some(path);
+        self
+    }
+    pub fn set_hd_path(&mut self, path: HdPath) {
+        self.hd_path = Some(path);
+    }
+    /// Derivation path of the sender key
+    pub fn derivation_path(&self) -> HdPath {
+        let path = self.hd_path.clone().unwrap_or_default();
+        match self.hd_index {
+            Some(index) => path.index(index),
+            None => path,
+        }
+    }
+    pub fn broadcaster(mut self, broadcaster: impl Into<BroadcastBackend>) -> Self {
+        self.broadcaster = broadcaster.into();
+        self
+    }
+    pub fn set_broadcaster(&mut self, broadcaster: impl Into<BroadcastBackend>) {
+        self.broadcaster = broadcaster.into();
+    }
+    pub fn fee_tokens(mut self, fee_tokens: Vec<FeeToken>) -> Self {
+        self.fee_tokens = fee_tokens;
+        self
+    }
+    pub fn set_fee_tokens(&mut self, fee_tokens: Vec<FeeToken>) {
+        self.fee_tokens = fee_tokens;
+    }
+    pub fn gas_price_oracle(mut self, oracle: GasPriceOracle) -> Self {
+        self.gas_price_oracle = Some(oracle);
+        self
+    }
+    pub fn set_gas_price_oracle(&mut self, oracle: GasPriceOracle) {
+        self.gas_price_oracle = Some(oracle);
+    }
+    pub fn broadcast_policy(mut self, policy: BroadcastPolicy) -> Self {
+        self.broadcast_policy = policy;
+        self
+    }
+    pub fn set_broadcast_policy(&mut self, policy: BroadcastPolicy) {
+        self.broadcast_policy = policy;
+    }
+    pub fn track_sequence(mut self, track_sequence: bool) -> Self {
+        self.track_sequence = track_sequence;
+        self
+    }
+    pub fn set_track_sequence(&mut self, track_sequence: bool) {
+        self.track_sequence = track_sequence;
+    }
+    pub fn eth_pub_key_type_url(mut self, type_url: impl Into<String>) -> Self {
+        self.eth_pub_key_type_url = Some(type_url.into());
+        self
+    }
+    pub fn set_eth_pub_key_type_url(&mut self, type_url: impl Into<String>) {
+        self.eth_pub_key_type_url = Some(type_url.into());
+    }
+    pub fn gas_estimation(mut self, gas_estimation: GasEstimation) -> Self {
+        self.gas_estimation = gas_estimation;
+        self
+    }
+    pub fn set_gas_estimation(&mut self, gas_estimation: GasEstimation) {
+        self.gas_estimation = gas_estimation;
+    }
+    pub fn address_algorithm(mut self, algorithm: AddressAlgorithm) -> Self {
+        self.address_algorithm = Some(algorithm);
+        self
+    }
+    pub fn set_address_algorithm(&mut self, algorithm: AddressAlgorithm) {
+        self.address_algorithm = Some(algorithm);
+    }
 }
 
 impl Sender<All> {
@@ -139,11 +274,10 @@ impl Sender<All> {
         options: SenderOptions,
     ) -> Result<Sender<All>, DaemonError> {
         let secp = Secp256k1::new();
-        let p_key: PrivateKey = PrivateKey::from_words(
+        let p_key: PrivateKey = PrivateKey::from_words_with_path(
             &secp,
             mnemonic,
-            0,
-            options.hd_index.unwrap_or(0),
+            &options.derivation_path(),
             chain_info.network_info.coin_type,
         )?;
 
@@ -153,6 +287,7 @@ impl Sender<All> {
             private_key: p_key,
             secp,
             options,
+            sequence: SequenceTracker::default(),
         };
         log::info!(
             target: &local_target(),
@@ -171,11 +306,10 @@ impl Sender<All> {
         options: SenderOptions,
     ) -> Result<Sender<All>, DaemonError> {
         let secp = Secp256k1::new();
-        let p_key: PrivateKey = PrivateKey::from_raw_key(
+        let p_key: PrivateKey = PrivateKey::from_raw_key_with_path(
             &secp,
             raw_key,
-            0,
-            options.hd_index.unwrap_or(0),
+            &options.derivation_path(),
             chain_info.network_info.coin_type,
         )?;
         let sender = Sender {
@@ -184,6 +318,34 @@ impl Sender<All> {
             options,
             grpc_channel: channel,
             chain_info,
+            sequence: SequenceTracker::default(),
+        };
+        log::info!(
+            target: &local_target(),
+            "Interacting with {} using address: {}",
+            sender.chain_info.chain_id,
+            sender.pub_addr_str()?
+        );
+        Ok(sender)
+    }
+
+    /// Construct a new Sender from an armored private key exported with `<chaind> keys export`.
+    /// The key is used as is, HD derivation options don't apply to it
+    pub fn from_armored_with_options(
+        chain_info: ChainInfoOwned,
+        channel: Channel,
+        armor: &str,
+        passphrase: &str,
+        options: SenderOptions,
+    ) -> Result<Sender<All>, DaemonError> {
+        let p_key = PrivateKey::from_armored(armor, passphrase, chain_info.network_info.coin_type)?;
+        let sender = Sender {
+            private_key: p_key,
+            secp: Secp256k1::new(),
+            options,
+            grpc_channel: channel,
+            chain_info,
+            sequence: SequenceTracker::default(),
         };
         log::info!(
             target: &local_target(),
@@ -203,8 +365,8 @@ impl Sender<All> {
     }
 
     pub fn set_options(&mut self, options: SenderOptions) {
-        if options.hd_index.is_some() {
-            // Need to generate new sender as hd_index impacts private key
+        if options.hd_index.is_some() || options.hd_path.is_some() {
+            // Need to generate new sender as hd_index and hd_path impact private key
             let new_sender = Sender::from_raw_key_with_options(
                 self.chain_info.clone(),
                 self.channel(),
@@ -225,7 +387,11 @@ impl Sender<All> {
     pub fn pub_addr(&self) -> Result<AccountId, DaemonError> {
         Ok(AccountId::new(
             &self.chain_info.network_info.pub_address_prefix,
-            &self.private_key.public_key(&self.secp).raw_address.unwrap(),
+            &self
+                .private_key
+                .public_key_with_algorithm(&self.secp, self.address_algorithm())
+                .raw_address
+                .unwrap(),
         )?)
     }
 
@@ -266,9 +432,58 @@ impl Sender<All> {
         self.chain_info.gas_denom.to_string()
     }
 
-    /// Compute the gas fee from the expected gas in the transaction
-    /// Applies a Gas Buffer for including signature verification
-    pub(crate) fn get_fee_from_gas(&self, gas: u64) -> Result<(u64, u128), DaemonError> {
+    /// Compute the gas limit and fee from the expected gas in the transaction.
+    /// Applies a Gas Buffer for including signature verification.
+    /// The fee is paid with the first fee token the sender holds enough of, at the gas price of the oracle if it is higher.
+    pub async fn fee_for_gas(&self, gas: u64) -> Result<(u64, Coin), DaemonError> {
+        let gas_expected = Self::gas_with_buffer(gas);
+        let fee_tokens = if self.options.fee_tokens.is_empty() {
+            vec![FeeToken::new(
+                self.chain_info.gas_denom.clone(),
+                self.chain_info.gas_price,
+            )]
+        } else {
+            self.options.fee_tokens.clone()
+        };
+
+        let mut fees = vec![];
+        for fee_token in fee_tokens {
+            let mut gas_price = fee_token.gas_price;
+            if let Some(oracle) = &self.options.gas_price_oracle {
+                match oracle
+                    .gas_price(self.channel(), &fee_token.denom, &self.chain_info.gas_denom)
+                    .await
+                {
+                    Ok(Some(oracle_price)) => gas_price = gas_price.max(oracle_price),
+                    Ok(None) => {}
+                    Err(e) => log::warn!(
+                        "Could not fetch the gas price of {}, using {}: {e}",
+                        fee_token.denom,
+                        gas_price
+                    ),
+                }
+            }
+            let fee_amount = gas_expected * (gas_price + 0.00001);
+            fees.push(coin(fee_amount as u128, fee_token.denom));
+        }
+
+        // Only check balances when there is a choice to make
+        if fees.len() > 1 {
+            let bank = Bank::new_async(self.channel());
+            for fee in &fees {
+                let balance = bank
+                    ._balance(self.address()?, Some(fee.denom.clone()))
+                    .await?;
+                if balance.first().is_some_and(|b| b.amount >= fee.amount) {
+                    return Ok((gas_expected as u64, fee.clone()));
+                }
+            }
+        }
+        Ok((gas_expected as u64, fees.swap_remove(0)))
+    }
+
+    /// Applies the gas buffer to the simulated gas
+    fn gas_with_buffer(gas: u64) -> f64 {
         let mut gas_expected = if let Some(gas_buffer) = DaemonEnvVars::gas_buffer() {
             gas as f64 * gas_buffer
         } else if gas < BUFFER_THRESHOLD {
@@ -280,9 +495,7 @@ impl Sender<All> {
         if let Some(min_gas) = DaemonEnvVars::min_gas() {
             gas_expected = (min_gas as f64).max(gas_expected);
         }
-        let fee_amount = gas_expected * (self.chain_info.gas_price + 0.00001);
-
-        Ok((gas_expected as u64, fee_amount as u128))
+        gas_expected
     }
 
     /// Computes the gas needed for submitting a transaction
@@ -291,22 +504,33 @@ impl Sender<All> {
         tx_body: &tx::Body,
         sequence: u64,
         account_number: u64,
+    ) -> Result<u64, DaemonError> {
+        self.calculate_gas_for_body(tx_body.clone().into_bytes()?, sequence, account_number)
+            .await
+    }
+
+    /// Simulates a transaction with an already encoded body and returns the gas it uses
+    pub async fn calculate_gas_for_body(
+        &self,
+        body_bytes: Vec<u8>,
+        sequence: u64,
+        account_number: u64,
     ) -> Result<u64, DaemonError> {
         let fee = TxBuilder::build_fee(0u8, &self.chain_info.gas_denom, 0, self.options.clone())?;
 
         let auth_info = SignerInfo {
-            public_key: self.private_key.get_signer_public_key(&self.secp),
+            public_key: self.signer_public_key(),
             mode_info: ModeInfo::single(SignMode::Direct),
             sequence,
         }
         .auth_info(fee);
 
-        let sign_doc = SignDoc::new(
-            tx_body,
-            &auth_info,
-            &Id::try_from(self.chain_info.chain_id.to_string())?,
+        let sign_doc = SignDoc {
+            body_bytes,
+            auth_info_bytes: auth_info.into_bytes()?,
+            chain_id: Id::try_from(self.chain_info.chain_id.to_string())?.to_string(),
             account_number,
-        )?;
+        };
 
         let tx_raw = self.sign(sign_doc)?;
 
@@ -315,6 +539,36 @@ impl Sender<All> {
             .await
     }
 
+    /// Estimates the gas used by a transaction containing `msgs` with an already encoded body.
+    /// The transaction is simulated unless a [`GasTable`](crate::gas_table::GasTable) is configured, see [`SenderOptions::gas_estimation`]
+    pub async fn estimate_gas_for_body(
+        &self,
+        msgs: &[Any],
+        body_bytes: Vec<u8>,
+        sequence: u64,
+        account_number: u64,
+    ) -> Result<u64, DaemonError> {
+        match &self.options.gas_estimation {
+            GasEstimation::Simulate => {
+                self.calculate_gas_for_body(body_bytes, sequence, account_number)
+                    .await
+            }
+            GasEstimation::Table(table) => Ok(table.estimate(msgs)),
+            GasEstimation::SimulateOrTable(table) => {
+                match self
+                    .calculate_gas_for_body(body_bytes, sequence, account_number)
+                    .await
+                {
+                    Ok(gas) => Ok(gas),
+                    Err(e) => {
+                        log::warn!("Simulation failed, estimating the gas from the gas table: {e}");
+                        Ok(table.estimate(msgs))
+                    }
+                }
+            }
+        }
+    }
+
     /// Simulates the transaction against an actual node
     /// Returns the gas needed as well as the fee needed for submitting a transaction
     pub async fn simulate(
@@ -330,8 +584,7 @@ impl Sender<All> {
 
         let gas_needed = tx_builder.simulate(self).await?;
 
-        let (gas_for_submission, fee_amount) = self.get_fee_from_gas(gas_needed)?;
-        let expected_fee = coin(fee_amount, self.get_fee_token());
+        let (gas_for_submission, expected_fee) = self.fee_for_gas(gas_needed).await?;
         // During simulation, we also make sure the account has enough balance to submit the transaction
         // This is disabled by an env variable
         if DaemonEnvVars::wallet_balance_assertion() {
@@ -360,41 +613,146 @@ impl Sender<All> {
         msgs: Vec<Any>,
         memo: Option<&str>,
     ) -> Result<CosmTxResponse, DaemonError> {
-        let timeout_height = Node::new_async(self.channel())._block_height().await? + 10u64;
+        let options = TxOptions {
+            memo: memo.map(ToString::to_string),
+            ..Default::default()
+        };
+        self.commit_tx_any_with_options(msgs, &options).await
+    }
 
-        let msgs = if self.options.authz_granter.is_some() {
-            // We wrap authz messages
-            vec![Any {
-                type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
-                value: MsgExec {
-                    grantee: self.pub_addr_str()?,
-                    msgs,
-                }
-                .encode_to_vec(),
-            }]
-        } else {
-            msgs
+    /// Commits the messages with a custom memo and timeout height, see [`TxOptions`]
+    pub async fn commit_tx_any_with_options(
+        &self,
+        msgs: Vec<Any>,
+        options: &TxOptions,
+    ) -> Result<CosmTxResponse, DaemonError> {
+        self.commit_tx_any_with_receipt(msgs, options, |_| Ok(()))
+            .await
+    }
+
+    /// Commits the messages, calling `on_broadcast` with the hash of the transaction once it is broadcasted,
+    /// before waiting for it to be included in a block. Used to persist receipts of broadcasted transactions, see [`crate::receipts`]
+    pub async fn commit_tx_any_with_receipt(
+        &self,
+        msgs: Vec<Any>,
+        options: &TxOptions,
+        on_broadcast: impl FnOnce(&str) -> Result<(), DaemonError>,
+    ) -> Result<CosmTxResponse, DaemonError> {
+        // The gas table learns the gas used by the messages as they are broadcasted
+        let broadcasted_msgs = match self.options.gas_estimation.gas_table() {
+            Some(_) => self.wrap_authz(msgs.clone())?,
+            None => vec![],
         };
+        let tx_response = self.broadcast_tx_any(msgs, options).await.map_err(|e| {
+            self.log_tx_stage(TxStage::Failed {
+                txhash: None,
+                error: e.to_string(),
+            });
+            e
+        })?;
+        self.log_tx_stage(TxStage::Broadcasted {
+            txhash: tx_response.txhash.clone(),
+        });
+        on_broadcast(&tx_response.txhash)?;
 
-        let tx_body = TxBuilder::build_body(msgs, memo, timeout_height);
+        let resp = Node::new_async(self.channel())
+            ._find_tx(tx_response.txhash.clone())
+            .await
+            .map_err(|e| {
+                self.log_tx_stage(TxStage::Failed {
+                    txhash: Some(tx_response.txhash.clone()),
+                    error: e.to_string(),
+                });
+                e
+            })?;
+        self.log_tx_stage(TxStage::Confirmed {
+            txhash: resp.txhash.clone(),
+            height: resp.height,
+            gas_used: resp.gas_used,
+            code: resp.code,
+        });
+
+        let resp = assert_broadcast_code_cosm_response(resp)?;
+        for msg in resp.decode_msgs() {
+            log::debug!(target: &transaction_target(), "Committed: {msg}");
+        }
+        if let Some(table) = self.options.gas_estimation.gas_table() {
+            table.learn(&broadcasted_msgs, resp.gas_used)?;
+        }
+        Ok(resp)
+    }
 
-        let tx_builder = TxBuilder::new(tx_body);
+    /// Broadcasts the messages without waiting for the transaction to be included in a block.
+    /// Combined with [`SenderOptions::track_sequence`] or [`TxOptions::unordered`], multiple transactions can be included in the same block
+    pub async fn broadcast_tx_any(
+        &self,
+        msgs: Vec<Any>,
+        options: &TxOptions,
+    ) -> Result<TxResponse, DaemonError> {
+        let current_height = Node::new_async(self.channel())._block_height().await?;
+
+        let msgs = self.wrap_authz(msgs)?;
+
+        let tx_builder = TxBuilder::new_with_options(msgs, options, current_height);
 
         // We retry broadcasting the tx, with the following strategies
         // 1. In case there is an `incorrect account sequence` error, we can retry as much as possible (doesn't cost anything to the user)
         // 2. In case there is an insufficient_fee error, we retry once (costs fee to the user everytime we submit this kind of tx)
         // 3. In case there is an other error, we fail
-        let tx_response = TxBroadcaster::default()
-            .add_strategy(insufficient_fee_strategy())
-            .add_strategy(account_sequence_strategy())
+        TxBroadcaster::from_policy(&self.options.broadcast_policy)
             .broadcast(tx_builder, self)
-            .await?;
+            .await
+    }
 
-        let resp = Node::new_async(self.channel())
-            ._find_tx(tx_response.txhash)
-            .await?;
+    /// Logs a lifecycle event of a transaction of this sender, see [`crate::log`]
+    fn log_tx_stage(&self, stage: TxStage) {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_tx_stage(&self.chain_info.chain_id, &stage);
+        let sender = self.pub_addr_str().unwrap_or_default();
+        TxEvent::new(self.chain_info.chain_id.clone(), sender, stage).log();
+    }
 
-        assert_broadcast_code_cosm_response(resp)
+    /// Wraps the messages in an authz `MsgExec` when the sender acts for an [`SenderOptions::authz_granter`]
+    fn wrap_authz(&self, msgs: Vec<Any>) -> Result<Vec<Any>, DaemonError> {
+        if self.options.authz_granter.is_none() {
+            return Ok(msgs);
+        }
+        Ok(vec![Any {
+            type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+            value: MsgExec {
+                grantee: self.pub_addr_str()?,
+                msgs,
+            }
+            .encode_to_vec(),
+        }])
+    }
+
+    /// Sequence tracked locally, see [`SenderOptions::track_sequence`]
+    pub fn sequence_tracker(&self) -> &SequenceTracker {
+        &self.sequence
+    }
+
+    /// Type url of the ethsecp256k1 public key of the sender, used on chains with the ethereum coin type
+    pub fn eth_pub_key_type_url(&self) -> &str {
+        self.options
+            .eth_pub_key_type_url
+            .as_deref()
+            .unwrap_or_else(|| {
+                default_eth_pub_key_type_url(&self.chain_info.network_info.pub_address_prefix)
+            })
+    }
+
+    /// Algorithm used to derive the sender address, see [`SenderOptions::address_algorithm`]
+    pub fn address_algorithm(&self) -> AddressAlgorithm {
+        self.options
+            .address_algorithm
+            .unwrap_or_else(|| self.chain_info.network_info.address_algorithm())
+    }
+
+    /// Public key set in the signer infos of the transactions
+    pub fn signer_public_key(&self) -> Option<SignerPublicKey> {
+        self.private_key
+            .get_signer_public_key_with_type(&self.secp, self.eth_pub_key_type_url())
     }
 
     pub fn sign(&self, sign_doc: SignDoc) -> Result<Raw, DaemonError> {
@@ -405,7 +763,7 @@ impl Sender<All> {
                 ETHEREUM_COIN_TYPE
             );
             #[cfg(feature = "eth")]
-            self.private_key.sign_injective(sign_doc)?
+            self.private_key.sign_eth(sign_doc)?
         } else {
             sign_doc.sign(&self.cosmos_private_key())?
         };
@@ -440,10 +798,11 @@ impl Sender<All> {
         Ok(acc)
     }
 
-    pub async fn broadcast_tx(
-        &self,
-        tx: Raw,
-    ) -> Result<cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse, DaemonError> {
+    pub async fn broadcast_tx(&self, tx: Raw) -> Result<TxResponse, DaemonError> {
+        if let BroadcastBackend::Http(broadcaster) = &self.options.broadcaster {
+            return broadcaster.broadcast(tx.to_bytes()?).await;
+        }
+
         let mut client = cosmos_modules::tx::service_client::ServiceClient::new(self.channel());
         let commit = client
             .broadcast_tx(cosmos_modules::tx::BroadcastTxRequest {
@@ -458,11 +817,9 @@ impl Sender<All> {
 
     /// Allows for checking wether the sender is able to broadcast a transaction that necessitates the provided `gas`
     pub async fn has_enough_balance_for_gas(&self, gas: u64) -> Result<(), DaemonError> {
-        let (_gas_expected, fee_amount) = self.get_fee_from_gas(gas)?;
-        let fee_denom = self.get_fee_token();
+        let (_gas_expected, fee) = self.fee_for_gas(gas).await?;
 
-        self.assert_wallet_balance(&coin(fee_amount, fee_denom))
-            .await
+        self.assert_wallet_balance(&fee).await
     }
 
     /// Allows checking wether the sender has more funds than the provided `fee` argument
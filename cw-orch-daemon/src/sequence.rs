@@ -0,0 +1,102 @@
+//! Local tracking of the account sequence.
+//!
+//! By default, the sequence of every transaction is queried from the node, which only knows the sequence of committed transactions.
+//! A sender can only have one transaction in flight per block.
+//!
+//! With sequence tracking enabled, the sender keeps the sequence locally and increments it for every broadcasted transaction.
+//! Transactions can then be broadcasted without waiting for the previous ones to be committed.
+//! When the node rejects a transaction because of a sequence mismatch, the local sequence is resynced with the one expected by the node.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{networks, sender::SenderOptions, DaemonAsync, TxOptions};
+//! # async fn run(msgs: Vec<Vec<cosmrs::Any>>) -> Result<(), cw_orch_daemon::DaemonError> {
+//! let daemon = DaemonAsync::builder()
+//!     .chain(networks::LOCAL_JUNO)
+//!     .track_sequence(true)
+//!     .build()
+//!     .await?;
+//!
+//! // Fires all the transactions in the same block
+//! let mut hashes = vec![];
+//! for msgs in msgs {
+//!     let response = daemon.sender.broadcast_tx_any(msgs, &TxOptions::default()).await?;
+//!     hashes.push(response.txhash);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+/// Next sequence of a sender, shared between the clones of the sender
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    next: Arc<Mutex<Option<u64>>>,
+}
+
+impl SequenceTracker {
+    /// Next sequence known locally, if any
+    pub fn next_sequence(&self) -> Option<u64> {
+        *self.next.lock().unwrap()
+    }
+
+    /// Reserves a sequence for a transaction.
+    /// `chain_sequence` (the sequence queried from the node) is used when no sequence is known locally
+    pub fn reserve(&self, chain_sequence: u64) -> u64 {
+        let mut next = self.next.lock().unwrap();
+        let sequence = next.unwrap_or(chain_sequence);
+        *next = Some(sequence + 1);
+        sequence
+    }
+
+    /// Gives back a sequence reserved for a transaction that wasn't accepted by the node
+    pub fn release(&self, sequence: u64) {
+        let mut next = self.next.lock().unwrap();
+        if *next == Some(sequence + 1) {
+            *next = Some(sequence);
+        } else {
+            // Other transactions reserved a sequence after this one, they will fail and resync
+            *next = None;
+        }
+    }
+
+    /// Resyncs the local sequence with the sequence `expected` by the node.
+    /// With `None`, the sequence is queried from the node for the next transaction
+    pub fn resync(&self, expected: Option<u64>) {
+        *self.next.lock().unwrap() = expected;
+    }
+}
+
+/// Parses the sequence expected by the node from a sequence mismatch error
+/// (`account sequence mismatch, expected 12, got 10: incorrect account sequence`)
+pub(crate) fn parse_expected_sequence(raw_log: &str) -> Option<u64> {
+    let (_, expected) = raw_log.split_once("expected ")?;
+    let end = expected
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(expected.len());
+    expected[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sequence_tracking() {
+        let tracker = SequenceTracker::default();
+        assert_eq!(tracker.reserve(5), 5);
+        // The chain sequence lags behind the pending transactions
+        assert_eq!(tracker.clone().reserve(5), 6);
+        tracker.release(6);
+        assert_eq!(tracker.next_sequence(), Some(6));
+        tracker.release(4);
+        assert_eq!(tracker.next_sequence(), None);
+
+        tracker.resync(parse_expected_sequence(
+            "account sequence mismatch, expected 12, got 10: incorrect account sequence",
+        ));
+        assert_eq!(tracker.reserve(5), 12);
+        assert_eq!(parse_expected_sequence("out of gas"), None);
+    }
+}
@@ -0,0 +1,256 @@
+//! Submission of source verification metadata after uploads.
+//!
+//! Explorers and source registries show uploaded code as unverified until its source is submitted to them.
+//! When a [`SourceVerification`] hook is set on the daemon builder, every successful upload on a mainnet chain
+//! posts the code id, the checksum of the code and the source metadata (repository, commit, optimizer version)
+//! to the configured endpoint. The outcome is recorded in the state under the `verifications` key, indexed by code id.
+//!
+//! A failing submission doesn't fail the upload, it is logged and recorded as [`VerificationStatus::Failed`].
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{networks, source_verification::SourceVerification, DaemonBuilder};
+//!
+//! let daemon = DaemonBuilder::default()
+//!     .chain(networks::JUNO_1)
+//!     .source_verification(
+//!         SourceVerification::new(
+//!             "https://verifier.example.com/api/verify",
+//!             "https://github.com/my-org/my-contracts",
+//!             "2b7a1c4",
+//!         )
+//!         .optimizer_version("0.15.0")
+//!         .api_key("my-api-key"),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use cosmwasm_std::HexBinary;
+use cw_orch_core::environment::ChainKind;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{broadcast_backend::DEFAULT_API_KEY_HEADER, DaemonError, DaemonState};
+
+/// State key the verification statuses are stored under
+pub const VERIFICATIONS_KEY: &str = "verifications";
+
+/// Endpoint and source metadata submitted after uploads, see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceVerification {
+    /// Url the verification metadata is posted to
+    pub url: String,
+    /// API key sent with every request
+    pub api_key: Option<String>,
+    /// Header the API key is sent in, defaults to [`DEFAULT_API_KEY_HEADER`]
+    pub api_key_header: String,
+    /// Repository of the contract sources
+    pub repository: String,
+    /// Commit the code was built from
+    pub commit: String,
+    /// Version of the cosmwasm optimizer the code was built with
+    pub optimizer_version: Option<String>,
+    /// Also submit uploads on testnets and local chains. Defaults to `false`
+    pub all_chains: bool,
+}
+
+/// Metadata posted to the verification endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerificationRequest {
+    /// Chain the code was uploaded to
+    pub chain_id: String,
+    /// Uploaded code id
+    pub code_id: u64,
+    /// Sha256 checksum of the uploaded code
+    pub checksum: HexBinary,
+    /// Hash of the upload transaction
+    pub txhash: String,
+    /// Repository of the contract sources
+    pub repository: String,
+    /// Commit the code was built from
+    pub commit: String,
+    /// Version of the cosmwasm optimizer the code was built with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimizer_version: Option<String>,
+}
+
+/// Verification status of an uploaded code, as recorded in the state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum VerificationStatus {
+    /// The metadata was accepted by the verification endpoint
+    Submitted {
+        /// Repository of the contract sources
+        repository: String,
+        /// Commit the code was built from
+        commit: String,
+    },
+    /// The submission failed
+    Failed {
+        /// Error returned when submitting
+        error: String,
+    },
+}
+
+impl SourceVerification {
+    /// Hook posting the metadata of uploads built from `commit` of `repository` to `url`
+    pub fn new(
+        url: impl Into<String>,
+        repository: impl Into<String>,
+        commit: impl Into<String>,
+    ) -> Self {
+        Self {
+            url: url.into(),
+            api_key: None,
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            repository: repository.into(),
+            commit: commit.into(),
+            optimizer_version: None,
+            all_chains: false,
+        }
+    }
+
+    /// Sets the API key sent with every request
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sets the header the API key is sent in
+    pub fn api_key_header(mut self, header: impl Into<String>) -> Self {
+        self.api_key_header = header.into();
+        self
+    }
+
+    /// Sets the version of the cosmwasm optimizer the code was built with
+    pub fn optimizer_version(mut self, version: impl Into<String>) -> Self {
+        self.optimizer_version = Some(version.into());
+        self
+    }
+
+    /// Whether to also submit uploads on testnets and local chains
+    pub fn all_chains(mut self, all_chains: bool) -> Self {
+        self.all_chains = all_chains;
+        self
+    }
+
+    /// Returns whether uploads on a chain of this kind are submitted
+    pub fn applies_to(&self, kind: &ChainKind) -> bool {
+        self.all_chains || *kind == ChainKind::Mainnet
+    }
+
+    /// Metadata submitted for an upload of `code`
+    pub fn request(
+        &self,
+        chain_id: impl Into<String>,
+        code_id: u64,
+        code: &[u8],
+        txhash: impl Into<String>,
+    ) -> VerificationRequest {
+        VerificationRequest {
+            chain_id: chain_id.into(),
+            code_id,
+            checksum: HexBinary::from(Sha256::digest(code).to_vec()),
+            txhash: txhash.into(),
+            repository: self.repository.clone(),
+            commit: self.commit.clone(),
+            optimizer_version: self.optimizer_version.clone(),
+        }
+    }
+
+    /// Posts the metadata to the verification endpoint
+    pub async fn submit(&self, request: &VerificationRequest) -> Result<(), DaemonError> {
+        let mut http_request = reqwest::Client::new()
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_string(request)?);
+        if let Some(api_key) = &self.api_key {
+            http_request = http_request.header(self.api_key_header.as_str(), api_key);
+        }
+        http_request.send().await?.error_for_status()?;
+        Ok(())
+    }
+
+    /// Submits the metadata and records the outcome in the state.
+    /// Submission errors are logged and recorded, only state errors are returned
+    pub async fn submit_and_record(
+        &self,
+        state: &mut DaemonState,
+        request: &VerificationRequest,
+    ) -> Result<VerificationStatus, DaemonError> {
+        let status = match self.submit(request).await {
+            Ok(()) => {
+                log::info!(
+                    "Submitted source verification of code id {} ({}@{})",
+                    request.code_id,
+                    request.repository,
+                    request.commit
+                );
+                VerificationStatus::Submitted {
+                    repository: request.repository.clone(),
+                    commit: request.commit.clone(),
+                }
+            }
+            Err(e) => {
+                log::warn!(
+                    "Source verification of code id {} failed: {e}",
+                    request.code_id
+                );
+                VerificationStatus::Failed {
+                    error: e.to_string(),
+                }
+            }
+        };
+        state.set(VERIFICATIONS_KEY, &request.code_id.to_string(), &status)?;
+        Ok(status)
+    }
+}
+
+/// Verification status recorded in the state for a code id, if any
+pub fn verification_status(
+    state: &DaemonState,
+    code_id: u64,
+) -> Result<Option<VerificationStatus>, DaemonError> {
+    let value = state.get(VERIFICATIONS_KEY)?[code_id.to_string()].clone();
+    if value.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_value(value)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn verification_request() {
+        let hook =
+            SourceVerification::new("https://verifier", "https://github.com/org/repo", "abc")
+                .optimizer_version("0.15.0");
+        assert!(hook.applies_to(&ChainKind::Mainnet));
+        assert!(!hook.applies_to(&ChainKind::Testnet));
+        assert!(hook.clone().all_chains(true).applies_to(&ChainKind::Local));
+
+        let request = hook.request("juno-1", 42, b"wasm", "TXHASH");
+        assert_eq!(request.code_id, 42);
+        assert_eq!(
+            request.checksum.to_hex(),
+            "336154bf67f765f8f75d16a0accee61b5ee5f6a75b2a2905703df913bd550f3e"
+        );
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["optimizer_version"], "0.15.0");
+
+        let status: VerificationStatus = serde_json::from_value(serde_json::json!({
+            "status": "failed",
+            "error": "timeout"
+        }))
+        .unwrap();
+        assert_eq!(
+            status,
+            VerificationStatus::Failed {
+                error: "timeout".to_string()
+            }
+        );
+    }
+}
@@ -1,20 +1,48 @@
 use super::error::DaemonError;
+use crate::address_book::ADDRESS_BOOK_KEY;
+use crate::airdrop::AIRDROP_KEY;
 use crate::env::{default_state_folder, DaemonEnvVars};
-use crate::{json_lock::JsonLockedState, networks::ChainKind};
+use crate::frontend_export::{FrontendExport, CHECKSUMS_KEY, INIT_MSG_HASHES_KEY};
+use crate::receipts::PENDING_TXS_KEY;
+use crate::source_verification::VERIFICATIONS_KEY;
+use crate::{
+    json_lock::{JsonLockedState, StateFileFormat, StateLockOptions},
+    networks::ChainKind,
+};
 
 use cosmwasm_std::Addr;
 use cw_orch_core::environment::ChainInfoOwned;
 use cw_orch_core::{environment::StateInterface, log::local_target, CwEnvError};
 use once_cell::sync::Lazy;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{json, Value};
 use std::sync::Arc;
 use std::{
     collections::{HashMap, HashSet},
     path::Path,
-    sync::Mutex,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
+/// Key under which code ids are stored, next to the deployment ids
+pub(crate) const CODE_IDS_KEY: &str = "code_ids";
+/// Key under which ibc channels and connections are stored, next to the deployment ids
+pub const IBC_KEY: &str = "ibc";
+/// Keys of the chain state that are not deployment ids, they can't be used as deployment ids
+pub const RESERVED_KEYS: &[&str] = &[
+    CODE_IDS_KEY,
+    IBC_KEY,
+    VERIFICATIONS_KEY,
+    PENDING_TXS_KEY,
+    ADDRESS_BOOK_KEY,
+    CHECKSUMS_KEY,
+    INIT_MSG_HASHES_KEY,
+    AIRDROP_KEY,
+];
+
 /// Global state to track which files are already open by other daemons from other threads
 /// This is necessary because File lock will allow same process to lock file how many times as process wants
 pub(crate) static LOCKED_FILES: Lazy<Mutex<HashSet<String>>> =
@@ -31,6 +59,8 @@ pub struct DaemonState {
     pub chain_data: ChainInfoOwned,
     /// Whether to write on every change of the state
     pub write_on_change: bool,
+    /// File the deployments are exported to when code ids or addresses change, see [`crate::frontend_export`]
+    pub frontend_export: Option<FrontendExport>,
 }
 
 impl Drop for DaemonState {
@@ -57,12 +87,35 @@ impl DaemonState {
     /// Creates a new state from the given chain data and deployment id.
     /// Attempts to connect to any of the provided gRPC endpoints.
     pub fn new(
+        json_file_path: String,
+        chain_data: ChainInfoOwned,
+        deployment_id: String,
+        read_only: bool,
+        write_on_change: bool,
+    ) -> Result<DaemonState, DaemonError> {
+        Self::new_with_lock_options(
+            json_file_path,
+            chain_data,
+            deployment_id,
+            read_only,
+            write_on_change,
+            StateLockOptions::default(),
+        )
+    }
+
+    /// Creates a new state, waiting for other processes to release the state file according to `lock_options`.
+    ///
+    /// A `read_only` state doesn't lock the file, so it can be used while another process writes to it.
+    /// Writing to a read-only state returns [`DaemonError::StateReadOnly`].
+    pub fn new_with_lock_options(
         mut json_file_path: String,
         chain_data: ChainInfoOwned,
         deployment_id: String,
         read_only: bool,
         write_on_change: bool,
+        lock_options: StateLockOptions,
     ) -> Result<DaemonState, DaemonError> {
+        check_deployment_id(&deployment_id)?;
         let chain_id = &chain_data.chain_id;
         let chain_name = &chain_data.network_info.chain_name;
 
@@ -94,14 +147,7 @@ impl DaemonState {
                 "Writing daemon state JSON file: {json_file_path:#?}",
             );
 
-            let mut lock = LOCKED_FILES.lock().unwrap();
-            if lock.contains(&json_file_path) {
-                return Err(DaemonError::StateAlreadyLocked(json_file_path));
-            }
-            let mut json_file_state = JsonLockedState::new(&json_file_path);
-            // Insert file to a locked files list and drop global mutex lock asap
-            lock.insert(json_file_path);
-            drop(lock);
+            let mut json_file_state = lock_state_file(json_file_path, lock_options)?;
 
             json_file_state.prepare(chain_id, chain_name, &deployment_id);
             if write_on_change {
@@ -117,6 +163,7 @@ impl DaemonState {
             deployment_id,
             chain_data,
             write_on_change,
+            frontend_export: None,
         })
     }
 
@@ -155,6 +202,38 @@ impl DaemonState {
         Ok(state_file_path)
     }
 
+    /// Path of a new state file in the temp directory, unique for this process.
+    /// Used by [`DaemonAsyncBuilder::test_state`](crate::DaemonAsyncBuilder::test_state) to isolate tests
+    pub fn temporary_state_file_path() -> String {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!(
+                "cw-orch-test-state-{}-{}-{nanos}.json",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed),
+            ))
+            .into_os_string()
+            .into_string()
+            .unwrap()
+    }
+
+    /// Deletes the state file once the last clone of this state is dropped, instead of writing it.
+    /// Has no effect on a read-only state
+    pub fn remove_file_on_drop(&self) {
+        if let DaemonStateFile::FullAccess { json_file_state } = &self.json_state {
+            json_file_state.lock().unwrap().set_remove_on_drop(true);
+        }
+    }
+
+    /// Returns true if the state file can't be written to, see [`crate::DaemonAsyncBuilder::read_only_state`]
+    pub fn is_read_only(&self) -> bool {
+        matches!(self.json_state, DaemonStateFile::ReadOnly { .. })
+    }
+
     /// Retrieve a stateful value using the chainId and networkId
     pub fn get(&self, key: &str) -> Result<Value, DaemonError> {
         let json = match &self.json_state {
@@ -199,8 +278,7 @@ impl DaemonState {
         if self.write_on_change {
             json_file_lock.force_write();
         }
-
-        Ok(())
+        self.write_frontend_export(key, &json_file_lock)
     }
 
     /// Remove a stateful value using the chainId and networkId
@@ -222,8 +300,21 @@ impl DaemonState {
         if self.write_on_change {
             json_file_lock.force_write();
         }
+        self.write_frontend_export(key, &json_file_lock)
+    }
 
-        Ok(())
+    /// Writes the [`FrontendExport`] of the state if the `key` section changes it
+    fn write_frontend_export(
+        &self,
+        key: &str,
+        json_file_lock: &JsonLockedState,
+    ) -> Result<(), DaemonError> {
+        match &self.frontend_export {
+            Some(export) if export.tracks(key, &self.deployment_id) => {
+                export.write(&json_file_lock.state(), &self.deployment_id)
+            }
+            _ => Ok(()),
+        }
     }
 
     /// Forcefully write current json to a file
@@ -238,6 +329,13 @@ impl DaemonState {
         Ok(())
     }
 
+    /// Sets how the state file is written, see [`StateFileFormat`]. Has no effect on a read-only state
+    pub fn set_file_format(&self, format: StateFileFormat) {
+        if let DaemonStateFile::FullAccess { json_file_state } = &self.json_state {
+            json_file_state.lock().unwrap().set_format(format);
+        }
+    }
+
     /// Flushes all the state related to the current chain
     /// Only works on Local networks
     pub fn flush(&mut self) -> Result<(), DaemonError> {
@@ -264,6 +362,303 @@ impl DaemonState {
         }
         Ok(())
     }
+
+    /// Stores an ibc entry (e.g. a channel or connection description) under a logical name, in the `ibc` section of the current chain
+    pub fn set_ibc<T: Serialize>(&mut self, name: &str, value: &T) -> Result<(), DaemonError> {
+        self.set(IBC_KEY, name, value)
+    }
+
+    /// Retrieves the ibc entry stored under a logical name for the current chain, if any
+    pub fn get_ibc<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, DaemonError> {
+        let value = self.get(IBC_KEY)?[name].clone();
+        if value.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_value(value)?))
+    }
+
+    /// Removes the ibc entry stored under a logical name for the current chain
+    pub fn remove_ibc(&mut self, name: &str) -> Result<(), DaemonError> {
+        self.remove(IBC_KEY, name)
+    }
+
+    /// Returns the names of all the ibc entries stored for the current chain
+    pub fn ibc_names(&self) -> Result<Vec<String>, DaemonError> {
+        Ok(self
+            .get(IBC_KEY)?
+            .as_object()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|(_, value)| !value.is_null())
+                    .map(|(name, _)| name.clone())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Returns all the deployment ids stored for the current chain
+    pub fn deployment_ids(&self) -> Result<Vec<String>, DaemonError> {
+        let chain_state = self.chain_state()?;
+        Ok(chain_state
+            .as_object()
+            .map(|o| {
+                o.keys()
+                    .filter(|k| !RESERVED_KEYS.contains(&k.as_str()))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Removes all the entries that were removed from the state (they are set to `null`) from the whole state file.
+    /// Returns the number of removed entries
+    pub fn compact(&mut self) -> Result<usize, DaemonError> {
+        let mut json_file_lock = self.full_access()?.lock().unwrap();
+        let removed = json_file_lock.compact();
+        json_file_lock.force_write();
+        Ok(removed)
+    }
+
+    /// Moves a deployment of the current chain from the state file to the archive file (see [`DaemonState::archive_path`]).
+    /// The active deployment can't be archived.
+    /// Returns the archived contracts, or `None` if the deployment doesn't exist
+    pub fn archive_deployment(
+        &mut self,
+        deployment_id: &str,
+    ) -> Result<Option<Value>, DaemonError> {
+        if deployment_id == self.deployment_id || RESERVED_KEYS.contains(&deployment_id) {
+            return Err(DaemonError::StdErr(format!(
+                "Can't archive deployment {deployment_id}, it's in use"
+            )));
+        }
+        let chain_name = self.chain_data.network_info.chain_name.clone();
+        let chain_id = self.chain_data.chain_id.clone();
+
+        let mut json_file_lock = self.full_access()?.lock().unwrap();
+        let Some(deployment) = json_file_lock
+            .get_mut(&chain_name, &chain_id)
+            .as_object_mut()
+            .and_then(|o| o.remove(deployment_id))
+        else {
+            return Ok(None);
+        };
+
+        update_locked_file(&archive_path(json_file_lock.path()), |archive| {
+            archive[&chain_name][&chain_id][deployment_id] = deployment.clone();
+        })?;
+
+        json_file_lock.force_write();
+        Ok(Some(deployment))
+    }
+
+    /// Path of the file where archived deployments are moved to
+    pub fn archive_path(&self) -> Result<String, DaemonError> {
+        match &self.json_state {
+            DaemonStateFile::ReadOnly { path } => Ok(archive_path(path)),
+            DaemonStateFile::FullAccess { json_file_state } => {
+                Ok(archive_path(json_file_state.lock().unwrap().path()))
+            }
+        }
+    }
+
+    /// Snapshots the whole state file under `label` in the checkpoint file (see [`DaemonState::checkpoints_path`]),
+    /// replacing the previous checkpoint with the same label.
+    /// Take a checkpoint before running a deployment script to be able to [`rollback_to`](DaemonState::rollback_to) it if the script fails halfway.
+    pub fn checkpoint(&self, label: &str) -> Result<(), DaemonError> {
+        let state = match &self.json_state {
+            DaemonStateFile::ReadOnly { path } => crate::json_lock::read(path)?,
+            DaemonStateFile::FullAccess { json_file_state } => {
+                json_file_state.lock().unwrap().state()
+            }
+        };
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        update_locked_file(&self.checkpoints_path()?, |checkpoints| {
+            checkpoints[label] = json!({ "created_at": created_at, "state": state });
+        })
+    }
+
+    /// Restores the deployments and code ids of the current chain to the checkpoint `label`, see [`DaemonState::checkpoint`].
+    /// The addresses and code ids recorded on this chain since the checkpoint are lost.
+    /// Other chains and the other sections of the chain state (pending transactions, address book, ...) are kept as they are
+    pub fn rollback_to(&mut self, label: &str) -> Result<(), DaemonError> {
+        let checkpoints_path = self.checkpoints_path()?;
+        let checkpoint = read_checkpoints(&checkpoints_path)?[label]["state"].clone();
+        if checkpoint.is_null() {
+            return Err(DaemonError::CheckpointNotFound {
+                label: label.to_string(),
+                path: checkpoints_path,
+            });
+        }
+        let chain_name = &self.chain_data.network_info.chain_name;
+        let chain_id = &self.chain_data.chain_id;
+        let is_restored = |key: &str| key == CODE_IDS_KEY || !RESERVED_KEYS.contains(&key);
+
+        let mut json_file_lock = self.full_access()?.lock().unwrap();
+        if let Some(chain_state) = json_file_lock.get_mut(chain_name, chain_id).as_object_mut() {
+            chain_state.retain(|key, _| !is_restored(key));
+            if let Some(restored) = checkpoint[chain_name][chain_id].as_object() {
+                chain_state.extend(
+                    restored
+                        .iter()
+                        .filter(|(key, _)| is_restored(key))
+                        .map(|(key, value)| (key.clone(), value.clone())),
+                );
+            }
+        }
+        json_file_lock.prepare(chain_id, chain_name, &self.deployment_id);
+        json_file_lock.force_write();
+        log::info!(target: &local_target(), "Rolled back {chain_id} state to checkpoint {label}");
+        if let Some(export) = &self.frontend_export {
+            export.write(&json_file_lock.state(), &self.deployment_id)?;
+        }
+        Ok(())
+    }
+
+    /// Labels of the checkpoints of the state file, see [`DaemonState::checkpoint`]
+    pub fn checkpoints(&self) -> Result<Vec<String>, DaemonError> {
+        Ok(read_checkpoints(&self.checkpoints_path()?)?
+            .as_object()
+            .map(|checkpoints| checkpoints.keys().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// Removes the checkpoint `label`. Returns whether it existed
+    pub fn remove_checkpoint(&self, label: &str) -> Result<bool, DaemonError> {
+        let checkpoints_path = self.checkpoints_path()?;
+        if !Path::new(&checkpoints_path).exists() {
+            return Ok(false);
+        }
+        update_locked_file(&checkpoints_path, |checkpoints| {
+            checkpoints
+                .as_object_mut()
+                .and_then(|checkpoints| checkpoints.remove(label))
+                .is_some()
+        })
+    }
+
+    /// Path of the file where the checkpoints of the state file are stored
+    pub fn checkpoints_path(&self) -> Result<String, DaemonError> {
+        match &self.json_state {
+            DaemonStateFile::ReadOnly { path } => Ok(checkpoints_path(path)),
+            DaemonStateFile::FullAccess { json_file_state } => {
+                Ok(checkpoints_path(json_file_state.lock().unwrap().path()))
+            }
+        }
+    }
+
+    fn chain_state(&self) -> Result<Value, DaemonError> {
+        Ok(match &self.json_state {
+            DaemonStateFile::ReadOnly { path } => {
+                let j = crate::json_lock::read(path)?;
+                j[&self.chain_data.network_info.chain_name][&self.chain_data.chain_id].clone()
+            }
+            DaemonStateFile::FullAccess { json_file_state } => json_file_state
+                .lock()
+                .unwrap()
+                .get(
+                    &self.chain_data.network_info.chain_name,
+                    &self.chain_data.chain_id,
+                )
+                .clone(),
+        })
+    }
+
+    fn full_access(&self) -> Result<&Arc<Mutex<JsonLockedState>>, DaemonError> {
+        match &self.json_state {
+            DaemonStateFile::ReadOnly { path } => Err(DaemonError::StateReadOnly(path.clone())),
+            DaemonStateFile::FullAccess { json_file_state } => Ok(json_file_state),
+        }
+    }
+}
+
+/// Errors if `deployment_id` is one of the [`RESERVED_KEYS`] of the chain state, the deployment would overwrite that section
+pub(crate) fn check_deployment_id(deployment_id: &str) -> Result<(), DaemonError> {
+    if RESERVED_KEYS.contains(&deployment_id) {
+        return Err(DaemonError::ReservedDeploymentId(deployment_id.to_string()));
+    }
+    Ok(())
+}
+
+/// Locks the state file for this process, retrying according to `lock_options` while another process holds it.
+/// The global [`LOCKED_FILES`] mutex is released between two attempts, so waiting for this file doesn't block other states
+fn lock_state_file(
+    json_file_path: String,
+    lock_options: StateLockOptions,
+) -> Result<JsonLockedState, DaemonError> {
+    let start = Instant::now();
+    loop {
+        let mut locked_files = LOCKED_FILES.lock().unwrap();
+        if locked_files.contains(&json_file_path) {
+            return Err(DaemonError::StateAlreadyLocked(json_file_path));
+        }
+        // Single attempt, the retries happen without holding the global mutex
+        match JsonLockedState::try_new(&json_file_path, StateLockOptions::default()) {
+            Ok(json_file_state) => {
+                // Insert file to a locked files list and drop global mutex lock asap
+                locked_files.insert(json_file_path);
+                return Ok(json_file_state);
+            }
+            Err(DaemonError::StateLocked(_))
+                if lock_options
+                    .timeout
+                    .is_some_and(|timeout| start.elapsed() < timeout) =>
+            {
+                drop(locked_files);
+                log::debug!(
+                    target: &local_target(),
+                    "State file {json_file_path} is locked by another process, retrying"
+                );
+                std::thread::sleep(lock_options.retry_interval);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Updates a json file stored next to the state file (archive, checkpoints) while holding its lock,
+/// and writes it like the state file, with an atomic rename
+fn update_locked_file<T>(
+    path: &str,
+    update: impl FnOnce(&mut Value) -> T,
+) -> Result<T, DaemonError> {
+    let mut file = JsonLockedState::try_new(path, StateLockOptions::default())?;
+    let mut json = file.state();
+    let result = update(&mut json);
+    file.set_state(json);
+    file.try_write()?;
+    Ok(result)
+}
+
+/// `state.json` -> `state_archive.json`
+fn archive_path(state_path: &str) -> String {
+    let path = Path::new(state_path);
+    let name = path.file_stem().unwrap().to_str().unwrap();
+    path.with_file_name(format!("{name}_archive.json"))
+        .into_os_string()
+        .into_string()
+        .unwrap()
+}
+
+/// `state.json` -> `state_checkpoints.json`
+fn checkpoints_path(state_path: &str) -> String {
+    let path = Path::new(state_path);
+    let name = path.file_stem().unwrap().to_str().unwrap();
+    path.with_file_name(format!("{name}_checkpoints.json"))
+        .into_os_string()
+        .into_string()
+        .unwrap()
+}
+
+fn read_checkpoints(checkpoints_path: &String) -> Result<Value, DaemonError> {
+    if Path::new(checkpoints_path).exists() {
+        crate::json_lock::read(checkpoints_path)
+    } else {
+        Ok(json!({}))
+    }
 }
 
 impl StateInterface for DaemonState {
@@ -293,7 +688,7 @@ impl StateInterface for DaemonState {
     /// Get the locally-saved version of the contract's version on this network
     fn get_code_id(&self, contract_id: &str) -> Result<u64, CwEnvError> {
         let value = self
-            .get("code_ids")
+            .get(CODE_IDS_KEY)
             .ok()
             .and_then(|v| v.get(contract_id).cloned())
             .ok_or_else(|| CwEnvError::CodeIdNotInStore(contract_id.to_owned()))?
@@ -303,10 +698,10 @@ impl StateInterface for DaemonState {
 
     /// Set the locally-saved version of the contract's latest version on this network
     fn set_code_id(&mut self, contract_id: &str, code_id: u64) {
-        self.set("code_ids", contract_id, code_id).unwrap();
+        self.set(CODE_IDS_KEY, contract_id, code_id).unwrap();
     }
     fn remove_code_id(&mut self, contract_id: &str) {
-        self.remove("code_ids", contract_id).unwrap();
+        self.remove(CODE_IDS_KEY, contract_id).unwrap();
     }
 
     /// Get all addresses for deployment id from state file
@@ -322,7 +717,7 @@ impl StateInterface for DaemonState {
 
     fn get_all_code_ids(&self) -> Result<HashMap<String, u64>, CwEnvError> {
         let mut store = HashMap::new();
-        let code_ids = self.get("code_ids")?;
+        let code_ids = self.get(CODE_IDS_KEY)?;
         let value = code_ids.as_object().cloned().unwrap_or_default();
         for (id, code_id) in value {
             store.insert(id, code_id.as_u64().unwrap());
@@ -335,7 +730,80 @@ impl StateInterface for DaemonState {
 pub mod test {
     use std::env;
 
-    use crate::{env::STATE_FILE_ENV_NAME, DaemonState};
+    use crate::{address_book::ADDRESS_BOOK_KEY, env::STATE_FILE_ENV_NAME, networks, DaemonState};
+
+    #[test]
+    fn temporary_state_is_isolated_and_removed() -> anyhow::Result<()> {
+        let path = DaemonState::temporary_state_file_path();
+        assert_ne!(path, DaemonState::temporary_state_file_path());
+
+        let mut state = DaemonState::new(
+            path.clone(),
+            networks::JUNO_1.into(),
+            "default".to_string(),
+            false,
+            true,
+        )?;
+        state.remove_file_on_drop();
+        state.set("code_ids", "counter", 1)?;
+        assert!(std::path::Path::new(&path).exists());
+
+        let clone = state.clone();
+        drop(state);
+        assert!(std::path::Path::new(&path).exists());
+        drop(clone);
+        assert!(!std::path::Path::new(&path).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn reserved_deployment_ids_are_rejected() {
+        let state = DaemonState::new(
+            DaemonState::temporary_state_file_path(),
+            networks::JUNO_1.into(),
+            "pending_txs".to_string(),
+            false,
+            true,
+        );
+        assert!(matches!(
+            state,
+            Err(crate::DaemonError::ReservedDeploymentId(id)) if id == "pending_txs"
+        ));
+        assert!(matches!(
+            check_deployment_id(crate::airdrop::AIRDROP_KEY),
+            Err(crate::DaemonError::ReservedDeploymentId(_))
+        ));
+    }
+
+    #[test]
+    fn rollback_to_checkpoint() -> anyhow::Result<()> {
+        let path = DaemonState::temporary_state_file_path();
+        let mut state = DaemonState::new(
+            path,
+            networks::JUNO_1.into(),
+            "default".to_string(),
+            false,
+            true,
+        )?;
+        state.remove_file_on_drop();
+        state.set("code_ids", "counter", 1)?;
+        state.checkpoint("v1")?;
+
+        state.set("code_ids", "counter", 2)?;
+        state.set("default", "counter", "juno1counter")?;
+        state.set(ADDRESS_BOOK_KEY, "treasury", "juno1treasury")?;
+        state.rollback_to("v1")?;
+        assert_eq!(state.get("code_ids")?["counter"], 1);
+        assert!(state.get("default")?["counter"].is_null());
+        // Sections written after the checkpoint that aren't deployments are kept
+        assert_eq!(state.get(ADDRESS_BOOK_KEY)?["treasury"], "juno1treasury");
+
+        assert_eq!(state.checkpoints()?, vec!["v1"]);
+        assert!(state.rollback_to("v2").is_err());
+        assert!(state.remove_checkpoint("v1")?);
+        std::fs::remove_file(state.checkpoints_path()?)?;
+        Ok(())
+    }
 
     #[test]
     fn test_env_variable_state_path() -> anyhow::Result<()> {
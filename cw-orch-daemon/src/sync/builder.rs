@@ -1,12 +1,26 @@
 use crate::{
+    broadcast_backend::BroadcastBackend,
+    cache::QueryCache,
+    fees::{FeeToken, GasPriceOracle},
+    frontend_export::FrontendExport,
+    json_lock::{StateFileFormat, StateLockOptions},
+    keys::hd_path::HdPath,
+    query_only_sender::QueryOnlySender,
     sender::{Sender, SenderBuilder, SenderOptions},
+    source_verification::SourceVerification,
+    tx_broadcaster::BroadcastPolicy,
     DaemonAsyncBuilder,
 };
-use crate::{DaemonState, RUNTIME};
+use crate::{runtime::RuntimeHandle, DaemonState};
 use bitcoin::secp256k1::All;
-use cw_orch_core::environment::ChainInfoOwned;
+use cw_orch_core::environment::{ChainId, ChainInfoOwned, ChainKind};
+use std::time::Duration;
+use tokio::runtime::Handle;
 
-use super::{super::error::DaemonError, core::Daemon};
+use super::{
+    super::error::DaemonError,
+    core::{Daemon, DaemonBase},
+};
 
 #[derive(Clone, Default)]
 /// Create [`Daemon`] through [`DaemonBuilder`]
@@ -33,6 +47,16 @@ pub struct DaemonBuilder {
     /// State from rebuild or existing daemon
     pub(crate) state: Option<DaemonState>,
     pub(crate) write_on_change: Option<bool>,
+    pub(crate) read_only_state: bool,
+    pub(crate) test_state: bool,
+    pub(crate) state_lock_options: StateLockOptions,
+    pub(crate) state_file_format: Option<StateFileFormat>,
+    pub(crate) frontend_export: Option<FrontendExport>,
+    pub(crate) source_verification: Option<SourceVerification>,
+    pub(crate) query_cache: Option<QueryCache>,
+    pub(crate) ignore_pending_txs: bool,
+    pub(crate) expected_chain_kind: Option<ChainKind>,
+    pub(crate) expected_chain_id: Option<ChainId>,
 
     /* Sender Options */
     /// Wallet sender
@@ -79,6 +103,34 @@ impl DaemonBuilder {
         self
     }
 
+    /// Use the mnemonic stored under `name` in the OS keychain, see [`MnemonicKeyring`](crate::keyring::MnemonicKeyring)
+    #[cfg(feature = "keyring")]
+    pub fn keyring_entry(&mut self, name: impl ToString) -> &mut Self {
+        self.sender = Some(SenderBuilder::Keyring(name.to_string()));
+        self
+    }
+
+    /// Use an armored private key, as exported by `<chaind> keys export <name>`, decrypted with `passphrase`
+    pub fn armored_key(&mut self, armor: impl ToString, passphrase: impl ToString) -> &mut Self {
+        self.sender = Some(SenderBuilder::Armored {
+            armor: armor.to_string(),
+            passphrase: passphrase.to_string(),
+        });
+        self
+    }
+
+    /// Fail to build if the chain is not of this kind (e.g. only allow [`ChainKind::Mainnet`] in release scripts)
+    pub fn expect_chain_kind(&mut self, kind: ChainKind) -> &mut Self {
+        self.expected_chain_kind = Some(kind);
+        self
+    }
+
+    /// Fail to build if the chain doesn't have this chain id
+    pub fn expect_chain_id(&mut self, chain_id: impl Into<ChainId>) -> &mut Self {
+        self.expected_chain_id = Some(chain_id.into());
+        self
+    }
+
     /// Specifies a sender to use with this chain
     /// This will be used in priority when set on the builder
     pub fn sender(&mut self, wallet: Sender<All>) -> &mut Self {
@@ -104,6 +156,21 @@ impl DaemonBuilder {
         self
     }
 
+    /// Specifies the full derivation path of the daemon sender, see [`HdPath`]
+    /// Used to access funds held on nonstandard derivations of the chain
+    pub fn hd_path(&mut self, path: HdPath) -> &mut Self {
+        self.sender_options.set_hd_path(path);
+        self
+    }
+
+    /// Specifies the backend used to broadcast transactions, see [`BroadcastBackend`]
+    /// Queries still use the gRPC endpoints of the chain.
+    /// Defaults to broadcasting with gRPC
+    pub fn broadcaster(&mut self, broadcaster: impl Into<BroadcastBackend>) -> &mut Self {
+        self.sender_options.set_broadcaster(broadcaster);
+        self
+    }
+
     /// Overwrites the grpc_url used to interact with the chain
     pub fn grpc_url(&mut self, url: &str) -> &mut Self {
         self.overwrite_grpc_url = Some(url.to_string());
@@ -120,6 +187,39 @@ impl DaemonBuilder {
         self
     }
 
+    /// Tokens fees can be paid in, by order of preference, see [`FeeToken`]
+    /// Defaults to the gas denom and gas price of the chain
+    pub fn fee_tokens(&mut self, fee_tokens: Vec<FeeToken>) -> &mut Self {
+        self.sender_options.set_fee_tokens(fee_tokens);
+        self
+    }
+
+    /// Fetch the gas price before every transaction, to avoid failing transactions during fee spikes, see [`GasPriceOracle`]
+    pub fn gas_price_oracle(&mut self, oracle: GasPriceOracle) -> &mut Self {
+        self.sender_options.set_gas_price_oracle(oracle);
+        self
+    }
+
+    /// Specifies how failed transaction broadcasts are retried, see [`BroadcastPolicy`]
+    pub fn broadcast_policy(&mut self, policy: BroadcastPolicy) -> &mut Self {
+        self.sender_options.set_broadcast_policy(policy);
+        self
+    }
+
+    /// Keeps the account sequence locally, so transactions can be broadcasted without waiting for the previous ones, see [`crate::sequence`]
+    pub fn track_sequence(&mut self, track_sequence: bool) -> &mut Self {
+        self.sender_options.set_track_sequence(track_sequence);
+        self
+    }
+
+    /// Type url of the sender public key on chains with the ethereum coin type,
+    /// e.g. [`ETHERMINT_PUB_KEY_TYPE_URL`](crate::proto::injective::ETHERMINT_PUB_KEY_TYPE_URL).
+    /// Defaults to the Injective public key on Injective and to the ethermint one on other chains
+    pub fn eth_pub_key_type_url(&mut self, type_url: impl Into<String>) -> &mut Self {
+        self.sender_options.set_eth_pub_key_type_url(type_url);
+        self
+    }
+
     /// Reuse already existent [`DaemonState`]
     /// Useful for multi-chain scenarios
     pub fn state(&mut self, state: DaemonState) -> &mut Self {
@@ -136,6 +236,67 @@ impl DaemonBuilder {
         self
     }
 
+    /// Use a read-only state: the state file is not locked, so other processes can keep writing to it.
+    /// Useful for scripts that only query contracts while a deployment is running.
+    /// Writing to the state (e.g. uploading or instantiating contracts) returns [`DaemonError::StateReadOnly`].
+    /// Defaults to `false`
+    pub fn read_only_state(&mut self, read_only: bool) -> &mut Self {
+        self.read_only_state = read_only;
+        self
+    }
+
+    /// Use an isolated state file in the temp directory, deleted when the daemon is dropped.
+    /// Tests don't share state between them, whatever the `STATE_FILE` env variable.
+    /// Ignored when an existing state is passed with [`Self::state`]
+    pub fn test_state(&mut self) -> &mut Self {
+        self.test_state = true;
+        self
+    }
+
+    /// Wait up to `timeout` for other processes to release the state file instead of failing with [`DaemonError::StateLocked`]
+    pub fn state_lock_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.state_lock_options.timeout = Some(timeout);
+        self
+    }
+
+    /// Submit source verification metadata to an explorer or source registry after every mainnet upload, see [`SourceVerification`]
+    pub fn source_verification(&mut self, verification: SourceVerification) -> &mut Self {
+        self.source_verification = Some(verification);
+        self
+    }
+
+    /// Cache the responses to immutable queries (code checksums, code info, historical blocks), see [`QueryCache`]
+    pub fn query_cache(&mut self, cache: QueryCache) -> &mut Self {
+        self.query_cache = Some(cache);
+        self
+    }
+
+    /// Don't resume the confirmation of the transactions left pending by a previous run when building the daemon, see [`crate::receipts`].
+    /// Defaults to `false`
+    pub fn ignore_pending_txs(&mut self, ignore: bool) -> &mut Self {
+        self.ignore_pending_txs = ignore;
+        self
+    }
+
+    /// Specifies how to wait for other processes to release the state file, see [`StateLockOptions`]
+    pub fn state_lock_options(&mut self, options: StateLockOptions) -> &mut Self {
+        self.state_lock_options = options;
+        self
+    }
+
+    /// Specifies how the state file is written (pretty or compact json, atomic writes), see [`StateFileFormat`]
+    /// Defaults to pretty json written atomically
+    pub fn state_file_format(&mut self, format: StateFileFormat) -> &mut Self {
+        self.state_file_format = Some(format);
+        self
+    }
+
+    /// Export the deployments of the state to a JSON or TypeScript file every time a code id or address is stored, see [`FrontendExport`]
+    pub fn frontend_export(&mut self, export: FrontendExport) -> &mut Self {
+        self.frontend_export = Some(export);
+        self
+    }
+
     /// Specifies path to the daemon state file
     /// Defaults to env variable.
     ///
@@ -148,10 +309,30 @@ impl DaemonBuilder {
 
     /// Build a Daemon
     pub fn build(&self) -> Result<Daemon, DaemonError> {
-        let rt_handle = self
-            .handle
-            .clone()
-            .unwrap_or_else(|| RUNTIME.handle().clone());
+        let (rt_handle, builder) = self.async_builder()?;
+
+        // build the underlying daemon
+        let daemon = rt_handle.block_on(builder.build())?;
+
+        Ok(Daemon { rt_handle, daemon })
+    }
+
+    /// Build a daemon that can only query the chain, impersonating `address`.
+    /// No mnemonic or key is needed, which is useful for read-only scripts and state inspection
+    pub fn build_query_only(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<DaemonBase<QueryOnlySender>, DaemonError> {
+        let (rt_handle, builder) = self.async_builder()?;
+
+        let daemon = rt_handle.block_on(builder.build_query_only(address))?;
+
+        Ok(DaemonBase { rt_handle, daemon })
+    }
+
+    /// Runtime and async builder with the chain overrides applied
+    fn async_builder(&self) -> Result<(Handle, DaemonAsyncBuilder), DaemonError> {
+        let rt_handle = self.runtime_handle();
 
         let mut chain = self
             .chain
@@ -166,10 +347,7 @@ impl DaemonBuilder {
         let mut builder = self.clone();
         builder.chain = Some(chain);
 
-        // build the underlying daemon
-        let daemon = rt_handle.block_on(DaemonAsyncBuilder::from(builder).build())?;
-
-        Ok(Daemon { rt_handle, daemon })
+        Ok((rt_handle, DaemonAsyncBuilder::from(builder)))
     }
 }
 
@@ -1,16 +1,22 @@
 use std::fmt::Debug;
 
-use super::super::{sender::Wallet, DaemonAsync};
+use super::super::{
+    sender::{QuerySender, Wallet},
+    DaemonAsyncBase,
+};
 use crate::{
-    queriers::{Bank, CosmWasm, Node},
+    queriers::{Bank, CosmWasmBase, Node},
+    receipts::ResumedTx,
+    tx_builder::TxOptions,
     CosmTxResponse, DaemonBuilder, DaemonError, DaemonState,
 };
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Coin, Timestamp};
 use cw_orch_core::{
     contract::{interface_traits::Uploadable, WasmPath},
-    environment::{ChainState, DefaultQueriers, QueryHandler, TxHandler},
+    environment::{ChainState, ChainTime, DefaultQueriers, QueryHandler, SudoHandler, TxHandler},
+    CwEnvError,
 };
-use cw_orch_traits::stargate::Stargate;
+use cw_orch_traits::stargate::{Stargate, StargateQuerier};
 use serde::Serialize;
 use tokio::runtime::Handle;
 use tonic::transport::Channel;
@@ -41,23 +47,41 @@ use tonic::transport::Channel;
     Different Cosmos SDK modules can be queried through the daemon by calling the [`Daemon.query_client<Querier>`] method with a specific querier.
     See [Querier](crate::queriers) for examples.
 */
-pub struct Daemon {
-    pub daemon: DaemonAsync,
+pub struct DaemonBase<S: QuerySender = Wallet> {
+    pub daemon: DaemonAsyncBase<S>,
     /// Runtime handle to execute async tasks
     pub rt_handle: Handle,
 }
 
+/// [`DaemonBase`] that signs and broadcasts transactions with a [`Wallet`]
+pub type Daemon = DaemonBase<Wallet>;
+
 impl Daemon {
     /// Get the daemon builder
     pub fn builder() -> DaemonBuilder {
         DaemonBuilder::default()
     }
+}
 
+impl<S: QuerySender> DaemonBase<S> {
     /// Get the channel configured for this Daemon
     pub fn channel(&self) -> Channel {
-        self.daemon.sender.grpc_channel.clone()
+        self.daemon.sender.grpc_channel()
+    }
+
+    /// Flushes all the state related to the current chain
+    /// Only works on Local networks
+    pub fn flush_state(&mut self) -> Result<(), DaemonError> {
+        self.daemon.flush_state()
     }
 
+    /// Confirms the transactions that were broadcasted but not confirmed by a previous run, see [`crate::receipts`]
+    pub fn resume_pending_txs(&self) -> Result<Vec<ResumedTx>, DaemonError> {
+        self.rt_handle.block_on(self.daemon.resume_pending_txs())
+    }
+}
+
+impl Daemon {
     /// Get the channel configured for this Daemon
     pub fn wallet(&self) -> Wallet {
         self.daemon.sender.clone()
@@ -68,6 +92,8 @@ impl Daemon {
     pub fn rebuild(&self) -> DaemonBuilder {
         let mut builder = DaemonBuilder {
             state: Some(self.state()),
+            source_verification: self.daemon.source_verification.clone(),
+            query_cache: self.daemon.query_cache.clone(),
             ..Default::default()
         };
         builder
@@ -76,14 +102,29 @@ impl Daemon {
         builder
     }
 
-    /// Flushes all the state related to the current chain
-    /// Only works on Local networks
-    pub fn flush_state(&mut self) -> Result<(), DaemonError> {
-        self.daemon.flush_state()
+    /// Execute a message on a contract with a custom memo and timeout height.
+    pub fn execute_with_options<E: Serialize>(
+        &self,
+        exec_msg: &E,
+        coins: &[cosmwasm_std::Coin],
+        contract_address: &Addr,
+        options: &TxOptions,
+    ) -> Result<CosmTxResponse, DaemonError> {
+        self.rt_handle.block_on(self.daemon.execute_with_options(
+            exec_msg,
+            coins,
+            contract_address,
+            options,
+        ))
+    }
+
+    /// Upload a wasm file to the chain.
+    pub fn upload_wasm(&self, wasm_path: &WasmPath) -> Result<CosmTxResponse, DaemonError> {
+        self.rt_handle.block_on(self.daemon.upload_wasm(wasm_path))
     }
 }
 
-impl ChainState for Daemon {
+impl<S: QuerySender> ChainState for DaemonBase<S> {
     type Out = DaemonState;
 
     fn state(&self) -> Self::Out {
@@ -110,6 +151,10 @@ impl TxHandler for Daemon {
         self.rt_handle.block_on(self.daemon.upload(uploadable))
     }
 
+    fn upload_from_bytes(&self, code: Vec<u8>) -> Result<Self::Response, DaemonError> {
+        self.rt_handle.block_on(self.daemon.upload_from_bytes(code))
+    }
+
     fn execute<E: Serialize>(
         &self,
         exec_msg: &E,
@@ -162,6 +207,18 @@ impl TxHandler for Daemon {
     }
 }
 
+/// Only the chain can call the sudo entry point of a contract.
+/// On live chains, this is done through a governance proposal containing a `MsgSudoContract`.
+impl SudoHandler for Daemon {
+    fn sudo<M: Serialize + Debug>(
+        &self,
+        _sudo_msg: &M,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, DaemonError> {
+        Err(DaemonError::SudoNotSupported(contract_address.to_string()))
+    }
+}
+
 impl Stargate for Daemon {
     fn commit_any<R>(
         &self,
@@ -182,7 +239,18 @@ impl Stargate for Daemon {
     }
 }
 
-impl QueryHandler for Daemon {
+impl<S: QuerySender> StargateQuerier for DaemonBase<S> {
+    fn raw_module_query<Req, Res>(&self, path: &str, request: &Req) -> Result<Res, DaemonError>
+    where
+        Req: prost::Message + Clone + Send + Sync + 'static,
+        Res: prost::Message + Default + Send + Sync + 'static,
+    {
+        self.rt_handle
+            .block_on(self.daemon.raw_module_query(path, request))
+    }
+}
+
+impl<S: QuerySender> QueryHandler for DaemonBase<S> {
     type Error = DaemonError;
 
     fn wait_blocks(&self, amount: u64) -> Result<(), DaemonError> {
@@ -204,8 +272,33 @@ impl QueryHandler for Daemon {
     }
 }
 
-impl DefaultQueriers for Daemon {
+/// The time of a live chain can't be changed, use [`QueryHandler::wait_blocks`] to wait for the next blocks
+impl<S: QuerySender> ChainTime for DaemonBase<S> {
+    type Error = DaemonError;
+
+    fn advance_blocks(&self, _blocks: u64) -> Result<(), DaemonError> {
+        Err(time_travel_unsupported())
+    }
+
+    fn advance_seconds(&self, _seconds: u64) -> Result<(), DaemonError> {
+        Err(time_travel_unsupported())
+    }
+
+    fn set_block_time(&self, _time: Timestamp) -> Result<(), DaemonError> {
+        Err(time_travel_unsupported())
+    }
+}
+
+fn time_travel_unsupported() -> DaemonError {
+    CwEnvError::TimeTravelUnsupported(
+        "the Daemon is connected to a live chain, wait for blocks with `wait_blocks` instead"
+            .to_string(),
+    )
+    .into()
+}
+
+impl<S: QuerySender> DefaultQueriers for DaemonBase<S> {
     type Bank = Bank;
-    type Wasm = CosmWasm;
+    type Wasm = CosmWasmBase<S>;
     type Node = Node;
 }
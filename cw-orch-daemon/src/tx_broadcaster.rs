@@ -1,8 +1,17 @@
+use std::{borrow::Cow, time::Duration};
+
 use bitcoin::secp256k1::All;
 use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
 use cw_orch_core::log::transaction_target;
+use tonic::Code;
+
+use crate::{
+    queriers::Node, sender::Sender, sequence::parse_expected_sequence, CosmTxResponse, DaemonError,
+    GrpcChannel, TxBuilder,
+};
 
-use crate::{queriers::Node, sender::Sender, CosmTxResponse, DaemonError, TxBuilder};
+/// Delay used when the block time of the chain can't be queried
+const FALLBACK_RETRY_DELAY: Duration = Duration::from_secs(1);
 
 pub type StrategyAction =
     fn(&mut TxBuilder, &Result<TxResponse, DaemonError>) -> Result<(), DaemonError>;
@@ -47,14 +56,154 @@ impl RetryStrategy {
 #[derive(Default)]
 pub struct TxBroadcaster {
     strategies: Vec<RetryStrategy>,
+    backoff: Backoff,
+    transient_retries: u64,
+    retriable_errors: Vec<String>,
+    rotate_grpc: bool,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum BroadcastRetry {
     Infinite,
     Finite(u64),
 }
 
+/// Delay between two broadcast attempts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Backoff {
+    /// Wait for the average block time of the chain
+    #[default]
+    BlockTime,
+    /// Wait a fixed duration
+    Fixed(Duration),
+    /// Wait `initial * factor^attempt`, capped at `max`
+    Exponential {
+        initial: Duration,
+        factor: f64,
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// Delay before the retry number `attempt` (starting at 0), `block_time` is only used for [`Backoff::BlockTime`]
+    pub fn delay(&self, attempt: u32, block_time: Duration) -> Duration {
+        match self {
+            Backoff::BlockTime => block_time,
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => initial.mul_f64(factor.powi(attempt as i32)).min(*max),
+        }
+    }
+}
+
+/// Retry policy of the transaction broadcasting, configurable per daemon.
+///
+/// By default, account sequence errors are retried indefinitely and insufficient fee errors once, waiting a block between attempts.
+/// Transient errors (unavailable endpoints, timeouts, ...) are not retried unless [`BroadcastPolicy::max_retries`] is set.
+/// ```
+/// use std::time::Duration;
+/// use cw_orch_daemon::tx_broadcaster::{Backoff, BroadcastPolicy};
+///
+/// let policy = BroadcastPolicy::default()
+///     .max_retries(5)
+///     .backoff(Backoff::Exponential {
+///         initial: Duration::from_millis(500),
+///         factor: 2.0,
+///         max: Duration::from_secs(10),
+///     })
+///     .retry_on("rate limit exceeded")
+///     .rotate_grpc(true);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct BroadcastPolicy {
+    /// Retries of account sequence errors
+    pub sequence_retries: BroadcastRetry,
+    /// Retries of insufficient fee errors, the fee suggested by the node is used for the retry
+    pub insufficient_fee_retries: u64,
+    /// Retries of transient errors (unavailable endpoints, timeouts and [`BroadcastPolicy::retriable_errors`])
+    pub max_retries: u64,
+    /// Additional errors considered transient, matched against the error message
+    pub retriable_errors: Vec<String>,
+    /// Delay between two attempts
+    pub backoff: Backoff,
+    /// Switch to the next gRPC endpoint of the chain when retrying a transient error
+    pub rotate_grpc: bool,
+}
+
+impl Default for BroadcastPolicy {
+    fn default() -> Self {
+        Self {
+            sequence_retries: BroadcastRetry::Infinite,
+            insufficient_fee_retries: 1,
+            max_retries: 0,
+            retriable_errors: vec![],
+            backoff: Backoff::default(),
+            rotate_grpc: false,
+        }
+    }
+}
+
+impl BroadcastPolicy {
+    /// Sets the retries of account sequence errors
+    pub fn sequence_retries(mut self, retries: BroadcastRetry) -> Self {
+        self.sequence_retries = retries;
+        self
+    }
+
+    /// Sets the retries of insufficient fee errors
+    pub fn insufficient_fee_retries(mut self, retries: u64) -> Self {
+        self.insufficient_fee_retries = retries;
+        self
+    }
+
+    /// Sets the retries of transient errors
+    pub fn max_retries(mut self, retries: u64) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Retries errors whose message contains `pattern`, as transient errors
+    pub fn retry_on(mut self, pattern: impl Into<String>) -> Self {
+        self.retriable_errors.push(pattern.into());
+        self
+    }
+
+    /// Sets the delay between two attempts
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Switch to the next gRPC endpoint of the chain when retrying a transient error
+    pub fn rotate_grpc(mut self, rotate: bool) -> Self {
+        self.rotate_grpc = rotate;
+        self
+    }
+}
+
 impl TxBroadcaster {
+    /// Broadcaster applying the policy, with the account sequence and insufficient fee strategies
+    pub fn from_policy(policy: &BroadcastPolicy) -> Self {
+        let mut insufficient_fee = insufficient_fee_strategy();
+        insufficient_fee.max_retries = BroadcastRetry::Finite(policy.insufficient_fee_retries);
+        let mut account_sequence = account_sequence_strategy();
+        account_sequence.max_retries = policy.sequence_retries.clone();
+
+        Self {
+            strategies: vec![],
+            backoff: policy.backoff.clone(),
+            transient_retries: policy.max_retries,
+            retriable_errors: policy.retriable_errors.clone(),
+            rotate_grpc: policy.rotate_grpc,
+        }
+        .add_strategy(insufficient_fee)
+        .add_strategy(account_sequence)
+    }
+
     /// Adds a retry strategy to the broadcaster
     /// Order of strategy addition matters, strategy conditions are tested in order of addition.
     /// Each time a transaction is retried, only the first retry strategy met is applied
@@ -70,45 +219,113 @@ impl TxBroadcaster {
         mut tx_builder: TxBuilder,
         wallet: &Sender<All>,
     ) -> Result<TxResponse, DaemonError> {
-        let mut tx_retry = true;
+        // The wallet is replaced by a wallet connected to another endpoint when rotating gRPC endpoints
+        let mut wallet = Cow::Borrowed(wallet);
+        let mut grpc_index = 0;
+        let mut transient_retries = 0;
+        let mut attempt = 0;
 
         // We try and broadcast once
-        let mut tx_response = broadcast_helper(&mut tx_builder, wallet).await;
+        let mut tx_response = broadcast_helper(&mut tx_builder, &wallet).await;
         log::info!(
             target: &transaction_target(),
             "Awaiting TX inclusion in block..."
         );
-        while tx_retry {
-            tx_retry = false;
-
+        loop {
             // On error, we verify it matches the retry strategies. If it does, we update the retry counts and start over
-            for s in self.strategies.iter_mut() {
-                if strategy_condition_met(s, &tx_response) && can_retry(s) {
+            let reason = match self
+                .strategies
+                .iter_mut()
+                .find(|s| strategy_condition_met(s, &tx_response) && can_retry(s))
+            {
+                Some(s) => {
                     // We update the tx and resubmit
                     if let Some(action) = s.action {
                         action(&mut tx_builder, &tx_response)?;
                     }
-                    tx_retry = true;
-
-                    // We still await for the next block, to avoid spamming retry when an error occurs
-                    let block_speed = Node::new_async(wallet.channel())
-                        ._average_block_speed(None)
-                        .await?;
-                    log::warn!(
-                        target: &transaction_target(),
-                        "Retrying broadcasting TX in {:?} milliseconds because of {}",
-                        block_speed.as_millis(),
-                        s.reason
-                    );
-                    tokio::time::sleep(block_speed).await;
-
-                    tx_response = broadcast_helper(&mut tx_builder, wallet).await;
-                    continue;
+                    s.reason.clone()
+                }
+                None if transient_retries < self.transient_retries
+                    && self.is_transient(&tx_response) =>
+                {
+                    transient_retries += 1;
+                    if self.rotate_grpc {
+                        if let Some(rotated) = rotate_grpc(&wallet, &mut grpc_index).await {
+                            wallet = Cow::Owned(rotated);
+                        }
+                    }
+                    format!("a transient error: {}", tx_response.as_ref().unwrap_err())
                 }
+                None => break,
+            };
+            #[cfg(feature = "metrics")]
+            if let Some(recorder) = crate::metrics::recorder() {
+                recorder.tx_retried(&wallet.chain_info.chain_id, &reason);
             }
+
+            // We still wait before retrying, to avoid spamming retry when an error occurs
+            let block_speed = Node::new_async(wallet.channel())
+                ._average_block_speed(None)
+                .await
+                .unwrap_or(FALLBACK_RETRY_DELAY);
+            let delay = self.backoff.delay(attempt, block_speed);
+            attempt += 1;
+            log::warn!(
+                target: &transaction_target(),
+                "Retrying broadcasting TX in {:?} milliseconds because of {}",
+                delay.as_millis(),
+                reason
+            );
+            tokio::time::sleep(delay).await;
+
+            tx_response = broadcast_helper(&mut tx_builder, &wallet).await;
         }
         tx_response
     }
+
+    /// Returns true if the broadcast failed because of a transient error
+    fn is_transient(&self, tx_response: &Result<TxResponse, DaemonError>) -> bool {
+        let Err(error) = tx_response else {
+            return false;
+        };
+        let transport_error = match error {
+            DaemonError::TransportError(_) => true,
+            DaemonError::Status(status) => matches!(
+                status.code(),
+                Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted
+            ),
+            DaemonError::ReqwestError(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        };
+        let message = error.to_string();
+        transport_error
+            || self
+                .retriable_errors
+                .iter()
+                .any(|pattern| message.contains(pattern))
+    }
+}
+
+/// Connects to the next gRPC endpoint of the chain that is available.
+/// Returns `None` if there is no other endpoint to connect to
+async fn rotate_grpc(wallet: &Sender<All>, grpc_index: &mut usize) -> Option<Sender<All>> {
+    let urls = &wallet.chain_info.grpc_urls;
+    for _ in 1..urls.len() {
+        *grpc_index = (*grpc_index + 1) % urls.len();
+        let url = &urls[*grpc_index];
+        match GrpcChannel::connect(&[url.clone()], &wallet.chain_info.chain_id).await {
+            Ok(channel) => {
+                log::warn!(target: &transaction_target(), "Switching to gRPC endpoint {url}");
+                let mut rotated = wallet.clone();
+                rotated.grpc_channel = channel;
+                return Some(rotated);
+            }
+            Err(e) => {
+                log::warn!(target: &transaction_target(), "gRPC endpoint {url} unavailable: {e}")
+            }
+        }
+    }
+    None
 }
 
 fn strategy_condition_met(
@@ -125,11 +342,25 @@ async fn broadcast_helper(
     tx_builder: &mut TxBuilder,
     wallet: &Sender<All>,
 ) -> Result<TxResponse, DaemonError> {
-    let tx = tx_builder.build(wallet).await?;
-    let tx_response = wallet.broadcast_tx(tx).await?;
-    log::debug!(target: &transaction_target(), "TX broadcast response: {:?}", tx_response);
+    let tx_response = async {
+        let tx = tx_builder.build(wallet).await?;
+        let tx_response = wallet.broadcast_tx(tx).await?;
+        log::debug!(target: &transaction_target(), "TX broadcast response: {:?}", tx_response);
 
-    assert_broadcast_code_response(tx_response)
+        assert_broadcast_code_response(tx_response)
+    }
+    .await;
+
+    // The reserved sequence is not consumed when the tx is rejected
+    if let (Err(e), Some(sequence)) = (&tx_response, tx_builder.reserved_sequence.take()) {
+        let error = e.to_string();
+        if has_account_sequence_error(&error) {
+            wallet.sequence.resync(parse_expected_sequence(&error));
+        } else {
+            wallet.sequence.release(sequence);
+        }
+    }
+    tx_response
 }
 
 /// Tx Responses with a non 0 code, should also error with the raw loq
@@ -259,6 +490,20 @@ pub fn account_sequence_strategy() -> RetryStrategy {
 mod tests {
     use super::*;
 
+    #[test]
+    fn backoff_delays() {
+        let block_time = Duration::from_secs(6);
+        assert_eq!(Backoff::BlockTime.delay(3, block_time), block_time);
+        let exponential = Backoff::Exponential {
+            initial: Duration::from_millis(500),
+            factor: 2.0,
+            max: Duration::from_secs(3),
+        };
+        assert_eq!(exponential.delay(0, block_time), Duration::from_millis(500));
+        assert_eq!(exponential.delay(2, block_time), Duration::from_secs(2));
+        assert_eq!(exponential.delay(5, block_time), Duration::from_secs(3));
+    }
+
     #[test]
     fn test_parse_suggested_fee() {
         let log = "insufficient fees; got: 14867ujuno required: 17771ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9,444255ujuno: insufficient fee";
@@ -1,4 +1,7 @@
-use std::str::FromStr;
+use std::{
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
 
 use bitcoin::secp256k1::All;
 use cosmrs::tx::{ModeInfo, SignMode};
@@ -10,11 +13,66 @@ use cosmrs::{
     Any, Coin,
 };
 use cw_orch_core::log::transaction_target;
+use prost::Message;
 
-use crate::sender::SenderOptions;
+use crate::{
+    log::{TxEvent, TxStage},
+    proto::tx::TxBody,
+    sender::SenderOptions,
+};
 
 use super::{sender::Sender, DaemonError};
 
+/// Number of blocks after which a transaction times out by default
+pub const DEFAULT_TIMEOUT_BLOCKS: u64 = 10;
+
+/// Time after which an unordered transaction times out by default.
+/// Chains reject unordered transactions with a timeout further than 10 minutes by default
+pub const DEFAULT_UNORDERED_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Per-transaction options, see [`ExecuteWithOptions`](crate::execute_with::ExecuteWithOptions)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TxOptions {
+    /// Memo of the transaction, defaults to the cw-orchestrator memo
+    pub memo: Option<String>,
+    /// Block height after which the transaction is not included anymore.
+    /// Defaults to [`DEFAULT_TIMEOUT_BLOCKS`] blocks after the current height for ordered transactions
+    pub timeout_height: Option<u64>,
+    /// Send the transaction unordered (cosmos-sdk 0.53+): it doesn't use the account sequence,
+    /// so it can't fail with an account sequence mismatch when broadcasting transactions in parallel
+    pub unordered: bool,
+    /// Block time after which the transaction is not included anymore (cosmos-sdk 0.53+).
+    /// Defaults to [`DEFAULT_UNORDERED_TIMEOUT`] from now for unordered transactions
+    pub timeout_timestamp: Option<SystemTime>,
+}
+
+impl TxOptions {
+    /// Sets the memo of the transaction
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Sets the block height after which the transaction is not included anymore
+    pub fn timeout_height(mut self, timeout_height: u64) -> Self {
+        self.timeout_height = Some(timeout_height);
+        self
+    }
+
+    /// Sends the transaction unordered, only supported by chains running cosmos-sdk 0.53+
+    pub fn unordered(mut self) -> Self {
+        self.unordered = true;
+        self
+    }
+
+    /// Sets the block time after which the transaction is not included anymore
+    pub fn timeout_timestamp(mut self, timeout_timestamp: SystemTime) -> Self {
+        self.timeout_timestamp = Some(timeout_timestamp);
+        self
+    }
+}
+
 /// Struct used to build a raw transaction and broadcast it with a sender.
 #[derive(Clone, Debug)]
 pub struct TxBuilder {
@@ -25,6 +83,13 @@ pub struct TxBuilder {
     pub(crate) gas_limit: Option<u64>,
     // if defined, use this sequence, else get it from the node
     pub(crate) sequence: Option<SequenceNumber>,
+    // denom of the fee, selected when computing the fee
+    pub(crate) fee_denom: Option<String>,
+    // sequence reserved from the sender's sequence tracker, reused when the tx is rebuilt
+    pub(crate) reserved_sequence: Option<SequenceNumber>,
+    // unordered tx, the sequence is not used
+    pub(crate) unordered: bool,
+    pub(crate) timeout_timestamp: Option<SystemTime>,
 }
 
 impl TxBuilder {
@@ -35,13 +100,46 @@ impl TxBuilder {
             fee_amount: None,
             gas_limit: None,
             sequence: None,
+            fee_denom: None,
+            reserved_sequence: None,
+            unordered: false,
+            timeout_timestamp: None,
         }
     }
+
+    /// Create a new TxBuilder for the messages, with the given options.
+    /// `current_height` is used to compute the default timeout height of ordered transactions
+    pub fn new_with_options(msgs: Vec<Any>, options: &TxOptions, current_height: u64) -> Self {
+        if !options.unordered {
+            let mut builder =
+                Self::new(Self::build_body_with_options(msgs, options, current_height));
+            builder.timeout_timestamp = options.timeout_timestamp;
+            return builder;
+        }
+        // Unordered txs are bounded by their timeout timestamp
+        let body = Self::build_body(
+            msgs,
+            options.memo.as_deref(),
+            options.timeout_height.unwrap_or(0),
+        );
+        let mut builder = Self::new(body);
+        builder.unordered(true).timeout_timestamp(
+            options
+                .timeout_timestamp
+                .unwrap_or_else(|| SystemTime::now() + DEFAULT_UNORDERED_TIMEOUT),
+        );
+        builder
+    }
     /// Set a fixed fee amount for the tx
     pub fn fee_amount(&mut self, fee_amount: u128) -> &mut Self {
         self.fee_amount = Some(fee_amount);
         self
     }
+    /// Set the denom of the fee for the tx
+    pub fn fee_denom(&mut self, fee_denom: impl Into<String>) -> &mut Self {
+        self.fee_denom = Some(fee_denom.into());
+        self
+    }
     /// Set a gas limit for the tx
     pub fn gas_limit(&mut self, gas_limit: u64) -> &mut Self {
         self.gas_limit = Some(gas_limit);
@@ -52,6 +150,42 @@ impl TxBuilder {
         self.sequence = Some(sequence);
         self
     }
+    /// Send the tx unordered, it needs a timeout timestamp
+    pub fn unordered(&mut self, unordered: bool) -> &mut Self {
+        self.unordered = unordered;
+        self
+    }
+    /// Set a timeout timestamp for the tx
+    pub fn timeout_timestamp(&mut self, timeout_timestamp: SystemTime) -> &mut Self {
+        self.timeout_timestamp = Some(timeout_timestamp);
+        self
+    }
+
+    /// Encoded body of the tx, including the unordered fields if set
+    pub fn body_bytes(&self) -> Result<Vec<u8>, DaemonError> {
+        if !self.unordered && self.timeout_timestamp.is_none() {
+            return Ok(self.body.clone().into_bytes()?);
+        }
+        let body = TxBody {
+            messages: self.body.messages.clone(),
+            memo: self.body.memo.clone(),
+            timeout_height: self.body.timeout_height.value(),
+            unordered: self.unordered,
+            timeout_timestamp: self.timeout_timestamp.map(Into::into),
+            extension_options: self.body.extension_options.clone(),
+            non_critical_extension_options: self.body.non_critical_extension_options.clone(),
+        };
+        Ok(body.encode_to_vec())
+    }
+
+    /// Sequence used to sign the tx, when it's not tracked by the sender.
+    /// Unordered txs don't use the sequence of the account
+    fn fixed_sequence(&self) -> Option<SequenceNumber> {
+        if self.unordered {
+            return Some(0);
+        }
+        self.sequence
+    }
 
     /// Builds the body of the tx with a given memo and timeout.
     pub fn build_body(msgs: Vec<Any>, memo: Option<&str>, timeout: u64) -> tx::Body {
@@ -62,6 +196,22 @@ impl TxBuilder {
         )
     }
 
+    /// Builds the body of the tx with the given options.
+    /// `current_height` is used to compute the default timeout height
+    pub fn build_body_with_options(
+        msgs: Vec<Any>,
+        options: &TxOptions,
+        current_height: u64,
+    ) -> tx::Body {
+        Self::build_body(
+            msgs,
+            options.memo.as_deref(),
+            options
+                .timeout_height
+                .unwrap_or(current_height + DEFAULT_TIMEOUT_BLOCKS),
+        )
+    }
+
     pub(crate) fn build_fee(
         amount: impl Into<u128>,
         denom: &str,
@@ -87,10 +237,18 @@ impl TxBuilder {
         } = wallet.base_account().await?;
 
         // overwrite sequence if set (can be used for concurrent txs)
-        let sequence = self.sequence.unwrap_or(sequence);
+        let sequence = self
+            .fixed_sequence()
+            .or(self.reserved_sequence)
+            .unwrap_or(sequence);
 
         wallet
-            .calculate_gas(&self.body, sequence, account_number)
+            .estimate_gas_for_body(
+                &self.body.messages,
+                self.body_bytes()?,
+                sequence,
+                account_number,
+            )
             .await
     }
 
@@ -105,10 +263,16 @@ impl TxBuilder {
         } = wallet.base_account().await?;
 
         // overwrite sequence if set (can be used for concurrent txs)
-        let sequence = self.sequence.unwrap_or(sequence);
+        let sequence = match self.fixed_sequence() {
+            Some(sequence) => sequence,
+            None if wallet.options.track_sequence => *self
+                .reserved_sequence
+                .get_or_insert_with(|| wallet.sequence.reserve(sequence)),
+            None => sequence,
+        };
 
         //
-        let (tx_fee, gas_limit) = if let (Some(fee), Some(gas_limit)) =
+        let (tx_fee, fee_denom, gas_limit) = if let (Some(fee), Some(gas_limit)) =
             (self.fee_amount, self.gas_limit)
         {
             log::debug!(
@@ -117,29 +281,44 @@ impl TxBuilder {
                 fee,
                 gas_limit
             );
-            (fee, gas_limit)
+            let fee_denom = self
+                .fee_denom
+                .clone()
+                .unwrap_or_else(|| wallet.get_fee_token());
+            (fee, fee_denom, gas_limit)
         } else {
             let sim_gas_used = wallet
-                .calculate_gas(&self.body, sequence, account_number)
+                .estimate_gas_for_body(
+                    &self.body.messages,
+                    self.body_bytes()?,
+                    sequence,
+                    account_number,
+                )
                 .await?;
             log::debug!(target: &transaction_target(), "Simulated gas needed {:?}", sim_gas_used);
 
-            let (gas_expected, fee_amount) = wallet.get_fee_from_gas(sim_gas_used)?;
+            let (gas_expected, fee) = wallet.fee_for_gas(sim_gas_used).await?;
 
-            log::debug!(target: &transaction_target(), "Calculated fee needed: {:?}", fee_amount);
-            // set the gas limit of self for future txs
+            log::debug!(target: &transaction_target(), "Calculated fee needed: {:?}", fee);
+            TxEvent::new(
+                wallet.chain_info.chain_id.clone(),
+                wallet.pub_addr_str()?,
+                TxStage::Simulated {
+                    gas_used: sim_gas_used,
+                    gas_limit: gas_expected,
+                    fee: fee.to_string(),
+                },
+            )
+            .log();
+            // set the gas limit and fee denom of self for future txs
             // there's no way to change the tx_builder body so simulation gas should remain the same as well
             self.gas_limit = Some(gas_expected);
+            self.fee_denom = Some(fee.denom.clone());
 
-            (fee_amount, gas_expected)
+            (fee.amount.u128(), fee.denom, gas_expected)
         };
 
-        let fee = Self::build_fee(
-            tx_fee,
-            &wallet.get_fee_token(),
-            gas_limit,
-            wallet.options.clone(),
-        )?;
+        let fee = Self::build_fee(tx_fee, &fee_denom, gas_limit, wallet.options.clone())?;
 
         log::debug!(
             target: &transaction_target(),
@@ -150,18 +329,51 @@ impl TxBuilder {
         );
 
         let auth_info = SignerInfo {
-            public_key: wallet.private_key.get_signer_public_key(&wallet.secp),
+            public_key: wallet.signer_public_key(),
             mode_info: ModeInfo::single(SignMode::Direct),
             sequence,
         }
         .auth_info(fee);
 
-        let sign_doc = SignDoc::new(
-            &self.body,
-            &auth_info,
-            &Id::try_from(wallet.chain_info.chain_id.to_string())?,
+        let sign_doc = SignDoc {
+            body_bytes: self.body_bytes()?,
+            auth_info_bytes: auth_info.into_bytes()?,
+            chain_id: Id::try_from(wallet.chain_info.chain_id.to_string())?.to_string(),
             account_number,
-        )?;
+        };
         wallet.sign(sign_doc).map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unordered_body_encoding() {
+        let options = TxOptions::default()
+            .memo("memo")
+            .unordered()
+            .timeout_timestamp(SystemTime::UNIX_EPOCH + Duration::from_secs(100));
+        let builder = TxBuilder::new_with_options(vec![], &options, 10);
+        assert!(builder.unordered);
+        assert_eq!(builder.fixed_sequence(), Some(0));
+
+        let body = TxBody::decode(builder.body_bytes().unwrap().as_slice()).unwrap();
+        assert!(body.unordered);
+        assert_eq!(body.memo, "memo");
+        assert_eq!(body.timeout_height, 0);
+        assert_eq!(body.timeout_timestamp.unwrap().seconds, 100);
+    }
+
+    #[test]
+    fn ordered_body_encoding_unchanged() {
+        let builder = TxBuilder::new_with_options(vec![], &TxOptions::default(), 10);
+        assert_eq!(
+            builder.body_bytes().unwrap(),
+            TxBuilder::build_body(vec![], None, 10 + DEFAULT_TIMEOUT_BLOCKS)
+                .into_bytes()
+                .unwrap()
+        );
+    }
+}
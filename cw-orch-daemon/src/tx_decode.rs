@@ -0,0 +1,311 @@
+//! Decoding of the messages of a transaction from its events.
+//!
+//! The events of each message are mapped to a [`DecodedMsg`] describing what the message did,
+//! with the values that are usually looked up manually in the events (code id of a stored code, address of an instantiated contract, ...).
+//! ```no_run
+//! # use cw_orch_daemon::CosmTxResponse;
+//! use cw_orch_daemon::tx_decode::DecodedMsg;
+//! # let response = CosmTxResponse::default();
+//! for msg in response.decode_msgs() {
+//!     if let DecodedMsg::StoreCode { code_id: Some(code_id), .. } = msg {
+//!         println!("Stored code {code_id}");
+//!     }
+//! }
+//! ```
+
+use std::fmt::Display;
+
+use cosmwasm_std::Event;
+
+use crate::tx_resp::MSG_INDEX_ATTR;
+
+/// Message of a transaction, decoded from its events
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodedMsg {
+    /// Execution of a contract
+    WasmExecute {
+        sender: Option<String>,
+        contract: Option<String>,
+    },
+    /// Instantiation of a contract
+    Instantiate {
+        sender: Option<String>,
+        code_id: Option<u64>,
+        address: Option<String>,
+    },
+    /// Upload of a code
+    StoreCode {
+        sender: Option<String>,
+        code_id: Option<u64>,
+    },
+    /// Migration of a contract
+    Migrate {
+        sender: Option<String>,
+        contract: Option<String>,
+        code_id: Option<u64>,
+    },
+    /// Bank transfer
+    Transfer {
+        from: Option<String>,
+        to: Option<String>,
+        /// Transferred coins, e.g. `100ujuno,5uatom`
+        amount: Option<String>,
+    },
+    /// ICS20 transfer to another chain
+    IbcTransfer {
+        sender: Option<String>,
+        receiver: Option<String>,
+        channel: Option<String>,
+    },
+    /// Message that isn't decoded, identified by its action (usually the type url of the message)
+    Other { action: String },
+}
+
+impl DecodedMsg {
+    /// Decodes the message that emitted `events`
+    pub fn from_msg_events(events: &[Event]) -> Self {
+        let attr = |event_type: &str, key: &str| {
+            events
+                .iter()
+                .filter(|event| event.ty == event_type)
+                .flat_map(|event| &event.attributes)
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+        };
+        let code_id = |event_type: &str| attr(event_type, "code_id").and_then(|id| id.parse().ok());
+
+        let action = attr("message", "action").unwrap_or_default();
+        let sender = attr("message", "sender");
+        // Type urls since cosmos-sdk 0.46, legacy message names before
+        match action.rsplit('.').next().unwrap_or_default() {
+            "MsgExecuteContract" | "execute" => DecodedMsg::WasmExecute {
+                sender,
+                contract: attr("execute", "_contract_address"),
+            },
+            "MsgInstantiateContract" | "MsgInstantiateContract2" | "instantiate" => {
+                DecodedMsg::Instantiate {
+                    sender,
+                    code_id: code_id("instantiate"),
+                    address: attr("instantiate", "_contract_address"),
+                }
+            }
+            "MsgStoreCode" | "store-code" => DecodedMsg::StoreCode {
+                sender,
+                code_id: code_id("store_code"),
+            },
+            "MsgMigrateContract" | "migrate" => DecodedMsg::Migrate {
+                sender,
+                contract: attr("migrate", "_contract_address"),
+                code_id: code_id("migrate"),
+            },
+            "MsgSend" | "send" => DecodedMsg::Transfer {
+                from: attr("transfer", "sender"),
+                to: attr("transfer", "recipient"),
+                amount: attr("transfer", "amount"),
+            },
+            "MsgTransfer" | "transfer" => DecodedMsg::IbcTransfer {
+                sender: attr("ibc_transfer", "sender"),
+                receiver: attr("ibc_transfer", "receiver"),
+                channel: attr("send_packet", "packet_src_channel"),
+            },
+            _ => DecodedMsg::Other { action },
+        }
+    }
+
+    /// Decodes the messages of a transaction from its ordered events.
+    /// The events are split by their `msg_index` attribute, or before each `message` event with an action on chains that don't index them
+    pub fn from_tx_events(events: &[Event]) -> Vec<Self> {
+        let msg_index = |event: &Event| {
+            event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == MSG_INDEX_ATTR)
+                .map(|attr| attr.value.clone())
+        };
+
+        let mut messages: Vec<Vec<Event>> = vec![];
+        if events.iter().any(|event| msg_index(event).is_some()) {
+            let mut current_index = None;
+            // Events without index are emitted by the transaction itself (fees, signatures)
+            for event in events.iter().filter(|event| msg_index(event).is_some()) {
+                if msg_index(event) != current_index {
+                    current_index = msg_index(event);
+                    messages.push(vec![]);
+                }
+                messages.last_mut().unwrap().push(event.clone());
+            }
+        } else {
+            for event in events {
+                let starts_message = event.ty == "message"
+                    && event.attributes.iter().any(|attr| attr.key == "action");
+                if starts_message || messages.is_empty() {
+                    messages.push(vec![]);
+                }
+                messages.last_mut().unwrap().push(event.clone());
+            }
+        }
+
+        messages
+            .iter()
+            .map(|events| Self::from_msg_events(events))
+            .collect()
+    }
+}
+
+impl Display for DecodedMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn or_unknown(value: &Option<impl Display>) -> String {
+            value
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| "?".to_string())
+        }
+
+        match self {
+            DecodedMsg::WasmExecute { sender, contract } => write!(
+                f,
+                "execute {} (sender: {})",
+                or_unknown(contract),
+                or_unknown(sender)
+            ),
+            DecodedMsg::Instantiate {
+                sender,
+                code_id,
+                address,
+            } => write!(
+                f,
+                "instantiate code {} at {} (sender: {})",
+                or_unknown(code_id),
+                or_unknown(address),
+                or_unknown(sender)
+            ),
+            DecodedMsg::StoreCode { sender, code_id } => write!(
+                f,
+                "store code {} (sender: {})",
+                or_unknown(code_id),
+                or_unknown(sender)
+            ),
+            DecodedMsg::Migrate {
+                sender,
+                contract,
+                code_id,
+            } => write!(
+                f,
+                "migrate {} to code {} (sender: {})",
+                or_unknown(contract),
+                or_unknown(code_id),
+                or_unknown(sender)
+            ),
+            DecodedMsg::Transfer { from, to, amount } => write!(
+                f,
+                "transfer {} from {} to {}",
+                or_unknown(amount),
+                or_unknown(from),
+                or_unknown(to)
+            ),
+            DecodedMsg::IbcTransfer {
+                sender,
+                receiver,
+                channel,
+            } => write!(
+                f,
+                "ibc transfer from {} to {} over {}",
+                or_unknown(sender),
+                or_unknown(receiver),
+                or_unknown(channel)
+            ),
+            DecodedMsg::Other { action } => write!(f, "{action}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn indexed(event: Event, index: &str) -> Event {
+        event.add_attribute(MSG_INDEX_ATTR, index)
+    }
+
+    #[test]
+    fn indexed_messages() {
+        let events = vec![
+            Event::new("tx").add_attribute("fee", "100ujuno"),
+            indexed(
+                Event::new("message")
+                    .add_attribute("action", "/cosmwasm.wasm.v1.MsgStoreCode")
+                    .add_attribute("sender", "juno1sender"),
+                "0",
+            ),
+            indexed(Event::new("store_code").add_attribute("code_id", "12"), "0"),
+            indexed(
+                Event::new("message")
+                    .add_attribute("action", "/cosmwasm.wasm.v1.MsgInstantiateContract")
+                    .add_attribute("sender", "juno1sender"),
+                "1",
+            ),
+            indexed(
+                Event::new("instantiate")
+                    .add_attribute("_contract_address", "juno1contract")
+                    .add_attribute("code_id", "12"),
+                "1",
+            ),
+            indexed(
+                Event::new("message").add_attribute("action", "/cosmos.gov.v1.MsgVote"),
+                "2",
+            ),
+        ];
+
+        let msgs = DecodedMsg::from_tx_events(&events);
+        assert_eq!(
+            msgs,
+            vec![
+                DecodedMsg::StoreCode {
+                    sender: Some("juno1sender".to_string()),
+                    code_id: Some(12),
+                },
+                DecodedMsg::Instantiate {
+                    sender: Some("juno1sender".to_string()),
+                    code_id: Some(12),
+                    address: Some("juno1contract".to_string()),
+                },
+                DecodedMsg::Other {
+                    action: "/cosmos.gov.v1.MsgVote".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            msgs[1].to_string(),
+            "instantiate code 12 at juno1contract (sender: juno1sender)"
+        );
+    }
+
+    #[test]
+    fn legacy_messages() {
+        let events = vec![
+            Event::new("message")
+                .add_attribute("action", "/cosmos.bank.v1beta1.MsgSend")
+                .add_attribute("sender", "juno1sender"),
+            Event::new("transfer")
+                .add_attribute("recipient", "juno1recipient")
+                .add_attribute("sender", "juno1sender")
+                .add_attribute("amount", "100ujuno"),
+            Event::new("message").add_attribute("action", "/cosmwasm.wasm.v1.MsgExecuteContract"),
+            Event::new("execute").add_attribute("_contract_address", "juno1contract"),
+        ];
+
+        let msgs = DecodedMsg::from_tx_events(&events);
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(
+            msgs[0].to_string(),
+            "transfer 100ujuno from juno1sender to juno1recipient"
+        );
+        assert_eq!(
+            msgs[1],
+            DecodedMsg::WasmExecute {
+                sender: None,
+                contract: Some("juno1contract".to_string()),
+            }
+        );
+    }
+}
@@ -1,16 +1,18 @@
 use prost::bytes::Bytes;
 
 use super::{
+    call_trace::CallTrace,
     cosmos_modules::{
         abci::{AbciMessageLog, Attribute, StringEvent, TxResponse},
         tendermint_abci::Event,
     },
     error::DaemonError,
+    tx_decode::DecodedMsg,
 };
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 
-use cosmwasm_std::{to_json_binary, Binary, StdError, StdResult};
-use cw_orch_core::environment::IndexResponse;
+use cosmwasm_std::{to_json_binary, Binary, StdResult};
+use cw_orch_core::environment::{missing_event_attr_error, IndexResponse};
 use serde::{Deserialize, Serialize};
 
 const FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
@@ -18,6 +20,9 @@ const FORMAT_TZ_SUPPLIED: &str = "%Y-%m-%dT%H:%M:%S.%f%:z";
 const FORMAT_SHORT_Z: &str = "%Y-%m-%dT%H:%M:%SZ";
 const FORMAT_SHORT_Z2: &str = "%Y-%m-%dT%H:%M:%S.%fZ";
 
+/// Index of the message that emitted an event, added as attribute to the events since cosmos-sdk 0.50
+pub(crate) const MSG_INDEX_ATTR: &str = "msg_index";
+
 fn parse_attribute_bytes(value: &Bytes) -> String {
     String::from_utf8_lossy(value).to_string()
 }
@@ -60,7 +65,7 @@ impl CosmTxResponse {
         attribute_key: &str,
     ) -> Vec<(usize, String)> {
         let mut response: Vec<(usize, String)> = Default::default();
-        let logs = &self.logs;
+        let logs = &self.msg_logs();
 
         for log_part in logs {
             let msg_index = log_part.msg_index.unwrap_or_default();
@@ -116,10 +121,82 @@ impl CosmTxResponse {
         }
     }
 
+    /// Nested contract calls of this transaction, rebuilt from its events (see [`crate::call_trace`])
+    pub fn call_trace(&self) -> CallTrace {
+        CallTrace::from_events(&self.events())
+    }
+
+    /// Events of the transaction grouped per message.
+    /// Uses the logs of the transaction, or groups the events by their `msg_index` attribute on chains that don't fill the logs anymore (cosmos-sdk 0.50+).
+    /// Events that weren't emitted by a message (fees, signatures, ...) aren't included
+    pub fn msg_logs(&self) -> Vec<TxResultBlockMsg> {
+        if !self.logs.is_empty() {
+            return self.logs.clone();
+        }
+
+        let mut msgs: Vec<TxResultBlockMsg> = vec![];
+        for event in &self.events {
+            let mut msg_index = None;
+            let mut attributes = vec![];
+            for attr in &event.attributes {
+                let key = parse_attribute_bytes(&attr.key);
+                let value = parse_attribute_bytes(&attr.value);
+                if key == MSG_INDEX_ATTR {
+                    msg_index = value.parse::<usize>().ok();
+                } else {
+                    attributes.push(TxResultBlockAttribute { key, value });
+                }
+            }
+            let Some(msg_index) = msg_index else {
+                continue;
+            };
+
+            let event = TxResultBlockEvent {
+                s_type: event.r#type.clone(),
+                attributes,
+            };
+            match msgs.iter_mut().find(|msg| msg.msg_index == Some(msg_index)) {
+                Some(msg) => msg.events.push(event),
+                None => msgs.push(TxResultBlockMsg {
+                    msg_index: Some(msg_index),
+                    events: vec![event],
+                }),
+            }
+        }
+        msgs.sort_by_key(|msg| msg.msg_index);
+        msgs
+    }
+
+    /// Messages of this transaction, decoded from its events (see [`crate::tx_decode`])
+    pub fn decode_msgs(&self) -> Vec<DecodedMsg> {
+        let msg_logs = self.msg_logs();
+        if msg_logs.is_empty() {
+            return DecodedMsg::from_tx_events(&self.events());
+        }
+        msg_logs
+            .iter()
+            .map(|log| {
+                let events: Vec<cosmwasm_std::Event> = log
+                    .events
+                    .iter()
+                    .map(|event| {
+                        cosmwasm_std::Event::new(&event.s_type).add_attributes(
+                            event
+                                .attributes
+                                .iter()
+                                .map(|attr| (attr.key.clone(), attr.value.clone())),
+                        )
+                    })
+                    .collect();
+                DecodedMsg::from_msg_events(&events)
+            })
+            .collect()
+    }
+
     fn get_events_from_logs(&self, event_type: &str) -> Vec<TxResultBlockEvent> {
         let mut response: Vec<TxResultBlockEvent> = Default::default();
 
-        for log_part in &self.logs {
+        for log_part in &self.msg_logs() {
             let events = &log_part.events;
 
             let events_filtered = events
@@ -164,6 +241,23 @@ impl From<TxResponse> for CosmTxResponse {
 
 impl IndexResponse for CosmTxResponse {
     fn events(&self) -> Vec<cosmwasm_std::Event> {
+        // Some nodes only return the events of the messages in the logs
+        if self.events.is_empty() {
+            return self
+                .logs
+                .iter()
+                .flat_map(|log| &log.events)
+                .map(|event| {
+                    cosmwasm_std::Event::new(event.s_type.clone()).add_attributes(
+                        event
+                            .attributes
+                            .iter()
+                            .map(|attr| (attr.key.clone(), attr.value.clone())),
+                    )
+                })
+                .collect();
+        }
+
         let mut parsed_events = vec![];
 
         for event in &self.events {
@@ -192,30 +286,37 @@ impl IndexResponse for CosmTxResponse {
         }
     }
 
+    fn gas_used(&self) -> Option<u64> {
+        Some(self.gas_used)
+    }
+
+    fn tx_hash(&self) -> Option<String> {
+        Some(self.txhash.clone())
+    }
+
     fn event_attr_value(&self, event_type: &str, attr_key: &str) -> StdResult<String> {
-        for event in &self.events {
-            if event.r#type == event_type {
+        let events = self.events();
+        for event in &events {
+            if event.ty == event_type {
                 for attr in &event.attributes {
                     if attr.key == attr_key {
-                        return Ok(parse_attribute_bytes(&attr.value));
+                        return Ok(attr.value.clone());
                     }
                 }
             }
         }
 
-        Err(StdError::generic_err(format!(
-            "event of type {event_type} does not have a value at key {attr_key}"
-        )))
+        Err(missing_event_attr_error(&events, event_type, attr_key))
     }
 
     fn event_attr_values(&self, event_type: &str, attr_key: &str) -> Vec<String> {
         let mut all_results = vec![];
 
-        for event in &self.events {
-            if event.r#type == event_type {
+        for event in &self.events() {
+            if event.ty == event_type {
                 for attr in &event.attributes {
                     if attr.key == attr_key {
-                        all_results.push(parse_attribute_bytes(&attr.value));
+                        all_results.push(attr.value.clone());
                     }
                 }
             }
@@ -0,0 +1,205 @@
+//! Verification of deployed code against local artifacts.
+//!
+//! [`VerifyAgainstChain::verify_against_chain`] downloads the wasm of the code id stored for a contract, hashes it and compares it with the local artifact.
+//! When the artifact was built with the cosmwasm optimizer, the checksum recorded in `checksums.txt` is verified as well.
+//! This catches stale builds that were deployed by accident.
+//!
+//! ## Usage
+//! ```no_run
+//! use cw_orch_daemon::{verify::VerifyAgainstChain, DaemonError};
+//!
+//! fn check_deployment(contract: &impl VerifyAgainstChain) -> Result<(), DaemonError> {
+//!     let verification = contract.verify_against_chain()?;
+//!     assert!(verification.is_verified(), "{}", verification);
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt::Display;
+
+use cosmwasm_std::HexBinary;
+use cw_orch_core::contract::interface_traits::{ContractInstance, Uploadable};
+use sha2::{Digest, Sha256};
+
+use crate::{queriers::CosmWasm, Daemon, DaemonError};
+
+/// Outcome of [`VerifyAgainstChain::verify_against_chain`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeVerification {
+    /// Id of the contract
+    pub contract_id: String,
+    /// Code id stored for the contract
+    pub code_id: u64,
+    /// Checksum of the wasm downloaded from the chain
+    pub on_chain_checksum: HexBinary,
+    /// Checksum of the local wasm artifact
+    pub local_checksum: HexBinary,
+    /// Checksum recorded by the optimizer for the local artifact, if any
+    pub optimizer_checksum: Option<HexBinary>,
+}
+
+impl CodeVerification {
+    /// Compares the wasm code stored on chain under `code_id` with the checksums of the local artifact
+    fn new(
+        contract_id: String,
+        code_id: u64,
+        on_chain_code: &[u8],
+        local_checksum: HexBinary,
+        optimizer_checksum: Option<HexBinary>,
+    ) -> Self {
+        let on_chain_checksum: [u8; 32] = Sha256::digest(on_chain_code).into();
+        Self {
+            contract_id,
+            code_id,
+            on_chain_checksum: on_chain_checksum.into(),
+            local_checksum,
+            optimizer_checksum,
+        }
+    }
+
+    /// Returns true if the code on chain is the local artifact
+    pub fn matches_chain(&self) -> bool {
+        self.on_chain_checksum == self.local_checksum
+    }
+
+    /// Returns whether the local artifact is the one built by the optimizer, if the optimizer recorded a checksum
+    pub fn matches_optimizer(&self) -> Option<bool> {
+        self.optimizer_checksum
+            .as_ref()
+            .map(|checksum| checksum == &self.local_checksum)
+    }
+
+    /// Returns true if the code on chain is the local artifact and the artifact matches the optimizer checksum
+    pub fn is_verified(&self) -> bool {
+        self.mismatches().is_empty()
+    }
+
+    /// Describes all the checksum mismatches
+    pub fn mismatches(&self) -> Vec<String> {
+        let mut mismatches = vec![];
+        if !self.matches_chain() {
+            mismatches.push(format!(
+                "code {} on chain ({}) doesn't match the local artifact ({})",
+                self.code_id, self.on_chain_checksum, self.local_checksum
+            ));
+        }
+        if self.matches_optimizer() == Some(false) {
+            mismatches.push(format!(
+                "local artifact ({}) doesn't match the optimizer checksum ({}), it was modified after the optimized build",
+                self.local_checksum,
+                self.optimizer_checksum.as_ref().unwrap()
+            ));
+        }
+        mismatches
+    }
+}
+
+impl Display for CodeVerification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_verified() {
+            return writeln!(
+                f,
+                "{}: code {} matches the local artifact ({})",
+                self.contract_id, self.code_id, self.local_checksum
+            );
+        }
+        writeln!(f, "{}: verification failed", self.contract_id)?;
+        for mismatch in self.mismatches() {
+            writeln!(f, "  {}", mismatch)?;
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the code of a contract deployed with a [`Daemon`] against its local wasm artifact
+pub trait VerifyAgainstChain: Uploadable + ContractInstance<Daemon> {
+    /// Downloads the wasm of the stored code id and compares its checksum with the local artifact and the optimizer checksum file
+    fn verify_against_chain(&self) -> Result<CodeVerification, DaemonError> {
+        let daemon = self.get_chain();
+        let code_id = self.code_id()?;
+
+        let chain_info = &daemon.daemon.sender.chain_info;
+        let (local_checksum, optimizer_checksum) =
+            match <Self as Uploadable>::wasm_bytes(chain_info) {
+                Some(code) => {
+                    let checksum: [u8; 32] = Sha256::digest(code).into();
+                    (checksum.into(), None)
+                }
+                None => {
+                    let wasm = <Self as Uploadable>::wasm(chain_info);
+                    (wasm.checksum()?, wasm.optimizer_checksum()?)
+                }
+            };
+
+        let code = daemon
+            .rt_handle
+            .block_on(CosmWasm::new_async(daemon.channel())._code_data(code_id))?;
+        let verification = CodeVerification::new(
+            self.id(),
+            code_id,
+            &code,
+            local_checksum,
+            optimizer_checksum,
+        );
+        log::info!("{}", verification);
+        Ok(verification)
+    }
+}
+
+impl<T: Uploadable + ContractInstance<Daemon>> VerifyAgainstChain for T {}
+
+#[cfg(test)]
+mod test {
+    use cw_orch_core::contract::WasmPath;
+
+    use super::*;
+
+    const ARTIFACTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../artifacts");
+
+    fn verify(on_chain_code: &[u8], local: &WasmPath) -> anyhow::Result<CodeVerification> {
+        Ok(CodeVerification::new(
+            "mock-contract".to_string(),
+            1,
+            on_chain_code,
+            local.checksum()?,
+            local.optimizer_checksum()?,
+        ))
+    }
+
+    #[test]
+    fn verification_mismatches() -> anyhow::Result<()> {
+        let local = WasmPath::new(format!("{ARTIFACTS}/mock_contract.wasm"))?;
+        let deployed = std::fs::read(local.path())?;
+        let verification = verify(&deployed, &local)?;
+        assert_eq!(verification.matches_optimizer(), Some(true));
+        assert!(verification.is_verified());
+
+        // Another build was deployed
+        let stale = verify(
+            &std::fs::read(format!("{ARTIFACTS}/mock_contract_u64.wasm"))?,
+            &local,
+        )?;
+        assert!(!stale.matches_chain());
+        assert_eq!(stale.mismatches().len(), 1);
+
+        // The local artifact was modified after the optimized build
+        let dir = std::env::temp_dir().join(format!("cw-orch-verify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::copy(
+            format!("{ARTIFACTS}/checksums.txt"),
+            dir.join("checksums.txt"),
+        )?;
+        let mut modified_code = deployed.clone();
+        modified_code.push(0);
+        std::fs::write(dir.join("mock_contract.wasm"), &modified_code)?;
+        let modified = verify(
+            &modified_code,
+            &WasmPath::new(dir.join("mock_contract.wasm"))?,
+        )?;
+        std::fs::remove_dir_all(&dir)?;
+        assert!(modified.matches_chain());
+        assert_eq!(modified.matches_optimizer(), Some(false));
+        assert!(!modified.is_verified());
+        Ok(())
+    }
+}
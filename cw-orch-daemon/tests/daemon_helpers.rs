@@ -15,6 +15,38 @@ mod tests {
 
     use crate::common::Id;
 
+    #[test]
+    #[serial_test::serial]
+    fn upload_deduplication() {
+        use cw_orch_networks::networks;
+
+        let daemon = Daemon::builder()
+            .chain(networks::LOCAL_JUNO)
+            .build()
+            .unwrap();
+
+        let contract = mock_contract::MockContract::new(
+            format!("test:mock_contract:{}", Id::new()),
+            daemon.clone(),
+        );
+        let code_id = contract.ensure_uploaded().unwrap();
+
+        // Same wasm file, different contract id
+        let other = mock_contract::MockContract::new(
+            format!("test:mock_contract:{}", Id::new()),
+            daemon.clone(),
+        );
+        asserting!("existing code is found")
+            .that(&other.existing_code_id().unwrap())
+            .is_equal_to(Some(code_id));
+        asserting!("upload_if_needed doesn't upload")
+            .that(&other.upload_if_needed().unwrap())
+            .is_none();
+        asserting!("code id is reused")
+            .that(&other.code_id().unwrap())
+            .is_equal_to(code_id);
+    }
+
     #[test]
     #[serial_test::serial]
     fn helper_traits() {
@@ -160,6 +160,29 @@ mod queriers {
         asserting!("block_time is ok").that(&block_time).is_ok();
     }
 
+    #[test]
+    fn mempool() {
+        // RPC endpoint of the local juno node
+        let rpc_url = "http://localhost:26657";
+        let rt = Runtime::new().unwrap();
+        let channel = rt.block_on(build_channel());
+
+        let node = Node::new_async(channel);
+
+        let unconfirmed_txs = rt.block_on(node._unconfirmed_txs(rpc_url, None));
+        asserting!("unconfirmed_txs is ok")
+            .that(&unconfirmed_txs)
+            .is_ok();
+
+        let address = "juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y";
+        let drained = rt.block_on(node._wait_for_mempool_drain(
+            rpc_url,
+            address,
+            std::time::Duration::from_secs(30),
+        ));
+        asserting!("mempool is drained").that(&drained).is_ok();
+    }
+
     #[test]
     #[serial_test::serial]
     fn simulate_tx() {
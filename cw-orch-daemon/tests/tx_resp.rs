@@ -176,3 +176,90 @@ fn test_breaking_data_is_none() {
         .that(&tx_res.data())
         .is_none();
 }
+
+fn event(ty: &str, attrs: &[(&str, &str)]) -> Event {
+    Event {
+        r#type: ty.to_string(),
+        attributes: attrs
+            .iter()
+            .map(|(key, value)| EventAttribute {
+                key: key.to_string().into(),
+                value: value.to_string().into(),
+                index: true,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_sdk_50_events() {
+    // Cosmos-sdk 0.50+ chains leave the logs empty and index the events by message
+    let tx_res = CosmTxResponse {
+        events: vec![
+            event("tx", &[("fee", "100ujuno")]),
+            event(
+                "message",
+                &[
+                    ("action", "/cosmwasm.wasm.v1.MsgStoreCode"),
+                    ("msg_index", "0"),
+                ],
+            ),
+            event("store_code", &[("code_id", "12"), ("msg_index", "0")]),
+            event(
+                "message",
+                &[
+                    ("action", "/cosmwasm.wasm.v1.MsgInstantiateContract"),
+                    ("msg_index", "1"),
+                ],
+            ),
+            event(
+                "instantiate",
+                &[
+                    ("_contract_address", "juno1contract"),
+                    ("code_id", "12"),
+                    ("msg_index", "1"),
+                ],
+            ),
+        ],
+        ..Default::default()
+    };
+
+    let msg_logs = tx_res.msg_logs();
+    asserting!("events are grouped per message")
+        .that(&msg_logs.len())
+        .is_equal_to(2);
+    asserting!("msg_index attribute is removed")
+        .that(&msg_logs[1].events[1].attributes.len())
+        .is_equal_to(2);
+
+    asserting!("get_attribute_from_logs returns the message index")
+        .that(&tx_res.get_attribute_from_logs("instantiate", "_contract_address"))
+        .is_equal_to(vec![(1, "juno1contract".to_string())]);
+
+    asserting!("instantiated contract address is found")
+        .that(&tx_res.instantiated_contract_address().unwrap().to_string())
+        .is_equal_to("juno1contract".to_string());
+    asserting!("uploaded code id is found")
+        .that(&tx_res.uploaded_code_id().unwrap())
+        .is_equal_to(12);
+}
+
+#[test]
+fn test_logs_only_events() {
+    // Older nodes can return the message events in the logs only
+    let log: Value = serde_json::json!({
+        "msg_index": 0,
+        "events": [{
+            "type": "instantiate",
+            "attributes": [{ "key": "_contract_address", "value": "juno1contract" }]
+        }]
+    });
+    let tx_res = CosmTxResponse {
+        logs: vec![TxResultBlockMsg::from(&log)],
+        ..Default::default()
+    };
+
+    asserting!("instantiated contract address is found in the logs")
+        .that(&tx_res.instantiated_contract_address().unwrap().to_string())
+        .is_equal_to("juno1contract".to_string());
+}
@@ -19,6 +19,7 @@ pub const NOBLE_1: ChainInfo = ChainInfo {
     grpc_urls: &["http://noble-grpc.polkachu.com:21590"],
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
     network_info: NOBLE,
     kind: cw_orch::environment::ChainKind::Mainnet,
 };
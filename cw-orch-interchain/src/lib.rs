@@ -6,7 +6,7 @@ pub mod prelude {
 
     #[cfg(feature = "daemon")]
     pub use cw_orch_interchain_daemon::{
-        ChannelCreationValidator, ChannelCreator, DaemonInterchainEnv,
+        ChannelCreationValidator, ChannelCreator, DaemonInterchainEnv, HermesRelayer,
     };
     #[cfg(feature = "daemon")]
     pub use cw_orch_starship::Starship;
@@ -0,0 +1,177 @@
+use cosmwasm_std::{coin, CosmosMsg, IbcMsg, IbcOrder, IbcTimeout, IbcTimeoutBlock};
+use cw_orch::{
+    environment::{QueryHandler, TxHandler},
+    mock::cw_multi_test::Executor,
+};
+use cw_orch_interchain_core::{types::IbcPacketOutcome, InterchainEnv};
+use cw_orch_interchain_mock::{InterchainMockError, MockChannelStatus, MockInterchainEnv};
+use ibc_relayer_types::core::{
+    ics04_channel::packet::Sequence,
+    ics24_host::identifier::{ChannelId, PortId},
+};
+
+#[test]
+fn close_channel_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("stargaze-1", "sender")]);
+
+    let channel = interchain.create_channel(
+        "juno-1",
+        "stargaze-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+    let juno = interchain.chain("juno-1")?;
+    let stargaze = interchain.chain("stargaze-1")?;
+
+    let (juno_port, stargaze_port) = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+    let juno_channel = juno_port.channel.unwrap();
+    let stargaze_channel = stargaze_port.channel.unwrap();
+
+    interchain.close_channel("juno-1", &PortId::transfer(), &juno_channel)?;
+
+    // Both ends are closed
+    let state = interchain
+        .channel_state("stargaze-1", &PortId::transfer(), &stargaze_channel)
+        .unwrap();
+    assert_eq!(state.status, MockChannelStatus::Closed);
+
+    // Closing twice errors
+    assert!(interchain
+        .close_channel("stargaze-1", &PortId::transfer(), &stargaze_channel)
+        .is_err());
+
+    // Packets can't be relayed on a closed channel
+    juno.add_balance(juno.sender().to_string(), vec![coin(100_000, "ujuno")])?;
+    let block = juno.block_info()?;
+    let tx_resp = juno.app.borrow_mut().execute(
+        juno.sender(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: juno_channel.to_string(),
+            to_address: stargaze.sender().to_string(),
+            amount: coin(100_000, "ujuno"),
+            timeout: IbcTimeout::with_timestamp(block.time.plus_seconds(600)),
+        }),
+    )?;
+
+    assert!(interchain.wait_ibc("juno-1", tx_resp).is_err());
+
+    Ok(())
+}
+
+/// Creates an ordered transfer channel between juno-1 and stargaze-1 and returns the channel ids on both chains
+fn ordered_channel(
+    interchain: &MockInterchainEnv,
+) -> cw_orch::anyhow::Result<(ChannelId, ChannelId)> {
+    let channel = interchain.create_channel(
+        "juno-1",
+        "stargaze-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        Some(IbcOrder::Ordered),
+    )?;
+    let (juno_port, stargaze_port) = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+    Ok((juno_port.channel.unwrap(), stargaze_port.channel.unwrap()))
+}
+
+fn transfer(
+    interchain: &MockInterchainEnv,
+    channel: &ChannelId,
+    timeout: IbcTimeout,
+) -> cw_orch::anyhow::Result<cw_orch::mock::cw_multi_test::AppResponse> {
+    let juno = interchain.chain("juno-1")?;
+    let stargaze = interchain.chain("stargaze-1")?;
+    juno.add_balance(juno.sender().to_string(), vec![coin(100, "ujuno")])?;
+    let response = juno.app.borrow_mut().execute(
+        juno.sender(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: channel.to_string(),
+            to_address: stargaze.sender().to_string(),
+            amount: coin(100, "ujuno"),
+            timeout,
+        }),
+    )?;
+    Ok(response)
+}
+
+#[test]
+fn ordered_channel_sequence_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("stargaze-1", "sender")]);
+    let (juno_channel, _) = ordered_channel(&interchain)?;
+    let juno = interchain.chain("juno-1")?;
+    let timeout = IbcTimeout::with_timestamp(juno.block_info()?.time.plus_seconds(600));
+
+    let first_tx = transfer(&interchain, &juno_channel, timeout.clone())?;
+    let second_tx = transfer(&interchain, &juno_channel, timeout)?;
+
+    // The second packet can't be relayed before the first one
+    let error = interchain
+        .follow_packet(
+            "juno-1",
+            PortId::transfer(),
+            juno_channel.clone(),
+            "stargaze-1",
+            Sequence::from(2),
+        )
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error,
+        InterchainMockError::UnexpectedSequence {
+            expected: 1,
+            received: 2,
+            ..
+        }
+    ));
+
+    // In order, both packets are relayed
+    interchain.wait_ibc("juno-1", first_tx)?;
+    interchain.wait_ibc("juno-1", second_tx)?;
+    let state = interchain
+        .channel_state("juno-1", &PortId::transfer(), &juno_channel)
+        .unwrap();
+    assert_eq!(state.next_sequence_send, 3);
+    assert_eq!(state.status, MockChannelStatus::Open);
+
+    Ok(())
+}
+
+#[test]
+fn ordered_channel_timeout_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("stargaze-1", "sender")]);
+    let (juno_channel, stargaze_channel) = ordered_channel(&interchain)?;
+    let stargaze = interchain.chain("stargaze-1")?;
+
+    let tx_resp = transfer(
+        &interchain,
+        &juno_channel,
+        IbcTimeout::with_block(IbcTimeoutBlock {
+            revision: 1,
+            height: stargaze.block_info()?.height,
+        }),
+    )?;
+    let result = interchain.wait_ibc("juno-1", tx_resp)?;
+    assert!(matches!(
+        result.packets[0].outcome,
+        IbcPacketOutcome::Timeout { .. }
+    ));
+
+    // The timeout closed both ends of the ordered channel
+    for (chain_id, channel) in [("juno-1", &juno_channel), ("stargaze-1", &stargaze_channel)] {
+        let state = interchain
+            .channel_state(chain_id, &PortId::transfer(), channel)
+            .unwrap();
+        assert_eq!(state.status, MockChannelStatus::Closed);
+    }
+    assert!(interchain
+        .close_channel("juno-1", &PortId::transfer(), &juno_channel)
+        .is_err());
+
+    Ok(())
+}
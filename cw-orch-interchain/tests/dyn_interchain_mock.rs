@@ -0,0 +1,42 @@
+use cw_orch::prelude::Mock;
+use cw_orch_interchain_core::{BoxedInterchainEnv, InterchainEnv};
+use cw_orch_interchain_mock::MockInterchainEnv;
+use ibc_relayer_types::core::ics24_host::identifier::PortId;
+
+/// Environment selected at runtime, as a binary would from its config
+fn interchain(with_stargaze: bool) -> BoxedInterchainEnv<Mock> {
+    if with_stargaze {
+        Box::new(MockInterchainEnv::new(vec![
+            ("juno-1", "sender"),
+            ("stargaze-1", "sender"),
+        ]))
+    } else {
+        Box::new(MockInterchainEnv::new(vec![
+            ("juno-1", "sender"),
+            ("osmosis-1", "sender"),
+        ]))
+    }
+}
+
+#[test]
+fn dyn_interchain_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = interchain(true);
+
+    let channel = interchain.create_channel(
+        "juno-1",
+        "stargaze-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+    let (juno_port, stargaze_port) = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+    assert!(juno_port.channel.is_some());
+    assert!(stargaze_port.channel.is_some());
+
+    assert!(interchain.chain("stargaze-1").is_ok());
+    assert!(interchain.chain("osmosis-1").is_err());
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use cosmwasm_std::{coin, CosmosMsg, IbcMsg, IbcTimeout};
+use cw_orch::{
+    environment::{QueryHandler, TxHandler},
+    mock::cw_multi_test::Executor,
+};
+use cw_orch_interchain_core::{types::IbcPacketStatus, InterchainEnv};
+use cw_orch_interchain_mock::MockInterchainEnv;
+use ibc_relayer_types::core::{ics04_channel::packet::Sequence, ics24_host::identifier::PortId};
+
+#[test]
+fn packet_status_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("stargaze-1", "sender")]);
+
+    let channel = interchain.create_channel(
+        "juno-1",
+        "stargaze-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+    let juno = interchain.chain("juno-1")?;
+    let stargaze = interchain.chain("stargaze-1")?;
+
+    let (juno_port, _) = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+    let juno_channel = juno_port.channel.unwrap();
+
+    let status = |sequence: u64| {
+        interchain.packet_status(
+            "juno-1",
+            PortId::transfer(),
+            juno_channel.clone(),
+            "stargaze-1",
+            Sequence::from(sequence),
+        )
+    };
+    assert_eq!(status(1)?, IbcPacketStatus::NotFound);
+
+    juno.add_balance(juno.sender().to_string(), vec![coin(100_000, "ujuno")])?;
+    let block = juno.block_info()?;
+    let tx_resp = juno.app.borrow_mut().execute(
+        juno.sender(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: juno_channel.to_string(),
+            to_address: stargaze.sender().to_string(),
+            amount: coin(100_000, "ujuno"),
+            timeout: IbcTimeout::with_timestamp(block.time.plus_seconds(600)),
+        }),
+    )?;
+    interchain.check_ibc("juno-1", tx_resp)?;
+
+    assert_eq!(status(1)?, IbcPacketStatus::Acknowledged);
+    assert!(!status(1)?.is_pending());
+    assert_eq!(status(2)?, IbcPacketStatus::NotFound);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use cosmwasm_std::{coin, coins, CosmosMsg, IbcMsg, IbcTimeout, IbcTimeoutBlock};
+use cw_orch::{
+    environment::{QueryHandler, TxHandler},
+    mock::cw_multi_test::Executor,
+};
+use cw_orch_interchain_core::InterchainEnv;
+use cw_orch_interchain_mock::{MockInterchainEnv, RelayedPacketOutcome, RelayerCostSchedule};
+use ibc_relayer_types::core::ics24_host::identifier::PortId;
+
+#[test]
+fn relayer_costs_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("stargaze-1", "sender")]);
+    assert!(interchain.relayer_cost_summary().is_none());
+
+    let channel = interchain.create_channel(
+        "juno-1",
+        "stargaze-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+    let juno = interchain.chain("juno-1")?;
+    let stargaze = interchain.chain("stargaze-1")?;
+
+    let schedule = RelayerCostSchedule {
+        packet_fee: coins(1_000, "ujuno"),
+        ..Default::default()
+    };
+    interchain.simulate_relayer_costs(schedule.clone());
+
+    let stargaze_height = stargaze.block_info()?;
+    let channel = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+
+    juno.add_balance(juno.sender().to_string(), vec![coin(100_000, "ujuno")])?;
+    let tx_resp = juno.app.borrow_mut().execute(
+        juno.sender(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: channel.0.channel.unwrap().to_string(),
+            to_address: stargaze.sender().to_string(),
+            amount: coin(100_000, "ujuno"),
+            timeout: IbcTimeout::with_block(IbcTimeoutBlock {
+                revision: 1,
+                height: stargaze_height.height,
+            }),
+        }),
+    )?;
+    interchain.wait_ibc("juno-1", tx_resp)?;
+
+    let summary = interchain.relayer_cost_summary().unwrap();
+    assert_eq!(summary.packets.len(), 1);
+    assert_eq!(summary.packets[0].outcome, RelayedPacketOutcome::Timeout);
+    assert_eq!(summary.packets[0].recv_gas, 0);
+    assert_eq!(summary.total_gas(), schedule.timeout_gas);
+    assert_eq!(
+        summary.gas_per_chain().get("juno-1"),
+        Some(&schedule.timeout_gas)
+    );
+    assert_eq!(summary.total_fees().get("ujuno").unwrap().u128(), 1_000);
+
+    let channel_costs = summary.per_channel();
+    assert_eq!(channel_costs.len(), 1);
+    assert_eq!(channel_costs.values().next().unwrap().timed_out, 1);
+
+    let final_summary = interchain.stop_relayer_costs_simulation();
+    assert_eq!(final_summary, Some(summary));
+    assert!(interchain.relayer_cost_summary().is_none());
+
+    Ok(())
+}
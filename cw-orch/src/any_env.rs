@@ -0,0 +1,742 @@
+//! Execution environment selected at runtime.
+//!
+//! Environments are usually selected at compile time through the `Chain` generic parameter of the contract interfaces.
+//! [`AnyCwEnv`] wraps the supported environments in an enum implementing the environment traits by delegation,
+//! so a single binary can run against a [`Mock`] or a live chain depending on a CLI flag or a config file.
+//! `CloneTesting` and `OsmosisTestTube` are wrapped when the `clone-testing` and `osmosis-test-tube` features are enabled.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cw_orch::{any_env::AnyCwEnv, prelude::*};
+//!
+//! let live = std::env::args().any(|arg| arg == "--live");
+//! let chain: AnyCwEnv = if live {
+//!     Daemon::builder().chain(networks::LOCAL_JUNO).build().unwrap().into()
+//! } else {
+//!     Mock::new("sender").into()
+//! };
+//! // `chain` can be used with any contract interface, e.g. `MyContract::new("my-contract", chain)`
+//! println!("Running on {}", chain.env_info().chain_id);
+//! ```
+
+use cosmwasm_std::{
+    Addr, Binary, BlockInfo, CodeInfoResponse, Coin, ContractInfoResponse, Event, HexBinary,
+    StdResult, Timestamp,
+};
+use cw_orch_core::{
+    contract::interface_traits::{ContractInstance, Uploadable},
+    environment::{
+        BankQuerier, ChainState, ChainTime, DefaultQueriers, EnvironmentInfo, EnvironmentQuerier,
+        IndexResponse, NodeQuerier, Querier, QuerierGetter, QueryHandler, StateInterface,
+        SudoHandler, TxHandler, WasmQuerier,
+    },
+    CwEnvError,
+};
+use cw_orch_mock::{cw_multi_test::AppResponse, Mock};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, fmt::Debug};
+
+#[cfg(feature = "clone-testing")]
+use cw_orch_clone_testing::CloneTesting;
+#[cfg(feature = "daemon")]
+use cw_orch_daemon::{CosmTxResponse, Daemon, QuerySender, Wallet};
+#[cfg(feature = "osmosis-test-tube")]
+use cw_orch_osmosis_test_tube::OsmosisTestTube;
+
+/// Matches all the variants of an environment enum, binding the inner value to `$inner`
+macro_rules! delegate {
+    ($enum:ident, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $enum::Mock($inner) => $body,
+            #[cfg(feature = "daemon")]
+            $enum::Daemon($inner) => $body,
+            #[cfg(feature = "clone-testing")]
+            $enum::CloneTesting($inner) => $body,
+            #[cfg(feature = "osmosis-test-tube")]
+            $enum::OsmosisTestTube($inner) => $body,
+        }
+    };
+}
+
+/// Environment selected at runtime, see the [module documentation](self)
+#[derive(Clone)]
+pub enum AnyCwEnv {
+    /// In-memory [`Mock`] environment
+    Mock(Mock),
+    /// Live chain
+    #[cfg(feature = "daemon")]
+    Daemon(Daemon),
+    /// Fork of a live chain
+    #[cfg(feature = "clone-testing")]
+    CloneTesting(CloneTesting),
+    /// Osmosis test-tube chain
+    #[cfg(feature = "osmosis-test-tube")]
+    OsmosisTestTube(OsmosisTestTube),
+}
+
+impl AnyCwEnv {
+    /// Returns the wrapped [`Mock`], if this is a mock environment
+    pub fn as_mock(&self) -> Option<&Mock> {
+        match self {
+            AnyCwEnv::Mock(mock) => Some(mock),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped [`Daemon`], if this is a live chain
+    #[cfg(feature = "daemon")]
+    pub fn as_daemon(&self) -> Option<&Daemon> {
+        match self {
+            AnyCwEnv::Daemon(daemon) => Some(daemon),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped [`CloneTesting`], if this is a forked chain
+    #[cfg(feature = "clone-testing")]
+    pub fn as_clone_testing(&self) -> Option<&CloneTesting> {
+        match self {
+            AnyCwEnv::CloneTesting(clone_testing) => Some(clone_testing),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped [`OsmosisTestTube`], if this is a test-tube chain
+    #[cfg(feature = "osmosis-test-tube")]
+    pub fn as_osmosis_test_tube(&self) -> Option<&OsmosisTestTube> {
+        match self {
+            AnyCwEnv::OsmosisTestTube(test_tube) => Some(test_tube),
+            _ => None,
+        }
+    }
+
+    /// Name of the selected environment
+    fn kind(&self) -> &'static str {
+        match self {
+            AnyCwEnv::Mock(_) => "Mock",
+            #[cfg(feature = "daemon")]
+            AnyCwEnv::Daemon(_) => "Daemon",
+            #[cfg(feature = "clone-testing")]
+            AnyCwEnv::CloneTesting(_) => "CloneTesting",
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyCwEnv::OsmosisTestTube(_) => "OsmosisTestTube",
+        }
+    }
+
+    /// Sets the sender of the transactions, returns an error if the sender doesn't belong to the selected environment
+    pub fn try_set_sender(&mut self, sender: AnySender) -> Result<(), CwEnvError> {
+        let expected = self.kind();
+        match (self, sender) {
+            (AnyCwEnv::Mock(mock), AnySender::Mock(sender)) => mock.set_sender(sender),
+            #[cfg(feature = "daemon")]
+            (AnyCwEnv::Daemon(daemon), AnySender::Daemon(sender)) => daemon.set_sender(sender),
+            #[cfg(feature = "clone-testing")]
+            (AnyCwEnv::CloneTesting(clone_testing), AnySender::CloneTesting(sender)) => {
+                clone_testing.set_sender(sender)
+            }
+            #[cfg(feature = "osmosis-test-tube")]
+            (AnyCwEnv::OsmosisTestTube(test_tube), AnySender::OsmosisTestTube(sender)) => {
+                test_tube.set_sender(sender)
+            }
+            #[allow(unreachable_patterns)]
+            (_, sender) => {
+                return Err(CwEnvError::SenderMismatch {
+                    expected: expected.to_string(),
+                    found: sender.kind().to_string(),
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Mock> for AnyCwEnv {
+    fn from(mock: Mock) -> Self {
+        AnyCwEnv::Mock(mock)
+    }
+}
+
+#[cfg(feature = "daemon")]
+impl From<Daemon> for AnyCwEnv {
+    fn from(daemon: Daemon) -> Self {
+        AnyCwEnv::Daemon(daemon)
+    }
+}
+
+#[cfg(feature = "clone-testing")]
+impl From<CloneTesting> for AnyCwEnv {
+    fn from(clone_testing: CloneTesting) -> Self {
+        AnyCwEnv::CloneTesting(clone_testing)
+    }
+}
+
+#[cfg(feature = "osmosis-test-tube")]
+impl From<OsmosisTestTube> for AnyCwEnv {
+    fn from(test_tube: OsmosisTestTube) -> Self {
+        AnyCwEnv::OsmosisTestTube(test_tube)
+    }
+}
+
+/// Transaction response of an [`AnyCwEnv`]
+#[derive(Clone, Debug)]
+pub enum AnyTxResponse {
+    /// Response of a [`Mock`] transaction
+    Mock(AppResponse),
+    /// Response of a [`Daemon`] transaction
+    #[cfg(feature = "daemon")]
+    Daemon(CosmTxResponse),
+    /// Response of a [`CloneTesting`] transaction
+    #[cfg(feature = "clone-testing")]
+    CloneTesting(<CloneTesting as TxHandler>::Response),
+    /// Response of an [`OsmosisTestTube`] transaction
+    #[cfg(feature = "osmosis-test-tube")]
+    OsmosisTestTube(<OsmosisTestTube as TxHandler>::Response),
+}
+
+impl IndexResponse for AnyTxResponse {
+    fn events(&self) -> Vec<Event> {
+        delegate!(AnyTxResponse, self, response => response.events())
+    }
+
+    fn event_attr_value(&self, event_type: &str, attr_key: &str) -> StdResult<String> {
+        delegate!(AnyTxResponse, self, response => response.event_attr_value(event_type, attr_key))
+    }
+
+    fn event_attr_values(&self, event_type: &str, attr_key: &str) -> Vec<String> {
+        delegate!(AnyTxResponse, self, response => response.event_attr_values(event_type, attr_key))
+    }
+
+    fn data(&self) -> Option<Binary> {
+        delegate!(AnyTxResponse, self, response => response.data())
+    }
+
+    fn gas_used(&self) -> Option<u64> {
+        delegate!(AnyTxResponse, self, response => response.gas_used())
+    }
+
+    fn tx_hash(&self) -> Option<String> {
+        delegate!(AnyTxResponse, self, response => response.tx_hash())
+    }
+
+    fn fee(&self) -> Option<String> {
+        delegate!(AnyTxResponse, self, response => response.fee())
+    }
+
+    fn instantiated_contract_address(&self) -> StdResult<Addr> {
+        delegate!(AnyTxResponse, self, response => response.instantiated_contract_address())
+    }
+
+    fn uploaded_code_id(&self) -> StdResult<u64> {
+        delegate!(AnyTxResponse, self, response => response.uploaded_code_id())
+    }
+}
+
+/// Sender of an [`AnyCwEnv`], must match the selected environment
+#[derive(Clone)]
+pub enum AnySender {
+    /// Sender address of a [`Mock`]
+    Mock(Addr),
+    /// Wallet of a [`Daemon`]
+    #[cfg(feature = "daemon")]
+    Daemon(Wallet),
+    /// Sender address of a [`CloneTesting`]
+    #[cfg(feature = "clone-testing")]
+    CloneTesting(Addr),
+    /// Account of an [`OsmosisTestTube`]
+    #[cfg(feature = "osmosis-test-tube")]
+    OsmosisTestTube(<OsmosisTestTube as TxHandler>::Sender),
+}
+
+impl AnySender {
+    /// Name of the environment of the sender
+    fn kind(&self) -> &'static str {
+        match self {
+            AnySender::Mock(_) => "Mock",
+            #[cfg(feature = "daemon")]
+            AnySender::Daemon(_) => "Daemon",
+            #[cfg(feature = "clone-testing")]
+            AnySender::CloneTesting(_) => "CloneTesting",
+            #[cfg(feature = "osmosis-test-tube")]
+            AnySender::OsmosisTestTube(_) => "OsmosisTestTube",
+        }
+    }
+}
+
+/// Deployment state of an [`AnyCwEnv`]
+#[derive(Clone)]
+pub enum AnyState {
+    /// State of a [`Mock`]
+    Mock(<Mock as ChainState>::Out),
+    /// State of a [`Daemon`]
+    #[cfg(feature = "daemon")]
+    Daemon(<Daemon as ChainState>::Out),
+    /// State of a [`CloneTesting`]
+    #[cfg(feature = "clone-testing")]
+    CloneTesting(<CloneTesting as ChainState>::Out),
+    /// State of an [`OsmosisTestTube`]
+    #[cfg(feature = "osmosis-test-tube")]
+    OsmosisTestTube(<OsmosisTestTube as ChainState>::Out),
+}
+
+impl StateInterface for AnyState {
+    fn get_address(&self, contract_id: &str) -> Result<Addr, CwEnvError> {
+        delegate!(AnyState, self, state => state.get_address(contract_id))
+    }
+
+    fn set_address(&mut self, contract_id: &str, address: &Addr) {
+        delegate!(AnyState, self, state => state.set_address(contract_id, address))
+    }
+
+    fn remove_address(&mut self, contract_id: &str) {
+        delegate!(AnyState, self, state => state.remove_address(contract_id))
+    }
+
+    fn get_code_id(&self, contract_id: &str) -> Result<u64, CwEnvError> {
+        delegate!(AnyState, self, state => state.get_code_id(contract_id))
+    }
+
+    fn set_code_id(&mut self, contract_id: &str, code_id: u64) {
+        delegate!(AnyState, self, state => state.set_code_id(contract_id, code_id))
+    }
+
+    fn remove_code_id(&mut self, contract_id: &str) {
+        delegate!(AnyState, self, state => state.remove_code_id(contract_id))
+    }
+
+    fn get_all_addresses(&self) -> Result<HashMap<String, Addr>, CwEnvError> {
+        delegate!(AnyState, self, state => state.get_all_addresses())
+    }
+
+    fn get_all_code_ids(&self) -> Result<HashMap<String, u64>, CwEnvError> {
+        delegate!(AnyState, self, state => state.get_all_code_ids())
+    }
+}
+
+impl ChainState for AnyCwEnv {
+    type Out = AnyState;
+
+    fn state(&self) -> Self::Out {
+        match self {
+            AnyCwEnv::Mock(mock) => AnyState::Mock(mock.state()),
+            #[cfg(feature = "daemon")]
+            AnyCwEnv::Daemon(daemon) => AnyState::Daemon(daemon.state()),
+            #[cfg(feature = "clone-testing")]
+            AnyCwEnv::CloneTesting(clone_testing) => AnyState::CloneTesting(clone_testing.state()),
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyCwEnv::OsmosisTestTube(test_tube) => AnyState::OsmosisTestTube(test_tube.state()),
+        }
+    }
+}
+
+/// Wraps the response of the selected environment in an [`AnyTxResponse`]
+macro_rules! delegate_tx {
+    ($value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            AnyCwEnv::Mock($inner) => $body.map(AnyTxResponse::Mock).map_err(Into::into),
+            #[cfg(feature = "daemon")]
+            AnyCwEnv::Daemon($inner) => $body.map(AnyTxResponse::Daemon).map_err(Into::into),
+            #[cfg(feature = "clone-testing")]
+            AnyCwEnv::CloneTesting($inner) => {
+                $body.map(AnyTxResponse::CloneTesting).map_err(Into::into)
+            }
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyCwEnv::OsmosisTestTube($inner) => $body
+                .map(AnyTxResponse::OsmosisTestTube)
+                .map_err(Into::into),
+        }
+    };
+}
+
+impl TxHandler for AnyCwEnv {
+    type Response = AnyTxResponse;
+
+    type Error = CwEnvError;
+
+    /// Contracts are uploaded with the [`Uploadable`] source of the selected environment
+    type ContractSource = ();
+
+    type Sender = AnySender;
+
+    fn sender(&self) -> Addr {
+        delegate!(AnyCwEnv, self, chain => chain.sender())
+    }
+
+    /// Leaves the sender unchanged and logs an error if the sender doesn't belong to the selected environment,
+    /// use [`AnyCwEnv::try_set_sender`] to handle the mismatch
+    fn set_sender(&mut self, sender: Self::Sender) {
+        if let Err(error) = self.try_set_sender(sender) {
+            log::error!("{error}");
+        }
+    }
+
+    fn upload<T: Uploadable>(&self, contract_source: &T) -> Result<Self::Response, Self::Error> {
+        delegate_tx!(self, chain => chain.upload(contract_source))
+    }
+
+    fn upload_from_bytes(&self, code: Vec<u8>) -> Result<Self::Response, Self::Error> {
+        delegate_tx!(self, chain => chain.upload_from_bytes(code))
+    }
+
+    fn instantiate<I: Serialize + Debug>(
+        &self,
+        code_id: u64,
+        init_msg: &I,
+        label: Option<&str>,
+        admin: Option<&Addr>,
+        coins: &[Coin],
+    ) -> Result<Self::Response, Self::Error> {
+        delegate_tx!(self, chain => chain.instantiate(code_id, init_msg, label, admin, coins))
+    }
+
+    fn instantiate2<I: Serialize + Debug>(
+        &self,
+        code_id: u64,
+        init_msg: &I,
+        label: Option<&str>,
+        admin: Option<&Addr>,
+        coins: &[Coin],
+        salt: Binary,
+    ) -> Result<Self::Response, Self::Error> {
+        delegate_tx!(self, chain => chain.instantiate2(code_id, init_msg, label, admin, coins, salt))
+    }
+
+    fn execute<E: Serialize + Debug>(
+        &self,
+        exec_msg: &E,
+        coins: &[Coin],
+        contract_address: &Addr,
+    ) -> Result<Self::Response, Self::Error> {
+        delegate_tx!(self, chain => chain.execute(exec_msg, coins, contract_address))
+    }
+
+    fn migrate<M: Serialize + Debug>(
+        &self,
+        migrate_msg: &M,
+        new_code_id: u64,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, Self::Error> {
+        delegate_tx!(self, chain => chain.migrate(migrate_msg, new_code_id, contract_address))
+    }
+}
+
+impl SudoHandler for AnyCwEnv {
+    fn sudo<S: Serialize + Debug>(
+        &self,
+        sudo_msg: &S,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, Self::Error> {
+        delegate_tx!(self, chain => chain.sudo(sudo_msg, contract_address))
+    }
+}
+
+impl QueryHandler for AnyCwEnv {
+    type Error = CwEnvError;
+
+    fn wait_blocks(&self, amount: u64) -> Result<(), Self::Error> {
+        delegate!(AnyCwEnv, self, chain => chain.wait_blocks(amount).map_err(Into::into))
+    }
+
+    fn wait_seconds(&self, secs: u64) -> Result<(), Self::Error> {
+        delegate!(AnyCwEnv, self, chain => chain.wait_seconds(secs).map_err(Into::into))
+    }
+
+    fn next_block(&self) -> Result<(), Self::Error> {
+        delegate!(AnyCwEnv, self, chain => chain.next_block().map_err(Into::into))
+    }
+}
+
+impl ChainTime for AnyCwEnv {
+    type Error = CwEnvError;
+
+    fn advance_blocks(&self, blocks: u64) -> Result<(), Self::Error> {
+        delegate!(AnyCwEnv, self, chain => chain.advance_blocks(blocks).map_err(Into::into))
+    }
+
+    fn advance_seconds(&self, seconds: u64) -> Result<(), Self::Error> {
+        delegate!(AnyCwEnv, self, chain => chain.advance_seconds(seconds).map_err(Into::into))
+    }
+
+    fn set_block_time(&self, time: Timestamp) -> Result<(), Self::Error> {
+        delegate!(AnyCwEnv, self, chain => chain.set_block_time(time).map_err(Into::into))
+    }
+}
+
+impl EnvironmentQuerier for AnyCwEnv {
+    fn env_info(&self) -> EnvironmentInfo {
+        delegate!(AnyCwEnv, self, chain => chain.env_info())
+    }
+}
+
+impl DefaultQueriers for AnyCwEnv {
+    type Bank = AnyBankQuerier;
+    type Wasm = AnyWasmQuerier;
+    type Node = AnyNodeQuerier;
+}
+
+/// Bank querier of an [`AnyCwEnv`]
+pub enum AnyBankQuerier {
+    /// Bank querier of a [`Mock`]
+    Mock(<Mock as DefaultQueriers>::Bank),
+    /// Bank querier of a [`Daemon`]
+    #[cfg(feature = "daemon")]
+    Daemon(<Daemon as DefaultQueriers>::Bank),
+    /// Bank querier of a [`CloneTesting`]
+    #[cfg(feature = "clone-testing")]
+    CloneTesting(<CloneTesting as DefaultQueriers>::Bank),
+    /// Bank querier of an [`OsmosisTestTube`]
+    #[cfg(feature = "osmosis-test-tube")]
+    OsmosisTestTube(<OsmosisTestTube as DefaultQueriers>::Bank),
+}
+
+impl QuerierGetter<AnyBankQuerier> for AnyCwEnv {
+    fn querier(&self) -> AnyBankQuerier {
+        match self {
+            AnyCwEnv::Mock(mock) => AnyBankQuerier::Mock(mock.bank_querier()),
+            #[cfg(feature = "daemon")]
+            AnyCwEnv::Daemon(daemon) => AnyBankQuerier::Daemon(daemon.bank_querier()),
+            #[cfg(feature = "clone-testing")]
+            AnyCwEnv::CloneTesting(clone_testing) => {
+                AnyBankQuerier::CloneTesting(clone_testing.bank_querier())
+            }
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyCwEnv::OsmosisTestTube(test_tube) => {
+                AnyBankQuerier::OsmosisTestTube(test_tube.bank_querier())
+            }
+        }
+    }
+}
+
+impl Querier for AnyBankQuerier {
+    type Error = CwEnvError;
+}
+
+impl BankQuerier for AnyBankQuerier {
+    fn balance(
+        &self,
+        address: impl Into<String>,
+        denom: Option<String>,
+    ) -> Result<Vec<Coin>, Self::Error> {
+        delegate!(AnyBankQuerier, self, querier => querier.balance(address, denom).map_err(Into::into))
+    }
+
+    fn total_supply(&self) -> Result<Vec<Coin>, Self::Error> {
+        delegate!(AnyBankQuerier, self, querier => querier.total_supply().map_err(Into::into))
+    }
+
+    fn supply_of(&self, denom: impl Into<String>) -> Result<Coin, Self::Error> {
+        delegate!(AnyBankQuerier, self, querier => querier.supply_of(denom).map_err(Into::into))
+    }
+}
+
+/// Node querier of an [`AnyCwEnv`]
+pub enum AnyNodeQuerier {
+    /// Node querier of a [`Mock`]
+    Mock(<Mock as DefaultQueriers>::Node),
+    /// Node querier of a [`Daemon`]
+    #[cfg(feature = "daemon")]
+    Daemon(<Daemon as DefaultQueriers>::Node),
+    /// Node querier of a [`CloneTesting`]
+    #[cfg(feature = "clone-testing")]
+    CloneTesting(<CloneTesting as DefaultQueriers>::Node),
+    /// Node querier of an [`OsmosisTestTube`]
+    #[cfg(feature = "osmosis-test-tube")]
+    OsmosisTestTube(<OsmosisTestTube as DefaultQueriers>::Node),
+}
+
+impl QuerierGetter<AnyNodeQuerier> for AnyCwEnv {
+    fn querier(&self) -> AnyNodeQuerier {
+        match self {
+            AnyCwEnv::Mock(mock) => AnyNodeQuerier::Mock(mock.node_querier()),
+            #[cfg(feature = "daemon")]
+            AnyCwEnv::Daemon(daemon) => AnyNodeQuerier::Daemon(daemon.node_querier()),
+            #[cfg(feature = "clone-testing")]
+            AnyCwEnv::CloneTesting(clone_testing) => {
+                AnyNodeQuerier::CloneTesting(clone_testing.node_querier())
+            }
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyCwEnv::OsmosisTestTube(test_tube) => {
+                AnyNodeQuerier::OsmosisTestTube(test_tube.node_querier())
+            }
+        }
+    }
+}
+
+impl Querier for AnyNodeQuerier {
+    type Error = CwEnvError;
+}
+
+impl NodeQuerier for AnyNodeQuerier {
+    type Response = AnyTxResponse;
+
+    fn latest_block(&self) -> Result<BlockInfo, Self::Error> {
+        delegate!(AnyNodeQuerier, self, querier => querier.latest_block().map_err(Into::into))
+    }
+
+    fn block_by_height(&self, height: u64) -> Result<BlockInfo, Self::Error> {
+        delegate!(AnyNodeQuerier, self, querier => querier.block_by_height(height).map_err(Into::into))
+    }
+
+    fn block_height(&self) -> Result<u64, Self::Error> {
+        delegate!(AnyNodeQuerier, self, querier => querier.block_height().map_err(Into::into))
+    }
+
+    fn block_time(&self) -> Result<u128, Self::Error> {
+        delegate!(AnyNodeQuerier, self, querier => querier.block_time().map_err(Into::into))
+    }
+
+    fn simulate_tx(&self, tx_bytes: Vec<u8>) -> Result<u64, Self::Error> {
+        delegate!(AnyNodeQuerier, self, querier => querier.simulate_tx(tx_bytes).map_err(Into::into))
+    }
+
+    fn find_tx(&self, hash: String) -> Result<Self::Response, Self::Error> {
+        match self {
+            AnyNodeQuerier::Mock(querier) => querier
+                .find_tx(hash)
+                .map(AnyTxResponse::Mock)
+                .map_err(Into::into),
+            #[cfg(feature = "daemon")]
+            AnyNodeQuerier::Daemon(querier) => querier
+                .find_tx(hash)
+                .map(AnyTxResponse::Daemon)
+                .map_err(Into::into),
+            #[cfg(feature = "clone-testing")]
+            AnyNodeQuerier::CloneTesting(querier) => querier
+                .find_tx(hash)
+                .map(AnyTxResponse::CloneTesting)
+                .map_err(Into::into),
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyNodeQuerier::OsmosisTestTube(querier) => querier
+                .find_tx(hash)
+                .map(AnyTxResponse::OsmosisTestTube)
+                .map_err(Into::into),
+        }
+    }
+}
+
+/// Wasm querier of an [`AnyCwEnv`]
+pub enum AnyWasmQuerier {
+    /// Wasm querier of a [`Mock`]
+    Mock(<Mock as DefaultQueriers>::Wasm),
+    /// Wasm querier of a [`Daemon`]
+    #[cfg(feature = "daemon")]
+    Daemon(<Daemon as DefaultQueriers>::Wasm),
+    /// Wasm querier of a [`CloneTesting`]
+    #[cfg(feature = "clone-testing")]
+    CloneTesting(<CloneTesting as DefaultQueriers>::Wasm),
+    /// Wasm querier of an [`OsmosisTestTube`]
+    #[cfg(feature = "osmosis-test-tube")]
+    OsmosisTestTube(<OsmosisTestTube as DefaultQueriers>::Wasm),
+}
+
+impl QuerierGetter<AnyWasmQuerier> for AnyCwEnv {
+    fn querier(&self) -> AnyWasmQuerier {
+        match self {
+            AnyCwEnv::Mock(mock) => AnyWasmQuerier::Mock(mock.wasm_querier()),
+            #[cfg(feature = "daemon")]
+            AnyCwEnv::Daemon(daemon) => AnyWasmQuerier::Daemon(daemon.wasm_querier()),
+            #[cfg(feature = "clone-testing")]
+            AnyCwEnv::CloneTesting(clone_testing) => {
+                AnyWasmQuerier::CloneTesting(clone_testing.wasm_querier())
+            }
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyCwEnv::OsmosisTestTube(test_tube) => {
+                AnyWasmQuerier::OsmosisTestTube(test_tube.wasm_querier())
+            }
+        }
+    }
+}
+
+impl Querier for AnyWasmQuerier {
+    type Error = CwEnvError;
+}
+
+impl WasmQuerier for AnyWasmQuerier {
+    type Chain = AnyCwEnv;
+
+    fn code_id_hash(&self, code_id: u64) -> Result<HexBinary, Self::Error> {
+        delegate!(AnyWasmQuerier, self, querier => querier.code_id_hash(code_id).map_err(Into::into))
+    }
+
+    fn contract_info(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<ContractInfoResponse, Self::Error> {
+        delegate!(AnyWasmQuerier, self, querier => querier.contract_info(address).map_err(Into::into))
+    }
+
+    fn raw_query(
+        &self,
+        address: impl Into<String>,
+        query_keys: Vec<u8>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        delegate!(AnyWasmQuerier, self, querier => querier.raw_query(address, query_keys).map_err(Into::into))
+    }
+
+    fn smart_query<Q: Serialize, T: DeserializeOwned>(
+        &self,
+        address: impl Into<String>,
+        query_msg: &Q,
+    ) -> Result<T, Self::Error> {
+        delegate!(AnyWasmQuerier, self, querier => querier.smart_query(address, query_msg).map_err(Into::into))
+    }
+
+    fn code(&self, code_id: u64) -> Result<CodeInfoResponse, Self::Error> {
+        delegate!(AnyWasmQuerier, self, querier => querier.code(code_id).map_err(Into::into))
+    }
+
+    fn code_id_by_checksum(
+        &self,
+        checksum: &HexBinary,
+        instantiator: &Addr,
+    ) -> Result<Option<u64>, Self::Error> {
+        delegate!(AnyWasmQuerier, self, querier => querier.code_id_by_checksum(checksum, instantiator).map_err(Into::into))
+    }
+
+    fn instantiate2_addr(
+        &self,
+        code_id: u64,
+        creator: impl Into<String>,
+        salt: Binary,
+    ) -> Result<String, Self::Error> {
+        delegate!(AnyWasmQuerier, self, querier => querier.instantiate2_addr(code_id, creator, salt).map_err(Into::into))
+    }
+
+    /// Same hash as the querier of the selected environment: the hashed contract id on [`Mock`] and `CloneTesting`,
+    /// the checksum of the wasm code on live chains and test-tubes
+    fn local_hash<T: Uploadable + ContractInstance<Self::Chain>>(
+        &self,
+        contract: &T,
+    ) -> Result<HexBinary, CwEnvError> {
+        match contract.get_chain() {
+            AnyCwEnv::Mock(_) => {
+                let hash: [u8; 32] = Sha256::digest(contract.id()).into();
+                Ok(hash.into())
+            }
+            #[cfg(feature = "clone-testing")]
+            AnyCwEnv::CloneTesting(_) => {
+                let hash: [u8; 32] = Sha256::digest(contract.id()).into();
+                Ok(hash.into())
+            }
+            #[cfg(feature = "osmosis-test-tube")]
+            AnyCwEnv::OsmosisTestTube(_) => {
+                let chain_info = cw_orch_osmosis_test_tube::MOCK_CHAIN_INFO.into();
+                if let Some(code) = <T as Uploadable>::wasm_bytes(&chain_info) {
+                    let checksum: [u8; 32] = Sha256::digest(code).into();
+                    return Ok(checksum.into());
+                }
+                <T as Uploadable>::wasm(&chain_info).checksum()
+            }
+            #[cfg(feature = "daemon")]
+            AnyCwEnv::Daemon(daemon) => {
+                let chain_info = daemon.daemon.sender.chain_info();
+                if let Some(code) = <T as Uploadable>::wasm_bytes(chain_info) {
+                    let checksum: [u8; 32] = Sha256::digest(code).into();
+                    return Ok(checksum.into());
+                }
+                <T as Uploadable>::wasm(chain_info).checksum()
+            }
+        }
+    }
+}
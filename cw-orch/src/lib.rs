@@ -4,7 +4,7 @@
 
 // macros
 pub use cw_orch_contract_derive::interface;
-pub use cw_orch_fns_derive::{ExecuteFns, QueryFns};
+pub use cw_orch_fns_derive::{ExecuteFns, QueryFns, SudoFns};
 
 // prelude
 #[cfg(not(target_arch = "wasm32"))]
@@ -13,6 +13,9 @@ pub mod prelude;
 #[cfg(not(target_arch = "wasm32"))]
 mod error;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod any_env;
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "daemon")]
 pub mod daemon;
@@ -30,7 +33,7 @@ pub mod wasm_protected {
 
     // This re-export should not be touched or the derive macros WILL break
     pub use cw_orch_core as core;
-    pub use cw_orch_core::{build, contract};
+    pub use cw_orch_core::{build, contract, gas};
 
     /// Related to execution environments
     pub mod environment {
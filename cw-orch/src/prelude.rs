@@ -12,27 +12,31 @@
 
 // Contract traits
 pub use crate::contract::interface_traits::{
-    CallAs, ConditionalMigrate, ConditionalUpload, ContractInstance, CwOrchExecute,
-    CwOrchInstantiate, CwOrchMigrate, CwOrchQuery, CwOrchUpload, ExecutableContract,
-    InstantiableContract, MigratableContract, QueryableContract, Uploadable,
+    AsyncCwOrchExecute, AsyncCwOrchQuery, CallAs, ConditionalMigrate, ConditionalUpload,
+    ContractInstance, CwOrchExecute, CwOrchInstantiate, CwOrchMigrate, CwOrchQuery, CwOrchSudo,
+    CwOrchUpload, CwOrchVersionedMigrate, ExecutableContract, InstantiableContract,
+    MigratableContract, QueryableContract, SudoableContract, Uploadable,
 };
 
-pub use cw_orch_core::contract::Deploy;
+pub use cw_orch_core::contract::{
+    Deploy, DeploymentExport, MigrationPlan, Recording, VersionedMigrateMsgs,
+};
 
 pub use crate::environment::ChainState;
 pub use crate::environment::StateInterface;
 
 // Response trait
-pub use crate::environment::IndexResponse;
+pub use crate::environment::{EventAssertions, IndexResponse};
 
 // Environment
 pub use crate::environment::{
-    BankQuerier, BankSetter, CwEnv, DefaultQueriers, EnvironmentInfo, EnvironmentQuerier,
-    NodeQuerier, QuerierGetter, QueryHandler, TxHandler, TxResponse, WasmQuerier,
+    AsyncTxHandler, AsyncWasmQuerier, BankQuerier, BankSetter, ChainTime, CwEnv, DefaultQueriers,
+    EnvironmentInfo, EnvironmentQuerier, NodeQuerier, QuerierGetter, QueryHandler,
+    StorageInspector, StorageKey, SudoHandler, TxHandler, TxResponse, WasmQuerier,
 };
 
 // Chains
-pub use crate::environment::{ChainInfo, ChainInfoOwned};
+pub use crate::environment::{ChainId, ChainInfo, ChainInfoOwned, TypedChain};
 
 // Mock for testing
 pub use crate::mock::{Mock, MockBech32};
@@ -0,0 +1,42 @@
+use cw_orch::any_env::{AnyCwEnv, AnySender, AnyTxResponse};
+use cw_orch::prelude::*;
+use mock_contract::{ExecuteMsg, InstantiateMsg, MockContract, QueryMsg};
+
+#[test]
+fn mock_any_env() -> anyhow::Result<()> {
+    let chain: AnyCwEnv = Mock::new("sender").into();
+    let contract = MockContract::new("test:mock_contract", chain.clone());
+    contract.upload()?;
+    contract.instantiate(&InstantiateMsg {}, None, None)?;
+
+    let response = contract.execute(&ExecuteMsg::FirstMessage {}, None)?;
+    assert!(matches!(response, AnyTxResponse::Mock(_)));
+    assert_eq!(
+        response.event_attr_value("wasm", "action")?,
+        "first message passed"
+    );
+
+    let response: String = contract.query(&QueryMsg::FirstQuery {})?;
+    assert_eq!(response, "first query passed");
+
+    // The state and queriers are the ones of the wrapped environment
+    let mock = chain.as_mock().unwrap();
+    assert_eq!(
+        mock.state().get_address("test:mock_contract")?,
+        contract.address()?
+    );
+    assert_eq!(
+        chain.wasm_querier().code_id_hash(contract.code_id()?)?,
+        mock.wasm_querier().code_id_hash(contract.code_id()?)?
+    );
+    Ok(())
+}
+
+#[test]
+fn mock_any_env_set_sender() -> anyhow::Result<()> {
+    let mut chain: AnyCwEnv = Mock::new("sender").into();
+    let new_sender = Addr::unchecked("new_sender");
+    chain.set_sender(AnySender::Mock(new_sender.clone()));
+    assert_eq!(chain.sender(), new_sender);
+    Ok(())
+}
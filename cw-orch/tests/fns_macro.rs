@@ -1,10 +1,28 @@
-use mock_contract::{ExecuteMsgFns, InstantiateMsg, MockContract, QueryMsgFns};
+use mock_contract::{
+    AsyncExecuteMsgFns, AsyncQueryMsgFns, ExecuteMsgFns, InstantiateMsg, MockContract, QueryMsgFns,
+};
 
 use cosmwasm_std::Event;
 
 use cw_orch::prelude::CwOrchUpload;
 use cw_orch::prelude::{CwOrchInstantiate, Mock};
 
+/// Message returning the action attribute of the response instead of the response
+#[cosmwasm_schema::cw_serde]
+#[derive(cw_orch::ExecuteFns)]
+pub enum ActionMsg {
+    #[cw_orch(fn_name("first_action"), event_attr("wasm", "action"))]
+    FirstMessage {},
+}
+
+impl From<ActionMsg> for mock_contract::ExecuteMsg {
+    fn from(msg: ActionMsg) -> Self {
+        match msg {
+            ActionMsg::FirstMessage {} => mock_contract::ExecuteMsg::FirstMessage {},
+        }
+    }
+}
+
 #[test]
 fn test_execute() {
     let contract = MockContract::new("test:mock_contract", Mock::new("Ghazshag"));
@@ -39,3 +57,44 @@ fn test_query() {
 
     contract.second_query("".to_string()).unwrap_err();
 }
+
+#[test]
+fn test_event_attr() {
+    let contract = MockContract::new("test:mock_contract", Mock::new("Ghazshag"));
+    contract.upload().unwrap();
+
+    contract
+        .instantiate(&InstantiateMsg {}, None, None)
+        .unwrap();
+
+    let action: String = contract.first_action().unwrap();
+    assert_eq!(action, "first message passed");
+}
+
+#[tokio::test]
+async fn test_async_fns() {
+    let contract = MockContract::new("test:mock_contract", Mock::new("Ghazshag"));
+    contract.upload().unwrap();
+
+    contract
+        .instantiate(&InstantiateMsg {}, None, None)
+        .unwrap();
+
+    let response = contract.first_message_async().await.unwrap();
+    response.has_event(
+        &Event::new("wasm")
+            .add_attribute("_contract_addr", "contract0")
+            .add_attribute("action", "first message passed"),
+    );
+    contract
+        .second_message_async("".to_string(), &[])
+        .await
+        .unwrap_err();
+
+    let response = contract.first_query_async().await.unwrap();
+    assert_eq!(response, "first query passed");
+    contract
+        .second_query_async("".to_string())
+        .await
+        .unwrap_err();
+}
@@ -8,7 +8,7 @@ use mock_contract::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use cosmwasm_std::Event;
 use cw_orch::prelude::{
     ContractInstance, CwOrchExecute, CwOrchInstantiate, CwOrchMigrate, CwOrchQuery, CwOrchUpload,
-    Mock,
+    CwOrchVersionedMigrate, Mock, VersionedMigrateMsgs,
 };
 
 use cw_orch::interface;
@@ -35,6 +35,27 @@ impl<Chain> Uploadable for MockContract<Chain> {
     }
 }
 
+#[cosmwasm_schema::cw_serde]
+pub struct MigrateFromV1 {
+    pub new_admin: Option<String>,
+}
+
+#[interface(
+    InstantiateMsg,
+    ExecuteMsg,
+    QueryMsg,
+    MigrateMsg,
+    id = "test:versioned_mock_contract",
+    migrate_versions(MigrateMsg = "<1.0.0", MigrateFromV1 = ">=1.0.0, <2.0.0")
+)]
+pub struct VersionedMockContract;
+
+impl<Chain> Uploadable for VersionedMockContract<Chain> {
+    fn wrapper() -> <Mock as TxHandler>::ContractSource {
+        MockContract::<Chain>::wrapper()
+    }
+}
+
 #[test]
 fn test_instantiate() {
     let contract = MockContract::new(Mock::new("Ghazshag"));
@@ -116,3 +137,34 @@ fn test_migrate() {
         .unwrap();
     assert_eq!(response.events.len(), 1);
 }
+
+#[test]
+fn test_versioned_migrate() {
+    let chain = Mock::new("Ghazshag");
+    let contract = VersionedMockContract::new(chain.clone());
+    contract.upload().unwrap();
+
+    contract
+        .instantiate(&InstantiateMsg {}, Some(&chain.sender()), None)
+        .unwrap();
+    // The mock contract sets its cw2 version to "0"
+    assert_eq!(contract.contract_version().unwrap().version, "0");
+
+    // No message provided for the on-chain version
+    let msgs = VersionedMigrateMsgs::new()
+        .with(&MigrateFromV1 { new_admin: None })
+        .unwrap();
+    contract
+        .versioned_migrate(&msgs, contract.code_id().unwrap())
+        .unwrap_err();
+
+    let msgs = msgs
+        .with(&MigrateMsg {
+            t: "success".to_string(),
+        })
+        .unwrap();
+    let response = contract
+        .versioned_migrate(&msgs, contract.code_id().unwrap())
+        .unwrap();
+    assert_eq!(response.events.len(), 1);
+}
@@ -1,8 +1,31 @@
 use cw_orch::{interface, prelude::*};
 use mock_contract::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
+use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Event;
 use cw_orch::prelude::Mock;
+use serde::Serialize;
+
+/// Generic query message whose return type depends on its generic parameter
+#[cosmwasm_schema::cw_serde]
+#[derive(QueryResponses, cw_orch::QueryFns)]
+pub enum GenericQueryMsg<T>
+where
+    T: Serialize,
+{
+    /// Queries the fourth value
+    #[returns(T)]
+    #[cw_orch(fn_name("fourth"))]
+    FourthQuery(u64, String),
+}
+
+impl<T: Serialize> From<GenericQueryMsg<T>> for QueryMsg {
+    fn from(msg: GenericQueryMsg<T>) -> Self {
+        match msg {
+            GenericQueryMsg::FourthQuery(n, s) => QueryMsg::FourthQuery(n, s),
+        }
+    }
+}
 
 #[interface(InstantiateMsg, ExecuteMsg<T>, QueryMsg, MigrateMsg, id = "test:mock_contract")]
 pub struct MockContract;
@@ -107,3 +130,18 @@ fn test_migrate() {
         .unwrap();
     assert_eq!(response.events.len(), 1);
 }
+
+#[test]
+fn test_generic_returns() {
+    let contract = MockContract::<_, u64>::new(Mock::new("Ghazshag"));
+
+    contract.upload().unwrap();
+
+    contract
+        .instantiate(&InstantiateMsg {}, None, None)
+        .unwrap();
+
+    // The return type of the generated function is the generic parameter of the message
+    let response: String = contract.fourth(1, "fourth".to_string()).unwrap();
+    assert_eq!(response, "fourth query passed");
+}
@@ -15,6 +15,7 @@ pub const NEW_CHAIN_INFO: ChainInfo = ChainInfo {
     grpc_urls: &["Some GRPC URLS"],
     lcd_url: None, // Not necessary for cw-orch
     fcd_url: None, // Not necessary for cw-orch
+    rpc_url: None,
     network_info: NEW_NETWORK_INFO,
     kind: ChainKind::Mainnet,
 };
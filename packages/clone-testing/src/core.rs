@@ -6,19 +6,24 @@ use clone_cw_multi_test::{
     App, AppBuilder, BankKeeper, Contract, Executor, WasmKeeper,
 };
 use cosmwasm_std::{to_json_binary, WasmMsg};
-use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, Empty, Event, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    Addr, Api, Binary, BlockInfo, CanonicalAddr, Coin, ContractInfoResponse, CosmosMsg, Empty,
+    Event, StdResult, Uint128,
+};
 use cw_orch_core::{
     contract::interface_traits::Uploadable,
     environment::{
-        BankQuerier, BankSetter, ChainInfoOwned, ChainState, DefaultQueriers, IndexResponse,
-        StateInterface, TxHandler,
+        missing_event_attr_error, BankQuerier, BankSetter, ChainInfoOwned, ChainState,
+        DefaultQueriers, FundsManager, IndexResponse, StateInterface, SudoHandler, TxHandler,
     },
     CwEnvError,
 };
 use cw_orch_daemon::queriers::Node;
 use cw_orch_daemon::{GrpcChannel, DEFAULT_DEPLOYMENT};
+use cw_orch_mock::fixture::MockFixture;
 use cw_utils::NativeBalance;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use tokio::runtime::Runtime;
 
 use crate::{contract::CloneTestingContract, queriers::bank::CloneBankQuerier};
@@ -74,6 +79,8 @@ pub struct CloneTesting<S: StateInterface = MockState> {
     pub state: Rc<RefCell<S>>,
     /// Inner mutable cw-multi-test app backend
     pub app: Rc<RefCell<CloneTestingApp>>,
+    /// Connection to the forked chain
+    remote: RemoteChannel,
 }
 
 impl CloneTesting {
@@ -181,35 +188,24 @@ impl<S: StateInterface> CloneTesting<S> {
         let chain: ChainInfoOwned = chain.into();
         let state = Rc::new(RefCell::new(custom_state));
 
-        let pub_address_prefix = &chain.network_info.pub_address_prefix;
         let remote_channel = RemoteChannel::new(
             rt,
             get_channel(chain.clone(), rt)?,
-            pub_address_prefix.clone(),
+            chain.network_info.pub_address_prefix.clone(),
         )
         .unwrap();
 
-        let wasm = WasmKeeper::<Empty, Empty>::new()
-            .with_remote(remote_channel.clone())
-            .with_address_generator(MockAddressGenerator);
-
-        let bank = BankKeeper::new().with_remote(remote_channel.clone());
-
         // We update the block_height
         let block_info = remote_channel
             .rt
             .block_on(Node::new_async(remote_channel.channel.clone())._block_info())
             .unwrap();
 
-        // Finally we instantiate a new app
-        let app = AppBuilder::default()
-            .with_wasm(wasm)
-            .with_bank(bank)
-            .with_api(MockApiBech32::new(pub_address_prefix))
-            .with_block(block_info)
-            .with_remote(remote_channel.clone());
-
-        let app = Rc::new(RefCell::new(app.build(|_, _, _| {})?));
+        let app = Rc::new(RefCell::new(build_app(
+            &remote_channel,
+            &chain,
+            block_info,
+        )?));
         let sender = app.borrow_mut().next_address();
 
         Ok(Self {
@@ -217,12 +213,138 @@ impl<S: StateInterface> CloneTesting<S> {
             sender: sender.clone(),
             state,
             app,
+            remote: remote_channel,
         })
     }
 
+    /// Re-forks the chain at the latest block of the node.
+    /// All the local writes (balances, uploaded code, contract instantiations and executions) are discarded
+    /// and the environment starts over from the current remote state.
+    /// The remote connection is reused, so no new connection to the node is made.
+    ///
+    /// The deployment state (`contract_id -> code_id/address`) is left untouched,
+    /// entries pointing to locally deployed contracts need to be removed or re-deployed by the caller.
+    pub fn refork(&self) -> Result<(), CwEnvError> {
+        let block_info = self.latest_block_info()?;
+        self.refork_with(block_info)
+    }
+
+    /// Re-forks the chain at the given block height, see [`Self::refork`].
+    ///
+    /// Remote storage is read through `clone-cw-multi-test`, which always queries the node at its latest height.
+    /// Forking at a past height would mix the block environment of that height with the latest storage,
+    /// so any height other than the latest block of the node is rejected.
+    pub fn refork_at(&self, height: u64) -> Result<(), CwEnvError> {
+        let block_info = self.latest_block_info()?;
+        if block_info.height != height {
+            return Err(CwEnvError::StdErr(format!(
+                "Can't fork {} at height {height}, the remote storage is only readable at the latest height {}",
+                self.chain.chain_id, block_info.height
+            )));
+        }
+        self.refork_with(block_info)
+    }
+
+    fn latest_block_info(&self) -> Result<BlockInfo, CwEnvError> {
+        Ok(self
+            .remote
+            .rt
+            .block_on(Node::new_async(self.remote.channel.clone())._block_info())?)
+    }
+
+    fn refork_with(&self, block_info: BlockInfo) -> Result<(), CwEnvError> {
+        let app = build_app(&self.remote, &self.chain, block_info)?;
+        *self.app.borrow_mut() = app;
+        Ok(())
+    }
+
+    /// Clone of this environment sending its transactions as the `contract`.
+    /// The contract can be a local or a forked one, it is checked to exist.
+    ///
+    /// Contract interfaces can then be called as the contract, for admin-gated or owner-gated paths:
+    /// ```rust,ignore
+    /// let as_owner = chain.call_as_contract(&owner_contract)?;
+    /// my_contract.call_as(&as_owner.sender()).update_config(...)?;
+    /// ```
+    pub fn call_as_contract(&self, contract: &Addr) -> Result<Self, CwEnvError> {
+        self.contract_info(contract)?;
+        Ok(self.call_as(contract))
+    }
+
+    /// Clone of this environment sending its transactions as the admin of `contract`, to migrate it.
+    /// The admin can be an account, a contract or the gov module.
+    pub fn call_as_admin_of(&self, contract: &Addr) -> Result<Self, CwEnvError> {
+        let admin = self
+            .contract_info(contract)?
+            .admin
+            .ok_or_else(|| CwEnvError::StdErr(format!("Contract {contract} has no admin")))?;
+        Ok(self.call_as(&Addr::unchecked(admin)))
+    }
+
+    /// Clone of this environment sending its transactions as the gov module of the forked chain
+    pub fn call_as_gov(&self) -> Result<Self, CwEnvError> {
+        Ok(self.call_as(&self.module_address("gov")?))
+    }
+
+    /// Address of a module account of the forked chain (e.g. `gov`, `distribution`)
+    pub fn module_address(&self, module_name: &str) -> Result<Addr, CwEnvError> {
+        let hash = Sha256::digest(module_name.as_bytes());
+        let canonical = CanonicalAddr::from(&hash[..20]);
+        Ok(self.app.borrow().api().addr_humanize(&canonical)?)
+    }
+
+    fn contract_info(&self, contract: &Addr) -> Result<ContractInfoResponse, CwEnvError> {
+        Ok(self
+            .app
+            .borrow()
+            .wrap()
+            .query_wasm_contract_info(contract)?)
+    }
+
     pub fn storage_analysis(&self) -> StorageAnalyzer {
         StorageAnalyzer::new(&self.app.borrow()).unwrap()
     }
+
+    /// Sets the balances, uploads the codes and instantiates the contracts of a [`MockFixture`] on top of the forked state.
+    /// Code ids and contract addresses are registered in the state, so contract interfaces with the same ids can be used right away
+    pub fn load_fixture(&self, fixture: &MockFixture) -> Result<(), CwEnvError> {
+        self.app
+            .borrow_mut()
+            .init_modules(|router, _, storage| -> Result<(), CwEnvError> {
+                for (address, amount) in fixture.balances() {
+                    router
+                        .bank
+                        .init_balance(storage, &Addr::unchecked(address), amount.clone())?;
+                }
+                Ok(())
+            })?;
+
+        for (code_id, wrapper) in fixture.codes() {
+            let wrapper_contract = CloneTestingContract::new(wrapper());
+            let code = self.app.borrow_mut().store_code(Box::new(wrapper_contract));
+            self.state.borrow_mut().set_code_id(code_id, code);
+        }
+
+        for contract in fixture.contracts() {
+            let code_id = self.state.borrow().get_code_id(&contract.code)?;
+            let sender = contract
+                .sender
+                .as_ref()
+                .map(Addr::unchecked)
+                .unwrap_or_else(|| self.sender.clone());
+            let resp: AppResponse = self
+                .app
+                .borrow_mut()
+                .execute(sender, CosmosMsg::Wasm(contract.instantiate_msg(code_id)))?
+                .into();
+            let address = IndexResponse::instantiated_contract_address(&resp)?;
+
+            let mut state = self.state.borrow_mut();
+            state.set_code_id(&contract.contract_id, code_id);
+            state.set_address(&contract.contract_id, &address);
+        }
+        Ok(())
+    }
 }
 
 impl<S: StateInterface> ChainState for CloneTesting<S> {
@@ -261,6 +383,19 @@ impl<S: StateInterface> TxHandler for CloneTesting<S> {
         Ok(resp)
     }
 
+    /// The wasm code is run by the wasm emulation of the cloned chain, like the codes of the remote contracts
+    fn upload_from_bytes(&self, code: Vec<u8>) -> Result<Self::Response, CwEnvError> {
+        let code_id = self.app.borrow_mut().store_wasm_code(code);
+        // add contract code_id to events manually
+        let mut event = Event::new("store_code");
+        event = event.add_attribute("code_id", code_id.to_string());
+        let resp = AppResponse {
+            events: vec![event],
+            ..Default::default()
+        };
+        Ok(resp)
+    }
+
     fn execute<E: Serialize + Debug>(
         &self,
         exec_msg: &E,
@@ -353,6 +488,20 @@ impl<S: StateInterface> TxHandler for CloneTesting<S> {
     }
 }
 
+impl<S: StateInterface> SudoHandler for CloneTesting<S> {
+    fn sudo<M: Serialize + Debug>(
+        &self,
+        sudo_msg: &M,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, CwEnvError> {
+        self.app
+            .borrow_mut()
+            .wasm_sudo(contract_address.clone(), sudo_msg)
+            .map_err(From::from)
+            .map(Into::into)
+    }
+}
+
 /// Custom AppResponse type for working with the IndexResponse trait
 #[derive(Default, Clone, Debug)]
 pub struct AppResponse {
@@ -397,10 +546,7 @@ impl IndexResponse for AppResponse {
                 }
             }
         }
-        Err(StdError::generic_err(format!(
-            "missing combination (event: {}, attribute: {})",
-            event_type, attr_key
-        )))
+        Err(missing_event_attr_error(&self.events, event_type, attr_key))
     }
 
     fn event_attr_values(&self, event_type: &str, attr_key: &str) -> Vec<String> {
@@ -430,6 +576,41 @@ impl BankSetter for CloneTesting {
     }
 }
 
+impl FundsManager for CloneTesting {
+    type Error = CwEnvError;
+
+    fn set_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        CloneTesting::set_balance(self, &Addr::unchecked(address), amount)
+    }
+
+    fn add_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        CloneTesting::add_balance(self, &Addr::unchecked(address), amount)
+    }
+}
+
+/// Builds a new app forking the remote chain at the given block
+fn build_app(
+    remote_channel: &RemoteChannel,
+    chain: &ChainInfoOwned,
+    block_info: BlockInfo,
+) -> Result<CloneTestingApp, CwEnvError> {
+    let wasm = WasmKeeper::<Empty, Empty>::new()
+        .with_remote(remote_channel.clone())
+        .with_address_generator(MockAddressGenerator);
+
+    let bank = BankKeeper::new().with_remote(remote_channel.clone());
+
+    // Finally we instantiate a new app
+    let app = AppBuilder::default()
+        .with_wasm(wasm)
+        .with_bank(bank)
+        .with_api(MockApiBech32::new(&chain.network_info.pub_address_prefix))
+        .with_block(block_info)
+        .with_remote(remote_channel.clone());
+
+    Ok(app.build(|_, _, _| {})?)
+}
+
 /// Simple helper to get the GRPC transport channel
 fn get_channel(
     chain: impl Into<ChainInfoOwned>,
@@ -658,4 +839,67 @@ mod test {
             .contains_all_of(&[&Coin::new(amount, denom_1), &Coin::new(amount, denom_2)]);
         Ok(())
     }
+
+    #[test]
+    fn refork() -> anyhow::Result<()> {
+        let rt = Runtime::new().unwrap();
+        let chain = CloneTesting::new(&rt, JUNO_1)?;
+        let recipient = &chain.init_account();
+        chain.set_balance(recipient, vec![Coin::new(100, "ujuno")])?;
+
+        let height = chain.block_info()?.height;
+        chain.refork()?;
+
+        asserting("block height moved to the latest block")
+            .that(&chain.block_info()?.height)
+            .is_greater_than_or_equal_to(height);
+        asserting("local writes were discarded")
+            .that(&chain.query_all_balances(recipient)?)
+            .is_empty();
+
+        asserting("past heights can't be forked")
+            .that(&chain.refork_at(height - 100).is_err())
+            .is_true();
+        Ok(())
+    }
+
+    #[test]
+    fn call_as_contract() -> anyhow::Result<()> {
+        let rt = Runtime::new().unwrap();
+        let chain = CloneTesting::new(&rt, JUNO_1)?;
+        let sender = chain.sender();
+
+        chain.upload(&MockCw20)?;
+        let code_id = (1 + LOCAL_RUST_CODE_OFFSET) as u64;
+        let init_msg = cw20_base::msg::InstantiateMsg {
+            name: String::from("Token"),
+            symbol: String::from("TOK"),
+            decimals: 6u8,
+            initial_balances: vec![],
+            mint: None,
+            marketing: None,
+        };
+        let contract = chain
+            .instantiate(code_id, &init_msg, None, Some(&sender), &[])?
+            .instantiated_contract_address()?;
+
+        let as_contract = chain.call_as_contract(&contract)?;
+        asserting("sender is the contract")
+            .that(&as_contract.sender())
+            .is_equal_to(&contract);
+        asserting("only contracts can be impersonated")
+            .that(&chain.call_as_contract(&chain.init_account()).is_err())
+            .is_true();
+
+        let as_admin = as_contract.call_as_admin_of(&contract)?;
+        asserting("sender is the admin")
+            .that(&as_admin.sender())
+            .is_equal_to(&sender);
+
+        let gov = chain.call_as_gov()?.sender();
+        asserting("gov module address has the chain prefix")
+            .that(&gov.as_str().starts_with("juno1"))
+            .is_true();
+        Ok(())
+    }
 }
@@ -2,8 +2,9 @@ use crate::{CloneTesting, MockState};
 
 use clone_cw_multi_test::next_block;
 
+use cosmwasm_std::Timestamp;
 use cw_orch_core::{
-    environment::{DefaultQueriers, QueryHandler},
+    environment::{ChainTime, DefaultQueriers, QueryHandler},
     CwEnvError,
 };
 pub mod bank;
@@ -36,6 +37,28 @@ impl QueryHandler for CloneTesting {
     }
 }
 
+impl ChainTime for CloneTesting {
+    type Error = CwEnvError;
+
+    fn advance_blocks(&self, blocks: u64) -> Result<(), CwEnvError> {
+        self.wait_blocks(blocks)
+    }
+
+    fn advance_seconds(&self, seconds: u64) -> Result<(), CwEnvError> {
+        self.wait_seconds(seconds)
+    }
+
+    fn set_block_time(&self, time: Timestamp) -> Result<(), CwEnvError> {
+        self.app.borrow_mut().update_block(|b| {
+            if time > b.time {
+                b.height += (time.seconds() - b.time.seconds()) / 5;
+            }
+            b.time = time;
+        });
+        Ok(())
+    }
+}
+
 impl DefaultQueriers for CloneTesting {
     type Bank = bank::CloneBankQuerier;
     type Wasm = wasm::CloneWasmQuerier<MockState>;
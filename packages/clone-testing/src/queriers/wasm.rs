@@ -4,10 +4,12 @@ use std::{cell::RefCell, rc::Rc};
 use crate::{core::CloneTestingApp, CloneTesting};
 use clone_cw_multi_test::AddressGenerator;
 use clone_cw_multi_test::CosmosRouter;
-use cosmwasm_std::{instantiate2_address, Api, ContractInfoResponse, HexBinary};
+use cosmwasm_std::{instantiate2_address, Addr, Api, ContractInfoResponse, HexBinary};
 use cw_orch_core::{
     contract::interface_traits::{ContractInstance, Uploadable},
-    environment::{Querier, QuerierGetter, StateInterface, WasmQuerier},
+    environment::{
+        Querier, QuerierGetter, StateInterface, StorageDump, StorageInspector, WasmQuerier,
+    },
     CwEnvError,
 };
 use serde::{de::DeserializeOwned, Serialize};
@@ -132,3 +134,18 @@ impl<S: StateInterface> WasmQuerier for CloneWasmQuerier<S> {
 }
 
 impl<S> AddressGenerator for CloneWasmQuerier<S> {}
+
+/// Only the storage held by the local app is returned.
+/// Storage that is only present on the forked chain and was never loaded locally is not part of the dump
+impl<S: StateInterface> StorageInspector for CloneTesting<S> {
+    type Error = CwEnvError;
+
+    fn dump_storage(&self, contract: &Addr) -> Result<StorageDump, Self::Error> {
+        Ok(self
+            .app
+            .borrow()
+            .dump_wasm_raw(contract)
+            .into_iter()
+            .collect())
+    }
+}
@@ -0,0 +1,331 @@
+//! Comparison of the same deployment on two environments.
+//!
+//! An [`EnvComparison`] deploys a [`Deploy`] implementation on two environments (e.g. `Mock` and `OsmosisTestTube`, or a testnet and a fork)
+//! and runs the same probes (contract configs, queries, balances, ...) on both deployments.
+//! The results of the probes are compared as json and the divergences are reported:
+//! ```text
+//! Comparison `mock` vs `osmosis-test-tube`
+//!   ✓ config
+//!   ✗ pool
+//!       /fee: "0.003" != "0.002"
+//! ```
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cw_orch_core::{comparison::EnvComparison, contract::Deploy, environment::CwEnv, CwEnvError};
+//! # struct App<Chain>(Chain);
+//! # impl<Chain: CwEnv> Deploy<Chain> for App<Chain> {
+//! #     type Error = CwEnvError;
+//! #     type DeployData = ();
+//! #     fn store_on(chain: Chain) -> Result<Self, CwEnvError> { Ok(App(chain)) }
+//! #     fn deployed_state_file_path() -> Option<String> { None }
+//! #     fn get_contracts_mut(&mut self) -> Vec<Box<&mut dyn cw_orch_core::contract::interface_traits::ContractInstance<Chain>>> { vec![] }
+//! #     fn load_from(chain: Chain) -> Result<Self, CwEnvError> { Ok(App(chain)) }
+//! # }
+//! // Probes are usually generic over the environment, so the same function can be used on both sides
+//! fn block_height<Chain: CwEnv>(app: &App<Chain>) -> Result<u64, CwEnvError> {
+//!     Ok(app.0.block_info().map_err(Into::into)?.height)
+//! }
+//!
+//! # fn run<A: CwEnv, B: CwEnv>(mock: A, test_tube: B) -> Result<(), CwEnvError> {
+//! EnvComparison::<App<A>, App<B>>::new("mock", "test-tube")
+//!     .probe("height", block_height, block_height)
+//!     .deploy_and_compare((mock, ()), (test_tube, ()))?
+//!     .into_result()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::Display;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{contract::Deploy, environment::CwEnv, CwEnvError};
+
+type ProbeFn<'a, App> = Box<dyn Fn(&App) -> Result<Value, CwEnvError> + 'a>;
+
+/// Probe run on both deployments
+struct Probe<'a, L, R> {
+    name: String,
+    left: ProbeFn<'a, L>,
+    right: ProbeFn<'a, R>,
+}
+
+/// Compares the deployments `L` and `R` of an application on two environments, see the [module documentation](self)
+pub struct EnvComparison<'a, L, R> {
+    left_name: String,
+    right_name: String,
+    probes: Vec<Probe<'a, L, R>>,
+}
+
+impl<'a, L, R> EnvComparison<'a, L, R> {
+    /// Comparison without probes, the environments are identified by `left_name` and `right_name` in the report
+    pub fn new(left_name: impl Into<String>, right_name: impl Into<String>) -> Self {
+        Self {
+            left_name: left_name.into(),
+            right_name: right_name.into(),
+            probes: vec![],
+        }
+    }
+
+    /// Adds a probe, run with `left` on the left deployment and `right` on the right deployment.
+    /// The results are serialized to json to be compared
+    pub fn probe<T: Serialize>(
+        mut self,
+        name: impl Into<String>,
+        left: impl Fn(&L) -> Result<T, CwEnvError> + 'a,
+        right: impl Fn(&R) -> Result<T, CwEnvError> + 'a,
+    ) -> Self {
+        self.probes.push(Probe {
+            name: name.into(),
+            left: Box::new(move |app| Ok(serde_json::to_value(left(app)?)?)),
+            right: Box::new(move |app| Ok(serde_json::to_value(right(app)?)?)),
+        });
+        self
+    }
+
+    /// Runs the probes on already deployed applications and compares their results
+    pub fn compare(&self, left: &L, right: &R) -> ComparisonReport {
+        let probes = self
+            .probes
+            .iter()
+            .map(|probe| {
+                let outcome = match ((probe.left)(left), (probe.right)(right)) {
+                    (Ok(left), Ok(right)) => {
+                        let mut divergences = vec![];
+                        diff_values(String::new(), &left, &right, &mut divergences);
+                        if divergences.is_empty() {
+                            ProbeOutcome::Equal
+                        } else {
+                            ProbeOutcome::Diverged(divergences)
+                        }
+                    }
+                    (left, right) => ProbeOutcome::Failed {
+                        left: left.err().map(|e| e.to_string()),
+                        right: right.err().map(|e| e.to_string()),
+                    },
+                };
+                ProbeReport {
+                    name: probe.name.clone(),
+                    outcome,
+                }
+            })
+            .collect();
+
+        ComparisonReport {
+            left: self.left_name.clone(),
+            right: self.right_name.clone(),
+            probes,
+        }
+    }
+
+    /// Deploys the application on both environments with [`Deploy::deploy_on`], then runs the probes and compares their results
+    pub fn deploy_and_compare<A: CwEnv, B: CwEnv>(
+        &self,
+        left: (A, L::DeployData),
+        right: (B, R::DeployData),
+    ) -> Result<ComparisonReport, CwEnvError>
+    where
+        L: Deploy<A>,
+        R: Deploy<B>,
+    {
+        let left_app = L::deploy_on(left.0, left.1).map_err(|e| {
+            CwEnvError::StdErr(format!("Deployment on {} failed: {e}", self.left_name))
+        })?;
+        let right_app = R::deploy_on(right.0, right.1).map_err(|e| {
+            CwEnvError::StdErr(format!("Deployment on {} failed: {e}", self.right_name))
+        })?;
+        Ok(self.compare(&left_app, &right_app))
+    }
+}
+
+/// Recursively collects the json paths at which `left` and `right` differ
+fn diff_values(path: String, left: &Value, right: &Value, divergences: &mut Vec<Divergence>) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            let mut keys: Vec<&String> = left.keys().chain(right.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_values(
+                    format!("{path}/{key}"),
+                    left.get(key).unwrap_or(&Value::Null),
+                    right.get(key).unwrap_or(&Value::Null),
+                    divergences,
+                );
+            }
+        }
+        (Value::Array(left_items), Value::Array(right_items))
+            if left_items.len() == right_items.len() =>
+        {
+            for (i, (left, right)) in left_items.iter().zip(right_items).enumerate() {
+                diff_values(format!("{path}/{i}"), left, right, divergences);
+            }
+        }
+        (left, right) if left != right => divergences.push(Divergence {
+            path,
+            left: left.clone(),
+            right: right.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Value that differs between the two environments
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// Json pointer to the value in the probe result, empty for the whole result
+    pub path: String,
+    pub left: Value,
+    pub right: Value,
+}
+
+/// Outcome of a probe
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProbeOutcome {
+    /// The probe returned the same result on both environments
+    Equal,
+    /// The probe returned different results
+    Diverged(Vec<Divergence>),
+    /// The probe failed on at least one environment, with the errors of each side
+    Failed {
+        left: Option<String>,
+        right: Option<String>,
+    },
+}
+
+/// Report of a probe
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeReport {
+    pub name: String,
+    pub outcome: ProbeOutcome,
+}
+
+/// Report of an [`EnvComparison`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport {
+    pub left: String,
+    pub right: String,
+    pub probes: Vec<ProbeReport>,
+}
+
+impl ComparisonReport {
+    /// Returns true if all the probes returned the same results on both environments
+    pub fn is_equal(&self) -> bool {
+        self.probes
+            .iter()
+            .all(|probe| probe.outcome == ProbeOutcome::Equal)
+    }
+
+    /// Errors with the report if the environments diverged
+    pub fn into_result(self) -> Result<Self, CwEnvError> {
+        if self.is_equal() {
+            Ok(self)
+        } else {
+            Err(CwEnvError::EnvironmentsDiverged(self.to_string()))
+        }
+    }
+}
+
+impl Display for ComparisonReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Comparison `{}` vs `{}`", self.left, self.right)?;
+        for probe in &self.probes {
+            match &probe.outcome {
+                ProbeOutcome::Equal => write!(f, "\n  ✓ {}", probe.name)?,
+                ProbeOutcome::Diverged(divergences) => {
+                    write!(f, "\n  ✗ {}", probe.name)?;
+                    for divergence in divergences {
+                        let path = match divergence.path.as_str() {
+                            "" => "/",
+                            path => path,
+                        };
+                        write!(
+                            f,
+                            "\n      {path}: {} != {}",
+                            divergence.left, divergence.right
+                        )?;
+                    }
+                }
+                ProbeOutcome::Failed { left, right } => {
+                    write!(f, "\n  ✗ {} failed", probe.name)?;
+                    if let Some(e) = left {
+                        write!(f, "\n      on {}: {e}", self.left)?;
+                    }
+                    if let Some(e) = right {
+                        write!(f, "\n      on {}: {e}", self.right)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    struct Config {
+        owner: &'static str,
+        fee: &'static str,
+        assets: Vec<&'static str>,
+    }
+
+    fn config(config: &Config) -> Result<Value, CwEnvError> {
+        Ok(json!({ "owner": config.owner, "fee": config.fee, "assets": config.assets }))
+    }
+
+    #[test]
+    fn divergences() {
+        let comparison = EnvComparison::new("mock", "test-tube")
+            .probe("config", config, config)
+            .probe("owner", |c: &Config| Ok(c.owner), |c: &Config| Ok(c.owner))
+            .probe(
+                "failing",
+                |_: &Config| Ok(0u64),
+                |_: &Config| Err::<u64, _>(CwEnvError::StdErr("not supported".to_string())),
+            );
+
+        let left = Config {
+            owner: "admin",
+            fee: "0.003",
+            assets: vec!["uosmo", "uatom"],
+        };
+        let right = Config {
+            owner: "admin",
+            fee: "0.002",
+            assets: vec!["uosmo", "ujuno"],
+        };
+        let report = comparison.compare(&left, &right);
+
+        assert!(!report.is_equal());
+        assert_eq!(
+            report.probes[0].outcome,
+            ProbeOutcome::Diverged(vec![
+                Divergence {
+                    path: "/assets/1".to_string(),
+                    left: json!("uatom"),
+                    right: json!("ujuno"),
+                },
+                Divergence {
+                    path: "/fee".to_string(),
+                    left: json!("0.003"),
+                    right: json!("0.002"),
+                },
+            ])
+        );
+        assert_eq!(report.probes[1].outcome, ProbeOutcome::Equal);
+        assert_eq!(
+            report.probes[2].outcome,
+            ProbeOutcome::Failed {
+                left: None,
+                right: Some("Generic Error not supported".to_string()),
+            }
+        );
+        assert!(report.into_result().is_err());
+    }
+}
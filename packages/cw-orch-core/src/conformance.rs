@@ -0,0 +1,423 @@
+//! Conformance test-suite for third-party environments.
+//!
+//! An environment is usable with every contract interface once it implements [`CwEnv`]:
+//! - [`TxHandler`] to upload, instantiate, execute and migrate contracts,
+//! - [`QueryHandler`] and its [`DefaultQueriers`](crate::environment::DefaultQueriers) ([`BankQuerier`](crate::environment::BankQuerier),
+//!   [`WasmQuerier`], [`NodeQuerier`] and [`EnvironmentQuerier`]),
+//! - [`ChainState`] to access the [`StateInterface`] storing code ids and addresses.
+//!
+//! The other traits (e.g. [`SudoHandler`](crate::environment::SudoHandler), [`BankSetter`](crate::environment::BankSetter),
+//! [`StorageInspector`](crate::environment::StorageInspector)) are optional capabilities.
+//!
+//! [`ConformanceSuite`] checks that an implementation behaves as the contract interfaces expect.
+//! The state entries it writes under the `cw-orch-conformance` id are removed by the checks.
+//! With a contract, it uploads and instantiates new contracts, run it against test environments only.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cw_orch_core::{conformance::ConformanceSuite, environment::CwEnv, CwEnvError};
+//! # use cw_orch_core::contract::interface_traits::Uploadable;
+//! # fn run<Chain: CwEnv>(chain: Chain, contract: impl Uploadable) -> Result<(), CwEnvError> {
+//! // In the test-suite of the crate implementing the environment
+//! ConformanceSuite::new(&chain)
+//!     .contract(&contract, &cosmwasm_std::Empty {})?
+//!     .execute_msg(&serde_json::json!({ "increment": {} }))?
+//!     .query_msg(&serde_json::json!({ "count": {} }))?
+//!     .migrate_msg(&cosmwasm_std::Empty {})?
+//!     .run()
+//!     .into_result()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::Display;
+
+use cosmwasm_std::{Addr, Binary};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    contract::interface_traits::Uploadable,
+    environment::{
+        ChainState, CwEnv, DefaultQueriers, EnvironmentQuerier, IndexResponse, NodeQuerier,
+        QueryHandler, StateInterface, TxHandler, WasmQuerier,
+    },
+    CwEnvError,
+};
+
+/// Id the test entries are stored under in the state of the environment, they are removed by the checks
+pub const CONFORMANCE_ID: &str = "cw-orch-conformance";
+
+type Upload<'a, Chain> =
+    Box<dyn Fn(&Chain) -> Result<<Chain as TxHandler>::Response, CwEnvError> + 'a>;
+
+/// Outcome of a conformance check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckOutcome {
+    /// Name of the check
+    pub name: &'static str,
+    /// Error of the check, `None` if it passed
+    pub error: Option<String>,
+}
+
+/// Outcome of [`ConformanceSuite::run`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceReport {
+    /// Chain id of the environment
+    pub chain_id: String,
+    /// Outcome per check, in the order they were run
+    pub checks: Vec<CheckOutcome>,
+}
+
+impl ConformanceReport {
+    /// Checks that failed
+    pub fn failures(&self) -> Vec<&CheckOutcome> {
+        self.checks.iter().filter(|c| c.error.is_some()).collect()
+    }
+
+    /// Returns true if all the checks passed
+    pub fn is_success(&self) -> bool {
+        self.failures().is_empty()
+    }
+
+    /// Errors with the report if a check failed
+    pub fn into_result(self) -> Result<Self, CwEnvError> {
+        if self.is_success() {
+            Ok(self)
+        } else {
+            Err(CwEnvError::ConformanceFailed(self.to_string()))
+        }
+    }
+}
+
+impl Display for ConformanceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Conformance of `{}`", self.chain_id)?;
+        for check in &self.checks {
+            match &check.error {
+                None => writeln!(f, "  ✓ {}", check.name)?,
+                Some(error) => writeln!(f, "  ✗ {}: {}", check.name, error)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks an implementation of [`CwEnv`] against the behavior expected by contract interfaces
+pub struct ConformanceSuite<'a, Chain: CwEnv> {
+    chain: &'a Chain,
+    contract: Option<(Upload<'a, Chain>, Value)>,
+    execute_msg: Option<Value>,
+    query_msg: Option<Value>,
+    migrate_msg: Option<Value>,
+}
+
+impl<'a, Chain: CwEnv> ConformanceSuite<'a, Chain> {
+    /// Suite running against `chain`. Without a [`Self::contract`], only the state and query checks are run
+    pub fn new(chain: &'a Chain) -> Self {
+        Self {
+            chain,
+            contract: None,
+            execute_msg: None,
+            query_msg: None,
+            migrate_msg: None,
+        }
+    }
+
+    /// Uploads `contract` and instantiates it with `instantiate_msg` to check transactions and wasm queries
+    pub fn contract<T: Uploadable>(
+        mut self,
+        contract: &'a T,
+        instantiate_msg: &impl Serialize,
+    ) -> Result<Self, CwEnvError> {
+        let upload: Upload<'a, Chain> =
+            Box::new(move |chain: &Chain| chain.upload(contract).map_err(Into::into));
+        self.contract = Some((upload, serde_json::to_value(instantiate_msg)?));
+        Ok(self)
+    }
+
+    /// Executes `msg` on the instantiated contract, it must succeed
+    pub fn execute_msg(mut self, msg: &impl Serialize) -> Result<Self, CwEnvError> {
+        self.execute_msg = Some(serde_json::to_value(msg)?);
+        Ok(self)
+    }
+
+    /// Queries the instantiated contract with `msg`, it must succeed
+    pub fn query_msg(mut self, msg: &impl Serialize) -> Result<Self, CwEnvError> {
+        self.query_msg = Some(serde_json::to_value(msg)?);
+        Ok(self)
+    }
+
+    /// Migrates the instantiated contract to its own code with `msg`, it must succeed
+    pub fn migrate_msg(mut self, msg: &impl Serialize) -> Result<Self, CwEnvError> {
+        self.migrate_msg = Some(serde_json::to_value(msg)?);
+        Ok(self)
+    }
+
+    /// Runs all the checks, a failing check doesn't stop the next ones.
+    /// Transaction checks that need a contract are skipped if the upload or instantiation failed
+    pub fn run(&self) -> ConformanceReport {
+        let chain = self.chain;
+        let mut checks = vec![];
+        let mut check = |name: &'static str, result: Result<(), CwEnvError>| {
+            if let Err(e) = &result {
+                log::warn!("Conformance check `{}` failed: {}", name, e);
+            }
+            checks.push(CheckOutcome {
+                name,
+                error: result.err().map(|e| e.to_string()),
+            });
+        };
+
+        check("state: addresses", check_addresses(chain));
+        check("state: code ids", check_code_ids(chain));
+        check("queries: block info", check_block_info(chain));
+        check("queries: next block", check_next_block(chain));
+        check("queries: sender balance", check_balance(chain));
+        let Some((upload, instantiate_msg)) = &self.contract else {
+            return ConformanceReport {
+                chain_id: chain.env_info().chain_id,
+                checks,
+            };
+        };
+
+        match check_upload(chain, upload) {
+            Err(e) => check("tx: upload", Err(e)),
+            Ok(code_id) => {
+                check("tx: upload", Ok(()));
+                match check_instantiate(chain, code_id, instantiate_msg) {
+                    Err(e) => check("tx: instantiate", Err(e)),
+                    Ok(address) => {
+                        check("tx: instantiate", Ok(()));
+                        if let Some(msg) = &self.execute_msg {
+                            check("tx: execute", check_execute(chain, &address, msg));
+                        }
+                        if let Some(msg) = &self.query_msg {
+                            check("queries: smart query", check_query(chain, &address, msg));
+                        }
+                        if let Some(msg) = &self.migrate_msg {
+                            check("tx: migrate", check_migrate(chain, code_id, &address, msg));
+                        }
+                    }
+                }
+                check(
+                    "tx: instantiate2",
+                    check_instantiate2(chain, code_id, instantiate_msg),
+                );
+            }
+        }
+
+        ConformanceReport {
+            chain_id: chain.env_info().chain_id,
+            checks,
+        }
+    }
+}
+
+fn expect(condition: bool, message: impl Into<String>) -> Result<(), CwEnvError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(CwEnvError::StdErr(message.into()))
+    }
+}
+
+fn check_addresses<Chain: CwEnv>(chain: &Chain) -> Result<(), CwEnvError> {
+    let sender = chain.sender();
+    let mut state = chain.state();
+    state.set_address(CONFORMANCE_ID, &sender);
+
+    // The state needs to be shared with the environment
+    let stored = chain.state().get_address(CONFORMANCE_ID);
+    let all = chain.state().get_all_addresses();
+    state.remove_address(CONFORMANCE_ID);
+
+    let stored = stored?;
+    expect(
+        stored == sender,
+        format!("stored {stored}, expected {sender}"),
+    )?;
+    expect(
+        all?.get(CONFORMANCE_ID) == Some(&sender),
+        "address missing from `get_all_addresses`",
+    )?;
+    expect(
+        chain.state().get_address(CONFORMANCE_ID).is_err(),
+        "address still stored after `remove_address`",
+    )
+}
+
+fn check_code_ids<Chain: CwEnv>(chain: &Chain) -> Result<(), CwEnvError> {
+    let mut state = chain.state();
+    state.set_code_id(CONFORMANCE_ID, u64::MAX);
+
+    let stored = chain.state().get_code_id(CONFORMANCE_ID);
+    let all = chain.state().get_all_code_ids();
+    state.remove_code_id(CONFORMANCE_ID);
+
+    let stored = stored?;
+    expect(stored == u64::MAX, format!("stored {stored}"))?;
+    expect(
+        all?.get(CONFORMANCE_ID) == Some(&u64::MAX),
+        "code id missing from `get_all_code_ids`",
+    )?;
+    expect(
+        chain.state().get_code_id(CONFORMANCE_ID).is_err(),
+        "code id still stored after `remove_code_id`",
+    )
+}
+
+fn check_block_info<Chain: CwEnv>(chain: &Chain) -> Result<(), CwEnvError> {
+    let block = chain.block_info().map_err(Into::<CwEnvError>::into)?;
+    let info = chain.env_info();
+    expect(
+        block.chain_id == info.chain_id,
+        format!(
+            "block chain id {} differs from environment chain id {}",
+            block.chain_id, info.chain_id
+        ),
+    )?;
+    let height = chain
+        .node_querier()
+        .block_height()
+        .map_err(Into::<CwEnvError>::into)?;
+    expect(
+        height >= block.height,
+        format!(
+            "height {height} is lower than the block height {}",
+            block.height
+        ),
+    )
+}
+
+fn check_next_block<Chain: CwEnv>(chain: &Chain) -> Result<(), CwEnvError> {
+    let before = chain.block_info().map_err(Into::<CwEnvError>::into)?;
+    chain.next_block().map_err(Into::<CwEnvError>::into)?;
+    let after = chain.block_info().map_err(Into::<CwEnvError>::into)?;
+    expect(
+        after.height > before.height,
+        format!("height {} didn't increase", before.height),
+    )?;
+    expect(
+        after.time >= before.time,
+        format!("block time went back to {}", after.time),
+    )
+}
+
+fn check_balance<Chain: CwEnv>(chain: &Chain) -> Result<(), CwEnvError> {
+    chain
+        .balance(chain.sender(), None)
+        .map_err(Into::<CwEnvError>::into)?;
+    Ok(())
+}
+
+fn check_upload<Chain: CwEnv>(
+    chain: &Chain,
+    upload: &Upload<'_, Chain>,
+) -> Result<u64, CwEnvError> {
+    let code_id = upload(chain)?.uploaded_code_id()?;
+    let wasm = chain.wasm_querier();
+    wasm.code_id_hash(code_id)
+        .map_err(Into::<CwEnvError>::into)?;
+    let code = wasm.code(code_id).map_err(Into::<CwEnvError>::into)?;
+    expect(
+        code.code_id == code_id,
+        format!("code info of {code_id} has the code id {}", code.code_id),
+    )?;
+    Ok(code_id)
+}
+
+fn check_contract_info<Chain: CwEnv>(
+    chain: &Chain,
+    address: &Addr,
+    code_id: u64,
+) -> Result<(), CwEnvError> {
+    let sender = chain.sender();
+    let info = chain
+        .wasm_querier()
+        .contract_info(address)
+        .map_err(Into::<CwEnvError>::into)?;
+    expect(
+        info.code_id == code_id,
+        format!(
+            "{address} has the code id {}, expected {code_id}",
+            info.code_id
+        ),
+    )?;
+    expect(
+        info.admin.map(|admin| admin.to_string()) == Some(sender.to_string()),
+        format!("admin of {address} isn't {sender}"),
+    )
+}
+
+fn check_instantiate<Chain: CwEnv>(
+    chain: &Chain,
+    code_id: u64,
+    msg: &Value,
+) -> Result<Addr, CwEnvError> {
+    let sender = chain.sender();
+    let address: Addr = chain
+        .instantiate(code_id, msg, Some(CONFORMANCE_ID), Some(&sender), &[])
+        .map_err(Into::<CwEnvError>::into)?
+        .instantiated_contract_address()?;
+    check_contract_info(chain, &address, code_id)?;
+    Ok(address)
+}
+
+fn check_instantiate2<Chain: CwEnv>(
+    chain: &Chain,
+    code_id: u64,
+    msg: &Value,
+) -> Result<(), CwEnvError> {
+    let sender = chain.sender();
+    // The address only depends on the code checksum, a salt per run avoids collisions with previous runs
+    let block = chain.block_info().map_err(Into::<CwEnvError>::into)?;
+    let salt = Binary::from(block.time.nanos().to_be_bytes().to_vec());
+    let expected = chain
+        .wasm_querier()
+        .instantiate2_addr(code_id, &sender, salt.clone())
+        .map_err(Into::<CwEnvError>::into)?;
+    let address: Addr = chain
+        .instantiate2(code_id, msg, Some(CONFORMANCE_ID), Some(&sender), &[], salt)
+        .map_err(Into::<CwEnvError>::into)?
+        .instantiated_contract_address()?;
+    expect(
+        address.as_str() == expected,
+        format!("instantiated {address}, predicted {expected}"),
+    )?;
+    check_contract_info(chain, &address, code_id)
+}
+
+fn check_execute<Chain: CwEnv>(
+    chain: &Chain,
+    address: &Addr,
+    msg: &Value,
+) -> Result<(), CwEnvError> {
+    let response = chain
+        .execute(msg, &[], address)
+        .map_err(Into::<CwEnvError>::into)?;
+    expect(
+        !response.events().is_empty(),
+        "execution response doesn't contain any event",
+    )
+}
+
+fn check_query<Chain: CwEnv>(chain: &Chain, address: &Addr, msg: &Value) -> Result<(), CwEnvError> {
+    chain
+        .wasm_querier()
+        .smart_query::<_, Value>(address, msg)
+        .map_err(Into::<CwEnvError>::into)?;
+    Ok(())
+}
+
+fn check_migrate<Chain: CwEnv>(
+    chain: &Chain,
+    code_id: u64,
+    address: &Addr,
+    msg: &Value,
+) -> Result<(), CwEnvError> {
+    chain
+        .migrate(msg, code_id, address)
+        .map_err(Into::<CwEnvError>::into)?;
+    check_contract_info(chain, address, code_id)
+}
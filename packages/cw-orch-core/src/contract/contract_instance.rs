@@ -1,8 +1,14 @@
 //! Main functional component for interacting with a contract. Used as the base for generating contract interfaces.
-use super::interface_traits::Uploadable;
+use super::{
+    interface_traits::Uploadable,
+    recording::{record, RecordedCall},
+};
 use crate::{
     env::CoreEnvVars,
-    environment::{ChainState, IndexResponse, StateInterface, TxHandler, TxResponse},
+    environment::{
+        AsyncTxHandler, AsyncWasmQuerier, ChainState, IndexResponse, StateInterface, SudoHandler,
+        TxHandler, TxResponse,
+    },
     error::CwEnvError,
     log::{contract_target, transaction_target},
 };
@@ -148,6 +154,16 @@ impl<Chain: TxHandler> Contract<Chain> {
         let resp = self
             .chain
             .execute(msg, coins.unwrap_or(&[]), &self.address()?);
+        if resp.is_ok() {
+            record(|| {
+                Ok(RecordedCall::Execute {
+                    contract_id: self.id.clone(),
+                    msg: serde_json::to_value(msg)?,
+                    funds: coins.unwrap_or(&[]).to_vec(),
+                    sender: self.chain.sender().to_string(),
+                })
+            });
+        }
 
         log::info!(
             target: &contract_target(),
@@ -199,6 +215,17 @@ impl<Chain: TxHandler> Contract<Chain> {
         let contract_address = resp.instantiated_contract_address()?;
 
         self.set_address(&contract_address);
+        record(|| {
+            Ok(RecordedCall::Instantiate {
+                contract_id: self.id.clone(),
+                msg: serde_json::to_value(msg)?,
+                admin: admin.map(ToString::to_string),
+                funds: coins.unwrap_or(&[]).to_vec(),
+                sender: self.chain.sender().to_string(),
+                salt: None,
+                address: contract_address.to_string(),
+            })
+        });
 
         log::info!(
             target: &&contract_target(),
@@ -245,12 +272,23 @@ impl<Chain: TxHandler> Contract<Chain> {
                 Some(&self.id),
                 admin,
                 coins.unwrap_or(&[]),
-                salt,
+                salt.clone(),
             )
             .map_err(Into::into)?;
         let contract_address = resp.instantiated_contract_address()?;
 
         self.set_address(&contract_address);
+        record(|| {
+            Ok(RecordedCall::Instantiate {
+                contract_id: self.id.clone(),
+                msg: serde_json::to_value(msg)?,
+                admin: admin.map(ToString::to_string),
+                funds: coins.unwrap_or(&[]).to_vec(),
+                sender: self.chain.sender().to_string(),
+                salt: Some(salt),
+                address: contract_address.to_string(),
+            })
+        });
 
         log::info!(
             target: &&contract_target(),
@@ -293,6 +331,14 @@ impl<Chain: TxHandler> Contract<Chain> {
             .chain
             .migrate(migrate_msg, new_code_id, &self.address()?)
             .map_err(Into::into)?;
+        record(|| {
+            Ok(RecordedCall::Migrate {
+                contract_id: self.id.clone(),
+                msg: serde_json::to_value(migrate_msg)?,
+                new_code_id,
+                sender: self.chain.sender().to_string(),
+            })
+        });
 
         log::info!(
             target: &contract_target(),
@@ -311,6 +357,41 @@ impl<Chain: TxHandler> Contract<Chain> {
     }
 }
 
+impl<Chain: SudoHandler> Contract<Chain> {
+    /// Calls the sudo entry point of the contract
+    pub fn sudo<S: Serialize + Debug>(
+        &self,
+        sudo_msg: &S,
+    ) -> Result<TxResponse<Chain>, CwEnvError> {
+        log::info!(
+            target: &contract_target(),
+            "[{}][Sudo][{}]",
+            self.id,
+            self.address()?,
+        );
+
+        log::debug!(
+            target: &contract_target(),
+            "[{}][Sudo] {}",
+            self.id,
+            log_serialize_message(sudo_msg)?
+        );
+
+        let resp = self
+            .chain
+            .sudo(sudo_msg, &self.address()?)
+            .map_err(Into::into)?;
+
+        log::debug!(
+            target: &transaction_target(),
+            "[{}][Sudo] response: {:?}",
+            self.id,
+            resp
+        );
+        Ok(resp)
+    }
+}
+
 impl<Chain: ChainState + QueryHandler> Contract<Chain> {
     /// Query the contract
     pub fn query<Q: Serialize + Debug, T: Serialize + DeserializeOwned + Debug>(
@@ -341,6 +422,82 @@ impl<Chain: ChainState + QueryHandler> Contract<Chain> {
     }
 }
 
+impl<Chain: AsyncTxHandler> Contract<Chain> {
+    /// Executes an operation on the contract asynchronously
+    pub async fn async_execute<E: Serialize + Debug>(
+        &self,
+        msg: &E,
+        coins: Option<&[Coin]>,
+    ) -> Result<Chain::Response, CwEnvError> {
+        log::info!(
+            target: &contract_target(),
+            "[{}][Execute][{}] {}",
+            self.id,
+            self.address()?,
+            get_struct_name(msg)?
+        );
+
+        log::debug!(
+            target: &contract_target(),
+            "[{}][Execute] {}",
+            self.id,
+            log_serialize_message(msg)?
+        );
+
+        let resp = self
+            .chain
+            .async_execute(msg, coins.unwrap_or(&[]), &self.address()?)
+            .await;
+
+        log::info!(
+            target: &contract_target(),
+            "[{}][Executed][{}] {}",
+            self.id,
+            self.address()?,
+            get_struct_name(msg)?
+        );
+        log::debug!(
+            target: &transaction_target(),
+            "[{}][Executed] response: {:?}",
+            self.id,
+            resp
+        );
+
+        resp.map_err(Into::into)
+    }
+}
+
+impl<Chain: AsyncWasmQuerier> Contract<Chain> {
+    /// Query the contract asynchronously
+    pub async fn async_query<Q: Serialize + Debug, T: Serialize + DeserializeOwned + Debug>(
+        &self,
+        query_msg: &Q,
+    ) -> Result<T, CwEnvError> {
+        log::debug!(
+            target: &contract_target(),
+            "[{}][Query][{}] {}",
+            self.id,
+            self.address()?,
+            log_serialize_message(query_msg)?
+        );
+
+        let resp = self
+            .chain
+            .async_query(query_msg, &self.address()?)
+            .await
+            .map_err(Into::into)?;
+
+        log::debug!(
+            target: &contract_target(),
+            "[{}][Queried][{}] response {}",
+            self.id,
+            self.address()?,
+            log_serialize_message(&resp)?
+        );
+        Ok(resp)
+    }
+}
+
 /// Helper to serialize objects (JSON or Rust DEBUG)
 fn log_serialize_message<E: Serialize + Debug>(msg: &E) -> Result<String, CwEnvError> {
     if CoreEnvVars::serialize_json() {
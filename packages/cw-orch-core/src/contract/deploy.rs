@@ -17,6 +17,7 @@ use crate::environment::CwEnv;
 use crate::environment::QueryHandler;
 use crate::CwEnvError;
 
+use super::deployment_export::DeploymentExport;
 use super::interface_traits::ContractInstance;
 
 /// Indicates the ability to deploy an application to a mock chain.
@@ -237,6 +238,20 @@ pub trait Deploy<Chain: CwEnv>: Sized {
         }
     }
 
+    /// Exports the addresses and code ids of the contracts of this deployment.
+    /// Exports of deployments on several chains can be merged with [`DeploymentExport::add`]
+    fn export_deployment(&mut self) -> DeploymentExport {
+        let mut export = DeploymentExport::default();
+        export.add(self);
+        export
+    }
+
+    /// Imports the addresses and code ids of an exported deployment into the state of this deployment's chain.
+    /// Returns the number of imported contracts
+    fn import_deployment(&mut self, export: &DeploymentExport) -> usize {
+        export.import(self)
+    }
+
     /// Gets all the chain ids on which the library is deployed on
     /// This loads all chains that are registered in the crate-local daemon_state file
     /// The state file should have the following format :
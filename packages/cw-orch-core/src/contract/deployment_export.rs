@@ -0,0 +1,233 @@
+//! Export and import of multi-chain deployments
+//!
+//! A [`DeploymentExport`] holds the addresses and code ids of all the contracts of a [`Deploy`] structure on several chains.
+//! It can be written to a single file and shipped with a crate, so that users can load the official deployment addresses into their own (fresh) state.
+//!
+//! ## Format
+//! ```json
+//! {
+//!   "juno-1": {
+//!     "chain_name": "juno",
+//!     "contracts": {
+//!       "my-token": { "address": "juno1...", "code_id": 1 }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Exports written from a daemon state also contain the `checksum` of the code and the `init_msg_hash` of the contract when known.
+//! The export can be written as a TypeScript module with [`DeploymentExport::to_typescript`] for frontends and indexers.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use cosmwasm_std::Addr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    environment::{CwEnv, EnvironmentQuerier},
+    CwEnvError,
+};
+
+use super::Deploy;
+
+/// Addresses and code ids of a deployment across several chains, indexed by chain id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DeploymentExport {
+    /// Deployment on each chain, indexed by chain id
+    pub chains: BTreeMap<String, ChainDeployment>,
+}
+
+/// Contracts deployed on a single chain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChainDeployment {
+    /// Name of the chain (e.g. `juno`)
+    pub chain_name: String,
+    /// Contracts deployed on the chain, indexed by contract id
+    pub contracts: BTreeMap<String, ExportedContract>,
+}
+
+/// Address and code id of a deployed contract
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportedContract {
+    /// Address of the contract, if instantiated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Code id of the contract, if uploaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_id: Option<u64>,
+    /// Hex encoded sha256 checksum of the code, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Hex encoded sha256 hash of the json instantiate message, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub init_msg_hash: Option<String>,
+}
+
+impl DeploymentExport {
+    /// Exports the contracts of the deployments.
+    /// Deployments on the same chain are merged
+    pub fn export<Chain: CwEnv, D: Deploy<Chain>>(
+        deployments: impl IntoIterator<Item = D>,
+    ) -> Self {
+        let mut export = Self::default();
+        for mut deployment in deployments {
+            export.add(&mut deployment);
+        }
+        export
+    }
+
+    /// Adds the contracts of the deployment to the export
+    pub fn add<Chain: CwEnv, D: Deploy<Chain>>(&mut self, deployment: &mut D) {
+        for contract in deployment.get_contracts_mut() {
+            let env_info = contract.get_chain().env_info();
+            let chain = self
+                .chains
+                .entry(env_info.chain_id)
+                .or_insert_with(|| ChainDeployment {
+                    chain_name: env_info.chain_name,
+                    contracts: BTreeMap::new(),
+                });
+            let address = contract.address().ok().map(|addr| addr.to_string());
+            let code_id = contract.code_id().ok();
+            if address.is_none() && code_id.is_none() {
+                continue;
+            }
+            chain.contracts.insert(
+                contract.id(),
+                ExportedContract {
+                    address,
+                    code_id,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    /// Imports the addresses and code ids of the chain of the deployment into its state.
+    /// Returns the number of imported contracts, contracts that are missing from the export are left untouched
+    pub fn import<Chain: CwEnv, D: Deploy<Chain>>(&self, deployment: &mut D) -> usize {
+        let mut imported = 0;
+        for contract in deployment.get_contracts_mut() {
+            let chain_id = contract.get_chain().env_info().chain_id;
+            let Some(exported) = self
+                .chains
+                .get(&chain_id)
+                .and_then(|chain| chain.contracts.get(&contract.id()))
+            else {
+                continue;
+            };
+            if let Some(code_id) = exported.code_id {
+                contract.set_code_id(code_id);
+            }
+            if let Some(address) = &exported.address {
+                contract.set_address(&Addr::unchecked(address));
+            }
+            imported += 1;
+        }
+        imported
+    }
+
+    /// Chain ids the deployment is exported for
+    pub fn chain_ids(&self) -> Vec<String> {
+        self.chains.keys().cloned().collect()
+    }
+
+    /// Converts the export into the state file format, under the provided deployment id.
+    /// The result can be shipped as the [`Deploy::deployed_state_file_path`] of a crate or passed to [`Deploy::set_contracts_state`]
+    pub fn to_state(&self, deployment_id: &str) -> Value {
+        let mut state = Map::new();
+        for (chain_id, chain) in &self.chains {
+            let code_ids: Map<String, Value> = chain
+                .contracts
+                .iter()
+                .filter_map(|(id, contract)| Some((id.clone(), Value::from(contract.code_id?))))
+                .collect();
+            let addresses: Map<String, Value> = chain
+                .contracts
+                .iter()
+                .filter_map(|(id, contract)| {
+                    Some((id.clone(), Value::from(contract.address.as_deref()?)))
+                })
+                .collect();
+
+            let chain_state = state
+                .entry(chain.chain_name.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+            let mut chain_id_state = Map::new();
+            chain_id_state.insert("code_ids".to_string(), Value::Object(code_ids));
+            chain_id_state.insert(deployment_id.to_string(), Value::Object(addresses));
+            chain_state[chain_id] = Value::Object(chain_id_state);
+        }
+        Value::Object(state)
+    }
+
+    /// Loads an export from a json file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CwEnvError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Writes the export to a json file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CwEnvError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Typed TypeScript module exporting the deployment as `deployments`, indexed by chain id
+    pub fn to_typescript(&self) -> Result<String, CwEnvError> {
+        Ok(format!(
+            "// Generated by cw-orchestrator, do not edit\n\
+            export const deployments = {} as const;\n\n\
+            export type ChainId = keyof typeof deployments;\n\n\
+            export default deployments;\n",
+            serde_json::to_string_pretty(self)?
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn export_to_state() {
+        let export: DeploymentExport = serde_json::from_value(json!({
+            "juno-1": {
+                "chain_name": "juno",
+                "contracts": {
+                    "token": { "address": "juno1token", "code_id": 4 },
+                    "vault": { "code_id": 5 }
+                }
+            },
+            "osmosis-1": {
+                "chain_name": "osmosis",
+                "contracts": {
+                    "token": { "address": "osmo1token", "code_id": 12 }
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(export.chain_ids(), vec!["juno-1", "osmosis-1"]);
+        assert_eq!(export.chains["juno-1"].contracts["vault"].address, None);
+
+        let state = export.to_state("v1");
+        assert_eq!(state["juno"]["juno-1"]["code_ids"]["vault"], json!(5));
+        assert_eq!(state["juno"]["juno-1"]["v1"]["token"], json!("juno1token"));
+        assert_eq!(state["juno"]["juno-1"]["v1"].get("vault"), None);
+        assert_eq!(
+            state["osmosis"]["osmosis-1"]["v1"]["token"],
+            json!("osmo1token")
+        );
+
+        let typescript = export.to_typescript().unwrap();
+        assert!(typescript.contains("export const deployments = {"));
+        assert!(typescript.contains("\"address\": \"osmo1token\""));
+
+        let roundtrip: DeploymentExport =
+            serde_json::from_value(serde_json::to_value(&export).unwrap()).unwrap();
+        assert_eq!(roundtrip, export);
+    }
+}
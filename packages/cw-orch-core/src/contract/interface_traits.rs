@@ -1,7 +1,8 @@
 use super::{Contract, WasmPath};
 use crate::{
     environment::{
-        ChainInfoOwned, ChainState, CwEnv, QueryHandler, TxHandler, TxResponse, WasmQuerier,
+        AsyncTxHandler, AsyncWasmQuerier, ChainInfoOwned, ChainState, CwEnv, QueryHandler,
+        SudoHandler, TxHandler, TxResponse, WasmQuerier,
     },
     error::CwEnvError,
     log::contract_target,
@@ -10,7 +11,11 @@ use cosmwasm_std::{Addr, Binary, Coin, Empty};
 use cw_multi_test::Contract as MockContract;
 use cw_storage_plus::{Item, Map, PrimaryKey};
 use serde::{de::DeserializeOwned, Serialize};
-use std::fmt::Debug;
+use std::{fmt::Debug, future::Future};
+
+pub use super::versioned_migrate::{
+    CwOrchVersionedMigrate, MigrateMsgVersion, VersionedMigratableContract,
+};
 
 // Fn for custom implementation to return ContractInstance
 /// Interface to the underlying `Contract` struct. Implemented automatically when using our macros.
@@ -105,6 +110,12 @@ pub trait MigratableContract {
     type MigrateMsg: Serialize + Debug;
 }
 
+/// Trait that indicates that the contract has a sudo entry point called with the associated message.
+pub trait SudoableContract {
+    /// Sudo message for the contract.
+    type SudoMsg: Serialize + Debug;
+}
+
 /// Smart contract execute entry point.
 pub trait CwOrchExecute<Chain: TxHandler>: ExecutableContract + ContractInstance<Chain> {
     /// Send a ExecuteMsg to the contract.
@@ -213,6 +224,53 @@ pub trait CwOrchMigrate<Chain: TxHandler>: MigratableContract + ContractInstance
 
 impl<T: MigratableContract + ContractInstance<Chain>, Chain: TxHandler> CwOrchMigrate<Chain> for T {}
 
+/// Smart contract sudo entry point.
+pub trait CwOrchSudo<Chain: SudoHandler>: SudoableContract + ContractInstance<Chain> {
+    /// Call the sudo entry point of the contract.
+    fn sudo(&self, sudo_msg: &Self::SudoMsg) -> Result<Chain::Response, CwEnvError> {
+        self.as_instance().sudo(sudo_msg)
+    }
+}
+
+impl<T: SudoableContract + ContractInstance<Chain>, Chain: SudoHandler> CwOrchSudo<Chain> for T {}
+
+/// Smart contract execute entry point, for environments that are awaited.
+pub trait AsyncCwOrchExecute<Chain: AsyncTxHandler>:
+    ExecutableContract + ContractInstance<Chain>
+{
+    /// Send a ExecuteMsg to the contract asynchronously.
+    fn async_execute(
+        &self,
+        execute_msg: &Self::ExecuteMsg,
+        coins: Option<&[Coin]>,
+    ) -> impl Future<Output = Result<Chain::Response, CwEnvError>> {
+        self.as_instance().async_execute(execute_msg, coins)
+    }
+}
+
+impl<T: ExecutableContract + ContractInstance<Chain>, Chain: AsyncTxHandler>
+    AsyncCwOrchExecute<Chain> for T
+{
+}
+
+/// Smart contract query entry point, for environments that are awaited.
+pub trait AsyncCwOrchQuery<Chain: AsyncWasmQuerier>:
+    QueryableContract + ContractInstance<Chain>
+{
+    /// Query the contract asynchronously.
+    fn async_query<G: Serialize + DeserializeOwned + Debug>(
+        &self,
+        query_msg: &Self::QueryMsg,
+    ) -> impl Future<Output = Result<G, CwEnvError>> {
+        self.as_instance().async_query(query_msg)
+    }
+}
+
+impl<T: QueryableContract + ContractInstance<Chain>, Chain: AsyncWasmQuerier>
+    AsyncCwOrchQuery<Chain> for T
+{
+}
+
 /// Trait to implement on the contract to enable it to be uploaded
 /// Should return [`WasmPath`](crate::contract::interface_traits::WasmPath) for `Chain = Daemon`
 /// and [`Box<&dyn Contract>`] for `Chain = Mock`
@@ -222,6 +280,12 @@ pub trait Uploadable {
         unimplemented!("no wasm file provided for this contract")
     }
 
+    /// Return the wasm code of the contract when it's kept in memory (e.g. embedded with `include_bytes!` or fetched from a registry).
+    /// When provided, environments that upload wasm code use it instead of [`Uploadable::wasm`].
+    fn wasm_bytes(_chain: &ChainInfoOwned) -> Option<Vec<u8>> {
+        None
+    }
+
     /// Return the wrapper object for the contract, only works for non-custom mock environments
     fn wrapper() -> Box<dyn MockContract<Empty, Empty>> {
         unimplemented!("no wrapper function implemented for this contract")
@@ -263,12 +327,34 @@ impl<T: CwOrchExecute<Chain> + ContractInstance<Chain> + Clone, Chain: TxHandler
 /// Helper methods for conditional uploading of a contract.
 pub trait ConditionalUpload<Chain: CwEnv>: CwOrchUpload<Chain> {
     /// Only upload the contract if it is not uploaded yet (checksum does not match)
+    /// If the same code was already uploaded on chain (e.g. by another deployment) and the sender can instantiate it,
+    /// its code id is registered for this contract instead, see [`WasmQuerier::code_id_by_checksum`].
     fn upload_if_needed(&self) -> Result<Option<TxResponse<Chain>>, CwEnvError> {
         if let Ok(true) = self.latest_is_uploaded() {
-            Ok(None)
-        } else {
-            Some(self.upload()).transpose().map_err(Into::into)
+            return Ok(None);
+        }
+        if let Some(code_id) = self.existing_code_id()? {
+            log::info!(target: &contract_target(), "Skipped upload. {} code already exists on chain with code id {}", self.id(), code_id);
+            self.set_code_id(code_id);
+            return Ok(None);
         }
+        Some(self.upload()).transpose().map_err(Into::into)
+    }
+
+    /// Uploads the contract if needed and returns its code id
+    fn ensure_uploaded(&self) -> Result<u64, CwEnvError> {
+        self.upload_if_needed()?;
+        self.code_id()
+    }
+
+    /// Returns the id of a code on chain with the same checksum as the local WASM file that the sender can instantiate, if any.
+    fn existing_code_id(&self) -> Result<Option<u64>, CwEnvError> {
+        let chain = self.get_chain();
+        let wasm_querier = chain.wasm_querier();
+        let local_hash = wasm_querier.local_hash(self)?;
+        wasm_querier
+            .code_id_by_checksum(&local_hash, &chain.sender())
+            .map_err(Into::into)
     }
 
     /// Returns whether the checksum of the WASM file matches the checksum of the latest uploaded code for this contract.
@@ -0,0 +1,391 @@
+//! Migration plans of a deployment towards newly built artifacts
+//!
+//! A [`MigrationPlan`] compares the contracts of a [`Deploy`] structure with a set of wasm artifacts, indexed by contract id:
+//! - A contract whose artifact checksum differs from the checksum of its code id needs a new code: the code is uploaded,
+//!   or registered if the same code was already uploaded on chain.
+//! - An instantiated contract that isn't running its (new) code id needs a migration.
+//!
+//! Uploads are ordered before migrations, in the order of [`Deploy::get_contracts_mut`].
+//! The plan can be inspected before being executed, with a confirmation for every step.
+//! The [`MigrationReport`] of the execution lists the steps needed to roll back the applied steps.
+//!
+//! ## Usage
+//! ```ignore
+//! let artifacts = HashMap::from([("counter".to_string(), ArtifactsDir::env().find_wasm_path("counter")?)]);
+//! let plan = MigrationPlan::new(&mut deployment, &artifacts)?;
+//! for step in &plan.steps {
+//!     println!("{step}");
+//! }
+//! let migrate_msgs = HashMap::from([("counter".to_string(), serde_json::to_value(MigrateMsg {})?)]);
+//! let report = plan.execute(&mut deployment, &migrate_msgs, |step| ask_user(step))?;
+//! if report.failure.is_some() {
+//!     for step in report.rollback_steps() {
+//!         println!("rollback: {step}");
+//!     }
+//! }
+//! ```
+
+use std::{collections::HashMap, fmt::Display, fs};
+
+use cosmwasm_std::{Addr, HexBinary};
+use serde_json::Value;
+
+use super::{Deploy, WasmPath};
+use crate::{
+    environment::{CwEnv, DefaultQueriers, IndexResponse, TxHandler, WasmQuerier},
+    log::contract_target,
+    CwEnvError,
+};
+
+/// Step of a [`MigrationPlan`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationStep {
+    /// Upload the artifact of the contract and use the new code id
+    Upload {
+        contract_id: String,
+        wasm: WasmPath,
+        checksum: HexBinary,
+        previous_code_id: Option<u64>,
+    },
+    /// Use a code with the checksum of the artifact that is already on chain
+    UseExistingCode {
+        contract_id: String,
+        code_id: u64,
+        previous_code_id: Option<u64>,
+    },
+    /// Migrate the contract to the latest code id of the contract
+    Migrate {
+        contract_id: String,
+        address: Addr,
+        from_code_id: u64,
+    },
+}
+
+impl MigrationStep {
+    /// Id of the contract the step applies to
+    pub fn contract_id(&self) -> &str {
+        match self {
+            MigrationStep::Upload { contract_id, .. }
+            | MigrationStep::UseExistingCode { contract_id, .. }
+            | MigrationStep::Migrate { contract_id, .. } => contract_id,
+        }
+    }
+}
+
+impl Display for MigrationStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationStep::Upload {
+                contract_id,
+                wasm,
+                checksum,
+                ..
+            } => write!(
+                f,
+                "Upload {contract_id} from {} (checksum {checksum})",
+                wasm.path().display()
+            ),
+            MigrationStep::UseExistingCode {
+                contract_id,
+                code_id,
+                ..
+            } => write!(f, "Use existing code {code_id} for {contract_id}"),
+            MigrationStep::Migrate {
+                contract_id,
+                address,
+                from_code_id,
+            } => write!(
+                f,
+                "Migrate {contract_id} ({address}) from code {from_code_id}"
+            ),
+        }
+    }
+}
+
+/// Step reverting an applied [`MigrationStep`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RollbackStep {
+    /// Set the code id of the contract back, or remove it if the contract had no code id
+    RestoreCodeId {
+        contract_id: String,
+        code_id: Option<u64>,
+    },
+    /// Migrate the contract back to its previous code id
+    MigrateBack {
+        contract_id: String,
+        address: Addr,
+        code_id: u64,
+    },
+}
+
+impl Display for RollbackStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RollbackStep::RestoreCodeId {
+                contract_id,
+                code_id: Some(code_id),
+            } => write!(f, "Restore code id {code_id} of {contract_id}"),
+            RollbackStep::RestoreCodeId {
+                contract_id,
+                code_id: None,
+            } => write!(f, "Remove the code id of {contract_id}"),
+            RollbackStep::MigrateBack {
+                contract_id,
+                address,
+                code_id,
+            } => write!(
+                f,
+                "Migrate {contract_id} ({address}) back to code {code_id}"
+            ),
+        }
+    }
+}
+
+/// Ordered steps bringing a deployment to newly built artifacts, see the [module documentation](self)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationPlan {
+    /// Steps of the plan, uploads first
+    pub steps: Vec<MigrationStep>,
+}
+
+/// Result of the execution of a [`MigrationPlan`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationReport {
+    /// Steps that were applied, in order
+    pub applied: Vec<MigrationStep>,
+    /// Steps that were not applied, because a step was declined or failed
+    pub pending: Vec<MigrationStep>,
+    /// Error of the step that failed, which is the first pending step
+    pub failure: Option<String>,
+}
+
+impl MigrationReport {
+    /// Steps reverting the applied steps, last applied step first
+    pub fn rollback_steps(&self) -> Vec<RollbackStep> {
+        self.applied
+            .iter()
+            .rev()
+            .map(|step| match step {
+                MigrationStep::Upload {
+                    contract_id,
+                    previous_code_id,
+                    ..
+                }
+                | MigrationStep::UseExistingCode {
+                    contract_id,
+                    previous_code_id,
+                    ..
+                } => RollbackStep::RestoreCodeId {
+                    contract_id: contract_id.clone(),
+                    code_id: *previous_code_id,
+                },
+                MigrationStep::Migrate {
+                    contract_id,
+                    address,
+                    from_code_id,
+                } => RollbackStep::MigrateBack {
+                    contract_id: contract_id.clone(),
+                    address: address.clone(),
+                    code_id: *from_code_id,
+                },
+            })
+            .collect()
+    }
+}
+
+impl MigrationPlan {
+    /// Computes the plan bringing the contracts of `deployment` to `artifacts`, indexed by contract id.
+    /// Contracts without an artifact are left untouched
+    pub fn new<Chain: CwEnv, D: Deploy<Chain>>(
+        deployment: &mut D,
+        artifacts: &HashMap<String, WasmPath>,
+    ) -> Result<Self, CwEnvError> {
+        let mut uploads = vec![];
+        let mut migrations = vec![];
+        for contract in deployment.get_contracts_mut() {
+            let contract_id = contract.id();
+            let Some(wasm) = artifacts.get(&contract_id) else {
+                continue;
+            };
+            let chain = contract.get_chain();
+            let wasm_querier = chain.wasm_querier();
+            let checksum = wasm.checksum()?;
+            let previous_code_id = contract.code_id().ok();
+
+            let current_code_is_latest = match previous_code_id {
+                Some(code_id) => {
+                    wasm_querier.code_id_hash(code_id).map_err(Into::into)? == checksum
+                }
+                None => false,
+            };
+            // `None` when the code id is only known after the upload
+            let target_code_id = if current_code_is_latest {
+                previous_code_id
+            } else if let Some(code_id) = wasm_querier
+                .code_id_by_checksum(&checksum, &chain.sender())
+                .map_err(Into::into)?
+            {
+                uploads.push(MigrationStep::UseExistingCode {
+                    contract_id: contract_id.clone(),
+                    code_id,
+                    previous_code_id,
+                });
+                Some(code_id)
+            } else {
+                uploads.push(MigrationStep::Upload {
+                    contract_id: contract_id.clone(),
+                    wasm: wasm.clone(),
+                    checksum,
+                    previous_code_id,
+                });
+                None
+            };
+
+            let Ok(address) = contract.address() else {
+                continue;
+            };
+            let running_code_id = wasm_querier
+                .contract_info(&address)
+                .map_err(Into::into)?
+                .code_id;
+            if target_code_id != Some(running_code_id) {
+                migrations.push(MigrationStep::Migrate {
+                    contract_id,
+                    address,
+                    from_code_id: running_code_id,
+                });
+            }
+        }
+        uploads.extend(migrations);
+        Ok(Self { steps: uploads })
+    }
+
+    /// Whether the deployment already matches the artifacts
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Executes the plan on `deployment`, with the json migrate messages indexed by contract id.
+    /// `confirm` is called before every step, declining a step stops the execution.
+    ///
+    /// Errors before any transaction if a migrate message is missing.
+    /// Failed steps don't error, they are reported in the [`MigrationReport`] with the applied steps to roll back
+    pub fn execute<Chain: CwEnv, D: Deploy<Chain>>(
+        &self,
+        deployment: &mut D,
+        migrate_msgs: &HashMap<String, Value>,
+        mut confirm: impl FnMut(&MigrationStep) -> bool,
+    ) -> Result<MigrationReport, CwEnvError> {
+        let missing_msg = self.steps.iter().find(|step| {
+            matches!(step, MigrationStep::Migrate { .. })
+                && !migrate_msgs.contains_key(step.contract_id())
+        });
+        if let Some(step) = missing_msg {
+            return Err(CwEnvError::MigrationPlan(format!(
+                "no migrate message for {}",
+                step.contract_id()
+            )));
+        }
+
+        let mut report = MigrationReport::default();
+        for (index, step) in self.steps.iter().enumerate() {
+            if !confirm(step) {
+                log::info!(target: &contract_target(), "Migration plan stopped before: {step}");
+                report.pending = self.steps[index..].to_vec();
+                return Ok(report);
+            }
+            if let Err(error) = apply_step(deployment, step, migrate_msgs) {
+                report.pending = self.steps[index..].to_vec();
+                report.failure = Some(error.to_string());
+                return Ok(report);
+            }
+            report.applied.push(step.clone());
+        }
+        Ok(report)
+    }
+}
+
+fn apply_step<Chain: CwEnv, D: Deploy<Chain>>(
+    deployment: &mut D,
+    step: &MigrationStep,
+    migrate_msgs: &HashMap<String, Value>,
+) -> Result<(), CwEnvError> {
+    let mut contracts = deployment.get_contracts_mut();
+    let contract = contracts
+        .iter_mut()
+        .find(|contract| contract.id() == step.contract_id())
+        .ok_or_else(|| {
+            CwEnvError::MigrationPlan(format!(
+                "{} is not part of the deployment",
+                step.contract_id()
+            ))
+        })?;
+    let chain = contract.get_chain().clone();
+    match step {
+        MigrationStep::Upload { wasm, .. } => {
+            let response = chain
+                .upload_from_bytes(fs::read(wasm.path())?)
+                .map_err(Into::into)?;
+            contract.set_code_id(response.uploaded_code_id()?);
+        }
+        MigrationStep::UseExistingCode { code_id, .. } => contract.set_code_id(*code_id),
+        MigrationStep::Migrate {
+            contract_id,
+            address,
+            ..
+        } => {
+            chain
+                .migrate(&migrate_msgs[contract_id], contract.code_id()?, address)
+                .map_err(Into::into)?;
+        }
+    }
+    log::info!(target: &contract_target(), "Migration plan step done: {step}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rollback_reverts_applied_steps() {
+        let report = MigrationReport {
+            applied: vec![
+                MigrationStep::UseExistingCode {
+                    contract_id: "token".to_string(),
+                    code_id: 8,
+                    previous_code_id: Some(4),
+                },
+                MigrationStep::Migrate {
+                    contract_id: "token".to_string(),
+                    address: Addr::unchecked("token_addr"),
+                    from_code_id: 4,
+                },
+            ],
+            ..Default::default()
+        };
+        let rollback = report.rollback_steps();
+        assert_eq!(
+            rollback,
+            vec![
+                RollbackStep::MigrateBack {
+                    contract_id: "token".to_string(),
+                    address: Addr::unchecked("token_addr"),
+                    code_id: 4,
+                },
+                RollbackStep::RestoreCodeId {
+                    contract_id: "token".to_string(),
+                    code_id: Some(4),
+                },
+            ]
+        );
+        assert_eq!(
+            rollback[0].to_string(),
+            "Migrate token (token_addr) back to code 4"
+        );
+        assert_eq!(
+            report.applied[0].to_string(),
+            "Use existing code 8 for token"
+        );
+    }
+}
@@ -1,10 +1,18 @@
 mod contract_instance;
 mod deploy;
+mod deployment_export;
 pub mod interface_traits;
+mod migration_plan;
 mod paths;
+pub mod recording;
+mod versioned_migrate;
 
 pub use contract_instance::Contract;
 pub use deploy::Deploy;
+pub use deployment_export::{ChainDeployment, DeploymentExport, ExportedContract};
+pub use migration_plan::{MigrationPlan, MigrationReport, MigrationStep, RollbackStep};
+pub use recording::{RecordedCall, Recording};
 
 pub use paths::from_workspace as artifacts_dir_from_workspace;
-pub use paths::{ArtifactsDir, WasmPath};
+pub use paths::{ArtifactsDir, ArtifactsManifest, ArtifactsResolution, WasmPath};
+pub use versioned_migrate::{ContractVersion, VersionedMigrateMsgs};
@@ -1,6 +1,6 @@
 pub use artifacts_dir::from_workspace;
-pub use artifacts_dir::ArtifactsDir;
-pub use wasm_path::WasmPath;
+pub use artifacts_dir::{ArtifactsDir, ArtifactsManifest, ArtifactsResolution};
+pub use wasm_path::{WasmPath, CHECKSUMS_FILE};
 
 mod wasm_path {
     use crate::error::CwEnvError;
@@ -11,6 +11,9 @@ mod wasm_path {
         path::{Path, PathBuf},
     };
 
+    /// Name of the checksums file written by the cosmwasm optimizer in the artifacts directory
+    pub const CHECKSUMS_FILE: &str = "checksums.txt";
+
     /// Direct path to a `.wasm` file
     /// Stored as `PathBuf` to avoid lifetimes.
     /// Can be constructed from [`ArtifactsDir`](super::ArtifactsDir).
@@ -25,7 +28,7 @@ mod wasm_path {
     /// // Calculate the checksum of the WASM file.
     /// let checksum: cosmwasm_std::HexBinary = wasm_path.checksum().unwrap();
     /// ```
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub struct WasmPath(PathBuf);
 
     impl WasmPath {
@@ -58,6 +61,31 @@ mod wasm_path {
             let checksum: [u8; 32] = Sha256::digest(wasm).into();
             Ok(checksum.into())
         }
+
+        /// Checksum of the WASM file recorded in the `checksums.txt` file written next to it by the
+        /// [cosmwasm optimizer](https://github.com/CosmWasm/optimizer).
+        /// Returns `None` if there is no checksum file or if the file isn't listed in it.
+        pub fn optimizer_checksum(&self) -> Result<Option<HexBinary>, CwEnvError> {
+            let Some(checksums_path) = self.path().parent().map(|dir| dir.join(CHECKSUMS_FILE))
+            else {
+                return Ok(None);
+            };
+            if !checksums_path.exists() {
+                return Ok(None);
+            }
+            let file_name = self.path().file_name().and_then(|name| name.to_str());
+            let checksums = std::fs::read_to_string(checksums_path)?;
+            // Lines are formatted as `<hex checksum>  <file name>`
+            for line in checksums.lines() {
+                let mut parts = line.split_whitespace();
+                if let (Some(checksum), Some(name)) = (parts.next(), parts.next()) {
+                    if Some(name) == file_name {
+                        return Ok(Some(HexBinary::from_hex(checksum)?));
+                    }
+                }
+            }
+            Ok(None)
+        }
     }
 }
 
@@ -70,7 +98,12 @@ mod artifacts_dir {
         CoreEnvVars,
     };
 
-    use std::{env, fs, path::PathBuf};
+    use std::{
+        collections::BTreeMap,
+        env, fs,
+        path::{Path, PathBuf},
+        time::SystemTime,
+    };
 
     pub fn find_workspace_dir(start_path: Option<String>) -> ::std::path::PathBuf {
         let crate_path = start_path.unwrap_or(env!("CARGO_MANIFEST_DIR").to_string());
@@ -215,6 +248,209 @@ mod artifacts_dir {
         }
     }
 
+    impl ArtifactsDir {
+        /// Find a WASM file for the given contract name using a custom [`ArtifactsResolution`] strategy.
+        pub fn find_wasm_path_with(
+            &self,
+            name: &str,
+            resolution: &ArtifactsResolution,
+        ) -> Result<WasmPath, CwEnvError> {
+            if let Some(pinned) = resolution.pinned_path(name) {
+                return WasmPath::new(pinned);
+            }
+
+            let dirs = std::iter::once(self.path()).chain(resolution.extra_dirs.iter());
+            let mut candidates: Vec<(usize, PathBuf)> = vec![];
+            for dir in dirs {
+                for entry in fs::read_dir(dir)?.flatten() {
+                    let path = entry.path();
+                    if !path.is_file() || path.extension().unwrap_or_default() != "wasm" {
+                        continue;
+                    }
+                    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                    let Some(priority) = resolution.priority(&file_name, name) else {
+                        continue;
+                    };
+                    if resolution.verify_checksum && !matches_optimizer_checksum(&path)? {
+                        log::debug!(target: &local_target(), "Skipping {:?}, it doesn't match the optimizer checksum", path);
+                        continue;
+                    }
+                    candidates.push((priority, path));
+                }
+            }
+
+            let Some(best_priority) = candidates.iter().map(|(priority, _)| *priority).min() else {
+                return Err(CwEnvError::WasmNotFound(
+                    name.to_owned(),
+                    self.path().to_str().unwrap_or_default().to_owned(),
+                ));
+            };
+            let mut best: Vec<PathBuf> = candidates
+                .into_iter()
+                .filter(|(priority, _)| *priority == best_priority)
+                .map(|(_, path)| path)
+                .collect();
+            // Deterministic order, independent of the file system
+            best.sort();
+            let path = if resolution.latest {
+                best.into_iter().max_by_key(|path| modified(path)).unwrap()
+            } else {
+                best.swap_remove(0)
+            };
+            WasmPath::new(path)
+        }
+    }
+
+    /// Strategy used by [`ArtifactsDir::find_wasm_path_with`] to pick a WASM file when multiple artifacts match a contract.
+    ///
+    /// By default, artifacts are matched the same way as [`ArtifactsDir::find_wasm_path_with_build_postfix`]:
+    /// builds with the post-fix are preferred over default builds and non-ARM builds are preferred over ARM (`-aarch64`) builds.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use cw_orch_core::contract::{ArtifactsDir, ArtifactsManifest, ArtifactsResolution};
+    ///
+    /// let resolution = ArtifactsResolution::new()
+    ///     .build_postfix("juno")
+    ///     .extra_dir("contracts/other-workspace/artifacts")
+    ///     .latest()
+    ///     .verify_checksum()
+    ///     .pinned(ArtifactsManifest::load("artifacts/manifest.json").unwrap(), "juno-1");
+    ///
+    /// let wasm_path = ArtifactsDir::new("artifacts")
+    ///     .find_wasm_path_with("my_contract", &resolution)
+    ///     .unwrap();
+    /// ```
+    #[derive(Debug, Clone, Default)]
+    pub struct ArtifactsResolution {
+        build_postfix: String,
+        prefer_arm: bool,
+        latest: bool,
+        verify_checksum: bool,
+        extra_dirs: Vec<PathBuf>,
+        pinned: Option<(ArtifactsManifest, String)>,
+    }
+
+    impl ArtifactsResolution {
+        /// Default resolution strategy
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Prefer artifacts built with this post-fix, see [`BuildPostfix`]
+        pub fn build_postfix(mut self, build_postfix: impl Into<String>) -> Self {
+            self.build_postfix = build_postfix.into();
+            self
+        }
+
+        /// Prefer ARM (`-aarch64`) builds over non-ARM builds
+        pub fn prefer_arm(mut self) -> Self {
+            self.prefer_arm = true;
+            self
+        }
+
+        /// When multiple artifacts match with the same priority, pick the latest modified one
+        pub fn latest(mut self) -> Self {
+            self.latest = true;
+            self
+        }
+
+        /// Only consider artifacts listed with a matching checksum in the `checksums.txt` file of their directory
+        pub fn verify_checksum(mut self) -> Self {
+            self.verify_checksum = true;
+            self
+        }
+
+        /// Also search for artifacts in this directory (e.g. the artifacts directory of another workspace)
+        pub fn extra_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+            self.extra_dirs.push(dir.into());
+            self
+        }
+
+        /// Use the artifacts pinned for `chain_id` in the manifest, when there is one for the contract
+        pub fn pinned(mut self, manifest: ArtifactsManifest, chain_id: impl Into<String>) -> Self {
+            self.pinned = Some((manifest, chain_id.into()));
+            self
+        }
+
+        fn pinned_path(&self, name: &str) -> Option<PathBuf> {
+            let (manifest, chain_id) = self.pinned.as_ref()?;
+            manifest.artifact(chain_id, name)
+        }
+
+        /// Priority of the file for the contract, lower is better. `None` if the file is not an artifact of the contract
+        fn priority(&self, file_name: &str, name: &str) -> Option<usize> {
+            let priority = if !self.build_postfix.is_empty()
+                && is_artifact_with_build_postfix(file_name, name, &self.build_postfix)
+            {
+                0
+            } else if !self.build_postfix.is_empty()
+                && is_arm_artifact_with_build_postfix(file_name, name, &self.build_postfix)
+            {
+                1
+            } else if is_default_artifact(file_name, name) {
+                2
+            } else if is_default_arm_artifact(file_name, name) {
+                3
+            } else {
+                return None;
+            };
+            // ARM and non-ARM priorities are swapped when ARM builds are preferred
+            Some(if self.prefer_arm {
+                priority ^ 1
+            } else {
+                priority
+            })
+        }
+    }
+
+    /// Artifacts pinned per chain, e.g. to deploy builds with different features on different chains.
+    ///
+    /// The manifest is a json file that maps chain ids to contract names and wasm files.
+    /// Relative paths are resolved from the directory of the manifest.
+    /// ```json
+    /// {
+    ///   "juno-1": { "my_contract": "my_contract-juno.wasm" },
+    ///   "osmosis-1": { "my_contract": "osmosis/my_contract.wasm" }
+    /// }
+    /// ```
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct ArtifactsManifest {
+        base_dir: PathBuf,
+        artifacts: BTreeMap<String, BTreeMap<String, PathBuf>>,
+    }
+
+    impl ArtifactsManifest {
+        /// Loads a manifest file
+        pub fn load(path: impl AsRef<Path>) -> Result<Self, CwEnvError> {
+            let path = path.as_ref();
+            let artifacts = serde_json::from_slice(&fs::read(path)?)?;
+            Ok(Self {
+                base_dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+                artifacts,
+            })
+        }
+
+        /// Path of the artifact pinned for the contract on the chain
+        pub fn artifact(&self, chain_id: &str, name: &str) -> Option<PathBuf> {
+            self.artifacts
+                .get(chain_id)?
+                .get(name)
+                .map(|path| self.base_dir.join(path))
+        }
+    }
+
+    fn matches_optimizer_checksum(path: &Path) -> Result<bool, CwEnvError> {
+        let wasm = WasmPath::new(path)?;
+        Ok(wasm.optimizer_checksum()? == Some(wasm.checksum()?))
+    }
+
+    fn modified(path: &Path) -> SystemTime {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
     fn is_artifact(file_name: &str, contract_name: &str) -> bool {
         file_name.contains(contract_name)
     }
@@ -245,3 +481,108 @@ mod artifacts_dir {
             && file_name.ends_with(format!("{build_postfix}{ARM_POSTFIX}.wasm").as_str())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    fn artifacts_dir(test: &str, files: &[&str]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cw-orch-artifacts-{test}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for file in files {
+            fs::write(dir.join(file), file.as_bytes()).unwrap();
+        }
+        dir
+    }
+
+    fn file_name(wasm: WasmPath) -> String {
+        wasm.path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn artifacts_resolution() {
+        let dir = artifacts_dir(
+            "resolution",
+            &[
+                "my_contract.wasm",
+                "my_contract-aarch64.wasm",
+                "my_contract-juno.wasm",
+                "other_contract.wasm",
+            ],
+        );
+        let artifacts = ArtifactsDir::new(&dir);
+
+        let find = |resolution: ArtifactsResolution| {
+            file_name(
+                artifacts
+                    .find_wasm_path_with("my_contract", &resolution)
+                    .unwrap(),
+            )
+        };
+        assert_eq!(find(ArtifactsResolution::new()), "my_contract.wasm");
+        assert_eq!(
+            find(ArtifactsResolution::new().prefer_arm()),
+            "my_contract-aarch64.wasm"
+        );
+        assert_eq!(
+            find(ArtifactsResolution::new().build_postfix("juno")),
+            "my_contract-juno.wasm"
+        );
+        assert!(artifacts
+            .find_wasm_path_with("unknown", &ArtifactsResolution::new())
+            .is_err());
+
+        // Only the ARM build is listed in the checksum file
+        let checksum = WasmPath::new(dir.join("my_contract-aarch64.wasm"))
+            .unwrap()
+            .checksum()
+            .unwrap();
+        fs::write(
+            dir.join(CHECKSUMS_FILE),
+            format!("{}  my_contract-aarch64.wasm\n", checksum.to_hex()),
+        )
+        .unwrap();
+        assert_eq!(
+            find(ArtifactsResolution::new().verify_checksum()),
+            "my_contract-aarch64.wasm"
+        );
+
+        // Pinned artifacts take precedence
+        fs::write(
+            dir.join("manifest.json"),
+            r#"{"juno-1": {"my_contract": "other_contract.wasm"}}"#,
+        )
+        .unwrap();
+        let manifest = ArtifactsManifest::load(dir.join("manifest.json")).unwrap();
+        assert_eq!(
+            find(ArtifactsResolution::new().pinned(manifest.clone(), "juno-1")),
+            "other_contract.wasm"
+        );
+        assert_eq!(
+            find(ArtifactsResolution::new().pinned(manifest, "osmosis-1")),
+            "my_contract.wasm"
+        );
+    }
+
+    #[test]
+    fn extra_dirs() {
+        let dir = artifacts_dir("extra-dirs-main", &["my_contract.wasm"]);
+        let extra = artifacts_dir("extra-dirs-extra", &["other_contract.wasm"]);
+
+        let wasm = ArtifactsDir::new(dir)
+            .find_wasm_path_with(
+                "other_contract",
+                &ArtifactsResolution::new().extra_dir(extra),
+            )
+            .unwrap();
+        assert_eq!(file_name(wasm), "other_contract.wasm");
+    }
+}
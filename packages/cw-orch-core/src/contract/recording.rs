@@ -0,0 +1,281 @@
+//! Recording and replay of contract interactions
+//!
+//! While a recording is running, every successful instantiate, execute and migrate call made through a [`Contract`]
+//! (and so through the interface traits) is captured with its json message, funds and sender.
+//! Recordings are per thread: calls made by other threads (e.g. parallel tests) are not captured.
+//! The resulting [`Recording`] can be saved as a json script and replayed against another environment,
+//! e.g. recorded on a testnet and replayed on mainnet, or replayed on `Mock` as a regression test.
+//!
+//! On replay:
+//! - Calls are sent by the sender of the target environment, the recorded senders are informative.
+//! - Contracts are instantiated with the code id of their contract id in the target state, so the code must be uploaded first.
+//! - Migrations use the code id of the contract id in the target state.
+//! - Addresses of the contracts instantiated by the recording are replaced in the messages by the replayed addresses.
+//! - The replayed calls are not captured by a running recording.
+//!
+//! ## Usage
+//! ```ignore
+//! recording::start_recording();
+//! counter.instantiate(&InstantiateMsg { count: 0 }, None, None)?;
+//! counter.increment()?;
+//! recording::stop_recording().save("scripts/counter.json")?;
+//!
+//! // Later, against another environment with the counter code uploaded
+//! let recording = Recording::load("scripts/counter.json")?;
+//! recording.replay(&mock)?;
+//! ```
+
+use std::{cell::RefCell, collections::HashMap, path::Path};
+
+use cosmwasm_std::{Addr, Binary, Coin};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::Contract;
+use crate::{
+    environment::{CwEnv, IndexResponse},
+    log::contract_target,
+    CwEnvError,
+};
+
+thread_local! {
+    /// Calls recorded since [`start_recording`] on this thread, `None` when no recording is running
+    static RECORDER: RefCell<Option<Vec<RecordedCall>>> = const { RefCell::new(None) };
+}
+
+/// Contract call captured by a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RecordedCall {
+    /// Instantiation of the contract, with `instantiate2` when a salt is set
+    Instantiate {
+        contract_id: String,
+        msg: Value,
+        admin: Option<String>,
+        funds: Vec<Coin>,
+        sender: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        salt: Option<Binary>,
+        /// Address of the instantiated contract
+        address: String,
+    },
+    /// Execution on the contract
+    Execute {
+        contract_id: String,
+        msg: Value,
+        funds: Vec<Coin>,
+        sender: String,
+    },
+    /// Migration of the contract
+    Migrate {
+        contract_id: String,
+        msg: Value,
+        new_code_id: u64,
+        sender: String,
+    },
+}
+
+impl RecordedCall {
+    /// Id of the called contract
+    pub fn contract_id(&self) -> &str {
+        match self {
+            RecordedCall::Instantiate { contract_id, .. }
+            | RecordedCall::Execute { contract_id, .. }
+            | RecordedCall::Migrate { contract_id, .. } => contract_id,
+        }
+    }
+}
+
+/// Ordered contract calls, see the [module documentation](self)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    /// Calls in the order they were made
+    pub calls: Vec<RecordedCall>,
+}
+
+/// Starts capturing the contract calls of this thread, dropping the calls of a running recording
+pub fn start_recording() {
+    RECORDER.with(|recorder| *recorder.borrow_mut() = Some(vec![]));
+}
+
+/// Stops capturing the contract calls and returns the calls made on this thread since [`start_recording`]
+pub fn stop_recording() -> Recording {
+    Recording {
+        calls: RECORDER
+            .with(|recorder| recorder.borrow_mut().take())
+            .unwrap_or_default(),
+    }
+}
+
+/// Whether a recording is running on this thread
+pub fn is_recording() -> bool {
+    RECORDER.with(|recorder| recorder.borrow().is_some())
+}
+
+/// Adds the call to the running recording, the call is only built if a recording is running.
+/// The call already happened, so a call that can't be recorded is logged instead of failing it
+pub(crate) fn record(call: impl FnOnce() -> Result<RecordedCall, CwEnvError>) {
+    RECORDER.with(|recorder| {
+        if let Some(calls) = recorder.borrow_mut().as_mut() {
+            match call() {
+                Ok(call) => calls.push(call),
+                Err(error) => log::warn!(
+                    target: &contract_target(),
+                    "Contract call not recorded: {error}"
+                ),
+            }
+        }
+    });
+}
+
+impl Recording {
+    /// Writes the recording as a json script
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CwEnvError> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Reads a recording written by [`Recording::save`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CwEnvError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Re-executes the calls against `chain`, stopping at the first failing call
+    pub fn replay<Chain: CwEnv>(&self, chain: &Chain) -> Result<(), CwEnvError> {
+        // The running recording is suspended so that it doesn't capture the replay
+        let suspended = RECORDER.with(|recorder| recorder.borrow_mut().take());
+        let result = self.replay_calls(chain);
+        RECORDER.with(|recorder| *recorder.borrow_mut() = suspended);
+        result
+    }
+
+    fn replay_calls<Chain: CwEnv>(&self, chain: &Chain) -> Result<(), CwEnvError> {
+        // Recorded address -> replayed address
+        let mut addresses = HashMap::<String, String>::new();
+        for (index, call) in self.calls.iter().enumerate() {
+            let contract = Contract::new(call.contract_id(), chain.clone());
+            replay_call(&contract, call, &mut addresses).map_err(|error| {
+                CwEnvError::ReplayFailed {
+                    index,
+                    error: error.to_string(),
+                }
+            })?;
+            log::info!(
+                target: &contract_target(),
+                "[{}][Replayed] call {index}",
+                call.contract_id()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn replay_call<Chain: CwEnv>(
+    contract: &Contract<Chain>,
+    call: &RecordedCall,
+    addresses: &mut HashMap<String, String>,
+) -> Result<(), CwEnvError> {
+    match call {
+        RecordedCall::Instantiate {
+            msg,
+            admin,
+            funds,
+            salt,
+            address,
+            ..
+        } => {
+            let msg = replace_addresses(msg.clone(), addresses);
+            let admin = admin
+                .as_ref()
+                .map(|admin| Addr::unchecked(replaced(admin, addresses)));
+            let response = match salt {
+                Some(salt) => {
+                    contract.instantiate2(&msg, admin.as_ref(), Some(funds), salt.clone())?
+                }
+                None => contract.instantiate(&msg, admin.as_ref(), Some(funds))?,
+            };
+            addresses.insert(
+                address.clone(),
+                response.instantiated_contract_address()?.to_string(),
+            );
+        }
+        RecordedCall::Execute { msg, funds, .. } => {
+            contract.execute(&replace_addresses(msg.clone(), addresses), Some(funds))?;
+        }
+        RecordedCall::Migrate { msg, .. } => {
+            contract.migrate(
+                &replace_addresses(msg.clone(), addresses),
+                contract.code_id()?,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn replaced(value: &str, addresses: &HashMap<String, String>) -> String {
+    addresses
+        .get(value)
+        .cloned()
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Replaces the recorded addresses found in the string values of `msg`
+fn replace_addresses(msg: Value, addresses: &HashMap<String, String>) -> Value {
+    match msg {
+        Value::String(value) => Value::String(replaced(&value, addresses)),
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|value| replace_addresses(value, addresses))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, replace_addresses(value, addresses)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::coins;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn recording_roundtrip() -> anyhow::Result<()> {
+        start_recording();
+        record(|| {
+            Ok(RecordedCall::Execute {
+                contract_id: "counter".to_string(),
+                msg: json!({ "increment": {} }),
+                funds: coins(10, "ujuno"),
+                sender: "sender".to_string(),
+            })
+        });
+        // Calls that can't be recorded are skipped
+        record(|| Err(CwEnvError::StdErr("unserializable".to_string())));
+        let recording = stop_recording();
+        assert!(!is_recording());
+        assert_eq!(recording.calls.len(), 1);
+        assert_eq!(recording.calls[0].contract_id(), "counter");
+
+        let json = serde_json::to_value(&recording)?;
+        assert_eq!(json["calls"][0]["action"], "execute");
+        assert_eq!(serde_json::from_value::<Recording>(json)?, recording);
+
+        let addresses = HashMap::from([("juno1old".to_string(), "juno1new".to_string())]);
+        assert_eq!(
+            replace_addresses(
+                json!({ "transfer": { "recipient": "juno1old", "list": ["juno1old", 3] } }),
+                &addresses
+            ),
+            json!({ "transfer": { "recipient": "juno1new", "list": ["juno1new", 3] } })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+//! Migrate messages selected by the on-chain (cw2) version of a contract.
+//!
+//! Long-lived contracts accumulate migrate messages that only apply to some versions of the contract.
+//! The `interface` macro accepts a list of migrate message types, each with the [semver requirement](semver::VersionReq) on the on-chain version it migrates from:
+//! ```ignore
+//! #[interface(InstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg, migrate_versions(
+//!     MigrateFromV1 = ">=1.0.0, <2.0.0",
+//!     MigrateMsg = ">=2.0.0"
+//! ))]
+//! pub struct MyContract;
+//! ```
+//! [`CwOrchVersionedMigrate::versioned_migrate`] then reads the cw2 version of the contract and migrates with the matching message:
+//! ```ignore
+//! let msgs = VersionedMigrateMsgs::new()
+//!     .with(&MigrateFromV1 { new_admin: None })?
+//!     .with(&MigrateMsg {})?;
+//! contract.versioned_migrate(&msgs, new_code_id)?;
+//! ```
+
+use std::{collections::HashMap, fmt::Debug};
+
+use cw_storage_plus::Item;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::interface_traits::ContractInstance;
+use crate::{
+    environment::{CwEnv, TxResponse, WasmQuerier},
+    error::CwEnvError,
+    log::contract_target,
+};
+
+/// Storage key of the cw2 contract version
+const CW2_CONTRACT_INFO: Item<ContractVersion> = Item::new("contract_info");
+
+/// Version of a contract, as stored by cw2
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractVersion {
+    /// Name of the contract
+    pub contract: String,
+    /// Version of the contract
+    pub version: String,
+}
+
+/// Migrate message type applying to a range of on-chain versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrateMsgVersion {
+    /// Requirement on the on-chain version, in semver format (e.g. `">=1.0.0, <2.0.0"`)
+    pub version_req: &'static str,
+    /// Type name of the migrate message
+    pub type_name: &'static str,
+}
+
+impl MigrateMsgVersion {
+    /// Migrate message of type `M`, applying to contracts matching `version_req`
+    pub fn of<M: Serialize + Debug>(version_req: &'static str) -> Self {
+        Self {
+            version_req,
+            type_name: std::any::type_name::<M>(),
+        }
+    }
+
+    /// Returns whether the message applies to a contract with the given on-chain version
+    pub fn matches(&self, version: &str) -> Result<bool, CwEnvError> {
+        let req = VersionReq::parse(self.version_req).map_err(|e| {
+            CwEnvError::MigrateVersion(format!(
+                "invalid version requirement {}: {e}",
+                self.version_req
+            ))
+        })?;
+        Ok(req.matches(&parse_version(version)?))
+    }
+}
+
+/// Declares the migrate messages of a contract per on-chain version range.
+/// Implemented by the `interface` macro when `migrate_versions(...)` is provided.
+pub trait VersionedMigratableContract {
+    /// Migrate message types, in the order they are matched against the on-chain version
+    fn migrate_msg_versions() -> Vec<MigrateMsgVersion>;
+
+    /// Migrate message type that applies to a contract with the given on-chain version
+    fn migrate_msg_version(version: &str) -> Result<MigrateMsgVersion, CwEnvError> {
+        for msg_version in Self::migrate_msg_versions() {
+            if msg_version.matches(version)? {
+                return Ok(msg_version);
+            }
+        }
+        Err(CwEnvError::MigrateVersion(format!(
+            "no migrate message declared for version {version}"
+        )))
+    }
+}
+
+/// Migrate messages provided for a versioned migration, indexed by type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VersionedMigrateMsgs {
+    msgs: HashMap<&'static str, Value>,
+}
+
+impl VersionedMigrateMsgs {
+    /// Empty set of migrate messages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a migrate message. Messages of the same type are replaced
+    pub fn with<M: Serialize + Debug>(mut self, msg: &M) -> Result<Self, CwEnvError> {
+        self.msgs
+            .insert(std::any::type_name::<M>(), serde_json::to_value(msg)?);
+        Ok(self)
+    }
+
+    /// Message provided for the migrate message type
+    pub fn get(&self, msg_version: &MigrateMsgVersion) -> Option<&Value> {
+        self.msgs.get(msg_version.type_name)
+    }
+}
+
+/// Migration with the migrate message matching the on-chain (cw2) version of the contract.
+pub trait CwOrchVersionedMigrate<Chain: CwEnv>:
+    VersionedMigratableContract + ContractInstance<Chain>
+{
+    /// Queries the cw2 version of the contract
+    fn contract_version(&self) -> Result<ContractVersion, CwEnvError> {
+        self.get_chain()
+            .wasm_querier()
+            .item_query(self.address()?, CW2_CONTRACT_INFO)
+    }
+
+    /// Migrates the contract with the message matching its on-chain version
+    fn versioned_migrate(
+        &self,
+        msgs: &VersionedMigrateMsgs,
+        new_code_id: u64,
+    ) -> Result<TxResponse<Chain>, CwEnvError> {
+        let version = self.contract_version()?;
+        let msg_version = Self::migrate_msg_version(&version.version)?;
+        let msg = msgs.get(&msg_version).ok_or_else(|| {
+            CwEnvError::MigrateVersion(format!(
+                "no {} provided to migrate {} from version {}",
+                msg_version.type_name,
+                self.id(),
+                version.version
+            ))
+        })?;
+        log::info!(
+            target: &contract_target(),
+            "[{}][Migrate] from version {} with {}",
+            self.id(),
+            version.version,
+            msg_version.type_name
+        );
+        self.as_instance().migrate(msg, new_code_id)
+    }
+}
+
+impl<T: VersionedMigratableContract + ContractInstance<Chain>, Chain: CwEnv>
+    CwOrchVersionedMigrate<Chain> for T
+{
+}
+
+/// Parses a version, missing minor and patch numbers default to 0 (e.g. `1.2` is `1.2.0`)
+fn parse_version(version: &str) -> Result<Version, CwEnvError> {
+    let version = version.trim().trim_start_matches('v');
+    let core_len = version.find(['-', '+']).unwrap_or(version.len());
+    let missing = 2usize.saturating_sub(version[..core_len].matches('.').count());
+    let padded = format!(
+        "{}{}{}",
+        &version[..core_len],
+        ".0".repeat(missing),
+        &version[core_len..]
+    );
+    Version::parse(&padded)
+        .map_err(|e| CwEnvError::MigrateVersion(format!("invalid contract version {version}: {e}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Versioned;
+    impl VersionedMigratableContract for Versioned {
+        fn migrate_msg_versions() -> Vec<MigrateMsgVersion> {
+            vec![
+                MigrateMsgVersion::of::<()>("<1.0.0"),
+                MigrateMsgVersion::of::<Option<u8>>(">=1.0.0, <2.0.0"),
+                MigrateMsgVersion::of::<String>(">=2.0.0"),
+            ]
+        }
+    }
+
+    #[test]
+    fn select_migrate_msg() {
+        assert_eq!(Versioned::migrate_msg_version("0").unwrap().type_name, "()");
+        assert_eq!(
+            Versioned::migrate_msg_version("1.4").unwrap(),
+            MigrateMsgVersion::of::<Option<u8>>(">=1.0.0, <2.0.0")
+        );
+        assert_eq!(
+            Versioned::migrate_msg_version("v2.1.0").unwrap().type_name,
+            std::any::type_name::<String>()
+        );
+
+        let msgs = VersionedMigrateMsgs::new()
+            .with(&Some(1u8))
+            .unwrap()
+            .with(&"migrate".to_string())
+            .unwrap();
+        let msg_version = Versioned::migrate_msg_version("1.0.0").unwrap();
+        assert_eq!(msgs.get(&msg_version), Some(&Value::from(1)));
+        assert_eq!(msgs.get(&MigrateMsgVersion::of::<()>("<1.0.0")), None);
+
+        assert!(parse_version("not-a-version").is_err());
+        assert_eq!(
+            parse_version("1.0-beta").unwrap(),
+            Version::parse("1.0.0-beta").unwrap()
+        );
+    }
+}
@@ -13,6 +13,7 @@ use cosmwasm_std::StdError;
 pub const ARTIFACTS_DIR_ENV_NAME: &str = "ARTIFACTS_DIR";
 pub const SERIALIZE_ENV_NAME: &str = "CW_ORCH_SERIALIZE_JSON";
 pub const MANUAL_INTERACTION_ENV_NAME: &str = "CW_ORCH_MANUAL_INTERACTION";
+pub const GAS_GOLDEN_UPDATE_ENV_NAME: &str = "CW_ORCH_GAS_GOLDEN_UPDATE";
 
 pub struct CoreEnvVars;
 
@@ -57,6 +58,17 @@ impl CoreEnvVars {
             true
         }
     }
+
+    /// Optional - boolean
+    /// Defaults to "false"
+    /// If equals to true, gas golden files are overwritten with the new measurements instead of being checked
+    pub fn gas_golden_update() -> bool {
+        if let Ok(str_value) = env::var(GAS_GOLDEN_UPDATE_ENV_NAME) {
+            parse_with_log(str_value, GAS_GOLDEN_UPDATE_ENV_NAME)
+        } else {
+            false
+        }
+    }
 }
 
 fn parse_with_log<F: FromStr<Err = E>, E: std::fmt::Display>(
@@ -0,0 +1,34 @@
+//! Asynchronous counterparts of the transactional and query traits, for environments that are awaited.
+
+use super::{ChainState, IndexResponse};
+use crate::error::CwEnvError;
+use cosmwasm_std::{Addr, Coin};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Debug, future::Future};
+
+/// Environments that can query contracts asynchronously.
+pub trait AsyncWasmQuerier: ChainState + Clone {
+    /// Error type for queries and transactions on an environment.
+    type Error: Into<CwEnvError> + Debug + std::error::Error + Send + Sync + 'static;
+
+    /// Send a QueryMsg to a contract.
+    fn async_query<Q: Serialize + Debug, T: Serialize + DeserializeOwned>(
+        &self,
+        query_msg: &Q,
+        contract_address: &Addr,
+    ) -> impl Future<Output = Result<T, Self::Error>>;
+}
+
+/// Environments that can send transactions asynchronously.
+pub trait AsyncTxHandler: AsyncWasmQuerier {
+    /// Response type for transactions on an environment.
+    type Response: IndexResponse + Debug + Send + Clone;
+
+    /// Send a ExecMsg to a contract.
+    fn async_execute<E: Serialize + Debug>(
+        &self,
+        exec_msg: &E,
+        coins: &[Coin],
+        contract_address: &Addr,
+    ) -> impl Future<Output = Result<Self::Response, Self::Error>>;
+}
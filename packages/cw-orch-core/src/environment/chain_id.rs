@@ -0,0 +1,266 @@
+//! Strongly-typed chain identifiers.
+//!
+//! [`ChainId`] replaces raw strings when passing chain ids around, and [`TypedChain`] carries the [`ChainKind`] of a chain in its type.
+//! Functions can then require a chain of a given kind at compile time:
+//! ```
+//! use cw_orch_core::environment::{ChainInfo, ChainKind, Mainnet, NetworkInfo, TypedChain};
+//!
+//! const MY_MAINNET: TypedChain<Mainnet> = TypedChain::new(ChainInfo {
+//!     kind: ChainKind::Mainnet,
+//!     chain_id: "my-chain-1",
+//!     gas_denom: "umy",
+//!     gas_price: 0.025,
+//!     grpc_urls: &["http://grpc.my-chain.com:443"],
+//!     network_info: NetworkInfo {
+//!         chain_name: "my-chain",
+//!         pub_address_prefix: "my",
+//!         coin_type: 118u32,
+//!     },
+//!     lcd_url: None,
+//!     fcd_url: None,
+//!     rpc_url: None,
+//! });
+//!
+//! // Only accepts mainnet chains
+//! fn release(chain: TypedChain<Mainnet>) {
+//!     println!("Releasing on {}", chain.chain_id());
+//! }
+//!
+//! release(MY_MAINNET);
+//! ```
+//! Constructing a typed constant with a chain of another kind fails to compile.
+//! Chains only known at runtime are checked with [`TypedChain::try_new`].
+
+use std::{fmt::Display, marker::PhantomData, ops::Deref, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use super::{ChainInfo, ChainInfoOwned, ChainKind};
+use crate::CwEnvError;
+
+/// Identifier of a chain (e.g. `juno-1`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChainId(String);
+
+impl ChainId {
+    /// Chain id from its string representation
+    pub fn new(chain_id: impl Into<String>) -> Self {
+        Self(chain_id.into())
+    }
+
+    /// String representation of the chain id
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Revision number of the chain id, following the ibc format `{chain_name}-{revision}`
+    pub fn revision(&self) -> Option<u64> {
+        self.0.rsplit_once('-')?.1.parse().ok()
+    }
+}
+
+impl Display for ChainId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for ChainId {
+    type Err = CwEnvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s.chars().any(char::is_whitespace) {
+            return Err(CwEnvError::StdErr(format!("Invalid chain id: {s:?}")));
+        }
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl From<&str> for ChainId {
+    fn from(chain_id: &str) -> Self {
+        Self::new(chain_id)
+    }
+}
+
+impl From<String> for ChainId {
+    fn from(chain_id: String) -> Self {
+        Self(chain_id)
+    }
+}
+
+impl From<ChainId> for String {
+    fn from(chain_id: ChainId) -> Self {
+        chain_id.0
+    }
+}
+
+impl AsRef<str> for ChainId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for ChainId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ChainId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Kind of chain known at compile time, see [`TypedChain`]
+pub trait NetworkKind {
+    /// Kind of the chains
+    const KIND: ChainKind;
+}
+
+/// Mainnet chains
+#[derive(Debug, Clone, Copy)]
+pub struct Mainnet;
+/// Testnet chains
+#[derive(Debug, Clone, Copy)]
+pub struct Testnet;
+/// Local chains
+#[derive(Debug, Clone, Copy)]
+pub struct Local;
+
+impl NetworkKind for Mainnet {
+    const KIND: ChainKind = ChainKind::Mainnet;
+}
+impl NetworkKind for Testnet {
+    const KIND: ChainKind = ChainKind::Testnet;
+}
+impl NetworkKind for Local {
+    const KIND: ChainKind = ChainKind::Local;
+}
+
+/// Chain whose [`ChainKind`] is part of its type, see the [module documentation](self)
+#[derive(Debug, Clone)]
+pub struct TypedChain<K: NetworkKind> {
+    info: ChainInfo,
+    kind: PhantomData<K>,
+}
+
+impl<K: NetworkKind> TypedChain<K> {
+    /// Typed chain, panics if the chain is not of kind `K`.
+    /// When used in a constant, the kind is checked at compile time
+    pub const fn new(info: ChainInfo) -> Self {
+        assert!(
+            same_kind(&info.kind, &K::KIND),
+            "chain kind doesn't match the typed chain kind"
+        );
+        Self {
+            info,
+            kind: PhantomData,
+        }
+    }
+
+    /// Typed chain, errors if the chain is not of kind `K`
+    pub fn try_new(info: ChainInfo) -> Result<Self, CwEnvError> {
+        if info.kind != K::KIND {
+            return Err(CwEnvError::UnexpectedChainKind {
+                chain_id: info.chain_id.to_string(),
+                expected: K::KIND,
+                found: info.kind,
+            });
+        }
+        Ok(Self {
+            info,
+            kind: PhantomData,
+        })
+    }
+
+    /// Id of the chain
+    pub fn chain_id(&self) -> ChainId {
+        ChainId::from(self.info.chain_id)
+    }
+
+    /// Untyped chain information
+    pub fn info(&self) -> ChainInfo {
+        self.info.clone()
+    }
+}
+
+impl<K: NetworkKind> Deref for TypedChain<K> {
+    type Target = ChainInfo;
+
+    fn deref(&self) -> &Self::Target {
+        &self.info
+    }
+}
+
+impl<K: NetworkKind> TryFrom<ChainInfo> for TypedChain<K> {
+    type Error = CwEnvError;
+
+    fn try_from(info: ChainInfo) -> Result<Self, Self::Error> {
+        Self::try_new(info)
+    }
+}
+
+impl<K: NetworkKind> From<TypedChain<K>> for ChainInfo {
+    fn from(chain: TypedChain<K>) -> Self {
+        chain.info
+    }
+}
+
+impl<K: NetworkKind> From<TypedChain<K>> for ChainInfoOwned {
+    fn from(chain: TypedChain<K>) -> Self {
+        chain.info.into()
+    }
+}
+
+/// Const comparison of chain kinds
+const fn same_kind(a: &ChainKind, b: &ChainKind) -> bool {
+    matches!(
+        (a, b),
+        (ChainKind::Local, ChainKind::Local)
+            | (ChainKind::Testnet, ChainKind::Testnet)
+            | (ChainKind::Mainnet, ChainKind::Mainnet)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::environment::NetworkInfo;
+
+    const TESTNET: ChainInfo = ChainInfo {
+        kind: ChainKind::Testnet,
+        chain_id: "test-2",
+        gas_denom: "utest",
+        gas_price: 0.1,
+        grpc_urls: &[],
+        network_info: NetworkInfo {
+            chain_name: "test",
+            pub_address_prefix: "test",
+            coin_type: 118u32,
+        },
+        lcd_url: None,
+        fcd_url: None,
+        rpc_url: None,
+    };
+
+    #[test]
+    fn typed_chains() {
+        let testnet = TypedChain::<Testnet>::new(TESTNET);
+        assert_eq!(testnet.chain_id(), "test-2");
+        assert_eq!(testnet.chain_id().revision(), Some(2));
+        assert!(TypedChain::<Mainnet>::try_new(TESTNET).is_err());
+
+        assert_eq!("juno-1".parse::<ChainId>().unwrap(), ChainId::new("juno-1"));
+        assert!("".parse::<ChainId>().is_err());
+        assert!("juno 1".parse::<ChainId>().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn typed_chain_kind_mismatch() {
+        TypedChain::<Local>::new(TESTNET);
+    }
+}
@@ -25,6 +25,8 @@ pub struct ChainInfoBase<StringType: Into<String>, StringArrayType: AsRef<[Strin
     pub lcd_url: Option<StringType>,
     /// Optional urls for custom functionality
     pub fcd_url: Option<StringType>,
+    /// Optional tendermint RPC url, used for the queries not exposed over gRPC (consensus params, peers, abci queries with proofs)
+    pub rpc_url: Option<StringType>,
     /// Underlying network details (coin type, address prefix, etc)
     pub network_info: NetworkInfoBase<StringType>,
     /// Chain kind, (local, testnet, mainnet)
@@ -42,6 +44,34 @@ pub struct NetworkInfoBase<StringType> {
     pub coin_type: u32,
 }
 
+impl<StringType> NetworkInfoBase<StringType> {
+    /// Algorithm used to derive the account addresses from the public keys, see [`AddressAlgorithm::default_for_coin_type`].
+    /// Chains that don't follow the usual algorithm of their coin type set it on the sender (e.g. `DaemonBuilder::address_algorithm`)
+    pub const fn address_algorithm(&self) -> AddressAlgorithm {
+        AddressAlgorithm::default_for_coin_type(self.coin_type)
+    }
+}
+
+/// Algorithm used by a chain to derive account addresses from secp256k1 public keys
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+pub enum AddressAlgorithm {
+    /// `ripemd160(sha256(compressed public key))`, used by the Cosmos SDK
+    #[default]
+    Cosmos,
+    /// Last 20 bytes of `keccak256(uncompressed public key)`, used by ethermint chains (Injective, Evmos, etc)
+    Ethereum,
+}
+
+impl AddressAlgorithm {
+    /// Algorithm usually associated with a coin type
+    pub const fn default_for_coin_type(coin_type: u32) -> Self {
+        match coin_type {
+            60 => AddressAlgorithm::Ethereum,
+            _ => AddressAlgorithm::Cosmos,
+        }
+    }
+}
+
 impl From<ChainInfo> for ChainInfoOwned {
     fn from(value: ChainInfo) -> Self {
         ChainInfoOwned {
@@ -51,6 +81,7 @@ impl From<ChainInfo> for ChainInfoOwned {
             grpc_urls: value.grpc_urls.iter().map(|url| url.to_string()).collect(),
             lcd_url: value.lcd_url.map(ToString::to_string),
             fcd_url: value.fcd_url.map(ToString::to_string),
+            rpc_url: value.rpc_url.map(ToString::to_string),
             network_info: value.network_info.into(),
             kind: value.kind,
         }
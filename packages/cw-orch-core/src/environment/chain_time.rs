@@ -0,0 +1,31 @@
+use cosmwasm_std::Timestamp;
+
+use crate::CwEnvError;
+
+/// Moves the time of test environments, so that time-dependent tests (vesting, unbonding, expirations) run the same way on every environment.
+///
+/// Test environments implement it by changing their block info, a `Daemon` returns [`CwEnvError::TimeTravelUnsupported`]
+/// as the time of a live chain can't be changed.
+///
+/// ## Example
+/// ```
+/// use cw_orch_core::environment::ChainTime;
+/// # fn unbond<Chain: ChainTime>(chain: Chain) -> Result<(), Chain::Error> {
+/// // Unbonding period of 21 days
+/// chain.advance_seconds(21 * 24 * 60 * 60)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ChainTime {
+    type Error: Into<CwEnvError>;
+
+    /// Advances the chain by `blocks` blocks. Each block adds the block time of the environment
+    fn advance_blocks(&self, blocks: u64) -> Result<(), Self::Error>;
+
+    /// Advances the chain time by `seconds`. The height increases with the number of blocks produced in that time
+    fn advance_seconds(&self, seconds: u64) -> Result<(), Self::Error>;
+
+    /// Sets the time of the chain.
+    /// Environments backed by a chain binary (e.g. test-tube) can only move forward in time and error if `time` is in the past
+    fn set_block_time(&self, time: Timestamp) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,24 @@
+//! Executions of a contract, as found in the transaction history of a chain.
+
+use cosmwasm_std::{Addr, Binary, Coin};
+use serde::{Deserialize, Serialize};
+
+/// Execution of a contract by a transaction, used to replay the history of a contract in another environment.
+///
+/// Only top-level executions (sent in the transaction body) are recorded,
+/// executions triggered by other contracts are replayed by replaying the executions of the calling contracts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractExecution {
+    /// Height of the block including the transaction
+    pub height: u64,
+    /// Hash of the transaction
+    pub txhash: String,
+    /// Sender of the execution
+    pub sender: Addr,
+    /// Contract executed
+    pub contract: Addr,
+    /// Json encoded execute message
+    pub msg: Binary,
+    /// Funds sent with the execution
+    pub funds: Vec<Coin>,
+}
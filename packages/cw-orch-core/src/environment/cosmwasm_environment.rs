@@ -36,6 +36,10 @@ pub trait TxHandler: ChainState + Clone {
     /// Uploads a contract to the chain.
     fn upload<T: Uploadable>(&self, contract_source: &T) -> Result<Self::Response, Self::Error>;
 
+    /// Uploads raw wasm code to the chain, without reading it from the filesystem.
+    /// Environments that don't run wasm code return an error.
+    fn upload_from_bytes(&self, code: Vec<u8>) -> Result<Self::Response, Self::Error>;
+
     /// Send a InstantiateMsg to a contract.
     fn instantiate<I: Serialize + Debug>(
         &self,
@@ -82,6 +86,16 @@ pub trait TxHandler: ChainState + Clone {
     }
 }
 
+/// Environments that can call the `sudo` entry point of contracts, as the chain itself would.
+pub trait SudoHandler: TxHandler {
+    /// Send a SudoMsg to a contract.
+    fn sudo<S: Serialize + Debug>(
+        &self,
+        sudo_msg: &S,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, Self::Error>;
+}
+
 // TODO: Perfect test candidate for `trybuild`
 #[cfg(test)]
 mod tests {
@@ -149,6 +163,10 @@ mod tests {
             unimplemented!()
         }
 
+        fn upload_from_bytes(&self, _code: Vec<u8>) -> Result<Self::Response, Self::Error> {
+            unimplemented!()
+        }
+
         fn instantiate<I: Serialize + Debug>(
             &self,
             _code_id: u64,
@@ -0,0 +1,126 @@
+use cosmwasm_std::{Event, StdError};
+
+use crate::CwEnvError;
+
+use super::IndexResponse;
+
+/// Assertions on the events of a transaction response.
+/// This is implemented for all [`IndexResponse`] types, so the same assertions can be used on Mock, test tubes and Daemon responses.
+///
+/// ## Example
+/// ```
+/// use cosmwasm_std::Event;
+/// use cw_multi_test::AppResponse;
+/// use cw_orch_core::environment::EventAssertions;
+///
+/// let response = AppResponse {
+///     events: vec![Event::new("wasm").add_attribute("action", "first message passed")],
+///     data: None,
+/// };
+///
+/// response.assert_event("wasm", &[("action", "first message passed")]);
+/// response.assert_no_event("wasm", &[("action", "second message passed")]);
+/// ```
+pub trait EventAssertions: IndexResponse {
+    /// Returns the first event of type `event_type` that contains all the given `(key, value)` attributes.
+    /// The error lists the events of that type that were emitted
+    fn find_event(
+        &self,
+        event_type: &str,
+        attributes: &[(&str, &str)],
+    ) -> Result<Event, CwEnvError> {
+        let events = self.events();
+        events
+            .iter()
+            .find(|event| event_matches(event, event_type, attributes))
+            .cloned()
+            .ok_or_else(|| {
+                let expected = attributes
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                CwEnvError::EventNotFound(format!(
+                    "expected event `{event_type}` with attributes [{expected}]\n{}",
+                    describe_events(&events, event_type)
+                ))
+            })
+    }
+
+    /// Panics if no event of type `event_type` contains all the given `(key, value)` attributes.
+    /// Returns the matching event
+    #[track_caller]
+    fn assert_event(&self, event_type: &str, attributes: &[(&str, &str)]) -> Event {
+        match self.find_event(event_type, attributes) {
+            Ok(event) => event,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Panics if an event of type `event_type` contains all the given `(key, value)` attributes
+    #[track_caller]
+    fn assert_no_event(&self, event_type: &str, attributes: &[(&str, &str)]) {
+        if let Ok(event) = self.find_event(event_type, attributes) {
+            panic!("unexpected event found: {:?}", event)
+        }
+    }
+
+    /// Panics if the first `attr_key` attribute of the events of type `event_type` isn't `expected`
+    #[track_caller]
+    fn assert_event_attr_value(&self, event_type: &str, attr_key: &str, expected: &str) {
+        match self.event_attr_value(event_type, attr_key) {
+            Ok(value) => assert_eq!(
+                value, expected,
+                "unexpected value for attribute `{attr_key}` of event `{event_type}`"
+            ),
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+impl<T: IndexResponse + ?Sized> EventAssertions for T {}
+
+fn event_matches(event: &Event, event_type: &str, attributes: &[(&str, &str)]) -> bool {
+    event.ty == event_type
+        && attributes.iter().all(|(key, value)| {
+            event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == *key && attr.value == *value)
+        })
+}
+
+/// Lists the events of type `event_type`, or all the emitted event types if there is none
+fn describe_events(events: &[Event], event_type: &str) -> String {
+    let matching: Vec<_> = events.iter().filter(|e| e.ty == event_type).collect();
+    if matching.is_empty() {
+        let types = events
+            .iter()
+            .map(|e| e.ty.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return format!("no `{event_type}` event was emitted, emitted events: [{types}]");
+    }
+    matching
+        .iter()
+        .map(|event| {
+            let attributes = event
+                .attributes
+                .iter()
+                .map(|attr| format!("{}={}", attr.key, attr.value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("emitted `{event_type}` event: [{attributes}]")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Error returned by [`IndexResponse::event_attr_value`] implementations when the attribute is missing.
+/// It describes the emitted events to ease debugging
+pub fn missing_event_attr_error(events: &[Event], event_type: &str, attr_key: &str) -> StdError {
+    StdError::generic_err(format!(
+        "missing attribute `{attr_key}` on event `{event_type}`\n{}",
+        describe_events(events, event_type)
+    ))
+}
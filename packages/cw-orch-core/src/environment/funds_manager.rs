@@ -0,0 +1,98 @@
+use cosmwasm_std::{Coin, Uint128};
+
+use crate::CwEnvError;
+
+/// Manipulates the native balances of accounts, so that test setup code runs the same way on every non-mainnet environment.
+///
+/// Test environments (`Mock`, `CloneTesting`, test-tube) change their bank state directly.
+/// `Starship` emulates it with faucet requests: it can only increase balances and returns [`CwEnvError::BalanceUnsupported`] otherwise.
+///
+/// ## Example
+/// ```
+/// use cosmwasm_std::coins;
+/// use cw_orch_core::environment::FundsManager;
+/// # fn setup<Chain: FundsManager>(chain: Chain) -> Result<(), Chain::Error> {
+/// chain.add_balance("user", coins(1_000_000, "ujuno"))?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait FundsManager {
+    type Error: Into<CwEnvError>;
+
+    /// Sets the balance of `address` to exactly `amount`
+    fn set_balance(&self, address: impl Into<String>, amount: Vec<Coin>)
+        -> Result<(), Self::Error>;
+
+    /// Adds `amount` to the balance of `address`
+    fn add_balance(&self, address: impl Into<String>, amount: Vec<Coin>)
+        -> Result<(), Self::Error>;
+
+    /// Mints `amount` to `address`, increasing the total supply of the tokens.
+    /// Defaults to [`FundsManager::add_balance`] for environments without a supply
+    fn mint_native(
+        &self,
+        address: impl Into<String>,
+        amount: Vec<Coin>,
+    ) -> Result<(), Self::Error> {
+        self.add_balance(address, amount)
+    }
+}
+
+/// Coins to add to `current` to reach the `target` balance.
+/// Used by the environments that can only increase balances, errors if a balance of `current` would have to decrease
+pub fn missing_funds(current: &[Coin], target: &[Coin]) -> Result<Vec<Coin>, CwEnvError> {
+    let target_of = |denom: &str| {
+        target
+            .iter()
+            .filter(|c| c.denom == denom)
+            .map(|c| c.amount)
+            .sum::<Uint128>()
+    };
+    if let Some(coin) = current.iter().find(|c| c.amount > target_of(&c.denom)) {
+        return Err(CwEnvError::BalanceUnsupported(format!(
+            "balance of {} would decrease to {}, it can only be increased",
+            coin,
+            target_of(&coin.denom)
+        )));
+    }
+
+    let mut missing: Vec<Coin> = vec![];
+    for coin in target {
+        if missing.iter().any(|c| c.denom == coin.denom) {
+            continue;
+        }
+        let held = current
+            .iter()
+            .filter(|c| c.denom == coin.denom)
+            .map(|c| c.amount)
+            .sum::<Uint128>();
+        let amount = target_of(&coin.denom) - held;
+        if !amount.is_zero() {
+            missing.push(Coin::new(amount.u128(), coin.denom.clone()));
+        }
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coin, coins};
+
+    use super::*;
+
+    #[test]
+    fn missing_funds_only_increase() {
+        let current = vec![coin(10, "ujuno"), coin(5, "uosmo")];
+        assert_eq!(
+            missing_funds(
+                &current,
+                &[coin(15, "ujuno"), coin(5, "uosmo"), coin(3, "uatom")]
+            )
+            .unwrap(),
+            vec![coin(5, "ujuno"), coin(3, "uatom")]
+        );
+        // uosmo would go back to 0
+        assert!(missing_funds(&current, &coins(15, "ujuno")).is_err());
+        assert!(missing_funds(&current, &[coin(9, "ujuno"), coin(5, "uosmo")]).is_err());
+    }
+}
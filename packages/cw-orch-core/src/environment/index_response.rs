@@ -1,10 +1,16 @@
-use cosmwasm_std::{Addr, Binary, Event, StdError, StdResult};
+use cosmwasm_std::{Addr, Binary, Decimal, Event, StdResult, Uint128, Uint64};
 use cw_multi_test::AppResponse;
 #[cfg(feature = "eth")]
 use snailquote::unescape;
 
+use super::missing_event_attr_error;
+use crate::CwEnvError;
+
 const CODE_ID_UPLOAD_EVENT: (&str, &str) = ("store_code", "code_id");
 const ADDRESS_INSTANTIATE_EVENT: (&str, &str) = ("instantiate", "_contract_address");
+const FEE_TX_EVENT: (&str, &str) = ("tx", "fee");
+/// Event added by simulated environments that execute wasm (e.g. wasm-sim) to report the gas used by the transaction
+pub const GAS_USED_EVENT: (&str, &str) = ("mock_gas", "gas_used");
 
 #[cfg(feature = "eth")]
 const INJECTIVE_CODE_ID_UPLOAD_EVENT: (&str, &str) =
@@ -32,6 +38,21 @@ pub trait IndexResponse {
     /// Get the data field of the response.
     fn data(&self) -> Option<Binary>;
 
+    /// Get the gas used by the transaction, if the environment reports it.
+    fn gas_used(&self) -> Option<u64> {
+        None
+    }
+
+    /// Get the hash of the transaction, if the environment reports it.
+    fn tx_hash(&self) -> Option<String> {
+        None
+    }
+
+    /// Get the fee paid for the transaction (e.g. `1500ujuno`), if the environment reports it.
+    fn fee(&self) -> Option<String> {
+        self.event_attr_value(FEE_TX_EVENT.0, FEE_TX_EVENT.1).ok()
+    }
+
     /// Helper to get the contract address of a instantiate response.
     fn instantiated_contract_address(&self) -> StdResult<Addr> {
         if let Ok(code_id) = self
@@ -83,6 +104,12 @@ impl IndexResponse for AppResponse {
         self.data.clone()
     }
 
+    fn gas_used(&self) -> Option<u64> {
+        self.event_attr_value(GAS_USED_EVENT.0, GAS_USED_EVENT.1)
+            .ok()
+            .and_then(|gas| gas.parse().ok())
+    }
+
     fn event_attr_value(&self, event_type: &str, attr_key: &str) -> StdResult<String> {
         for event in &self.events {
             if event.ty == event_type {
@@ -93,10 +120,7 @@ impl IndexResponse for AppResponse {
                 }
             }
         }
-        Err(StdError::generic_err(format!(
-            "missing combination (event: {}, attribute: {})",
-            event_type, attr_key
-        )))
+        Err(missing_event_attr_error(&self.events, event_type, attr_key))
     }
 
     fn event_attr_values(&self, event_type: &str, attr_key: &str) -> Vec<String> {
@@ -115,6 +139,44 @@ impl IndexResponse for AppResponse {
     }
 }
 
+/// Values that can be parsed from the value of an event attribute.
+/// Used by the execute functions generated for variants with the `#[cw_orch(event_attr(..))]` attribute.
+pub trait FromEventAttribute: Sized {
+    /// Parses the value of an event attribute
+    fn from_event_attribute(value: String) -> Result<Self, CwEnvError>;
+}
+
+impl FromEventAttribute for String {
+    fn from_event_attribute(value: String) -> Result<Self, CwEnvError> {
+        Ok(value)
+    }
+}
+
+impl FromEventAttribute for Addr {
+    fn from_event_attribute(value: String) -> Result<Self, CwEnvError> {
+        Ok(Addr::unchecked(value))
+    }
+}
+
+macro_rules! from_str_event_attribute {
+    ($($ty:ty),*) => {
+        $(
+            impl FromEventAttribute for $ty {
+                fn from_event_attribute(value: String) -> Result<Self, CwEnvError> {
+                    value.parse().map_err(|e| {
+                        CwEnvError::StdErr(format!(
+                            "Can't parse event attribute value {value} as {}: {e}",
+                            stringify!($ty)
+                        ))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+from_str_event_attribute!(u8, u16, u32, u64, u128, i32, i64, bool, Uint64, Uint128, Decimal);
+
 #[cfg(test)]
 mod index_response_test {
     use cosmwasm_std::{Addr, Event};
@@ -122,7 +184,7 @@ mod index_response_test {
 
     use speculoos::prelude::*;
 
-    use super::IndexResponse;
+    use super::{FromEventAttribute, IndexResponse};
 
     const CONTRACT_ADDRESS: &str =
         "cosmos1fd68ah02gr2y8ze7tm9te7m70zlmc7vjyyhs6xlhsdmqqcjud4dql4wpxr";
@@ -184,4 +246,21 @@ mod index_response_test {
             .that(&test_uploaded_code_id(&idxres))
             .is_ok();
     }
+
+    #[test]
+    fn from_event_attribute() -> anyhow::Result<()> {
+        let idxres = AppResponse {
+            events: vec![Event::new("wasm")
+                .add_attribute("pool_id", "12")
+                .add_attribute("pool", CONTRACT_ADDRESS)],
+            data: None,
+        };
+
+        let pool_id = u64::from_event_attribute(idxres.event_attr_value("wasm", "pool_id")?)?;
+        assert_eq!(pool_id, 12);
+        let pool = Addr::from_event_attribute(idxres.event_attr_value("wasm", "pool")?)?;
+        assert_eq!(pool, Addr::unchecked(CONTRACT_ADDRESS));
+        assert!(u64::from_event_attribute(CONTRACT_ADDRESS.to_string()).is_err());
+        Ok(())
+    }
 }
@@ -1,19 +1,35 @@
+mod async_env;
+mod chain_id;
 mod chain_info;
+mod chain_time;
+mod contract_execution;
 mod cosmwasm_environment;
+mod event_assertions;
+mod funds_manager;
 mod index_response;
 mod mut_env;
 mod queriers;
 mod state;
+mod storage_inspector;
 
-pub use chain_info::{ChainInfo, ChainInfoOwned, ChainKind, NetworkInfo, NetworkInfoOwned};
-pub use cosmwasm_environment::{CwEnv, TxHandler, TxResponse};
-pub use index_response::IndexResponse;
+pub use async_env::{AsyncTxHandler, AsyncWasmQuerier};
+pub use chain_id::{ChainId, Local, Mainnet, NetworkKind, Testnet, TypedChain};
+pub use chain_info::{
+    AddressAlgorithm, ChainInfo, ChainInfoOwned, ChainKind, NetworkInfo, NetworkInfoOwned,
+};
+pub use chain_time::ChainTime;
+pub use contract_execution::ContractExecution;
+pub use cosmwasm_environment::{CwEnv, SudoHandler, TxHandler, TxResponse};
+pub use event_assertions::{missing_event_attr_error, EventAssertions};
+pub use funds_manager::{missing_funds, FundsManager};
+pub use index_response::{FromEventAttribute, IndexResponse, GAS_USED_EVENT};
 pub use mut_env::{BankSetter, MutCwEnv};
 pub use queriers::{
     bank::BankQuerier,
     env::{EnvironmentInfo, EnvironmentQuerier},
     node::NodeQuerier,
-    wasm::WasmQuerier,
+    wasm::{StorageKey, WasmQuerier},
     DefaultQueriers, Querier, QuerierGetter, QueryHandler,
 };
 pub use state::{ChainState, StateInterface};
+pub use storage_inspector::{StorageDiff, StorageDump, StorageInspector};
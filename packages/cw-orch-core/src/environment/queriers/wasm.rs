@@ -1,4 +1,6 @@
-use cosmwasm_std::{from_json, CodeInfoResponse, ContractInfoResponse, HexBinary};
+use std::fmt::Display;
+
+use cosmwasm_std::{from_json, Addr, CodeInfoResponse, ContractInfoResponse, HexBinary};
 use cw_storage_plus::{Item, Map, PrimaryKey};
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -52,6 +54,46 @@ pub trait WasmQuerier: Querier {
         from_json(current_manager_version).map_err(Into::into)
     }
 
+    /// Query a value stored with cw-storage-plus in the raw storage of a contract.
+    /// Errors if no value is stored under `key`
+    ///
+    /// ```
+    /// # use cw_orch_core::{environment::{StorageKey, WasmQuerier}, CwEnvError};
+    /// # #[derive(serde::Deserialize)] struct Config {}
+    /// # fn config<Q: WasmQuerier>(querier: Q, contract: &str) -> Result<(), CwEnvError> {
+    /// let config: Config = querier.raw_query_typed(contract, StorageKey::item("config"))?;
+    /// let balance: u128 = querier.raw_query_typed(contract, StorageKey::map("balances", ("juno1...", 5u64)))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn raw_query_typed<T: DeserializeOwned>(
+        &self,
+        address: impl Into<String>,
+        key: StorageKey,
+    ) -> Result<T, CwEnvError> {
+        let address = address.into();
+        self.may_raw_query_typed(address.clone(), key.clone())?
+            .ok_or_else(|| CwEnvError::StorageKeyNotFound {
+                address,
+                key: key.to_string(),
+            })
+    }
+
+    /// Query a value stored with cw-storage-plus in the raw storage of a contract, `None` if no value is stored under `key`
+    fn may_raw_query_typed<T: DeserializeOwned>(
+        &self,
+        address: impl Into<String>,
+        key: StorageKey,
+    ) -> Result<Option<T>, CwEnvError> {
+        let raw_value = self
+            .raw_query(address, key.to_bytes())
+            .map_err(Into::into)?;
+        if raw_value.is_empty() {
+            return Ok(None);
+        }
+        from_json(raw_value).map(Some).map_err(Into::into)
+    }
+
     fn smart_query<Q: Serialize, T: DeserializeOwned>(
         &self,
         address: impl Into<String>,
@@ -67,6 +109,16 @@ pub trait WasmQuerier: Querier {
         contract: &T,
     ) -> Result<HexBinary, CwEnvError>;
 
+    /// Returns the id of the latest code uploaded with the given checksum that `instantiator` is allowed to instantiate, if any.
+    /// Environments that can't look codes up by checksum return `None`, in which case the code is uploaded again.
+    fn code_id_by_checksum(
+        &self,
+        _checksum: &HexBinary,
+        _instantiator: &Addr,
+    ) -> Result<Option<u64>, Self::Error> {
+        Ok(None)
+    }
+
     fn instantiate2_addr(
         &self,
         code_id: u64,
@@ -74,3 +126,96 @@ pub trait WasmQuerier: Querier {
         salt: cosmwasm_std::Binary,
     ) -> Result<String, Self::Error>;
 }
+
+/// Key of a value stored with cw-storage-plus, encoded like the storage types of the contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageKey {
+    /// Value of an `Item` stored under this namespace
+    Item(String),
+    /// Value of a `Map` stored under `namespace`.
+    /// `key` contains the parts of the key, e.g. 2 parts for a `(&Addr, u64)` key
+    Map {
+        namespace: String,
+        key: Vec<Vec<u8>>,
+    },
+    /// Raw storage key
+    Raw(Vec<u8>),
+}
+
+impl StorageKey {
+    /// Key of an `Item`
+    pub fn item(namespace: impl Into<String>) -> Self {
+        StorageKey::Item(namespace.into())
+    }
+
+    /// Key of the value of `key` in a `Map`. Composite keys are tuples, as in the `Map` definition
+    pub fn map<'a>(namespace: impl Into<String>, key: impl PrimaryKey<'a>) -> Self {
+        StorageKey::Map {
+            namespace: namespace.into(),
+            key: key.key().iter().map(|k| k.as_ref().to_vec()).collect(),
+        }
+    }
+
+    /// Raw storage key, with the cw-storage-plus encoding
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            StorageKey::Item(namespace) => namespace.as_bytes().to_vec(),
+            StorageKey::Map { namespace, key } => {
+                // The namespace and all the parts but the last one are prefixed with their length
+                let Some((last, prefixes)) = key.split_last() else {
+                    return namespace.as_bytes().to_vec();
+                };
+                let mut bytes = vec![];
+                for part in
+                    std::iter::once(namespace.as_bytes()).chain(prefixes.iter().map(Vec::as_slice))
+                {
+                    bytes.extend_from_slice(&(part.len() as u16).to_be_bytes());
+                    bytes.extend_from_slice(part);
+                }
+                bytes.extend_from_slice(last);
+                bytes
+            }
+            StorageKey::Raw(key) => key.clone(),
+        }
+    }
+}
+
+impl Display for StorageKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageKey::Item(namespace) => write!(f, "item `{namespace}`"),
+            StorageKey::Map { namespace, .. } => write!(
+                f,
+                "map `{namespace}` key {}",
+                HexBinary::from(self.to_bytes())
+            ),
+            StorageKey::Raw(key) => write!(f, "raw key {}", HexBinary::from(key.as_slice())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::Addr;
+
+    use super::*;
+
+    #[test]
+    fn storage_keys_match_cw_storage_plus() {
+        let config: Item<u64> = Item::new("config");
+        assert_eq!(StorageKey::item("config").to_bytes(), config.as_slice());
+
+        let balances: Map<&Addr, u128> = Map::new("balances");
+        let addr = Addr::unchecked("juno1user");
+        assert_eq!(
+            StorageKey::map("balances", &addr).to_bytes(),
+            balances.key(&addr).to_vec()
+        );
+
+        let positions: Map<(&Addr, u64, &str), u128> = Map::new("positions");
+        assert_eq!(
+            StorageKey::map("positions", (&addr, 5u64, "uatom")).to_bytes(),
+            positions.key((&addr, 5, "uatom")).to_vec()
+        );
+    }
+}
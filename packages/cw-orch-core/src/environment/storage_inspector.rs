@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use cosmwasm_std::Addr;
+
+use crate::CwEnvError;
+
+/// Raw storage of a contract, indexed by key
+pub type StorageDump = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Allows reading the full raw storage of contracts.
+/// This is mostly useful in migration tests, to assert which storage keys were changed by a migration.
+///
+/// ## Example
+/// ```
+/// use cw_orch_core::environment::StorageInspector;
+/// # fn check<Chain: StorageInspector>(chain: Chain, contract: cosmwasm_std::Addr) -> Result<(), Chain::Error> {
+/// let before = chain.dump_storage(&contract)?;
+/// // Migrate the contract
+/// let after = chain.dump_storage(&contract)?;
+///
+/// let diff = chain.diff_storage(&before, &after);
+/// assert!(diff.removed.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub trait StorageInspector {
+    type Error: Into<CwEnvError>;
+
+    /// Returns all the raw storage entries of a contract
+    fn dump_storage(&self, contract: &Addr) -> Result<StorageDump, Self::Error>;
+
+    /// Compares two storage dumps of the same contract
+    fn diff_storage(&self, before: &StorageDump, after: &StorageDump) -> StorageDiff {
+        StorageDiff::new(before, after)
+    }
+}
+
+/// Difference between two [`StorageDump`]s
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StorageDiff {
+    /// Entries that only exist in the new storage
+    pub added: StorageDump,
+    /// Entries that only exist in the old storage
+    pub removed: StorageDump,
+    /// Entries whose value changed, as `(before, after)`
+    pub changed: BTreeMap<Vec<u8>, (Vec<u8>, Vec<u8>)>,
+}
+
+impl StorageDiff {
+    /// Computes the difference between two storage dumps
+    pub fn new(before: &StorageDump, after: &StorageDump) -> Self {
+        let mut diff = Self::default();
+        for (key, old_value) in before {
+            match after.get(key) {
+                None => {
+                    diff.removed.insert(key.clone(), old_value.clone());
+                }
+                Some(new_value) if new_value != old_value => {
+                    diff.changed
+                        .insert(key.clone(), (old_value.clone(), new_value.clone()));
+                }
+                _ => {}
+            }
+        }
+        for (key, new_value) in after {
+            if !before.contains_key(key) {
+                diff.added.insert(key.clone(), new_value.clone());
+            }
+        }
+        diff
+    }
+
+    /// Returns true if both dumps are identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// All the keys that were added, removed or changed
+    pub fn keys(&self) -> Vec<&Vec<u8>> {
+        let mut keys: Vec<_> = self
+            .added
+            .keys()
+            .chain(self.removed.keys())
+            .chain(self.changed.keys())
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+impl Display for StorageDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in &self.added {
+            writeln!(f, "+ {}: {}", lossy(key), lossy(value))?;
+        }
+        for (key, value) in &self.removed {
+            writeln!(f, "- {}: {}", lossy(key), lossy(value))?;
+        }
+        for (key, (before, after)) in &self.changed {
+            writeln!(f, "~ {}: {} -> {}", lossy(key), lossy(before), lossy(after))?;
+        }
+        Ok(())
+    }
+}
+
+fn lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn storage_diff() {
+        let before = StorageDump::from([
+            (b"kept".to_vec(), b"1".to_vec()),
+            (b"removed".to_vec(), b"2".to_vec()),
+            (b"changed".to_vec(), b"3".to_vec()),
+        ]);
+        let after = StorageDump::from([
+            (b"kept".to_vec(), b"1".to_vec()),
+            (b"changed".to_vec(), b"4".to_vec()),
+            (b"added".to_vec(), b"5".to_vec()),
+        ]);
+
+        let diff = StorageDiff::new(&before, &after);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(
+            diff.changed.get(b"changed".as_slice()),
+            Some(&(b"3".to_vec(), b"4".to_vec()))
+        );
+        assert_eq!(diff.keys().len(), 3);
+        assert!(StorageDiff::new(&before, &before).is_empty());
+    }
+}
@@ -44,6 +44,36 @@ pub enum CwEnvError {
     StdErr(String),
     #[error("Environment variable not defined {0}")]
     EnvVarNotPresentNamed(String),
+    #[error("Gas regression detected\n{0}")]
+    GasRegression(String),
+    #[error("Event assertion failed: {0}")]
+    EventNotFound(String),
+    #[error("Scenario failed\n{0}")]
+    ScenarioFailed(String),
+    #[error("Environments diverged\n{0}")]
+    EnvironmentsDiverged(String),
+    #[error("Conformance checks failed\n{0}")]
+    ConformanceFailed(String),
+    #[error("Versioned migration failed: {0}")]
+    MigrateVersion(String),
+    #[error("Migration plan failed: {0}")]
+    MigrationPlan(String),
+    #[error("Replay failed at call {index}: {error}")]
+    ReplayFailed { index: usize, error: String },
+    #[error("Can't change the time of this environment: {0}")]
+    TimeTravelUnsupported(String),
+    #[error("Can't change this balance in this environment: {0}")]
+    BalanceUnsupported(String),
+    #[error("No value stored under {key} in {address}")]
+    StorageKeyNotFound { address: String, key: String },
+    #[error("The sender of a {found} environment can't be used on a {expected} environment")]
+    SenderMismatch { expected: String, found: String },
+    #[error("Chain {chain_id} is a {found} chain, expected a {expected} chain")]
+    UnexpectedChainKind {
+        chain_id: String,
+        expected: crate::environment::ChainKind,
+        found: crate::environment::ChainKind,
+    },
 }
 
 impl CwEnvError {
@@ -0,0 +1,455 @@
+//! Gas usage regression tracking and profiling.
+//!
+//! Records the gas used by named operations and compares it against a golden file committed alongside the code.
+//! The golden file is a json file indexed by environment key (usually the chain id) and operation name.
+//! ```json
+//! {
+//!   "juno-1": {
+//!     "counter:increment": 132456
+//!   }
+//! }
+//! ```
+//! Set the `CW_ORCH_GAS_GOLDEN_UPDATE` env variable to `true`, or call [`GasGoldenFile::update`], to overwrite the golden file with the new measurements.
+//!
+//! The [`GasProfiler`] records the gas used, fee paid and hash of every transaction of a script,
+//! which is useful to estimate deployment costs from testnet runs.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cw_orch_core::gas::GasGoldenFile;
+//!
+//! let mut golden = GasGoldenFile::load("gas.json", "juno-1").unwrap().threshold(5.0);
+//! golden.record("counter:increment", 132_501);
+//! // Errors if any operation uses more than 5% gas than recorded in the golden file
+//! golden.check().unwrap();
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use crate::{environment::IndexResponse, CoreEnvVars, CwEnvError};
+
+type GoldenContent = BTreeMap<String, BTreeMap<String, u64>>;
+
+/// Default allowed deviation, in percent
+pub const DEFAULT_GAS_THRESHOLD: f64 = 1.0;
+
+/// Gas measurements compared against a golden file
+#[derive(Debug, Clone)]
+pub struct GasGoldenFile {
+    path: PathBuf,
+    key: String,
+    threshold: f64,
+    fail_on_regression: bool,
+    update: bool,
+    golden: GoldenContent,
+    recorded: BTreeMap<String, u64>,
+}
+
+impl GasGoldenFile {
+    /// Loads the golden file at `path` for the environment identified by `key`.
+    /// The file doesn't need to exist, it will be created when calling [`GasGoldenFile::save`].
+    /// The golden file is updated instead of checked if `CW_ORCH_GAS_GOLDEN_UPDATE` is set, see [`GasGoldenFile::update`]
+    pub fn load(path: impl AsRef<Path>, key: impl Into<String>) -> Result<Self, CwEnvError> {
+        let path = path.as_ref().to_path_buf();
+        let golden = if path.exists() {
+            serde_json::from_reader(File::open(&path)?)?
+        } else {
+            GoldenContent::default()
+        };
+
+        Ok(Self {
+            path,
+            key: key.into(),
+            threshold: DEFAULT_GAS_THRESHOLD,
+            fail_on_regression: true,
+            update: CoreEnvVars::gas_golden_update(),
+            golden,
+            recorded: BTreeMap::new(),
+        })
+    }
+
+    /// Sets the allowed gas deviation, in percent, before an operation is reported as a regression
+    pub fn threshold(mut self, percent: f64) -> Self {
+        self.threshold = percent;
+        self
+    }
+
+    /// Only log regressions as warnings instead of erroring in [`GasGoldenFile::check`]
+    pub fn warn_only(mut self) -> Self {
+        self.fail_on_regression = false;
+        self
+    }
+
+    /// Sets whether [`GasGoldenFile::check`] overwrites the golden file instead of checking it,
+    /// overriding the `CW_ORCH_GAS_GOLDEN_UPDATE` env variable
+    pub fn update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Records the gas used by an operation
+    pub fn record(&mut self, operation: impl Into<String>, gas_used: u64) {
+        self.recorded.insert(operation.into(), gas_used);
+    }
+
+    /// Records the gas used by a transaction response
+    /// Errors if the environment doesn't report gas usage
+    pub fn record_response(
+        &mut self,
+        operation: impl Into<String>,
+        response: &impl IndexResponse,
+    ) -> Result<(), CwEnvError> {
+        let operation = operation.into();
+        let gas_used = response.gas_used().ok_or(CwEnvError::StdErr(format!(
+            "No gas usage reported for operation {operation}"
+        )))?;
+        self.record(operation, gas_used);
+        Ok(())
+    }
+
+    /// Compares recorded measurements with the golden file
+    pub fn report(&self) -> GasReport {
+        let golden = self.golden.get(&self.key);
+        let entries = self
+            .recorded
+            .iter()
+            .map(|(operation, current)| {
+                let previous = golden.and_then(|g| g.get(operation)).copied();
+                GasReportEntry {
+                    operation: operation.clone(),
+                    previous,
+                    current: *current,
+                }
+            })
+            .collect();
+
+        GasReport {
+            key: self.key.clone(),
+            threshold: self.threshold,
+            entries,
+        }
+    }
+
+    /// Compares recorded measurements with the golden file and returns the report.
+    /// Errors if some operations regressed beyond the threshold, unless [`GasGoldenFile::warn_only`] was set.
+    /// In update mode (see [`GasGoldenFile::update`]), the golden file is overwritten with the recorded measurements instead.
+    pub fn check(&self) -> Result<GasReport, CwEnvError> {
+        let report = self.report();
+
+        if self.update {
+            self.save()?;
+            return Ok(report);
+        }
+
+        let regressions = report.regressions();
+        if !regressions.is_empty() {
+            if self.fail_on_regression {
+                return Err(CwEnvError::GasRegression(report.to_string()));
+            }
+            log::warn!("{}", report);
+        }
+
+        Ok(report)
+    }
+
+    /// Writes the recorded measurements to the golden file.
+    /// Operations that were not recorded during this run are kept untouched
+    pub fn save(&self) -> Result<(), CwEnvError> {
+        let mut golden = self.golden.clone();
+        golden
+            .entry(self.key.clone())
+            .or_default()
+            .extend(self.recorded.clone());
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        serde_json::to_writer_pretty(File::create(&self.path)?, &golden)?;
+        Ok(())
+    }
+}
+
+/// Gas usage of a single operation compared with its golden value
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasReportEntry {
+    /// Name of the operation
+    pub operation: String,
+    /// Gas recorded in the golden file, if any
+    pub previous: Option<u64>,
+    /// Gas used during this run
+    pub current: u64,
+}
+
+impl GasReportEntry {
+    /// Deviation from the golden value, in percent.
+    /// Returns `None` for new operations
+    pub fn deviation(&self) -> Option<f64> {
+        self.previous.map(|previous| {
+            if previous == 0 {
+                return if self.current == 0 {
+                    0.0
+                } else {
+                    f64::INFINITY
+                };
+            }
+            (self.current as f64 - previous as f64) / previous as f64 * 100.0
+        })
+    }
+}
+
+/// Comparison of the recorded gas usage with the golden file
+#[derive(Debug, Clone)]
+pub struct GasReport {
+    /// Environment key of the report
+    pub key: String,
+    /// Allowed deviation, in percent
+    pub threshold: f64,
+    /// Compared operations
+    pub entries: Vec<GasReportEntry>,
+}
+
+impl GasReport {
+    /// Operations whose gas usage increased beyond the threshold
+    pub fn regressions(&self) -> Vec<&GasReportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.deviation().is_some_and(|d| d > self.threshold))
+            .collect()
+    }
+
+    /// Operations whose gas usage decreased beyond the threshold
+    pub fn improvements(&self) -> Vec<&GasReportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.deviation().is_some_and(|d| d < -self.threshold))
+            .collect()
+    }
+
+    /// Operations that are not yet present in the golden file
+    pub fn new_operations(&self) -> Vec<&GasReportEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.previous.is_none())
+            .collect()
+    }
+}
+
+impl Display for GasReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Gas report for {} (threshold {}%):",
+            self.key, self.threshold
+        )?;
+        for entry in &self.entries {
+            match (entry.previous, entry.deviation()) {
+                (Some(previous), Some(deviation)) => {
+                    let status = if deviation > self.threshold {
+                        "REGRESSION"
+                    } else if deviation < -self.threshold {
+                        "improvement"
+                    } else {
+                        "ok"
+                    };
+                    writeln!(
+                        f,
+                        "  {}: {} -> {} ({:+.2}%) {}",
+                        entry.operation, previous, entry.current, deviation, status
+                    )?;
+                }
+                _ => writeln!(f, "  {}: {} (new)", entry.operation, entry.current)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Gas usage, fee and hash of a single profiled transaction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GasProfileEntry {
+    /// Name of the operation (e.g. `counter:increment`)
+    pub operation: String,
+    /// Hash of the transaction, if reported by the environment
+    pub tx_hash: Option<String>,
+    /// Gas used by the transaction, if reported by the environment
+    pub gas_used: Option<u64>,
+    /// Fee paid for the transaction, if reported by the environment
+    pub fee: Option<String>,
+}
+
+/// Records the gas used, fee paid and hash of transactions during a script and produces a summary report.
+/// The profiler is cheap to clone, all the clones record into the same report.
+///
+/// ## Example
+/// ```rust,no_run
+/// # use cw_orch_core::{environment::IndexResponse, CwEnvError};
+/// use cw_orch_core::gas::GasProfiler;
+///
+/// # fn script(execute: impl Fn() -> Result<cw_multi_test::AppResponse, CwEnvError>) -> Result<(), CwEnvError> {
+/// let profiler = GasProfiler::new();
+/// // Records the transaction returned by the closure
+/// profiler.profile("counter:increment", || execute())?;
+///
+/// println!("{}", profiler.report().to_markdown());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GasProfiler {
+    entries: Arc<Mutex<Vec<GasProfileEntry>>>,
+}
+
+impl GasProfiler {
+    /// Creates an empty profiler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a transaction response under the given operation name
+    pub fn record(&self, operation: impl Into<String>, response: &impl IndexResponse) {
+        self.entries.lock().unwrap().push(GasProfileEntry {
+            operation: operation.into(),
+            tx_hash: response.tx_hash(),
+            gas_used: response.gas_used(),
+            fee: response.fee(),
+        });
+    }
+
+    /// Executes a transaction and records its response under the given operation name.
+    /// Failed transactions are not recorded
+    pub fn profile<R: IndexResponse, E>(
+        &self,
+        operation: impl Into<String>,
+        tx: impl FnOnce() -> Result<R, E>,
+    ) -> Result<R, E> {
+        let response = tx()?;
+        self.record(operation, &response);
+        Ok(response)
+    }
+
+    /// Returns the report of all the recorded transactions
+    pub fn report(&self) -> GasProfileReport {
+        GasProfileReport {
+            entries: self.entries.lock().unwrap().clone(),
+        }
+    }
+}
+
+/// Summary of the transactions recorded by a [`GasProfiler`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GasProfileReport {
+    /// Recorded transactions, in execution order
+    pub entries: Vec<GasProfileEntry>,
+}
+
+impl GasProfileReport {
+    /// Total gas used by all the recorded transactions
+    pub fn total_gas_used(&self) -> u64 {
+        self.entries.iter().filter_map(|e| e.gas_used).sum()
+    }
+
+    /// Gas used per operation, summed over all the calls of the operation
+    pub fn gas_per_operation(&self) -> BTreeMap<String, u64> {
+        let mut per_operation = BTreeMap::new();
+        for entry in &self.entries {
+            *per_operation.entry(entry.operation.clone()).or_default() +=
+                entry.gas_used.unwrap_or_default();
+        }
+        per_operation
+    }
+
+    /// Serializes the report to json
+    pub fn to_json(&self) -> Result<String, CwEnvError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Renders the report as a markdown table
+    pub fn to_markdown(&self) -> String {
+        let mut markdown =
+            String::from("| Operation | Gas used | Fee | Tx hash |\n|---|---|---|---|\n");
+        for entry in &self.entries {
+            markdown.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                entry.operation,
+                display_option(&entry.gas_used),
+                display_option(&entry.fee),
+                display_option(&entry.tx_hash),
+            ));
+        }
+        markdown.push_str(&format!("| **Total** | {} | | |\n", self.total_gas_used()));
+        markdown
+    }
+
+    /// Writes the json report to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CwEnvError> {
+        serde_json::to_writer_pretty(File::create(path)?, self)?;
+        Ok(())
+    }
+}
+
+fn display_option<T: Display>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn profiler_report() {
+        let profiler = GasProfiler::new();
+        let response = cw_multi_test::AppResponse::default();
+        profiler.record("a", &response);
+        profiler
+            .clone()
+            .profile("b", || Ok::<_, CwEnvError>(response.clone()))
+            .unwrap();
+        assert!(profiler
+            .profile("c", || Err::<cw_multi_test::AppResponse, _>(()))
+            .is_err());
+
+        let report = profiler.report();
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.total_gas_used(), 0);
+        assert!(report.to_markdown().contains("| b | - | - | - |"));
+    }
+
+    #[test]
+    fn save_and_load() -> anyhow::Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("cw-orch-gas-golden-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut golden = GasGoldenFile::load(&path, "juno-1")?.update(false);
+        golden.record("op", 100);
+        golden.save()?;
+
+        let mut golden = GasGoldenFile::load(&path, "juno-1")?
+            .threshold(5.0)
+            .update(false);
+        golden.record("op", 200);
+        assert!(golden.check().is_err());
+
+        let golden = golden.warn_only();
+        assert_eq!(golden.check()?.regressions().len(), 1);
+
+        // Update mode overwrites the golden file instead of checking it
+        golden.clone().update(true).check()?;
+        let golden = GasGoldenFile::load(&path, "juno-1")?.update(false);
+        assert_eq!(golden.golden["juno-1"]["op"], 200);
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -4,8 +4,12 @@ pub use env::CoreEnvVars;
 pub mod environment;
 
 pub mod build;
+pub mod comparison;
+pub mod conformance;
 mod error;
+pub mod gas;
 pub mod log;
+pub mod scenario;
 pub use error::CwEnvError;
 
 pub use serde_json;
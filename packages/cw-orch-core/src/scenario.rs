@@ -0,0 +1,334 @@
+//! Structured multi-actor test scenarios.
+//!
+//! A [`Scenario`] is a list of named [`Step`]s run in order against any [`CwEnv`].
+//! Each step declares the sender acting in the step, the action it performs (a closure or a typed contract execution),
+//! the events the action is expected to emit and checks on the state after the action.
+//!
+//! When a step fails, the scenario stops and the error contains a report of all the steps:
+//! ```text
+//! Scenario `token transfers`
+//!   ✓ 1. alice mints [sender: alice, contract: token]
+//!   ✗ 2. bob transfers [sender: bob, contract: token]: expected event `wasm` with attributes [action=transfer]
+//!   - 3. alice burns (skipped)
+//! ```
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cw_orch_core::{
+//!     contract::interface_traits::CwOrchExecute,
+//!     environment::{CwEnv, TxHandler},
+//!     scenario::{Scenario, Step},
+//!     CwEnvError,
+//! };
+//! # fn run<Chain: CwEnv, Counter: CwOrchExecute<Chain, ExecuteMsg = String> + Clone>(
+//! #     chain: Chain,
+//! #     counter: Counter,
+//! #     alice: <Chain as TxHandler>::Sender,
+//! # ) -> Result<(), CwEnvError> {
+//! Scenario::new("counter", &chain)
+//!     .step(
+//!         Step::new("alice increments")
+//!             .sender(alice.clone())
+//!             .execute(&counter, "increment".to_string(), vec![])
+//!             .expect_event("wasm", &[("action", "increment")]),
+//!     )
+//!     .step(
+//!         Step::new("alice resets")
+//!             .sender(alice)
+//!             .execute(&counter, "reset".to_string(), vec![])
+//!             .expect_error("Unauthorized"),
+//!     )
+//!     .run()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{Debug, Display};
+
+use cosmwasm_std::{Addr, Coin};
+
+use crate::{
+    contract::interface_traits::{ContractInstance, CwOrchExecute},
+    environment::{CwEnv, EventAssertions, TxHandler},
+    CwEnvError,
+};
+
+type StepAction<'a, Chain> =
+    Box<dyn FnOnce(&Chain) -> Result<<Chain as TxHandler>::Response, CwEnvError> + 'a>;
+type StepCheck<'a, Chain> = Box<dyn FnOnce(&Chain) -> Result<(), CwEnvError> + 'a>;
+
+/// Step of a [`Scenario`]
+pub struct Step<'a, Chain: TxHandler> {
+    name: String,
+    sender: Option<Chain::Sender>,
+    contract: Option<String>,
+    action: Option<StepAction<'a, Chain>>,
+    expected_events: Vec<(String, Vec<(String, String)>)>,
+    expected_error: Option<String>,
+    checks: Vec<(String, StepCheck<'a, Chain>)>,
+}
+
+impl<'a, Chain: TxHandler> Step<'a, Chain> {
+    /// Step without action, identified by `name` in the report
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sender: None,
+            contract: None,
+            action: None,
+            expected_events: vec![],
+            expected_error: None,
+            checks: vec![],
+        }
+    }
+
+    /// Sets the sender acting in this step. Defaults to the sender of the scenario chain
+    pub fn sender(mut self, sender: Chain::Sender) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Sets the action of the step. The closure receives the chain with the sender of the step
+    pub fn action(
+        mut self,
+        action: impl FnOnce(&Chain) -> Result<Chain::Response, CwEnvError> + 'a,
+    ) -> Self {
+        self.action = Some(Box::new(action));
+        self
+    }
+
+    /// Sets the action of the step to the execution of `msg` on `contract`
+    pub fn execute<C>(mut self, contract: &C, msg: C::ExecuteMsg, coins: Vec<Coin>) -> Self
+    where
+        C: CwOrchExecute<Chain> + ContractInstance<Chain>,
+        C::ExecuteMsg: 'a,
+    {
+        self.contract = Some(contract.id());
+        let address = contract.address();
+        self.action = Some(Box::new(move |chain: &Chain| {
+            chain.execute(&msg, &coins, &address?).map_err(Into::into)
+        }));
+        self
+    }
+
+    /// Names the contract targeted by the step in the report
+    pub fn contract(mut self, contract_id: impl Into<String>) -> Self {
+        self.contract = Some(contract_id.into());
+        self
+    }
+
+    /// Expects the action to emit an event of type `event_type` with all the given attributes
+    pub fn expect_event(mut self, event_type: &str, attributes: &[(&str, &str)]) -> Self {
+        self.expected_events.push((
+            event_type.to_string(),
+            attributes
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        ));
+        self
+    }
+
+    /// Expects the action to fail with an error containing `error`
+    pub fn expect_error(mut self, error: impl Into<String>) -> Self {
+        self.expected_error = Some(error.into());
+        self
+    }
+
+    /// Checks the state after the action. The closure receives the chain with the sender of the step
+    pub fn check(
+        mut self,
+        description: impl Into<String>,
+        check: impl FnOnce(&Chain) -> Result<(), CwEnvError> + 'a,
+    ) -> Self {
+        self.checks.push((description.into(), Box::new(check)));
+        self
+    }
+
+    fn run(self, chain: &Chain) -> Result<(), String> {
+        if let Some(action) = self.action {
+            match (action(chain), self.expected_error) {
+                (Ok(response), None) => {
+                    for (event_type, attributes) in &self.expected_events {
+                        let attributes: Vec<_> = attributes
+                            .iter()
+                            .map(|(key, value)| (key.as_str(), value.as_str()))
+                            .collect();
+                        response
+                            .find_event(event_type, &attributes)
+                            .map_err(|e| e.to_string())?;
+                    }
+                }
+                (Ok(_), Some(expected)) => {
+                    return Err(format!(
+                        "expected an error containing `{expected}`, the action succeeded"
+                    ))
+                }
+                (Err(e), None) => return Err(format!("action failed: {}", describe_error(&e))),
+                (Err(e), Some(expected)) => {
+                    let error = describe_error(&e);
+                    if !error.contains(&expected) {
+                        return Err(format!(
+                            "expected an error containing `{expected}`, got: {error}"
+                        ));
+                    }
+                }
+            }
+        }
+        for (description, check) in self.checks {
+            check(chain)
+                .map_err(|e| format!("check `{description}` failed: {}", describe_error(&e)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Error and its causes, on a single line
+fn describe_error(error: &CwEnvError) -> String {
+    match error {
+        CwEnvError::AnyError(e) => format!("{e:#}"),
+        e => e.to_string(),
+    }
+}
+
+/// Ordered list of steps run against a chain, see the [module documentation](self)
+pub struct Scenario<'a, Chain: CwEnv> {
+    name: String,
+    chain: Chain,
+    steps: Vec<Step<'a, Chain>>,
+}
+
+impl<'a, Chain: CwEnv> Scenario<'a, Chain> {
+    /// Empty scenario run against `chain`
+    pub fn new(name: impl Into<String>, chain: &Chain) -> Self {
+        Self {
+            name: name.into(),
+            chain: chain.clone(),
+            steps: vec![],
+        }
+    }
+
+    /// Adds a step to the scenario
+    pub fn step(mut self, step: Step<'a, Chain>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Runs the steps in order and returns the report.
+    /// Stops at the first failing step, the error contains the report
+    pub fn run(self) -> Result<ScenarioReport, CwEnvError> {
+        let report = self.run_report();
+        if report.passed() {
+            Ok(report)
+        } else {
+            Err(CwEnvError::ScenarioFailed(report.to_string()))
+        }
+    }
+
+    /// Runs the steps in order until a step fails, without erroring
+    pub fn run_report(self) -> ScenarioReport {
+        let mut report = ScenarioReport {
+            name: self.name,
+            steps: vec![],
+        };
+        let mut failed = false;
+        for step in self.steps {
+            let chain = match &step.sender {
+                Some(sender) => self.chain.call_as(sender),
+                None => self.chain.clone(),
+            };
+            let mut step_report = StepReport {
+                name: step.name.clone(),
+                sender: chain.sender(),
+                contract: step.contract.clone(),
+                outcome: StepOutcome::Skipped,
+            };
+            if !failed {
+                step_report.outcome = match step.run(&chain) {
+                    Ok(()) => StepOutcome::Passed,
+                    Err(e) => {
+                        failed = true;
+                        StepOutcome::Failed(e)
+                    }
+                };
+            }
+            report.steps.push(step_report);
+        }
+        report
+    }
+}
+
+/// Outcome of a [`Step`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepOutcome {
+    /// The action and all the checks of the step succeeded
+    Passed,
+    /// The step failed, with the reason
+    Failed(String),
+    /// The step was not run because a previous step failed
+    Skipped,
+}
+
+/// Report of a [`Step`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepReport {
+    pub name: String,
+    pub sender: Addr,
+    pub contract: Option<String>,
+    pub outcome: StepOutcome,
+}
+
+/// Report of a [`Scenario`] run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub steps: Vec<StepReport>,
+}
+
+impl ScenarioReport {
+    /// Returns true if all the steps passed
+    pub fn passed(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|step| step.outcome == StepOutcome::Passed)
+    }
+}
+
+impl Display for ScenarioReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Scenario `{}`", self.name)?;
+        for (i, step) in self.steps.iter().enumerate() {
+            let symbol = match step.outcome {
+                StepOutcome::Passed => "✓",
+                StepOutcome::Failed(_) => "✗",
+                StepOutcome::Skipped => "-",
+            };
+            write!(f, "\n  {symbol} {}. {}", i + 1, step.name)?;
+            match &step.outcome {
+                StepOutcome::Skipped => write!(f, " (skipped)")?,
+                outcome => {
+                    write!(f, " [sender: {}", step.sender)?;
+                    if let Some(contract) = &step.contract {
+                        write!(f, ", contract: {contract}")?;
+                    }
+                    write!(f, "]")?;
+                    if let StepOutcome::Failed(reason) = outcome {
+                        write!(f, ": {reason}")?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Chain: TxHandler> Debug for Step<'_, Chain> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Step")
+            .field("name", &self.name)
+            .field("contract", &self.contract)
+            .field("expected_events", &self.expected_events)
+            .field("expected_error", &self.expected_error)
+            .finish()
+    }
+}
@@ -3,7 +3,7 @@ use std::{cell::RefCell, rc::Rc};
 use cosmwasm_std::{Addr, Coin, Uint128};
 use cw_multi_test::{AppBuilder, MockAddressGenerator, MockApiBech32, WasmKeeper};
 use cw_orch_core::{
-    environment::{BankQuerier, BankSetter, DefaultQueriers, StateInterface, TxHandler},
+    environment::{BankQuerier, BankSetter, ChainInfo, DefaultQueriers, StateInterface, TxHandler},
     CwEnvError,
 };
 use cw_utils::NativeBalance;
@@ -25,6 +25,18 @@ impl MockBase<MockApiBech32, MockState> {
 
         chain
     }
+
+    /// Create a mock environment simulating `chain_info`.
+    /// Addresses are created with the prefix of the chain, and its chain id and gas denom are used by the mock
+    /// and reported by [`env_info`](cw_orch_core::environment::EnvironmentQuerier::env_info), like for a `Daemon` on that chain.
+    pub fn new_with_chain(chain_info: ChainInfo) -> Self {
+        let chain = MockBech32::new_custom(
+            chain_info.network_info.pub_address_prefix,
+            MockState::new_with_chain_id(chain_info.chain_id),
+        );
+        chain.set_chain(chain_info.into());
+        chain
+    }
 }
 
 impl<S: StateInterface> MockBase<MockApiBech32, S> {
@@ -64,7 +76,14 @@ impl<S: StateInterface> MockBase<MockApiBech32, S> {
         // We create an address internally
         let sender = app.borrow().api().addr_make("sender");
 
-        Self { sender, state, app }
+        Self {
+            sender,
+            state,
+            app,
+            tx_cost_schedule: Rc::new(RefCell::new(None)),
+            last_tx_cost: Rc::new(RefCell::new(None)),
+            chain_info: Rc::new(RefCell::new(None)),
+        }
     }
 }
 
@@ -152,7 +171,9 @@ mod test {
     use cosmwasm_std::coins;
 
     use crate::MockBech32;
-    use cw_orch_core::environment::{BankQuerier, DefaultQueriers};
+    use cw_orch_core::environment::{
+        BankQuerier, ChainInfo, ChainKind, DefaultQueriers, EnvironmentQuerier, NetworkInfo,
+    };
     #[test]
     fn addr_make_with_balance() -> anyhow::Result<()> {
         let mock = MockBech32::new("mock");
@@ -165,4 +186,33 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn new_with_chain() {
+        const JUNO: ChainInfo = ChainInfo {
+            chain_id: "juno-1",
+            gas_denom: "ujuno",
+            gas_price: 0.0750,
+            grpc_urls: &[],
+            lcd_url: None,
+            fcd_url: None,
+            rpc_url: None,
+            network_info: NetworkInfo {
+                chain_name: "juno",
+                pub_address_prefix: "juno",
+                coin_type: 118u32,
+            },
+            kind: ChainKind::Mainnet,
+        };
+        let mock = MockBech32::new_with_chain(JUNO);
+
+        assert!(mock.addr_make("sender").as_str().starts_with("juno1"));
+        assert!(mock.sender.as_str().starts_with("juno1"));
+        assert_eq!(mock.gas_denom(), Some("ujuno".to_string()));
+        let info = mock.env_info();
+        assert_eq!(
+            (info.chain_id.as_str(), info.chain_name.as_str()),
+            ("juno-1", "juno")
+        );
+    }
 }
@@ -2,18 +2,24 @@ use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
 use cosmwasm_std::{
     testing::{MockApi, MockStorage},
-    to_json_binary, Addr, Api, Binary, CosmosMsg, Empty, Event, WasmMsg,
+    to_json_binary, to_json_vec, Addr, Api, Binary, CosmosMsg, Empty, Event, WasmMsg,
 };
 use cw_multi_test::{
-    ibc::IbcSimpleModule, App, AppResponse, BankKeeper, Contract, DistributionKeeper, Executor,
-    FailingModule, GovFailingModule, MockApiBech32, StakeKeeper, StargateFailingModule, WasmKeeper,
+    ibc::IbcSimpleModule, App, AppResponse, BankKeeper, BankSudo, Contract, DistributionKeeper,
+    Executor, FailingModule, GovFailingModule, MockApiBech32, StakeKeeper, StargateFailingModule,
+    WasmKeeper,
 };
-use serde::Serialize;
+use cw_utils::NativeBalance;
+use serde::{de::DeserializeOwned, Serialize};
 
 use super::state::MockState;
+use crate::MockTxCostSchedule;
 use cw_orch_core::{
     contract::interface_traits::Uploadable,
-    environment::{ChainState, IndexResponse, StateInterface, TxHandler},
+    environment::{
+        AsyncTxHandler, AsyncWasmQuerier, ChainInfoOwned, ChainState, FundsManager, IndexResponse,
+        QueryHandler, StateInterface, SudoHandler, TxHandler,
+    },
     CwEnvError,
 };
 
@@ -74,6 +80,12 @@ pub struct MockBase<A: Api = MockApi, S: StateInterface = MockState> {
     pub state: Rc<RefCell<S>>,
     /// Inner mutable cw-multi-test app backend
     pub app: Rc<RefCell<MockApp<A>>>,
+    /// Schedule used to estimate transaction costs, no cost is estimated if `None`
+    pub(crate) tx_cost_schedule: Rc<RefCell<Option<MockTxCostSchedule>>>,
+    /// Cost of the last transaction, when cost estimation is enabled
+    pub(crate) last_tx_cost: Rc<RefCell<Option<u64>>>,
+    /// Chain simulated by the mock, set with `new_with_chain`
+    pub(crate) chain_info: Rc<RefCell<Option<ChainInfoOwned>>>,
 }
 
 pub type Mock<S = MockState> = MockBase<MockApi, S>;
@@ -85,6 +97,9 @@ impl<A: Api, S: StateInterface> Clone for MockBase<A, S> {
             sender: self.sender.clone(),
             state: self.state.clone(),
             app: self.app.clone(),
+            tx_cost_schedule: self.tx_cost_schedule.clone(),
+            last_tx_cost: self.last_tx_cost.clone(),
+            chain_info: self.chain_info.clone(),
         }
     }
 }
@@ -95,10 +110,69 @@ impl<A: Api> MockBase<A, MockState> {
         self.app
             .borrow_mut()
             .update_block(|b| b.chain_id = chain_id.to_string());
+        if let Some(chain_info) = self.chain_info.borrow_mut().as_mut() {
+            chain_info.chain_id = chain_id.to_string();
+        }
     }
 }
 
 impl<A: Api, S: StateInterface> MockBase<A, S> {
+    /// Simulates `chain_info`: sets the chain id of the blocks and keeps the chain info for [`Self::gas_denom`] and the environment info
+    pub(crate) fn set_chain(&self, chain_info: ChainInfoOwned) {
+        self.app
+            .borrow_mut()
+            .update_block(|b| b.chain_id = chain_info.chain_id.clone());
+        *self.chain_info.borrow_mut() = Some(chain_info);
+    }
+
+    /// Chain simulated by the mock, if it was created with `new_with_chain`
+    pub fn chain_info(&self) -> Option<ChainInfoOwned> {
+        self.chain_info.borrow().clone()
+    }
+
+    /// Native gas denom of the simulated chain, if the mock was created with `new_with_chain`
+    pub fn gas_denom(&self) -> Option<String> {
+        self.chain_info
+            .borrow()
+            .as_ref()
+            .map(|chain_info| chain_info.gas_denom.clone())
+    }
+
+    /// Enables the estimation of deterministic, size-based transaction costs, see [`MockTxCostSchedule`].
+    /// The cost of the last transaction is then available through [`Self::last_tx_cost`]
+    pub fn enable_tx_cost_estimation(&self, schedule: MockTxCostSchedule) {
+        *self.tx_cost_schedule.borrow_mut() = Some(schedule);
+    }
+
+    /// Disables the estimation of transaction costs
+    pub fn disable_tx_cost_estimation(&self) {
+        *self.tx_cost_schedule.borrow_mut() = None;
+        *self.last_tx_cost.borrow_mut() = None;
+    }
+
+    /// Size-based cost of the last transaction sent through this mock (or its clones).
+    /// Returns `None` if cost estimation is not enabled or no transaction was sent since it was enabled
+    pub fn last_tx_cost(&self) -> Option<u64> {
+        *self.last_tx_cost.borrow()
+    }
+
+    /// Records the cost of the transaction if cost estimation is enabled, the response is returned unchanged
+    fn metered<M: Serialize>(
+        &self,
+        msg: Option<&M>,
+        response: AppResponse,
+    ) -> Result<AppResponse, CwEnvError> {
+        let Some(schedule) = *self.tx_cost_schedule.borrow() else {
+            return Ok(response);
+        };
+        let msg_len = match msg {
+            Some(msg) => to_json_vec(msg)?.len(),
+            None => 0,
+        };
+        *self.last_tx_cost.borrow_mut() = Some(schedule.cost(msg_len, &response));
+        Ok(response)
+    }
+
     /// Upload a custom contract wrapper.
     /// Support for this is limited.
     pub fn upload_custom(
@@ -116,7 +190,7 @@ impl<A: Api, S: StateInterface> MockBase<A, S> {
         };
         let code_id = IndexResponse::uploaded_code_id(&resp)?;
         self.state.borrow_mut().set_code_id(contract_id, code_id);
-        Ok(resp)
+        self.metered(None::<&Empty>, resp)
     }
 }
 impl<A: Api, S: StateInterface> ChainState for MockBase<A, S> {
@@ -151,7 +225,15 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
             events: vec![event],
             ..Default::default()
         };
-        Ok(resp)
+        self.metered(None::<&Empty>, resp)
+    }
+
+    /// cw-multi-test doesn't run wasm code, contracts need to be uploaded from their [`Uploadable::wrapper`]
+    fn upload_from_bytes(&self, _code: Vec<u8>) -> Result<Self::Response, CwEnvError> {
+        Err(CwEnvError::StdErr(
+            "Mock environments can't run wasm code, upload the contract wrapper instead"
+                .to_string(),
+        ))
     }
 
     fn execute<E: Serialize + Debug>(
@@ -160,15 +242,13 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
         coins: &[cosmwasm_std::Coin],
         contract_address: &Addr,
     ) -> Result<Self::Response, CwEnvError> {
-        self.app
-            .borrow_mut()
-            .execute_contract(
-                self.sender.clone(),
-                contract_address.to_owned(),
-                exec_msg,
-                coins,
-            )
-            .map_err(From::from)
+        let resp = self.app.borrow_mut().execute_contract(
+            self.sender.clone(),
+            contract_address.to_owned(),
+            exec_msg,
+            coins,
+        )?;
+        self.metered(Some(exec_msg), resp)
     }
 
     fn instantiate<I: Serialize + Debug>(
@@ -195,7 +275,7 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
             events: app.events,
             data: app.data,
         };
-        Ok(resp)
+        self.metered(Some(init_msg), resp)
     }
 
     fn instantiate2<I: Serialize + Debug>(
@@ -225,7 +305,7 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
             events: app.events,
             data: app.data,
         };
-        Ok(resp)
+        self.metered(Some(init_msg), resp)
     }
 
     fn migrate<M: Serialize + Debug>(
@@ -234,15 +314,98 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
         new_code_id: u64,
         contract_address: &Addr,
     ) -> Result<Self::Response, CwEnvError> {
+        let resp = self.app.borrow_mut().migrate_contract(
+            self.sender.clone(),
+            contract_address.clone(),
+            migrate_msg,
+            new_code_id,
+        )?;
+        self.metered(Some(migrate_msg), resp)
+    }
+}
+
+impl<A: Api, S: StateInterface> SudoHandler for MockBase<A, S> {
+    fn sudo<M: Serialize + Debug>(
+        &self,
+        sudo_msg: &M,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, CwEnvError> {
+        let resp = self
+            .app
+            .borrow_mut()
+            .wasm_sudo(contract_address.clone(), sudo_msg)?;
+        self.metered(Some(sudo_msg), resp)
+    }
+}
+
+// The mock resolves immediately, this allows testing code written for async environments
+impl<A: Api, S: StateInterface> AsyncWasmQuerier for MockBase<A, S> {
+    type Error = CwEnvError;
+
+    async fn async_query<Q: Serialize + Debug, T: Serialize + DeserializeOwned>(
+        &self,
+        query_msg: &Q,
+        contract_address: &Addr,
+    ) -> Result<T, CwEnvError> {
+        QueryHandler::query(self, query_msg, contract_address)
+    }
+}
+
+impl<A: Api, S: StateInterface> AsyncTxHandler for MockBase<A, S> {
+    type Response = AppResponse;
+
+    async fn async_execute<E: Serialize + Debug>(
+        &self,
+        exec_msg: &E,
+        coins: &[cosmwasm_std::Coin],
+        contract_address: &Addr,
+    ) -> Result<AppResponse, CwEnvError> {
+        TxHandler::execute(self, exec_msg, coins, contract_address)
+    }
+}
+
+impl<A: Api, S: StateInterface> FundsManager for MockBase<A, S> {
+    type Error = CwEnvError;
+
+    fn set_balance(
+        &self,
+        address: impl Into<String>,
+        amount: Vec<cosmwasm_std::Coin>,
+    ) -> Result<(), CwEnvError> {
         self.app
             .borrow_mut()
-            .migrate_contract(
-                self.sender.clone(),
-                contract_address.clone(),
-                migrate_msg,
-                new_code_id,
-            )
-            .map_err(From::from)
+            .init_modules(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(storage, &Addr::unchecked(address.into()), amount)
+            })
+            .map_err(Into::into)
+    }
+
+    fn add_balance(
+        &self,
+        address: impl Into<String>,
+        amount: Vec<cosmwasm_std::Coin>,
+    ) -> Result<(), CwEnvError> {
+        let address = address.into();
+        let current = self.app.borrow().wrap().query_all_balances(&address)?;
+        let new_amount = NativeBalance(current) + NativeBalance(amount);
+        FundsManager::set_balance(self, address, new_amount.into_vec())
+    }
+
+    fn mint_native(
+        &self,
+        address: impl Into<String>,
+        amount: Vec<cosmwasm_std::Coin>,
+    ) -> Result<(), CwEnvError> {
+        self.app.borrow_mut().sudo(
+            BankSudo::Mint {
+                to_address: address.into(),
+                amount,
+            }
+            .into(),
+        )?;
+        Ok(())
     }
 }
 
@@ -254,7 +417,7 @@ mod test {
         StdResult, Uint128,
     };
     use cw_multi_test::ContractWrapper;
-    use cw_orch_core::environment::{BankQuerier, DefaultQueriers, QueryHandler};
+    use cw_orch_core::environment::{BankQuerier, DefaultQueriers, FundsManager, QueryHandler};
     use speculoos::prelude::*;
 
     use crate::core::*;
@@ -368,6 +531,37 @@ mod test {
             .is_ok();
     }
 
+    fn sudo(_deps: DepsMut, _env: Env, msg: String) -> StdResult<Response> {
+        Ok(Response::default().add_attribute("sudo", msg))
+    }
+
+    #[test]
+    fn sudo_entry_point() -> Result<(), CwEnvError> {
+        let chain = Mock::new(SENDER);
+        let contract_source = Box::new(
+            ContractWrapper::new(execute, cw20_base::contract::instantiate, query).with_sudo(sudo),
+        );
+        chain.upload_custom("cw20", contract_source)?;
+
+        let init_msg = cw20_base::msg::InstantiateMsg {
+            name: String::from("Token"),
+            symbol: String::from("TOK"),
+            decimals: 6u8,
+            initial_balances: vec![],
+            mint: None,
+            marketing: None,
+        };
+        let contract_address = chain
+            .instantiate(1, &init_msg, None, None, &[])?
+            .instantiated_contract_address()?;
+
+        let sudo_res = chain.sudo(&"privileged".to_string(), &contract_address)?;
+        asserting("that sudo passed on correctly")
+            .that(&sudo_res.event_attr_value("wasm", "sudo")?)
+            .is_equal_to(String::from("privileged"));
+        Ok(())
+    }
+
     #[test]
     fn custom_mock_env() {
         let mock_state = MockState::new();
@@ -454,4 +648,67 @@ mod test {
 
         Ok(())
     }
+
+    fn fund<Chain: FundsManager>(chain: &Chain, address: &str) -> Result<(), Chain::Error> {
+        chain.set_balance(address, coins(100, "ujuno"))?;
+        chain.add_balance(address, coins(50, "ujuno"))?;
+        chain.mint_native(address, coins(10, "uosmo"))
+    }
+
+    #[test]
+    fn funds_manager() -> Result<(), CwEnvError> {
+        let app = Mock::new(SENDER);
+        fund(&app, BALANCE_ADDR)?;
+        assert_eq!(
+            app.bank_querier().balance(BALANCE_ADDR, None)?,
+            vec![Coin::new(150u128, "ujuno"), Coin::new(10u128, "uosmo")]
+        );
+
+        let app = MockBech32::new("mock");
+        let address = app.addr_make("user");
+        fund(&app, address.as_str())?;
+        assert_eq!(
+            app.bank_querier().supply_of("uosmo")?,
+            Coin::new(10u128, "uosmo")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tx_cost_estimation() -> Result<(), CwEnvError> {
+        let chain = Mock::new(SENDER);
+        let contract_source = Box::new(ContractWrapper::new(
+            execute,
+            cw20_base::contract::instantiate,
+            query,
+        ));
+        chain.upload_custom("cw20", contract_source)?;
+        assert_eq!(chain.last_tx_cost(), None);
+
+        chain.enable_tx_cost_estimation(MockTxCostSchedule::default());
+        let init_res = chain.instantiate(
+            1,
+            &cw20_base::msg::InstantiateMsg {
+                name: String::from("Token"),
+                symbol: String::from("TOK"),
+                decimals: 6u8,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
+            },
+            None,
+            None,
+            &[],
+        )?;
+        // The cost is reported aside, the response only contains the events of the transaction
+        let cost = chain.last_tx_cost().unwrap();
+        assert!(cost > MockTxCostSchedule::default().tx_base);
+        assert_eq!(init_res.gas_used(), None);
+        assert!(init_res.events.iter().all(|e| e.ty != "mock_gas"));
+
+        // Clones share the estimation
+        chain.clone().disable_tx_cost_estimation();
+        assert_eq!(chain.last_tx_cost(), None);
+        Ok(())
+    }
 }
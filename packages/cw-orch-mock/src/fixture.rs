@@ -0,0 +1,212 @@
+//! Declarative setup of a [`MockBase`] environment.
+//!
+//! A [`MockFixture`] lists the initial balances, the uploaded codes and the instantiated contracts of a test environment.
+//! It is plain data, so it can be built once in a shared test module and loaded in any number of environments
+//! with [`MockBase::load_fixture`].
+//!
+//! ## Example
+//! ```
+//! # use cosmwasm_std::coins;
+//! use cw_orch_core::{contract::interface_traits::ContractInstance, CwEnvError};
+//! use cw_orch_mock::{fixture::{FixtureContract, MockFixture}, Mock};
+//! use mock_contract::{InstantiateMsg, MockContract};
+//!
+//! // Shared by all the test modules
+//! fn fixture() -> Result<MockFixture, CwEnvError> {
+//!     Ok(MockFixture::new()
+//!         .balance("alice", coins(1_000, "ujuno"))
+//!         .code::<MockContract<Mock>>("mock_contract")
+//!         .contract(FixtureContract::new("mock_contract", &InstantiateMsg {})?))
+//! }
+//!
+//! let mock = Mock::new("sender");
+//! mock.load_fixture(&fixture()?)?;
+//!
+//! let contract = MockContract::new("mock_contract", mock.clone());
+//! assert!(contract.address().is_ok());
+//! assert_eq!(mock.query_balance("alice", "ujuno")?.u128(), 1_000);
+//! # Ok::<(), CwEnvError>(())
+//! ```
+
+use cosmwasm_std::{to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, Empty, WasmMsg};
+use cw_multi_test::{Contract, Executor};
+use cw_orch_core::{
+    contract::interface_traits::Uploadable,
+    environment::{IndexResponse, StateInterface},
+    CwEnvError,
+};
+use serde::Serialize;
+
+use crate::MockBase;
+
+/// Constructor of a contract wrapper, usually [`Uploadable::wrapper`]
+pub type WrapperFn = fn() -> Box<dyn Contract<Empty, Empty>>;
+
+/// Contract instantiated by a [`MockFixture`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixtureContract {
+    /// Id the contract address is stored under, also used as the instantiation label
+    pub contract_id: String,
+    /// Id of the code to instantiate, as registered with [`MockFixture::code`]
+    pub code: String,
+    /// Serialized instantiate message
+    pub msg: Binary,
+    /// Admin of the contract
+    pub admin: Option<String>,
+    /// Funds sent along the instantiation
+    pub funds: Vec<Coin>,
+    /// Sender of the instantiation, the sender of the environment if `None`
+    pub sender: Option<String>,
+}
+
+impl FixtureContract {
+    /// Contract `contract_id` instantiated from the code registered under the same id
+    pub fn new(contract_id: impl Into<String>, msg: &impl Serialize) -> Result<Self, CwEnvError> {
+        let contract_id = contract_id.into();
+        Ok(Self {
+            code: contract_id.clone(),
+            contract_id,
+            msg: to_json_binary(msg)?,
+            admin: None,
+            funds: vec![],
+            sender: None,
+        })
+    }
+
+    /// Instantiates the contract from the code registered under `code`
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = code.into();
+        self
+    }
+
+    /// Sets the admin of the contract
+    pub fn with_admin(mut self, admin: impl Into<String>) -> Self {
+        self.admin = Some(admin.into());
+        self
+    }
+
+    /// Sends `funds` along the instantiation
+    pub fn with_funds(mut self, funds: Vec<Coin>) -> Self {
+        self.funds = funds;
+        self
+    }
+
+    /// Instantiates the contract from `sender` instead of the sender of the environment
+    pub fn with_sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    /// Instantiate message of the contract for the code id `code_id`
+    pub fn instantiate_msg(&self, code_id: u64) -> WasmMsg {
+        WasmMsg::Instantiate {
+            admin: self.admin.clone(),
+            code_id,
+            msg: self.msg.clone(),
+            funds: self.funds.clone(),
+            label: self.contract_id.clone(),
+        }
+    }
+}
+
+/// Initial balances, codes and contracts of a test environment.
+/// Balances are set first, then codes are uploaded and contracts are instantiated in declaration order
+#[derive(Clone, Default)]
+pub struct MockFixture {
+    balances: Vec<(String, Vec<Coin>)>,
+    codes: Vec<(String, WrapperFn)>,
+    contracts: Vec<FixtureContract>,
+}
+
+impl MockFixture {
+    /// Empty fixture
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the balance of `address`
+    pub fn balance(mut self, address: impl Into<String>, amount: Vec<Coin>) -> Self {
+        self.balances.push((address.into(), amount));
+        self
+    }
+
+    /// Uploads the code of `T` under `code_id`
+    pub fn code<T: Uploadable>(self, code_id: impl Into<String>) -> Self {
+        self.code_wrapper(code_id, T::wrapper)
+    }
+
+    /// Uploads the contract wrapper returned by `wrapper` under `code_id`
+    pub fn code_wrapper(mut self, code_id: impl Into<String>, wrapper: WrapperFn) -> Self {
+        self.codes.push((code_id.into(), wrapper));
+        self
+    }
+
+    /// Instantiates `contract`
+    pub fn contract(mut self, contract: FixtureContract) -> Self {
+        self.contracts.push(contract);
+        self
+    }
+
+    /// Adds the balances, codes and contracts of `other` after the ones of this fixture
+    pub fn merge(mut self, other: MockFixture) -> Self {
+        self.balances.extend(other.balances);
+        self.codes.extend(other.codes);
+        self.contracts.extend(other.contracts);
+        self
+    }
+
+    /// Balances set by the fixture
+    pub fn balances(&self) -> &[(String, Vec<Coin>)] {
+        &self.balances
+    }
+
+    /// Codes uploaded by the fixture
+    pub fn codes(&self) -> &[(String, WrapperFn)] {
+        &self.codes
+    }
+
+    /// Contracts instantiated by the fixture
+    pub fn contracts(&self) -> &[FixtureContract] {
+        &self.contracts
+    }
+}
+
+impl<A: Api, S: StateInterface> MockBase<A, S> {
+    /// Sets the balances, uploads the codes and instantiates the contracts of `fixture`.
+    /// Code ids and contract addresses are registered in the state, so contract interfaces with the same ids can be used right away
+    pub fn load_fixture(&self, fixture: &MockFixture) -> Result<(), CwEnvError> {
+        self.app
+            .borrow_mut()
+            .init_modules(|router, _, storage| -> Result<(), CwEnvError> {
+                for (address, amount) in fixture.balances() {
+                    router
+                        .bank
+                        .init_balance(storage, &Addr::unchecked(address), amount.clone())?;
+                }
+                Ok(())
+            })?;
+
+        for (code_id, wrapper) in fixture.codes() {
+            self.upload_custom(code_id, wrapper())?;
+        }
+
+        for contract in fixture.contracts() {
+            let code_id = self.state.borrow().get_code_id(&contract.code)?;
+            let sender = contract
+                .sender
+                .as_ref()
+                .map(Addr::unchecked)
+                .unwrap_or_else(|| self.sender.clone());
+            let resp = self
+                .app
+                .borrow_mut()
+                .execute(sender, CosmosMsg::Wasm(contract.instantiate_msg(code_id)))?;
+            let address = IndexResponse::instantiated_contract_address(&resp)?;
+
+            let mut state = self.state.borrow_mut();
+            state.set_code_id(&contract.contract_id, code_id);
+            state.set_address(&contract.contract_id, &address);
+        }
+        Ok(())
+    }
+}
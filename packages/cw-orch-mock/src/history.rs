@@ -0,0 +1,240 @@
+//! Replay of the execution history of an on-chain contract in a [`MockBase`] environment.
+//!
+//! When the raw state of a contract can't be exported, its state can be approximated by replaying its executions
+//! (e.g. fetched from the transaction history of the chain with `Node::_contract_executions` in `cw-orch-daemon`)
+//! against a fresh instance of the contract in the mock.
+//!
+//! The approximation is only as good as the history is complete:
+//! - The contract must be instantiated in the mock with the same instantiate message first.
+//! - Addresses contained in the messages are replayed as is.
+//! - The funds sent with an execution are minted to its sender before the execution.
+//! - The block height is advanced by the number of blocks between two executions.
+//!
+//! ## Example
+//! ```ignore
+//! use cw_orch_mock::Mock;
+//!
+//! let executions = daemon.rt_handle.block_on(node._contract_executions(&onchain_addr, 1_000_000, None))?;
+//!
+//! let mock = Mock::new("sender");
+//! let contract = MyContract::new(mock.clone());
+//! contract.upload()?;
+//! contract.instantiate(&onchain_instantiate_msg, None, None)?;
+//!
+//! let report = mock.replay_executions(&contract.address()?, executions)?;
+//! assert!(report.is_complete(), "{report:?}");
+//! ```
+
+use cosmwasm_std::{Addr, Api, CosmosMsg, WasmMsg};
+use cw_multi_test::Executor;
+use cw_orch_core::{
+    environment::{ContractExecution, QueryHandler, StateInterface},
+    CwEnvError,
+};
+use cw_utils::NativeBalance;
+
+use crate::MockBase;
+
+/// Execution that failed during a replay
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayFailure {
+    pub txhash: String,
+    pub height: u64,
+    pub error: String,
+}
+
+/// Report of [`MockBase::replay_executions`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Number of executions that succeeded
+    pub replayed: usize,
+    /// Executions that failed, in order
+    pub failed: Vec<ReplayFailure>,
+}
+
+impl ReplayReport {
+    /// Returns true if all the executions succeeded
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl<A: Api, S: StateInterface> MockBase<A, S> {
+    /// Replays `executions`, oldest first, against `contract` as the execution history of the on-chain contract.
+    /// Failing executions are reported and don't stop the replay, see the [module documentation](crate::history)
+    pub fn replay_executions(
+        &self,
+        contract: &Addr,
+        executions: impl IntoIterator<Item = ContractExecution>,
+    ) -> Result<ReplayReport, CwEnvError> {
+        let mut report = ReplayReport::default();
+        let mut last_height = None;
+        for execution in executions {
+            if let Some(last_height) = last_height {
+                if execution.height > last_height {
+                    self.wait_blocks(execution.height - last_height)?;
+                }
+            }
+            last_height = Some(execution.height);
+
+            if !execution.funds.is_empty() {
+                self.mint(&execution.sender, &execution.funds)?;
+            }
+            let result = self.app.borrow_mut().execute(
+                execution.sender.clone(),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract.to_string(),
+                    msg: execution.msg,
+                    funds: execution.funds,
+                }),
+            );
+            match result {
+                Ok(_) => report.replayed += 1,
+                Err(e) => {
+                    log::debug!("Replay of {} failed: {e:#}", execution.txhash);
+                    report.failed.push(ReplayFailure {
+                        txhash: execution.txhash,
+                        height: execution.height,
+                        error: format!("{e:#}"),
+                    })
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Adds `funds` to the balance of `address`
+    fn mint(&self, address: &Addr, funds: &[cosmwasm_std::Coin]) -> Result<(), CwEnvError> {
+        let balance = self.app.borrow().wrap().query_all_balances(address)?;
+        let new_balance = NativeBalance(balance) + NativeBalance(funds.to_vec());
+        self.app
+            .borrow_mut()
+            .init_modules(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(storage, address, new_balance.into_vec())
+            })
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        coins, from_json, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+        StdError, StdResult,
+    };
+    use cw_multi_test::ContractWrapper;
+
+    use super::*;
+    use crate::Mock;
+
+    const TOTAL: &[u8] = b"total";
+
+    fn instantiate(deps: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        deps.storage.set(TOTAL, &to_json_binary(&0u64)?);
+        Ok(Response::new())
+    }
+
+    /// Adds the amount in the message and the funds to the total
+    fn execute(deps: DepsMut, _: Env, info: MessageInfo, amount: u64) -> StdResult<Response> {
+        if amount == 0 {
+            return Err(StdError::generic_err("nothing to add"));
+        }
+        let funds: u64 = info.funds.iter().map(|c| c.amount.u128() as u64).sum();
+        let total: u64 = from_json(deps.storage.get(TOTAL).unwrap_or_default())?;
+        deps.storage
+            .set(TOTAL, &to_json_binary(&(total + amount + funds))?);
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        Ok(deps.storage.get(TOTAL).unwrap_or_default().into())
+    }
+
+    fn setup(mock: &Mock) -> anyhow::Result<Addr> {
+        let code_id = mock
+            .app
+            .borrow_mut()
+            .store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+        let contract = mock.app.borrow_mut().instantiate_contract(
+            code_id,
+            mock.sender.clone(),
+            &Empty {},
+            &[],
+            "history",
+            None,
+        )?;
+        Ok(contract)
+    }
+
+    fn total(mock: &Mock, contract: &Addr) -> anyhow::Result<u64> {
+        Ok(mock
+            .app
+            .borrow()
+            .wrap()
+            .query_wasm_smart(contract, &Empty {})?)
+    }
+
+    /// Executes `amount` on the source chain and records the execution the way it would be fetched from a node
+    fn record(
+        source: &Mock,
+        contract: &Addr,
+        amount: u64,
+        funds: Vec<cosmwasm_std::Coin>,
+    ) -> anyhow::Result<ContractExecution> {
+        let sender = Addr::unchecked("juno1sender");
+        source.mint(&sender, &funds)?;
+        let height = source.block_info()?.height;
+        // Failed transactions are part of the on-chain history too
+        let _ = source.app.borrow_mut().execute_contract(
+            sender.clone(),
+            contract.clone(),
+            &amount,
+            &funds,
+        );
+        Ok(ContractExecution {
+            height,
+            txhash: format!("TX{height}"),
+            sender,
+            contract: contract.clone(),
+            msg: to_json_binary(&amount)?,
+            funds,
+        })
+    }
+
+    #[test]
+    fn replay() -> anyhow::Result<()> {
+        let source = Mock::new("sender");
+        let source_contract = setup(&source)?;
+        let source_start = source.block_info()?.height;
+
+        let mut executions = vec![record(&source, &source_contract, 1, vec![])?];
+        source.wait_blocks(5)?;
+        executions.push(record(&source, &source_contract, 0, vec![])?);
+        source.wait_blocks(5)?;
+        executions.push(record(&source, &source_contract, 2, coins(10, "ujuno"))?);
+        let failed_txhash = executions[1].txhash.clone();
+
+        let mock = Mock::new("sender");
+        let contract = setup(&mock)?;
+        let start_height = mock.block_info()?.height;
+        let report = mock.replay_executions(&contract, executions)?;
+
+        assert_eq!(report.replayed, 2);
+        assert_eq!(
+            report.failed,
+            vec![ReplayFailure {
+                txhash: failed_txhash,
+                height: source_start + 5,
+                error: report.failed[0].error.clone(),
+            }]
+        );
+        assert!(report.failed[0].error.contains("nothing to add"));
+
+        assert_eq!(total(&mock, &contract)?, 13);
+        assert_eq!(total(&mock, &contract)?, total(&source, &source_contract)?);
+        assert_eq!(mock.block_info()?.height, start_height + 10);
+        Ok(())
+    }
+}
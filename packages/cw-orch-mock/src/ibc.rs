@@ -0,0 +1,272 @@
+//! White-box testing of the IBC entry points of a contract in a [`MockBase`] environment.
+//!
+//! The helpers call an IBC entry point of an instantiated contract with a constructed channel or packet,
+//! without creating channels or relaying packets through an interchain environment.
+//! The entry point runs against the storage of the contract and its changes are only committed if it succeeds.
+//! The messages of the returned response are not dispatched, they are returned to be inspected by the test.
+//!
+//! ## Example
+//! ```ignore
+//! use cosmwasm_std::{testing::mock_ibc_channel, IbcOrder};
+//! use cw_orch_mock::ibc::incoming_packet;
+//!
+//! let channel = mock_ibc_channel("channel-0", IbcOrder::Unordered, "my-protocol-1");
+//! mock.ibc_channel_open(&contract, my_contract::ibc::ibc_channel_open, channel.clone())?;
+//! mock.ibc_channel_connect(&contract, my_contract::ibc::ibc_channel_connect, channel.clone())?;
+//!
+//! let packet = incoming_packet(&channel, 1, &PacketMsg::Ping {})?;
+//! let response = mock.ibc_packet_receive(&contract, my_contract::ibc::ibc_packet_receive, packet)?;
+//! assert_eq!(response.acknowledgement, to_json_binary(&Ack::Pong {})?);
+//! ```
+
+use std::fmt::Display;
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, Binary, ContractInfo, DepsMut, Env, IbcAcknowledgement,
+    IbcBasicResponse, IbcChannel, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
+    IbcReceiveResponse, IbcTimeout, IbcTimeoutBlock, MemoryStorage, Order, QuerierWrapper,
+    StdResult, Storage,
+};
+use cw_orch_core::{environment::StateInterface, CwEnvError};
+use serde::Serialize;
+
+use crate::MockBase;
+
+/// Packet sent by the counterparty of `channel` to the contract, to be used with [`MockBase::ibc_packet_receive`]
+pub fn incoming_packet<T: Serialize>(
+    channel: &IbcChannel,
+    sequence: u64,
+    data: &T,
+) -> StdResult<IbcPacket> {
+    Ok(IbcPacket::new(
+        to_json_binary(data)?,
+        channel.counterparty_endpoint.clone(),
+        channel.endpoint.clone(),
+        sequence,
+        never_timeout(),
+    ))
+}
+
+/// Packet sent by the contract on `channel`, to be used with [`MockBase::ibc_packet_ack`] and [`MockBase::ibc_packet_timeout`]
+pub fn outgoing_packet<T: Serialize>(
+    channel: &IbcChannel,
+    sequence: u64,
+    data: &T,
+) -> StdResult<IbcPacket> {
+    Ok(IbcPacket::new(
+        to_json_binary(data)?,
+        channel.endpoint.clone(),
+        channel.counterparty_endpoint.clone(),
+        sequence,
+        never_timeout(),
+    ))
+}
+
+fn never_timeout() -> IbcTimeout {
+    IbcTimeout::with_block(IbcTimeoutBlock {
+        revision: 0,
+        height: u64::MAX,
+    })
+}
+
+impl<A: Api, S: StateInterface> MockBase<A, S> {
+    /// Calls the `ibc_channel_open` entry point of `contract`, as the first step of the channel handshake (`OpenInit`)
+    pub fn ibc_channel_open<E: Display>(
+        &self,
+        contract: &Addr,
+        entry_point: impl FnOnce(DepsMut, Env, IbcChannelOpenMsg) -> Result<IbcChannelOpenResponse, E>,
+        channel: IbcChannel,
+    ) -> Result<IbcChannelOpenResponse, CwEnvError> {
+        self.call_ibc_entry_point(contract, entry_point, IbcChannelOpenMsg::new_init(channel))
+    }
+
+    /// Calls the `ibc_channel_connect` entry point of `contract`, as the channel handshake completes (`OpenAck`)
+    pub fn ibc_channel_connect<E: Display>(
+        &self,
+        contract: &Addr,
+        entry_point: impl FnOnce(DepsMut, Env, IbcChannelConnectMsg) -> Result<IbcBasicResponse, E>,
+        channel: IbcChannel,
+    ) -> Result<IbcBasicResponse, CwEnvError> {
+        let counterparty_version = channel.version.clone();
+        self.call_ibc_entry_point(
+            contract,
+            entry_point,
+            IbcChannelConnectMsg::new_ack(channel, counterparty_version),
+        )
+    }
+
+    /// Calls the `ibc_channel_close` entry point of `contract`, as the channel is closed on its side (`CloseInit`)
+    pub fn ibc_channel_close<E: Display>(
+        &self,
+        contract: &Addr,
+        entry_point: impl FnOnce(DepsMut, Env, IbcChannelCloseMsg) -> Result<IbcBasicResponse, E>,
+        channel: IbcChannel,
+    ) -> Result<IbcBasicResponse, CwEnvError> {
+        self.call_ibc_entry_point(contract, entry_point, IbcChannelCloseMsg::new_init(channel))
+    }
+
+    /// Calls the `ibc_packet_receive` entry point of `contract` with a packet relayed by the sender, see [`incoming_packet`]
+    pub fn ibc_packet_receive<E: Display>(
+        &self,
+        contract: &Addr,
+        entry_point: impl FnOnce(DepsMut, Env, IbcPacketReceiveMsg) -> Result<IbcReceiveResponse, E>,
+        packet: IbcPacket,
+    ) -> Result<IbcReceiveResponse, CwEnvError> {
+        let msg = IbcPacketReceiveMsg::new(packet, self.sender.clone());
+        self.call_ibc_entry_point(contract, entry_point, msg)
+    }
+
+    /// Calls the `ibc_packet_ack` entry point of `contract` with the acknowledgement of a packet it sent, see [`outgoing_packet`]
+    pub fn ibc_packet_ack<E: Display>(
+        &self,
+        contract: &Addr,
+        entry_point: impl FnOnce(DepsMut, Env, IbcPacketAckMsg) -> Result<IbcBasicResponse, E>,
+        packet: IbcPacket,
+        acknowledgement: Binary,
+    ) -> Result<IbcBasicResponse, CwEnvError> {
+        let msg = IbcPacketAckMsg::new(
+            IbcAcknowledgement::new(acknowledgement),
+            packet,
+            self.sender.clone(),
+        );
+        self.call_ibc_entry_point(contract, entry_point, msg)
+    }
+
+    /// Calls the `ibc_packet_timeout` entry point of `contract` with a packet it sent, see [`outgoing_packet`]
+    pub fn ibc_packet_timeout<E: Display>(
+        &self,
+        contract: &Addr,
+        entry_point: impl FnOnce(DepsMut, Env, IbcPacketTimeoutMsg) -> Result<IbcBasicResponse, E>,
+        packet: IbcPacket,
+    ) -> Result<IbcBasicResponse, CwEnvError> {
+        let msg = IbcPacketTimeoutMsg::new(packet, self.sender.clone());
+        self.call_ibc_entry_point(contract, entry_point, msg)
+    }
+
+    /// Runs an entry point against a copy of the contract storage and commits the copy if it succeeds
+    fn call_ibc_entry_point<M, R, E: Display>(
+        &self,
+        contract: &Addr,
+        entry_point: impl FnOnce(DepsMut, Env, M) -> Result<R, E>,
+        msg: M,
+    ) -> Result<R, CwEnvError> {
+        let mut storage = MemoryStorage::new();
+        let response = {
+            let app = self.app.borrow();
+            // Errors if the contract doesn't exist
+            app.wrap().query_wasm_contract_info(contract)?;
+            for (key, value) in app
+                .contract_storage(contract)
+                .range(None, None, Order::Ascending)
+            {
+                storage.set(&key, &value);
+            }
+
+            let env = Env {
+                block: app.block_info(),
+                transaction: None,
+                contract: ContractInfo {
+                    address: contract.clone(),
+                },
+            };
+            let deps = DepsMut {
+                storage: &mut storage,
+                api: app.api(),
+                querier: QuerierWrapper::new(&*app),
+            };
+            entry_point(deps, env, msg).map_err(|e| CwEnvError::StdErr(e.to_string()))?
+        };
+
+        let mut app = self.app.borrow_mut();
+        let mut contract_storage = app.contract_storage_mut(contract);
+        let keys: Vec<Vec<u8>> = contract_storage
+            .range(None, None, Order::Ascending)
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys {
+            contract_storage.remove(&key);
+        }
+        for (key, value) in storage.range(None, None, Order::Ascending) {
+            contract_storage.set(&key, &value);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        from_json, testing::mock_ibc_channel, Empty, IbcOrder, MessageInfo, Response, StdError,
+    };
+    use cw_multi_test::{ContractWrapper, Executor};
+
+    use super::*;
+    use crate::Mock;
+
+    const RECEIVED: &[u8] = b"received";
+
+    fn instantiate(deps: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        deps.storage.set(RECEIVED, &to_json_binary(&0u64)?);
+        Ok(Response::new())
+    }
+
+    fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(deps: cosmwasm_std::Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        Ok(deps.storage.get(RECEIVED).unwrap_or_default().into())
+    }
+
+    fn ibc_packet_receive(
+        deps: DepsMut,
+        _: Env,
+        msg: IbcPacketReceiveMsg,
+    ) -> StdResult<IbcReceiveResponse> {
+        let amount: u64 = from_json(&msg.packet.data)?;
+        if amount == 0 {
+            return Err(StdError::generic_err("nothing received"));
+        }
+        let received: u64 = from_json(deps.storage.get(RECEIVED).unwrap_or_default())?;
+        deps.storage
+            .set(RECEIVED, &to_json_binary(&(received + amount))?);
+        Ok(IbcReceiveResponse::new().set_ack(to_json_binary(&true)?))
+    }
+
+    #[test]
+    fn packet_receive() -> anyhow::Result<()> {
+        let mock = Mock::new("sender");
+        let code_id = mock
+            .app
+            .borrow_mut()
+            .store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+        let contract = mock.app.borrow_mut().instantiate_contract(
+            code_id,
+            mock.sender.clone(),
+            &Empty {},
+            &[],
+            "ibc",
+            None,
+        )?;
+
+        let channel = mock_ibc_channel("channel-0", IbcOrder::Unordered, "ibc-test-1");
+        let packet = incoming_packet(&channel, 1, &5u64)?;
+        let response = mock.ibc_packet_receive(&contract, ibc_packet_receive, packet)?;
+        assert_eq!(response.acknowledgement, to_json_binary(&true)?);
+
+        // Failing entry points don't change the storage
+        let packet = incoming_packet(&channel, 2, &0u64)?;
+        assert!(mock
+            .ibc_packet_receive(&contract, ibc_packet_receive, packet)
+            .is_err());
+
+        let received: u64 = mock
+            .app
+            .borrow()
+            .wrap()
+            .query_wasm_smart(&contract, &Empty {})?;
+        assert_eq!(received, 5);
+        Ok(())
+    }
+}
@@ -6,11 +6,18 @@ pub extern crate cw_multi_test;
 
 mod bech32;
 mod core;
+pub mod fixture;
+pub mod history;
+pub mod ibc;
 pub mod queriers;
 mod simple;
+mod snapshot;
 mod state;
+mod tx_cost;
 
 pub use self::core::{Mock, MockBase, MockBech32};
+pub use snapshot::MockSnapshot;
+pub use tx_cost::MockTxCostSchedule;
 
 pub type MockApp = self::core::MockApp<MockApi>;
 pub type MockAppBech32 = self::core::MockApp<MockApiBech32>;
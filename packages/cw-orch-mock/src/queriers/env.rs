@@ -9,7 +9,10 @@ impl<A: Api, S: StateInterface> EnvironmentQuerier for MockBase<A, S> {
     fn env_info(&self) -> EnvironmentInfo {
         let block_info = self.block_info().unwrap();
         let chain_id = block_info.chain_id.clone();
-        let chain_name = chain_id.rsplitn(2, '-').last().unwrap().to_string();
+        let chain_name = match self.chain_info() {
+            Some(chain_info) => chain_info.network_info.chain_name,
+            None => chain_id.rsplitn(2, '-').last().unwrap().to_string(),
+        };
 
         EnvironmentInfo {
             chain_id,
@@ -1,9 +1,9 @@
 use crate::MockBase;
 
-use cosmwasm_std::Api;
+use cosmwasm_std::{Api, Timestamp};
 use cw_multi_test::next_block;
 use cw_orch_core::{
-    environment::{DefaultQueriers, QueryHandler, StateInterface},
+    environment::{ChainTime, DefaultQueriers, QueryHandler, StateInterface},
     CwEnvError,
 };
 
@@ -37,8 +37,53 @@ impl<A: Api, S: StateInterface> QueryHandler for MockBase<A, S> {
     }
 }
 
+impl<A: Api, S: StateInterface> ChainTime for MockBase<A, S> {
+    type Error = CwEnvError;
+
+    fn advance_blocks(&self, blocks: u64) -> Result<(), CwEnvError> {
+        self.wait_blocks(blocks)
+    }
+
+    fn advance_seconds(&self, seconds: u64) -> Result<(), CwEnvError> {
+        self.wait_seconds(seconds)
+    }
+
+    fn set_block_time(&self, time: Timestamp) -> Result<(), CwEnvError> {
+        self.app.borrow_mut().update_block(|b| {
+            if time > b.time {
+                b.height += (time.seconds() - b.time.seconds()) / 5;
+            }
+            b.time = time;
+        });
+        Ok(())
+    }
+}
+
 impl<A: Api, S: StateInterface> DefaultQueriers for MockBase<A, S> {
     type Bank = bank::MockBankQuerier<A>;
     type Wasm = wasm::MockWasmQuerier<A, S>;
     type Node = node::MockNodeQuerier<A>;
 }
+
+#[cfg(test)]
+mod test {
+    use cw_orch_core::environment::{ChainTime, QueryHandler};
+
+    use crate::Mock;
+
+    #[test]
+    fn time_travel() -> anyhow::Result<()> {
+        let mock = Mock::new("sender");
+        let start = mock.block_info()?;
+
+        mock.advance_blocks(2)?;
+        mock.advance_seconds(50)?;
+        let block = mock.block_info()?;
+        assert_eq!(block.height, start.height + 12);
+        assert_eq!(block.time, start.time.plus_seconds(60));
+
+        mock.set_block_time(start.time.plus_days(21))?;
+        assert_eq!(mock.block_info()?.time, start.time.plus_days(21));
+        Ok(())
+    }
+}
@@ -2,11 +2,16 @@ use std::marker::PhantomData;
 use std::{cell::RefCell, rc::Rc};
 
 use cosmwasm_std::testing::MockApi;
-use cosmwasm_std::{instantiate2_address, Api, Binary, ContractResult, StdError, SystemResult};
+use cosmwasm_std::{
+    instantiate2_address, Addr, Api, Binary, ContractResult, StdError, SystemResult,
+};
 use cosmwasm_std::{to_json_binary, ContractInfoResponse, HexBinary};
 use cw_orch_core::{
     contract::interface_traits::{ContractInstance, Uploadable},
-    environment::{Querier, QuerierGetter, QueryHandler, StateInterface, TxHandler, WasmQuerier},
+    environment::{
+        Querier, QuerierGetter, QueryHandler, StateInterface, StorageDump, StorageInspector,
+        TxHandler, WasmQuerier,
+    },
     CwEnvError,
 };
 use serde::{de::DeserializeOwned, Serialize};
@@ -63,6 +68,8 @@ fn local_hash<Chain: TxHandler + QueryHandler, T: Uploadable + ContractInstance<
 ) -> Result<HexBinary, CwEnvError> {
     // We return the hashed contract-id.
     // This will cause the logic to never re-upload a contract if it has the same contract-id.
+    // The checksums of the codes stored in cw-multi-test are generated from the code id instead,
+    // so codes can't be looked up by checksum and `code_id_by_checksum` always returns `None`.
     let hash: [u8; 32] = Sha256::digest(contract.id()).into();
     Ok(hash.into())
 }
@@ -205,6 +212,19 @@ impl<A: Api, S: StateInterface> WasmQuerier for MockWasmQuerier<A, S> {
     }
 }
 
+impl<A: Api, S: StateInterface> StorageInspector for MockBase<A, S> {
+    type Error = CwEnvError;
+
+    fn dump_storage(&self, contract: &Addr) -> Result<StorageDump, Self::Error> {
+        Ok(self
+            .app
+            .borrow()
+            .dump_wasm_raw(contract)
+            .into_iter()
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{Addr, Binary, Empty, HexBinary, Response, StdError};
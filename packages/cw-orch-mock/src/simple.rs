@@ -4,7 +4,7 @@ use std::rc::Rc;
 use cosmwasm_std::testing::MockApi;
 use cosmwasm_std::{Addr, Coin, Uint128};
 use cw_multi_test::AppBuilder;
-use cw_orch_core::environment::{BankQuerier, BankSetter, TxHandler};
+use cw_orch_core::environment::{BankQuerier, BankSetter, ChainInfoOwned, TxHandler};
 use cw_orch_core::{
     environment::{DefaultQueriers, StateInterface},
     CwEnvError,
@@ -108,6 +108,18 @@ impl Mock {
 
         chain
     }
+
+    /// Create a mock environment simulating `chain_info`: its chain id and gas denom are used by the mock and reported by [`env_info`](cw_orch_core::environment::EnvironmentQuerier::env_info).
+    /// Use [`MockBech32::new_with_chain`](crate::MockBech32::new_with_chain) to also get addresses with the prefix of the chain
+    pub fn new_with_chain(
+        sender: impl Into<String>,
+        chain_info: impl Into<ChainInfoOwned>,
+    ) -> Self {
+        let chain_info = chain_info.into();
+        let chain = Mock::new_custom(sender, MockState::new_with_chain_id(&chain_info.chain_id));
+        chain.set_chain(chain_info);
+        chain
+    }
 }
 impl<S: StateInterface> Mock<S> {
     /// Create a mock environment with a custom mock state.
@@ -120,6 +132,9 @@ impl<S: StateInterface> Mock<S> {
             sender: Addr::unchecked(sender),
             state,
             app,
+            tx_cost_schedule: Rc::new(RefCell::new(None)),
+            last_tx_cost: Rc::new(RefCell::new(None)),
+            chain_info: Rc::new(RefCell::new(None)),
         }
     }
 }
@@ -0,0 +1,118 @@
+//! Snapshot and restore of a [`MockBase`] environment.
+//! This allows branching multiple test scenarios from a common prepared state without re-running the setup.
+
+use cosmwasm_std::{Api, BlockInfo, Order, Record};
+use cw_orch_core::environment::StateInterface;
+
+use crate::{MockBase, MockState};
+
+/// Captured state of a [`MockBase`] environment.
+/// It contains the whole cw-multi-test storage, the current block and the cw-orch deployment state.
+///
+/// Uploaded wasm codes are kept in memory by cw-multi-test and are not part of the snapshot.
+/// Codes uploaded after taking a snapshot are thus still available after restoring it.
+#[derive(Clone, Debug)]
+pub struct MockSnapshot<S: StateInterface = MockState> {
+    storage: Vec<Record>,
+    block: BlockInfo,
+    state: S,
+}
+
+impl<S: StateInterface> MockSnapshot<S> {
+    /// Block at which the snapshot was taken
+    pub fn block(&self) -> &BlockInfo {
+        &self.block
+    }
+}
+
+impl<A: Api, S: StateInterface> MockBase<A, S> {
+    /// Captures the entire storage, block info and deployment state of the environment
+    ///
+    /// ## Example
+    /// ```
+    /// # use cosmwasm_std::coins;
+    /// use cw_orch_mock::Mock;
+    ///
+    /// let mock = Mock::new("sender");
+    /// let snapshot = mock.snapshot();
+    ///
+    /// mock.set_balance("sender", coins(100, "token")).unwrap();
+    /// mock.restore(&snapshot);
+    ///
+    /// assert!(mock.query_balance("sender", "token").unwrap().is_zero());
+    /// ```
+    pub fn snapshot(&self) -> MockSnapshot<S> {
+        let mut app = self.app.borrow_mut();
+        let storage = app.init_modules(|_, _, storage| {
+            storage
+                .range(None, None, Order::Ascending)
+                .collect::<Vec<_>>()
+        });
+
+        MockSnapshot {
+            storage,
+            block: app.block_info(),
+            state: self.state.borrow().clone(),
+        }
+    }
+
+    /// Restores the environment to a previously captured [`MockSnapshot`].
+    /// All storage entries written after the snapshot are discarded.
+    /// The snapshot can be restored multiple times
+    pub fn restore(&self, snapshot: &MockSnapshot<S>) {
+        let mut app = self.app.borrow_mut();
+        app.init_modules(|_, _, storage| {
+            let keys = storage
+                .range(None, None, Order::Ascending)
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>();
+            for key in keys {
+                storage.remove(&key);
+            }
+            for (key, value) in &snapshot.storage {
+                storage.set(key, value);
+            }
+        });
+        app.set_block(snapshot.block.clone());
+
+        *self.state.borrow_mut() = snapshot.state.clone();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coins, Addr};
+    use cw_orch_core::environment::{BankQuerier, DefaultQueriers, QueryHandler, StateInterface};
+
+    use crate::Mock;
+
+    #[test]
+    fn snapshot_restore() -> anyhow::Result<()> {
+        let mock = Mock::new("sender");
+        mock.set_balance("sender", coins(100, "token"))?;
+        let snapshot = mock.snapshot();
+        let height = mock.block_info()?.height;
+
+        // First scenario
+        mock.set_balance("sender", coins(50, "token"))?;
+        mock.wait_blocks(10)?;
+        mock.state
+            .borrow_mut()
+            .set_address("contract", &Addr::unchecked("addr"));
+        mock.restore(&snapshot);
+
+        assert_eq!(mock.query_balance("sender", "token")?.u128(), 100);
+        assert_eq!(mock.block_info()?.height, height);
+        assert!(mock.state.borrow().get_address("contract").is_err());
+
+        // Second scenario, the snapshot can be restored multiple times
+        mock.set_balance("other", coins(10, "token"))?;
+        mock.restore(&snapshot);
+        assert!(mock
+            .bank_querier()
+            .balance(Addr::unchecked("other"), None)?
+            .is_empty());
+
+        Ok(())
+    }
+}
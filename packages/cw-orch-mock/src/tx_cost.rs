@@ -0,0 +1,74 @@
+//! Deterministic, size-based cost estimates for [`MockBase`](crate::MockBase) transactions.
+//! cw-multi-test doesn't execute wasm and thus can't meter gas.
+//! This module derives a cost from the size of the message of a transaction and of the events it emits.
+//! The cost is deterministic and can be used for regression budgets (e.g. detecting a message or event payload growing),
+//! but it is not a gas estimate: no execution is metered and the values don't relate to the gas used on a real chain.
+
+use cw_multi_test::AppResponse;
+
+/// Costs used to compute the size-based cost of mock transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockTxCostSchedule {
+    /// Fixed cost of every transaction
+    pub tx_base: u64,
+    /// Cost per byte of the json message sent to the contract
+    pub per_msg_byte: u64,
+    /// Cost per emitted event attribute
+    pub per_attribute: u64,
+    /// Cost per byte of emitted event types, attribute keys and attribute values
+    pub per_attribute_byte: u64,
+}
+
+impl Default for MockTxCostSchedule {
+    fn default() -> Self {
+        Self {
+            tx_base: 60_000,
+            per_msg_byte: 10,
+            per_attribute: 10,
+            per_attribute_byte: 1,
+        }
+    }
+}
+
+impl MockTxCostSchedule {
+    /// Cost of a transaction with a message of `msg_len` bytes that resulted in `response`
+    pub fn cost(&self, msg_len: usize, response: &AppResponse) -> u64 {
+        let events_cost: u64 = response
+            .events
+            .iter()
+            .map(|event| {
+                let attributes_len: usize = event
+                    .attributes
+                    .iter()
+                    .map(|a| a.key.len() + a.value.len())
+                    .sum();
+                event.attributes.len() as u64 * self.per_attribute
+                    + (event.ty.len() + attributes_len) as u64 * self.per_attribute_byte
+            })
+            .sum();
+
+        self.tx_base + msg_len as u64 * self.per_msg_byte + events_cost
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::Event;
+    use cw_multi_test::AppResponse;
+
+    use super::MockTxCostSchedule;
+
+    #[test]
+    fn deterministic_cost() {
+        let schedule = MockTxCostSchedule::default();
+        let response = AppResponse {
+            events: vec![Event::new("wasm").add_attribute("action", "mint")],
+            data: None,
+        };
+
+        // 60_000 + 10 * 10 + 1 * 10 + (4 + 6 + 4) * 1
+        assert_eq!(schedule.cost(10, &response), 60_124);
+        assert_eq!(schedule.cost(10, &response), schedule.cost(10, &response));
+        assert_eq!(schedule.cost(0, &AppResponse::default()), 60_000);
+    }
+}
@@ -0,0 +1,46 @@
+use cw_orch_core::{
+    conformance::ConformanceSuite,
+    environment::{ChainState, StateInterface},
+    CwEnvError,
+};
+use cw_orch_mock::{Mock, MockBech32};
+use mock_contract::{ExecuteMsg, InstantiateMsg, MigrateMsg, MockContract, QueryMsg};
+
+#[test]
+fn mock_conformance() -> Result<(), CwEnvError> {
+    let mock = Mock::new("sender");
+    let contract = MockContract::new("mock_contract", mock.clone());
+
+    let report = ConformanceSuite::new(&mock)
+        .contract(&contract, &InstantiateMsg {})?
+        .execute_msg(&ExecuteMsg::<String>::FirstMessage {})?
+        .query_msg(&QueryMsg::<String>::FirstQuery {})?
+        .migrate_msg(&MigrateMsg {
+            t: "success".to_string(),
+        })?
+        .run()
+        .into_result()?;
+    assert_eq!(report.checks.len(), 11);
+
+    // The suite doesn't leave entries in the state
+    assert!(mock.state().get_all_addresses()?.is_empty());
+    assert!(mock.state().get_all_code_ids()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn mock_bech32_conformance() -> Result<(), CwEnvError> {
+    let mock = MockBech32::new("mock");
+    let contract = MockContract::new("mock_contract", mock.clone());
+
+    ConformanceSuite::new(&mock)
+        .contract(&contract, &InstantiateMsg {})?
+        .execute_msg(&ExecuteMsg::<String>::FirstMessage {})?
+        .query_msg(&QueryMsg::<String>::FirstQuery {})?
+        .migrate_msg(&MigrateMsg {
+            t: "success".to_string(),
+        })?
+        .run()
+        .into_result()?;
+    Ok(())
+}
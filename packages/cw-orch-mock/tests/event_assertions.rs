@@ -0,0 +1,44 @@
+use cw_multi_test::AppResponse;
+use cw_orch_core::contract::interface_traits::{
+    ContractInstance, CwOrchExecute, CwOrchInstantiate, CwOrchUpload,
+};
+use cw_orch_core::environment::EventAssertions;
+use cw_orch_mock::Mock;
+use mock_contract::{ExecuteMsg, InstantiateMsg, MockContract};
+
+fn first_message_response() -> anyhow::Result<(AppResponse, String)> {
+    let chain = Mock::new("sender");
+    let contract = MockContract::new("mock-contract", chain);
+    contract.upload()?;
+    contract.instantiate(&InstantiateMsg {}, None, None)?;
+    let response = contract.execute(&ExecuteMsg::FirstMessage {}, None)?;
+    Ok((response, contract.address()?.to_string()))
+}
+
+#[test]
+fn event_assertions() -> anyhow::Result<()> {
+    let (response, address) = first_message_response()?;
+    let event = response.assert_event("wasm", &[("action", "first message passed")]);
+    assert_eq!(event.attributes.len(), 2);
+    response.assert_no_event("wasm", &[("action", "other")]);
+    response.assert_event_attr_value("wasm", "_contract_address", &address);
+    response.assert_event("execute", &[("_contract_address", address.as_str())]);
+
+    let err = response
+        .find_event("wasm", &[("action", "other")])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("action=other"));
+    assert!(err.contains("action=first message passed"));
+
+    let err = response.event_attr_value("transfer", "amount").unwrap_err();
+    assert!(err.to_string().contains("emitted events: [execute, wasm]"));
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "expected event `wasm` with attributes [action=other]")]
+fn assert_event_panics() {
+    let (response, _) = first_message_response().unwrap();
+    response.assert_event("wasm", &[("action", "other")]);
+}
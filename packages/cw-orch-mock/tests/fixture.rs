@@ -0,0 +1,51 @@
+use cosmwasm_std::{coins, Addr};
+use cw_orch_core::{
+    contract::interface_traits::{ContractInstance, CwOrchQuery},
+    environment::{DefaultQueriers, WasmQuerier},
+    CwEnvError,
+};
+use cw_orch_mock::{
+    fixture::{FixtureContract, MockFixture},
+    Mock,
+};
+use mock_contract::{InstantiateMsg, MockContract, QueryMsg};
+
+fn fixture() -> Result<MockFixture, CwEnvError> {
+    Ok(MockFixture::new()
+        .balance("alice", coins(1_000, "ujuno"))
+        .balance("sender", coins(100, "ujuno"))
+        .code::<MockContract<Mock>>("mock_contract")
+        .contract(FixtureContract::new("mock_contract", &InstantiateMsg {})?)
+        .contract(
+            FixtureContract::new("second_contract", &InstantiateMsg {})?
+                .with_code("mock_contract")
+                .with_admin("alice")
+                .with_funds(coins(10, "ujuno")),
+        ))
+}
+
+#[test]
+fn load_fixture() -> Result<(), CwEnvError> {
+    let fixture = fixture()?;
+    let mock = Mock::new("sender");
+    mock.load_fixture(&fixture)?;
+
+    assert_eq!(mock.query_balance("alice", "ujuno")?.u128(), 1_000);
+    assert_eq!(mock.query_balance("sender", "ujuno")?.u128(), 90);
+
+    let first = MockContract::new("mock_contract", mock.clone());
+    let second = MockContract::new("second_contract", mock.clone());
+    assert_eq!(first.code_id()?, second.code_id()?);
+    assert_ne!(first.address()?, second.address()?);
+    assert_eq!(mock.query_balance(second.address()?, "ujuno")?.u128(), 10);
+
+    let info = mock.wasm_querier().contract_info(second.address()?)?;
+    assert_eq!(info.admin, Some(Addr::unchecked("alice").to_string()));
+
+    // The same fixture can be loaded in another environment
+    let other = Mock::new("sender");
+    other.load_fixture(&fixture)?;
+    let _: String =
+        MockContract::new("mock_contract", other.clone()).query(&QueryMsg::FirstQuery {})?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use cw_orch_core::contract::interface_traits::{CwOrchExecute, CwOrchInstantiate, CwOrchUpload};
+use cw_orch_core::gas::GasGoldenFile;
+use cw_orch_mock::{Mock, MockTxCostSchedule};
+use mock_contract::{ExecuteMsg, InstantiateMsg, MockContract};
+
+/// Size-based cost of executing `msg` on a fresh mock contract
+fn execute_cost(msg: &ExecuteMsg) -> anyhow::Result<u64> {
+    let chain = Mock::new("sender");
+    let contract = MockContract::new("mock-contract", chain.clone());
+    contract.upload()?;
+    contract.instantiate(&InstantiateMsg {}, None, None)?;
+
+    chain.enable_tx_cost_estimation(MockTxCostSchedule::default());
+    contract.execute(msg, None)?;
+    Ok(chain.last_tx_cost().unwrap())
+}
+
+#[test]
+fn golden_file_regressions() -> anyhow::Result<()> {
+    let path =
+        std::env::temp_dir().join(format!("cw-orch-mock-golden-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let small = execute_cost(&ExecuteMsg::FirstMessage {})?;
+    let large = execute_cost(&ExecuteMsg::ThirdMessage {
+        t: "x".repeat(1_000),
+    })?;
+    assert!(large > small);
+    // Costs are deterministic
+    assert_eq!(execute_cost(&ExecuteMsg::FirstMessage {})?, small);
+
+    let mut golden = GasGoldenFile::load(&path, "mock")?.update(false);
+    golden.record("execute", small);
+    let report = golden.check()?;
+    assert_eq!(report.new_operations().len(), 1);
+    assert!(report.regressions().is_empty());
+    golden.save()?;
+
+    let mut golden = GasGoldenFile::load(&path, "mock")?
+        .threshold(5.0)
+        .update(false);
+    golden.record("execute", large);
+    assert_eq!(golden.report().regressions().len(), 1);
+    assert!(golden.check().is_err());
+
+    let mut golden = GasGoldenFile::load(&path, "mock")?.update(false);
+    golden.record("execute", small);
+    let report = golden.check()?;
+    assert!(report.regressions().is_empty());
+    assert!(report.improvements().is_empty());
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use cw_orch_core::contract::interface_traits::{
+    ContractInstance, CwOrchExecute, CwOrchInstantiate, CwOrchUpload,
+};
+use cw_orch_core::contract::{recording, RecordedCall};
+use cw_orch_mock::MockBech32;
+use mock_contract::{ExecuteMsg, InstantiateMsg, MockContract};
+
+#[test]
+fn record_and_replay() -> anyhow::Result<()> {
+    let testnet = MockBech32::new("testnet");
+    let contract = MockContract::new("mock-contract", testnet.clone());
+    contract.upload()?;
+
+    recording::start_recording();
+    contract.instantiate(&InstantiateMsg {}, None, None)?;
+    contract.execute(&ExecuteMsg::<String>::FirstMessage {}, None)?;
+    let recording = recording::stop_recording();
+
+    assert_eq!(recording.calls.len(), 2);
+    let RecordedCall::Instantiate { address, .. } = &recording.calls[0] else {
+        panic!("expected an instantiation, got {:?}", recording.calls[0]);
+    };
+    assert_eq!(address, contract.address()?.as_str());
+
+    // Replay on another environment with the code uploaded
+    let mainnet = MockBech32::new("mainnet");
+    let replayed = MockContract::new("mock-contract", mainnet.clone());
+    replayed.upload()?;
+    // The replay isn't captured by the running recording
+    recording::start_recording();
+    recording.replay(&mainnet)?;
+    assert!(recording::stop_recording().calls.is_empty());
+    assert!(replayed.address()?.as_str().starts_with("mainnet"));
+    Ok(())
+}
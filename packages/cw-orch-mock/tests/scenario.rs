@@ -0,0 +1,56 @@
+use cosmwasm_std::Addr;
+use cw_orch_core::contract::interface_traits::{ContractInstance, CwOrchInstantiate, CwOrchUpload};
+use cw_orch_core::scenario::{Scenario, Step, StepOutcome};
+use cw_orch_mock::Mock;
+use mock_contract::{ExecuteMsg, InstantiateMsg, MockContract};
+
+#[test]
+fn scenario_report() -> anyhow::Result<()> {
+    let chain = Mock::new("admin");
+    let contract = MockContract::new("mock-contract", chain.clone());
+    contract.upload()?;
+    contract.instantiate(&InstantiateMsg {}, None, None)?;
+
+    let report = Scenario::new("mock flows", &chain)
+        .step(
+            Step::new("alice executes")
+                .sender(Addr::unchecked("alice"))
+                .execute(&contract, ExecuteMsg::FirstMessage {}, vec![])
+                .expect_event("wasm", &[("action", "first message passed")])
+                .check("contract is instantiated", |_| {
+                    contract.address().map(|_| ())
+                }),
+        )
+        .step(
+            Step::new("bob fails")
+                .sender(Addr::unchecked("bob"))
+                .execute(
+                    &contract,
+                    ExecuteMsg::SecondMessage { t: "t".to_string() },
+                    vec![],
+                )
+                .expect_error("Second Message Failed"),
+        )
+        .step(
+            Step::new("alice expects the wrong event")
+                .sender(Addr::unchecked("alice"))
+                .execute(&contract, ExecuteMsg::FourthMessage, vec![])
+                .expect_event("wasm", &[("action", "first message passed")]),
+        )
+        .step(Step::new("never run"))
+        .run_report();
+
+    assert!(!report.passed());
+    assert_eq!(report.steps[0].outcome, StepOutcome::Passed);
+    assert_eq!(report.steps[1].outcome, StepOutcome::Passed);
+    assert_eq!(report.steps[1].sender, Addr::unchecked("bob"));
+    assert!(matches!(report.steps[2].outcome, StepOutcome::Failed(_)));
+    assert_eq!(report.steps[3].outcome, StepOutcome::Skipped);
+
+    let report = report.to_string();
+    assert!(report.contains("✓ 1. alice executes [sender: alice, contract: mock-contract]"));
+    assert!(report.contains("✗ 3. alice expects the wrong event"));
+    assert!(report.contains("action=fourth message passed"));
+    assert!(report.contains("- 4. never run (skipped)"));
+    Ok(())
+}
@@ -18,6 +18,7 @@ pub const CONSTANTINE_3: ChainInfo = ChainInfo {
     network_info: ARCHWAY_NETWORK,
     lcd_url: Some("https://api.constantine.archway.io"),
     fcd_url: None,
+    rpc_url: None,
 };
 
 /// Archway Docs: <https://docs.archway.io/resources/networks>
@@ -31,5 +32,6 @@ pub const ARCHWAY_1: ChainInfo = ChainInfo {
     network_info: ARCHWAY_NETWORK,
     lcd_url: Some("https://api.mainnet.archway.io"),
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: archway
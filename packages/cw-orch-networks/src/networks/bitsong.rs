@@ -16,6 +16,7 @@ pub const BITSONG_1: ChainInfo = ChainInfo {
     network_info: BITSONG_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const BOBNET: ChainInfo = ChainInfo {
@@ -27,6 +28,7 @@ pub const BOBNET: ChainInfo = ChainInfo {
     network_info: BITSONG_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const LOCAL_BITSONG: ChainInfo = ChainInfo {
@@ -38,5 +40,6 @@ pub const LOCAL_BITSONG: ChainInfo = ChainInfo {
     network_info: BITSONG_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: bitsong
@@ -18,6 +18,7 @@ pub const VOTA_ASH: ChainInfo = ChainInfo {
     network_info: DORAVOTA_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const VOTA_TESTNET: ChainInfo = ChainInfo {
@@ -29,4 +30,5 @@ pub const VOTA_TESTNET: ChainInfo = ChainInfo {
     network_info: DORAVOTA_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
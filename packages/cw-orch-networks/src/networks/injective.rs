@@ -19,6 +19,7 @@ pub const INJECTIVE_1: ChainInfo = ChainInfo {
     network_info: INJECTIVE_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 /// <https://docs.injective.network/develop/public-endpoints/#testnet>
@@ -32,5 +33,6 @@ pub const INJECTIVE_888: ChainInfo = ChainInfo {
     network_info: INJECTIVE_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: injective
@@ -18,6 +18,7 @@ pub const UNI_6: ChainInfo = ChainInfo {
     network_info: JUNO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const JUNO_1: ChainInfo = ChainInfo {
@@ -29,6 +30,7 @@ pub const JUNO_1: ChainInfo = ChainInfo {
     network_info: JUNO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const LOCAL_JUNO: ChainInfo = ChainInfo {
@@ -40,5 +42,6 @@ pub const LOCAL_JUNO: ChainInfo = ChainInfo {
     network_info: JUNO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: juno
@@ -16,5 +16,6 @@ pub const HARPOON_4: ChainInfo = ChainInfo {
     network_info: KUJIRA_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: kujira
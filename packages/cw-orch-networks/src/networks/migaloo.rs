@@ -16,6 +16,7 @@ pub const LOCAL_MIGALOO: ChainInfo = ChainInfo {
     network_info: MIGALOO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 /// <https://docs.migaloo.zone/validators/testnet>
@@ -28,6 +29,7 @@ pub const NARWHAL_1: ChainInfo = ChainInfo {
     network_info: MIGALOO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 /// <https://docs.migaloo.zone/validators/mainnet>
@@ -40,5 +42,6 @@ pub const MIGALOO_1: ChainInfo = ChainInfo {
     network_info: MIGALOO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: migaloo
@@ -12,9 +12,12 @@ pub mod migaloo;
 pub mod neutron;
 pub mod nibiru;
 pub mod osmosis;
+#[cfg(feature = "registry")]
+pub mod registry;
 pub mod rollkit;
 pub mod sei;
 pub mod terra;
+pub mod typed;
 pub mod xion;
 
 pub use archway::{ARCHWAY_1, CONSTANTINE_3};
@@ -28,6 +31,8 @@ pub use migaloo::{LOCAL_MIGALOO, MIGALOO_1, NARWHAL_1};
 pub use neutron::{LOCAL_NEUTRON, NEUTRON_1, PION_1};
 pub use nibiru::NIBIRU_ITN_2;
 pub use osmosis::{LOCAL_OSMO, OSMOSIS_1, OSMO_5};
+#[cfg(feature = "registry")]
+pub use registry::from_registry;
 pub use rollkit::{LOCAL_ROLLKIT, ROLLKIT_TESTNET};
 pub use sei::{ATLANTIC_2, LOCAL_SEI, PACIFIC_1, SEI_DEVNET_3};
 pub use terra::{LOCAL_TERRA, PHOENIX_1, PISCO_1};
@@ -17,6 +17,7 @@ pub const PION_1: ChainInfo = ChainInfo {
     network_info: NEUTRON_NETWORK,
     lcd_url: Some("https://rest-palvus.pion-1.ntrn.tech"),
     fcd_url: None,
+    rpc_url: None,
 };
 
 /// <https://github.com/cosmos/chain-registry/blob/master/neutron/chain.json>
@@ -29,6 +30,7 @@ pub const NEUTRON_1: ChainInfo = ChainInfo {
     network_info: NEUTRON_NETWORK,
     lcd_url: Some("https://rest-kralum.neutron-1.neutron.org"),
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const LOCAL_NEUTRON: ChainInfo = ChainInfo {
@@ -40,5 +42,6 @@ pub const LOCAL_NEUTRON: ChainInfo = ChainInfo {
     network_info: NEUTRON_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: neutron
@@ -16,5 +16,6 @@ pub const NIBIRU_ITN_2: ChainInfo = ChainInfo {
     network_info: NIBIRU_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: nibiru
@@ -16,6 +16,7 @@ pub const OSMOSIS_1: ChainInfo = ChainInfo {
     network_info: OSMO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const OSMO_5: ChainInfo = ChainInfo {
@@ -27,6 +28,7 @@ pub const OSMO_5: ChainInfo = ChainInfo {
     network_info: OSMO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const LOCAL_OSMO: ChainInfo = ChainInfo {
@@ -38,5 +40,6 @@ pub const LOCAL_OSMO: ChainInfo = ChainInfo {
     network_info: OSMO_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: osmosis
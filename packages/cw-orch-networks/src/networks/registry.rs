@@ -0,0 +1,269 @@
+//! # Cosmos Chain Registry
+//! Builds a [`ChainInfoOwned`] from the `chain.json` of a chain in the [Cosmos Chain Registry](https://github.com/cosmos/chain-registry),
+//! for chains that are not (yet) defined in this crate.
+//!
+//! ```rust,no_run
+//! # async fn example() -> Result<(), cw_orch_networks::networks::registry::RegistryError> {
+//! use cw_orch_networks::networks;
+//! let osmosis = networks::from_registry("osmosis").await?;
+//! let osmosis_testnet = networks::from_registry("osmosistestnet").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use cw_orch_core::{
+    environment::{ChainInfoOwned, NetworkInfoOwned},
+    CwEnvError,
+};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+/// Raw content of the master branch of the Cosmos Chain Registry
+pub const CHAIN_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/cosmos/chain-registry/master";
+/// Timeout of the liveness check of an endpoint
+const LIVENESS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Chain infos already pulled from a registry, by registry url and chain name
+static REGISTRY_CACHE: Lazy<Mutex<HashMap<(String, String), ChainInfoOwned>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(thiserror::Error, Debug)]
+pub enum RegistryError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    #[error("Invalid chain.json of {0}: {1}")]
+    InvalidChainData(String, serde_json::Error),
+    #[error("Chain {0} doesn't define any fee token in the chain registry")]
+    MissingFeeToken(String),
+    #[error("None of the {count} gRPC endpoints of {chain} in the chain registry is reachable")]
+    NoLiveEndpoint { chain: String, count: usize },
+}
+
+impl From<RegistryError> for CwEnvError {
+    fn from(value: RegistryError) -> Self {
+        CwEnvError::AnyError(value.into())
+    }
+}
+
+/// Pulls the [`ChainInfoOwned`] of `chain_name` from the Cosmos Chain Registry, see [`from_registry_url`]
+pub async fn from_registry(chain_name: &str) -> Result<ChainInfoOwned, RegistryError> {
+    from_registry_url(CHAIN_REGISTRY_URL, chain_name).await
+}
+
+/// Pulls the [`ChainInfoOwned`] of `chain_name` from the registry served at `registry_url`.
+///
+/// `chain_name` is the directory of the chain in the registry, testnets (e.g. `osmosistestnet`) are looked up in the `testnets` directory.
+/// Only the gRPC endpoints answering a request are kept. The result is cached for the lifetime of the process.
+pub async fn from_registry_url(
+    registry_url: &str,
+    chain_name: &str,
+) -> Result<ChainInfoOwned, RegistryError> {
+    let cache_key = (registry_url.to_string(), chain_name.to_string());
+    if let Some(chain_info) = REGISTRY_CACHE.lock().unwrap().get(&cache_key) {
+        return Ok(chain_info.clone());
+    }
+
+    let content = reqwest::get(chain_json_url(registry_url, chain_name))
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let mut chain_info = parse_chain_json(chain_name, &content)?;
+
+    let grpc_count = chain_info.grpc_urls.len();
+    let liveness =
+        futures::future::join_all(chain_info.grpc_urls.iter().map(|url| is_live(url))).await;
+    chain_info.grpc_urls = chain_info
+        .grpc_urls
+        .into_iter()
+        .zip(liveness)
+        .filter_map(|(url, live)| live.then_some(url))
+        .collect();
+    if chain_info.grpc_urls.is_empty() {
+        return Err(RegistryError::NoLiveEndpoint {
+            chain: chain_name.to_string(),
+            count: grpc_count,
+        });
+    }
+
+    REGISTRY_CACHE
+        .lock()
+        .unwrap()
+        .insert(cache_key, chain_info.clone());
+    Ok(chain_info)
+}
+
+/// Empties the cache of [`from_registry`], the next calls pull the registry again
+pub fn clear_registry_cache() {
+    REGISTRY_CACHE.lock().unwrap().clear();
+}
+
+/// Converts the content of a `chain.json` file into a [`ChainInfoOwned`], without checking the endpoints
+pub fn parse_chain_json(chain_name: &str, content: &str) -> Result<ChainInfoOwned, RegistryError> {
+    let chain: RegistryChain = serde_json::from_str(content)
+        .map_err(|e| RegistryError::InvalidChainData(chain_name.to_string(), e))?;
+    let fee_token = chain
+        .fees
+        .fee_tokens
+        .first()
+        .ok_or_else(|| RegistryError::MissingFeeToken(chain.chain_id.clone()))?;
+
+    Ok(ChainInfoOwned {
+        chain_id: chain.chain_id,
+        gas_denom: fee_token.denom.clone(),
+        gas_price: fee_token
+            .average_gas_price
+            .or(fee_token.low_gas_price)
+            .or(fee_token.fixed_min_gas_price)
+            .unwrap_or_default(),
+        grpc_urls: chain
+            .apis
+            .grpc
+            .into_iter()
+            .map(|endpoint| grpc_url(&endpoint.address))
+            .collect(),
+        lcd_url: chain.apis.rest.into_iter().map(|e| e.address).next(),
+        fcd_url: None,
+        rpc_url: chain.apis.rpc.into_iter().map(|e| e.address).next(),
+        network_info: NetworkInfoOwned {
+            chain_name: chain.chain_name,
+            pub_address_prefix: chain.bech32_prefix,
+            coin_type: chain.slip44,
+        },
+        kind: chain.network_type.unwrap_or_default().into(),
+    })
+}
+
+fn chain_json_url(registry_url: &str, chain_name: &str) -> String {
+    let registry_url = registry_url.trim_end_matches('/');
+    if chain_name.ends_with("testnet") {
+        format!("{registry_url}/testnets/{chain_name}/chain.json")
+    } else {
+        format!("{registry_url}/{chain_name}/chain.json")
+    }
+}
+
+/// The registry lists most gRPC endpoints without a scheme (`grpc.osmosis.zone:9090`)
+fn grpc_url(address: &str) -> String {
+    if address.contains("://") {
+        address.to_string()
+    } else if address.ends_with(":443") {
+        format!("https://{address}")
+    } else {
+        format!("http://{address}")
+    }
+}
+
+/// Any HTTP/2 answer means a server is listening, gRPC servers answer plain requests with an error status
+async fn is_live(url: &str) -> bool {
+    let builder = reqwest::Client::builder().timeout(LIVENESS_TIMEOUT);
+    let builder = if url.starts_with("http://") {
+        builder.http2_prior_knowledge()
+    } else {
+        builder
+    };
+    let Ok(client) = builder.build() else {
+        return false;
+    };
+    client.get(url).send().await.is_ok()
+}
+
+#[derive(Deserialize)]
+struct RegistryChain {
+    chain_name: String,
+    chain_id: String,
+    bech32_prefix: String,
+    slip44: u32,
+    network_type: Option<String>,
+    fees: RegistryFees,
+    #[serde(default)]
+    apis: RegistryApis,
+}
+
+#[derive(Deserialize)]
+struct RegistryFees {
+    fee_tokens: Vec<RegistryFeeToken>,
+}
+
+#[derive(Deserialize)]
+struct RegistryFeeToken {
+    denom: String,
+    fixed_min_gas_price: Option<f64>,
+    low_gas_price: Option<f64>,
+    average_gas_price: Option<f64>,
+}
+
+#[derive(Deserialize, Default)]
+struct RegistryApis {
+    #[serde(default)]
+    rpc: Vec<RegistryEndpoint>,
+    #[serde(default)]
+    rest: Vec<RegistryEndpoint>,
+    #[serde(default)]
+    grpc: Vec<RegistryEndpoint>,
+}
+
+#[derive(Deserialize)]
+struct RegistryEndpoint {
+    address: String,
+}
+
+#[cfg(test)]
+mod test {
+    use cw_orch_core::environment::{AddressAlgorithm, ChainKind};
+
+    use super::*;
+
+    const CHAIN_JSON: &str = r#"{
+        "chain_name": "evmos",
+        "chain_id": "evmos_9001-2",
+        "network_type": "mainnet",
+        "bech32_prefix": "evmos",
+        "slip44": 60,
+        "key_algos": ["ethsecp256k1"],
+        "fees": {
+            "fee_tokens": [{ "denom": "aevmos", "low_gas_price": 20000000000, "average_gas_price": 25000000000 }]
+        },
+        "apis": {
+            "rpc": [{ "address": "https://rpc.evmos.example", "provider": "example" }],
+            "grpc": [
+                { "address": "grpc.evmos.example:443" },
+                { "address": "grpc.evmos.example:9090" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn chain_json_conversion() -> anyhow::Result<()> {
+        let chain_info = parse_chain_json("evmos", CHAIN_JSON)?;
+        assert_eq!(chain_info.chain_id, "evmos_9001-2");
+        assert_eq!(chain_info.gas_denom, "aevmos");
+        assert_eq!(chain_info.gas_price, 25000000000.0);
+        assert_eq!(
+            chain_info.grpc_urls,
+            vec![
+                "https://grpc.evmos.example:443",
+                "http://grpc.evmos.example:9090"
+            ]
+        );
+        assert_eq!(chain_info.lcd_url, None);
+        assert_eq!(
+            chain_info.rpc_url.as_deref(),
+            Some("https://rpc.evmos.example")
+        );
+        assert_eq!(
+            chain_info.network_info.address_algorithm(),
+            AddressAlgorithm::Ethereum
+        );
+        assert_eq!(chain_info.kind, ChainKind::Mainnet);
+
+        assert_eq!(
+            chain_json_url(CHAIN_REGISTRY_URL, "osmosistestnet"),
+            format!("{CHAIN_REGISTRY_URL}/testnets/osmosistestnet/chain.json")
+        );
+        Ok(())
+    }
+}
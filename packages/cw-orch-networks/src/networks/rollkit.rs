@@ -16,6 +16,7 @@ pub const LOCAL_ROLLKIT: ChainInfo = ChainInfo {
     network_info: ROLLKIT_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const ROLLKIT_TESTNET: ChainInfo = ChainInfo {
@@ -27,5 +28,6 @@ pub const ROLLKIT_TESTNET: ChainInfo = ChainInfo {
     network_info: ROLLKIT_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: rollkit
@@ -16,6 +16,7 @@ pub const LOCAL_SEI: ChainInfo = ChainInfo {
     network_info: SEI_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const SEI_DEVNET_3: ChainInfo = ChainInfo {
@@ -27,6 +28,7 @@ pub const SEI_DEVNET_3: ChainInfo = ChainInfo {
     network_info: SEI_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const ATLANTIC_2: ChainInfo = ChainInfo {
@@ -38,6 +40,7 @@ pub const ATLANTIC_2: ChainInfo = ChainInfo {
     network_info: SEI_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 pub const PACIFIC_1: ChainInfo = ChainInfo {
@@ -49,5 +52,6 @@ pub const PACIFIC_1: ChainInfo = ChainInfo {
     network_info: SEI_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: sei
@@ -18,6 +18,7 @@ pub const PISCO_1: ChainInfo = ChainInfo {
     network_info: TERRA_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 /// Terra mainnet network.
@@ -31,6 +32,7 @@ pub const PHOENIX_1: ChainInfo = ChainInfo {
     network_info: TERRA_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 /// Terra local network.
@@ -44,5 +46,6 @@ pub const LOCAL_TERRA: ChainInfo = ChainInfo {
     network_info: TERRA_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 // ANCHOR_END: terra
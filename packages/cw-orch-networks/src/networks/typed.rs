@@ -0,0 +1,77 @@
+//! Networks typed with their [`ChainKind`](cw_orch_core::environment::ChainKind), see [`TypedChain`].
+//!
+//! ```rust,no_run
+//! use cw_orch_networks::networks::typed::{mainnet, Mainnet, TypedChain};
+//!
+//! // Can't be called with a testnet or local chain
+//! fn release(chain: TypedChain<Mainnet>) {
+//!     println!("Releasing on {}", chain.chain_id());
+//! }
+//!
+//! release(mainnet::JUNO_1);
+//! ```
+
+pub use cw_orch_core::environment::{Local, Mainnet, Testnet, TypedChain};
+
+/// Mainnet networks
+pub mod mainnet {
+    use super::{Mainnet, TypedChain};
+
+    pub const ARCHWAY_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::archway::ARCHWAY_1);
+    pub const BITSONG_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::bitsong::BITSONG_1);
+    pub const VOTA_ASH: TypedChain<Mainnet> = TypedChain::new(crate::networks::doravota::VOTA_ASH);
+    pub const INJECTIVE_1: TypedChain<Mainnet> =
+        TypedChain::new(crate::networks::injective::INJECTIVE_1);
+    pub const JUNO_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::juno::JUNO_1);
+    pub const MIGALOO_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::migaloo::MIGALOO_1);
+    pub const NEUTRON_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::neutron::NEUTRON_1);
+    pub const OSMOSIS_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::osmosis::OSMOSIS_1);
+    pub const PACIFIC_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::sei::PACIFIC_1);
+    pub const PHOENIX_1: TypedChain<Mainnet> = TypedChain::new(crate::networks::terra::PHOENIX_1);
+}
+
+/// Testnet networks
+pub mod testnet {
+    use super::{Testnet, TypedChain};
+
+    pub const CONSTANTINE_3: TypedChain<Testnet> =
+        TypedChain::new(crate::networks::archway::CONSTANTINE_3);
+    pub const BOBNET: TypedChain<Testnet> = TypedChain::new(crate::networks::bitsong::BOBNET);
+    pub const VOTA_TESTNET: TypedChain<Testnet> =
+        TypedChain::new(crate::networks::doravota::VOTA_TESTNET);
+    pub const INJECTIVE_888: TypedChain<Testnet> =
+        TypedChain::new(crate::networks::injective::INJECTIVE_888);
+    pub const UNI_6: TypedChain<Testnet> = TypedChain::new(crate::networks::juno::UNI_6);
+    pub const HARPOON_4: TypedChain<Testnet> = TypedChain::new(crate::networks::kujira::HARPOON_4);
+    pub const NARWHAL_1: TypedChain<Testnet> = TypedChain::new(crate::networks::migaloo::NARWHAL_1);
+    pub const PION_1: TypedChain<Testnet> = TypedChain::new(crate::networks::neutron::PION_1);
+    pub const NIBIRU_ITN_2: TypedChain<Testnet> =
+        TypedChain::new(crate::networks::nibiru::NIBIRU_ITN_2);
+    pub const OSMO_5: TypedChain<Testnet> = TypedChain::new(crate::networks::osmosis::OSMO_5);
+    pub const SEI_DEVNET_3: TypedChain<Testnet> =
+        TypedChain::new(crate::networks::sei::SEI_DEVNET_3);
+    pub const ATLANTIC_2: TypedChain<Testnet> = TypedChain::new(crate::networks::sei::ATLANTIC_2);
+    pub const PISCO_1: TypedChain<Testnet> = TypedChain::new(crate::networks::terra::PISCO_1);
+    pub const XION_TESTNET_1: TypedChain<Testnet> =
+        TypedChain::new(crate::networks::xion::XION_TESTNET_1);
+}
+
+/// Local networks
+pub mod local {
+    use super::{Local, TypedChain};
+
+    pub const LOCAL_BITSONG: TypedChain<Local> =
+        TypedChain::new(crate::networks::bitsong::LOCAL_BITSONG);
+    pub const LOCAL_JUNO: TypedChain<Local> = TypedChain::new(crate::networks::juno::LOCAL_JUNO);
+    pub const LOCAL_MIGALOO: TypedChain<Local> =
+        TypedChain::new(crate::networks::migaloo::LOCAL_MIGALOO);
+    pub const LOCAL_NEUTRON: TypedChain<Local> =
+        TypedChain::new(crate::networks::neutron::LOCAL_NEUTRON);
+    pub const LOCAL_OSMO: TypedChain<Local> = TypedChain::new(crate::networks::osmosis::LOCAL_OSMO);
+    pub const LOCAL_ROLLKIT: TypedChain<Local> =
+        TypedChain::new(crate::networks::rollkit::LOCAL_ROLLKIT);
+    pub const ROLLKIT_TESTNET: TypedChain<Local> =
+        TypedChain::new(crate::networks::rollkit::ROLLKIT_TESTNET);
+    pub const LOCAL_SEI: TypedChain<Local> = TypedChain::new(crate::networks::sei::LOCAL_SEI);
+    pub const LOCAL_TERRA: TypedChain<Local> = TypedChain::new(crate::networks::terra::LOCAL_TERRA);
+}
@@ -16,6 +16,7 @@ pub const XION_TESTNET_1: ChainInfo = ChainInfo {
     network_info: XION_NETWORK,
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
 };
 
 // ANCHOR_END: xion
@@ -2,12 +2,14 @@ use cosmwasm_std::{coin, Addr, Coins};
 
 use cw_orch_core::contract::interface_traits::Uploadable;
 use cw_orch_core::contract::WasmPath;
-use cw_orch_core::environment::{BankQuerier, BankSetter, ChainInfo, DefaultQueriers, NetworkInfo};
+use cw_orch_core::environment::{
+    missing_funds, BankQuerier, BankSetter, ChainInfo, DefaultQueriers, FundsManager, NetworkInfo,
+};
 
 use cosmwasm_std::{Binary, Coin, Uint128};
 use cw_orch_core::CwEnvError;
 use cw_orch_mock::cw_multi_test::AppResponse;
-use cw_orch_traits::Stargate;
+use cw_orch_traits::{Stargate, StargateQuerier};
 use osmosis_test_tube::{
     Account, Bank, ExecuteResponse, Gamm, Module, Runner, RunnerError, SigningAccount, Wasm,
 };
@@ -24,8 +26,8 @@ use std::{cell::RefCell, fmt::Debug, rc::Rc};
 use serde::Serialize;
 
 use cw_orch_core::{
-    environment::TxHandler,
     environment::{ChainState, StateInterface},
+    environment::{SudoHandler, TxHandler},
 };
 
 use cw_orch_mock::MockState;
@@ -42,6 +44,7 @@ pub const MOCK_CHAIN_INFO: ChainInfo = ChainInfo {
     grpc_urls: &[],
     lcd_url: None,
     fcd_url: None,
+    rpc_url: None,
     network_info: NetworkInfo {
         chain_name: "osmosis",
         pub_address_prefix: "osmo",
@@ -153,6 +156,46 @@ impl<S: StateInterface> OsmosisTestTube<S> {
         Ok(pool_id)
     }
 
+    /// Executes a protobuf message of any module of the chain, signed by the sender.
+    /// This allows using modules that don't have a helper yet (e.g. concentrated liquidity or interchain queries),
+    /// `R` is the protobuf response of the message.
+    pub fn execute_proto<M, R>(&self, msg: M) -> Result<ExecuteResponse<R>, CwEnvError>
+    where
+        M: prost::Message + prost::Name,
+        R: prost::Message + Default,
+    {
+        self.execute_proto_as(msg, &self.sender)
+    }
+
+    /// Executes a protobuf message of any module of the chain, signed by `signer`
+    pub fn execute_proto_as<M, R>(
+        &self,
+        msg: M,
+        signer: &SigningAccount,
+    ) -> Result<ExecuteResponse<R>, CwEnvError>
+    where
+        M: prost::Message + prost::Name,
+        R: prost::Message + Default,
+    {
+        let any = prost_types::Any {
+            type_url: M::type_url(),
+            value: msg.encode_to_vec(),
+        };
+        self.app
+            .borrow()
+            .execute_multiple_raw(vec![any], signer)
+            .map_err(map_err)
+    }
+
+    /// Queries any module of the chain, `path` is the gRPC path of the query (e.g. `/cosmos.bank.v1beta1.Query/Balance`)
+    pub fn query_proto<Req, Res>(&self, path: &str, request: &Req) -> Result<Res, CwEnvError>
+    where
+        Req: prost::Message,
+        Res: prost::Message + Default,
+    {
+        self.app.borrow().query(path, request).map_err(map_err)
+    }
+
     /// Query the (bank) balance of a native token for and address.
     /// Returns the amount of the native token.
     pub fn query_balance(&self, address: &str, denom: &str) -> Result<Uint128, CwEnvError> {
@@ -220,9 +263,17 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
     }
 
     fn upload<T: Uploadable>(&self, _contract: &T) -> Result<Self::Response, CwEnvError> {
-        let wasm_contents = std::fs::read(<T as Uploadable>::wasm(&MOCK_CHAIN_INFO.into()).path())?;
+        let chain_info = MOCK_CHAIN_INFO.into();
+        let wasm_contents = match <T as Uploadable>::wasm_bytes(&chain_info) {
+            Some(code) => code,
+            None => std::fs::read(<T as Uploadable>::wasm(&chain_info).path())?,
+        };
+        self.upload_from_bytes(wasm_contents)
+    }
+
+    fn upload_from_bytes(&self, code: Vec<u8>) -> Result<Self::Response, CwEnvError> {
         let upload_response = Wasm::new(&*self.app.borrow())
-            .store_code(&wasm_contents, None, &self.sender)
+            .store_code(&code, None, &self.sender)
             .map_err(map_err)?;
 
         Ok(AppResponse {
@@ -294,19 +345,39 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
     }
 }
 
+impl<S: StateInterface> SudoHandler for OsmosisTestTube<S> {
+    fn sudo<M: Serialize + Debug>(
+        &self,
+        sudo_msg: &M,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, CwEnvError> {
+        let data = self
+            .app
+            .borrow()
+            .wasm_sudo(contract_address.as_str(), sudo_msg)
+            .map_err(map_err)?;
+
+        // The sudo call doesn't go through a transaction, only its data is returned
+        Ok(AppResponse {
+            data: Some(Binary(data)),
+            events: vec![],
+        })
+    }
+}
+
 /// Gas Fee token for OmosisTestTube, used in BankSetter
 pub const GAS_TOKEN: &str = "uosmo";
 
 impl BankSetter for OsmosisTestTube {
     type T = OsmosisTestTubeBankQuerier;
 
-    /// It's impossible to set the balance of an address directly in OsmosisTestTube
+    /// The balance of an address can only be increased in OsmosisTestTube, see [`FundsManager::set_balance`]
     fn set_balance(
         &mut self,
-        _address: impl Into<String>,
-        _amount: Vec<Coin>,
+        address: impl Into<String>,
+        amount: Vec<Coin>,
     ) -> Result<(), <Self as TxHandler>::Error> {
-        unimplemented!();
+        FundsManager::set_balance(self, address, amount)
     }
 
     fn add_balance(
@@ -314,18 +385,40 @@ impl BankSetter for OsmosisTestTube {
         address: impl Into<String>,
         amount: Vec<Coin>,
     ) -> Result<(), <Self as TxHandler>::Error> {
-        let mut all_coins: Coins = amount.clone().try_into().unwrap();
+        FundsManager::add_balance(self, address, amount)
+    }
+}
+
+/// The balances are changed by sending funds from new accounts, they can only be increased
+impl<S: StateInterface> FundsManager for OsmosisTestTube<S> {
+    type Error = CwEnvError;
+
+    fn set_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        let address = address.into();
+        let current = self.bank_querier().balance(address.clone(), None)?;
+        let missing = missing_funds(&current, &amount)?;
+        if missing.is_empty() {
+            return Ok(());
+        }
+        FundsManager::add_balance(self, address, missing)
+    }
+
+    fn add_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        let mut all_coins: Coins = amount.clone().try_into()?;
         let gas_balance = coin(100_000_000_000_000, GAS_TOKEN);
-        all_coins.add(gas_balance).unwrap();
+        all_coins.add(gas_balance)?;
 
-        let new_account = self.init_account(all_coins.into())?;
+        let new_account = self
+            .app
+            .borrow()
+            .init_account(&all_coins.into_vec())
+            .map_err(map_err)
+            .map(Rc::new)?;
 
         self.call_as(&new_account)
             .bank_send(address.into(), amount)?;
 
         Ok(())
-
-        // We check the current balance
     }
 }
 
@@ -348,6 +441,16 @@ impl Stargate for OsmosisTestTube {
     }
 }
 
+impl<S: StateInterface> StargateQuerier for OsmosisTestTube<S> {
+    fn raw_module_query<Req, Res>(&self, path: &str, request: &Req) -> Result<Res, CwEnvError>
+    where
+        Req: prost::Message + Clone + Send + Sync + 'static,
+        Res: prost::Message + Default + Send + Sync + 'static,
+    {
+        self.query_proto(path, request)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use cosmwasm_std::{coin, coins, ContractInfoResponse};
@@ -388,6 +491,18 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn upload_from_bytes_works() -> cw_orch::anyhow::Result<()> {
+        let app = OsmosisTestTube::new(coins(100_000_000_000_000, "uosmo"));
+
+        let wasm = CounterContract::<Mock>::wasm(&MOCK_CHAIN_INFO.into());
+        let code = std::fs::read(wasm.path())?;
+        let code_id = app.upload_from_bytes(code)?.uploaded_code_id()?;
+
+        assert_eq!(wasm.checksum()?, app.wasm_querier().code_id_hash(code_id)?);
+        Ok(())
+    }
+
     #[test]
     fn bank_querier_works() -> cw_orch::anyhow::Result<()> {
         let denom = "urandom";
@@ -406,6 +521,111 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn raw_module_query_works() -> cw_orch::anyhow::Result<()> {
+        use cw_orch_traits::StargateQuerier;
+        use osmosis_test_tube::osmosis_std::types::cosmos::bank::v1beta1::{
+            QueryBalanceRequest, QueryBalanceResponse,
+        };
+
+        let denom = "urandom";
+        let app = OsmosisTestTube::new(coins(45, denom));
+        let response: QueryBalanceResponse = app.raw_module_query(
+            "/cosmos.bank.v1beta1.Query/Balance",
+            &QueryBalanceRequest {
+                address: app.sender.address(),
+                denom: denom.to_string(),
+            },
+        )?;
+        assert_eq!(response.balance.unwrap().amount, "45");
+        Ok(())
+    }
+
+    #[test]
+    fn proto_messages_work() -> cw_orch::anyhow::Result<()> {
+        use osmosis_test_tube::osmosis_std::types::cosmos::{
+            bank::v1beta1::{QueryBalanceRequest, QueryBalanceResponse},
+            base::v1beta1::Coin as ProtoCoin,
+        };
+
+        // Message of a module without a typed helper
+        #[derive(Clone, PartialEq, prost::Message)]
+        struct MsgSend {
+            #[prost(string, tag = "1")]
+            from_address: String,
+            #[prost(string, tag = "2")]
+            to_address: String,
+            #[prost(message, repeated, tag = "3")]
+            amount: Vec<ProtoCoin>,
+        }
+        impl prost::Name for MsgSend {
+            const NAME: &'static str = "MsgSend";
+            const PACKAGE: &'static str = "cosmos.bank.v1beta1";
+        }
+        #[derive(Clone, PartialEq, prost::Message)]
+        struct MsgSendResponse {}
+
+        let denom = "urandom";
+        let mut app = OsmosisTestTube::new(vec![coin(100, denom), coin(1_000_000_000, GAS_TOKEN)]);
+        let recipient = app.init_account(vec![])?;
+
+        app.execute_proto::<_, MsgSendResponse>(MsgSend {
+            from_address: app.sender.address(),
+            to_address: recipient.address(),
+            amount: vec![ProtoCoin {
+                denom: denom.to_string(),
+                amount: "40".to_string(),
+            }],
+        })?;
+
+        let response: QueryBalanceResponse = app.query_proto(
+            "/cosmos.bank.v1beta1.Query/Balance",
+            &QueryBalanceRequest {
+                address: recipient.address(),
+                denom: denom.to_string(),
+            },
+        )?;
+        assert_eq!(response.balance.unwrap().amount, "40");
+        Ok(())
+    }
+
+    #[test]
+    fn staking_queriers_work() -> cw_orch::anyhow::Result<()> {
+        use osmosis_test_tube::osmosis_std::types::cosmos::{
+            base::v1beta1::Coin as ProtoCoin,
+            staking::v1beta1::{MsgDelegate, MsgDelegateResponse},
+        };
+        use osmosis_test_tube::Runner;
+
+        let app = OsmosisTestTube::new(coins(100_000_000_000_000, GAS_TOKEN));
+        let validator = app.staking_querier().validators(None)?.remove(0);
+
+        let _: osmosis_test_tube::ExecuteResponse<MsgDelegateResponse> = app.app.borrow().execute(
+            MsgDelegate {
+                delegator_address: app.sender.address(),
+                validator_address: validator.operator_address.clone(),
+                amount: Some(ProtoCoin {
+                    denom: GAS_TOKEN.to_string(),
+                    amount: "1000000".to_string(),
+                }),
+            },
+            MsgDelegate::TYPE_URL,
+            &app.sender,
+        )?;
+
+        let delegations = app
+            .staking_querier()
+            .delegator_delegations(app.sender.address())?;
+        assert_eq!(delegations.len(), 1);
+        assert_eq!(
+            app.distribution_querier()
+                .delegator_validators(app.sender.address())?,
+            vec![validator.operator_address]
+        );
+        app.gov_querier().params("voting")?;
+        Ok(())
+    }
+
     #[test]
     fn add_balance_works() -> cw_orch::anyhow::Result<()> {
         let denom = "uosmo";
@@ -433,4 +653,20 @@ pub mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn set_balance_only_increases() -> cw_orch::anyhow::Result<()> {
+        use cw_orch::environment::FundsManager;
+
+        let mut app = OsmosisTestTube::new(coins(100_000_000_000_000, "uosmo"));
+        let account = app.init_account(coins(78, "uweird"))?;
+
+        FundsManager::set_balance(&app, account.address(), coins(100, "uweird"))?;
+        assert_eq!(
+            app.bank_querier().balance(account.address(), None)?,
+            coins(100, "uweird")
+        );
+        assert!(FundsManager::set_balance(&app, account.address(), coins(10, "uweird")).is_err());
+        Ok(())
+    }
 }
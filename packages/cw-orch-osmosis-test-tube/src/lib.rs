@@ -4,3 +4,7 @@ mod core;
 
 mod queriers;
 pub use self::core::*;
+pub use queriers::{
+    distribution::OsmosisTestTubeDistributionQuerier, gov::OsmosisTestTubeGovQuerier,
+    staking::OsmosisTestTubeStakingQuerier,
+};
@@ -0,0 +1,163 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cw_orch_core::environment::{Querier, QuerierGetter, StateInterface};
+use cw_orch_core::CwEnvError;
+use osmosis_test_tube::osmosis_std::types::cosmos::{
+    base::v1beta1::DecCoin,
+    distribution::v1beta1::{
+        Params, QueryCommunityPoolRequest, QueryCommunityPoolResponse,
+        QueryDelegationRewardsRequest, QueryDelegationRewardsResponse,
+        QueryDelegationTotalRewardsRequest, QueryDelegationTotalRewardsResponse,
+        QueryDelegatorValidatorsRequest, QueryDelegatorValidatorsResponse,
+        QueryDelegatorWithdrawAddressRequest, QueryDelegatorWithdrawAddressResponse,
+        QueryParamsRequest, QueryParamsResponse, QueryValidatorCommissionRequest,
+        QueryValidatorCommissionResponse, QueryValidatorOutstandingRewardsRequest,
+        QueryValidatorOutstandingRewardsResponse,
+    },
+};
+use osmosis_test_tube::OsmosisTestApp;
+
+use super::{missing, query};
+use crate::OsmosisTestTube;
+
+/// Querier for the Cosmos Distribution module
+pub struct OsmosisTestTubeDistributionQuerier {
+    app: Rc<RefCell<OsmosisTestApp>>,
+}
+
+impl OsmosisTestTubeDistributionQuerier {
+    fn new<S: StateInterface>(mock: &OsmosisTestTube<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl Querier for OsmosisTestTubeDistributionQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<OsmosisTestTubeDistributionQuerier> for OsmosisTestTube<S> {
+    fn querier(&self) -> OsmosisTestTubeDistributionQuerier {
+        OsmosisTestTubeDistributionQuerier::new(self)
+    }
+}
+
+impl OsmosisTestTubeDistributionQuerier {
+    /// Queries the rewards accrued by a delegation
+    pub fn delegation_rewards(
+        &self,
+        delegator_address: impl Into<String>,
+        validator_address: impl Into<String>,
+    ) -> Result<Vec<DecCoin>, CwEnvError> {
+        let response: QueryDelegationRewardsResponse = query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/DelegationRewards",
+            &QueryDelegationRewardsRequest {
+                delegator_address: delegator_address.into(),
+                validator_address: validator_address.into(),
+            },
+        )?;
+        Ok(response.rewards)
+    }
+
+    /// Queries the rewards accrued by all the delegations of a delegator
+    pub fn delegation_total_rewards(
+        &self,
+        delegator_address: impl Into<String>,
+    ) -> Result<QueryDelegationTotalRewardsResponse, CwEnvError> {
+        query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards",
+            &QueryDelegationTotalRewardsRequest {
+                delegator_address: delegator_address.into(),
+            },
+        )
+    }
+
+    /// Queries the validators a delegator is delegating to
+    pub fn delegator_validators(
+        &self,
+        delegator_address: impl Into<String>,
+    ) -> Result<Vec<String>, CwEnvError> {
+        let response: QueryDelegatorValidatorsResponse = query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/DelegatorValidators",
+            &QueryDelegatorValidatorsRequest {
+                delegator_address: delegator_address.into(),
+            },
+        )?;
+        Ok(response.validators)
+    }
+
+    /// Queries the address the rewards of a delegator are withdrawn to
+    pub fn delegator_withdraw_address(
+        &self,
+        delegator_address: impl Into<String>,
+    ) -> Result<String, CwEnvError> {
+        let response: QueryDelegatorWithdrawAddressResponse = query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/DelegatorWithdrawAddress",
+            &QueryDelegatorWithdrawAddressRequest {
+                delegator_address: delegator_address.into(),
+            },
+        )?;
+        Ok(response.withdraw_address)
+    }
+
+    /// Queries the outstanding rewards of a validator
+    pub fn validator_outstanding_rewards(
+        &self,
+        validator_address: impl Into<String>,
+    ) -> Result<Vec<DecCoin>, CwEnvError> {
+        let response: QueryValidatorOutstandingRewardsResponse = query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/ValidatorOutstandingRewards",
+            &QueryValidatorOutstandingRewardsRequest {
+                validator_address: validator_address.into(),
+            },
+        )?;
+        Ok(response
+            .rewards
+            .map(|rewards| rewards.rewards)
+            .unwrap_or_default())
+    }
+
+    /// Queries the accumulated commission of a validator
+    pub fn validator_commission(
+        &self,
+        validator_address: impl Into<String>,
+    ) -> Result<Vec<DecCoin>, CwEnvError> {
+        let response: QueryValidatorCommissionResponse = query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/ValidatorCommission",
+            &QueryValidatorCommissionRequest {
+                validator_address: validator_address.into(),
+            },
+        )?;
+        Ok(response
+            .commission
+            .map(|commission| commission.commission)
+            .unwrap_or_default())
+    }
+
+    /// Queries the coins of the community pool
+    pub fn community_pool(&self) -> Result<Vec<DecCoin>, CwEnvError> {
+        let response: QueryCommunityPoolResponse = query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/CommunityPool",
+            &QueryCommunityPoolRequest {},
+        )?;
+        Ok(response.pool)
+    }
+
+    /// Queries the distribution parameters
+    pub fn params(&self) -> Result<Params, CwEnvError> {
+        let response: QueryParamsResponse = query(
+            &self.app,
+            "/cosmos.distribution.v1beta1.Query/Params",
+            &QueryParamsRequest {},
+        )?;
+        response.params.ok_or_else(|| missing("params"))
+    }
+}
@@ -0,0 +1,150 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cw_orch_core::environment::{Querier, QuerierGetter, StateInterface};
+use cw_orch_core::CwEnvError;
+use osmosis_test_tube::osmosis_std::types::cosmos::gov::v1beta1::{
+    Deposit, Proposal, QueryDepositRequest, QueryDepositResponse, QueryDepositsRequest,
+    QueryDepositsResponse, QueryParamsRequest, QueryParamsResponse, QueryProposalRequest,
+    QueryProposalResponse, QueryProposalsRequest, QueryProposalsResponse, QueryTallyResultRequest,
+    QueryTallyResultResponse, QueryVoteRequest, QueryVoteResponse, QueryVotesRequest,
+    QueryVotesResponse, TallyResult, Vote,
+};
+use osmosis_test_tube::OsmosisTestApp;
+
+use super::{missing, query};
+use crate::OsmosisTestTube;
+
+/// Querier for the Cosmos Gov module
+pub struct OsmosisTestTubeGovQuerier {
+    app: Rc<RefCell<OsmosisTestApp>>,
+}
+
+impl OsmosisTestTubeGovQuerier {
+    fn new<S: StateInterface>(mock: &OsmosisTestTube<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl Querier for OsmosisTestTubeGovQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<OsmosisTestTubeGovQuerier> for OsmosisTestTube<S> {
+    fn querier(&self) -> OsmosisTestTubeGovQuerier {
+        OsmosisTestTubeGovQuerier::new(self)
+    }
+}
+
+impl OsmosisTestTubeGovQuerier {
+    /// Queries a proposal by id
+    pub fn proposal(&self, proposal_id: u64) -> Result<Proposal, CwEnvError> {
+        let response: QueryProposalResponse = query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/Proposal",
+            &QueryProposalRequest { proposal_id },
+        )?;
+        response.proposal.ok_or_else(|| missing("proposal"))
+    }
+
+    /// Queries the proposals with the given status (`0` for all), optionally filtered by voter and depositor
+    pub fn proposals(
+        &self,
+        proposal_status: i32,
+        voter: impl Into<String>,
+        depositor: impl Into<String>,
+    ) -> Result<Vec<Proposal>, CwEnvError> {
+        let response: QueryProposalsResponse = query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/Proposals",
+            &QueryProposalsRequest {
+                proposal_status,
+                voter: voter.into(),
+                depositor: depositor.into(),
+                ..Default::default()
+            },
+        )?;
+        Ok(response.proposals)
+    }
+
+    /// Queries the vote of a voter on a proposal
+    pub fn vote(&self, proposal_id: u64, voter: impl Into<String>) -> Result<Vote, CwEnvError> {
+        let response: QueryVoteResponse = query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/Vote",
+            &QueryVoteRequest {
+                proposal_id,
+                voter: voter.into(),
+            },
+        )?;
+        response.vote.ok_or_else(|| missing("vote"))
+    }
+
+    /// Queries the votes on a proposal
+    pub fn votes(&self, proposal_id: u64) -> Result<Vec<Vote>, CwEnvError> {
+        let response: QueryVotesResponse = query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/Votes",
+            &QueryVotesRequest {
+                proposal_id,
+                ..Default::default()
+            },
+        )?;
+        Ok(response.votes)
+    }
+
+    /// Queries the deposit of a depositor on a proposal
+    pub fn deposit(
+        &self,
+        proposal_id: u64,
+        depositor: impl Into<String>,
+    ) -> Result<Deposit, CwEnvError> {
+        let response: QueryDepositResponse = query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/Deposit",
+            &QueryDepositRequest {
+                proposal_id,
+                depositor: depositor.into(),
+            },
+        )?;
+        response.deposit.ok_or_else(|| missing("deposit"))
+    }
+
+    /// Queries the deposits on a proposal
+    pub fn deposits(&self, proposal_id: u64) -> Result<Vec<Deposit>, CwEnvError> {
+        let response: QueryDepositsResponse = query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/Deposits",
+            &QueryDepositsRequest {
+                proposal_id,
+                ..Default::default()
+            },
+        )?;
+        Ok(response.deposits)
+    }
+
+    /// Queries the tally of a proposal
+    pub fn tally_result(&self, proposal_id: u64) -> Result<TallyResult, CwEnvError> {
+        let response: QueryTallyResultResponse = query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/TallyResult",
+            &QueryTallyResultRequest { proposal_id },
+        )?;
+        response.tally.ok_or_else(|| missing("tally"))
+    }
+
+    /// Queries the gov parameters of the given type: `voting`, `deposit` or `tallying`
+    pub fn params(
+        &self,
+        params_type: impl Into<String>,
+    ) -> Result<QueryParamsResponse, CwEnvError> {
+        query(
+            &self.app,
+            "/cosmos.gov.v1beta1.Query/Params",
+            &QueryParamsRequest {
+                params_type: params_type.into(),
+            },
+        )
+    }
+}
@@ -1,15 +1,39 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::Timestamp;
 use cw_orch_core::{
-    environment::{DefaultQueriers, QueryHandler, StateInterface},
+    environment::{ChainTime, DefaultQueriers, QuerierGetter, QueryHandler, StateInterface},
     CwEnvError,
 };
+use osmosis_test_tube::{OsmosisTestApp, Runner};
 
-use super::OsmosisTestTube;
+use super::{map_err, OsmosisTestTube};
 
 pub mod bank;
+pub mod distribution;
 mod env;
+pub mod gov;
 pub mod node;
+pub mod staking;
 pub mod wasm;
 
+/// Sends a gRPC query to a module of the app
+fn query<Req, Res>(
+    app: &Rc<RefCell<OsmosisTestApp>>,
+    path: &str,
+    request: &Req,
+) -> Result<Res, CwEnvError>
+where
+    Req: prost::Message,
+    Res: prost::Message + Default,
+{
+    app.borrow().query(path, request).map_err(map_err)
+}
+
+fn missing(field: &str) -> CwEnvError {
+    CwEnvError::StdErr(format!("Missing {field} in the query response"))
+}
+
 impl<S: StateInterface> QueryHandler for OsmosisTestTube<S> {
     type Error = CwEnvError;
 
@@ -27,8 +51,50 @@ impl<S: StateInterface> QueryHandler for OsmosisTestTube<S> {
     }
 }
 
+impl<S: StateInterface> ChainTime for OsmosisTestTube<S> {
+    type Error = CwEnvError;
+
+    fn advance_blocks(&self, blocks: u64) -> Result<(), CwEnvError> {
+        self.wait_blocks(blocks)
+    }
+
+    fn advance_seconds(&self, seconds: u64) -> Result<(), CwEnvError> {
+        self.wait_seconds(seconds)
+    }
+
+    fn set_block_time(&self, time: Timestamp) -> Result<(), CwEnvError> {
+        let now = self.app.borrow().get_block_time_nanos() as u64;
+        if time.nanos() < now {
+            return Err(CwEnvError::TimeTravelUnsupported(format!(
+                "osmosis test tube can't go back in time, from {} to {}",
+                Timestamp::from_nanos(now),
+                time
+            )));
+        }
+        // Time only moves by whole seconds
+        self.wait_seconds((time.nanos() - now).div_ceil(1_000_000_000))
+    }
+}
+
 impl<S: StateInterface> DefaultQueriers for OsmosisTestTube<S> {
     type Bank = bank::OsmosisTestTubeBankQuerier;
     type Wasm = wasm::OsmosisTestTubeWasmQuerier<S>;
     type Node = node::OsmosisTestTubeNodeQuerier;
 }
+
+impl<S: StateInterface> OsmosisTestTube<S> {
+    /// Querier for the staking module (validators, delegations, unbondings)
+    pub fn staking_querier(&self) -> staking::OsmosisTestTubeStakingQuerier {
+        self.querier()
+    }
+
+    /// Querier for the distribution module (rewards, commissions, community pool)
+    pub fn distribution_querier(&self) -> distribution::OsmosisTestTubeDistributionQuerier {
+        self.querier()
+    }
+
+    /// Querier for the gov module (proposals, votes, deposits)
+    pub fn gov_querier(&self) -> gov::OsmosisTestTubeGovQuerier {
+        self.querier()
+    }
+}
@@ -0,0 +1,157 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cw_orch_core::environment::{Querier, QuerierGetter, StateInterface};
+use cw_orch_core::CwEnvError;
+use osmosis_test_tube::osmosis_std::types::cosmos::staking::v1beta1::{
+    DelegationResponse, Params, Pool, QueryDelegationRequest, QueryDelegationResponse,
+    QueryDelegatorDelegationsRequest, QueryDelegatorDelegationsResponse,
+    QueryDelegatorUnbondingDelegationsRequest, QueryDelegatorUnbondingDelegationsResponse,
+    QueryParamsRequest, QueryParamsResponse, QueryPoolRequest, QueryPoolResponse,
+    QueryUnbondingDelegationRequest, QueryUnbondingDelegationResponse, QueryValidatorRequest,
+    QueryValidatorResponse, QueryValidatorsRequest, QueryValidatorsResponse, UnbondingDelegation,
+    Validator,
+};
+use osmosis_test_tube::OsmosisTestApp;
+
+use super::{missing, query};
+use crate::OsmosisTestTube;
+
+/// Querier for the Cosmos Staking module
+pub struct OsmosisTestTubeStakingQuerier {
+    app: Rc<RefCell<OsmosisTestApp>>,
+}
+
+impl OsmosisTestTubeStakingQuerier {
+    fn new<S: StateInterface>(mock: &OsmosisTestTube<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl Querier for OsmosisTestTubeStakingQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<OsmosisTestTubeStakingQuerier> for OsmosisTestTube<S> {
+    fn querier(&self) -> OsmosisTestTubeStakingQuerier {
+        OsmosisTestTubeStakingQuerier::new(self)
+    }
+}
+
+impl OsmosisTestTubeStakingQuerier {
+    /// Queries validator info for given validator address
+    pub fn validator(&self, validator_addr: impl Into<String>) -> Result<Validator, CwEnvError> {
+        let response: QueryValidatorResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/Validator",
+            &QueryValidatorRequest {
+                validator_addr: validator_addr.into(),
+            },
+        )?;
+        response.validator.ok_or_else(|| missing("validator"))
+    }
+
+    /// Queries all validators that match the given status (e.g. `BOND_STATUS_BONDED`), all validators if `None`
+    pub fn validators(&self, status: Option<String>) -> Result<Vec<Validator>, CwEnvError> {
+        let response: QueryValidatorsResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/Validators",
+            &QueryValidatorsRequest {
+                status: status.unwrap_or_default(),
+                ..Default::default()
+            },
+        )?;
+        Ok(response.validators)
+    }
+
+    /// Queries the delegation of a delegator to a validator
+    pub fn delegation(
+        &self,
+        delegator_addr: impl Into<String>,
+        validator_addr: impl Into<String>,
+    ) -> Result<DelegationResponse, CwEnvError> {
+        let response: QueryDelegationResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/Delegation",
+            &QueryDelegationRequest {
+                delegator_addr: delegator_addr.into(),
+                validator_addr: validator_addr.into(),
+            },
+        )?;
+        response
+            .delegation_response
+            .ok_or_else(|| missing("delegation"))
+    }
+
+    /// Queries all the delegations of a delegator
+    pub fn delegator_delegations(
+        &self,
+        delegator_addr: impl Into<String>,
+    ) -> Result<Vec<DelegationResponse>, CwEnvError> {
+        let response: QueryDelegatorDelegationsResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/DelegatorDelegations",
+            &QueryDelegatorDelegationsRequest {
+                delegator_addr: delegator_addr.into(),
+                ..Default::default()
+            },
+        )?;
+        Ok(response.delegation_responses)
+    }
+
+    /// Queries the unbonding delegation of a delegator from a validator
+    pub fn unbonding_delegation(
+        &self,
+        delegator_addr: impl Into<String>,
+        validator_addr: impl Into<String>,
+    ) -> Result<UnbondingDelegation, CwEnvError> {
+        let response: QueryUnbondingDelegationResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/UnbondingDelegation",
+            &QueryUnbondingDelegationRequest {
+                delegator_addr: delegator_addr.into(),
+                validator_addr: validator_addr.into(),
+            },
+        )?;
+        response
+            .unbond
+            .ok_or_else(|| missing("unbonding delegation"))
+    }
+
+    /// Queries all the unbonding delegations of a delegator
+    pub fn delegator_unbonding_delegations(
+        &self,
+        delegator_addr: impl Into<String>,
+    ) -> Result<Vec<UnbondingDelegation>, CwEnvError> {
+        let response: QueryDelegatorUnbondingDelegationsResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/DelegatorUnbondingDelegations",
+            &QueryDelegatorUnbondingDelegationsRequest {
+                delegator_addr: delegator_addr.into(),
+                ..Default::default()
+            },
+        )?;
+        Ok(response.unbonding_responses)
+    }
+
+    /// Queries the bonded and unbonded tokens of the chain
+    pub fn pool(&self) -> Result<Pool, CwEnvError> {
+        let response: QueryPoolResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/Pool",
+            &QueryPoolRequest {},
+        )?;
+        response.pool.ok_or_else(|| missing("pool"))
+    }
+
+    /// Queries the staking parameters
+    pub fn params(&self) -> Result<Params, CwEnvError> {
+        let response: QueryParamsResponse = query(
+            &self.app,
+            "/cosmos.staking.v1beta1.Query/Params",
+            &QueryParamsRequest {},
+        )?;
+        response.params.ok_or_else(|| missing("params"))
+    }
+}
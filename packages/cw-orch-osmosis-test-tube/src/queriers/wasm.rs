@@ -11,6 +11,7 @@ use cw_orch_core::{
 };
 use osmosis_test_tube::cosmrs::AccountId;
 use osmosis_test_tube::{OsmosisTestApp, Runner};
+use sha2::{Digest, Sha256};
 
 use crate::{map_err, OsmosisTestTube, MOCK_CHAIN_INFO};
 use osmosis_test_tube::osmosis_std::types::cosmwasm::wasm::v1::{
@@ -182,6 +183,11 @@ impl<S: StateInterface> WasmQuerier for OsmosisTestTubeWasmQuerier<S> {
         &self,
         _contract: &T,
     ) -> Result<HexBinary, CwEnvError> {
-        <T as Uploadable>::wasm(&MOCK_CHAIN_INFO.into()).checksum()
+        let chain_info = MOCK_CHAIN_INFO.into();
+        if let Some(code) = <T as Uploadable>::wasm_bytes(&chain_info) {
+            let checksum: [u8; 32] = Sha256::digest(code).into();
+            return Ok(checksum.into());
+        }
+        <T as Uploadable>::wasm(&chain_info).checksum()
     }
 }
@@ -0,0 +1,32 @@
+use cosmwasm_std::{coins, Addr};
+use counter_contract::{msg::InstantiateMsg, CounterContract, CounterExecuteMsgFns};
+use cw_orch::{
+    any_env::{AnyCwEnv, AnySender, AnyTxResponse},
+    prelude::*,
+};
+use cw_orch_osmosis_test_tube::OsmosisTestTube;
+use osmosis_test_tube::Account;
+
+#[test]
+fn osmosis_test_tube_any_env() -> cw_orch::anyhow::Result<()> {
+    let mut test_tube = OsmosisTestTube::new(coins(1_000_000_000_000, "uosmo"));
+    let account = test_tube.init_account(coins(1_000_000_000_000, "uosmo"))?;
+    let mut chain: AnyCwEnv = test_tube.into();
+
+    let contract = CounterContract::new(chain.clone());
+    contract.upload()?;
+    contract.instantiate(&InstantiateMsg { count: 0 }, None, None)?;
+    let response = contract.increment()?;
+    assert!(matches!(response, AnyTxResponse::OsmosisTestTube(_)));
+
+    // A sender of another environment is rejected and the sender is left unchanged
+    let sender = chain.sender();
+    assert!(chain
+        .try_set_sender(AnySender::Mock(Addr::unchecked("mock")))
+        .is_err());
+    assert_eq!(chain.sender(), sender);
+
+    chain.try_set_sender(AnySender::OsmosisTestTube(account.clone()))?;
+    assert_eq!(chain.sender().as_str(), account.address());
+    Ok(())
+}
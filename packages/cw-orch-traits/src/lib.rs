@@ -2,7 +2,7 @@ use cw_orch_core::environment::CwEnv;
 
 pub mod stargate;
 
-pub use stargate::Stargate;
+pub use stargate::{ContractStargateQuery, Stargate, StargateQuerier};
 pub trait FullNode: CwEnv + Stargate {}
 
 impl<C: CwEnv + Stargate> FullNode for C {}
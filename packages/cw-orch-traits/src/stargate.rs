@@ -1,4 +1,7 @@
-use cw_orch_core::environment::TxHandler;
+use cw_orch_core::{
+    contract::interface_traits::ContractInstance,
+    environment::{QueryHandler, TxHandler},
+};
 use prost::Message;
 use prost_types::Any;
 
@@ -14,3 +17,38 @@ pub trait Stargate: TxHandler {
         memo: Option<&str>,
     ) -> Result<<Self as TxHandler>::Response, <Self as TxHandler>::Error>;
 }
+
+/// Allows querying chain modules with raw protobuf queries on cw-orch environments
+/// This covers module queries that don't have a typed querier
+pub trait StargateQuerier: QueryHandler {
+    /// Execute a protobuf query on a chain module
+    /// `path` is the full gRPC method path, for instance `/cosmos.bank.v1beta1.Query/Balance`
+    fn raw_module_query<Req, Res>(
+        &self,
+        path: &str,
+        request: &Req,
+    ) -> Result<Res, <Self as QueryHandler>::Error>
+    where
+        Req: Message + Clone + Send + Sync + 'static,
+        Res: Message + Default + Send + Sync + 'static;
+}
+
+/// Allows contract interfaces to perform module queries related to the contract
+/// (e.g. its tokenfactory denoms or its IBC channels) without switching to the environment queriers
+pub trait ContractStargateQuery<Chain: StargateQuerier>: ContractInstance<Chain> {
+    /// Execute a protobuf query on a chain module of the contract environment
+    /// See [`StargateQuerier::raw_module_query`]
+    fn raw_module_query<Req, Res>(
+        &self,
+        path: &str,
+        request: &Req,
+    ) -> Result<Res, <Chain as QueryHandler>::Error>
+    where
+        Req: Message + Clone + Send + Sync + 'static,
+        Res: Message + Default + Send + Sync + 'static,
+    {
+        self.get_chain().raw_module_query(path, request)
+    }
+}
+
+impl<Chain: StargateQuerier, T: ContractInstance<Chain>> ContractStargateQuery<Chain> for T {}
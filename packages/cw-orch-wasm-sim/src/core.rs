@@ -0,0 +1,355 @@
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, rc::Rc};
+
+use cosmwasm_std::{testing::MockApi, Addr, Binary, Coin, Event, HexBinary};
+use cw_orch_core::{
+    contract::{interface_traits::Uploadable, WasmPath},
+    environment::{
+        BankSetter, ChainInfo, ChainKind, ChainState, FundsManager, NetworkInfo, StateInterface,
+        SudoHandler, TxHandler, GAS_USED_EVENT,
+    },
+    CwEnvError,
+};
+use cw_orch_mock::{cw_multi_test::AppResponse, queriers::bank::MockBankQuerier, Mock, MockState};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::vm::{GasUsage, Vm, WasmContract};
+
+/// Chain info used to get the wasm files of the contracts
+pub const WASM_SIM_CHAIN_INFO: ChainInfo = ChainInfo {
+    chain_id: "cosmos-testnet-14002",
+    gas_denom: "ustake",
+    gas_price: 0.0,
+    grpc_urls: &[],
+    lcd_url: None,
+    fcd_url: None,
+    rpc_url: None,
+    network_info: NetworkInfo {
+        chain_name: "cosmos-testnet",
+        pub_address_prefix: "cosmos",
+        coin_type: 118u32,
+    },
+    kind: ChainKind::Local,
+};
+
+/// Environment running the compiled wasm of the contracts in process, without a chain.
+///
+/// The modules (bank, staking, ...) are the ones of a cw-multi-test [`Mock`], but the contracts are executed by [`cosmwasm_vm`]
+/// from their wasm file instead of their [`Uploadable::wrapper`].
+/// This catches the errors that only happen in wasm (e.g. floating point operations or unsupported capabilities)
+/// and reports the gas used by the contracts, without the Go dependency of test-tube.
+///
+/// The gas used by every call to a contract is available through [`WasmSim::gas_usages`]
+/// and the total gas of a transaction through [`IndexResponse::gas_used`](cw_orch_core::environment::IndexResponse::gas_used).
+///
+/// ## Example
+/// ```no_run
+/// # use cosmwasm_std::coins;
+/// use cw_orch_wasm_sim::WasmSim;
+///
+/// let sim = WasmSim::new("sender").unwrap();
+/// sim.mock.set_balance("sender", coins(1_000_000, "ustake")).unwrap();
+/// // Upload, instantiate and execute contracts as usual
+/// println!("{:?}", sim.last_gas_used());
+/// ```
+pub struct WasmSim<S: StateInterface = MockState> {
+    /// Underlying mock, providing the modules and the state
+    pub mock: Mock<S>,
+    pub(crate) vm: Vm,
+    /// Checksum of the uploaded codes
+    pub(crate) checksums: Rc<RefCell<HashMap<u64, HexBinary>>>,
+}
+
+impl<S: StateInterface> Clone for WasmSim<S> {
+    fn clone(&self) -> Self {
+        Self {
+            mock: self.mock.clone(),
+            vm: self.vm.clone(),
+            checksums: self.checksums.clone(),
+        }
+    }
+}
+
+impl WasmSim<MockState> {
+    /// Create a wasm-sim environment with the default mock state
+    pub fn new(sender: impl Into<String>) -> Result<Self, CwEnvError> {
+        Self::new_custom(sender, MockState::new())
+    }
+}
+
+impl<S: StateInterface> WasmSim<S> {
+    /// Create a wasm-sim environment with a custom mock state.
+    /// The state is customizable by implementing the `StateInterface` trait on a custom struct and providing it on the custom constructor.
+    pub fn new_custom(sender: impl Into<String>, custom_state: S) -> Result<Self, CwEnvError> {
+        Ok(Self {
+            mock: Mock::new_custom(sender, custom_state),
+            vm: Vm::new()?,
+            checksums: Rc::new(RefCell::new(HashMap::new())),
+        })
+    }
+
+    /// Sets the gas limit of a call to a contract, in Cosmos SDK gas. Defaults to [`DEFAULT_GAS_LIMIT`](crate::DEFAULT_GAS_LIMIT)
+    pub fn set_gas_limit(&self, gas_limit: u64) {
+        *self.vm.gas_limit.borrow_mut() = gas_limit;
+    }
+
+    /// Gas used by every call to a contract since the creation of the environment
+    pub fn gas_usages(&self) -> Vec<GasUsage> {
+        self.vm.gas_usages.borrow().clone()
+    }
+
+    /// Gas used by the contracts during the last call to a contract, in Cosmos SDK gas
+    pub fn last_gas_used(&self) -> Option<u64> {
+        self.vm.gas_usages.borrow().last().map(|usage| usage.gas)
+    }
+
+    /// Removes the recorded gas usages
+    pub fn clear_gas_usages(&self) {
+        self.vm.gas_usages.borrow_mut().clear();
+    }
+
+    /// Runs a transaction and adds the gas used by the contracts during it to the response
+    fn metered(
+        &self,
+        tx: impl FnOnce(&Mock<S>) -> Result<AppResponse, CwEnvError>,
+    ) -> Result<AppResponse, CwEnvError> {
+        let first_usage = self.vm.gas_usages.borrow().len();
+        let mut response = tx(&self.mock)?;
+        let gas_used: u64 = self.vm.gas_usages.borrow()[first_usage..]
+            .iter()
+            .map(|usage| usage.gas)
+            .sum();
+        response.events.push(
+            Event::new(GAS_USED_EVENT.0).add_attribute(GAS_USED_EVENT.1, gas_used.to_string()),
+        );
+        Ok(response)
+    }
+}
+
+impl<S: StateInterface> ChainState for WasmSim<S> {
+    type Out = Rc<RefCell<S>>;
+
+    fn state(&self) -> Self::Out {
+        self.mock.state()
+    }
+}
+
+impl<S: StateInterface> TxHandler for WasmSim<S> {
+    type Response = AppResponse;
+    type Error = CwEnvError;
+    type ContractSource = WasmPath;
+    type Sender = Addr;
+
+    fn sender(&self) -> Addr {
+        self.mock.sender()
+    }
+
+    fn set_sender(&mut self, sender: Self::Sender) {
+        self.mock.set_sender(sender)
+    }
+
+    fn upload<T: Uploadable>(&self, _contract: &T) -> Result<Self::Response, CwEnvError> {
+        let chain_info = WASM_SIM_CHAIN_INFO.into();
+        let wasm_contents = match <T as Uploadable>::wasm_bytes(&chain_info) {
+            Some(code) => code,
+            None => std::fs::read(<T as Uploadable>::wasm(&chain_info).path())?,
+        };
+        self.upload_from_bytes(wasm_contents)
+    }
+
+    fn upload_from_bytes(&self, code: Vec<u8>) -> Result<Self::Response, CwEnvError> {
+        let checksum = self.vm.save_wasm(&code)?;
+        let contract = WasmContract {
+            vm: self.vm.clone(),
+            checksum,
+        };
+        let code_id = self.mock.app.borrow_mut().store_code(Box::new(contract));
+        let hash: [u8; 32] = Sha256::digest(&code).into();
+        self.checksums.borrow_mut().insert(code_id, hash.into());
+
+        // add contract code_id to events manually
+        let event = Event::new("store_code").add_attribute("code_id", code_id.to_string());
+        Ok(AppResponse {
+            events: vec![event],
+            ..Default::default()
+        })
+    }
+
+    fn execute<E: Serialize + Debug>(
+        &self,
+        exec_msg: &E,
+        coins: &[Coin],
+        contract_address: &Addr,
+    ) -> Result<Self::Response, CwEnvError> {
+        self.metered(|mock| mock.execute(exec_msg, coins, contract_address))
+    }
+
+    fn instantiate<I: Serialize + Debug>(
+        &self,
+        code_id: u64,
+        init_msg: &I,
+        label: Option<&str>,
+        admin: Option<&Addr>,
+        coins: &[Coin],
+    ) -> Result<Self::Response, CwEnvError> {
+        self.metered(|mock| mock.instantiate(code_id, init_msg, label, admin, coins))
+    }
+
+    fn instantiate2<I: Serialize + Debug>(
+        &self,
+        code_id: u64,
+        init_msg: &I,
+        label: Option<&str>,
+        admin: Option<&Addr>,
+        coins: &[Coin],
+        salt: Binary,
+    ) -> Result<Self::Response, CwEnvError> {
+        self.metered(|mock| mock.instantiate2(code_id, init_msg, label, admin, coins, salt))
+    }
+
+    fn migrate<M: Serialize + Debug>(
+        &self,
+        migrate_msg: &M,
+        new_code_id: u64,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, CwEnvError> {
+        self.metered(|mock| mock.migrate(migrate_msg, new_code_id, contract_address))
+    }
+}
+
+impl<S: StateInterface> SudoHandler for WasmSim<S> {
+    fn sudo<M: Serialize + Debug>(
+        &self,
+        sudo_msg: &M,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, CwEnvError> {
+        self.metered(|mock| mock.sudo(sudo_msg, contract_address))
+    }
+}
+
+impl<S: StateInterface> BankSetter for WasmSim<S> {
+    type T = MockBankQuerier<MockApi>;
+
+    fn set_balance(
+        &mut self,
+        address: impl Into<String>,
+        amount: Vec<Coin>,
+    ) -> Result<(), <Self as TxHandler>::Error> {
+        self.mock.set_balance(address, amount)
+    }
+}
+
+impl<S: StateInterface> FundsManager for WasmSim<S> {
+    type Error = CwEnvError;
+
+    fn set_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        FundsManager::set_balance(&self.mock, address, amount)
+    }
+
+    fn add_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        FundsManager::add_balance(&self.mock, address, amount)
+    }
+
+    fn mint_native(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        FundsManager::mint_native(&self.mock, address, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coins;
+    use counter_contract::{
+        msg::InstantiateMsg, CounterContract, CounterExecuteMsgFns, CounterQueryMsgFns,
+    };
+    use cw_orch::prelude::*;
+
+    use super::{WasmSim, WASM_SIM_CHAIN_INFO};
+    use crate::INSTANCE_COST;
+
+    #[test]
+    fn runs_wasm_contracts() -> cw_orch::anyhow::Result<()> {
+        let sim = WasmSim::new("sender")?;
+        let sender = sim.sender();
+        sim.mock
+            .set_balance(sender.to_string(), coins(1_000, "ustake"))?;
+
+        let contract = CounterContract::new(sim.clone());
+        contract.upload()?;
+        contract.instantiate(&InstantiateMsg { count: 7 }, Some(&sender), None)?;
+        let response = contract.increment()?;
+        assert_eq!(contract.get_count()?.count, 8);
+
+        // The contract was run by the vm, which reported its gas
+        let gas_used = response.gas_used().unwrap();
+        assert!(gas_used > INSTANCE_COST);
+        let usages = sim.gas_usages();
+        assert_eq!(
+            usages
+                .iter()
+                .map(|usage| usage.entry_point)
+                .collect::<Vec<_>>(),
+            vec!["instantiate", "execute", "query"]
+        );
+        assert_eq!(usages[1].gas, gas_used);
+        Ok(())
+    }
+
+    #[test]
+    fn code_checksum_is_the_wasm_checksum() -> cw_orch::anyhow::Result<()> {
+        let sim = WasmSim::new("sender")?;
+        let contract = CounterContract::new(sim.clone());
+        contract.upload()?;
+
+        let checksum = CounterContract::<Mock>::wasm(&WASM_SIM_CHAIN_INFO.into()).checksum()?;
+        assert_eq!(
+            sim.wasm_querier().code_id_hash(contract.code_id()?)?,
+            checksum
+        );
+        assert_eq!(
+            sim.wasm_querier()
+                .code_id_by_checksum(&checksum, &sim.sender())?,
+            Some(contract.code_id()?)
+        );
+        // Already uploaded, the same code isn't uploaded again
+        assert!(contract.upload_if_needed()?.is_none());
+
+        // A deployment that doesn't know the code reuses the code already on chain
+        let code_id = contract.code_id()?;
+        contract.remove_code_id();
+        assert!(contract.upload_if_needed()?.is_none());
+        assert_eq!(contract.code_id()?, code_id);
+        Ok(())
+    }
+
+    #[test]
+    fn gas_limit_is_enforced() -> cw_orch::anyhow::Result<()> {
+        let sim = WasmSim::new("sender")?;
+        let contract = CounterContract::new(sim.clone());
+        contract.upload()?;
+
+        sim.set_gas_limit(1);
+        let sender = sim.sender();
+        assert!(contract
+            .instantiate(&InstantiateMsg { count: 7 }, Some(&sender), None)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn cache_dir_is_removed_on_drop() -> cw_orch::anyhow::Result<()> {
+        let sim = WasmSim::new("sender")?;
+        let other = WasmSim::new("sender")?;
+        let dir = sim.vm.cache.dir().to_path_buf();
+        assert_ne!(dir, other.vm.cache.dir());
+
+        CounterContract::new(sim.clone()).upload()?;
+        assert!(dir.exists());
+        let clone = sim.clone();
+        drop(sim);
+        assert!(dir.exists());
+        drop(clone);
+        assert!(!dir.exists());
+        assert!(other.vm.cache.dir().exists());
+        Ok(())
+    }
+}
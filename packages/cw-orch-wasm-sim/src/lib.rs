@@ -0,0 +1,13 @@
+//! Integration testing execution environment running the compiled wasm of the contracts in process.
+//!
+//! [`WasmSim`] executes the contracts with [`cosmwasm_vm`], the virtual machine used by wasmd, on top of the modules of a cw-multi-test app.
+//! Tests run on the actual wasm artifacts and report gas the way a chain does, faster than test-tube and without a Go toolchain.
+
+mod core;
+pub mod queriers;
+mod vm;
+
+pub use self::core::{WasmSim, WASM_SIM_CHAIN_INFO};
+pub use vm::{GasUsage, DEFAULT_GAS_LIMIT, GAS_MULTIPLIER, INSTANCE_COST};
+
+pub use cosmwasm_vm;
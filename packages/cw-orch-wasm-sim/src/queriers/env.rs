@@ -0,0 +1,9 @@
+use cw_orch_core::environment::{EnvironmentInfo, EnvironmentQuerier, StateInterface};
+
+use crate::WasmSim;
+
+impl<S: StateInterface> EnvironmentQuerier for WasmSim<S> {
+    fn env_info(&self) -> EnvironmentInfo {
+        self.mock.env_info()
+    }
+}
@@ -0,0 +1,62 @@
+use cosmwasm_std::{testing::MockApi, Timestamp};
+use cw_orch_core::{
+    environment::{ChainTime, DefaultQueriers, QuerierGetter, QueryHandler, StateInterface},
+    CwEnvError,
+};
+use cw_orch_mock::queriers::{bank::MockBankQuerier, node::MockNodeQuerier};
+
+use crate::WasmSim;
+
+mod env;
+pub mod wasm;
+
+impl<S: StateInterface> QueryHandler for WasmSim<S> {
+    type Error = CwEnvError;
+
+    fn wait_blocks(&self, amount: u64) -> Result<(), CwEnvError> {
+        self.mock.wait_blocks(amount)
+    }
+
+    fn wait_seconds(&self, secs: u64) -> Result<(), CwEnvError> {
+        self.mock.wait_seconds(secs)
+    }
+
+    fn next_block(&self) -> Result<(), CwEnvError> {
+        self.mock.next_block()
+    }
+}
+
+impl<S: StateInterface> ChainTime for WasmSim<S> {
+    type Error = CwEnvError;
+
+    fn advance_blocks(&self, blocks: u64) -> Result<(), CwEnvError> {
+        self.mock.advance_blocks(blocks)
+    }
+
+    fn advance_seconds(&self, seconds: u64) -> Result<(), CwEnvError> {
+        self.mock.advance_seconds(seconds)
+    }
+
+    fn set_block_time(&self, time: Timestamp) -> Result<(), CwEnvError> {
+        self.mock.set_block_time(time)
+    }
+}
+
+// The bank and node queriers are the ones of the underlying mock
+impl<S: StateInterface> QuerierGetter<MockBankQuerier<MockApi>> for WasmSim<S> {
+    fn querier(&self) -> MockBankQuerier<MockApi> {
+        self.mock.querier()
+    }
+}
+
+impl<S: StateInterface> QuerierGetter<MockNodeQuerier<MockApi>> for WasmSim<S> {
+    fn querier(&self) -> MockNodeQuerier<MockApi> {
+        self.mock.querier()
+    }
+}
+
+impl<S: StateInterface> DefaultQueriers for WasmSim<S> {
+    type Bank = MockBankQuerier<MockApi>;
+    type Wasm = wasm::WasmSimWasmQuerier<S>;
+    type Node = MockNodeQuerier<MockApi>;
+}
@@ -0,0 +1,117 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use cosmwasm_std::{
+    testing::MockApi, Addr, Binary, CodeInfoResponse, ContractInfoResponse, HexBinary,
+};
+use cw_orch_core::{
+    contract::interface_traits::{ContractInstance, Uploadable},
+    environment::{DefaultQueriers, Querier, QuerierGetter, StateInterface, WasmQuerier},
+    CwEnvError,
+};
+use cw_orch_mock::queriers::wasm::MockWasmQuerier;
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{WasmSim, WASM_SIM_CHAIN_INFO};
+
+/// Wasm querier of the underlying mock, reporting the checksums of the uploaded wasm codes
+pub struct WasmSimWasmQuerier<S: StateInterface> {
+    mock: MockWasmQuerier<MockApi, S>,
+    checksums: Rc<RefCell<HashMap<u64, HexBinary>>>,
+}
+
+impl<S: StateInterface> WasmSimWasmQuerier<S> {
+    fn new(sim: &WasmSim<S>) -> Self {
+        Self {
+            mock: sim.mock.wasm_querier(),
+            checksums: sim.checksums.clone(),
+        }
+    }
+}
+
+impl<S: StateInterface> Querier for WasmSimWasmQuerier<S> {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<WasmSimWasmQuerier<S>> for WasmSim<S> {
+    fn querier(&self) -> WasmSimWasmQuerier<S> {
+        WasmSimWasmQuerier::new(self)
+    }
+}
+
+impl<S: StateInterface> WasmQuerier for WasmSimWasmQuerier<S> {
+    type Chain = WasmSim<S>;
+
+    fn code_id_hash(&self, code_id: u64) -> Result<HexBinary, CwEnvError> {
+        match self.checksums.borrow().get(&code_id) {
+            Some(checksum) => Ok(checksum.clone()),
+            None => self.mock.code_id_hash(code_id),
+        }
+    }
+
+    fn contract_info(
+        &self,
+        address: impl Into<String>,
+    ) -> Result<ContractInfoResponse, CwEnvError> {
+        self.mock.contract_info(address)
+    }
+
+    fn raw_query(
+        &self,
+        address: impl Into<String>,
+        query_data: Vec<u8>,
+    ) -> Result<Vec<u8>, CwEnvError> {
+        self.mock.raw_query(address, query_data)
+    }
+
+    fn smart_query<Q, T>(&self, address: impl Into<String>, query_data: &Q) -> Result<T, CwEnvError>
+    where
+        T: DeserializeOwned,
+        Q: Serialize,
+    {
+        self.mock.smart_query(address, query_data)
+    }
+
+    fn code(&self, code_id: u64) -> Result<CodeInfoResponse, CwEnvError> {
+        let mut code = self.mock.code(code_id)?;
+        code.checksum = self.code_id_hash(code_id)?;
+        Ok(code)
+    }
+
+    /// Checksum of the code uploaded by [`WasmSim`]: the embedded wasm bytes of the contract if any, its wasm file otherwise
+    fn local_hash<T: Uploadable + ContractInstance<Self::Chain>>(
+        &self,
+        _contract: &T,
+    ) -> Result<HexBinary, CwEnvError> {
+        let chain_info = WASM_SIM_CHAIN_INFO.into();
+        if let Some(code) = <T as Uploadable>::wasm_bytes(&chain_info) {
+            let checksum: [u8; 32] = Sha256::digest(code).into();
+            return Ok(checksum.into());
+        }
+        <T as Uploadable>::wasm(&chain_info).checksum()
+    }
+
+    /// The mock has no instantiate permissions, every code can be instantiated by anyone
+    fn code_id_by_checksum(
+        &self,
+        checksum: &HexBinary,
+        _instantiator: &Addr,
+    ) -> Result<Option<u64>, CwEnvError> {
+        Ok(self
+            .checksums
+            .borrow()
+            .iter()
+            .filter(|(_, code_checksum)| *code_checksum == checksum)
+            .map(|(code_id, _)| *code_id)
+            .max())
+    }
+
+    fn instantiate2_addr(
+        &self,
+        code_id: u64,
+        creator: impl Into<String>,
+        salt: Binary,
+    ) -> Result<String, CwEnvError> {
+        self.mock.instantiate2_addr(code_id, creator, salt)
+    }
+}
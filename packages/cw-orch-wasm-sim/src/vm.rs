@@ -0,0 +1,570 @@
+//! Runs compiled wasm contracts inside the cw-multi-test app with [`cosmwasm_vm`].
+//!
+//! The vm needs a backend it owns, so the instance of a contract runs on a scoped thread
+//! and its backend sends the storage and querier calls back through a channel to the thread running the call,
+//! which serves them with the storage and querier provided by the app to the contract.
+//! The contract reads and writes the app state directly. Storage, api and query calls are charged the gas wasmd charges for them.
+
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Sender, SyncSender},
+    },
+};
+
+use cosmwasm_std::{
+    testing::MockApi, Addr, Api, Binary, CanonicalAddr, ContractResult, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, QuerierWrapper, Record, Reply, Response, SystemResult,
+};
+use cosmwasm_vm::{
+    call_execute, call_instantiate, call_migrate, call_query, call_reply, call_sudo, Backend,
+    BackendApi, BackendError, BackendResult, Cache, CacheOptions, Checksum, GasInfo, Instance,
+    InstanceOptions, Size, VmResult,
+};
+use cw_orch_core::CwEnvError;
+use cw_orch_mock::cw_multi_test::Contract;
+
+/// Number of CosmWasm gas units in one unit of Cosmos SDK gas, as in wasmd
+pub const GAS_MULTIPLIER: u64 = 140_000_000;
+/// Default gas limit of a call to a contract, in Cosmos SDK gas
+pub const DEFAULT_GAS_LIMIT: u64 = 30_000_000;
+/// Gas charged by wasmd to load a contract instance that isn't pinned, in Cosmos SDK gas
+pub const INSTANCE_COST: u64 = 60_000;
+
+// Storage costs of the default KVStore gas config of the Cosmos SDK
+const READ_COST_FLAT: u64 = 1_000;
+const READ_COST_PER_BYTE: u64 = 3;
+const WRITE_COST_FLAT: u64 = 2_000;
+const WRITE_COST_PER_BYTE: u64 = 30;
+const DELETE_COST: u64 = 1_000;
+const ITER_NEXT_COST_FLAT: u64 = 30;
+// Address costs of wasmd
+const HUMANIZE_COST: u64 = 5;
+const CANONICALIZE_COST: u64 = 4;
+
+/// Capabilities supported by the contracts, the same as a wasmd chain supporting CosmWasm 1.4
+const CAPABILITIES: [&str; 7] = [
+    "iterator",
+    "staking",
+    "stargate",
+    "cosmwasm_1_1",
+    "cosmwasm_1_2",
+    "cosmwasm_1_3",
+    "cosmwasm_1_4",
+];
+
+type SimInstance = Instance<ApiBridge, StorageBridge, QuerierBridge>;
+pub(crate) type SimCache = Cache<ApiBridge, StorageBridge, QuerierBridge>;
+
+/// Gas used by a call to an entry point of a wasm contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasUsage {
+    /// Address of the called contract
+    pub contract: Addr,
+    /// Entry point that was called, e.g. `execute`
+    pub entry_point: &'static str,
+    /// CosmWasm gas used by the call, including the storage and api costs
+    pub wasm_gas: u64,
+    /// Cosmos SDK gas used by the call, including the instance cost
+    pub gas: u64,
+}
+
+/// Shared between the environment and the contracts it uploaded
+#[derive(Clone)]
+pub(crate) struct Vm {
+    pub cache: Rc<VmCache>,
+    /// Gas limit of a call, in Cosmos SDK gas
+    pub gas_limit: Rc<RefCell<u64>>,
+    pub gas_usages: Rc<RefCell<Vec<GasUsage>>>,
+}
+
+/// Number of caches created by this process, to give each environment its own cache directory
+static CACHES: AtomicUsize = AtomicUsize::new(0);
+
+impl Vm {
+    pub fn new() -> Result<Self, CwEnvError> {
+        let dir = std::env::temp_dir().join(format!(
+            "cw-orch-wasm-sim-{}-{}",
+            std::process::id(),
+            CACHES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let options = CacheOptions {
+            base_dir: dir.clone(),
+            available_capabilities: CAPABILITIES
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<HashSet<_>>(),
+            memory_cache_size: Size::mebi(64),
+            instance_memory_limit: Size::mebi(32),
+        };
+        // SAFETY: the cache directory is only used by this environment,
+        // the modules in it are written by this same version of cosmwasm-vm
+        let cache = unsafe { Cache::new(options) }.map_err(map_err)?;
+
+        Ok(Self {
+            cache: Rc::new(VmCache {
+                cache,
+                dir: CacheDir(dir),
+            }),
+            gas_limit: Rc::new(RefCell::new(DEFAULT_GAS_LIMIT)),
+            gas_usages: Rc::new(RefCell::new(vec![])),
+        })
+    }
+
+    /// Validates and compiles the code, returns its checksum
+    pub fn save_wasm(&self, code: &[u8]) -> Result<Checksum, CwEnvError> {
+        self.cache.cache.save_wasm(code).map_err(map_err)
+    }
+}
+
+/// Cache of the compiled modules, stored in a directory removed when the last clone of the environment is dropped
+pub(crate) struct VmCache {
+    // Declared before `dir` to be dropped, and stop using the directory, before it is removed
+    cache: SimCache,
+    dir: CacheDir,
+}
+
+impl VmCache {
+    /// Directory of the compiled modules
+    pub fn dir(&self) -> &Path {
+        &self.dir.0
+    }
+}
+
+struct CacheDir(PathBuf);
+
+impl Drop for CacheDir {
+    fn drop(&mut self) {
+        // Nothing to do about a failure when dropping, the directory is in the temporary directory anyway
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+pub(crate) fn map_err(e: impl ToString) -> CwEnvError {
+    CwEnvError::StdErr(e.to_string())
+}
+
+/// cw-multi-test contract executing a compiled wasm code
+pub(crate) struct WasmContract {
+    pub vm: Vm,
+    pub checksum: Checksum,
+}
+
+impl WasmContract {
+    fn call<T: Send>(
+        &self,
+        storage: HostStorage,
+        querier: QuerierWrapper,
+        entry_point: &'static str,
+        env: &Env,
+        call: impl FnOnce(&mut SimInstance) -> VmResult<ContractResult<T>> + Send,
+    ) -> anyhow::Result<T> {
+        let options = InstanceOptions {
+            gas_limit: self.vm.gas_limit.borrow().saturating_mul(GAS_MULTIPLIER),
+            print_debug: false,
+        };
+        let mut host = Host {
+            storage,
+            querier,
+            iterators: vec![],
+            gas_usages: &self.vm.gas_usages,
+        };
+        let cache = &self.vm.cache.cache;
+        let checksum = &self.checksum;
+
+        let (result, report) = std::thread::scope(|scope| {
+            let (sender, calls) = mpsc::channel();
+            let instance = scope.spawn(move || {
+                let channel = HostChannel(sender);
+                let backend = Backend {
+                    api: ApiBridge {
+                        api: MockApi::default(),
+                    },
+                    storage: StorageBridge {
+                        host: channel.clone(),
+                    },
+                    querier: QuerierBridge { host: channel },
+                };
+                let mut instance = cache.get_instance(checksum, backend, options)?;
+                let result = call(&mut instance);
+                VmResult::Ok((result, instance.create_gas_report()))
+            });
+            // The instance, and the channel in its backend, is dropped when the call ends
+            for call in calls {
+                host.serve(call);
+            }
+            instance
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let wasm_gas = report.used_internally + report.used_externally;
+        self.vm.gas_usages.borrow_mut().push(GasUsage {
+            contract: env.contract.address.clone(),
+            entry_point,
+            wasm_gas,
+            gas: INSTANCE_COST + wasm_gas.div_ceil(GAS_MULTIPLIER),
+        });
+
+        result
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?
+            .into_result()
+            .map_err(anyhow::Error::msg)
+    }
+}
+
+impl Contract<Empty, Empty> for WasmContract {
+    fn execute(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> anyhow::Result<Response> {
+        self.call(
+            HostStorage::Mut(deps.storage),
+            deps.querier,
+            "execute",
+            &env,
+            |instance| call_execute(instance, &env, &info, &msg),
+        )
+    }
+
+    fn instantiate(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> anyhow::Result<Response> {
+        self.call(
+            HostStorage::Mut(deps.storage),
+            deps.querier,
+            "instantiate",
+            &env,
+            |instance| call_instantiate(instance, &env, &info, &msg),
+        )
+    }
+
+    fn query(&self, deps: Deps, env: Env, msg: Vec<u8>) -> anyhow::Result<Binary> {
+        self.call(
+            HostStorage::ReadOnly(deps.storage),
+            deps.querier,
+            "query",
+            &env,
+            |instance| call_query(instance, &env, &msg),
+        )
+    }
+
+    fn sudo(&self, deps: DepsMut, env: Env, msg: Vec<u8>) -> anyhow::Result<Response> {
+        self.call(
+            HostStorage::Mut(deps.storage),
+            deps.querier,
+            "sudo",
+            &env,
+            |instance| call_sudo(instance, &env, &msg),
+        )
+    }
+
+    fn reply(&self, deps: DepsMut, env: Env, msg: Reply) -> anyhow::Result<Response> {
+        self.call(
+            HostStorage::Mut(deps.storage),
+            deps.querier,
+            "reply",
+            &env,
+            |instance| call_reply(instance, &env, &msg),
+        )
+    }
+
+    fn migrate(&self, deps: DepsMut, env: Env, msg: Vec<u8>) -> anyhow::Result<Response> {
+        self.call(
+            HostStorage::Mut(deps.storage),
+            deps.querier,
+            "migrate",
+            &env,
+            |instance| call_migrate(instance, &env, &msg),
+        )
+    }
+}
+
+fn gas(sdk_gas: u64) -> GasInfo {
+    GasInfo::with_externally_used(sdk_gas * GAS_MULTIPLIER)
+}
+
+type HostReply<T> = SyncSender<T>;
+
+/// Call of the vm to the app, served by [`Host`]
+enum HostCall {
+    Get(Vec<u8>, HostReply<Option<Vec<u8>>>),
+    Scan(Option<Vec<u8>>, Option<Vec<u8>>, Order, HostReply<u32>),
+    /// Replies `None` if the iterator doesn't exist
+    Next(u32, HostReply<Option<Option<Record>>>),
+    Set(Vec<u8>, Vec<u8>, HostReply<Result<(), String>>),
+    Remove(Vec<u8>, HostReply<Result<(), String>>),
+    /// Replies the result of the query and the gas used by the queried contracts, in Cosmos SDK gas
+    Query(
+        Vec<u8>,
+        HostReply<(SystemResult<ContractResult<Binary>>, u64)>,
+    ),
+}
+
+/// Serves the calls of the vm with the storage and querier provided by the app to the contract
+struct Host<'a> {
+    storage: HostStorage<'a>,
+    querier: QuerierWrapper<'a>,
+    iterators: Vec<std::vec::IntoIter<Record>>,
+    gas_usages: &'a RefCell<Vec<GasUsage>>,
+}
+
+enum HostStorage<'a> {
+    ReadOnly(&'a dyn cosmwasm_std::Storage),
+    Mut(&'a mut dyn cosmwasm_std::Storage),
+}
+
+impl Host<'_> {
+    fn storage(&self) -> &dyn cosmwasm_std::Storage {
+        match &self.storage {
+            HostStorage::ReadOnly(storage) => *storage,
+            HostStorage::Mut(storage) => &**storage,
+        }
+    }
+
+    fn storage_mut(&mut self) -> Result<&mut dyn cosmwasm_std::Storage, String> {
+        match &mut self.storage {
+            HostStorage::ReadOnly(_) => Err("storage is read-only during queries".to_string()),
+            HostStorage::Mut(storage) => Ok(&mut **storage),
+        }
+    }
+
+    /// Serves `call`. The reply can't fail to be sent, the vm waits for it
+    fn serve(&mut self, call: HostCall) {
+        match call {
+            HostCall::Get(key, reply) => {
+                reply.send(self.storage().get(&key)).ok();
+            }
+            HostCall::Scan(start, end, order, reply) => {
+                let records: Vec<Record> = self
+                    .storage()
+                    .range(start.as_deref(), end.as_deref(), order)
+                    .collect();
+                self.iterators.push(records.into_iter());
+                reply.send(self.iterators.len() as u32 - 1).ok();
+            }
+            HostCall::Next(iterator_id, reply) => {
+                let record = self
+                    .iterators
+                    .get_mut(iterator_id as usize)
+                    .map(Iterator::next);
+                reply.send(record).ok();
+            }
+            HostCall::Set(key, value, reply) => {
+                let result = self.storage_mut().map(|storage| storage.set(&key, &value));
+                reply.send(result).ok();
+            }
+            HostCall::Remove(key, reply) => {
+                let result = self.storage_mut().map(|storage| storage.remove(&key));
+                reply.send(result).ok();
+            }
+            HostCall::Query(request, reply) => {
+                let first_usage = self.gas_usages.borrow().len();
+                let result = self.querier.raw_query(&request);
+                // As in wasmd, the gas of the queried contracts is charged to the querying contract
+                let gas = self
+                    .gas_usages
+                    .borrow_mut()
+                    .drain(first_usage..)
+                    .map(|usage| usage.gas)
+                    .sum();
+                reply.send((result, gas)).ok();
+            }
+        }
+    }
+}
+
+/// Channel from the vm to the [`Host`]
+#[derive(Clone)]
+struct HostChannel(Sender<HostCall>);
+
+impl HostChannel {
+    fn call<T>(&self, call: impl FnOnce(HostReply<T>) -> HostCall) -> T {
+        let (reply, response) = mpsc::sync_channel(1);
+        self.0
+            .send(call(reply))
+            .expect("the host serves the vm until the instance is dropped");
+        response.recv().expect("the host replies to every call")
+    }
+}
+
+/// [`Api`] of the app of a [`Mock`](cw_orch_mock::Mock), exposed to the vm
+#[derive(Clone, Copy)]
+pub(crate) struct ApiBridge {
+    api: MockApi,
+}
+
+impl BackendApi for ApiBridge {
+    fn addr_validate(&self, input: &str) -> BackendResult<()> {
+        let result = self
+            .api
+            .addr_validate(input)
+            .map(|_| ())
+            .map_err(|e| BackendError::user_err(e.to_string()));
+        (result, gas(CANONICALIZE_COST + HUMANIZE_COST))
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> BackendResult<Vec<u8>> {
+        let result = self
+            .api
+            .addr_canonicalize(human)
+            .map(|canonical| canonical.as_slice().to_vec())
+            .map_err(|e| BackendError::user_err(e.to_string()));
+        (result, gas(CANONICALIZE_COST))
+    }
+
+    fn addr_humanize(&self, canonical: &[u8]) -> BackendResult<String> {
+        let result = self
+            .api
+            .addr_humanize(&CanonicalAddr::from(canonical))
+            .map(Addr::into_string)
+            .map_err(|e| BackendError::user_err(e.to_string()));
+        (result, gas(HUMANIZE_COST))
+    }
+}
+
+/// Storage of the contract in the app, exposed to the vm
+pub(crate) struct StorageBridge {
+    host: HostChannel,
+}
+
+impl cosmwasm_vm::Storage for StorageBridge {
+    fn get(&self, key: &[u8]) -> BackendResult<Option<Vec<u8>>> {
+        let value = self.host.call(|reply| HostCall::Get(key.to_vec(), reply));
+        let len = key.len() + value.as_ref().map_or(0, Vec::len);
+        (
+            Ok(value),
+            gas(READ_COST_FLAT + READ_COST_PER_BYTE * len as u64),
+        )
+    }
+
+    fn scan(
+        &mut self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> BackendResult<u32> {
+        let iterator_id = self.host.call(|reply| {
+            HostCall::Scan(
+                start.map(<[u8]>::to_vec),
+                end.map(<[u8]>::to_vec),
+                order,
+                reply,
+            )
+        });
+        (Ok(iterator_id), gas(READ_COST_FLAT))
+    }
+
+    fn next(&mut self, iterator_id: u32) -> BackendResult<Option<Record>> {
+        let Some(record) = self.host.call(|reply| HostCall::Next(iterator_id, reply)) else {
+            return (
+                Err(BackendError::iterator_does_not_exist(iterator_id)),
+                GasInfo::free(),
+            );
+        };
+        let len = record.as_ref().map_or(0, |(k, v)| k.len() + v.len());
+        (
+            Ok(record),
+            gas(ITER_NEXT_COST_FLAT + READ_COST_PER_BYTE * len as u64),
+        )
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) -> BackendResult<()> {
+        let cost = WRITE_COST_FLAT + WRITE_COST_PER_BYTE * (key.len() + value.len()) as u64;
+        let result = self
+            .host
+            .call(|reply| HostCall::Set(key.to_vec(), value.to_vec(), reply))
+            .map_err(BackendError::user_err);
+        (result, gas(cost))
+    }
+
+    fn remove(&mut self, key: &[u8]) -> BackendResult<()> {
+        let result = self
+            .host
+            .call(|reply| HostCall::Remove(key.to_vec(), reply))
+            .map_err(BackendError::user_err);
+        (result, gas(DELETE_COST))
+    }
+}
+
+/// Querier of the app, exposed to the vm. The gas used by the queried contracts is charged to the querying contract
+pub(crate) struct QuerierBridge {
+    host: HostChannel,
+}
+
+impl cosmwasm_vm::Querier for QuerierBridge {
+    fn query_raw(
+        &self,
+        request: &[u8],
+        _gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        let (result, sdk_gas) = self
+            .host
+            .call(|reply| HostCall::Query(request.to_vec(), reply));
+        (Ok(result), gas(sdk_gas))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        from_json, testing::MockStorage, to_json_binary, to_json_vec, QueryRequest, WasmQuery,
+    };
+    use counter_contract::{
+        msg::{GetCountResponse, InstantiateMsg, QueryMsg},
+        CounterContract,
+    };
+    use cw_orch::prelude::*;
+
+    use super::*;
+    use crate::WasmSim;
+
+    #[test]
+    fn queried_contracts_gas_is_charged_to_the_querier() -> anyhow::Result<()> {
+        let sim = WasmSim::new("sender")?;
+        let contract = CounterContract::new(sim.clone());
+        contract.upload()?;
+        contract.instantiate(&InstantiateMsg { count: 7 }, None, None)?;
+        sim.clear_gas_usages();
+
+        let app = sim.mock.app.borrow();
+        let mut storage = MockStorage::new();
+        let mut host = Host {
+            storage: HostStorage::Mut(&mut storage),
+            querier: app.wrap(),
+            iterators: vec![],
+            gas_usages: &sim.vm.gas_usages,
+        };
+        let request = to_json_vec(&QueryRequest::<Empty>::Wasm(WasmQuery::Smart {
+            contract_addr: contract.addr_str()?,
+            msg: to_json_binary(&QueryMsg::GetCount {})?,
+        }))?;
+        let (reply, response) = mpsc::sync_channel(1);
+        host.serve(HostCall::Query(request, reply));
+        let (result, gas) = response.recv()?;
+
+        let count: GetCountResponse = from_json(
+            result
+                .into_result()?
+                .into_result()
+                .map_err(anyhow::Error::msg)?,
+        )?;
+        assert_eq!(count.count, 7);
+        assert!(gas > INSTANCE_COST);
+        // The gas of the queried contract is part of the gas of the querier, it isn't reported on its own
+        assert!(sim.gas_usages().is_empty());
+        Ok(())
+    }
+}
@@ -2,7 +2,16 @@
 //! Those structures are mostly used internally for dealing with channel creation and analysis
 //! But they can also be used in a user application if they need specific channel description
 
+use std::str::FromStr;
+
+use cosmwasm_schema::cw_serde;
+use ibc_relayer_types::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty, IdentifiedChannelEnd, Order, State,
+};
+use ibc_relayer_types::core::ics04_channel::version::Version;
 use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
+use ibc_relayer_types::core::ics24_host::identifier::ConnectionId;
+use ibc_relayer_types::core::ics24_host::identifier::PortChannelId;
 use ibc_relayer_types::core::ics24_host::identifier::PortId;
 
 use crate::env::ChainId;
@@ -97,3 +106,309 @@ where
         }
     }
 }
+
+impl<Channel> IbcPort<Channel> {
+    /// Returns the port/channel identifier pair of this side of the channel
+    /// Returns `None` if the channel-id is not specified
+    pub fn port_channel_id(&self) -> Option<PortChannelId> {
+        self.channel.clone().map(|channel_id| PortChannelId {
+            channel_id,
+            port_id: self.port.clone(),
+        })
+    }
+
+    /// Returns the typed connection identifier of this side of the channel
+    /// Returns `None` if the connection-id is not specified
+    pub fn connection(&self) -> Result<Option<ConnectionId>, InterchainError> {
+        Ok(self
+            .connection_id
+            .as_deref()
+            .map(ConnectionId::from_str)
+            .transpose()?)
+    }
+
+    /// Returns the ibc representation of this side of the channel, as seen from the counterparty channel end
+    pub fn counterparty(&self) -> Counterparty {
+        Counterparty::new(self.port.clone(), self.channel.clone())
+    }
+
+    /// Returns the metadata of this side of the channel, without the chain accessor
+    pub fn metadata(&self) -> IbcPortMetadata {
+        IbcPortMetadata {
+            chain_id: self.chain_id.clone(),
+            connection_id: self.connection_id.clone(),
+            port: self.port.to_string(),
+            channel: self.channel.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
+impl<Channel> InterchainChannel<Channel>
+where
+    Channel: Clone + Send + Sync,
+{
+    /// Returns the metadata of the channel, without the chain accessors.
+    /// The metadata can be serialized to be re-used across runs
+    pub fn metadata(&self) -> InterchainChannelMetadata {
+        InterchainChannelMetadata {
+            port_a: self.port_a.metadata(),
+            port_b: self.port_b.metadata(),
+            version: None,
+        }
+    }
+
+    /// Creates a channel from the ibc representation of a channel end, queried on `chain_id`
+    /// `chain` and `counterparty_chain` are the chain accessors for respectively `chain_id` and `counterparty_chain_id`
+    /// `counterparty_connection_id` is the connection id on the counterparty chain,
+    /// as found in the counterparty of the connection end of the channel (`ConnectionEnd::counterparty().connection_id()`)
+    pub fn from_identified_channel_end(
+        chain_id: ChainId,
+        chain: Channel,
+        counterparty_chain_id: ChainId,
+        counterparty_chain: Channel,
+        channel: &IdentifiedChannelEnd,
+        counterparty_connection_id: Option<&ConnectionId>,
+    ) -> Self {
+        let connection_id = channel
+            .channel_end
+            .connection_hops
+            .first()
+            .map(ToString::to_string);
+        Self::new(
+            IbcPort {
+                chain_id: chain_id.to_string(),
+                connection_id,
+                port: channel.port_id.clone(),
+                channel: Some(channel.channel_id.clone()),
+                chain,
+            },
+            IbcPort {
+                chain_id: counterparty_chain_id.to_string(),
+                connection_id: counterparty_connection_id.map(ToString::to_string),
+                port: channel.channel_end.remote.port_id.clone(),
+                channel: channel.channel_end.remote.channel_id.clone(),
+                chain: counterparty_chain,
+            },
+        )
+    }
+
+    /// Returns the ibc representation of the channel end on `chain_id`.
+    /// The state, ordering and version of the channel are not tracked by this object and need to be provided.
+    /// Errors if `chain_id` is not registered in the object or if the channel id on `chain_id` is not specified
+    pub fn identified_channel_end(
+        &self,
+        chain_id: ChainId,
+        state: State,
+        ordering: Order,
+        version: Version,
+    ) -> Result<IdentifiedChannelEnd, InterchainError> {
+        let (port, counterparty) = self.get_ordered_ports_from(chain_id)?;
+        let channel_id = port
+            .channel
+            .clone()
+            .ok_or_else(|| InterchainError::ChannelIdNotSpecified(chain_id.to_string()))?;
+        let connection_hops = port.connection()?.into_iter().collect();
+        Ok(IdentifiedChannelEnd::new(
+            port.port,
+            channel_id,
+            ChannelEnd::new(
+                state,
+                ordering,
+                counterparty.counterparty(),
+                connection_hops,
+                version,
+            ),
+        ))
+    }
+}
+
+/// Serializable description of one side of an IBC channel
+#[cw_serde]
+pub struct IbcPortMetadata {
+    /// The chain id of the network which belongs on one side of the channel
+    pub chain_id: NetworkId,
+    /// The connection-id on the network
+    pub connection_id: Option<String>,
+    /// The port Id, that the channel binds on
+    pub port: String,
+    /// The channel-id on the network
+    pub channel: Option<String>,
+}
+
+impl IbcPortMetadata {
+    /// Creates the port object by providing an accessor to the chain
+    pub fn into_port<Channel>(self, chain: Channel) -> Result<IbcPort<Channel>, InterchainError> {
+        Ok(IbcPort {
+            chain_id: self.chain_id,
+            connection_id: self.connection_id,
+            port: PortId::from_str(&self.port)?,
+            channel: self
+                .channel
+                .as_deref()
+                .map(ChannelId::from_str)
+                .transpose()?,
+            chain,
+        })
+    }
+}
+
+/// Serializable description of an IBC channel
+/// This can be stored and re-used across runs to avoid re-creating or re-querying channels
+#[cw_serde]
+pub struct InterchainChannelMetadata {
+    /// Port on one side of the channel
+    pub port_a: IbcPortMetadata,
+    /// Port on the other side of the channel
+    pub port_b: IbcPortMetadata,
+    /// Version negotiated for the channel, if known
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl InterchainChannelMetadata {
+    /// Sets the version negotiated for the channel
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Returns the metadata of the side of the channel on `chain_id`, followed by its counterparty
+    pub fn ordered_ports_from(
+        &self,
+        chain_id: &str,
+    ) -> Result<(&IbcPortMetadata, &IbcPortMetadata), InterchainError> {
+        if self.port_a.chain_id == chain_id {
+            Ok((&self.port_a, &self.port_b))
+        } else if self.port_b.chain_id == chain_id {
+            Ok((&self.port_b, &self.port_a))
+        } else {
+            Err(InterchainError::ChainNotFound(chain_id.to_string()))
+        }
+    }
+
+    /// Creates the channel object by providing accessors to both chains
+    pub fn into_channel<Channel>(
+        self,
+        chain_a: Channel,
+        chain_b: Channel,
+    ) -> Result<InterchainChannel<Channel>, InterchainError>
+    where
+        Channel: Clone + Send + Sync,
+    {
+        Ok(InterchainChannel::new(
+            self.port_a.into_port(chain_a)?,
+            self.port_b.into_port(chain_b)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn metadata_roundtrip() -> Result<(), InterchainError> {
+        let channel = InterchainChannel::new(
+            IbcPort {
+                chain_id: "juno-1".to_string(),
+                connection_id: Some("connection-0".to_string()),
+                port: PortId::transfer(),
+                channel: Some(ChannelId::new(3)),
+                chain: (),
+            },
+            IbcPort {
+                chain_id: "osmosis-1".to_string(),
+                connection_id: Some("connection-5".to_string()),
+                port: PortId::transfer(),
+                channel: Some(ChannelId::new(12)),
+                chain: (),
+            },
+        );
+
+        let metadata = channel.metadata();
+        let serialized = serde_json::to_string(&metadata).unwrap();
+        let deserialized: InterchainChannelMetadata = serde_json::from_str(&serialized).unwrap();
+        let restored = deserialized.into_channel((), ())?;
+
+        assert_eq!(
+            restored.port_a.port_channel_id(),
+            channel.port_a.port_channel_id()
+        );
+        assert_eq!(restored.port_b.connection()?, Some(ConnectionId::new(5)));
+        Ok(())
+    }
+
+    #[test]
+    fn identified_channel_end_roundtrip() -> Result<(), InterchainError> {
+        let juno_end = IdentifiedChannelEnd::new(
+            PortId::transfer(),
+            ChannelId::new(3),
+            ChannelEnd::new(
+                State::Open,
+                Order::Unordered,
+                Counterparty::new(PortId::transfer(), Some(ChannelId::new(12))),
+                vec![ConnectionId::new(0)],
+                Version::ics20(),
+            ),
+        );
+
+        let channel = InterchainChannel::from_identified_channel_end(
+            "juno-1",
+            (),
+            "osmosis-1",
+            (),
+            &juno_end,
+            Some(&ConnectionId::new(5)),
+        );
+        assert_eq!(channel.port_b.connection()?, Some(ConnectionId::new(5)));
+        assert_eq!(
+            channel.identified_channel_end(
+                "juno-1",
+                State::Open,
+                Order::Unordered,
+                Version::ics20()
+            )?,
+            juno_end
+        );
+
+        // The counterparty end is built from the same object
+        let osmosis_end = channel.identified_channel_end(
+            "osmosis-1",
+            State::Open,
+            Order::Unordered,
+            Version::ics20(),
+        )?;
+        assert_eq!(osmosis_end.channel_id, ChannelId::new(12));
+        assert_eq!(
+            osmosis_end.channel_end.connection_hops,
+            vec![ConnectionId::new(5)]
+        );
+        assert_eq!(
+            osmosis_end.channel_end.remote,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::new(3)))
+        );
+        let restored = InterchainChannel::from_identified_channel_end(
+            "osmosis-1",
+            (),
+            "juno-1",
+            (),
+            &osmosis_end,
+            Some(&ConnectionId::new(0)),
+        );
+        assert_eq!(restored.metadata().port_b, channel.metadata().port_a);
+
+        // The channel id is required
+        let mut unopened = channel.clone();
+        unopened.port_a.channel = None;
+        assert!(matches!(
+            unopened.identified_channel_end(
+                "juno-1",
+                State::Init,
+                Order::Unordered,
+                Version::ics20()
+            ),
+            Err(InterchainError::ChannelIdNotSpecified(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+//! Object safe version of [`InterchainEnv`], to select the interchain environment at runtime.
+//!
+//! [`InterchainEnv`] has associated types and generic methods, so it can't be used as a trait object.
+//! Every [`InterchainEnv`] implements [`DynInterchainEnv`], and a [`BoxedInterchainEnv`] implements [`InterchainEnv`] again,
+//! so code written against [`InterchainEnv`] works unchanged with an environment chosen at runtime (e.g. from a config file).
+//! The environments must share the same `Chain` type, for instance `DaemonInterchainEnv`s using different channel creators (hermes, manual validation, starship).
+//!
+//! ## Example
+//! ```ignore
+//! use cw_orch_interchain_core::{dyn_env::BoxedInterchainEnv, InterchainEnv};
+//!
+//! let interchain: BoxedInterchainEnv<Daemon> = if config.use_starship {
+//!     Box::new(DaemonInterchainEnv::from_daemons(daemons, &starship))
+//! } else {
+//!     Box::new(DaemonInterchainEnv::from_daemons(daemons, &ChannelCreationValidator))
+//! };
+//! let juno = interchain.chain("juno-1")?;
+//! ```
+
+use std::any::Any;
+
+use cosmwasm_std::IbcOrder;
+use cw_orch_core::environment::TxHandler;
+use ibc_relayer_types::core::{
+    ics04_channel::packet::Sequence,
+    ics24_host::identifier::{ChannelId, PortId},
+};
+
+use crate::{
+    channel::InterchainChannel,
+    env::ChainId,
+    types::{
+        ChannelCreationTransactionsResult, IbcPacketStatus, IbcTxAnalysis,
+        InternalChannelCreationResult, SimpleIbcPacketAnalysis,
+    },
+    IbcQueryHandler, InterchainEnv, InterchainError,
+};
+
+/// Interchain environment selected at runtime
+pub type BoxedInterchainEnv<Chain> = Box<dyn DynInterchainEnv<Chain>>;
+
+/// Object safe version of [`InterchainEnv`], implemented for all the [`InterchainEnv`]s.
+/// The errors are converted to [`InterchainError`] and the channel creation result is type erased.
+/// See the [module documentation](self)
+pub trait DynInterchainEnv<Chain: IbcQueryHandler> {
+    /// See [`InterchainEnv::chain`]
+    fn dyn_chain(&self, chain_id: &str) -> Result<Chain, InterchainError>;
+
+    /// See [`InterchainEnv::_internal_create_channel`]
+    fn dyn_internal_create_channel(
+        &self,
+        src_chain: ChainId,
+        dst_chain: ChainId,
+        src_port: &PortId,
+        dst_port: &PortId,
+        version: &str,
+        order: Option<IbcOrder>,
+    ) -> Result<InternalChannelCreationResult<Box<dyn Any>>, InterchainError>;
+
+    /// See [`InterchainEnv::get_channel_creation_txs`]
+    fn dyn_get_channel_creation_txs(
+        &self,
+        src_chain: ChainId,
+        ibc_channel: &mut InterchainChannel<<Chain as IbcQueryHandler>::Handler>,
+        channel_creation_result: Box<dyn Any>,
+    ) -> Result<ChannelCreationTransactionsResult<Chain>, InterchainError>;
+
+    /// See [`InterchainEnv::wait_ibc`]
+    fn dyn_wait_ibc(
+        &self,
+        chain_id: ChainId,
+        tx_response: <Chain as TxHandler>::Response,
+    ) -> Result<IbcTxAnalysis<Chain>, InterchainError>;
+
+    /// See [`InterchainEnv::follow_packet`]
+    fn dyn_follow_packet(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<SimpleIbcPacketAnalysis<Chain>, InterchainError>;
+
+    /// See [`InterchainEnv::packet_status`]
+    fn dyn_packet_status(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<IbcPacketStatus, InterchainError>;
+}
+
+impl<Chain: IbcQueryHandler, Env: InterchainEnv<Chain>> DynInterchainEnv<Chain> for Env
+where
+    Env::ChannelCreationResult: 'static,
+{
+    fn dyn_chain(&self, chain_id: &str) -> Result<Chain, InterchainError> {
+        self.chain(chain_id).map_err(Into::into)
+    }
+
+    fn dyn_internal_create_channel(
+        &self,
+        src_chain: ChainId,
+        dst_chain: ChainId,
+        src_port: &PortId,
+        dst_port: &PortId,
+        version: &str,
+        order: Option<IbcOrder>,
+    ) -> Result<InternalChannelCreationResult<Box<dyn Any>>, InterchainError> {
+        let InternalChannelCreationResult {
+            result,
+            src_connection_id,
+        } = self
+            ._internal_create_channel(src_chain, dst_chain, src_port, dst_port, version, order)
+            .map_err(Into::into)?;
+        Ok(InternalChannelCreationResult {
+            result: Box::new(result),
+            src_connection_id,
+        })
+    }
+
+    fn dyn_get_channel_creation_txs(
+        &self,
+        src_chain: ChainId,
+        ibc_channel: &mut InterchainChannel<<Chain as IbcQueryHandler>::Handler>,
+        channel_creation_result: Box<dyn Any>,
+    ) -> Result<ChannelCreationTransactionsResult<Chain>, InterchainError> {
+        let channel_creation_result = channel_creation_result
+            .downcast::<Env::ChannelCreationResult>()
+            .map_err(|_| {
+                InterchainError::GenericError(
+                    "Channel creation result was created by another interchain environment"
+                        .to_string(),
+                )
+            })?;
+        self.get_channel_creation_txs(src_chain, ibc_channel, *channel_creation_result)
+            .map_err(Into::into)
+    }
+
+    fn dyn_wait_ibc(
+        &self,
+        chain_id: ChainId,
+        tx_response: <Chain as TxHandler>::Response,
+    ) -> Result<IbcTxAnalysis<Chain>, InterchainError> {
+        self.wait_ibc(chain_id, tx_response).map_err(Into::into)
+    }
+
+    fn dyn_follow_packet(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<SimpleIbcPacketAnalysis<Chain>, InterchainError> {
+        self.follow_packet(src_chain, src_port, src_channel, dst_chain, sequence)
+            .map_err(Into::into)
+    }
+
+    fn dyn_packet_status(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<IbcPacketStatus, InterchainError> {
+        self.packet_status(src_chain, src_port, src_channel, dst_chain, sequence)
+            .map_err(Into::into)
+    }
+}
+
+impl<Chain: IbcQueryHandler> InterchainEnv<Chain> for BoxedInterchainEnv<Chain> {
+    type ChannelCreationResult = Box<dyn Any>;
+
+    type Error = InterchainError;
+
+    fn chain(&self, chain_id: impl ToString) -> Result<Chain, Self::Error> {
+        (**self).dyn_chain(&chain_id.to_string())
+    }
+
+    fn _internal_create_channel(
+        &self,
+        src_chain: ChainId,
+        dst_chain: ChainId,
+        src_port: &PortId,
+        dst_port: &PortId,
+        version: &str,
+        order: Option<IbcOrder>,
+    ) -> Result<InternalChannelCreationResult<Self::ChannelCreationResult>, Self::Error> {
+        (**self)
+            .dyn_internal_create_channel(src_chain, dst_chain, src_port, dst_port, version, order)
+    }
+
+    fn get_channel_creation_txs(
+        &self,
+        src_chain: ChainId,
+        ibc_channel: &mut InterchainChannel<<Chain as IbcQueryHandler>::Handler>,
+        channel_creation_result: Self::ChannelCreationResult,
+    ) -> Result<ChannelCreationTransactionsResult<Chain>, Self::Error> {
+        (**self).dyn_get_channel_creation_txs(src_chain, ibc_channel, channel_creation_result)
+    }
+
+    fn wait_ibc(
+        &self,
+        chain_id: ChainId,
+        tx_response: <Chain as TxHandler>::Response,
+    ) -> Result<IbcTxAnalysis<Chain>, Self::Error> {
+        (**self).dyn_wait_ibc(chain_id, tx_response)
+    }
+
+    fn follow_packet(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<SimpleIbcPacketAnalysis<Chain>, Self::Error> {
+        (**self).dyn_follow_packet(src_chain, src_port, src_channel, dst_chain, sequence)
+    }
+
+    fn packet_status(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<IbcPacketStatus, Self::Error> {
+        (**self).dyn_packet_status(src_chain, src_port, src_channel, dst_chain, sequence)
+    }
+}
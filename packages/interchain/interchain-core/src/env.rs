@@ -15,8 +15,8 @@ use crate::{
     channel::{IbcPort, InterchainChannel},
     types::{
         parse::SuccessIbcPacket, ChannelCreationResult, ChannelCreationTransactionsResult,
-        FullIbcPacketAnalysis, IbcPacketOutcome, IbcTxAnalysis, InternalChannelCreationResult,
-        SimpleIbcPacketAnalysis,
+        FullIbcPacketAnalysis, IbcPacketOutcome, IbcPacketStatus, IbcTxAnalysis,
+        InternalChannelCreationResult, SimpleIbcPacketAnalysis,
     },
     IbcQueryHandler, InterchainError,
 };
@@ -273,6 +273,18 @@ pub trait InterchainEnv<Chain: IbcQueryHandler> {
         dst_chain: ChainId,
         sequence: Sequence,
     ) -> Result<SimpleIbcPacketAnalysis<Chain>, Self::Error>;
+
+    /// Returns the current status of a single IBC packet, read from the state of both chains.
+    /// In contrast to [`Self::follow_packet`], this doesn't wait for the packet to be relayed,
+    /// which allows polling packets, for instance from monitoring tools.
+    fn packet_status(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<IbcPacketStatus, Self::Error>;
 }
 
 /// format the port for a contract
@@ -39,6 +39,9 @@ pub enum InterchainError {
     #[error("Chain `{0}` not found in the interchain environment. Please register it before analyzing transactions")]
     ChainNotFound(String),
 
+    #[error("The channel id on chain `{0}` is not specified")]
+    ChannelIdNotSpecified(String),
+
     #[error("Packet relaying failed, timeout received")]
     PacketTimeout {},
 
@@ -58,4 +61,25 @@ pub enum InterchainError {
 
     #[error("Failure acknowledgment received: {0:?}")]
     FailedAckReceived(String),
+
+    #[error(
+        "Unexpected {denom} balance change of the {account}: expected {expected}, found {found}"
+    )]
+    UnexpectedBalanceDelta {
+        account: String,
+        denom: String,
+        expected: i128,
+        found: i128,
+    },
+
+    #[error(
+        "{denom} escrowed on {chain_id} ({escrowed}) doesn't match the supply of {ibc_denom} on the counterparty ({vouchers})"
+    )]
+    EscrowImbalance {
+        chain_id: String,
+        denom: String,
+        ibc_denom: String,
+        escrowed: u128,
+        vouchers: u128,
+    },
 }
@@ -8,6 +8,10 @@ pub mod channel;
 /// Contains definitions of the main trait exposed by this crate
 pub mod env;
 
+pub mod dyn_env;
+
+pub mod polytone;
+
 /// Contains default acknowledgment parsers
 mod ack_parser;
 mod error;
@@ -16,6 +20,7 @@ mod error;
 pub mod types;
 
 pub use ack_parser::IbcAckParser;
+pub use dyn_env::{BoxedInterchainEnv, DynInterchainEnv};
 pub use env::InterchainEnv;
 pub use error::InterchainError;
 pub use types::IbcQueryHandler;
@@ -0,0 +1,362 @@
+//! Deployment and wiring of [Polytone](https://github.com/DA0-DA0/polytone) between two chains of an interchain environment.
+//!
+//! Polytone lets a contract (or account) on a chain execute messages on another chain through its proxy on that chain:
+//! - The note, on the controller chain, sends the messages over IBC.
+//! - The voice, on the host chain, receives them and executes them through a proxy contract it instantiates for every sender.
+//!
+//! [`Polytone::deploy`] uploads and instantiates the note and the voice, and creates the channel between them.
+//! The contracts are registered in the state of each chain under ids containing the counterparty chain id
+//! (e.g. `polytone:note | osmosis-1` on the controller chain), so [`Polytone::load`] retrieves an existing connection.
+//! The Polytone code has to be provided, as wasm files for live chains or as contract wrappers for `Mock` chains.
+//!
+//! ## Example
+//! ```ignore
+//! use cw_orch_interchain_core::polytone::{Polytone, PolytoneCode};
+//!
+//! let polytone = Polytone::deploy(
+//!     &interchain,
+//!     "juno-1",
+//!     "osmosis-1",
+//!     &PolytoneCode { note: &NoteWasm, voice: &VoiceWasm, proxy: &ProxyWasm },
+//!     DEFAULT_BLOCK_MAX_GAS,
+//! )?;
+//! // Sends 1 uosmo from the proxy of the sender on osmosis-1
+//! polytone.execute_remote(&interchain, vec![bank_send], None)?;
+//! let proxy = polytone.remote_address(&juno.sender_addr())?;
+//! ```
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Empty, IbcOrder, QueryRequest, Uint64};
+use cw_orch_core::{
+    contract::{
+        interface_traits::{
+            ContractInstance, CwOrchExecute, CwOrchInstantiate, ExecutableContract,
+            InstantiableContract, QueryableContract, Uploadable,
+        },
+        Contract,
+    },
+    environment::TxHandler,
+};
+use polytone::callbacks::CallbackRequest;
+
+use crate::{
+    env::ChainId,
+    types::{ChannelCreationResult, IbcTxAnalysis},
+    IbcQueryHandler, InterchainEnv, InterchainError,
+};
+
+/// IBC version of the channels between notes and voices
+pub const POLYTONE_VERSION: &str = "polytone-1";
+
+/// Maximum gas used by the voice to execute a packet, advised by the Polytone documentation
+pub const DEFAULT_BLOCK_MAX_GAS: u64 = 100_000_000;
+
+/// Timeout of the packets sent with [`Polytone::execute_remote`]
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 600;
+
+/// Id of the proxy code, registered on the host chain
+pub const PROXY_ID: &str = "polytone:proxy";
+
+/// Id of the note sending messages to `host_chain`
+pub fn note_id(host_chain: &str) -> String {
+    format!("polytone:note | {host_chain}")
+}
+
+/// Id of the voice receiving messages from `controller_chain`
+pub fn voice_id(controller_chain: &str) -> String {
+    format!("polytone:voice | {controller_chain}")
+}
+
+/// Messages of the Polytone note
+pub mod note {
+    use super::*;
+
+    /// Instantiate message of the note
+    #[cw_serde]
+    pub struct InstantiateMsg {
+        /// Connection and port of the voice, to restrict the channels of the note
+        pub pair: Option<Pair>,
+        /// Maximum gas used by the voice to execute a packet
+        pub block_max_gas: Uint64,
+    }
+
+    /// Connection and port of a voice
+    #[cw_serde]
+    pub struct Pair {
+        /// Connection id on the controller chain
+        pub connection_id: String,
+        /// Port of the voice
+        pub remote_port: String,
+    }
+
+    /// Execute message of the note
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        /// Queries the host chain, the result is sent to the callback
+        Query {
+            /// Queries to execute
+            msgs: Vec<QueryRequest<Empty>>,
+            /// Receiver of the result
+            callback: CallbackRequest,
+            /// Timeout of the packet
+            timeout_seconds: Uint64,
+        },
+        /// Executes messages from the proxy of the sender on the host chain
+        Execute {
+            /// Messages to execute
+            msgs: Vec<CosmosMsg<Empty>>,
+            /// Receiver of the result, if any
+            callback: Option<CallbackRequest>,
+            /// Timeout of the packet
+            timeout_seconds: Uint64,
+        },
+    }
+
+    /// Query message of the note
+    #[cw_serde]
+    pub enum QueryMsg {
+        /// Channel of the note, if any. Returns `Option<String>`
+        ActiveChannel,
+        /// Pair of the note, if any. Returns `Option<Pair>`
+        Pair,
+        /// Address of the proxy of `local_address` on the host chain, if created. Returns `Option<String>`
+        RemoteAddress {
+            /// Address on the controller chain
+            local_address: String,
+        },
+        /// Maximum gas used by the voice to execute a packet. Returns `Uint64`
+        BlockMaxGas,
+    }
+}
+
+/// Messages of the Polytone voice
+pub mod voice {
+    use super::*;
+
+    /// Instantiate message of the voice
+    #[cw_serde]
+    pub struct InstantiateMsg {
+        /// Code id of the proxies instantiated for every sender
+        pub proxy_code_id: Uint64,
+        /// Maximum gas used to execute a packet
+        pub block_max_gas: Uint64,
+        /// Length of the proxy addresses, for chains that don't use 32 bytes contract addresses (Polytone 1.1+)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub contract_addr_len: Option<u8>,
+    }
+
+    /// Query message of the voice
+    #[cw_serde]
+    pub enum QueryMsg {
+        /// Maximum gas used to execute a packet. Returns `Uint64`
+        BlockMaxGas,
+    }
+}
+
+/// Polytone note, sending messages to a voice
+#[derive(Clone)]
+pub struct PolytoneNote<Chain>(Contract<Chain>);
+
+/// Polytone voice, executing the messages of a note
+#[derive(Clone)]
+pub struct PolytoneVoice<Chain>(Contract<Chain>);
+
+impl<Chain> PolytoneNote<Chain> {
+    /// Note with this id on `chain`
+    pub fn new(id: impl ToString, chain: Chain) -> Self {
+        Self(Contract::new(id, chain))
+    }
+}
+
+impl<Chain> PolytoneVoice<Chain> {
+    /// Voice with this id on `chain`
+    pub fn new(id: impl ToString, chain: Chain) -> Self {
+        Self(Contract::new(id, chain))
+    }
+}
+
+macro_rules! polytone_interface {
+    ($interface:ident, $module:ident) => {
+        impl<Chain: TxHandler> ContractInstance<Chain> for $interface<Chain> {
+            fn as_instance(&self) -> &Contract<Chain> {
+                &self.0
+            }
+
+            fn as_instance_mut(&mut self) -> &mut Contract<Chain> {
+                &mut self.0
+            }
+        }
+
+        impl<Chain> InstantiableContract for $interface<Chain> {
+            type InstantiateMsg = $module::InstantiateMsg;
+        }
+
+        impl<Chain> QueryableContract for $interface<Chain> {
+            type QueryMsg = $module::QueryMsg;
+        }
+    };
+}
+
+polytone_interface!(PolytoneNote, note);
+polytone_interface!(PolytoneVoice, voice);
+
+impl<Chain> ExecutableContract for PolytoneNote<Chain> {
+    type ExecuteMsg = note::ExecuteMsg;
+}
+
+/// Polytone code uploaded by [`Polytone::deploy`]
+pub struct PolytoneCode<'a, N: Uploadable, V: Uploadable, P: Uploadable> {
+    /// Code of the note
+    pub note: &'a N,
+    /// Code of the voice
+    pub voice: &'a V,
+    /// Code of the proxy
+    pub proxy: &'a P,
+}
+
+/// Polytone connection between a note on the controller chain and a voice on the host chain, see the [module documentation](self)
+#[derive(Clone)]
+pub struct Polytone<Chain: IbcQueryHandler> {
+    /// Note on the controller chain
+    pub note: PolytoneNote<Chain>,
+    /// Voice on the host chain
+    pub voice: PolytoneVoice<Chain>,
+}
+
+impl<Chain: IbcQueryHandler> Polytone<Chain> {
+    /// Connection between `controller_chain` and `host_chain` registered in the state of the chains
+    pub fn load<IBC: InterchainEnv<Chain>>(
+        interchain: &IBC,
+        controller_chain: ChainId,
+        host_chain: ChainId,
+    ) -> Result<Self, InterchainError> {
+        let controller = interchain.chain(controller_chain).map_err(Into::into)?;
+        let host = interchain.chain(host_chain).map_err(Into::into)?;
+        Ok(Self {
+            note: PolytoneNote::new(note_id(host_chain), controller),
+            voice: PolytoneVoice::new(voice_id(controller_chain), host),
+        })
+    }
+
+    /// Uploads and instantiates a note on `controller_chain` and a voice on `host_chain`, then creates the channel between them
+    pub fn deploy<IBC, N, V, P>(
+        interchain: &IBC,
+        controller_chain: ChainId,
+        host_chain: ChainId,
+        code: &PolytoneCode<N, V, P>,
+        block_max_gas: u64,
+    ) -> Result<Self, InterchainError>
+    where
+        IBC: InterchainEnv<Chain>,
+        N: Uploadable,
+        V: Uploadable,
+        P: Uploadable,
+    {
+        let polytone = Self::load(interchain, controller_chain, host_chain)?;
+
+        let proxy = Contract::new(PROXY_ID, polytone.voice.get_chain().clone());
+        proxy.upload(code.proxy)?;
+        polytone.voice.as_instance().upload(code.voice)?;
+        polytone.note.as_instance().upload(code.note)?;
+
+        polytone.voice.instantiate(
+            &voice::InstantiateMsg {
+                proxy_code_id: proxy.code_id()?.into(),
+                block_max_gas: block_max_gas.into(),
+                contract_addr_len: None,
+            },
+            None,
+            None,
+        )?;
+        polytone.note.instantiate(
+            &note::InstantiateMsg {
+                pair: None,
+                block_max_gas: block_max_gas.into(),
+            },
+            None,
+            None,
+        )?;
+
+        polytone.connect(interchain)?;
+        Ok(polytone)
+    }
+
+    /// Creates a channel between the note and the voice
+    pub fn connect<IBC: InterchainEnv<Chain>>(
+        &self,
+        interchain: &IBC,
+    ) -> Result<ChannelCreationResult<Chain>, InterchainError> {
+        interchain.create_contract_channel(
+            &self.note,
+            &self.voice,
+            POLYTONE_VERSION,
+            Some(IbcOrder::Unordered),
+        )
+    }
+
+    /// Executes `msgs` on the host chain from the proxy of the sender of the controller chain and waits for the packets to be relayed.
+    /// The Polytone acknowledgement can be found in the analysis with [`crate::IbcAckParser::polytone_ack`]
+    pub fn execute_remote<IBC: InterchainEnv<Chain>>(
+        &self,
+        interchain: &IBC,
+        msgs: Vec<CosmosMsg<Empty>>,
+        callback: Option<CallbackRequest>,
+    ) -> Result<IbcTxAnalysis<Chain>, InterchainError> {
+        let response = self.note.execute(
+            &note::ExecuteMsg::Execute {
+                msgs,
+                callback,
+                timeout_seconds: DEFAULT_TIMEOUT_SECONDS.into(),
+            },
+            None,
+        )?;
+        interchain
+            .wait_ibc(&self.note.get_chain().chain_id(), response)
+            .map_err(Into::into)
+    }
+
+    /// Address of the proxy of `local_address` on the host chain, created by its first remote execution
+    pub fn remote_address(&self, local_address: &Addr) -> Result<Option<String>, InterchainError> {
+        Ok(self
+            .note
+            .as_instance()
+            .query(&note::QueryMsg::RemoteAddress {
+                local_address: local_address.to_string(),
+            })?)
+    }
+
+    /// Channel of the note on the controller chain, if connected
+    pub fn active_channel(&self) -> Result<Option<String>, InterchainError> {
+        Ok(self
+            .note
+            .as_instance()
+            .query(&note::QueryMsg::ActiveChannel)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{note, note_id, voice, voice_id};
+    use serde_json::to_value;
+
+    #[test]
+    fn polytone_msgs() -> Result<(), serde_json::Error> {
+        assert_eq!(note_id("osmosis-1"), "polytone:note | osmosis-1");
+        assert_eq!(voice_id("juno-1"), "polytone:voice | juno-1");
+        assert_eq!(
+            to_value(&note::QueryMsg::RemoteAddress {
+                local_address: "juno1sender".to_string()
+            })?,
+            serde_json::json!({"remote_address": {"local_address": "juno1sender"}})
+        );
+        assert_eq!(
+            to_value(&voice::InstantiateMsg {
+                proxy_code_id: 1u64.into(),
+                block_max_gas: 100u64.into(),
+                contract_addr_len: None,
+            })?,
+            serde_json::json!({"proxy_code_id": "1", "block_max_gas": "100"})
+        );
+        Ok(())
+    }
+}
@@ -79,6 +79,28 @@ pub struct IbcPacketInfo {
     pub dst_chain_id: NetworkId,
 }
 
+/// Status of a packet read from the chains state at a point in time, see [`crate::InterchainEnv::packet_status`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IbcPacketStatus {
+    /// No packet with this sequence is known on the channel
+    NotFound,
+    /// The packet was sent and is waiting to be received on the destination chain
+    Committed,
+    /// The packet was received on the destination chain, its acknowledgement is waiting to be relayed back
+    Received,
+    /// The acknowledgement of the packet was relayed back to the source chain
+    Acknowledged,
+    /// The timeout of the packet was relayed back to the source chain
+    TimedOut,
+}
+
+impl IbcPacketStatus {
+    /// Returns `true` if the packet still needs to be relayed
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Committed | Self::Received)
+    }
+}
+
 /// Adds additional capabilities to CwEnv for use with ibc environments
 pub trait IbcQueryHandler: CwEnv {
     /// Query handler for the environment
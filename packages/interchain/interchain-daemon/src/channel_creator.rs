@@ -1,13 +1,14 @@
 use cosmwasm_std::IbcOrder;
+use cw_orch_daemon::Daemon;
 use cw_orch_interchain_core::env::ChainId;
 use cw_orch_starship::Starship;
 use dialoguer::Input;
-use ibc_relayer_types::core::ics24_host::identifier::PortId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 
 use crate::{interchain_env::DaemonInterchainEnv, InterchainDaemonError};
 
 /// Used for allowing multiple interaction types with the Daemon interchain environment
-pub trait ChannelCreator: Clone {
+pub trait ChannelCreator: Clone + 'static {
     /// Creates a channel for the interchain environement
     /// Returns the connection id used for creating the channel
     fn create_ibc_channel(
@@ -22,6 +23,22 @@ pub trait ChannelCreator: Clone {
 
     /// Returns an interchain environment from the channel creator object
     fn interchain_env(&self) -> DaemonInterchainEnv<Self>;
+
+    /// Called every time a chain is accessed through the interchain environment, before returning it
+    fn on_chain_access(&self, _chain: &Daemon) -> Result<(), InterchainDaemonError> {
+        Ok(())
+    }
+
+    /// Called before waiting for a packet sent from `src_chain` on `src_port`/`src_channel` to be relayed.
+    /// This allows relaying the packet when no relayer is running in the background
+    fn on_packet_await(
+        &self,
+        _src_chain: ChainId,
+        _src_port: &PortId,
+        _src_channel: &ChannelId,
+    ) -> Result<(), InterchainDaemonError> {
+        Ok(())
+    }
 }
 
 /// This is a struct for use with actual RPCs where you want to create you channel manually
@@ -76,6 +93,12 @@ impl ChannelCreator for Starship {
         Ok(connection_id)
     }
 
+    /// Funds the sender from the faucet of the chain, if enabled with [`Starship::auto_fund`]
+    fn on_chain_access(&self, chain: &Daemon) -> Result<(), InterchainDaemonError> {
+        self.fund_sender_once(chain)?;
+        Ok(())
+    }
+
     fn interchain_env(&self) -> DaemonInterchainEnv<Self> {
         DaemonInterchainEnv::from_daemons(
             &self.rt_handle,
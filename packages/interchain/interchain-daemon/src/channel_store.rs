@@ -0,0 +1,130 @@
+//! Persistence of IBC channels in the daemon state.
+//!
+//! Channels created or discovered in a script can be saved under a logical name (e.g. `"juno-osmosis-transfer"`).
+//! They are stored in the `ibc` section of the state of both chains of the channel, so later runs can
+//! load them by name instead of stashing channel ids in ad-hoc files or environment variables.
+//!
+//! ```no_run
+//! use cw_orch_daemon::networks::{JUNO_1, OSMOSIS_1};
+//! use cw_orch_interchain_daemon::{ChannelCreationValidator, DaemonInterchainEnv};
+//! use ibc_relayer_types::core::ics24_host::identifier::PortId;
+//!
+//! let rt = tokio::runtime::Runtime::new().unwrap();
+//! let interchain = DaemonInterchainEnv::new(
+//!     rt.handle(),
+//!     vec![(JUNO_1, None), (OSMOSIS_1, None)],
+//!     &ChannelCreationValidator,
+//! )
+//! .unwrap();
+//!
+//! // First run: create the channel and save it
+//! interchain
+//!     .create_named_channel(
+//!         "juno-osmosis-transfer",
+//!         "juno-1",
+//!         "osmosis-1",
+//!         &PortId::transfer(),
+//!         &PortId::transfer(),
+//!         "ics20-1",
+//!         None,
+//!     )
+//!     .unwrap();
+//!
+//! // Later runs: load it by name
+//! let channel = interchain
+//!     .load_channel("juno-1", "juno-osmosis-transfer")
+//!     .unwrap()
+//!     .unwrap();
+//! ```
+
+use cosmwasm_std::IbcOrder;
+use cw_orch_core::environment::ChainState;
+use cw_orch_interchain_core::{
+    channel::{InterchainChannel, InterchainChannelMetadata},
+    env::ChainId,
+    InterchainEnv,
+};
+use ibc_relayer_types::core::ics24_host::identifier::PortId;
+use tonic::transport::Channel;
+
+use crate::{channel_creator::ChannelCreator, DaemonInterchainEnv, IcDaemonResult};
+
+impl<C: ChannelCreator> DaemonInterchainEnv<C> {
+    /// Saves the channel under `name` in the state of both chains of the channel.
+    /// An existing channel with the same name is replaced
+    pub fn save_channel(
+        &self,
+        name: &str,
+        channel: &InterchainChannel<Channel>,
+        version: Option<&str>,
+    ) -> IcDaemonResult<()> {
+        let mut metadata = channel.metadata();
+        metadata.version = version.map(ToString::to_string);
+        for chain_id in [&metadata.port_a.chain_id, &metadata.port_b.chain_id] {
+            self.chain(chain_id)?.state().set_ibc(name, &metadata)?;
+        }
+        log::info!("Saved channel {name} in the state");
+        Ok(())
+    }
+
+    /// Metadata of the channel saved under `name` in the state of `chain_id`, if any
+    pub fn channel_metadata(
+        &self,
+        chain_id: ChainId,
+        name: &str,
+    ) -> IcDaemonResult<Option<InterchainChannelMetadata>> {
+        Ok(self.chain(chain_id)?.state().get_ibc(name)?)
+    }
+
+    /// Loads the channel saved under `name` in the state of `chain_id`, if any.
+    /// Both chains of the channel need to be registered in the environment
+    pub fn load_channel(
+        &self,
+        chain_id: ChainId,
+        name: &str,
+    ) -> IcDaemonResult<Option<InterchainChannel<Channel>>> {
+        let Some(metadata) = self.channel_metadata(chain_id, name)? else {
+            return Ok(None);
+        };
+        let chain_a = self.chain(&metadata.port_a.chain_id)?.channel();
+        let chain_b = self.chain(&metadata.port_b.chain_id)?.channel();
+        Ok(Some(metadata.into_channel(chain_a, chain_b)?))
+    }
+
+    /// Names of the channels saved in the state of `chain_id`
+    pub fn saved_channels(&self, chain_id: ChainId) -> IcDaemonResult<Vec<String>> {
+        Ok(self.chain(chain_id)?.state().ibc_names()?)
+    }
+
+    /// Removes the channel saved under `name` from the state of both chains of the channel
+    pub fn forget_channel(&self, chain_id: ChainId, name: &str) -> IcDaemonResult<()> {
+        let Some(metadata) = self.channel_metadata(chain_id, name)? else {
+            return Ok(());
+        };
+        for chain_id in [&metadata.port_a.chain_id, &metadata.port_b.chain_id] {
+            // The counterparty chain may not be registered in the environment
+            if let Ok(chain) = self.chain(chain_id) {
+                chain.state().remove_ibc(name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a channel (see [`InterchainEnv::create_channel`]) and saves it under `name`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_named_channel(
+        &self,
+        name: &str,
+        src_chain: ChainId,
+        dst_chain: ChainId,
+        src_port: &PortId,
+        dst_port: &PortId,
+        version: &str,
+        order: Option<IbcOrder>,
+    ) -> IcDaemonResult<InterchainChannel<Channel>> {
+        let result =
+            self.create_channel(src_chain, dst_chain, src_port, dst_port, version, order)?;
+        self.save_channel(name, &result.interchain_channel, Some(version))?;
+        Ok(result.interchain_channel)
+    }
+}
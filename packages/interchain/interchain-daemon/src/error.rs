@@ -34,6 +34,9 @@ pub enum InterchainDaemonError {
     #[error("Could not find hermes container. Ensure it is running.")]
     HermesContainerNotFound,
 
+    #[error("Error interacting with hermes: {0}")]
+    HermesCommand(String),
+
     #[error("daemon for chain {0} not found")]
     DaemonNotFound(String),
 
@@ -45,6 +48,9 @@ pub enum InterchainDaemonError {
 
     #[error("Configuration already registered for chain {0}")]
     AlreadyRegistered(String),
+
+    #[error("Deployment failed on {0}")]
+    ParallelDeploymentFailed(String),
 }
 
 impl From<InterchainDaemonError> for InterchainError {
@@ -0,0 +1,314 @@
+//! Driver for a locally installed [Hermes](https://hermes.informal.systems/) relayer.
+//! This allows creating channels and clearing packets in devnet setups that are not managed by Starship
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use cosmwasm_std::IbcOrder;
+use cw_orch_interchain_core::env::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use serde_json::Value;
+
+use crate::{
+    channel_creator::ChannelCreator, interchain_env::DaemonInterchainEnv, IcDaemonResult,
+    InterchainDaemonError,
+};
+
+const DEFAULT_HERMES_BINARY: &str = "hermes";
+
+/// Hermes relayer driver that shells out to the `hermes` CLI.
+/// The chains used inside the interchain environment need to be registered in the hermes configuration.
+/// By default, pending packets are cleared on their channel before waiting for them to be relayed,
+/// so no hermes instance needs to be running in the background.
+///
+/// ## Example
+/// ```ignore
+/// # use cw_orch_interchain_daemon::{DaemonInterchainEnv, HermesRelayer};
+/// # let rt = tokio::runtime::Runtime::new().unwrap();
+/// let hermes = HermesRelayer::new().config("./hermes/config.toml");
+/// let interchain = DaemonInterchainEnv::new(
+///     rt.handle(),
+///     vec![(cw_orch_networks::networks::LOCAL_JUNO, None)],
+///     &hermes,
+/// ).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct HermesRelayer {
+    binary: PathBuf,
+    config: Option<PathBuf>,
+    clear_packets_on_await: bool,
+}
+
+impl Default for HermesRelayer {
+    fn default() -> Self {
+        Self {
+            binary: DEFAULT_HERMES_BINARY.into(),
+            config: None,
+            clear_packets_on_await: true,
+        }
+    }
+}
+
+impl HermesRelayer {
+    /// Creates a driver that uses the `hermes` binary available in the `PATH` with its default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path to the hermes binary
+    pub fn binary(mut self, binary: impl Into<PathBuf>) -> Self {
+        self.binary = binary.into();
+        self
+    }
+
+    /// Sets the path to the hermes configuration file
+    pub fn config(mut self, config: impl Into<PathBuf>) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    /// Sets whether pending packets are cleared on their channel before waiting for them to be relayed.
+    /// Disable this when a hermes instance is already relaying packets in the background
+    pub fn clear_packets_on_await(mut self, clear: bool) -> Self {
+        self.clear_packets_on_await = clear;
+        self
+    }
+
+    /// Returns the id of a connection between the 2 chains
+    /// Creates a new connection (and the associated clients) if none exists
+    pub fn connection(&self, chain_a: ChainId, chain_b: ChainId) -> IcDaemonResult<String> {
+        if let Some(connection_id) = self.query_connection(chain_a, chain_b)? {
+            return Ok(connection_id);
+        }
+
+        log::info!(
+            "No connection found between {} and {}, creating one with hermes",
+            chain_a,
+            chain_b
+        );
+        self.run(&[
+            "create",
+            "connection",
+            "--a-chain",
+            chain_a,
+            "--b-chain",
+            chain_b,
+        ])?;
+
+        self.query_connection(chain_a, chain_b)?
+            .ok_or(InterchainDaemonError::HermesCommand(format!(
+                "connection between {} and {} not found after creation",
+                chain_a, chain_b
+            )))
+    }
+
+    /// Relays all pending packets and acknowledgements on a channel
+    /// This is useful when no hermes instance is running in the background to relay packets
+    pub fn clear_packets(
+        &self,
+        chain_id: ChainId,
+        port: &PortId,
+        channel: &ChannelId,
+    ) -> IcDaemonResult<()> {
+        self.run(&[
+            "clear",
+            "packets",
+            "--chain",
+            chain_id,
+            "--port",
+            port.as_str(),
+            "--channel",
+            channel.as_str(),
+        ])?;
+        Ok(())
+    }
+
+    fn query_connection(
+        &self,
+        chain_a: ChainId,
+        chain_b: ChainId,
+    ) -> IcDaemonResult<Option<String>> {
+        let result = self.run(&[
+            "query",
+            "connections",
+            "--chain",
+            chain_a,
+            "--counterparty-chain",
+            chain_b,
+        ])?;
+
+        Ok(result
+            .as_array()
+            .and_then(|connections| connections.last())
+            .and_then(|connection| connection.as_str())
+            .map(ToString::to_string))
+    }
+
+    /// Executes a hermes command with json output and returns the `result` field of the output
+    fn run(&self, args: &[&str]) -> IcDaemonResult<Value> {
+        let mut command = self.command(args);
+        log::debug!("Executing hermes command: {:?}", command);
+        let output = command.output()?;
+
+        parse_output(
+            args,
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+            output.status.success(),
+        )
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(&self.binary);
+        if let Some(config) = &self.config {
+            command.arg("--config").arg(config);
+        }
+        command.arg("--json").args(args);
+        command
+    }
+}
+
+/// Returns the `result` field of the output of a hermes command.
+/// Hermes outputs one json object per line (logs included), the last one contains the command result
+fn parse_output(args: &[&str], stdout: &str, stderr: &str, success: bool) -> IcDaemonResult<Value> {
+    let last_line: Value = stdout
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .last()
+        .ok_or_else(|| {
+            InterchainDaemonError::HermesCommand(format!(
+                "no output for `hermes {}`: {}",
+                args.join(" "),
+                stderr
+            ))
+        })?;
+
+    if !success || last_line["status"] != "success" {
+        return Err(InterchainDaemonError::HermesCommand(format!(
+            "`hermes {}` failed: {}",
+            args.join(" "),
+            last_line["result"]
+        )));
+    }
+
+    Ok(last_line["result"].clone())
+}
+
+impl ChannelCreator for HermesRelayer {
+    fn create_ibc_channel(
+        &self,
+        src_chain: ChainId,
+        dst_chain: ChainId,
+        src_port: &PortId,
+        dst_port: &PortId,
+        version: &str,
+        order: Option<IbcOrder>,
+    ) -> Result<String, InterchainDaemonError> {
+        let connection_id = self.connection(src_chain, dst_chain)?;
+
+        let mut command = vec![
+            "create",
+            "channel",
+            "--a-chain",
+            src_chain,
+            "--a-connection",
+            connection_id.as_str(),
+            "--a-port",
+            src_port.as_str(),
+            "--b-port",
+            dst_port.as_str(),
+            "--channel-version",
+            version,
+        ];
+
+        if let Some(order) = order {
+            let order_string = match order {
+                IbcOrder::Unordered => "unordered",
+                IbcOrder::Ordered => "ordered",
+            };
+            command.push("--order");
+            command.push(order_string);
+        }
+
+        self.run(&command)?;
+        log::info!("Channel was created with hermes !");
+
+        Ok(connection_id)
+    }
+
+    fn on_packet_await(
+        &self,
+        src_chain: ChainId,
+        src_port: &PortId,
+        src_channel: &ChannelId,
+    ) -> Result<(), InterchainDaemonError> {
+        if self.clear_packets_on_await {
+            self.clear_packets(src_chain, src_port, src_channel)?;
+        }
+        Ok(())
+    }
+
+    fn interchain_env(&self) -> DaemonInterchainEnv<Self> {
+        panic!("Hermes doesn't know about the chains it relays between. Use DaemonInterchainEnv::new() or DaemonInterchainEnv::from_daemons() with this channel creator")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn command_construction() {
+        let hermes = HermesRelayer::new();
+        let command = hermes.command(&["clear", "packets", "--chain", "juno-1"]);
+        assert_eq!(command.get_program(), "hermes");
+        assert_eq!(
+            args(&command),
+            ["--json", "clear", "packets", "--chain", "juno-1"]
+        );
+
+        let hermes = HermesRelayer::new()
+            .binary("/opt/hermes")
+            .config("./hermes/config.toml");
+        let command = hermes.command(&["query", "connections"]);
+        assert_eq!(command.get_program(), "/opt/hermes");
+        assert_eq!(
+            args(&command),
+            [
+                "--config",
+                "./hermes/config.toml",
+                "--json",
+                "query",
+                "connections"
+            ]
+        );
+    }
+
+    #[test]
+    fn output_parsing() {
+        let args = ["query", "connections"];
+        let stdout = r#"{"timestamp":"Oct 17 15:00:00.000","level":"INFO","fields":{"message":"using default configuration"}}
+not json
+{"result":["connection-0","connection-3"],"status":"success"}"#;
+        let result = parse_output(&args, stdout, "", true).unwrap();
+        assert_eq!(result[1], "connection-3");
+
+        let failed = r#"{"result":"chain juno-1 not found","status":"error"}"#;
+        let error = parse_output(&args, failed, "", false).unwrap_err();
+        assert!(error.to_string().contains("chain juno-1 not found"));
+
+        // A non-zero exit code fails even with a success output
+        let success = r#"{"result":[],"status":"success"}"#;
+        assert!(parse_output(&args, success, "", false).is_err());
+
+        let error = parse_output(&args, "", "config.toml not found", false).unwrap_err();
+        assert!(error.to_string().contains("config.toml not found"));
+    }
+}
@@ -1,7 +1,7 @@
 use cosmwasm_std::IbcOrder;
 use cw_orch_core::environment::{ChainInfoOwned, ChainState, IndexResponse};
 use cw_orch_daemon::queriers::{Ibc, Node};
-use cw_orch_daemon::{CosmTxResponse, Daemon, DaemonError};
+use cw_orch_daemon::{runtime::RuntimeHandle, CosmTxResponse, Daemon, DaemonError};
 use cw_orch_interchain_core::channel::{IbcPort, InterchainChannel};
 use cw_orch_interchain_core::env::{ChainId, ChannelCreation};
 use cw_orch_interchain_core::InterchainEnv;
@@ -13,16 +13,18 @@ use tonic::transport::Channel;
 use crate::channel_creator::{ChannelCreationValidator, ChannelCreator};
 use crate::interchain_log::InterchainLog;
 use crate::packet_inspector::PacketInspector;
+use crate::parallel::ParallelDeployment;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 
 use crate::{IcDaemonResult, InterchainDaemonError};
 
 use cw_orch_interchain_core::types::{
-    ChannelCreationTransactionsResult, IbcTxAnalysis, InternalChannelCreationResult, NetworkId,
-    SimpleIbcPacketAnalysis,
+    ChannelCreationTransactionsResult, IbcPacketStatus, IbcTxAnalysis,
+    InternalChannelCreationResult, NetworkId, SimpleIbcPacketAnalysis,
 };
 use futures::future::try_join4;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::str::FromStr;
 use std::time::Duration;
 use tokio::runtime::Handle;
@@ -130,6 +132,58 @@ impl<C: ChannelCreator> DaemonInterchainEnv<C> {
         self.add_to_log(daemons)
     }
 
+    /// Runs `deploy` against the daemon of every chain concurrently, each on its own thread with its own sender.
+    /// The errors are collected per chain instead of stopping the other deployments.
+    /// The daemons share their state, so the state file is written once all the deployments are done
+    pub fn deploy_parallel<R, E, F>(&self, deploy: F) -> ParallelDeployment<R, E>
+    where
+        F: Fn(Daemon) -> Result<R, E> + Sync,
+        R: Send,
+        E: Send,
+    {
+        let results = std::thread::scope(|scope| {
+            let deploy = &deploy;
+            let handles: Vec<_> = self
+                .daemons
+                .iter()
+                .map(|(chain_id, daemon)| {
+                    let daemon = daemon.clone();
+                    let handle = std::thread::Builder::new()
+                        .name(format!("deploy-{chain_id}"))
+                        .spawn_scoped(scope, move || deploy(daemon))
+                        .expect("Failed to spawn the deployment thread");
+                    (chain_id.clone(), handle)
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|(chain_id, handle)| {
+                    let result = handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                    if result.is_err() {
+                        log::warn!("Deployment failed on {chain_id}");
+                    }
+                    (chain_id, result)
+                })
+                .collect()
+        });
+
+        for daemon in self.daemons.values() {
+            let mut state = daemon.state();
+            if !state.is_read_only() {
+                if let Err(e) = state.force_write() {
+                    log::warn!(
+                        "Failed to write the state of {}: {e}",
+                        state.chain_data.chain_id
+                    );
+                }
+            }
+        }
+
+        ParallelDeployment { results }
+    }
+
     // Adds the daemon to the log environment
     fn add_to_log(&mut self, daemons: Vec<Daemon>) {
         if let Some(log) = self.log.as_mut() {
@@ -150,10 +204,12 @@ impl<C: ChannelCreator> InterchainEnv<Daemon> for DaemonInterchainEnv<C> {
 
     /// Get the daemon for a network-id in the interchain.
     fn chain(&self, chain_id: impl ToString) -> Result<Daemon, InterchainDaemonError> {
-        self.daemons
+        let daemon = self
+            .daemons
             .get(&chain_id.to_string())
-            .ok_or(InterchainDaemonError::DaemonNotFound(chain_id.to_string()))
-            .cloned()
+            .ok_or(InterchainDaemonError::DaemonNotFound(chain_id.to_string()))?;
+        self.channel_creator.on_chain_access(daemon)?;
+        Ok(daemon.clone())
     }
 
     // In a daemon environmment, you don't create a channel between 2 chains, instead you just do it with external tools and returns here when the channel is ready
@@ -238,9 +294,7 @@ impl<C: ChannelCreator> InterchainEnv<Daemon> for DaemonInterchainEnv<C> {
         );
 
         // We crate an interchain env object that is safe to send between threads
-        let interchain_env = self
-            .rt_handle
-            .block_on(PacketInspector::new(self.daemons.values().collect()))?;
+        let interchain_env = self.packet_inspector()?;
 
         // We follow the trail
         let ibc_trail = self
@@ -260,9 +314,7 @@ impl<C: ChannelCreator> InterchainEnv<Daemon> for DaemonInterchainEnv<C> {
         sequence: Sequence,
     ) -> Result<SimpleIbcPacketAnalysis<Daemon>, Self::Error> {
         // We crate an interchain env object that is safe to send between threads
-        let interchain_env = self
-            .rt_handle
-            .block_on(PacketInspector::new(self.daemons.values().collect()))?;
+        let interchain_env = self.packet_inspector()?;
 
         // We follow the trail
         let ibc_trail = self.rt_handle.block_on(interchain_env.follow_packet(
@@ -275,9 +327,44 @@ impl<C: ChannelCreator> InterchainEnv<Daemon> for DaemonInterchainEnv<C> {
 
         Ok(ibc_trail)
     }
+
+    // This function reads the status of an IBC packet without waiting for it to be relayed
+    fn packet_status(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<IbcPacketStatus, Self::Error> {
+        let interchain_env = self
+            .rt_handle
+            .block_on(PacketInspector::new(self.daemons.values().collect()))?;
+
+        self.rt_handle.block_on(interchain_env.packet_status(
+            src_chain,
+            src_port,
+            src_channel,
+            dst_chain,
+            sequence,
+        ))
+    }
 }
 
 impl<C: ChannelCreator> DaemonInterchainEnv<C> {
+    /// Packet inspector over the chains of the environment, relaying awaited packets through the channel creator
+    fn packet_inspector(&self) -> IcDaemonResult<PacketInspector> {
+        let channel_creator = self.channel_creator.clone();
+        let inspector = self
+            .rt_handle
+            .block_on(PacketInspector::new(self.daemons.values().collect()))?;
+        Ok(inspector.with_packet_awaiter(Rc::new(
+            move |chain_id: &str, port: &PortId, channel: &ChannelId| {
+                channel_creator.on_packet_await(chain_id, port, channel)
+            },
+        )))
+    }
+
     /// This function follows every IBC packet sent out in a tx result
     /// This allows only providing the transaction hash when you don't have access to the whole response object
     pub fn wait_ibc_from_txhash(
@@ -442,6 +529,12 @@ impl<C: ChannelCreator> DaemonInterchainEnv<C> {
     }
 }
 
+impl<C: ChannelCreator> RuntimeHandle for DaemonInterchainEnv<C> {
+    fn runtime_handle(&self) -> Handle {
+        self.rt_handle.clone()
+    }
+}
+
 async fn find_one_tx_by_events(
     port: IbcPort<Channel>,
     events: Vec<String>,
@@ -3,9 +3,12 @@
 //! This also adds more helpers in the daemon case
 
 mod channel_creator;
+pub mod channel_store;
 pub mod error;
+mod hermes;
 mod interchain_env;
 pub mod packet_inspector;
+pub mod parallel;
 // Tracking IBC state
 pub mod ibc_tracker;
 pub mod interchain_log;
@@ -17,5 +20,6 @@ pub type IcDaemonResult<R> = Result<R, InterchainDaemonError>;
 
 /// We want to export some major elements
 pub use channel_creator::{ChannelCreationValidator, ChannelCreator};
+pub use hermes::HermesRelayer;
 
 pub use interchain_env::DaemonInterchainEnv;
@@ -1,7 +1,7 @@
 //! Module for tracking a specific packet inside the interchain
 
 use cw_orch_core::environment::{ChainInfoOwned, ChainState};
-use cw_orch_daemon::networks::parse_network;
+use cw_orch_daemon::network_config::parse_network;
 use cw_orch_daemon::queriers::{Ibc, Node};
 use cw_orch_daemon::GrpcChannel;
 use cw_orch_daemon::TxResultBlockEvent;
@@ -14,8 +14,8 @@ use ibc_relayer_types::core::ics04_channel::channel::State;
 
 use crate::{IcDaemonResult, InterchainDaemonError};
 use cw_orch_interchain_core::types::{
-    FullIbcPacketAnalysis, IbcPacketAnalysis, IbcPacketInfo, IbcPacketOutcome, IbcTxAnalysis,
-    NetworkId, SimpleIbcPacketAnalysis, TxId,
+    FullIbcPacketAnalysis, IbcPacketAnalysis, IbcPacketInfo, IbcPacketOutcome, IbcPacketStatus,
+    IbcTxAnalysis, NetworkId, SimpleIbcPacketAnalysis, TxId,
 };
 
 use futures::future::try_join_all;
@@ -24,14 +24,18 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use tonic::transport::Channel;
 
 use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Hook called before waiting for a packet sent from a chain on a port and channel, see [`crate::ChannelCreator::on_packet_await`]
+pub(crate) type PacketAwaiter = Rc<dyn Fn(ChainId, &PortId, &ChannelId) -> IcDaemonResult<()>>;
 
 /// Environment used to track IBC execution and updates on multiple chains.
 /// This can be used to track specific IBC packets or get general information update on channels between multiple chains
-/// This struct is safe to be sent between threads
-/// In contrary to InterchainStructure that holds Daemon in its definition which is not sendable
+/// In contrary to InterchainStructure, this struct only holds gRPC channels to the chains
 #[derive(Default, Clone)]
 pub(crate) struct PacketInspector {
     registered_chains: HashMap<NetworkId, Channel>,
+    packet_awaiter: Option<PacketAwaiter>,
 }
 
 // / TODO, change this doc comment that is not up to date anymore
@@ -65,6 +69,12 @@ impl PacketInspector {
         Ok(env)
     }
 
+    /// Sets the hook called before waiting for every followed packet
+    pub fn with_packet_awaiter(mut self, packet_awaiter: PacketAwaiter) -> Self {
+        self.packet_awaiter = Some(packet_awaiter);
+        self
+    }
+
     /// Following the IBC documentation of packets here : https://github.com/CosmWasm/cosmwasm/blob/main/IBC.md
     /// This function retrieves all ibc packets sent out during a transaction and follows them until they are acknoledged back on the sending chain
     ///
@@ -175,7 +185,7 @@ impl PacketInspector {
     }
 
     /// Gets the grpc channel associed with a specific `chain_id`
-    /// If it's not registered in this struct (using the `add_custom_chain` member), it will query the grpc from the chain regisry (`networks::parse_network` function)
+    /// If it's not registered in this struct (using the `add_custom_chain` member), it will query the grpc from the chain regisry (`network_config::parse_network` function, which includes the user defined networks)
     async fn get_grpc_channel<'a>(&self, chain_id: ChainId<'a>) -> IcDaemonResult<Channel> {
         let grpc_channel = self.registered_chains.get(chain_id);
 
@@ -183,7 +193,7 @@ impl PacketInspector {
             Ok(dst_grpc_channel.clone())
         } else {
             // If no custom channel was registered, we try to get it from the registry
-            let chain_data: ChainInfoOwned = parse_network(chain_id).unwrap().into(); // TODO, no unwrap here ?
+            let chain_data: ChainInfoOwned = parse_network(chain_id).unwrap(); // TODO, no unwrap here ?
             Ok(GrpcChannel::connect(&chain_data.grpc_urls, chain_id).await?)
         }
     }
@@ -198,6 +208,10 @@ impl PacketInspector {
         dst_chain: ChainId<'a>,
         sequence: Sequence,
     ) -> IcDaemonResult<SimpleIbcPacketAnalysis<Daemon>> {
+        if let Some(packet_awaiter) = &self.packet_awaiter {
+            packet_awaiter(src_chain, &src_port, &src_channel)?;
+        }
+
         let src_grpc_channel = self.get_grpc_channel(src_chain).await?;
         let dst_grpc_channel = self.get_grpc_channel(dst_chain).await?;
 
@@ -247,6 +261,72 @@ impl PacketInspector {
         result
     }
 
+    /// Reads the current status of a packet without waiting for it to be relayed
+    /// 1. The packet commitment is deleted from the source chain once the packet is acknowledged or timed out
+    /// 2. The destination chain stores a receipt (or increases its next receive sequence for ordered channels) when the packet is received
+    /// 3. Packets that were neither committed nor received are looked up in the timeout transactions of the source chain
+    pub async fn packet_status<'a>(
+        &self,
+        src_chain: ChainId<'a>,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId<'a>,
+        sequence: Sequence,
+    ) -> IcDaemonResult<IbcPacketStatus> {
+        let src_grpc_channel = self.get_grpc_channel(src_chain).await?;
+        let dst_grpc_channel = self.get_grpc_channel(dst_chain).await?;
+
+        let src_ibc = Ibc::new_async(src_grpc_channel.clone());
+        let counterparty = src_ibc
+            ._channel(src_port.to_string(), src_channel.to_string())
+            .await?
+            .counterparty
+            .ok_or(DaemonError::ibc_err(format!(
+                "No counterparty registered for channel {} on port {}",
+                src_channel, src_port
+            )))?;
+
+        // The commitment stays on the source chain until the ack or timeout is relayed back
+        let committed = !src_ibc
+            ._unreceived_acks(
+                src_port.to_string(),
+                src_channel.to_string(),
+                vec![sequence.into()],
+            )
+            .await?
+            .is_empty();
+        let received = Ibc::new_async(dst_grpc_channel)
+            ._unreceived_packets(
+                counterparty.port_id,
+                counterparty.channel_id,
+                vec![sequence.into()],
+            )
+            .await?
+            .is_empty();
+
+        let status = match (committed, received) {
+            (true, false) => IbcPacketStatus::Committed,
+            (true, true) => IbcPacketStatus::Received,
+            (false, true) => IbcPacketStatus::Acknowledged,
+            (false, false) => {
+                let timeout_events = vec![
+                    format!("timeout_packet.packet_src_port='{}'", src_port),
+                    format!("timeout_packet.packet_src_channel='{}'", src_channel),
+                    format!("timeout_packet.packet_sequence='{}'", sequence),
+                ];
+                let timeout_txs = Node::new_async(src_grpc_channel)
+                    ._find_tx_by_events(timeout_events, None, None)
+                    .await?;
+                if timeout_txs.is_empty() {
+                    IbcPacketStatus::NotFound
+                } else {
+                    IbcPacketStatus::TimedOut
+                }
+            }
+        };
+        Ok(status)
+    }
+
     /// This functions follows an IBC packet on the remote chain and back on its origin chain. It returns all encountered tx hashes
     /// 1. Receive packet. We use the identification of the packet to find the tx in which the packet was received
     ///     We make sure that only one transaction tracks receiving this packet.
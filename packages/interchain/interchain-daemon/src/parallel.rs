@@ -0,0 +1,82 @@
+//! Results of the deployments run on every chain of a [`DaemonInterchainEnv`](crate::DaemonInterchainEnv) concurrently,
+//! see [`DaemonInterchainEnv::deploy_parallel`](crate::DaemonInterchainEnv::deploy_parallel).
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use cw_orch_interchain_core::types::NetworkId;
+
+use crate::InterchainDaemonError;
+
+/// Outcome of a deployment on every chain, indexed by chain id
+#[derive(Debug)]
+pub struct ParallelDeployment<R, E> {
+    /// Result of the deployment on each chain
+    pub results: BTreeMap<NetworkId, Result<R, E>>,
+}
+
+impl<R, E> ParallelDeployment<R, E> {
+    /// Returns true if the deployment succeeded on all the chains
+    pub fn is_success(&self) -> bool {
+        self.results.values().all(Result::is_ok)
+    }
+
+    /// Errors of the chains where the deployment failed
+    pub fn errors(&self) -> impl Iterator<Item = (&NetworkId, &E)> {
+        self.results
+            .iter()
+            .filter_map(|(chain_id, result)| result.as_ref().err().map(|e| (chain_id, e)))
+    }
+
+    /// Ids of the chains where the deployment succeeded
+    pub fn succeeded(&self) -> Vec<&NetworkId> {
+        self.results
+            .iter()
+            .filter(|(_, result)| result.is_ok())
+            .map(|(chain_id, _)| chain_id)
+            .collect()
+    }
+}
+
+impl<R, E: Display> ParallelDeployment<R, E> {
+    /// Results of all the chains, or an error listing the errors of every chain where the deployment failed
+    pub fn into_result(self) -> Result<BTreeMap<NetworkId, R>, InterchainDaemonError> {
+        if !self.is_success() {
+            return Err(InterchainDaemonError::ParallelDeploymentFailed(
+                self.errors()
+                    .map(|(chain_id, e)| format!("{chain_id}: {e}"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        Ok(self
+            .results
+            .into_iter()
+            .filter_map(|(chain_id, result)| result.ok().map(|r| (chain_id, r)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ParallelDeployment;
+
+    #[test]
+    fn aggregates_errors() {
+        let deployment = ParallelDeployment {
+            results: [
+                ("juno-1".to_string(), Ok(1u64)),
+                ("osmosis-1".to_string(), Err("out of gas")),
+                ("stargaze-1".to_string(), Err("unauthorized")),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert!(!deployment.is_success());
+        assert_eq!(deployment.succeeded(), vec!["juno-1"]);
+        assert_eq!(
+            deployment.into_result().unwrap_err().to_string(),
+            "Deployment failed on osmosis-1: out of gas, stargaze-1: unauthorized"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Bookkeeping of the channels relayed by the mock relayer
+//! This allows the mock interchain environment to enforce channel ordering and closure semantics
+
+use cosmwasm_std::IbcOrder;
+use cw_orch_interchain_core::types::IbcPacketStatus;
+use std::collections::HashMap;
+
+use crate::InterchainMockError;
+
+/// Identifies one end of a channel: `(chain_id, port_id, channel_id)`
+pub type ChannelEnd = (String, String, String);
+
+/// Lifecycle status of a channel, as seen by the mock relayer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockChannelStatus {
+    /// The channel accepts and relays packets
+    Open,
+    /// The channel has been closed, no packet can be relayed on it anymore
+    Closed,
+}
+
+/// State of one end of a channel tracked by the mock relayer
+#[derive(Debug, Clone)]
+pub struct MockChannelState {
+    /// Ordering of the channel
+    pub order: IbcOrder,
+    /// Current status of the channel
+    pub status: MockChannelStatus,
+    /// The other end of the channel
+    pub counterparty: ChannelEnd,
+    /// Next sequence that is expected to be relayed from this end of the channel
+    /// This is only enforced for [`IbcOrder::Ordered`] channels
+    pub next_sequence_send: u64,
+}
+
+/// Channels registered within the mock relayer
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MockChannels {
+    channels: HashMap<ChannelEnd, MockChannelState>,
+    /// Final status of the packets relayed from a channel end, by sequence
+    packets: HashMap<(ChannelEnd, u64), IbcPacketStatus>,
+}
+
+impl MockChannels {
+    /// Registers both ends of a newly created channel
+    pub fn register(&mut self, src: ChannelEnd, dst: ChannelEnd, order: IbcOrder) {
+        self.channels.insert(
+            src.clone(),
+            MockChannelState {
+                order: order.clone(),
+                status: MockChannelStatus::Open,
+                counterparty: dst.clone(),
+                next_sequence_send: 1,
+            },
+        );
+        self.channels.insert(
+            dst,
+            MockChannelState {
+                order,
+                status: MockChannelStatus::Open,
+                counterparty: src,
+                next_sequence_send: 1,
+            },
+        );
+    }
+
+    /// Returns the state of a channel end if it was registered
+    pub fn get(&self, end: &ChannelEnd) -> Option<&MockChannelState> {
+        self.channels.get(end)
+    }
+
+    /// Returns the other end of an open channel
+    /// Errors if the channel is unknown or closed
+    pub fn open_counterparty(&self, end: &ChannelEnd) -> Result<ChannelEnd, InterchainMockError> {
+        let state = self
+            .channels
+            .get(end)
+            .ok_or_else(|| InterchainMockError::ChannelNotFound(format_end(end)))?;
+        if state.status == MockChannelStatus::Closed {
+            return Err(InterchainMockError::ChannelClosed(format_end(end)));
+        }
+        Ok(state.counterparty.clone())
+    }
+
+    /// Closes both ends of a channel
+    /// Errors if the channel is unknown or already closed
+    pub fn close(&mut self, end: &ChannelEnd) -> Result<(), InterchainMockError> {
+        let counterparty = self.open_counterparty(end)?;
+        if let Some(state) = self.channels.get_mut(end) {
+            state.status = MockChannelStatus::Closed;
+        }
+        if let Some(counterparty_state) = self.channels.get_mut(&counterparty) {
+            counterparty_state.status = MockChannelStatus::Closed;
+        }
+        Ok(())
+    }
+
+    /// Verifies a packet can be relayed from `end` with the given sequence
+    /// Packets can't be relayed on closed channels and ordered channels must relay packets in sequence
+    /// Channels that were not created through the mock relayer are not checked
+    pub fn check_relay(&self, end: &ChannelEnd, sequence: u64) -> Result<(), InterchainMockError> {
+        let Some(state) = self.channels.get(end) else {
+            return Ok(());
+        };
+        if state.status == MockChannelStatus::Closed {
+            return Err(InterchainMockError::ChannelClosed(format_end(end)));
+        }
+        if state.order == IbcOrder::Ordered && state.next_sequence_send != sequence {
+            return Err(InterchainMockError::UnexpectedSequence {
+                channel: format_end(end),
+                expected: state.next_sequence_send,
+                received: sequence,
+            });
+        }
+        Ok(())
+    }
+
+    /// Registers the outcome of a packet relayed from `end`
+    /// A timeout on an ordered channel closes the channel
+    pub fn packet_relayed(&mut self, end: &ChannelEnd, sequence: u64, timeout: bool) {
+        let status = if timeout {
+            IbcPacketStatus::TimedOut
+        } else {
+            IbcPacketStatus::Acknowledged
+        };
+        self.packets.insert((end.clone(), sequence), status);
+
+        let Some(state) = self.channels.get_mut(end) else {
+            return;
+        };
+        state.next_sequence_send += 1;
+        if timeout && state.order == IbcOrder::Ordered {
+            log::info!(
+                "Packet timed out on ordered channel {}, closing the channel",
+                format_end(end)
+            );
+            let end = end.clone();
+            // The channel is known to be open here, closing can't fail
+            let _ = self.close(&end);
+        }
+    }
+}
+
+impl MockChannels {
+    /// Status of a packet sent from `end`
+    /// The mock relayer only knows about the packets it relayed, other packets are reported as [`IbcPacketStatus::NotFound`]
+    pub fn packet_status(&self, end: &ChannelEnd, sequence: u64) -> IbcPacketStatus {
+        self.packets
+            .get(&(end.clone(), sequence))
+            .copied()
+            .unwrap_or(IbcPacketStatus::NotFound)
+    }
+}
+
+fn format_end((chain_id, port, channel): &ChannelEnd) -> String {
+    format!("{}:{}/{}", chain_id, port, channel)
+}
@@ -24,6 +24,19 @@ pub enum InterchainMockError {
 
     #[error("mock for chain {0} not found")]
     MockNotFound(String),
+
+    #[error("channel {0} is not registered in the mock relayer")]
+    ChannelNotFound(String),
+
+    #[error("channel {0} is closed")]
+    ChannelClosed(String),
+
+    #[error("unexpected packet sequence on ordered channel {channel}, expected {expected}, received {received}")]
+    UnexpectedSequence {
+        channel: String,
+        expected: u64,
+        received: u64,
+    },
 }
 
 impl From<InterchainMockError> for InterchainError {
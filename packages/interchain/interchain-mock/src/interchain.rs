@@ -7,8 +7,8 @@ use cw_orch_interchain_core::{
     env::{ChainId, ChannelCreation},
     types::{
         ChannelCreationTransactionsResult, FullIbcPacketAnalysis, IbcPacketAnalysis, IbcPacketInfo,
-        IbcPacketOutcome, IbcTxAnalysis, InternalChannelCreationResult, SimpleIbcPacketAnalysis,
-        TxId,
+        IbcPacketOutcome, IbcPacketStatus, IbcTxAnalysis, InternalChannelCreationResult,
+        SimpleIbcPacketAnalysis, TxId,
     },
     InterchainEnv,
 };
@@ -27,11 +27,17 @@ use ibc_relayer_types::core::{
     ics24_host::identifier::{ChannelId, PortId},
 };
 use std::{
+    cell::RefCell,
     collections::{HashMap, HashSet},
+    rc::Rc,
     str::FromStr,
 };
 
-use crate::InterchainMockError;
+use crate::{
+    channel::{ChannelEnd, MockChannelState, MockChannels},
+    relayer_costs::{RelayedPacketOutcome, RelayerCostSchedule, RelayerCostSummary, RelayerCosts},
+    InterchainMockError,
+};
 
 pub type MockBase<A> = cw_orch_mock::MockBase<A, MockState>;
 
@@ -41,6 +47,10 @@ pub type MockBase<A> = cw_orch_mock::MockBase<A, MockState>;
 pub struct MockInterchainEnvBase<A: Api> {
     /// Mock chains registered within the structure
     pub mocks: HashMap<String, MockBase<A>>,
+    /// Channels created through this environment, tracked by the mock relayer
+    channels: Rc<RefCell<MockChannels>>,
+    /// Relayer costs recorded when the simulation is enabled
+    relayer_costs: Rc<RefCell<Option<RelayerCosts>>>,
 }
 impl<A: Api> MockInterchainEnvBase<A> {
     /// Create an interchain structure from mocks
@@ -53,6 +63,8 @@ impl<A: Api> MockInterchainEnvBase<A> {
                     (chain_id, d.clone())
                 })
                 .collect(),
+            channels: Default::default(),
+            relayer_costs: Default::default(),
         }
     }
 
@@ -64,6 +76,89 @@ impl<A: Api> MockInterchainEnvBase<A> {
                 .map(|m| (m.block_info().unwrap().chain_id, m.clone())),
         );
     }
+
+    /// Closes a channel that was created through this environment.
+    /// The channel close handshake is relayed: `ibc_channel_close` is called with `CloseInit` on the contract of `chain_id`
+    /// and with `CloseConfirm` on the counterparty contract.
+    /// Both ends of the channel are closed and no packet can be relayed on it afterwards.
+    /// Errors if the channel is unknown to the mock relayer or already closed
+    pub fn close_channel(
+        &self,
+        chain_id: ChainId,
+        port: &PortId,
+        channel: &ChannelId,
+    ) -> Result<(), InterchainMockError> {
+        let end = channel_end(chain_id, port, channel);
+        let (counterparty_chain_id, _, _) = self.channels.borrow().open_counterparty(&end)?;
+        let src_mock = self.mock(chain_id)?;
+        let dst_mock = self.mock(&counterparty_chain_id)?;
+
+        relayer::close_channel(
+            &mut src_mock.app.borrow_mut(),
+            &mut dst_mock.app.borrow_mut(),
+            port.to_string(),
+            channel.to_string(),
+        )?;
+        self.channels.borrow_mut().close(&end)?;
+        log::info!(
+            "Successfully closed channel {}:{} on {}",
+            port,
+            channel,
+            chain_id
+        );
+        Ok(())
+    }
+
+    fn mock(&self, chain_id: ChainId) -> Result<&MockBase<A>, InterchainMockError> {
+        self.mocks
+            .get(chain_id)
+            .ok_or(InterchainMockError::MockNotFound(chain_id.to_string()))
+    }
+
+    /// Returns the state of a channel end as tracked by the mock relayer
+    /// Returns `None` if the channel was not created through this environment
+    pub fn channel_state(
+        &self,
+        chain_id: ChainId,
+        port: &PortId,
+        channel: &ChannelId,
+    ) -> Option<MockChannelState> {
+        self.channels
+            .borrow()
+            .get(&channel_end(chain_id, port, channel))
+            .cloned()
+    }
+
+    /// Starts accounting for the fees and gas a relayer would pay to relay the packets followed by this environment.
+    /// Costs recorded previously are discarded
+    pub fn simulate_relayer_costs(&self, schedule: RelayerCostSchedule) {
+        *self.relayer_costs.borrow_mut() = Some(RelayerCosts::new(schedule));
+    }
+
+    /// Stops the relayer costs simulation and returns the costs recorded until now
+    pub fn stop_relayer_costs_simulation(&self) -> Option<RelayerCostSummary> {
+        self.relayer_costs
+            .borrow_mut()
+            .take()
+            .map(|costs| RelayerCostSummary {
+                packets: costs.packets,
+            })
+    }
+
+    /// Summary of the relayer costs recorded since the simulation was enabled
+    /// Returns `None` if the simulation is not enabled
+    pub fn relayer_cost_summary(&self) -> Option<RelayerCostSummary> {
+        self.relayer_costs
+            .borrow()
+            .as_ref()
+            .map(|costs| RelayerCostSummary {
+                packets: costs.packets.clone(),
+            })
+    }
+}
+
+fn channel_end(chain_id: ChainId, port: &PortId, channel: &ChannelId) -> ChannelEnd {
+    (chain_id.to_string(), port.to_string(), channel.to_string())
 }
 type Sender<'a> = &'a str;
 type Prefix = &'static str;
@@ -87,6 +182,8 @@ impl MockInterchainEnvBase<MockApi> {
                     (chain_id.to_string(), mock)
                 })
                 .collect(),
+            channels: Default::default(),
+            relayer_costs: Default::default(),
         }
     }
 }
@@ -104,6 +201,8 @@ impl MockInterchainEnvBase<MockApiBech32> {
                     (chain_id.to_string(), mock)
                 })
                 .collect(),
+            channels: Default::default(),
+            relayer_costs: Default::default(),
         }
     }
 }
@@ -160,6 +259,7 @@ impl<A: Api + Clone> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
             src_connection_id
         };
 
+        let order = order.unwrap_or(IbcOrder::Unordered);
         let channel_creation = relayer::create_channel(
             &mut src_mock.app.borrow_mut(),
             &mut dst_mock.app.borrow_mut(),
@@ -167,9 +267,23 @@ impl<A: Api + Clone> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
             src_port.to_string(),
             dst_port.to_string(),
             version.to_string(),
-            order.unwrap_or(IbcOrder::Unordered),
+            order.clone(),
         )?;
 
+        self.channels.borrow_mut().register(
+            (
+                src_chain.to_string(),
+                src_port.to_string(),
+                channel_creation.src_channel.clone(),
+            ),
+            (
+                dst_chain.to_string(),
+                dst_port.to_string(),
+                channel_creation.dst_channel.clone(),
+            ),
+            order,
+        );
+
         log::info!("Successfully created a channel between {} and {} on '{}:{}' and channels {}:'{}' and {}:'{}'",
             src_port,
             dst_port,
@@ -288,6 +402,8 @@ impl<A: Api + Clone> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
 
     // This function follow the execution of an IBC packet across the chain
     /// In mock, it also relays the packet
+    /// Packets can't be relayed on closed channels and packets sent on ordered channels must be relayed in sequence.
+    /// A timeout on an ordered channel closes the channel
     fn follow_packet(
         &self,
         src_chain: ChainId,
@@ -299,6 +415,9 @@ impl<A: Api + Clone> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
         let src_mock = self.chain(src_chain)?;
         let dst_mock = self.chain(dst_chain)?;
 
+        let end = channel_end(src_chain, &src_port, &src_channel);
+        self.channels.borrow().check_relay(&end, sequence.into())?;
+
         // We get the packet data from the chain directly
         let relay_result = relayer::relay_packet(
             &mut src_mock.app.borrow_mut(),
@@ -343,6 +462,20 @@ impl<A: Api + Clone> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
             }
         };
 
+        self.channels.borrow_mut().packet_relayed(
+            &end,
+            sequence.into(),
+            matches!(outcome, IbcPacketOutcome::Timeout { .. }),
+        );
+
+        if let Some(costs) = self.relayer_costs.borrow_mut().as_mut() {
+            let relayed = match &outcome {
+                IbcPacketOutcome::Timeout { .. } => RelayedPacketOutcome::Timeout,
+                IbcPacketOutcome::Success { .. } => RelayedPacketOutcome::Acknowledgement,
+            };
+            costs.record(end.clone(), dst_chain.to_string(), sequence.into(), relayed);
+        }
+
         let analysis_result = IbcPacketAnalysis {
             send_tx: None, // This is not available in this context unfortunately
             outcome,
@@ -350,6 +483,24 @@ impl<A: Api + Clone> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
 
         Ok(analysis_result)
     }
+
+    /// In mock, packets are relayed while they are followed, so packets are either [`IbcPacketStatus::Acknowledged`] or [`IbcPacketStatus::TimedOut`] once followed.
+    /// Packets that were not followed yet are reported as [`IbcPacketStatus::NotFound`]
+    fn packet_status(
+        &self,
+        src_chain: ChainId,
+        src_port: PortId,
+        src_channel: ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<IbcPacketStatus, Self::Error> {
+        // Both chains need to be registered
+        self.chain(src_chain)?;
+        self.chain(dst_chain)?;
+
+        let end = channel_end(src_chain, &src_port, &src_channel);
+        Ok(self.channels.borrow().packet_status(&end, sequence.into()))
+    }
 }
 
 fn get_events(tx: &AppResponse, event: &str) -> Vec<Event> {
@@ -1,11 +1,18 @@
 //! Implementation of the interchain traits for the [cw_orch::prelude::Mock] environment
 
+mod channel;
 mod error;
 mod interchain;
+mod relayer_costs;
 
+pub use channel::{ChannelEnd, MockChannelState, MockChannelStatus};
 use cosmwasm_std::testing::MockApi;
 use cw_orch_mock::cw_multi_test::MockApiBech32;
 pub use error::InterchainMockError;
+pub use relayer_costs::{
+    ChannelRelayerCosts, RelayedPacketCost, RelayedPacketOutcome, RelayerCostSchedule,
+    RelayerCostSummary,
+};
 
 pub type MockInterchainEnv = interchain::MockInterchainEnvBase<MockApi>;
 pub type MockBech32InterchainEnv = interchain::MockInterchainEnvBase<MockApiBech32>;
@@ -0,0 +1,209 @@
+//! Simulation of the costs paid by relayers in the mock interchain environment.
+//! The mock relayer relays packets for free. When enabled, this module accounts for the fees and gas
+//! a relayer would pay on a live network (or on Starship) to relay every packet followed by the environment.
+//! Costs are only accounted for, no balance is deducted from any account.
+
+use cosmwasm_std::{Coin, Uint128};
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::ChannelEnd;
+
+/// Costs a relayer pays to relay a single packet.
+/// Default gas values approximate the ones observed with hermes on cosmos-sdk chains
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayerCostSchedule {
+    /// Fees paid for every relayed packet (e.g. ICS-29 relayer incentives)
+    pub packet_fee: Vec<Coin>,
+    /// Gas of the `MsgRecvPacket` transaction on the destination chain
+    pub recv_gas: u64,
+    /// Gas of the `MsgAcknowledgement` transaction on the source chain
+    pub ack_gas: u64,
+    /// Gas of the `MsgTimeout` transaction on the source chain
+    pub timeout_gas: u64,
+}
+
+impl Default for RelayerCostSchedule {
+    fn default() -> Self {
+        Self {
+            packet_fee: vec![],
+            recv_gas: 150_000,
+            ack_gas: 100_000,
+            timeout_gas: 100_000,
+        }
+    }
+}
+
+/// Way a relayed packet was finalized on its source chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayedPacketOutcome {
+    /// The packet was received and acknowledged
+    Acknowledgement,
+    /// The packet timed out
+    Timeout,
+}
+
+/// Costs of a single relayed packet
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayedPacketCost {
+    /// Channel end the packet was sent from
+    pub src: ChannelEnd,
+    /// Chain the packet was sent to
+    pub dst_chain: String,
+    /// Sequence of the packet
+    pub sequence: u64,
+    /// How the packet was finalized
+    pub outcome: RelayedPacketOutcome,
+    /// Gas paid on the destination chain to receive the packet, 0 for timeouts
+    pub recv_gas: u64,
+    /// Gas paid on the source chain to acknowledge or time out the packet
+    pub finalize_gas: u64,
+    /// Fees paid for the packet
+    pub fee: Vec<Coin>,
+}
+
+/// Relayer costs recorded by the environment
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RelayerCosts {
+    pub schedule: RelayerCostSchedule,
+    pub packets: Vec<RelayedPacketCost>,
+}
+
+impl RelayerCosts {
+    pub fn new(schedule: RelayerCostSchedule) -> Self {
+        Self {
+            schedule,
+            packets: vec![],
+        }
+    }
+
+    /// Records the costs of a packet, using the gas values of the schedule
+    pub fn record(
+        &mut self,
+        src: ChannelEnd,
+        dst_chain: String,
+        sequence: u64,
+        outcome: RelayedPacketOutcome,
+    ) {
+        let (recv_gas, finalize_gas) = match outcome {
+            RelayedPacketOutcome::Acknowledgement => {
+                (self.schedule.recv_gas, self.schedule.ack_gas)
+            }
+            RelayedPacketOutcome::Timeout => (0, self.schedule.timeout_gas),
+        };
+        self.packets.push(RelayedPacketCost {
+            src,
+            dst_chain,
+            sequence,
+            outcome,
+            recv_gas,
+            finalize_gas,
+            fee: self.schedule.packet_fee.clone(),
+        });
+    }
+}
+
+/// Totals of the relayer costs on a single channel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChannelRelayerCosts {
+    /// Number of acknowledged packets
+    pub acknowledged: u64,
+    /// Number of timed out packets
+    pub timed_out: u64,
+    /// Gas paid on the destination chain
+    pub recv_gas: u64,
+    /// Gas paid on the source chain
+    pub finalize_gas: u64,
+    /// Fees paid, per denom
+    pub fees: BTreeMap<String, Uint128>,
+}
+
+/// Summary of the relayer costs of all packets relayed by a mock interchain environment
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelayerCostSummary {
+    /// Costs of every relayed packet, in relaying order
+    pub packets: Vec<RelayedPacketCost>,
+}
+
+impl RelayerCostSummary {
+    /// Totals per source channel end
+    pub fn per_channel(&self) -> BTreeMap<ChannelEnd, ChannelRelayerCosts> {
+        let mut channels: BTreeMap<ChannelEnd, ChannelRelayerCosts> = BTreeMap::new();
+        for packet in &self.packets {
+            let totals = channels.entry(packet.src.clone()).or_default();
+            match packet.outcome {
+                RelayedPacketOutcome::Acknowledgement => totals.acknowledged += 1,
+                RelayedPacketOutcome::Timeout => totals.timed_out += 1,
+            }
+            totals.recv_gas += packet.recv_gas;
+            totals.finalize_gas += packet.finalize_gas;
+            add_fees(&mut totals.fees, &packet.fee);
+        }
+        channels
+    }
+
+    /// Gas paid by the relayer on each chain
+    pub fn gas_per_chain(&self) -> BTreeMap<String, u64> {
+        let mut chains: BTreeMap<String, u64> = BTreeMap::new();
+        for packet in &self.packets {
+            *chains.entry(packet.src.0.clone()).or_default() += packet.finalize_gas;
+            if packet.recv_gas > 0 {
+                *chains.entry(packet.dst_chain.clone()).or_default() += packet.recv_gas;
+            }
+        }
+        chains
+    }
+
+    /// Total gas paid by the relayer across all chains
+    pub fn total_gas(&self) -> u64 {
+        self.packets
+            .iter()
+            .map(|p| p.recv_gas + p.finalize_gas)
+            .sum()
+    }
+
+    /// Total fees paid for all packets, per denom
+    pub fn total_fees(&self) -> BTreeMap<String, Uint128> {
+        let mut fees = BTreeMap::new();
+        for packet in &self.packets {
+            add_fees(&mut fees, &packet.fee);
+        }
+        fees
+    }
+}
+
+fn add_fees(totals: &mut BTreeMap<String, Uint128>, fee: &[Coin]) {
+    for coin in fee {
+        *totals.entry(coin.denom.clone()).or_default() += coin.amount;
+    }
+}
+
+fn format_fees(fees: &BTreeMap<String, Uint128>) -> String {
+    if fees.is_empty() {
+        return "none".to_string();
+    }
+    fees.iter()
+        .map(|(denom, amount)| format!("{amount}{denom}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl Display for RelayerCostSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Relayer costs ({} packets):", self.packets.len())?;
+        for ((chain_id, port, channel), totals) in self.per_channel() {
+            writeln!(
+                f,
+                "  {chain_id} {port}:{channel}: {} acknowledged, {} timed out, recv gas {}, ack/timeout gas {}, fees {}",
+                totals.acknowledged,
+                totals.timed_out,
+                totals.recv_gas,
+                totals.finalize_gas,
+                format_fees(&totals.fees)
+            )?;
+        }
+        for (chain_id, gas) in self.gas_per_chain() {
+            writeln!(f, "  gas paid on {chain_id}: {gas}")?;
+        }
+        writeln!(f, "  total fees: {}", format_fees(&self.total_fees()))
+    }
+}
@@ -0,0 +1,194 @@
+//! ICS-20 escrow accounts and channel balance accounting.
+//!
+//! Tokens sent over an ICS-20 channel from their origin chain are locked in an escrow account derived from the channel end,
+//! and the same amount of vouchers is minted on the counterparty. As long as the channel is the only route of the token,
+//! the escrowed amount and the voucher supply must stay equal, which is checked by [`EscrowBalances::assert_conserved`].
+
+use std::str::FromStr;
+
+use cosmrs::AccountId;
+use cosmwasm_std::Addr;
+use cw_orch_core::{
+    environment::{BankQuerier, DefaultQueriers, QueryHandler, TxHandler},
+    CwEnvError,
+};
+use cw_orch_interchain_core::{
+    channel::InterchainChannel, IbcQueryHandler, InterchainEnv, InterchainError,
+};
+use tonic::transport::Channel;
+
+use crate::ics20_assertions::{balance, ibc_denom};
+
+pub use cw_orch_daemon::queriers::ibc_escrow_address;
+
+/// Bech32 prefix of a chain, taken from the address of its sender
+pub fn chain_prefix<Chain: TxHandler>(chain: &Chain) -> Result<String, InterchainError> {
+    let sender = chain.sender().to_string();
+    let account =
+        AccountId::from_str(&sender).map_err(|e| InterchainError::GenericError(e.to_string()))?;
+    Ok(account.prefix().to_string())
+}
+
+/// Escrowed amount of a token on its origin chain and supply of its vouchers on the counterparty chain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowBalances {
+    /// Chain the token originates from
+    pub chain_id: String,
+    /// Denom of the token on the origin chain
+    pub denom: String,
+    /// Denom of the vouchers on the counterparty chain
+    pub ibc_denom: String,
+    /// Escrow account of the channel end on the origin chain
+    pub escrow_address: Addr,
+    /// Amount of `denom` held by the escrow account
+    pub escrowed: u128,
+    /// Total supply of `ibc_denom` on the counterparty chain
+    pub vouchers: u128,
+}
+
+impl EscrowBalances {
+    /// Whether the escrowed amount matches the voucher supply
+    pub fn is_conserved(&self) -> bool {
+        self.escrowed == self.vouchers
+    }
+
+    /// Checks that the escrowed amount matches the voucher supply
+    pub fn assert_conserved(&self) -> Result<(), InterchainError> {
+        if !self.is_conserved() {
+            return Err(InterchainError::EscrowImbalance {
+                chain_id: self.chain_id.clone(),
+                denom: self.denom.clone(),
+                ibc_denom: self.ibc_denom.clone(),
+                escrowed: self.escrowed,
+                vouchers: self.vouchers,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that `amount` was escrowed and minted on the counterparty since the `before` snapshot.
+    /// A negative `amount` means tokens were sent back to the origin chain
+    pub fn assert_moved_since(
+        &self,
+        before: &EscrowBalances,
+        amount: i128,
+    ) -> Result<(), InterchainError> {
+        let escrowed_delta = self.escrowed as i128 - before.escrowed as i128;
+        if escrowed_delta != amount {
+            return Err(InterchainError::UnexpectedBalanceDelta {
+                account: "escrow".to_string(),
+                denom: self.denom.clone(),
+                expected: amount,
+                found: escrowed_delta,
+            });
+        }
+        let vouchers_delta = self.vouchers as i128 - before.vouchers as i128;
+        if vouchers_delta != amount {
+            return Err(InterchainError::UnexpectedBalanceDelta {
+                account: "voucher supply".to_string(),
+                denom: self.ibc_denom.clone(),
+                expected: amount,
+                found: vouchers_delta,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Escrowed amount of `denom` on `origin` for the channel `ibc_channel`,
+/// and supply of the corresponding vouchers on the other end of the channel
+pub fn escrow_balances<Chain: IbcQueryHandler, IBC: InterchainEnv<Chain>>(
+    origin: &Chain,
+    denom: &str,
+    interchain_env: &IBC,
+    ibc_channel: &InterchainChannel<Channel>,
+) -> Result<EscrowBalances, InterchainError> {
+    let chain_id = origin
+        .block_info()
+        .map_err(Into::<CwEnvError>::into)?
+        .chain_id;
+    let (origin_port, destination_port) = ibc_channel.get_ordered_ports_from(&chain_id)?;
+    let destination = interchain_env
+        .chain(&destination_port.chain_id)
+        .map_err(Into::<InterchainError>::into)?;
+
+    let origin_channel = origin_port
+        .channel
+        .ok_or_else(|| InterchainError::GenericError("Missing origin channel".into()))?;
+    let destination_channel = destination_port
+        .channel
+        .ok_or_else(|| InterchainError::GenericError("Missing destination channel".into()))?;
+
+    let escrow_address = ibc_escrow_address(
+        &chain_prefix(origin)?,
+        origin_port.port.as_str(),
+        origin_channel.as_str(),
+    )
+    .map_err(|e| InterchainError::GenericError(e.to_string()))?;
+    let ibc_denom = ibc_denom(
+        destination_port.port.as_str(),
+        destination_channel.as_str(),
+        denom,
+    );
+
+    let escrowed = balance(origin, escrow_address.as_str(), denom)?;
+    let vouchers = destination
+        .bank_querier()
+        .supply_of(&ibc_denom)
+        .map_err(Into::<CwEnvError>::into)?
+        .amount
+        .u128();
+
+    Ok(EscrowBalances {
+        chain_id,
+        denom: denom.to_string(),
+        ibc_denom,
+        escrow_address,
+        escrowed,
+        vouchers,
+    })
+}
+
+/// Checks that the escrowed amount of `denom` on `origin` matches the voucher supply on the other end of `ibc_channel`
+pub fn assert_escrow_conserved<Chain: IbcQueryHandler, IBC: InterchainEnv<Chain>>(
+    origin: &Chain,
+    denom: &str,
+    interchain_env: &IBC,
+    ibc_channel: &InterchainChannel<Channel>,
+) -> Result<EscrowBalances, InterchainError> {
+    let balances = escrow_balances(origin, denom, interchain_env, ibc_channel)?;
+    balances.assert_conserved()?;
+    Ok(balances)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escrow_accounting() {
+        let before = EscrowBalances {
+            chain_id: "juno-1".to_string(),
+            denom: "ujuno".to_string(),
+            ibc_denom: ibc_denom("transfer", "channel-0", "ujuno"),
+            escrow_address: Addr::unchecked("escrow"),
+            escrowed: 100,
+            vouchers: 100,
+        };
+        let after = EscrowBalances {
+            escrowed: 150,
+            vouchers: 150,
+            ..before.clone()
+        };
+        after.assert_conserved().unwrap();
+        after.assert_moved_since(&before, 50).unwrap();
+        assert!(after.assert_moved_since(&before, -50).is_err());
+
+        let imbalanced = EscrowBalances {
+            vouchers: 120,
+            ..after
+        };
+        assert!(!imbalanced.is_conserved());
+        assert!(imbalanced.assert_conserved().is_err());
+    }
+}
@@ -131,8 +131,9 @@ mod test {
     use cosmwasm_std::coin;
     use cw_orch_core::environment::TxHandler;
 
-    use crate::tokenfactory::{
-        create_denom, create_transfer_channel, get_denom, mint, transfer_tokens,
+    use crate::{
+        ics20_assertions::assert_transfer_tokens,
+        tokenfactory::{create_denom, create_transfer_channel, get_denom, mint, transfer_tokens},
     };
     use cw_orch_interchain_core::{
         channel::InterchainChannel, types::IbcPacketOutcome, IbcQueryHandler, InterchainEnv,
@@ -225,7 +226,8 @@ mod test {
         let chain2 = starship.daemon(STARGAZE)?;
 
         // This should pass ok, the timeout was set right
-        let success_outcome = transfer_tokens(
+        // The acknowledgment and the balances of the sender and receiver are asserted
+        let (success_outcome, _balances) = assert_transfer_tokens(
             chain1,
             chain2.sender().as_str(),
             &coin(TEST_AMOUNT / 2, denom.clone()),
@@ -238,9 +240,6 @@ mod test {
         // We assert we had a success_outcome
         assert_that!(success_outcome.packets).has_length(1);
 
-        // We want to assert the acknowledgment
-        success_outcome.into_result()?;
-
         // This should timeout
         let timeout_outcome = transfer_tokens(
             chain1,
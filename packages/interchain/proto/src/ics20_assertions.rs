@@ -0,0 +1,210 @@
+//! Balance assertions around ICS-20 transfers.
+//! Snapshots the balances of the sender and the receiver before and after a transfer and checks the expected deltas.
+
+use cosmwasm_std::Coin;
+use cw_orch_core::{
+    environment::{BankQuerier, DefaultQueriers, IndexResponse, QueryHandler},
+    CwEnvError,
+};
+use cw_orch_interchain_core::{
+    channel::InterchainChannel, types::IbcTxAnalysis, IbcQueryHandler, InterchainEnv,
+    InterchainError,
+};
+use cw_orch_traits::FullNode;
+use sha2::{Digest, Sha256};
+use tonic::transport::Channel;
+
+use crate::tokenfactory::transfer_tokens;
+
+/// Denom of a token received over ICS-20 on the destination chain.
+/// `port` and `channel` are the identifiers of the channel end on the destination chain.
+/// This is only valid for tokens that are not transferred back to their origin chain
+pub fn ibc_denom(port: &str, channel: &str, base_denom: &str) -> String {
+    let trace = format!("{port}/{channel}/{base_denom}");
+    let hash = Sha256::digest(trace.as_bytes());
+    let hex: String = hash.iter().map(|byte| format!("{byte:02X}")).collect();
+    format!("ibc/{hex}")
+}
+
+/// Balances of the sender and the receiver of an ICS-20 transfer, before and after the transfer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ics20TransferBalances {
+    /// Denom sent on the origin chain
+    pub denom: String,
+    /// Denom received on the destination chain
+    pub ibc_denom: String,
+    /// Transaction fee paid by the sender in `denom`
+    pub fee: u128,
+    /// Sender balance on the origin chain before the transfer
+    pub sender_before: u128,
+    /// Sender balance on the origin chain after the transfer
+    pub sender_after: u128,
+    /// Receiver balance on the destination chain before the transfer
+    pub receiver_before: u128,
+    /// Receiver balance on the destination chain after the transfer
+    pub receiver_after: u128,
+}
+
+impl Ics20TransferBalances {
+    /// Balance change of the sender, negative when tokens were sent
+    pub fn sender_delta(&self) -> i128 {
+        self.sender_after as i128 - self.sender_before as i128
+    }
+
+    /// Balance change of the receiver, positive when tokens were received
+    pub fn receiver_delta(&self) -> i128 {
+        self.receiver_after as i128 - self.receiver_before as i128
+    }
+
+    /// Checks that the sender sent `amount` (plus the transaction fee) and the receiver received `amount`
+    pub fn assert_transferred(&self, amount: u128) -> Result<(), InterchainError> {
+        let expected_sender_delta = -((amount + self.fee) as i128);
+        if self.sender_delta() != expected_sender_delta {
+            return Err(InterchainError::UnexpectedBalanceDelta {
+                account: "sender".to_string(),
+                denom: self.denom.clone(),
+                expected: expected_sender_delta,
+                found: self.sender_delta(),
+            });
+        }
+        if self.receiver_delta() != amount as i128 {
+            return Err(InterchainError::UnexpectedBalanceDelta {
+                account: "receiver".to_string(),
+                denom: self.ibc_denom.clone(),
+                expected: amount as i128,
+                found: self.receiver_delta(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Ibc token transfer, asserting the balance changes on both chains.
+/// The packet outcome is checked with [`IbcTxAnalysis::into_result`],
+/// then the sender must have sent `fund` and the receiver must have received `fund` in its ibc denom.
+#[allow(clippy::too_many_arguments)]
+pub fn assert_transfer_tokens<Chain: IbcQueryHandler + FullNode, IBC: InterchainEnv<Chain>>(
+    origin: &Chain,
+    receiver: &str,
+    fund: &Coin,
+    interchain_env: &IBC,
+    ibc_channel: &InterchainChannel<Channel>,
+    timeout: Option<u64>,
+    memo: Option<String>,
+) -> Result<(IbcTxAnalysis<Chain>, Ics20TransferBalances), InterchainError> {
+    let chain_id = origin
+        .block_info()
+        .map_err(Into::<CwEnvError>::into)?
+        .chain_id;
+    let (_, destination_port) = ibc_channel.get_ordered_ports_from(&chain_id)?;
+    let destination = interchain_env
+        .chain(&destination_port.chain_id)
+        .map_err(Into::<InterchainError>::into)?;
+
+    let destination_channel = destination_port
+        .channel
+        .ok_or_else(|| InterchainError::GenericError("Missing destination channel".into()))?;
+    let ibc_denom = ibc_denom(
+        destination_port.port.as_str(),
+        destination_channel.as_str(),
+        &fund.denom,
+    );
+
+    let sender = origin.sender().to_string();
+    let sender_before = balance(origin, &sender, &fund.denom)?;
+    let receiver_before = balance(&destination, receiver, &ibc_denom)?;
+
+    let tx_analysis = transfer_tokens(
+        origin,
+        receiver,
+        fund,
+        interchain_env,
+        ibc_channel,
+        timeout,
+        memo,
+    )?;
+    tx_analysis.into_result()?;
+
+    let balances = Ics20TransferBalances {
+        fee: paid_fee(tx_analysis.tx_id.response.fee(), &fund.denom),
+        sender_after: balance(origin, &sender, &fund.denom)?,
+        receiver_after: balance(&destination, receiver, &ibc_denom)?,
+        denom: fund.denom.clone(),
+        ibc_denom,
+        sender_before,
+        receiver_before,
+    };
+    balances.assert_transferred(fund.amount.u128())?;
+
+    Ok((tx_analysis, balances))
+}
+
+pub(crate) fn balance<Chain: DefaultQueriers>(
+    chain: &Chain,
+    address: &str,
+    denom: &str,
+) -> Result<u128, InterchainError> {
+    let coins = chain
+        .bank_querier()
+        .balance(address, Some(denom.to_string()))
+        .map_err(Into::<CwEnvError>::into)?;
+    Ok(coins
+        .into_iter()
+        .find(|coin| coin.denom == denom)
+        .map(|coin| coin.amount.u128())
+        .unwrap_or_default())
+}
+
+/// Amount of `denom` in the fee of a transaction (e.g. `1500ujuno,10uatom`)
+fn paid_fee(fee: Option<String>, denom: &str) -> u128 {
+    fee.unwrap_or_default()
+        .split(',')
+        .find_map(|coin| {
+            coin.trim()
+                .strip_suffix(denom)
+                .and_then(|amount| amount.parse().ok())
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ibc_denom_hash() {
+        // uosmo on the cosmoshub, received over channel-141
+        assert_eq!(
+            ibc_denom("transfer", "channel-141", "uosmo"),
+            "ibc/14F9BC3E44B8A9C1BE1FB08980FAB87034C9905EF17CF2F5008FC085218811CC"
+        );
+    }
+
+    #[test]
+    fn fee_in_denom() {
+        assert_eq!(paid_fee(Some("1500ujuno".to_string()), "ujuno"), 1500);
+        assert_eq!(
+            paid_fee(Some("10uatom,1500ujuno".to_string()), "ujuno"),
+            1500
+        );
+        assert_eq!(paid_fee(Some("1500ujuno".to_string()), "uatom"), 0);
+        assert_eq!(paid_fee(None, "ujuno"), 0);
+    }
+
+    #[test]
+    fn transferred_deltas() {
+        let balances = Ics20TransferBalances {
+            denom: "ujuno".to_string(),
+            ibc_denom: ibc_denom("transfer", "channel-0", "ujuno"),
+            fee: 10,
+            sender_before: 1_000,
+            sender_after: 890,
+            receiver_before: 0,
+            receiver_after: 100,
+        };
+        assert_eq!(balances.sender_delta(), -110);
+        assert_eq!(balances.receiver_delta(), 100);
+        balances.assert_transferred(100).unwrap();
+        assert!(balances.assert_transferred(50).is_err());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod escrow;
 pub mod ics20;
+pub mod ics20_assertions;
 pub mod tokenfactory;
@@ -53,6 +53,7 @@ pub fn create_denom<Chain: FullNode>(
 /// Gets the denom of a token created by a daemon object
 /// This actually creates the denom for a token created by an address (which is here taken to be the daemon sender address)
 /// This is mainly used for tests, but feel free to use that in production as well
+/// On a live chain, prefer querying the denoms that actually exist with `cw_orch_daemon::queriers::TokenFactory`
 pub fn get_denom<Chain: CwEnv>(daemon: &Chain, token_name: &str) -> String {
     let sender = daemon.sender().to_string();
     format!("factory/{}/{}", sender, token_name)
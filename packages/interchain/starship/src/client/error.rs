@@ -33,6 +33,22 @@ pub enum StarshipClientError {
 
     #[error("Missing test mnemonic for chain {0}")]
     MissingTestMnemonic(String),
+
+    #[error("No faucet registered for chain {0}")]
+    FaucetNotFound(String),
+
+    #[error("Chain {0} has no fee token in the registry")]
+    MissingFeeToken(String),
+
+    #[error("No chain with the bech32 prefix of address {0}")]
+    UnknownAddressPrefix(String),
+
+    #[error("The faucet didn't fund {address} with {amount} after {requests} requests")]
+    FaucetExhausted {
+        address: String,
+        amount: String,
+        requests: u32,
+    },
 }
 
 impl From<StarshipClientError> for CwEnvError {
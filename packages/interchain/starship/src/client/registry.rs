@@ -68,6 +68,18 @@ impl Registry {
         Ok(chains)
     }
 
+    /// Get the chain data of `chain_id`, as currently served by the registry.
+    pub async fn chain(&self, chain_id: &str) -> StarshipClientResult<ChainData> {
+        let url = self.get_url(&format!("chains/{}", chain_id));
+        let response = reqwest::get(&url).await?;
+        if !response.status().is_success() {
+            return Err(StarshipClientError::ChainConfigNotFound(
+                chain_id.to_string(),
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
     /// Get the first test account mnemonic from the chain registry.
     pub async fn test_mnemonic(&self, chain_id: &str) -> Result<String, StarshipClientError> {
         let url = self.get_url(&format!("chains/{}/keys", chain_id));
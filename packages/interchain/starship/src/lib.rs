@@ -3,14 +3,22 @@
 
 pub mod client;
 
-use crate::client::StarshipClient;
-use cw_orch_core::environment::{ChainInfoOwned, ChainState, NetworkInfoOwned};
+use crate::client::{faucet::Faucet, StarshipClient, StarshipClientError, StarshipClientResult};
+use cosmwasm_std::Coin;
+use cw_orch_core::environment::{
+    missing_funds, BankQuerier, ChainInfoOwned, ChainState, DefaultQueriers, FundsManager,
+    NetworkInfoOwned, TxHandler,
+};
 use cw_orch_core::CwEnvError;
-use cw_orch_daemon::{Daemon, DaemonBuilder};
+use cw_orch_daemon::{runtime::RuntimeHandle, Daemon, DaemonBuilder};
 use ibc_chain_registry::chain::ChainData;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use tokio::runtime::Handle;
 
+/// Maximum number of faucet requests sent by [`Starship`]'s [`FundsManager`] implementation for a single coin
+pub const MAX_FAUCET_REQUESTS: u32 = 10;
+
 #[derive(Clone)]
 /// Starship integration
 pub struct Starship {
@@ -19,6 +27,12 @@ pub struct Starship {
     starship_client: StarshipClient,
     /// Runtime handle for awaiting async functions
     pub rt_handle: Handle,
+    /// Faucets indexable by chain id
+    faucets: HashMap<String, Faucet>,
+    /// Whether senders are funded the first time their chain is accessed
+    auto_fund: bool,
+    /// Addresses already funded by [`Starship::fund_sender_once`], per chain id
+    funded: Arc<Mutex<HashSet<(String, String)>>>,
 }
 
 impl Starship {
@@ -38,7 +52,7 @@ impl Starship {
 
             let mut daemon_builder = DaemonBuilder::default();
             let mut daemon_builder = daemon_builder
-                .chain(chain_data_conversion(chain.clone()))
+                .chain(chain_data_conversion(chain.clone())?)
                 .mnemonic(mnemonic)
                 .handle(rt_handle);
 
@@ -53,14 +67,100 @@ impl Starship {
             daemons,
             starship_client,
             rt_handle: rt_handle.clone(),
+            faucets: HashMap::new(),
+            auto_fund: false,
+            funded: Arc::new(Mutex::new(HashSet::new())),
         })
     }
+
+    /// Registers the faucet of `chain_id`, served at `host:port`.
+    /// Starship port-forwards it to the `ports.faucet` port of the chain in its config, e.g. `("localhost", 8000)`
+    pub fn with_faucet(
+        mut self,
+        chain_id: &str,
+        host: impl ToString,
+        port: u16,
+    ) -> Result<Self, CwEnvError> {
+        self.daemon(chain_id)?;
+        let faucet = self.rt_handle.block_on(Faucet::new(host, port));
+        self.faucets.insert(chain_id.to_string(), faucet);
+        Ok(self)
+    }
+
+    /// Funds the sender of a chain from its faucet the first time the chain is accessed through the interchain environment.
+    /// Only chains with a registered faucet are funded, see [`Starship::with_faucet`]
+    pub fn auto_fund(mut self, enabled: bool) -> Self {
+        self.auto_fund = enabled;
+        self
+    }
+
+    /// Whether senders are funded when their chain is accessed, see [`Starship::auto_fund`]
+    pub fn is_auto_funding(&self) -> bool {
+        self.auto_fund
+    }
+
+    /// Requests gas tokens for `address` from the faucet of `chain_id`
+    pub fn fund(&self, chain_id: &str, address: impl ToString) -> StarshipClientResult<()> {
+        let faucet = self
+            .faucets
+            .get(chain_id)
+            .ok_or_else(|| StarshipClientError::FaucetNotFound(chain_id.to_string()))?;
+        let denom = self.chain_info(chain_id)?.gas_denom;
+        let address = address.to_string();
+        log::info!("Requesting {denom} for {address} from the faucet of {chain_id}");
+        self.rt_handle
+            .block_on(faucet.request_funds(&address, denom))
+    }
+
+    /// Requests gas tokens for the sender of `chain_id` from its faucet
+    pub fn fund_sender(&self, chain_id: &str) -> StarshipClientResult<()> {
+        let sender = self
+            .daemons
+            .get(chain_id)
+            .ok_or_else(|| StarshipClientError::DaemonNotFound(chain_id.to_string()))?
+            .sender();
+        self.fund(chain_id, sender)
+    }
+
+    /// Funds `daemon`'s sender if [`Starship::auto_fund`] is enabled, the chain has a faucet and the sender wasn't funded yet
+    pub fn fund_sender_once(&self, daemon: &Daemon) -> StarshipClientResult<()> {
+        let chain_id = daemon.state().chain_data.chain_id.to_string();
+        if !self.auto_fund || !self.faucets.contains_key(&chain_id) {
+            return Ok(());
+        }
+        let key = (chain_id.clone(), daemon.sender().to_string());
+        if self.funded.lock().unwrap().contains(&key) {
+            return Ok(());
+        }
+        self.fund(&chain_id, &key.1)?;
+        self.funded.lock().unwrap().insert(key);
+        Ok(())
+    }
+
+    /// Chain info of `chain_id`, pulled from the Starship registry
+    pub fn chain_info(&self, chain_id: &str) -> StarshipClientResult<ChainInfoOwned> {
+        let chain = self
+            .rt_handle
+            .block_on(async { self.starship_client.registry().await.chain(chain_id).await })?;
+        chain_data_conversion(chain)
+    }
     /// Get a chain daemon from the starship infrastructure
     pub fn daemon(&self, chain_id: &str) -> Result<&Daemon, CwEnvError> {
         self.daemons
             .get(chain_id)
             .ok_or(CwEnvError::StdErr(format!("Chain not found: {}", chain_id)))
     }
+    /// Get the daemon of the chain whose bech32 prefix is the one of `address`
+    pub fn daemon_of_address(&self, address: &str) -> StarshipClientResult<&Daemon> {
+        let prefix = address
+            .rsplit_once('1')
+            .map(|(prefix, _)| prefix)
+            .ok_or_else(|| StarshipClientError::UnknownAddressPrefix(address.to_string()))?;
+        self.daemons
+            .values()
+            .find(|daemon| daemon.state().chain_data.network_info.pub_address_prefix == prefix)
+            .ok_or_else(|| StarshipClientError::UnknownAddressPrefix(address.to_string()))
+    }
     /// Get the starship client
     pub fn client(&self) -> &StarshipClient {
         &self.starship_client
@@ -71,19 +171,91 @@ impl Starship {
     }
 }
 
-fn chain_data_conversion(chain: ChainData) -> ChainInfoOwned {
-    ChainInfoOwned {
+/// Balances are increased with faucet requests on the chain matching the prefix of the address.
+///
+/// A faucet sends a fixed amount per request, so the balance can end up higher than requested.
+/// Balances can't be decreased and minting is the same as adding a balance.
+impl FundsManager for Starship {
+    type Error = CwEnvError;
+
+    fn set_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        let address = address.into();
+        let daemon = self.daemon_of_address(&address)?;
+        let current = daemon
+            .bank_querier()
+            .balance(address.clone(), None)
+            .map_err(Into::<CwEnvError>::into)?;
+        let missing = missing_funds(&current, &amount)?;
+        FundsManager::add_balance(self, address, missing)
+    }
+
+    fn add_balance(&self, address: impl Into<String>, amount: Vec<Coin>) -> Result<(), CwEnvError> {
+        let address = address.into();
+        let daemon = self.daemon_of_address(&address)?;
+        let chain_id = daemon.state().chain_data.chain_id.to_string();
+        let faucet = self
+            .faucets
+            .get(&chain_id)
+            .ok_or_else(|| StarshipClientError::FaucetNotFound(chain_id.clone()))?;
+        let balance_of = |denom: &str| -> Result<u128, CwEnvError> {
+            let balance = daemon
+                .bank_querier()
+                .balance(address.clone(), Some(denom.to_string()))
+                .map_err(Into::<CwEnvError>::into)?;
+            Ok(balance.first().map(|c| c.amount.u128()).unwrap_or_default())
+        };
+
+        for coin in amount {
+            let target = balance_of(&coin.denom)? + coin.amount.u128();
+            let mut requests = 0;
+            while balance_of(&coin.denom)? < target {
+                if requests == MAX_FAUCET_REQUESTS {
+                    return Err(StarshipClientError::FaucetExhausted {
+                        address: address.clone(),
+                        amount: coin.to_string(),
+                        requests,
+                    }
+                    .into());
+                }
+                log::info!(
+                    "Requesting {} for {address} from the faucet of {chain_id}",
+                    coin.denom
+                );
+                self.rt_handle
+                    .block_on(faucet.request_funds(&address, &coin.denom))?;
+                requests += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RuntimeHandle for Starship {
+    fn runtime_handle(&self) -> Handle {
+        self.rt_handle.clone()
+    }
+}
+
+/// Converts the registry data of a chain, failing if the chain has no fee token
+fn chain_data_conversion(chain: ChainData) -> StarshipClientResult<ChainInfoOwned> {
+    let fee_token = chain
+        .fees
+        .fee_tokens
+        .first()
+        .ok_or_else(|| StarshipClientError::MissingFeeToken(chain.chain_id.to_string()))?;
+    Ok(ChainInfoOwned {
         chain_id: chain.chain_id.to_string(),
-        gas_denom: chain.fees.fee_tokens[0].denom.clone(),
-        gas_price: chain.fees.fee_tokens[0].average_gas_price,
+        gas_denom: fee_token.denom.clone(),
+        gas_price: fee_token.average_gas_price,
         grpc_urls: chain.apis.grpc.into_iter().map(|g| g.address).collect(),
         lcd_url: Some(chain.apis.rest.into_iter().map(|l| l.address).collect()),
         fcd_url: None,
+        rpc_url: chain.apis.rpc.into_iter().map(|r| r.address).next(),
         network_info: NetworkInfoOwned {
             chain_name: chain.chain_name,
             pub_address_prefix: chain.bech32_prefix,
             coin_type: chain.slip44,
         },
         kind: chain.network_type.into(),
-    }
+    })
 }
@@ -1,7 +1,7 @@
 #![recursion_limit = "128"]
 
-use syn::{Expr, Token};
 use syn::{__private::TokenStream2, parse_macro_input, Fields, GenericArgument, Item, Path};
+use syn::{parenthesized, Expr, LitStr, Token};
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
@@ -14,14 +14,34 @@ use syn::parse::{Parse, ParseStream};
 
 mod kw {
     syn::custom_keyword!(id);
+    syn::custom_keyword!(migrate_versions);
 }
 // This is used to parse the types into a list of types separated by Commas
 // and default contract id if provided by "id = $expr"
+// and versioned migrate messages if provided by "migrate_versions(Type = "req", ...)"
 struct InterfaceInput {
     expressions: Punctuated<Path, Comma>,
     _kw_id: Option<kw::id>,
     _eq_token: Option<Token![=]>,
     default_id: Option<Expr>,
+    migrate_versions: Option<Punctuated<MigrateVersion, Comma>>,
+}
+
+// A migrate message type and the on-chain version requirement it applies to: `Type = ">=1.0.0, <2.0.0"`
+struct MigrateVersion {
+    msg: Path,
+    _eq_token: Token![=],
+    version_req: LitStr,
+}
+
+impl Parse for MigrateVersion {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            msg: input.parse()?,
+            _eq_token: input.parse()?,
+            version_req: input.parse()?,
+        })
+    }
 }
 
 // Implement the `Parse` trait for your input struct
@@ -33,8 +53,8 @@ impl Parse for InterfaceInput {
             expressions.push(path);
             let _: Option<Token![,]> = input.parse().ok();
 
-            // If we found id = break
-            if input.peek(kw::id) {
+            // If we found id = or migrate_versions( break
+            if input.peek(kw::id) || input.peek(kw::migrate_versions) {
                 break;
             }
         }
@@ -42,21 +62,46 @@ impl Parse for InterfaceInput {
         let kw_id: Option<kw::id> = input.parse().map_err(|_| {
             syn::Error::new(
                 input.span(),
-                "The 5th argument of the macro should be of the format `id=my_contract_id`",
+                "The argument after the endpoint types should be of the format `id=my_contract_id`",
             )
         })?;
         let eq_token: Option<Token![=]> = input.parse().map_err(|_| {
             syn::Error::new(
                 input.span(),
-                "The 5th argument of the macro should be of the format `id=my_contract_id`",
+                "The argument after the endpoint types should be of the format `id=my_contract_id`",
             )
         })?;
-        let default_id: Option<Expr> = input.parse().ok();
+        let default_id: Option<Expr> = if eq_token.is_some() {
+            input.parse().ok()
+        } else {
+            None
+        };
+
+        // Parse the versioned migrate messages if there are any
+        let _: Option<Token![,]> = input.parse()?;
+        let migrate_versions = if input.peek(kw::migrate_versions) {
+            let _: kw::migrate_versions = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let migrate_versions = content
+                .parse_terminated(MigrateVersion::parse, Token![,])
+                .map_err(|_| {
+                    syn::Error::new(
+                        content.span(),
+                        "Versioned migrate messages should be of the format `migrate_versions(MigrateMsg = \">=1.0.0\", ...)`",
+                    )
+                })?;
+            let _: Option<Token![,]> = input.parse()?;
+            Some(migrate_versions)
+        } else {
+            None
+        };
         Ok(Self {
             expressions,
             _kw_id: kw_id,
             _eq_token: eq_token,
             default_id,
+            migrate_versions,
         })
     }
 }
@@ -116,6 +161,33 @@ impl <Chain> ::cw_orch::core::contract::interface_traits::ExecutableContract for
 // ... other entry point & upload traits
 ```
 
+## Sudo entry point
+
+Contracts with a `sudo` entry point can declare their sudo message as a 5th type.
+The contract can then be called with `CwOrchSudo::sudo` (or the `SudoFns` derive) on the environments implementing `SudoHandler`.
+
+```ignore
+#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg, SudoMsg)]
+pub struct MyContract;
+```
+
+## Versioned migrate messages
+
+Migrate messages that only apply to some on-chain (cw2) versions of the contract can be declared with `migrate_versions`.
+Each message type is associated with a semver requirement on the version the contract migrates from.
+The first matching message is selected by `CwOrchVersionedMigrate::versioned_migrate`.
+
+```ignore
+#[interface(
+    InstantiateMsg,
+    ExecuteMsg,
+    QueryMsg,
+    MigrateMsg,
+    migrate_versions(MigrateFromV1 = ">=1.0.0, <2.0.0", MigrateMsg = ">=2.0.0")
+)]
+pub struct MyContract;
+```
+
 ## Linking the interface to its source code
 
 The interface can be linked to its source code by implementing the `Uploadable` trait for the interface.
@@ -149,9 +221,10 @@ pub fn interface(attrs: TokenStream, input: TokenStream) -> TokenStream {
 
     let types_in_order = attributes.expressions;
     let default_id = attributes.default_id;
+    let migrate_versions = attributes.migrate_versions;
 
-    if types_in_order.len() != 4 {
-        panic!("Expected four endpoint types (InstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg). Use cosmwasm_std::Empty if not implemented.")
+    if types_in_order.len() != 4 && types_in_order.len() != 5 {
+        panic!("Expected four endpoint types (InstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg) and an optional SudoMsg. Use cosmwasm_std::Empty if not implemented.")
     }
 
     let Item::Struct(cw_orch_struct) = &mut item else {
@@ -165,6 +238,7 @@ pub fn interface(attrs: TokenStream, input: TokenStream) -> TokenStream {
     let exec = types_in_order[1].clone();
     let query = types_in_order[2].clone();
     let migrate = types_in_order[3].clone();
+    let sudo = types_in_order.get(4).cloned();
 
     // We create all generics for all types
     let all_generics: Punctuated<GenericArgument, Comma> = types_in_order
@@ -223,6 +297,38 @@ pub fn interface(attrs: TokenStream, input: TokenStream) -> TokenStream {
             }
         )
     };
+    let versioned_migrate = if let Some(migrate_versions) = migrate_versions {
+        let msg_versions: Vec<TokenStream2> = migrate_versions
+            .iter()
+            .map(|MigrateVersion { msg, version_req, .. }| {
+                quote!(
+                    ::cw_orch::core::contract::interface_traits::MigrateMsgVersion::of::<#msg>(#version_req)
+                )
+            })
+            .collect();
+        quote!(
+            #[cfg(not(target_arch = "wasm32"))]
+            impl<Chain, #all_generics> ::cw_orch::core::contract::interface_traits::VersionedMigratableContract for #name<Chain, #all_generics> #all_debug_serialize {
+                fn migrate_msg_versions() -> ::std::vec::Vec<::cw_orch::core::contract::interface_traits::MigrateMsgVersion> {
+                    ::std::vec![#(#msg_versions,)*]
+                }
+            }
+        )
+    } else {
+        quote!()
+    };
+
+    let sudo_contract = if let Some(sudo) = sudo {
+        quote!(
+            #[cfg(not(target_arch = "wasm32"))]
+            impl<Chain, #all_generics> ::cw_orch::core::contract::interface_traits::SudoableContract for #name<Chain, #all_generics> #all_debug_serialize {
+                type SudoMsg = #sudo;
+            }
+        )
+    } else {
+        quote!()
+    };
+
     let struct_def = quote!(
         #[cfg(not(target_arch = "wasm32"))]
         #[derive(
@@ -268,6 +374,10 @@ pub fn interface(attrs: TokenStream, input: TokenStream) -> TokenStream {
         impl<Chain, #all_generics> ::cw_orch::core::contract::interface_traits::MigratableContract for #name<Chain, #all_generics> #all_debug_serialize {
             type MigrateMsg = #migrate;
         }
+
+        #sudo_contract
+
+        #versioned_migrate
     );
     struct_def.into()
 }
@@ -1,14 +1,19 @@
 extern crate proc_macro;
 use crate::{
     execute_fns::payable,
-    helpers::{has_into, process_fn_name, process_sorting, LexiographicMatching, MsgType},
+    helpers::{
+        has_into, process_event_attr, process_fn_name, process_sorting, EventAttr,
+        LexiographicMatching, MsgType,
+    },
     query_fns::parse_query_type,
 };
 use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::{format_ident, quote};
-use syn::{parse_quote, visit_mut::VisitMut, Fields, Generics, Ident, ItemEnum, WhereClause};
+use syn::{
+    parse_quote, visit_mut::VisitMut, Fields, GenericParam, Generics, Ident, ItemEnum, WhereClause,
+};
 
 pub fn fns_derive(msg_type: MsgType, input: ItemEnum) -> TokenStream {
     let name = &input.ident;
@@ -31,22 +36,53 @@ pub fn fns_derive(msg_type: MsgType, input: ItemEnum) -> TokenStream {
                     + ::cw_orch::core::environment::ChainState
             ),
         ),
+        MsgType::Sudo => (
+            quote!(CwOrchSudo),
+            quote!(sudo),
+            quote!(SudoMsg),
+            quote!(CwOrchSudoMsgType),
+            quote!(::cw_orch::core::environment::SudoHandler),
+        ),
+    };
+
+    // Async counterparts, generated for execute and query messages only
+    let async_trait = match msg_type {
+        MsgType::Execute => Some((
+            quote!(AsyncCwOrchExecute),
+            quote!(async_execute),
+            quote!(::cw_orch::core::environment::AsyncTxHandler),
+        )),
+        MsgType::Query => Some((
+            quote!(AsyncCwOrchQuery),
+            quote!(async_query),
+            quote!(::cw_orch::core::environment::AsyncWasmQuerier),
+        )),
+        MsgType::Sudo => None,
     };
 
-    let variant_fns = input.variants.into_iter().map( |mut variant|{
+    let (variant_fns, async_variant_fns): (Vec<_>, Vec<_>) = input.variants.clone().into_iter().map( |mut variant|{
         let variant_name = variant.ident.clone();
 
         // We rename the variant if it has a fn_name attribute associated with it
         let mut variant_func_name =
                 format_ident!("{}", process_fn_name(&variant).to_case(Case::Snake));
         variant_func_name.set_span(variant_name.span());
+        let async_variant_func_name = format_ident!("{}_async", variant_func_name, span = variant_name.span());
 
 
-        let variant_doc: syn::Attribute = {
+        // The doc comments of the variant are passed through to the generated functions
+        let variant_doc = {
             let doc = format!("Automatically generated wrapper around {}::{} variant", name, variant_name);
-            parse_quote!(
-                #[doc=#doc]
-            )
+            let passed_docs: Vec<_> = variant.attrs.iter().filter(|attr| attr.path.is_ident("doc")).collect();
+            if passed_docs.is_empty() {
+                quote!(#[doc=#doc])
+            } else {
+                quote!(
+                    #(#passed_docs)*
+                    #[doc=""]
+                    #[doc=#doc]
+                )
+            }
         };
 
         // TODO
@@ -60,15 +96,57 @@ pub fn fns_derive(msg_type: MsgType, input: ItemEnum) -> TokenStream {
                     (quote!(),quote!(None))
                 }
             }
-            MsgType::Query => {
+            MsgType::Query | MsgType::Sudo => {
                 (quote!(), quote!())
             }
         };
 
 
-        let response = match msg_type{
-            MsgType::Execute => quote!(::cw_orch::core::environment::TxResponse<Chain>),
-            MsgType::Query => parse_query_type(&variant)
+        // Execute variants can return the value of an event attribute instead of the raw response
+        let event_attr = match msg_type {
+            MsgType::Execute => process_event_attr(&variant),
+            MsgType::Query | MsgType::Sudo => None,
+        };
+        let parse_response = match &event_attr {
+            Some(EventAttr { event, attribute, ty }) => {
+                let ty = ty.as_ref().map(|ty| quote!(#ty)).unwrap_or(quote!(String));
+                quote!(
+                    .and_then(|response| {
+                        let value = ::cw_orch::core::environment::IndexResponse::event_attr_value(&response, #event, #attribute)?;
+                        <#ty as ::cw_orch::core::environment::FromEventAttribute>::from_event_attribute(value)
+                    })
+                )
+            }
+            None => quote!(),
+        };
+
+        let (response, async_response) = match (&msg_type, &event_attr) {
+            (MsgType::Execute, Some(EventAttr { ty, .. })) => {
+                let ty = ty.as_ref().map(|ty| quote!(#ty)).unwrap_or(quote!(String));
+                (ty.clone(), ty)
+            }
+            (MsgType::Execute | MsgType::Sudo, _) => (
+                quote!(::cw_orch::core::environment::TxResponse<Chain>),
+                quote!(<Chain as ::cw_orch::core::environment::AsyncTxHandler>::Response),
+            ),
+            (MsgType::Query, _) => (parse_query_type(&variant), parse_query_type(&variant)),
+        };
+
+        // The async function awaits the async entry point with the same message
+        let async_fn = |params: proc_macro2::TokenStream, msg: proc_macro2::TokenStream| {
+            let Some((async_trait_name, async_func_name, _)) = &async_trait else {
+                return quote!();
+            };
+            quote!(
+                #variant_doc
+                #[allow(clippy::too_many_arguments)]
+                fn #async_variant_func_name(&self, #params #maybe_coins_attr) -> impl ::std::future::Future<Output = Result<#async_response, ::cw_orch::core::CwEnvError>> {
+                    let msg = #msg;
+                    async move {
+                        <Self as ::cw_orch::core::contract::interface_traits::#async_trait_name<Chain>>::#async_func_name(self, &msg.into(),#passed_coins).await #parse_response
+                    }
+                }
+            )
         };
 
         match &mut variant.fields {
@@ -113,26 +191,29 @@ pub fn fns_derive(msg_type: MsgType, input: ItemEnum) -> TokenStream {
                 });
 
 
-                quote!(
+                let params = quote!(#(#variant_params,)*);
+                let msg = quote!(#name::#variant_name (
+                    #(#variant_ident_content_names,)*
+                ));
+
+                (quote!(
                     #variant_doc
                     #[allow(clippy::too_many_arguments)]
-                    fn #variant_func_name(&self, #(#variant_params,)* #maybe_coins_attr) -> Result<#response, ::cw_orch::core::CwEnvError> {
-                        let msg = #name::#variant_name (
-                            #(#variant_ident_content_names,)*
-                        );
-                        <Self as ::cw_orch::core::contract::interface_traits::#trait_name<Chain>>::#func_name(self, &msg.into(),#passed_coins)
+                    fn #variant_func_name(&self, #params #maybe_coins_attr) -> Result<#response, ::cw_orch::core::CwEnvError> {
+                        let msg = #msg;
+                        <Self as ::cw_orch::core::contract::interface_traits::#trait_name<Chain>>::#func_name(self, &msg.into(),#passed_coins) #parse_response
                     }
-                )
+                ), async_fn(params, msg))
             },
             Fields::Unit => {
 
-                quote!(
+                (quote!(
                     #variant_doc
                     fn #variant_func_name(&self, #maybe_coins_attr) -> Result<#response, ::cw_orch::core::CwEnvError> {
                         let msg = #name::#variant_name;
-                        <Self as ::cw_orch::core::contract::interface_traits::#trait_name<Chain>>::#func_name(self, &msg.into(),#passed_coins)
+                        <Self as ::cw_orch::core::contract::interface_traits::#trait_name<Chain>>::#func_name(self, &msg.into(),#passed_coins) #parse_response
                     }
-                )
+                ), async_fn(quote!(), quote!(#name::#variant_name)))
             }
             Fields::Named(variant_fields) => {
                 let is_attributes_sorted = process_sorting(&input.attrs);
@@ -165,25 +246,91 @@ pub fn fns_derive(msg_type: MsgType, input: ItemEnum) -> TokenStream {
                         quote! (#field_name: #field_type )
                     }
                 });
-                quote!(
+                let params = quote!(#(#variant_attr,)*);
+                let msg = quote!(#name::#variant_name {
+                    #(#variant_idents,)*
+                });
+
+                (quote!(
                     #variant_doc
                     #[allow(clippy::too_many_arguments)]
-                    fn #variant_func_name(&self, #(#variant_attr,)* #maybe_coins_attr) -> Result<#response, ::cw_orch::core::CwEnvError> {
-                        let msg = #name::#variant_name {
-                            #(#variant_idents,)*
-                        };
-                        <Self as ::cw_orch::core::contract::interface_traits::#trait_name<Chain>>::#func_name(self, &msg.into(),#passed_coins)
+                    fn #variant_func_name(&self, #params #maybe_coins_attr) -> Result<#response, ::cw_orch::core::CwEnvError> {
+                        let msg = #msg;
+                        <Self as ::cw_orch::core::contract::interface_traits::#trait_name<Chain>>::#func_name(self, &msg.into(),#passed_coins) #parse_response
                     }
-                )
+                ), async_fn(params, msg))
             }
         }
+    }).unzip();
+
+    let bname = Ident::new(&format!("{name}Fns"), name.span());
+    let trait_condition = quote!(::cw_orch::core::contract::interface_traits::#trait_name<Chain, #trait_msg_type = #generic_msg_type>);
+    let derived_trait = fns_trait(
+        &input,
+        &bname,
+        &chain_trait,
+        &generic_msg_type,
+        &trait_condition,
+        &variant_fns,
+    );
+
+    let derived_async_trait = async_trait.map(|(async_trait_name, _, async_chain_trait)| {
+        let bname = Ident::new(&format!("Async{name}Fns"), name.span());
+        let trait_condition = quote!(::cw_orch::core::contract::interface_traits::#async_trait_name<Chain, #trait_msg_type = #generic_msg_type>);
+        fns_trait(
+            &input,
+            &bname,
+            &async_chain_trait,
+            &generic_msg_type,
+            &trait_condition,
+            &async_variant_fns,
+        )
     });
 
+    let expand = quote!(
+        #derived_trait
+
+        #derived_async_trait
+    );
+
+    expand.into()
+}
+
+/// Generates the trait `bname` containing the variant functions, along with its blanket implementation for all contracts satisfying `trait_condition`
+fn fns_trait(
+    input: &ItemEnum,
+    bname: &Ident,
+    chain_trait: &proc_macro2::TokenStream,
+    generic_msg_type: &proc_macro2::TokenStream,
+    trait_condition: &proc_macro2::TokenStream,
+    variant_fns: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+
     // Generics for the Trait
-    let mut cw_orch_generics: Generics = parse_quote!(<Chain: #chain_trait,  #generic_msg_type>);
+    // Lifetimes of the message need to be declared before the type parameters
+    let mut cw_orch_generics: Generics = Generics::default();
+    cw_orch_generics.params.extend(
+        input
+            .generics
+            .lifetimes()
+            .cloned()
+            .map(GenericParam::Lifetime),
+    );
     cw_orch_generics
         .params
-        .extend(input.generics.params.clone());
+        .push(parse_quote!(Chain: #chain_trait));
+    cw_orch_generics
+        .params
+        .push(parse_quote!(#generic_msg_type));
+    cw_orch_generics.params.extend(
+        input
+            .generics
+            .params
+            .iter()
+            .filter(|param| !matches!(param, GenericParam::Lifetime(_)))
+            .cloned(),
+    );
 
     // Where clause for the Trait
     let mut combined_trait_where_clause = {
@@ -200,9 +347,6 @@ pub fn fns_derive(msg_type: MsgType, input: ItemEnum) -> TokenStream {
         clause
     };
 
-    let bname = Ident::new(&format!("{name}Fns"), name.span());
-    let trait_condition = quote!(::cw_orch::core::contract::interface_traits::#trait_name<Chain, #trait_msg_type = #generic_msg_type>);
-
     let derived_trait = quote!(
         #[cfg(not(target_arch = "wasm32"))]
         /// Automatically derived trait that allows you to call the variants of the message directly without the need to construct the struct yourself.
@@ -237,12 +381,10 @@ pub fn fns_derive(msg_type: MsgType, input: ItemEnum) -> TokenStream {
         #combined_trait_where_clause {}
     );
 
-    let expand = quote!(
+    quote!(
         #derived_trait
 
         #[cfg(not(target_arch = "wasm32"))]
         #derived_trait_blanket_impl
-    );
-
-    expand.into()
+    )
 }
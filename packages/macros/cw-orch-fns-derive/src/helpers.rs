@@ -3,12 +3,13 @@ use quote::ToTokens;
 use std::cmp::Ordering;
 use syn::{
     punctuated::Punctuated, token::Comma, Attribute, Field, FieldsNamed, Lit, Meta, MetaList,
-    NestedMeta, Type,
+    NestedMeta, Path, Type,
 };
 
 pub enum MsgType {
     Execute,
     Query,
+    Sudo,
 }
 
 pub(crate) fn process_fn_name(v: &syn::Variant) -> String {
@@ -17,7 +18,10 @@ pub(crate) fn process_fn_name(v: &syn::Variant) -> String {
             if let Some(ident) = list.path.get_ident() {
                 if ident == "cw_orch" {
                     for meta in list.nested {
-                        if let NestedMeta::Meta(Meta::List(MetaList { nested, .. })) = &meta {
+                        if let NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) = &meta {
+                            if !path.is_ident("fn_name") {
+                                continue;
+                            }
                             if let Some(NestedMeta::Lit(Lit::Str(lit_str))) = nested.last() {
                                 return lit_str.value();
                             }
@@ -30,6 +34,53 @@ pub(crate) fn process_fn_name(v: &syn::Variant) -> String {
     v.ident.to_string()
 }
 
+/// Event attribute carrying the result of an execute variant : `#[cw_orch(event_attr("wasm", "pool_id", u64))]`
+pub(crate) struct EventAttr {
+    pub event: String,
+    pub attribute: String,
+    /// Type the attribute value is parsed into, `String` if not specified
+    pub ty: Option<Path>,
+}
+
+pub(crate) fn process_event_attr(v: &syn::Variant) -> Option<EventAttr> {
+    for attr in &v.attrs {
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            if !list.path.is_ident("cw_orch") {
+                continue;
+            }
+            for meta in list.nested {
+                if let NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) = meta {
+                    if !path.is_ident("event_attr") {
+                        continue;
+                    }
+                    let args: Vec<_> = nested.into_iter().collect();
+                    return match args.as_slice() {
+                        [NestedMeta::Lit(Lit::Str(event)), NestedMeta::Lit(Lit::Str(attribute))] => {
+                            Some(EventAttr {
+                                event: event.value(),
+                                attribute: attribute.value(),
+                                ty: None,
+                            })
+                        }
+                        [NestedMeta::Lit(Lit::Str(event)), NestedMeta::Lit(Lit::Str(attribute)), NestedMeta::Meta(Meta::Path(ty))] => {
+                            Some(EventAttr {
+                                event: event.value(),
+                                attribute: attribute.value(),
+                                ty: Some(ty.clone()),
+                            })
+                        }
+                        _ => panic!(
+                            "event_attr of {} expects an event type, an attribute key and an optional return type, e.g. #[cw_orch(event_attr(\"wasm\", \"pool_id\", u64))]",
+                            v.ident
+                        ),
+                    };
+                }
+            }
+        }
+    }
+    None
+}
+
 pub(crate) fn process_sorting(attrs: &[Attribute]) -> bool {
     !has_cw_orch_attribute(attrs, "disable_fields_sorting")
 }
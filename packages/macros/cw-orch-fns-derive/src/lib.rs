@@ -16,6 +16,7 @@ use syn::{parse_macro_input, ItemEnum};
 /// fn_name - Modify the generated function name (useful for query or execute variants for instance)
 /// disable_fields_sorting - By default the fields are sorted on named variants. Disabled this behavior
 /// into - The field can be indicated in the generated function with a type that implements `Into` the field type
+/// event_attr - The generated function returns the value of an event attribute of the response, e.g. `event_attr("wasm", "pool_id", u64)`
 #[proc_macro_derive(ExecuteFns, attributes(cw_orch))]
 pub fn cw_orch_execute(input: TokenStream) -> TokenStream {
     // We only parse and return the modified code if the flag is activated
@@ -33,3 +34,13 @@ pub fn cw_orch_query(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as ItemEnum);
     fns_derive::fns_derive(MsgType::Query, ast)
 }
+
+/// Available attributes are :
+/// fn_name - Modify the generated function name
+/// disable_fields_sorting - By default the fields are sorted on named variants. Disabled this behavior
+/// into - The field can be indicated in the generated function with a type that implements `Into` the field type
+#[proc_macro_derive(SudoFns, attributes(cw_orch))]
+pub fn cw_orch_sudo(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as ItemEnum);
+    fns_derive::fns_derive(MsgType::Sudo, ast)
+}